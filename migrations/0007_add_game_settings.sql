@@ -0,0 +1,3 @@
+-- Migration number: 0007 	 2025-08-24T10:12:41.508Z
+
+ALTER TABLE games ADD COLUMN max_players INTEGER NOT NULL DEFAULT 5;
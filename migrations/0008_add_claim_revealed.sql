@@ -0,0 +1,3 @@
+-- Migration number: 0008 	 2025-08-24T14:03:17.220Z
+
+ALTER TABLE claims ADD COLUMN revealed INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Migration number: 0009 	 2025-08-25T09:41:55.873Z
+
+-- SQLite doesn't allow non-constant defaults when adding columns
+ALTER TABLE claims ADD COLUMN round_number INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE claims ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT '1970-01-01 00:00:00';
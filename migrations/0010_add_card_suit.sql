@@ -0,0 +1,4 @@
+-- Migration number: 0010 	 2025-08-26T18:22:09.114Z
+
+-- NULL for jokers and cards created before suits existed
+ALTER TABLE cards ADD COLUMN suit INTEGER;
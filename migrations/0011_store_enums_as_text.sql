@@ -0,0 +1,59 @@
+-- Migration number: 0011 	 2025-08-27T20:05:33.612Z
+
+-- Enums are stored with their 'as_str()' value from now on.
+-- SQLite keeps text values in INTEGER columns, so only the stored values need to be converted.
+
+UPDATE games SET state = CASE state
+  WHEN 0 THEN 'In Progress'
+  WHEN 1 THEN 'Ended'
+  WHEN 2 THEN 'Waiting for Players'
+  WHEN 3 THEN 'Starting'
+  WHEN 4 THEN 'Paused'
+  WHEN 5 THEN 'Abandoned'
+  WHEN 6 THEN 'Round Ending'
+  ELSE state
+END;
+
+UPDATE games SET card_to_play = CASE card_to_play
+  WHEN 0 THEN 'King'
+  WHEN 1 THEN 'Queen'
+  WHEN 2 THEN 'Jack'
+  WHEN 3 THEN 'Ace'
+  WHEN 4 THEN 'Joker'
+  WHEN 5 THEN 'Two'
+  WHEN 6 THEN 'Three'
+  WHEN 7 THEN 'Four'
+  WHEN 8 THEN 'Five'
+  WHEN 9 THEN 'Six'
+  WHEN 10 THEN 'Seven'
+  WHEN 11 THEN 'Eight'
+  WHEN 12 THEN 'Nine'
+  WHEN 13 THEN 'Ten'
+  ELSE card_to_play
+END;
+
+UPDATE cards SET card_type = CASE card_type
+  WHEN 0 THEN 'King'
+  WHEN 1 THEN 'Queen'
+  WHEN 2 THEN 'Jack'
+  WHEN 3 THEN 'Ace'
+  WHEN 4 THEN 'Joker'
+  WHEN 5 THEN 'Two'
+  WHEN 6 THEN 'Three'
+  WHEN 7 THEN 'Four'
+  WHEN 8 THEN 'Five'
+  WHEN 9 THEN 'Six'
+  WHEN 10 THEN 'Seven'
+  WHEN 11 THEN 'Eight'
+  WHEN 12 THEN 'Nine'
+  WHEN 13 THEN 'Ten'
+  ELSE card_type
+END;
+
+UPDATE cards SET suit = CASE suit
+  WHEN 0 THEN 'Hearts'
+  WHEN 1 THEN 'Diamonds'
+  WHEN 2 THEN 'Clubs'
+  WHEN 3 THEN 'Spades'
+  ELSE suit
+END;
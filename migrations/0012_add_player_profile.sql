@@ -0,0 +1,5 @@
+-- Migration number: 0012 	 2025-08-28T16:47:12.930Z
+
+ALTER TABLE players ADD COLUMN avatar_url TEXT;
+ALTER TABLE players ADD COLUMN color TEXT;
+ALTER TABLE players ADD COLUMN emoji TEXT;
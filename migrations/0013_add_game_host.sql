@@ -0,0 +1,3 @@
+-- Migration number: 0013 	 2025-08-29T09:12:41.508Z
+
+ALTER TABLE games ADD COLUMN host_player_id TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,5 @@
+-- Migration number: 0014 	 2025-08-29T14:03:27.216Z
+
+ALTER TABLE games ADD COLUMN winner_player_id TEXT;
+ALTER TABLE games ADD COLUMN finished_at TEXT;
+ALTER TABLE games ADD COLUMN total_rounds INTEGER;
@@ -0,0 +1,6 @@
+-- Migration number: 0015 	 2025-08-30T10:21:54.117Z
+
+ALTER TABLE games ADD COLUMN visibility TEXT NOT NULL DEFAULT 'Public';
+ALTER TABLE games ADD COLUMN join_code TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_games_join_code ON games(join_code);
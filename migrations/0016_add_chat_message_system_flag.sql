@@ -0,0 +1,3 @@
+-- Migration number: 0016 	 2025-08-31T09:12:40.561Z
+
+ALTER TABLE chat_messages ADD COLUMN is_system INTEGER NOT NULL DEFAULT 0;
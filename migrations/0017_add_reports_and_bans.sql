@@ -0,0 +1,22 @@
+-- Migration number: 0017 	 2025-08-31T14:03:27.904Z
+
+ALTER TABLE players ADD COLUMN fingerprint TEXT;
+
+CREATE TABLE reports (
+  id TEXT PRIMARY KEY,
+  game_id TEXT NOT NULL,
+  reporter_id TEXT NOT NULL,
+  reported_player_id TEXT NOT NULL,
+  reported_fingerprint TEXT,
+  reason TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_reports_reported_fingerprint ON reports(reported_fingerprint);
+
+CREATE TABLE bans (
+  fingerprint TEXT PRIMARY KEY,
+  reason TEXT NOT NULL,
+  banned_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  expires_at TIMESTAMP
+);
@@ -0,0 +1,12 @@
+-- Migration number: 0018 	 2025-09-01T08:47:13.220Z
+
+CREATE TABLE game_events (
+  sequence INTEGER PRIMARY KEY AUTOINCREMENT,
+  game_id TEXT NOT NULL,
+  recipient_player_id TEXT,
+  event_type TEXT NOT NULL,
+  payload TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_game_events_game_sequence ON game_events(game_id, sequence);
@@ -0,0 +1,7 @@
+-- Migration number: 0019 	 2025-09-01T16:30:52.774Z
+
+CREATE TABLE app_settings (
+  key TEXT PRIMARY KEY,
+  value TEXT NOT NULL,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,16 @@
+-- Migration number: 0020 	 2025-09-03T17:21:48.905Z
+
+CREATE TABLE game_results (
+  game_id TEXT NOT NULL,
+  player_id TEXT NOT NULL,
+  player_name TEXT NOT NULL,
+  fingerprint TEXT,
+  score INTEGER NOT NULL DEFAULT 0,
+  is_winner INTEGER NOT NULL DEFAULT 0,
+  rounds_played INTEGER NOT NULL DEFAULT 0,
+  finished_at TIMESTAMP NOT NULL,
+  PRIMARY KEY (game_id, player_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_game_results_player ON game_results(player_id);
+CREATE INDEX IF NOT EXISTS idx_game_results_fingerprint ON game_results(fingerprint);
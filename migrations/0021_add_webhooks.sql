@@ -0,0 +1,12 @@
+-- Migration number: 0021 	 2025-09-04T09:12:37.514Z
+
+-- a webhook without a game is registered by an operator and receives the events of all games
+CREATE TABLE webhooks (
+  id TEXT PRIMARY KEY,
+  game_id TEXT,
+  url TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhooks_game_id ON webhooks(game_id);
@@ -0,0 +1,12 @@
+-- Migration number: 0022 	 2025-09-04T15:40:19.226Z
+
+-- the address of the recipient isn't stored, the rows only count the invitations of a host
+CREATE TABLE invitations (
+  id TEXT PRIMARY KEY,
+  game_id TEXT NOT NULL,
+  sender_id TEXT NOT NULL,
+  sent_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_invitations_sender_sent_at ON invitations(sender_id, sent_at);
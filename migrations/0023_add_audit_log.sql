@@ -0,0 +1,12 @@
+-- Migration number: 0023 	 2025-09-05T09:12:44.318Z
+
+-- rows are never updated or deleted by the worker
+CREATE TABLE audit_log (
+  sequence INTEGER PRIMARY KEY AUTOINCREMENT,
+  actor TEXT NOT NULL,
+  action TEXT NOT NULL,
+  target TEXT,
+  details TEXT,
+  request_id TEXT,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
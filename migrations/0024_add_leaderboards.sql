@@ -0,0 +1,29 @@
+-- Migration number: 0024 	 2025-09-05T16:27:03.512Z
+
+-- bluffs and challenges of a player, counted while the game runs
+ALTER TABLE players ADD COLUMN bluffs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE players ADD COLUMN successful_bluffs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE players ADD COLUMN challenges INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE players ADD COLUMN successful_challenges INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE game_results ADD COLUMN bluffs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE game_results ADD COLUMN successful_bluffs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE game_results ADD COLUMN challenges INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE game_results ADD COLUMN successful_challenges INTEGER NOT NULL DEFAULT 0;
+
+-- aggregated from game_results, so a row can be rebuilt at any time
+CREATE TABLE leaderboards (
+  period TEXT NOT NULL,
+  period_start TEXT NOT NULL,
+  identity TEXT NOT NULL,
+  player_name TEXT NOT NULL,
+  games_played INTEGER NOT NULL DEFAULT 0,
+  wins INTEGER NOT NULL DEFAULT 0,
+  losses INTEGER NOT NULL DEFAULT 0,
+  successful_bluffs INTEGER NOT NULL DEFAULT 0,
+  successful_challenges INTEGER NOT NULL DEFAULT 0,
+  updated_at TIMESTAMP NOT NULL,
+  PRIMARY KEY (period, period_start, identity)
+);
+
+CREATE INDEX IF NOT EXISTS idx_leaderboards_ranking ON leaderboards(period, period_start, wins DESC);
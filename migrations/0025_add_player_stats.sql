@@ -0,0 +1,14 @@
+-- Migration number: 0025 	 2025-09-05T18:02:41.337Z
+
+-- lifetime statistics per player identity, rebuilt from game_results whenever a game ended
+CREATE TABLE player_stats (
+  identity TEXT PRIMARY KEY,
+  player_name TEXT NOT NULL,
+  games_played INTEGER NOT NULL DEFAULT 0,
+  wins INTEGER NOT NULL DEFAULT 0,
+  bluffs INTEGER NOT NULL DEFAULT 0,
+  successful_bluffs INTEGER NOT NULL DEFAULT 0,
+  challenges INTEGER NOT NULL DEFAULT 0,
+  successful_challenges INTEGER NOT NULL DEFAULT 0,
+  updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Migration number: 0026 	 2025-09-06T10:41:17.806Z
+
+-- an achievement is unlocked once per player identity, the first unlock is kept
+CREATE TABLE achievements (
+  identity TEXT NOT NULL,
+  achievement TEXT NOT NULL,
+  player_id TEXT NOT NULL,
+  game_id TEXT,
+  unlocked_at TIMESTAMP NOT NULL,
+  PRIMARY KEY (identity, achievement)
+);
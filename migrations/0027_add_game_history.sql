@@ -0,0 +1,8 @@
+-- Migration number: 0027 	 2025-09-06T14:23:09.154Z
+
+-- the history of a player outlives the game, which is archived and deleted
+ALTER TABLE game_results ADD COLUMN started_at TIMESTAMP;
+ALTER TABLE game_results ADD COLUMN player_count INTEGER NOT NULL DEFAULT 0;
+
+-- queries over the games of a player filter on the same expression as player_identity
+CREATE INDEX IF NOT EXISTS idx_game_results_identity ON game_results(COALESCE(fingerprint, 'name:' || player_name), finished_at DESC, game_id DESC);
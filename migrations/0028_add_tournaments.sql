@@ -0,0 +1,52 @@
+-- Migration number: 0028 	 2025-09-08T09:12:44.371Z
+
+CREATE TABLE tournaments (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  state TEXT NOT NULL,
+  players_per_game INTEGER NOT NULL,
+  max_entrants INTEGER NOT NULL,
+  current_round INTEGER NOT NULL DEFAULT 0,
+  winner_entrant_id TEXT,
+  created_at TIMESTAMP NOT NULL,
+  started_at TIMESTAMP,
+  finished_at TIMESTAMP
+);
+
+-- the id of an entrant is only handed to the client who registered, it grants their sessions
+CREATE TABLE tournament_entrants (
+  id TEXT PRIMARY KEY,
+  tournament_id TEXT NOT NULL,
+  player_name TEXT NOT NULL,
+  fingerprint TEXT,
+  seed INTEGER NOT NULL,
+  eliminated_in_round INTEGER,
+  registered_at TIMESTAMP NOT NULL,
+  FOREIGN KEY(tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE,
+  UNIQUE (tournament_id, seed)
+);
+
+-- a match without a game is a bye, its only entrant advances right away
+CREATE TABLE tournament_matches (
+  tournament_id TEXT NOT NULL,
+  round INTEGER NOT NULL,
+  slot INTEGER NOT NULL,
+  game_id TEXT UNIQUE,
+  winner_entrant_id TEXT,
+  finished_at TIMESTAMP,
+  PRIMARY KEY (tournament_id, round, slot),
+  FOREIGN KEY(tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE
+);
+
+CREATE TABLE tournament_seats (
+  tournament_id TEXT NOT NULL,
+  round INTEGER NOT NULL,
+  slot INTEGER NOT NULL,
+  entrant_id TEXT NOT NULL,
+  player_id TEXT,
+  PRIMARY KEY (tournament_id, round, entrant_id),
+  FOREIGN KEY(tournament_id) REFERENCES tournaments(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_tournament_entrants_tournament ON tournament_entrants(tournament_id);
+CREATE INDEX IF NOT EXISTS idx_tournament_seats_player ON tournament_seats(player_id);
@@ -0,0 +1,20 @@
+-- Migration number: 0029 	 2025-09-08T14:37:19.208Z
+
+-- the current season is referenced by the `current_season` row of app_settings
+CREATE TABLE seasons (
+  number INTEGER PRIMARY KEY,
+  name TEXT NOT NULL,
+  starts_at TEXT NOT NULL,
+  ends_at TEXT
+);
+
+-- the first season covers every game finished so far
+INSERT INTO seasons (number, name, starts_at)
+  VALUES (1, 'Season 1', strftime('%Y-%m-%dT%H:%M:%S+00:00', COALESCE((SELECT MIN(finished_at) FROM game_results), 'now')));
+
+INSERT INTO app_settings (key, value, updated_at) VALUES ('current_season', '1', CURRENT_TIMESTAMP);
+
+-- the season leaderboard starts out as the all-time leaderboard
+INSERT INTO leaderboards (period, period_start, identity, player_name, games_played, wins, losses, successful_bluffs, successful_challenges, updated_at)
+  SELECT 'season', (SELECT starts_at FROM seasons WHERE number = 1), identity, player_name, games_played, wins, losses, successful_bluffs, successful_challenges, updated_at
+  FROM leaderboards WHERE period = 'alltime';
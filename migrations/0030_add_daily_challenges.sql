@@ -0,0 +1,17 @@
+-- Migration number: 0030 	 2025-09-08T17:05:32.649Z
+
+-- one template per UTC day, created when it is requested first
+CREATE TABLE daily_challenges (
+  date TEXT PRIMARY KEY,
+  seed TEXT NOT NULL,
+  created_at TIMESTAMP NOT NULL
+);
+
+-- the seed drives the shuffle of the deck and the card to play of every round
+ALTER TABLE games ADD COLUMN daily_date TEXT;
+ALTER TABLE games ADD COLUMN daily_seed TEXT;
+
+-- kept on the results, since finished games are cleaned up
+ALTER TABLE game_results ADD COLUMN daily_date TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_game_results_daily_date ON game_results(daily_date);
@@ -0,0 +1,91 @@
+-- Migration number: 0031 	 2025-09-09T09:12:44.318Z
+
+-- compact copy of every game for the lobby, so listing it doesn't join the players each time
+CREATE TABLE game_summaries (
+  game_id TEXT PRIMARY KEY,
+  state TEXT NOT NULL,
+  visibility TEXT NOT NULL,
+  max_players INTEGER NOT NULL,
+  join_code TEXT,
+  host_name TEXT,
+  player_count INTEGER NOT NULL DEFAULT 0,
+  round_number INTEGER NOT NULL DEFAULT 0,
+  started_at TIMESTAMP NOT NULL,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_game_summaries_lobby
+  ON game_summaries(visibility, state, started_at);
+CREATE INDEX IF NOT EXISTS idx_game_summaries_join_code ON game_summaries(join_code);
+
+INSERT INTO game_summaries (game_id, state, visibility, max_players, join_code, host_name,
+    player_count, round_number, started_at)
+  SELECT g.id, g.state, g.visibility, g.max_players, g.join_code,
+      (SELECT name FROM players WHERE id = g.host_player_id),
+      (SELECT COUNT(*) FROM players WHERE game_id = g.id),
+      g.round_number, g.started_at
+  FROM games g;
+
+-- the triggers keep the summaries in sync with every write to the games and the players
+CREATE TRIGGER IF NOT EXISTS trg_game_summaries_insert_game AFTER INSERT ON games
+BEGIN
+  INSERT INTO game_summaries (game_id, state, visibility, max_players, join_code, host_name,
+      player_count, round_number, started_at)
+    VALUES (NEW.id, NEW.state, NEW.visibility, NEW.max_players, NEW.join_code,
+      (SELECT name FROM players WHERE id = NEW.host_player_id),
+      (SELECT COUNT(*) FROM players WHERE game_id = NEW.id),
+      NEW.round_number, NEW.started_at);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_game_summaries_update_game AFTER UPDATE ON games
+BEGIN
+  UPDATE game_summaries SET
+      state = NEW.state,
+      visibility = NEW.visibility,
+      max_players = NEW.max_players,
+      join_code = NEW.join_code,
+      host_name = (SELECT name FROM players WHERE id = NEW.host_player_id),
+      round_number = NEW.round_number,
+      started_at = NEW.started_at,
+      updated_at = CURRENT_TIMESTAMP
+    WHERE game_id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_game_summaries_delete_game AFTER DELETE ON games
+BEGIN
+  DELETE FROM game_summaries WHERE game_id = OLD.id;
+END;
+
+-- the host joins after the game row was created, so the host name is refreshed with the players
+CREATE TRIGGER IF NOT EXISTS trg_game_summaries_insert_player AFTER INSERT ON players
+WHEN NEW.game_id IS NOT NULL
+BEGIN
+  UPDATE game_summaries SET
+      player_count = (SELECT COUNT(*) FROM players WHERE game_id = NEW.game_id),
+      host_name = (SELECT p.name FROM games g JOIN players p ON p.id = g.host_player_id
+        WHERE g.id = NEW.game_id),
+      updated_at = CURRENT_TIMESTAMP
+    WHERE game_id = NEW.game_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_game_summaries_update_player
+AFTER UPDATE OF game_id, name ON players
+BEGIN
+  UPDATE game_summaries SET
+      player_count = (SELECT COUNT(*) FROM players WHERE game_id = game_summaries.game_id),
+      host_name = (SELECT p.name FROM games g JOIN players p ON p.id = g.host_player_id
+        WHERE g.id = game_summaries.game_id),
+      updated_at = CURRENT_TIMESTAMP
+    WHERE game_id IN (OLD.game_id, NEW.game_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_game_summaries_delete_player AFTER DELETE ON players
+WHEN OLD.game_id IS NOT NULL
+BEGIN
+  UPDATE game_summaries SET
+      player_count = (SELECT COUNT(*) FROM players WHERE game_id = OLD.game_id),
+      host_name = (SELECT p.name FROM games g JOIN players p ON p.id = g.host_player_id
+        WHERE g.id = OLD.game_id),
+      updated_at = CURRENT_TIMESTAMP
+    WHERE game_id = OLD.game_id;
+END;
@@ -0,0 +1,6 @@
+-- Migration number: 0032 	 2025-09-09T15:27:03.941Z
+
+-- restarted whenever the turn passes to another player
+ALTER TABLE games ADD COLUMN turn_started_at TIMESTAMP;
+-- seconds a player has for a move, chosen by the host like the other settings
+ALTER TABLE games ADD COLUMN turn_seconds INTEGER NOT NULL DEFAULT 60;
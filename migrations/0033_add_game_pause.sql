@@ -0,0 +1,4 @@
+-- Migration number: 0033 	 2025-09-10T08:41:56.207Z
+
+-- set while the host pauses the game, so the countdown of the turn continues where it stopped
+ALTER TABLE games ADD COLUMN paused_at TIMESTAMP;
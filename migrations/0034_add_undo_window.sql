@@ -0,0 +1,4 @@
+-- Migration number: 0034 	 2025-09-10T15:02:31.774Z
+
+-- house rule: seconds a player can take back their last claim; 0 turns it off
+ALTER TABLE games ADD COLUMN undo_seconds INTEGER NOT NULL DEFAULT 0;
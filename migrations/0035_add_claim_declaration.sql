@@ -0,0 +1,5 @@
+-- Migration number: 0035 	 2025-09-11T07:26:09.512Z
+
+-- what the player announced with the claim, e.g. "three Kings"; shown while the cards are hidden
+ALTER TABLE claims ADD COLUMN declared_card_type TEXT;
+ALTER TABLE claims ADD COLUMN declared_count INTEGER;
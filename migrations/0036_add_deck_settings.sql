@@ -0,0 +1,5 @@
+-- Migration number: 0036 	 2025-09-11T13:48:52.095Z
+
+-- number of standard decks shuffled together and cards dealt per player; NULL deals everything
+ALTER TABLE games ADD COLUMN deck_count INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE games ADD COLUMN hand_size INTEGER;
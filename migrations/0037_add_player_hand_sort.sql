@@ -0,0 +1,4 @@
+-- Migration number: 0037 	 2025-09-12T09:05:17.640Z
+
+-- order in which the server sorts the hand of a player: Dealt, Rank or Suit
+ALTER TABLE players ADD COLUMN hand_sort TEXT NOT NULL DEFAULT 'Dealt';
@@ -0,0 +1,6 @@
+-- Migration number: 0038 	 2025-09-12T14:21:40.388Z
+
+-- short-lived events like reactions are only delivered until they expire and pruned afterwards
+ALTER TABLE game_events ADD COLUMN expires_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_game_events_expires_at ON game_events(expires_at);
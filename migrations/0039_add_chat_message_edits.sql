@@ -0,0 +1,5 @@
+-- Migration number: 0039 	 2025-09-13T10:05:17.642Z
+
+-- authors can edit or delete their messages for a short while; deleted messages stay as tombstones
+ALTER TABLE chat_messages ADD COLUMN edited_at TIMESTAMP;
+ALTER TABLE chat_messages ADD COLUMN deleted_at TIMESTAMP;
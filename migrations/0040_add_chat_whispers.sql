@@ -0,0 +1,7 @@
+-- Migration number: 0040 	 2025-09-13T16:48:02.915Z
+
+-- whispers are stored next to the public messages but only delivered to sender and recipient
+ALTER TABLE chat_messages ADD COLUMN recipient_player_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_chat_messages_public
+  ON chat_messages(chat_id, sent_at) WHERE recipient_player_id IS NULL;
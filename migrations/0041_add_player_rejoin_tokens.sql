@@ -0,0 +1,4 @@
+-- Migration number: 0041 	 2025-09-14T09:21:37.408Z
+
+-- only the hash of the rejoin token is stored, the client keeps the token itself
+ALTER TABLE players ADD COLUMN rejoin_token_hash TEXT;
@@ -0,0 +1,18 @@
+-- Migration number: 0042 	 2025-09-14T14:05:52.671Z
+
+-- clients waiting for a seat in a full game; the id is only known to the waiting client
+CREATE TABLE waitlist (
+  id TEXT PRIMARY KEY,
+  game_id TEXT NOT NULL,
+  name TEXT NOT NULL,
+  avatar_url TEXT,
+  color TEXT,
+  emoji TEXT,
+  fingerprint TEXT,
+  joined_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  player_id TEXT,
+  promoted_at TIMESTAMP,
+  FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_waitlist_game_joined_at ON waitlist(game_id, joined_at);
@@ -0,0 +1,40 @@
+-- Migration number: 0043 	 2025-09-14T17:32:08.214Z
+
+-- optional language and region of the table, so players can find games whose chat they understand
+ALTER TABLE games ADD COLUMN language TEXT;
+ALTER TABLE games ADD COLUMN region TEXT;
+
+ALTER TABLE game_summaries ADD COLUMN language TEXT;
+ALTER TABLE game_summaries ADD COLUMN region TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_game_summaries_language
+  ON game_summaries(language, visibility, state);
+
+-- the summaries copy the new columns from now on
+DROP TRIGGER IF EXISTS trg_game_summaries_insert_game;
+CREATE TRIGGER trg_game_summaries_insert_game AFTER INSERT ON games
+BEGIN
+  INSERT INTO game_summaries (game_id, state, visibility, max_players, join_code, host_name,
+      player_count, round_number, started_at, language, region)
+    VALUES (NEW.id, NEW.state, NEW.visibility, NEW.max_players, NEW.join_code,
+      (SELECT name FROM players WHERE id = NEW.host_player_id),
+      (SELECT COUNT(*) FROM players WHERE game_id = NEW.id),
+      NEW.round_number, NEW.started_at, NEW.language, NEW.region);
+END;
+
+DROP TRIGGER IF EXISTS trg_game_summaries_update_game;
+CREATE TRIGGER trg_game_summaries_update_game AFTER UPDATE ON games
+BEGIN
+  UPDATE game_summaries SET
+      state = NEW.state,
+      visibility = NEW.visibility,
+      max_players = NEW.max_players,
+      join_code = NEW.join_code,
+      host_name = (SELECT name FROM players WHERE id = NEW.host_player_id),
+      round_number = NEW.round_number,
+      started_at = NEW.started_at,
+      language = NEW.language,
+      region = NEW.region,
+      updated_at = CURRENT_TIMESTAMP
+    WHERE game_id = NEW.id;
+END;
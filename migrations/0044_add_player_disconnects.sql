@@ -0,0 +1,4 @@
+-- Migration number: 0044 	 2025-09-15T08:47:21.536Z
+
+-- set while the heartbeat of a player stopped during their turn, cleared by the next heartbeat
+ALTER TABLE players ADD COLUMN disconnected_at TEXT;
@@ -0,0 +1,20 @@
+-- Migration number: 0045 	 2025-09-15T10:05:43.182Z
+
+-- every webhook signs its payloads with its own secret; older webhooks keep the worker secret
+ALTER TABLE webhooks ADD COLUMN secret TEXT;
+
+-- deliveries that failed on every attempt, kept for the operators to inspect
+CREATE TABLE webhook_dead_letters (
+  id TEXT PRIMARY KEY,
+  webhook_id TEXT NOT NULL,
+  delivery_id TEXT NOT NULL,
+  event TEXT NOT NULL,
+  game_id TEXT NOT NULL,
+  payload TEXT NOT NULL,
+  attempts INTEGER NOT NULL,
+  last_error TEXT NOT NULL,
+  failed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_dead_letters_failed_at ON webhook_dead_letters(failed_at);
@@ -0,0 +1,4 @@
+-- Migration number: 0046 	 2025-09-16T10:12:44.903Z
+
+-- set while a host or an operator muted a player in the chat, the mute ends on its own
+ALTER TABLE players ADD COLUMN muted_until TEXT;
@@ -0,0 +1,4 @@
+-- Migration number: 0047 	 2025-09-16T14:38:05.617Z
+
+-- set when the host reclaimed an abandoned game, the cleanup limits count from then on
+ALTER TABLE games ADD COLUMN reclaimed_at TEXT;
@@ -0,0 +1,4 @@
+-- Migration number: 0048 	 2025-09-17T09:21:47.330Z
+
+-- skin of the cards the host picked for the whole table
+ALTER TABLE games ADD COLUMN deck_theme TEXT NOT NULL DEFAULT 'Classic';
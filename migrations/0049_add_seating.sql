@@ -0,0 +1,15 @@
+-- Migration number: 0049 	 2025-09-18T16:04:12.587Z
+
+-- explicit seats of the players, which decide the order of the turns
+ALTER TABLE players ADD COLUMN seat_index INTEGER NOT NULL DEFAULT 0;
+
+-- the players of running games keep the order they joined in
+UPDATE players SET seat_index = (
+  SELECT COUNT(*) FROM players AS earlier
+    WHERE earlier.game_id = players.game_id
+      AND (earlier.joined_at < players.joined_at
+        OR (earlier.joined_at = players.joined_at AND earlier.id < players.id))
+);
+
+-- whether the seats are shuffled when the host starts the game
+ALTER TABLE games ADD COLUMN randomize_seats INTEGER NOT NULL DEFAULT 0;
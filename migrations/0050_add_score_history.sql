@@ -0,0 +1,14 @@
+-- Migration number: 0050 	 2025-09-19T11:38:26.904Z
+
+-- score and hand of every player after each round, so the end screen can plot the progression
+CREATE TABLE score_history (
+  game_id TEXT NOT NULL,
+  round_number INTEGER NOT NULL,
+  player_id TEXT NOT NULL,
+  player_name TEXT NOT NULL,
+  score INTEGER NOT NULL,
+  card_count INTEGER NOT NULL,
+  recorded_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (game_id, round_number, player_id),
+  FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
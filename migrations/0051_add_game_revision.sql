@@ -0,0 +1,4 @@
+-- Migration number: 0051 	 2025-09-21T08:14:52.371Z
+
+-- incremented with every update of a game, so an update based on an outdated game is rejected
+ALTER TABLE games ADD COLUMN revision INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- Migration number: 0052 	 2025-09-21T10:42:17.508Z
+
+-- hash of the token the waiting client proves with that an entry is its own
+ALTER TABLE waitlist ADD COLUMN token_hash TEXT;
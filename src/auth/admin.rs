@@ -0,0 +1,111 @@
+use axum::{
+    extract::FromRequestParts,
+    http::request::Parts,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    auth::cf_access::{verify_access_token, CF_ACCESS_JWT_HEADER},
+    errors::{authentication_error::AuthenticationError, authorization_error::AuthorizationError},
+    router::router_provider::AppState,
+    utils::time::server_time,
+};
+
+// constants
+/// Header carrying the token of an operator.
+pub const ADMIN_TOKEN_HEADER: &str = "Admin-Token";
+
+/// Name of the worker secret holding the admin token.
+pub const ADMIN_TOKEN_BINDING: &str = "ADMIN_TOKEN";
+
+/// Identity of an operator who authenticated with the static admin token.
+const STATIC_TOKEN_IDENTITY: &str = "admin-token";
+
+/// Operator authenticated by Cloudflare Access or the `Admin-Token` header.
+///
+/// Used as an extractor by every endpoint under `/admin`.
+///
+/// -> If `CF_ACCESS_TEAM_DOMAIN` and `CF_ACCESS_AUD` are set, only a valid
+///    `Cf-Access-Jwt-Assertion` header is accepted and the static token is ignored
+/// -> Otherwise the `Admin-Token` header is compared with the `ADMIN_TOKEN` secret; without the
+///    secret all admin requests are rejected
+///
+/// # Props
+///
+/// - `identity` -> Email of the operator or id of the service token; `admin-token` for the
+///   static token
+#[derive(Debug, Clone)]
+pub struct Admin {
+    /// Email of the operator or id of the service token
+    pub identity: String,
+}
+
+impl FromRequestParts<AppState> for Admin {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        match &state.config.cf_access {
+            Some(settings) => {
+                let token = header_value(parts, CF_ACCESS_JWT_HEADER).ok_or_else(|| {
+                    AuthenticationError::new("An Access token is required!".to_string())
+                        .into_response()
+                })?;
+                let claims = verify_access_token(&token, settings, server_time())
+                    .await
+                    .map_err(IntoResponse::into_response)?;
+
+                Ok(Admin {
+                    identity: claims.identity(),
+                })
+            }
+            None => {
+                let token = header_value(parts, ADMIN_TOKEN_HEADER).ok_or_else(|| {
+                    AuthenticationError::new("An admin token is required!".to_string())
+                        .into_response()
+                })?;
+
+                // the hashes of the header and the secret are compared in constant time
+                match &state.admin_token {
+                    Some(admin_token) if tokens_match(&token, admin_token) => Ok(Admin {
+                        identity: STATIC_TOKEN_IDENTITY.to_string(),
+                    }),
+                    _ => Err(AuthorizationError::new(
+                        "The admin token is invalid!".to_string(),
+                        String::new(),
+                    )
+                    .into_response()),
+                }
+            }
+        }
+    }
+}
+
+/// Returns the trimmed value of a header, `None` if it is missing or empty.
+fn header_value(parts: &Parts, name: &str) -> Option<String> {
+    parts
+        .headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Compares two tokens without leaking through the timing how many characters match.
+///
+/// Both tokens are hashed first, so the comparison always runs over the same number of bytes,
+/// independent of the length of the tokens.
+pub fn tokens_match(provided: &str, expected: &str) -> bool {
+    let provided = Sha256::digest(provided.as_bytes());
+    let expected = Sha256::digest(expected.as_bytes());
+
+    provided
+        .iter()
+        .zip(expected.iter())
+        .fold(0u8, |difference, (a, b)| difference | (a ^ b))
+        == 0
+}
@@ -0,0 +1,272 @@
+use std::cell::RefCell;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use rsa::{
+    pkcs1v15::{Signature, VerifyingKey},
+    signature::Verifier,
+    BigUint, RsaPublicKey,
+};
+use serde::{Deserialize, Deserializer};
+use sha2::Sha256;
+use worker::{send::SendFuture, Fetch, Url};
+
+use crate::errors::authentication_error::AuthenticationError;
+
+// constants
+/// Header in which Cloudflare Access forwards the token of an authenticated operator.
+pub const CF_ACCESS_JWT_HEADER: &str = "Cf-Access-Jwt-Assertion";
+
+/// Path of the signing keys below the team domain.
+const CERTS_PATH: &str = "/cdn-cgi/access/certs";
+
+/// Minutes the signing keys are kept before they are fetched again.
+///
+/// Access rotates its keys every six weeks and publishes the next key in advance, so an hour is
+/// short enough. A token signed with an unknown key triggers a refetch anyway.
+const JWKS_CACHE_MINUTES: i64 = 60;
+
+/// Seconds of clock skew tolerated when checking the lifetime of a token.
+const CLOCK_SKEW_SECONDS: i64 = 30;
+
+thread_local! {
+    /// Signing keys of the team domain, shared by all requests of this isolate.
+    static JWKS_CACHE: RefCell<Option<CachedJwks>> = const { RefCell::new(None) };
+}
+
+/// Settings of the Access application protecting the admin endpoints.
+///
+/// # Props
+///
+/// - `team_domain` -> Team domain of the Zero Trust organization, e.g. `team.cloudflareaccess.com`
+/// - `audience` -> Application audience tag of the Access application
+#[derive(Debug, Clone)]
+pub struct AccessSettings {
+    /// Team domain of the Zero Trust organization
+    pub team_domain: String,
+    /// Application audience tag of the Access application
+    pub audience: String,
+}
+
+/// Claims of an Access token that are checked.
+///
+/// # Props
+///
+/// - `aud` -> Audience tags the token was issued for
+/// - `iss` -> Team domain that issued the token
+/// - `exp` -> Unix timestamp after which the token is invalid
+/// - `nbf` -> Unix timestamp before which the token is invalid
+/// - `email` -> Email of an operator who signed in
+/// - `common_name` -> Client id of a service token
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccessClaims {
+    /// Audience tags the token was issued for
+    #[serde(deserialize_with = "one_or_many")]
+    pub aud: Vec<String>,
+    /// Team domain that issued the token
+    pub iss: String,
+    /// Unix timestamp after which the token is invalid
+    pub exp: i64,
+    /// Unix timestamp before which the token is invalid
+    #[serde(default)]
+    pub nbf: Option<i64>,
+    /// Email of an operator who signed in
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Client id of a service token
+    #[serde(default)]
+    pub common_name: Option<String>,
+}
+
+impl AccessClaims {
+    /// Returns who the token belongs to: the email of an operator or the id of a service token.
+    pub fn identity(&self) -> String {
+        self.email
+            .clone()
+            .or_else(|| self.common_name.clone())
+            .unwrap_or_else(|| "access".to_string())
+    }
+}
+
+/// Header of a JWT.
+#[derive(Deserialize)]
+struct JwtHeader {
+    /// Signing algorithm, only `RS256` is accepted
+    alg: String,
+    /// Id of the signing key
+    kid: Option<String>,
+}
+
+/// Public key of the team domain in JWK format.
+#[derive(Deserialize, Debug, Clone)]
+struct Jwk {
+    /// Id of the key
+    kid: String,
+    /// Modulus, base64url encoded
+    n: String,
+    /// Exponent, base64url encoded
+    e: String,
+}
+
+/// Response of the certs endpoint.
+#[derive(Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+/// Signing keys of a team domain with the point in time they were fetched.
+#[derive(Debug, Clone)]
+struct CachedJwks {
+    team_domain: String,
+    keys: Vec<Jwk>,
+    fetched_at: DateTime<Utc>,
+}
+
+/// Verifies the token Cloudflare Access forwarded with a request.
+///
+/// Checks the `RS256` signature against the keys of the team domain, the issuer, the audience and
+/// the lifetime of the token.
+///
+/// # Arguments
+///
+/// - `token` -> Value of the `Cf-Access-Jwt-Assertion` header
+/// - `settings` -> Team domain and audience of the Access application
+/// - `now` -> Point in time to check the lifetime against
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the token is malformed, signed by an unknown key, issued
+/// for another application or expired.
+pub async fn verify_access_token(
+    token: &str,
+    settings: &AccessSettings,
+    now: DateTime<Utc>,
+) -> Result<AccessClaims, AuthenticationError> {
+    let invalid = || AuthenticationError::new("The Access token is invalid!".to_string());
+
+    let mut parts = token.split('.');
+    let (Some(header), Some(payload), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+
+    let header: JwtHeader = decode_json(header).ok_or_else(invalid)?;
+    if header.alg != "RS256" {
+        return Err(invalid());
+    }
+    let kid = header.kid.ok_or_else(invalid)?;
+    let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+
+    let key = signing_key(&settings.team_domain, &kid, now)
+        .await
+        .ok_or_else(invalid)?;
+    let signing_input = &token[..header_and_payload_length(token)];
+    verify_signature(&key, signing_input.as_bytes(), &signature).ok_or_else(invalid)?;
+
+    let claims: AccessClaims = decode_json(payload).ok_or_else(invalid)?;
+    let timestamp = now.timestamp();
+    if claims.iss.trim_end_matches('/') != format!("https://{}", settings.team_domain)
+        || !claims.aud.iter().any(|aud| aud == &settings.audience)
+        || claims.exp + CLOCK_SKEW_SECONDS < timestamp
+        || claims
+            .nbf
+            .is_some_and(|nbf| nbf - CLOCK_SKEW_SECONDS > timestamp)
+    {
+        return Err(invalid());
+    }
+
+    Ok(claims)
+}
+
+// ----- Helper functions -----
+
+/// Gets the key with the passed id, fetching the keys again if it isn't cached.
+async fn signing_key(team_domain: &str, kid: &str, now: DateTime<Utc>) -> Option<Jwk> {
+    let cached = JWKS_CACHE.with(|cache| {
+        cache.borrow().as_ref().and_then(|cached| {
+            let fresh = cached.team_domain == team_domain
+                && now - cached.fetched_at < Duration::minutes(JWKS_CACHE_MINUTES);
+            fresh
+                .then(|| cached.keys.iter().find(|key| key.kid == kid).cloned())
+                .flatten()
+        })
+    });
+    if cached.is_some() {
+        return cached;
+    }
+
+    let keys = fetch_keys(team_domain).await?;
+    let key = keys.iter().find(|key| key.kid == kid).cloned();
+    JWKS_CACHE.with(|cache| {
+        *cache.borrow_mut() = Some(CachedJwks {
+            team_domain: team_domain.to_string(),
+            keys,
+            fetched_at: now,
+        })
+    });
+
+    key
+}
+
+/// Fetches the signing keys from the certs endpoint of the team domain.
+async fn fetch_keys(team_domain: &str) -> Option<Vec<Jwk>> {
+    let url = Url::parse(&format!("https://{team_domain}{CERTS_PATH}")).ok()?;
+
+    let result: worker::Result<JwkSet> =
+        SendFuture::new(async move { Fetch::Url(url).send().await?.json().await }).await;
+
+    match result {
+        Ok(jwk_set) => Some(jwk_set.keys),
+        Err(err) => {
+            warn!("Access signing keys couldn't be fetched: {err}");
+            None
+        }
+    }
+}
+
+/// Checks an `RS256` signature with a JWK.
+fn verify_signature(key: &Jwk, message: &[u8], signature: &[u8]) -> Option<()> {
+    let modulus = URL_SAFE_NO_PAD.decode(&key.n).ok()?;
+    let exponent = URL_SAFE_NO_PAD.decode(&key.e).ok()?;
+    let public_key = RsaPublicKey::new(
+        BigUint::from_bytes_be(&modulus),
+        BigUint::from_bytes_be(&exponent),
+    )
+    .ok()?;
+
+    let signature = Signature::try_from(signature).ok()?;
+    VerifyingKey::<Sha256>::new(public_key)
+        .verify(message, &signature)
+        .ok()
+}
+
+/// Length of `header.payload`, the part of a JWT that is signed.
+fn header_and_payload_length(token: &str) -> usize {
+    token.rfind('.').unwrap_or(token.len())
+}
+
+/// Decodes a base64url encoded JSON segment of a JWT.
+fn decode_json<T: serde::de::DeserializeOwned>(segment: &str) -> Option<T> {
+    let bytes = URL_SAFE_NO_PAD.decode(segment).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Accepts a single audience tag as well as a list of them.
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(aud) => vec![aud],
+        OneOrMany::Many(auds) => auds,
+    })
+}
@@ -0,0 +1,81 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    auth::session_token::{issue_scoped_token, scoped_key, verify_scoped_token},
+    errors::authentication_error::AuthenticationError,
+};
+
+// constants
+/// Header with the device token a client got from `/auth/device`.
+pub const DEVICE_TOKEN_HEADER: &str = "X-Device-Token";
+
+/// Scope of device tokens.
+///
+/// Device tokens are signed with a key derived from the session secret, so a device token can
+/// never pass as a session or spectator token and vice versa.
+const DEVICE_SCOPE: &str = "device";
+
+/// Claims that are signed into a device token.
+///
+/// A device token doesn't expire. The client keeps it and sends it with every game it creates or
+/// joins, so its statistics, history and recent opponents follow it from game to game.
+///
+/// # Props
+///
+/// - `device_id` -> Random id of the device
+/// - `issued_at` -> Unix timestamp in seconds the token was minted at
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceClaims {
+    /// Random id of the device
+    pub device_id: String,
+    /// Unix timestamp in seconds the token was minted at
+    pub issued_at: i64,
+}
+
+impl DeviceClaims {
+    /// Creates the claims of a new device.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` -> Point in time the token is minted at
+    pub fn new(now: DateTime<Utc>) -> Self {
+        DeviceClaims {
+            device_id: uuid::Uuid::new_v4().to_string(),
+            issued_at: now.timestamp(),
+        }
+    }
+
+    /// Derives the fingerprint of the device, which is stored with its players.
+    pub fn fingerprint(&self) -> String {
+        let identity = scoped_key(DEVICE_SCOPE, &self.device_id);
+        URL_SAFE_NO_PAD.encode(Sha256::digest(identity.as_bytes()))
+    }
+}
+
+/// Signs the claims and returns the device token.
+///
+/// # Arguments
+///
+/// - `claims` -> Claims to sign
+/// - `secret` -> Key of the worker used to sign all session tokens
+pub fn issue_device_token(claims: &DeviceClaims, secret: &str) -> String {
+    issue_scoped_token(DEVICE_SCOPE, claims, secret)
+}
+
+/// Checks the signature of a device token and returns its claims.
+///
+/// # Arguments
+///
+/// - `token` -> Token the client sent
+/// - `secret` -> Key of the worker used to sign all session tokens
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the token is malformed or was tampered with.
+pub fn verify_device_token(token: &str, secret: &str) -> Result<DeviceClaims, AuthenticationError> {
+    verify_scoped_token(DEVICE_SCOPE, token, secret)
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::session_token::{expiry, issue_scoped_token, verify_scoped_token, ExpiringClaims},
+    errors::authentication_error::AuthenticationError,
+};
+
+// constants
+/// Scope of export tokens.
+///
+/// Keeps export tokens apart from session and spectator tokens signed with the same secret.
+const EXPORT_SCOPE: &str = "export";
+
+/// Claims that are signed into the token of a download link.
+///
+/// # Props
+///
+/// - `game_id` -> Id of the game whose archive can be downloaded
+/// - `expires_at` -> Unix timestamp in seconds after which the token is invalid
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportClaims {
+    /// Id of the game whose archive can be downloaded
+    pub game_id: String,
+    /// Unix timestamp in seconds after which the token is invalid
+    pub expires_at: i64,
+}
+
+impl ExportClaims {
+    /// Creates claims which are valid for `lifetime_minutes`.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the exported game
+    /// - `now` -> Point in time the token is minted at
+    /// - `lifetime_minutes` -> Minutes the token stays valid, see `AppConfig`
+    pub fn new(game_id: String, now: DateTime<Utc>, lifetime_minutes: i64) -> Self {
+        ExportClaims {
+            game_id,
+            expires_at: expiry(now, lifetime_minutes),
+        }
+    }
+}
+
+impl ExpiringClaims for ExportClaims {
+    fn expires_at(&self) -> i64 {
+        self.expires_at
+    }
+}
+
+/// Signs the claims and returns the token of the download link.
+///
+/// # Arguments
+///
+/// - `claims` -> Claims to sign
+/// - `secret` -> Key of the worker used to sign all session tokens
+pub fn issue_export_token(claims: &ExportClaims, secret: &str) -> String {
+    issue_scoped_token(EXPORT_SCOPE, claims, secret)
+}
+
+/// Checks the signature and expiry of an export token and returns its claims.
+///
+/// # Arguments
+///
+/// - `token` -> Token of the download link
+/// - `secret` -> Key of the worker used to sign all session tokens
+/// - `now` -> Point in time the token is checked at
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the token is malformed, was tampered with or expired.
+pub fn verify_export_token(
+    token: &str,
+    secret: &str,
+    now: DateTime<Utc>,
+) -> Result<ExportClaims, AuthenticationError> {
+    let claims: ExportClaims = verify_scoped_token(EXPORT_SCOPE, token, secret)?;
+
+    if claims.is_expired(now) {
+        return Err(AuthenticationError::new(
+            "The download link expired!".to_string(),
+        ));
+    }
+
+    Ok(claims)
+}
@@ -0,0 +1,29 @@
+use axum::http::HeaderMap;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+
+// constants
+/// Header with a random id the client generates once and keeps in its storage.
+pub const CLIENT_ID_HEADER: &str = "X-Client-Id";
+
+/// Header Cloudflare sets to the IP address of the client.
+pub const CONNECTING_IP_HEADER: &str = "CF-Connecting-IP";
+
+/// Derives a fingerprint identifying the client of a request.
+///
+/// Uses the client id if one is sent and the IP address otherwise. The value is hashed, so no raw
+/// IP address ends up in the database.
+///
+/// # Returns
+///
+/// The fingerprint, or `None` if the request carries neither header.
+pub fn client_fingerprint(headers: &HeaderMap) -> Option<String> {
+    let identity = [CLIENT_ID_HEADER, CONNECTING_IP_HEADER]
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .filter_map(|value| value.to_str().ok())
+        .map(str::trim)
+        .find(|value| !value.is_empty())?;
+
+    Some(URL_SAFE_NO_PAD.encode(Sha256::digest(identity.as_bytes())))
+}
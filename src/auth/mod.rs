@@ -0,0 +1,9 @@
+pub mod admin;
+pub mod cf_access;
+pub mod device_token;
+pub mod export_token;
+pub mod fingerprint;
+pub mod rejoin_token;
+pub mod session_token;
+pub mod spectator_token;
+pub mod turnstile;
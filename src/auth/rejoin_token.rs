@@ -0,0 +1,50 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+
+use crate::{auth::admin::tokens_match, errors::authentication_error::AuthenticationError};
+
+/// Creates a new rejoin token for a player.
+///
+/// The token is handed to the client once, when the player joins. Only its hash is stored with
+/// the player, so a leaked database doesn't let anyone take over a seat.
+///
+/// # Returns
+///
+/// A tuple of the token for the client and the hash to store.
+pub fn issue_rejoin_token() -> (String, String) {
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    let hash = hash_rejoin_token(&token);
+
+    (token, hash)
+}
+
+/// Checks a rejoin token the client sent against the hash stored with the player.
+///
+/// # Arguments
+///
+/// - `token` -> Token the client sent
+/// - `stored_hash` -> Hash stored with the player; `None` if the player never got a token
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the token doesn't belong to the player.
+pub fn verify_rejoin_token(
+    token: &str,
+    stored_hash: Option<&str>,
+) -> Result<(), AuthenticationError> {
+    match stored_hash {
+        Some(stored_hash) if tokens_match(&hash_rejoin_token(token), stored_hash) => Ok(()),
+        _ => Err(AuthenticationError::new(
+            "The rejoin token is invalid!".to_string(),
+        )),
+    }
+}
+
+/// Hashes a rejoin token the way it is stored.
+fn hash_rejoin_token(token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(token.as_bytes()))
+}
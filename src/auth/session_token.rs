@@ -0,0 +1,206 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::errors::authentication_error::AuthenticationError;
+
+// constants
+/// Name of the worker secret holding the key to sign session tokens.
+pub const SESSION_SECRET_BINDING: &str = "SESSION_SECRET";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Claims that are signed into a session token.
+///
+/// A token binds a player to exactly one game.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the player the token was issued for
+/// - `game_id` -> Id of the game the player joined
+/// - `expires_at` -> Unix timestamp in seconds after which the token is invalid
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionClaims {
+    /// Id of the player the token was issued for
+    pub player_id: String,
+    /// Id of the game the player joined
+    pub game_id: String,
+    /// Unix timestamp in seconds after which the token is invalid
+    pub expires_at: i64,
+}
+
+impl SessionClaims {
+    /// Creates claims for a player which are valid for `lifetime_minutes`.
+    ///
+    /// # Arguments
+    ///
+    /// - `player_id` -> Id of the player
+    /// - `game_id` -> Id of the game the player joined
+    /// - `now` -> Point in time the token is issued at
+    /// - `lifetime_minutes` -> Minutes the token stays valid, see `AppConfig`
+    pub fn new(
+        player_id: String,
+        game_id: String,
+        now: DateTime<Utc>,
+        lifetime_minutes: i64,
+    ) -> Self {
+        SessionClaims {
+            player_id,
+            game_id,
+            expires_at: expiry(now, lifetime_minutes),
+        }
+    }
+}
+
+impl ExpiringClaims for SessionClaims {
+    fn expires_at(&self) -> i64 {
+        self.expires_at
+    }
+}
+
+/// Claims of a token which is only valid until a point in time.
+pub trait ExpiringClaims {
+    /// Unix timestamp in seconds after which the token is invalid.
+    fn expires_at(&self) -> i64;
+
+    /// Returns `true` if the token isn't valid anymore at the passed point in time.
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now.timestamp() >= self.expires_at()
+    }
+}
+
+/// Signs the claims and returns the token.
+///
+/// The token has the format `<base64url payload>.<base64url HMAC-SHA256 signature>`.
+///
+/// # Arguments
+///
+/// - `claims` -> Claims to sign
+/// - `secret` -> Key of the worker used to sign all tokens
+pub fn issue_token(claims: &SessionClaims, secret: &str) -> String {
+    encode_signed(claims, secret)
+}
+
+/// Checks the signature and expiry of a token and returns its claims.
+///
+/// # Arguments
+///
+/// - `token` -> Token sent by the client
+/// - `secret` -> Key of the worker used to sign all tokens
+/// - `now` -> Point in time the token is checked at
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the token is malformed, was tampered with or expired.
+pub fn verify_token(
+    token: &str,
+    secret: &str,
+    now: DateTime<Utc>,
+) -> Result<SessionClaims, AuthenticationError> {
+    let claims: SessionClaims = decode_signed("session", token, secret)?;
+
+    if claims.is_expired(now) {
+        return Err(AuthenticationError::new(
+            "The session token expired!".to_string(),
+        ));
+    }
+
+    Ok(claims)
+}
+
+/// Signs the claims of a token kept apart from session tokens and returns the token.
+///
+/// The token has the same format as a session token, but is signed with a key derived from the
+/// session secret and the scope, so it only passes the verification of its own scope.
+///
+/// # Arguments
+///
+/// - `scope` -> Name of the kind of token, e.g. `spectator`
+/// - `claims` -> Claims to sign
+/// - `secret` -> Key of the worker used to sign all session tokens
+pub(crate) fn issue_scoped_token<C: Serialize>(scope: &str, claims: &C, secret: &str) -> String {
+    encode_signed(claims, &scoped_key(scope, secret))
+}
+
+/// Checks the signature of a token of a scope and returns its claims.
+///
+/// The expiry isn't checked, since not every scope has one.
+///
+/// # Arguments
+///
+/// - `scope` -> Name of the kind of token, e.g. `spectator`
+/// - `token` -> Token sent by the client
+/// - `secret` -> Key of the worker used to sign all session tokens
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the token is malformed or was tampered with.
+pub(crate) fn verify_scoped_token<C: DeserializeOwned>(
+    scope: &str,
+    token: &str,
+    secret: &str,
+) -> Result<C, AuthenticationError> {
+    decode_signed(scope, token, &scoped_key(scope, secret))
+}
+
+/// Returns the Unix timestamp in seconds `lifetime_minutes` after `now`.
+pub(crate) fn expiry(now: DateTime<Utc>, lifetime_minutes: i64) -> i64 {
+    (now + Duration::minutes(lifetime_minutes)).timestamp()
+}
+
+// ----- Helper functions -----
+
+/// Derives the signing key of a scope from the session secret.
+pub(crate) fn scoped_key(scope: &str, secret: &str) -> String {
+    format!("{scope}:{secret}")
+}
+
+/// Serializes and signs the claims with the key.
+fn encode_signed<C: Serialize>(claims: &C, key: &str) -> String {
+    let payload = URL_SAFE_NO_PAD
+        .encode(serde_json::to_vec(claims).expect("token claims are always serializable"));
+    let signature = URL_SAFE_NO_PAD.encode(sign(payload.as_bytes(), key));
+
+    format!("{payload}.{signature}")
+}
+
+/// Checks the signature of the token with the key and deserializes its claims.
+///
+/// `scope` only names the kind of token in the error messages.
+fn decode_signed<C: DeserializeOwned>(
+    scope: &str,
+    token: &str,
+    key: &str,
+) -> Result<C, AuthenticationError> {
+    let malformed = || AuthenticationError::new(format!("The {scope} token is malformed!"));
+
+    let (payload, signature) = token.split_once('.').ok_or_else(malformed)?;
+    let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| malformed())?;
+
+    let mut mac = new_mac(key);
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&signature).map_err(|_| {
+        AuthenticationError::new(format!("The signature of the {scope} token is invalid!"))
+    })?;
+
+    URL_SAFE_NO_PAD
+        .decode(payload)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(malformed)
+}
+
+/// Creates the HMAC instance for the secret.
+pub(crate) fn new_mac(secret: &str) -> HmacSha256 {
+    HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length")
+}
+
+/// Calculates the signature of the payload.
+pub(crate) fn sign(payload: &[u8], secret: &str) -> Vec<u8> {
+    let mut mac = new_mac(secret);
+    mac.update(payload);
+    mac.finalize().into_bytes().to_vec()
+}
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::session_token::{expiry, issue_scoped_token, verify_scoped_token, ExpiringClaims},
+    errors::authentication_error::AuthenticationError,
+};
+
+// constants
+/// Scope of spectator tokens.
+///
+/// Spectator tokens are signed with a key derived from the session secret, so a spectator token
+/// can never pass as a session token and vice versa.
+const SPECTATOR_SCOPE: &str = "spectator";
+
+/// Claims that are signed into a spectator token.
+///
+/// A spectator isn't a player of the game and has read-only access to its public data.
+///
+/// # Props
+///
+/// - `game_id` -> Id of the game that can be watched
+/// - `expires_at` -> Unix timestamp in seconds after which the token is invalid
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectatorClaims {
+    /// Id of the game that can be watched
+    pub game_id: String,
+    /// Unix timestamp in seconds after which the token is invalid
+    pub expires_at: i64,
+}
+
+impl SpectatorClaims {
+    /// Creates claims which are valid for `lifetime_minutes`.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game that can be watched
+    /// - `now` -> Point in time the token is minted at
+    /// - `lifetime_minutes` -> Minutes the token stays valid, see `AppConfig`
+    pub fn new(game_id: String, now: DateTime<Utc>, lifetime_minutes: i64) -> Self {
+        SpectatorClaims {
+            game_id,
+            expires_at: expiry(now, lifetime_minutes),
+        }
+    }
+}
+
+impl ExpiringClaims for SpectatorClaims {
+    fn expires_at(&self) -> i64 {
+        self.expires_at
+    }
+}
+
+/// Signs the claims and returns the spectator token.
+///
+/// # Arguments
+///
+/// - `claims` -> Claims to sign
+/// - `secret` -> Key of the worker used to sign all session tokens
+pub fn issue_spectator_token(claims: &SpectatorClaims, secret: &str) -> String {
+    issue_scoped_token(SPECTATOR_SCOPE, claims, secret)
+}
+
+/// Checks the signature and expiry of a spectator token and returns its claims.
+///
+/// # Arguments
+///
+/// - `token` -> Token of the spectator link
+/// - `secret` -> Key of the worker used to sign all session tokens
+/// - `now` -> Point in time the token is checked at
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the token is malformed, was tampered with or expired.
+pub fn verify_spectator_token(
+    token: &str,
+    secret: &str,
+    now: DateTime<Utc>,
+) -> Result<SpectatorClaims, AuthenticationError> {
+    let claims: SpectatorClaims = verify_scoped_token(SPECTATOR_SCOPE, token, secret)?;
+
+    if claims.is_expired(now) {
+        return Err(AuthenticationError::new(
+            "The spectator link expired!".to_string(),
+        ));
+    }
+
+    Ok(claims)
+}
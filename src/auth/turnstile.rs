@@ -0,0 +1,106 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+use worker::{send::SendFuture, Fetch, Headers, Method, Request, RequestInit};
+
+use crate::errors::authentication_error::AuthenticationError;
+
+// constants
+/// Name of the worker secret holding the Turnstile secret key.
+pub const TURNSTILE_SECRET_BINDING: &str = "TURNSTILE_SECRET";
+
+/// Endpoint of Cloudflare which validates Turnstile tokens.
+pub const SITEVERIFY_URL: &str = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
+
+/// Body of a request to the siteverify API.
+///
+/// # Props
+///
+/// - `secret` -> Secret key of the Turnstile widget
+/// - `response` -> Token the client received from the widget
+/// - `remoteip` -> Optional IP address of the client
+#[derive(Serialize)]
+struct SiteverifyRequest<'a> {
+    /// Secret key of the Turnstile widget
+    secret: &'a str,
+    /// Token the client received from the widget
+    response: &'a str,
+    /// Optional IP address of the client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remoteip: Option<&'a str>,
+}
+
+/// Relevant part of the siteverify API response.
+///
+/// # Props
+///
+/// - `success` -> Whether the token is valid
+/// - `error_codes` -> Reasons why the token was rejected
+#[derive(Deserialize)]
+struct SiteverifyResponse {
+    /// Whether the token is valid
+    success: bool,
+    /// Reasons why the token was rejected
+    #[serde(default, rename = "error-codes")]
+    error_codes: Vec<String>,
+}
+
+/// Validates a Turnstile token against the siteverify API of Cloudflare.
+///
+/// Every token can only be validated once, so a replayed token is rejected as well.
+///
+/// # Arguments
+///
+/// - `token` -> Token the client received from the Turnstile widget
+/// - `secret` -> Secret key of the widget
+/// - `remote_ip` -> IP address of the client, if known
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the token is missing or invalid, or the API couldn't be
+/// reached.
+pub async fn verify_turnstile_token(
+    token: Option<&str>,
+    secret: &str,
+    remote_ip: Option<&str>,
+) -> Result<(), AuthenticationError> {
+    let token = token
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| AuthenticationError::new("A Turnstile token is required!".to_string()))?;
+
+    let unverified =
+        || AuthenticationError::new("The Turnstile challenge couldn't be verified!".to_string());
+
+    let body = serde_json::to_string(&SiteverifyRequest {
+        secret,
+        response: token,
+        remoteip: remote_ip,
+    })
+    .map_err(|_| unverified())?;
+
+    let headers = Headers::new();
+    headers
+        .set("Content-Type", "application/json")
+        .map_err(|_| unverified())?;
+    let mut init = RequestInit::new();
+    init.with_method(Method::Post)
+        .with_headers(headers)
+        .with_body(Some(JsValue::from_str(&body)));
+    let request = Request::new_with_init(SITEVERIFY_URL, &init).map_err(|_| unverified())?;
+
+    let result: SiteverifyResponse =
+        SendFuture::new(async move { Fetch::Request(request).send().await?.json().await })
+            .await
+            .map_err(|err| {
+                warn!("Siteverify request failed: {err}");
+                unverified()
+            })?;
+
+    if !result.success {
+        warn!("Turnstile token rejected: {:?}", result.error_codes);
+        return Err(unverified());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Plays games between bots without D1 or HTTP and prints their statistics.
+//!
+//! ```sh
+//! cargo run --features simulation --bin simulate -- --games 10000 --seed 7 \
+//!     --strategies honest,bluffer,skeptic --jokers 2 --max-rounds 300
+//! ```
+
+use std::{env, process::ExitCode, str::FromStr};
+
+use lue_lue_backend::simulation::{
+    bot_strategy::BotStrategy,
+    simulator::{run_simulation, SimulationConfig},
+};
+
+fn main() -> ExitCode {
+    match parse_args(env::args().skip(1)) {
+        Ok(config) => {
+            println!("{}", run_simulation(config));
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!(
+                "usage: simulate [--games N] [--seed N] [--strategies a,b,...] [--jokers N] \
+                 [--max-rounds N]"
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Reads the settings of the run from the command line arguments.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<SimulationConfig, String> {
+    let mut config = SimulationConfig::default();
+
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing the value of `{flag}`"))?;
+
+        match flag.as_str() {
+            "--games" => config.games = parse(&flag, &value)?,
+            "--seed" => config.seed = parse(&flag, &value)?,
+            "--jokers" => config.jokers = parse(&flag, &value)?,
+            "--max-rounds" => config.max_rounds = parse(&flag, &value)?,
+            "--strategies" => {
+                config.strategies = value
+                    .split(',')
+                    .map(|strategy| parse(&flag, strategy.trim()))
+                    .collect::<Result<Vec<BotStrategy>, String>>()?;
+            }
+            _ => return Err(format!("Unknown argument `{flag}`")),
+        }
+    }
+
+    if config.strategies.len() < 2 {
+        return Err("A game needs at least two strategies".to_string());
+    }
+
+    Ok(config)
+}
+
+/// Parses the value of a flag.
+fn parse<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value `{value}` of `{flag}`"))
+}
@@ -0,0 +1,365 @@
+use std::{fmt::Display, str::FromStr};
+
+use log::{warn, LevelFilter};
+use worker::Env;
+
+use crate::{
+    auth::cf_access::AccessSettings,
+    enums::{deployment_environment::DeploymentEnvironment, storage_mode::StorageMode},
+    errors::validation_errors::ValidationErrors,
+    types::{
+        game::MAX_PLAYERS,
+        game_settings::{GameSettings, MIN_PLAYERS},
+    },
+};
+
+// constants
+/// Default of the minutes after which a player without a status update request is removed.
+pub const DEFAULT_PLAYER_TIMEOUT_MINUTES: i64 = 5;
+
+/// Default of the seconds without a status update request after which the turn of a player is
+/// passed.
+pub const DEFAULT_DISCONNECT_AFTER_SECONDS: i64 = 45;
+
+/// Default of the minutes a session token stays valid after it was issued or refreshed.
+pub const DEFAULT_SESSION_LIFETIME_MINUTES: i64 = 60;
+
+/// Default of the minutes a spectator link stays valid after it was minted.
+pub const DEFAULT_SPECTATOR_LIFETIME_MINUTES: i64 = 30;
+
+/// Default of the minutes a download link of an exported game stays valid.
+pub const DEFAULT_EXPORT_LINK_LIFETIME_MINUTES: i64 = 15;
+
+/// Default of the hours after which a game that still waits for players is abandoned.
+pub const DEFAULT_STALE_LOBBY_HOURS: i64 = 3;
+
+/// Default of the hours after which a game that didn't end is abandoned; `0` turns it off.
+pub const DEFAULT_MAX_GAME_AGE_HOURS: i64 = 24;
+
+/// Default of the minutes a game waits for the next player before it is abandoned; `0` turns it
+/// off.
+pub const DEFAULT_LOBBY_IDLE_MINUTES: i64 = 60;
+
+/// Default of the rounds after which a game is abandoned; `0` turns it off.
+pub const DEFAULT_MAX_ROUNDS: usize = 0;
+
+/// Default of the minutes the host can reclaim a game after it was abandoned.
+pub const DEFAULT_RECLAIM_GRACE_MINUTES: i64 = 15;
+
+/// Default number of messages a player may send within one rate window of the chat.
+pub const DEFAULT_CHAT_MAX_MESSAGES_PER_WINDOW: usize = 5;
+
+/// Default length of the rate window of the chat in seconds.
+pub const DEFAULT_CHAT_RATE_WINDOW_SECONDS: i64 = 10;
+
+/// Default of the minutes a muted player can't write in the chat.
+pub const DEFAULT_MUTE_MINUTES: i64 = 10;
+
+/// Default number of invitation emails a host may send within one hour.
+pub const DEFAULT_INVITES_PER_HOUR: usize = 10;
+
+/// Default of the milliseconds after which a database query is logged as slow.
+pub const DEFAULT_SLOW_QUERY_MS: i64 = 200;
+
+/// Default of the most milliseconds of latency added to a query while the `chaos` feature is on.
+pub const DEFAULT_CHAOS_LATENCY_MS: u64 = 0;
+
+/// Default of the percentage of queries failed on purpose while the `chaos` feature is on.
+pub const DEFAULT_CHAOS_FAILURE_PERCENT: u32 = 0;
+
+/// Default of the most verbose level written to the logs.
+pub const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// Tunables of the worker.
+///
+/// Read from the variables of the worker once per request. A missing or invalid variable falls
+/// back to its default, so a deployment only needs to set what it wants to change.
+///
+/// | Variable                       | Default      | Format                      |
+/// |--------------------------------|--------------|-----------------------------|
+/// | `PLAYER_TIMEOUT_MINUTES`       | 5            | minutes                     |
+/// | `DISCONNECT_AFTER_SECONDS`     | 45           | seconds                     |
+/// | `MAX_PLAYERS`                  | 5            | 2 to 5                      |
+/// | `SESSION_LIFETIME_MINUTES`     | 60           | minutes                     |
+/// | `SPECTATOR_LIFETIME_MINUTES`   | 30           | minutes                     |
+/// | `EXPORT_LINK_LIFETIME_MINUTES` | 15           | minutes                     |
+/// | `STALE_LOBBY_HOURS`            | 3            | hours                       |
+/// | `MAX_GAME_AGE_HOURS`           | 24           | hours, `0` turns it off     |
+/// | `LOBBY_IDLE_MINUTES`           | 60           | minutes, `0` turns it off   |
+/// | `MAX_ROUNDS`                   | 0            | rounds, `0` turns it off    |
+/// | `RECLAIM_GRACE_MINUTES`        | 15           | minutes                     |
+/// | `CHAT_MAX_MESSAGES_PER_WINDOW` | 5            | messages                    |
+/// | `CHAT_RATE_WINDOW_SECONDS`     | 10           | seconds                     |
+/// | `MUTE_MINUTES`                 | 10           | minutes                     |
+/// | `CORS_ALLOWED_ORIGINS`         | *none*       | comma-separated, or `*`     |
+/// | `FRONTEND_URL`                 | *none*       | base URL of deep links      |
+/// | `INVITE_SENDER_EMAIL`          | *none*       | email address               |
+/// | `INVITES_PER_HOUR`             | 10           | emails per host             |
+/// | `GAME_STORAGE_MODE`            | `d1`         | `d1` or `durable-object`    |
+/// | `ENVIRONMENT`                  | `production` | see `DeploymentEnvironment` |
+/// | `CF_ACCESS_TEAM_DOMAIN`        | *none*       | `team.cloudflareaccess.com` |
+/// | `CF_ACCESS_AUD`                | *none*       | application audience tag    |
+/// | `LOG_LEVEL`                    | `info`       | `off`, `error` to `trace`   |
+/// | `SLOW_QUERY_MS`                | 200          | milliseconds                |
+/// | `CHAOS_LATENCY_MS`             | 0            | milliseconds                |
+/// | `CHAOS_FAILURE_PERCENT`        | 0            | 0 to 100                    |
+///
+/// # Props
+///
+/// - `player_timeout_minutes` -> Minutes after which a player without a status update is removed
+/// - `disconnect_after_seconds` -> Seconds after which the turn of a player without a status
+///   update is passed
+/// - `max_players` -> Highest number of players a host may allow in a game
+/// - `session_lifetime_minutes` -> Minutes a session token stays valid
+/// - `spectator_lifetime_minutes` -> Minutes a spectator link stays valid
+/// - `export_link_lifetime_minutes` -> Minutes a download link of an export stays valid
+/// - `stale_lobby_hours` -> Hours after which a game waiting for players is abandoned
+/// - `max_game_age_hours` -> Hours after which a game that didn't end is abandoned
+/// - `lobby_idle_minutes` -> Minutes a game waits for the next player before it is abandoned
+/// - `max_rounds` -> Rounds after which a game is abandoned
+/// - `reclaim_grace_minutes` -> Minutes the host can reclaim a game after it was abandoned
+/// - `chat_max_messages_per_window` -> Messages a player may send within one rate window
+/// - `chat_rate_window_seconds` -> Length of the rate window of the chat
+/// - `mute_minutes` -> Minutes a muted player can't write in the chat
+/// - `cors_allowed_origins` -> Origins of the frontends allowed to call the worker
+/// - `frontend_url` -> Base URL of the frontend the deep links point to
+/// - `invite_sender_email` -> Address the invitation emails are sent from
+/// - `invites_per_hour` -> Invitation emails a host may send within one hour
+/// - `storage_mode` -> Where the live state of the games is kept
+/// - `environment` -> Environment the worker is deployed to, selects the default feature flags
+/// - `cf_access` -> Access application protecting the admin endpoints; both variables are needed
+/// - `log_level` -> Most verbose level written to the logs
+/// - `slow_query_ms` -> Milliseconds after which a database query is logged as slow
+/// - `chaos_latency_ms` -> Most milliseconds of latency added to a query by the `chaos` feature
+/// - `chaos_failure_percent` -> Percentage of queries the `chaos` feature fails on purpose
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    /// Minutes after which a player without a status update request is removed
+    pub player_timeout_minutes: i64,
+    /// Seconds after which the turn of a player without a status update request is passed
+    pub disconnect_after_seconds: i64,
+    /// Highest number of players a host may allow in a game
+    pub max_players: usize,
+    /// Minutes a session token stays valid
+    pub session_lifetime_minutes: i64,
+    /// Minutes a spectator link stays valid
+    pub spectator_lifetime_minutes: i64,
+    /// Minutes a download link of an exported game stays valid
+    pub export_link_lifetime_minutes: i64,
+    /// Hours after which a game waiting for players is abandoned
+    pub stale_lobby_hours: i64,
+    /// Hours after which a game that didn't end is abandoned; `0` turns the limit off
+    pub max_game_age_hours: i64,
+    /// Minutes a game waits for the next player before it is abandoned; `0` turns the limit off
+    pub lobby_idle_minutes: i64,
+    /// Rounds after which a game is abandoned; `0` turns the limit off
+    pub max_rounds: usize,
+    /// Minutes the host can reclaim a game after it was abandoned
+    pub reclaim_grace_minutes: i64,
+    /// Messages a player may send within one rate window of the chat
+    pub chat_max_messages_per_window: usize,
+    /// Length of the rate window of the chat in seconds
+    pub chat_rate_window_seconds: i64,
+    /// Minutes a muted player can't write in the chat
+    pub mute_minutes: i64,
+    /// Origins of the frontends allowed to call the worker; `*` allows every origin
+    pub cors_allowed_origins: Vec<String>,
+    /// Base URL of the frontend the deep links point to, without a trailing slash
+    pub frontend_url: Option<String>,
+    /// Address the invitation emails are sent from
+    pub invite_sender_email: Option<String>,
+    /// Invitation emails a host may send within one hour
+    pub invites_per_hour: usize,
+    /// Where the live state of the games is kept
+    pub storage_mode: StorageMode,
+    /// Environment the worker is deployed to
+    pub environment: DeploymentEnvironment,
+    /// Access application protecting the admin endpoints, `None` to use the static admin token
+    pub cf_access: Option<AccessSettings>,
+    /// Most verbose level written to the logs
+    pub log_level: LevelFilter,
+    /// Milliseconds after which a database query is logged as slow
+    pub slow_query_ms: i64,
+    /// Most milliseconds of latency added to a query by the `chaos` feature
+    pub chaos_latency_ms: u64,
+    /// Percentage of queries the `chaos` feature fails on purpose
+    pub chaos_failure_percent: u32,
+}
+
+impl Default for AppConfig {
+    /// Creates the configuration with the default of every tunable.
+    fn default() -> Self {
+        AppConfig {
+            player_timeout_minutes: DEFAULT_PLAYER_TIMEOUT_MINUTES,
+            disconnect_after_seconds: DEFAULT_DISCONNECT_AFTER_SECONDS,
+            max_players: MAX_PLAYERS,
+            session_lifetime_minutes: DEFAULT_SESSION_LIFETIME_MINUTES,
+            spectator_lifetime_minutes: DEFAULT_SPECTATOR_LIFETIME_MINUTES,
+            export_link_lifetime_minutes: DEFAULT_EXPORT_LINK_LIFETIME_MINUTES,
+            stale_lobby_hours: DEFAULT_STALE_LOBBY_HOURS,
+            max_game_age_hours: DEFAULT_MAX_GAME_AGE_HOURS,
+            lobby_idle_minutes: DEFAULT_LOBBY_IDLE_MINUTES,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            reclaim_grace_minutes: DEFAULT_RECLAIM_GRACE_MINUTES,
+            chat_max_messages_per_window: DEFAULT_CHAT_MAX_MESSAGES_PER_WINDOW,
+            chat_rate_window_seconds: DEFAULT_CHAT_RATE_WINDOW_SECONDS,
+            mute_minutes: DEFAULT_MUTE_MINUTES,
+            cors_allowed_origins: vec![],
+            frontend_url: None,
+            invite_sender_email: None,
+            invites_per_hour: DEFAULT_INVITES_PER_HOUR,
+            storage_mode: StorageMode::default(),
+            environment: DeploymentEnvironment::default(),
+            cf_access: None,
+            log_level: DEFAULT_LOG_LEVEL,
+            slow_query_ms: DEFAULT_SLOW_QUERY_MS,
+            chaos_latency_ms: DEFAULT_CHAOS_LATENCY_MS,
+            chaos_failure_percent: DEFAULT_CHAOS_FAILURE_PERCENT,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Reads the configuration from the variables of the worker.
+    ///
+    /// # Arguments
+    ///
+    /// - `env` -> Environment of the worker
+    pub fn from_env(env: &Env) -> Self {
+        let defaults = AppConfig::default();
+
+        AppConfig {
+            player_timeout_minutes: read_var(env, "PLAYER_TIMEOUT_MINUTES")
+                .unwrap_or(defaults.player_timeout_minutes),
+            disconnect_after_seconds: read_var(env, "DISCONNECT_AFTER_SECONDS")
+                .unwrap_or(defaults.disconnect_after_seconds),
+            max_players: read_var(env, "MAX_PLAYERS")
+                .unwrap_or(defaults.max_players)
+                .clamp(MIN_PLAYERS, MAX_PLAYERS),
+            session_lifetime_minutes: read_var(env, "SESSION_LIFETIME_MINUTES")
+                .unwrap_or(defaults.session_lifetime_minutes),
+            spectator_lifetime_minutes: read_var(env, "SPECTATOR_LIFETIME_MINUTES")
+                .unwrap_or(defaults.spectator_lifetime_minutes),
+            export_link_lifetime_minutes: read_var(env, "EXPORT_LINK_LIFETIME_MINUTES")
+                .unwrap_or(defaults.export_link_lifetime_minutes),
+            stale_lobby_hours: read_var(env, "STALE_LOBBY_HOURS")
+                .unwrap_or(defaults.stale_lobby_hours),
+            max_game_age_hours: read_var(env, "MAX_GAME_AGE_HOURS")
+                .unwrap_or(defaults.max_game_age_hours),
+            lobby_idle_minutes: read_var(env, "LOBBY_IDLE_MINUTES")
+                .unwrap_or(defaults.lobby_idle_minutes),
+            max_rounds: read_var(env, "MAX_ROUNDS").unwrap_or(defaults.max_rounds),
+            reclaim_grace_minutes: read_var(env, "RECLAIM_GRACE_MINUTES")
+                .unwrap_or(defaults.reclaim_grace_minutes),
+            chat_max_messages_per_window: read_var(env, "CHAT_MAX_MESSAGES_PER_WINDOW")
+                .unwrap_or(defaults.chat_max_messages_per_window),
+            chat_rate_window_seconds: read_var(env, "CHAT_RATE_WINDOW_SECONDS")
+                .unwrap_or(defaults.chat_rate_window_seconds),
+            mute_minutes: read_var(env, "MUTE_MINUTES").unwrap_or(defaults.mute_minutes),
+            cors_allowed_origins: read_var::<String>(env, "CORS_ALLOWED_ORIGINS")
+                .map(|origins| {
+                    origins
+                        .split(',')
+                        .map(|origin| origin.trim().trim_end_matches('/').to_string())
+                        .filter(|origin| !origin.is_empty())
+                        .collect()
+                })
+                .unwrap_or(defaults.cors_allowed_origins),
+            frontend_url: read_var::<String>(env, "FRONTEND_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty())
+                .or(defaults.frontend_url),
+            invite_sender_email: read_var::<String>(env, "INVITE_SENDER_EMAIL")
+                .filter(|email| !email.is_empty())
+                .or(defaults.invite_sender_email),
+            invites_per_hour: read_var(env, "INVITES_PER_HOUR")
+                .unwrap_or(defaults.invites_per_hour),
+            storage_mode: StorageMode::from_env(env),
+            environment: read_var(env, "ENVIRONMENT").unwrap_or(defaults.environment),
+            cf_access: match (
+                read_var::<String>(env, "CF_ACCESS_TEAM_DOMAIN"),
+                read_var::<String>(env, "CF_ACCESS_AUD"),
+            ) {
+                (Some(team_domain), Some(audience))
+                    if !team_domain.is_empty() && !audience.is_empty() =>
+                {
+                    Some(AccessSettings {
+                        team_domain: team_domain
+                            .trim_start_matches("https://")
+                            .trim_end_matches('/')
+                            .to_string(),
+                        audience,
+                    })
+                }
+                _ => defaults.cf_access,
+            },
+            log_level: read_var(env, "LOG_LEVEL").unwrap_or(defaults.log_level),
+            slow_query_ms: read_var(env, "SLOW_QUERY_MS").unwrap_or(defaults.slow_query_ms),
+            chaos_latency_ms: read_var(env, "CHAOS_LATENCY_MS")
+                .unwrap_or(defaults.chaos_latency_ms),
+            chaos_failure_percent: read_var(env, "CHAOS_FAILURE_PERCENT")
+                .unwrap_or(defaults.chaos_failure_percent)
+                .min(100),
+        }
+    }
+
+    /// Returns the settings of a game whose host didn't choose any.
+    pub fn default_settings(&self) -> GameSettings {
+        GameSettings {
+            max_players: self.max_players,
+            ..Default::default()
+        }
+    }
+
+    /// Checks the settings chosen by a host against the limits of the deployment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationErrors` if the host allows more players than `max_players`.
+    pub fn validate_settings(&self, settings: &GameSettings) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if settings.max_players > self.max_players {
+            errors.add(
+                "max_players",
+                "out_of_range",
+                &format!(
+                    "The maximum number of players must not exceed {}!",
+                    self.max_players
+                ),
+            );
+        }
+
+        errors.into_result()
+    }
+
+    /// Checks whether a frontend with the passed origin may call the worker.
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        let origin = origin.trim_end_matches('/');
+
+        self.cors_allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed == origin)
+    }
+}
+
+/// Reads and parses a variable of the worker.
+///
+/// # Returns
+///
+/// `None` if the variable is missing or can't be parsed; an invalid value is logged.
+fn read_var<T>(env: &Env, name: &str) -> Option<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = env.var(name).ok()?.to_string();
+
+    match value.trim().parse::<T>() {
+        Ok(parsed) => Some(parsed),
+        Err(err) => {
+            warn!("Variable {name} has the invalid value '{value}': {err}");
+            None
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use worker::Env;
+
+use crate::{
+    enums::{deployment_environment::DeploymentEnvironment, feature::Feature},
+    errors::error_response::ErrorResponseBody,
+};
+
+// constants
+/// Name of the KV namespace holding the feature flags of every environment.
+pub const FEATURE_FLAGS_KV_BINDING: &str = "FEATURE_FLAGS";
+
+/// Switches of the experimental subsystems.
+///
+/// Hydrated in three layers, each one overriding the previous:
+///
+/// 1. Defaults of the environment: everything is on in development, off in staging and
+///    production
+/// 2. `FEATURE_*` variables of the worker, e.g. `FEATURE_BOTS = "true"`
+/// 3. JSON object in the `FEATURE_FLAGS` KV namespace under the name of the environment, e.g.
+///    `{ "bots": true }`, so a feature can be switched without a deployment
+///
+/// # Props
+///
+/// - `environment` -> Environment the worker is deployed to
+/// - `flags` -> Whether each feature is enabled
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureFlags {
+    /// Environment the worker is deployed to
+    pub environment: DeploymentEnvironment,
+    /// Whether each feature is enabled, keyed by its name
+    pub flags: BTreeMap<String, bool>,
+}
+
+impl FeatureFlags {
+    /// Creates the defaults of an environment.
+    ///
+    /// # Arguments
+    ///
+    /// - `environment` -> Environment the worker is deployed to
+    pub fn defaults(environment: DeploymentEnvironment) -> Self {
+        let enabled = environment == DeploymentEnvironment::Development;
+
+        FeatureFlags {
+            environment,
+            flags: Feature::ALL
+                .iter()
+                .map(|feature| (feature.as_str().to_string(), enabled))
+                .collect(),
+        }
+    }
+
+    /// Loads the feature flags from the variables and the KV namespace of the worker.
+    ///
+    /// A missing namespace or an unreadable entry is only logged; the flags then come from the
+    /// variables alone.
+    ///
+    /// # Arguments
+    ///
+    /// - `env` -> Environment of the worker
+    /// - `environment` -> Environment the worker is deployed to, see `AppConfig`
+    pub async fn load(env: &Env, environment: DeploymentEnvironment) -> Self {
+        let mut feature_flags = FeatureFlags::defaults(environment);
+
+        for feature in Feature::ALL {
+            let Ok(value) = env.var(&feature.variable_name()) else {
+                continue;
+            };
+            match parse_switch(&value.to_string()) {
+                Some(enabled) => feature_flags.set(feature, enabled),
+                None => warn!("Variable {} is neither on nor off", feature.variable_name()),
+            }
+        }
+
+        let store = match env.kv(FEATURE_FLAGS_KV_BINDING) {
+            Ok(store) => store,
+            Err(err) => {
+                warn!("Feature flag namespace not found: {err}");
+                return feature_flags;
+            }
+        };
+        let overrides = store
+            .get(environment.as_str())
+            .json::<BTreeMap<String, bool>>()
+            .await;
+
+        match overrides {
+            Ok(Some(overrides)) => {
+                for (name, enabled) in overrides {
+                    match name.parse::<Feature>() {
+                        Ok(feature) => feature_flags.set(feature, enabled),
+                        Err(_) => warn!("Unknown feature '{name}' in the feature flags"),
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(err) => warn!("Feature flags couldn't be read: {err}"),
+        }
+
+        feature_flags
+    }
+
+    /// Returns `true` if the feature is switched on.
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.flags.get(feature.as_str()).copied().unwrap_or(false)
+    }
+
+    /// Makes sure that a feature is switched on before its endpoint does anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `404` response if the feature is switched off, as if the endpoint didn't exist.
+    pub fn ensure_enabled(&self, feature: Feature) -> Result<(), Response> {
+        if self.is_enabled(feature) {
+            return Ok(());
+        }
+
+        let body: ErrorResponseBody<()> =
+            ErrorResponseBody::new(format!("The feature '{feature}' isn't available!"), None);
+        Err((StatusCode::NOT_FOUND, Json(body)).into_response())
+    }
+
+    // ----- utility functions of the 'FeatureFlags' struct -----
+
+    /// Switches a feature on or off.
+    fn set(&mut self, feature: Feature, enabled: bool) {
+        self.flags.insert(feature.as_str().to_string(), enabled);
+    }
+}
+
+impl IntoResponse for FeatureFlags {
+    /// Converts the `FeatureFlags` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Parses the value of a `FEATURE_*` variable.
+///
+/// # Returns
+///
+/// `None` if the value is neither on (`true`, `1`, `on`) nor off (`false`, `0`, `off`).
+fn parse_switch(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "on" => Some(true),
+        "false" | "0" | "off" => Some(false),
+        _ => None,
+    }
+}
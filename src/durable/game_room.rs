@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use worker::*;
+
+use crate::{
+    enums::game_state::GameState,
+    types::game::{Game, UpdateGameDTO},
+};
+
+// constants
+/// Name of the Durable Object binding of the game rooms.
+pub const GAME_ROOM_BINDING: &str = "GAME_ROOM";
+
+/// Key of the snapshot inside the storage of a room.
+const SNAPSHOT_KEY: &str = "snapshot";
+
+/// Live state of a game held by its room.
+///
+/// # Props
+///
+/// - `revision` -> Incremented with every applied update
+/// - `game` -> Columns of the `games` table; players, claims and the chat aren't included
+#[derive(Deserialize, Serialize)]
+pub struct RoomSnapshot {
+    /// Incremented with every applied update
+    pub revision: u64,
+    /// Columns of the `games` table
+    pub game: Game,
+}
+
+impl RoomSnapshot {
+    /// Returns the game carrying the revision of the snapshot.
+    pub fn into_game(self) -> Game {
+        let mut game = self.game;
+        game.revision = self.revision;
+        game
+    }
+}
+
+/// Durable Object holding the authoritative state of one game.
+///
+/// There is exactly one room per game id. A room handles one request after another and reads and
+/// writes its snapshot without awaiting anything else in between, so two simultaneous moves can't
+/// overwrite each other: the second update is based on an outdated revision and gets rejected.
+///
+/// Internal routes:
+///
+/// - `GET /state` -> Current snapshot, `404` if the room is empty
+/// - `POST /seed` -> Stores the passed snapshot if the room is empty and returns the snapshot
+/// - `POST /apply` -> Applies an `UpdateGameDTO`, `409` if its `expectedRevision` is outdated
+/// - `DELETE /state` -> Empties the room once the game is archived in D1
+#[durable_object]
+pub struct GameRoom {
+    /// State of the Durable Object giving access to its storage
+    state: State,
+}
+
+impl DurableObject for GameRoom {
+    fn new(state: State, _env: Env) -> Self {
+        GameRoom { state }
+    }
+
+    async fn fetch(&self, mut req: Request) -> Result<Response> {
+        let storage = self.state.storage();
+        let path = req.path();
+
+        match (req.method(), path.as_str()) {
+            (Method::Get, "/state") => match storage.get::<RoomSnapshot>(SNAPSHOT_KEY).await? {
+                Some(snapshot) => Response::from_json(&snapshot),
+                None => Response::error("The room holds no game", 404),
+            },
+            (Method::Post, "/seed") => {
+                let seed = req.json::<RoomSnapshot>().await?;
+
+                let snapshot = match storage.get::<RoomSnapshot>(SNAPSHOT_KEY).await? {
+                    Some(snapshot) => snapshot,
+                    None => {
+                        storage.put(SNAPSHOT_KEY, &seed).await?;
+                        seed
+                    }
+                };
+
+                Response::from_json(&snapshot)
+            }
+            (Method::Post, "/apply") => {
+                let update = req.json::<UpdateGameDTO>().await?;
+
+                let Some(mut snapshot) = storage.get::<RoomSnapshot>(SNAPSHOT_KEY).await? else {
+                    return Response::error("The room holds no game", 404);
+                };
+                if update
+                    .expected_revision
+                    .is_some_and(|expected| expected != snapshot.revision)
+                {
+                    return Response::error("The game changed in the meantime", 409);
+                }
+
+                snapshot.game.apply_update(&update);
+                snapshot.revision += 1;
+                storage.put(SNAPSHOT_KEY, &snapshot).await?;
+
+                Response::from_json(&snapshot)
+            }
+            (Method::Delete, "/state") => {
+                storage.delete_all().await?;
+                Ok(Response::empty()?.with_status(204))
+            }
+            _ => Response::error("Not found", 404),
+        }
+    }
+}
+
+/// `true` if a game in this state is archived in D1 and doesn't need a room anymore.
+pub fn is_archived_state(state: &GameState) -> bool {
+    matches!(state, GameState::Ended | GameState::Abandoned)
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use worker::*;
+
+use crate::{
+    types::matchmaking::{
+        match_size, MatchAssignment, QueueTicket, TicketStatus, ASSIGNMENT_LIFETIME_MINUTES,
+    },
+    utils::time::server_time,
+};
+
+// constants
+/// Name of the Durable Object binding of the matchmaking queue.
+pub const MATCHMAKING_QUEUE_BINDING: &str = "MATCHMAKING_QUEUE";
+
+/// Name of the single matchmaking queue every worker talks to.
+pub const MATCHMAKING_QUEUE_NAME: &str = "public";
+
+/// Key of the waiting tickets inside the storage of the queue.
+const TICKETS_KEY: &str = "tickets";
+
+/// Key of the assigned tickets inside the storage of the queue.
+const ASSIGNMENTS_KEY: &str = "assignments";
+
+/// Games assembled for tickets, keyed by the id of the ticket.
+#[derive(Deserialize, Serialize, Default)]
+pub struct AssignedTickets {
+    /// Assignment of every ticket that was matched recently
+    pub assignments: HashMap<String, MatchAssignment>,
+}
+
+/// Durable Object holding the public matchmaking queue.
+///
+/// There is exactly one queue. It handles one request after another, so two workers can't take
+/// the same tickets. The queue only decides who plays together; the worker that took the tickets
+/// creates the game and reports the assignments back.
+///
+/// Internal routes:
+///
+/// - `POST /enter` -> Adds a `QueueTicket`, replacing a waiting ticket of the same client
+/// - `GET /ticket/{id}` -> `TicketStatus` of a ticket, `404` if it's unknown
+/// - `DELETE /ticket/{id}` -> Removes a ticket from the queue
+/// - `POST /take` -> Removes and returns the tickets of the next game, empty if none is due
+/// - `POST /assign` -> Stores the `MatchAssignment`s of the tickets of an assembled game
+#[durable_object]
+pub struct MatchmakingQueue {
+    /// State of the Durable Object giving access to its storage
+    state: State,
+}
+
+impl DurableObject for MatchmakingQueue {
+    fn new(state: State, _env: Env) -> Self {
+        MatchmakingQueue { state }
+    }
+
+    async fn fetch(&self, mut req: Request) -> Result<Response> {
+        let storage = self.state.storage();
+        let path = req.path();
+        // a missing key is an error of the storage, it simply means nobody waits yet
+        let mut tickets = storage
+            .get::<Vec<QueueTicket>>(TICKETS_KEY)
+            .await
+            .unwrap_or_default();
+
+        match (req.method(), path.as_str()) {
+            (Method::Post, "/enter") => {
+                let ticket = req.json::<QueueTicket>().await?;
+
+                if ticket.fingerprint.is_some() {
+                    tickets.retain(|waiting| waiting.fingerprint != ticket.fingerprint);
+                }
+                let ticket_id = ticket.id.clone();
+                tickets.push(ticket);
+                storage.put(TICKETS_KEY, &tickets).await?;
+
+                Response::from_json(&ticket_status(&tickets, &ticket_id, None))
+            }
+            (Method::Get, path) if path.starts_with("/ticket/") => {
+                let ticket_id = &path["/ticket/".len()..];
+                let assignments = get_assignments(&storage).await;
+                let assignment = assignments.assignments.get(ticket_id).cloned();
+
+                if assignment.is_none() && !tickets.iter().any(|ticket| ticket.id == ticket_id) {
+                    return Response::error("The ticket isn't in the queue", 404);
+                }
+                Response::from_json(&ticket_status(&tickets, ticket_id, assignment))
+            }
+            (Method::Delete, path) if path.starts_with("/ticket/") => {
+                let ticket_id = &path["/ticket/".len()..];
+                let mut assignments = get_assignments(&storage).await;
+
+                tickets.retain(|ticket| ticket.id != ticket_id);
+                assignments.assignments.remove(ticket_id);
+                storage.put(TICKETS_KEY, &tickets).await?;
+                storage.put(ASSIGNMENTS_KEY, &assignments).await?;
+
+                Ok(Response::empty()?.with_status(204))
+            }
+            (Method::Post, "/take") => {
+                let size = match_size(&tickets, server_time());
+                let taken: Vec<QueueTicket> = tickets.drain(..size).collect();
+                if !taken.is_empty() {
+                    storage.put(TICKETS_KEY, &tickets).await?;
+                }
+
+                Response::from_json(&taken)
+            }
+            (Method::Post, "/assign") => {
+                let new_assignments = req.json::<HashMap<String, MatchAssignment>>().await?;
+                let mut assignments = get_assignments(&storage).await;
+                let now = server_time();
+
+                assignments.assignments.retain(|_, assignment| {
+                    now - assignment.assigned_at < Duration::minutes(ASSIGNMENT_LIFETIME_MINUTES)
+                });
+                assignments.assignments.extend(new_assignments);
+                storage.put(ASSIGNMENTS_KEY, &assignments).await?;
+
+                Ok(Response::empty()?.with_status(204))
+            }
+            _ => Response::error("Not found", 404),
+        }
+    }
+}
+
+/// Reads the assigned tickets; none if nothing was assigned yet.
+async fn get_assignments(storage: &Storage) -> AssignedTickets {
+    storage
+        .get::<AssignedTickets>(ASSIGNMENTS_KEY)
+        .await
+        .unwrap_or_default()
+}
+
+/// Creates the status of a ticket from the waiting tickets.
+fn ticket_status(
+    tickets: &[QueueTicket],
+    ticket_id: &str,
+    assignment: Option<MatchAssignment>,
+) -> TicketStatus {
+    TicketStatus {
+        ticket_id: ticket_id.to_string(),
+        position: tickets
+            .iter()
+            .position(|ticket| ticket.id == ticket_id)
+            .map(|index| index + 1),
+        waiting: tickets.len(),
+        assignment,
+    }
+}
@@ -0,0 +1,3 @@
+pub mod game_room;
+pub mod matchmaking_queue;
+pub mod typing_indicator;
@@ -0,0 +1,65 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+use worker::*;
+
+use crate::{types::game_event::PLAYER_TYPING_TTL_SECONDS, utils::time::server_time};
+
+// constants
+/// Name of the Durable Object binding of the typing indicators.
+pub const TYPING_INDICATOR_BINDING: &str = "TYPING_INDICATOR";
+
+/// Seconds within which repeated typing notifications of a player only count once.
+pub const TYPING_DEBOUNCE_SECONDS: i64 = 3;
+
+/// Durable Object knowing who types in the chat of one game.
+///
+/// There is exactly one per game id. The typing players are only held in memory and never
+/// written to the storage or the event log; if the object is evicted, the indicators are gone,
+/// which is fine for a state that lasts a few seconds.
+///
+/// Internal routes:
+///
+/// - `POST /typing/{player_id}` -> Marks the player as typing, unless the last mark is younger
+///   than `TYPING_DEBOUNCE_SECONDS`
+/// - `GET /typing` -> Ids of the players marked within the last `PLAYER_TYPING_TTL_SECONDS`
+#[durable_object]
+pub struct TypingIndicator {
+    /// Point in time every player was last marked as typing
+    typing: RefCell<HashMap<String, DateTime<Utc>>>,
+}
+
+impl DurableObject for TypingIndicator {
+    fn new(_state: State, _env: Env) -> Self {
+        TypingIndicator {
+            typing: RefCell::new(HashMap::new()),
+        }
+    }
+
+    async fn fetch(&self, req: Request) -> Result<Response> {
+        let path = req.path();
+        let now = server_time();
+        let mut typing = self.typing.borrow_mut();
+        typing
+            .retain(|_, marked_at| now - *marked_at < Duration::seconds(PLAYER_TYPING_TTL_SECONDS));
+
+        match (req.method(), path.as_str()) {
+            (Method::Post, path) if path.starts_with("/typing/") => {
+                let player_id = &path["/typing/".len()..];
+                let is_debounced = typing.get(player_id).is_some_and(|marked_at| {
+                    now - *marked_at < Duration::seconds(TYPING_DEBOUNCE_SECONDS)
+                });
+                if !is_debounced {
+                    typing.insert(player_id.to_string(), now);
+                }
+
+                Ok(Response::empty()?.with_status(204))
+            }
+            (Method::Get, "/typing") => {
+                let player_ids: Vec<&String> = typing.keys().collect();
+                Response::from_json(&player_ids)
+            }
+            _ => Response::error("Not found", 404),
+        }
+    }
+}
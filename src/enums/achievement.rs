@@ -0,0 +1,90 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Milestone a player can unlock once, across all of their games.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::achievement::Achievement;
+/// let achievement = Achievement::FirstWin;
+/// println!("{}", achievement.title());
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Achievement {
+    /// Won a game for the first time.
+    FirstWin = 0,
+    /// Got away with 10 bluffs in total.
+    TenSuccessfulBluffs = 1,
+    /// Uncovered 10 bluffs in total.
+    TenSuccessfulChallenges = 2,
+    /// Won the challenge ending a round in which every claim of the player was truthful.
+    FlawlessRound = 3,
+    /// Finished 25 games.
+    Veteran = 4,
+}
+
+impl_column_conversion!(Achievement {
+    FirstWin,
+    TenSuccessfulBluffs,
+    TenSuccessfulChallenges,
+    FlawlessRound,
+    Veteran
+});
+
+impl Achievement {
+    /// Every achievement in the order they are listed to the players.
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstWin,
+        Achievement::TenSuccessfulBluffs,
+        Achievement::TenSuccessfulChallenges,
+        Achievement::FlawlessRound,
+        Achievement::Veteran,
+    ];
+
+    /// Returns a string representation of the achievement, which is also its value in the
+    /// database.
+    ///
+    /// # Returns
+    /// A string slice representing the achievement.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Achievement::FirstWin => "first-win",
+            Achievement::TenSuccessfulBluffs => "ten-successful-bluffs",
+            Achievement::TenSuccessfulChallenges => "ten-successful-challenges",
+            Achievement::FlawlessRound => "flawless-round",
+            Achievement::Veteran => "veteran",
+        }
+    }
+
+    /// Returns the name of the achievement shown to the players.
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::FirstWin => "First win",
+            Achievement::TenSuccessfulBluffs => "Poker face",
+            Achievement::TenSuccessfulChallenges => "Lie detector",
+            Achievement::FlawlessRound => "Flawless round",
+            Achievement::Veteran => "Veteran",
+        }
+    }
+
+    /// Returns what a player has to do to unlock the achievement.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::FirstWin => "Win a game.",
+            Achievement::TenSuccessfulBluffs => "Get away with 10 bluffs.",
+            Achievement::TenSuccessfulChallenges => "Uncover 10 bluffs by challenging them.",
+            Achievement::FlawlessRound => {
+                "Make at least two claims in a round, all of them truthful, and win the challenge."
+            }
+            Achievement::Veteran => "Finish 25 games.",
+        }
+    }
+}
+
+impl fmt::Display for Achievement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Administrative or destructive action recorded in the audit log.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::audit_action::AuditAction;
+/// let action = AuditAction::ForceEndGame;
+/// println!("{}", action);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditAction {
+    /// An operator ended a game regardless of its host.
+    ForceEndGame = 0,
+    /// A host kicked a player out of their game.
+    KickPlayer = 1,
+    /// An operator added a client to the ban list.
+    AddBan = 2,
+    /// An operator removed a client from the ban list.
+    RemoveBan = 3,
+    /// An operator ran a data migration.
+    RunMigration = 4,
+    /// An operator enabled or disabled the maintenance mode.
+    SetMaintenance = 5,
+    /// An operator imported games from a bulk export.
+    ImportGames = 6,
+    /// An operator created a tournament.
+    CreateTournament = 7,
+    /// An operator changed the settings of a tournament.
+    UpdateTournament = 8,
+    /// An operator started a tournament.
+    StartTournament = 9,
+    /// An operator called a tournament off.
+    CancelTournament = 10,
+    /// An operator ended the current season and started the next one.
+    RollOverSeason = 11,
+    /// An operator looked at a game including the hands of all players.
+    InspectGame = 12,
+    /// A host or an operator muted a player in the chat.
+    MutePlayer = 13,
+    /// A host put their running game back to its first round.
+    ResetGame = 14,
+}
+
+impl_column_conversion!(AuditAction {
+    ForceEndGame,
+    KickPlayer,
+    AddBan,
+    RemoveBan,
+    RunMigration,
+    SetMaintenance,
+    ImportGames,
+    CreateTournament,
+    UpdateTournament,
+    StartTournament,
+    CancelTournament,
+    RollOverSeason,
+    InspectGame,
+    MutePlayer,
+    ResetGame
+});
+
+impl AuditAction {
+    /// Returns a string representation of the action, which is also its value in the database.
+    ///
+    /// # Returns
+    /// A string slice representing the action.
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuditAction::ForceEndGame => "force-end-game",
+            AuditAction::KickPlayer => "kick-player",
+            AuditAction::AddBan => "add-ban",
+            AuditAction::RemoveBan => "remove-ban",
+            AuditAction::RunMigration => "run-migration",
+            AuditAction::SetMaintenance => "set-maintenance",
+            AuditAction::ImportGames => "import-games",
+            AuditAction::CreateTournament => "create-tournament",
+            AuditAction::UpdateTournament => "update-tournament",
+            AuditAction::StartTournament => "start-tournament",
+            AuditAction::CancelTournament => "cancel-tournament",
+            AuditAction::RollOverSeason => "roll-over-season",
+            AuditAction::InspectGame => "inspect-game",
+            AuditAction::MutePlayer => "mute-player",
+            AuditAction::ResetGame => "reset-game",
+        }
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
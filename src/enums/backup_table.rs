@@ -0,0 +1,123 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// constants
+/// Subquery selecting the ids of one page of games; `?1` is the last id of the previous page,
+/// `?2` the page size.
+const GAME_PAGE: &str = "SELECT id FROM games WHERE (?1 IS NULL OR id > ?1) ORDER BY id LIMIT ?2";
+
+/// Table whose rows are part of a bulk export of games.
+///
+/// Webhooks and reports aren't exported: a webhook moved to another environment would notify a
+/// production receiver, and reports belong to the moderation of one environment.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::backup_table::BackupTable;
+/// let table = BackupTable::ChatMessages;
+/// println!("{}", table);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupTable {
+    /// Rows of the `games` table.
+    Games,
+    /// Rows of the `players` table.
+    Players,
+    /// Rows of the `chats` table.
+    Chats,
+    /// Rows of the `claims` table.
+    Claims,
+    /// Rows of the `cards` table.
+    Cards,
+    /// Rows of the `chat_messages` table.
+    ChatMessages,
+    /// Rows of the `game_events` table.
+    GameEvents,
+    /// Rows of the `game_results` table.
+    GameResults,
+    /// Rows of the `invitations` table.
+    Invitations,
+    /// Rows of the `score_history` table.
+    ScoreHistory,
+}
+
+impl BackupTable {
+    /// All exported tables; every table only references tables listed before it, so the rows
+    /// can be imported in this order without violating a foreign key.
+    pub const ALL: [BackupTable; 10] = [
+        BackupTable::Games,
+        BackupTable::Players,
+        BackupTable::Chats,
+        BackupTable::Claims,
+        BackupTable::Cards,
+        BackupTable::ChatMessages,
+        BackupTable::GameEvents,
+        BackupTable::GameResults,
+        BackupTable::Invitations,
+        BackupTable::ScoreHistory,
+    ];
+
+    /// Returns a string representation of the table, which is also its name in the database.
+    ///
+    /// # Returns
+    /// A string slice representing the table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackupTable::Games => "games",
+            BackupTable::Players => "players",
+            BackupTable::Chats => "chats",
+            BackupTable::Claims => "claims",
+            BackupTable::Cards => "cards",
+            BackupTable::ChatMessages => "chat_messages",
+            BackupTable::GameEvents => "game_events",
+            BackupTable::GameResults => "game_results",
+            BackupTable::Invitations => "invitations",
+            BackupTable::ScoreHistory => "score_history",
+        }
+    }
+
+    /// Returns the query selecting the rows of the table that belong to one page of games.
+    ///
+    /// Binds the last game id of the previous page as `?1` and the page size as `?2`.
+    pub fn export_query(&self) -> String {
+        let filter = match self {
+            BackupTable::Games => format!("id IN ({GAME_PAGE})"),
+            BackupTable::Cards => format!(
+                "player_id IN (SELECT id FROM players WHERE game_id IN ({GAME_PAGE}))
+                    OR claim_id IN (SELECT id FROM claims WHERE game_id IN ({GAME_PAGE}))"
+            ),
+            BackupTable::ChatMessages => {
+                format!("chat_id IN (SELECT id FROM chats WHERE game_id IN ({GAME_PAGE}))")
+            }
+            _ => format!("game_id IN ({GAME_PAGE})"),
+        };
+        let order = match self {
+            BackupTable::GameEvents => "sequence",
+            _ => "rowid",
+        };
+
+        format!(
+            "SELECT * FROM {} WHERE {filter} ORDER BY {order};",
+            self.as_str()
+        )
+    }
+
+    /// Returns the columns the target database assigns itself on an import.
+    ///
+    /// The sequence of an event is unique across all games, so keeping it could overwrite an
+    /// event of another game in the target environment.
+    pub fn generated_columns(&self) -> &'static [&'static str] {
+        match self {
+            BackupTable::GameEvents => &["sequence"],
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for BackupTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use log::warn;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Card types for a card game.
@@ -10,6 +10,10 @@ use serde::{Deserialize, Serialize};
 /// Each card type is represented by an enum variant, allowing for easy identification and handling
 /// of different card types.
 ///
+/// A variant is stored with its `as_str()` value in the database. The discriminant is the legacy
+/// integer code of older rows. New variants are always
+/// appended so already stored codes stay valid.
+///
 /// # Example usage:
 /// ```rust
 /// use your_crate::card_types::CardType;
@@ -23,22 +27,57 @@ use serde::{Deserialize, Serialize};
 ///     _ => println!("Unknown card type."),
 /// }
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub enum CardType {
     /// King card type.
-    King,
+    King = 0,
     /// Queen card type.
-    Queen,
+    Queen = 1,
     /// Jack card type.
-    Jack,
+    Jack = 2,
     /// Ace card type.
-    Ace,
+    Ace = 3,
     /// Joker card type.
     ///
     /// The Joker can be used as a wild card in the game.
-    Joker,
+    Joker = 4,
+    /// Two card type.
+    Two = 5,
+    /// Three card type.
+    Three = 6,
+    /// Four card type.
+    Four = 7,
+    /// Five card type.
+    Five = 8,
+    /// Six card type.
+    Six = 9,
+    /// Seven card type.
+    Seven = 10,
+    /// Eight card type.
+    Eight = 11,
+    /// Nine card type.
+    Nine = 12,
+    /// Ten card type.
+    Ten = 13,
 }
 
+impl_column_conversion!(CardType {
+    King,
+    Queen,
+    Jack,
+    Ace,
+    Joker,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+});
+
 impl CardType {
     /// Returns a string representation of the card type.
     ///
@@ -51,76 +90,40 @@ impl CardType {
             CardType::Jack => "Jack",
             CardType::Ace => "Ace",
             CardType::Joker => "Joker",
+            CardType::Two => "Two",
+            CardType::Three => "Three",
+            CardType::Four => "Four",
+            CardType::Five => "Five",
+            CardType::Six => "Six",
+            CardType::Seven => "Seven",
+            CardType::Eight => "Eight",
+            CardType::Nine => "Nine",
+            CardType::Ten => "Ten",
         }
     }
 
-    /// Returns the index of the card type.
-    ///
-    /// # Returns
-    ///
-    /// A `usize` representing the index of the card type.
-    ///
-    /// # Index Mapping
-    ///
-    /// - `King` is mapped to index `0`.
-    /// - `Queen` is mapped to index `1`.
-    /// - `Jack` is mapped to index `2`.
-    /// - `Ace` is mapped to index `3`.
-    /// - `Joker` is mapped to index `4`.
-    ///
-    pub fn index(&self) -> usize {
-        match self {
-            CardType::King => 0,
-            CardType::Queen => 1,
-            CardType::Jack => 2,
-            CardType::Ace => 3,
-            CardType::Joker => 4,
-        }
-    }
-
-    /// Simply returns the number of all enum variants of the `CardType` enum as a *usize*.
-    ///
-    /// Needs to be updated if the number of variants is modified!
-    pub fn number_of_values() -> usize {
-        5
-    }
-
-    /// Creates a new instance of `CardType` from a ***usize***.
-    ///
-    /// Makes sure that if an invalid number was provided that calculations still work properly.
-    ///
-    /// Covers all cases!
-    pub fn from_usize(num: usize) -> Self {
-        // make sure a valid number in the prefered range is used
-        let used_num = num % Self::number_of_values();
-
-        return match used_num {
-            0 => CardType::King,
-            1 => CardType::Queen,
-            2 => CardType::Jack,
-            3 => CardType::Ace,
-            4 => CardType::Joker,
-            5_usize.. => {
-                warn!("When creating an instance of 'CardType' a provided was out of range of the allowed scope!");
-
-                CardType::King
-            }
-        };
+    /// Returns all 13 ranks of a standard deck, which excludes the `Joker`.
+    pub fn ranks() -> [CardType; 13] {
+        [
+            CardType::Two,
+            CardType::Three,
+            CardType::Four,
+            CardType::Five,
+            CardType::Six,
+            CardType::Seven,
+            CardType::Eight,
+            CardType::Nine,
+            CardType::Ten,
+            CardType::Jack,
+            CardType::Queen,
+            CardType::King,
+            CardType::Ace,
+        ]
     }
 }
 
 impl fmt::Display for CardType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                CardType::King => "King",
-                CardType::Queen => "Queen",
-                CardType::Jack => "Jack",
-                CardType::Ace => "Ace",
-                CardType::Joker => "Joker",
-            }
-        )
+        write!(f, "{}", self.as_str())
     }
 }
@@ -0,0 +1,43 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Limit of the cleanup run a game can exceed, after which it is abandoned.
+///
+/// Each limit is set in the `AppConfig` of the worker and turned off with `0`.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::cleanup_policy::CleanupPolicy;
+/// let policy = CleanupPolicy::LobbyIdle;
+/// println!("{}", policy);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum CleanupPolicy {
+    /// The game was created more than `max_game_age_hours` ago.
+    MaxGameAge,
+    /// Nobody joined the waiting game for `lobby_idle_minutes`.
+    LobbyIdle,
+    /// The game is past round `max_rounds`.
+    MaxRounds,
+}
+
+impl CleanupPolicy {
+    /// Returns a string representation of the policy.
+    ///
+    /// # Returns
+    /// A string slice representing the policy.
+    pub fn as_str(&self) -> &str {
+        match self {
+            CleanupPolicy::MaxGameAge => "MaxGameAge",
+            CleanupPolicy::LobbyIdle => "LobbyIdle",
+            CleanupPolicy::MaxRounds => "MaxRounds",
+        }
+    }
+}
+
+impl fmt::Display for CleanupPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Data migrations an operator can run on demand.
+///
+/// Schema changes are applied with the D1 migrations. These migrations repair or backfill rows
+/// that were written before a schema change.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::data_migration::DataMigration;
+/// let migration: DataMigration = "backfill-join-codes".parse()?;
+/// println!("{}", migration);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DataMigration {
+    /// Generates join codes for games created before they existed.
+    BackfillJoinCodes = 0,
+    /// Recalculates the number of messages of every chat.
+    RecountChatMessages = 1,
+}
+
+impl_column_conversion!(DataMigration {
+    BackfillJoinCodes,
+    RecountChatMessages
+});
+
+impl DataMigration {
+    /// Returns a string representation of the migration, which is also its URL segment.
+    ///
+    /// # Returns
+    /// A string slice representing the migration.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DataMigration::BackfillJoinCodes => "backfill-join-codes",
+            DataMigration::RecountChatMessages => "recount-chat-messages",
+        }
+    }
+}
+
+impl fmt::Display for DataMigration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
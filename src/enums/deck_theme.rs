@@ -0,0 +1,57 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Skin of the cards, bundled with every client.
+///
+/// The host picks it for the whole table, so all players see the same cards.
+///
+/// A variant is stored with its `as_str()` value in the database.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::deck_theme::DeckTheme;
+/// let theme = DeckTheme::Noir;
+/// println!("{}", theme);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub enum DeckTheme {
+    /// The traditional red and black cards.
+    #[default]
+    Classic = 0,
+    /// Dark cards with silver suits.
+    Noir = 1,
+    /// Soft colors with rounded suits.
+    Pastel = 2,
+    /// Glowing suits on a black background.
+    Neon = 3,
+}
+
+impl_column_conversion!(DeckTheme {
+    Classic,
+    Noir,
+    Pastel,
+    Neon
+});
+
+impl DeckTheme {
+    /// Returns a string representation of the theme.
+    ///
+    /// # Returns
+    /// A string slice representing the theme.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DeckTheme::Classic => "Classic",
+            DeckTheme::Noir => "Noir",
+            DeckTheme::Pastel => "Pastel",
+            DeckTheme::Neon => "Neon",
+        }
+    }
+}
+
+impl fmt::Display for DeckTheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Environment a worker is deployed to.
+///
+/// Selects the default of every feature flag, so the same build can run with experimental
+/// subsystems in development and without them in production.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::deployment_environment::DeploymentEnvironment;
+/// let environment: DeploymentEnvironment = "staging".parse()?;
+/// println!("{}", environment);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeploymentEnvironment {
+    /// Local development with `wrangler dev`.
+    Development = 0,
+    /// Pre-release deployment for testing.
+    Staging = 1,
+    /// Deployment the players use.
+    #[default]
+    Production = 2,
+}
+
+impl_column_conversion!(DeploymentEnvironment {
+    Development,
+    Staging,
+    Production
+});
+
+impl DeploymentEnvironment {
+    /// Returns a string representation of the environment, which is also its variable value.
+    ///
+    /// # Returns
+    /// A string slice representing the environment.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DeploymentEnvironment::Development => "development",
+            DeploymentEnvironment::Staging => "staging",
+            DeploymentEnvironment::Production => "production",
+        }
+    }
+}
+
+impl fmt::Display for DeploymentEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Emotes a player can react with at the table.
+///
+/// Only these emotes are accepted, so reactions can't be abused to send free text.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::emote::Emote;
+/// let emote = Emote::Laugh;
+/// println!("{}", emote);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub enum Emote {
+    /// Approval of a move.
+    ThumbsUp = 0,
+    /// Disapproval of a move.
+    ThumbsDown = 1,
+    /// Laughing about a move.
+    Laugh = 2,
+    /// Surprise, e.g. about a revealed claim.
+    Surprised = 3,
+    /// Doubt about the last claim.
+    Suspicious = 4,
+    /// Applause for a good bluff or challenge.
+    Applause = 5,
+}
+
+impl_column_conversion!(Emote {
+    ThumbsUp,
+    ThumbsDown,
+    Laugh,
+    Surprised,
+    Suspicious,
+    Applause
+});
+
+impl Emote {
+    /// Returns a string representation of the emote.
+    ///
+    /// # Returns
+    /// A string slice representing the emote.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Emote::ThumbsUp => "ThumbsUp",
+            Emote::ThumbsDown => "ThumbsDown",
+            Emote::Laugh => "Laugh",
+            Emote::Surprised => "Surprised",
+            Emote::Suspicious => "Suspicious",
+            Emote::Applause => "Applause",
+        }
+    }
+}
+
+impl fmt::Display for Emote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Experimental subsystem that can be switched on per environment.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::feature::Feature;
+/// let feature: Feature = "new-scoring".parse()?;
+/// println!("{}", feature);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Feature {
+    /// Computer players filling empty seats.
+    Bots = 0,
+    /// Live updates over WebSockets instead of server-sent events.
+    Websockets = 1,
+    /// Revised scoring at the end of a game.
+    NewScoring = 2,
+    /// Artificial latency and failures of the database queries, see `ChaosSettings`.
+    Chaos = 3,
+}
+
+impl_column_conversion!(Feature {
+    Bots,
+    Websockets,
+    NewScoring,
+    Chaos
+});
+
+impl Feature {
+    /// Every feature, in the order they are listed to clients.
+    pub const ALL: [Feature; 4] = [
+        Feature::Bots,
+        Feature::Websockets,
+        Feature::NewScoring,
+        Feature::Chaos,
+    ];
+
+    /// Returns a string representation of the feature.
+    ///
+    /// # Returns
+    /// A string slice representing the feature.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Feature::Bots => "bots",
+            Feature::Websockets => "websockets",
+            Feature::NewScoring => "new-scoring",
+            Feature::Chaos => "chaos",
+        }
+    }
+
+    /// Name of the worker variable switching the feature, e.g. `FEATURE_NEW_SCORING`.
+    pub fn variable_name(&self) -> String {
+        format!("FEATURE_{}", self.as_str().replace('-', "_").to_uppercase())
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,39 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Reaction of the chat filter to a message containing a banned word.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::filter_action::FilterAction;
+/// let action = FilterAction::Reject;
+/// println!("{}", action);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum FilterAction {
+    /// The banned words are masked and the message is sent anyway.
+    #[default]
+    Replace,
+    /// The message is dropped.
+    Reject,
+}
+
+impl FilterAction {
+    /// Returns a string representation of the action.
+    ///
+    /// # Returns
+    /// A string slice representing the action.
+    pub fn as_str(&self) -> &str {
+        match self {
+            FilterAction::Replace => "Replace",
+            FilterAction::Reject => "Reject",
+        }
+    }
+}
+
+impl fmt::Display for FilterAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
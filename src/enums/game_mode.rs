@@ -0,0 +1,47 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// How a game came into being, used to filter the lobby.
+///
+/// - `Casual`: A host created the game for anyone to join.
+/// - `Daily`: The game plays the daily challenge with the deck of the day.
+/// - `Tournament`: The game is a match of a tournament round.
+///
+/// The mode isn't stored; it's derived from the daily date of a game and the tournament matches.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::game_mode::GameMode;
+/// let mode = GameMode::Daily;
+/// println!("{}", mode);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum GameMode {
+    /// Created by a host for anyone to join.
+    Casual,
+    /// Plays the daily challenge.
+    Daily,
+    /// A match of a tournament.
+    Tournament,
+}
+
+impl GameMode {
+    /// Returns a string representation of the mode.
+    ///
+    /// # Returns
+    /// A string slice representing the mode.
+    pub fn as_str(&self) -> &str {
+        match self {
+            GameMode::Casual => "Casual",
+            GameMode::Daily => "Daily",
+            GameMode::Tournament => "Tournament",
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// How a finished game went for one player.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::game_outcome::GameOutcome;
+/// let outcome = GameOutcome::from_result(true, true);
+/// println!("{}", outcome);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GameOutcome {
+    /// The player won the game.
+    Won,
+    /// Another player won the game.
+    Lost,
+    /// The game ended without a winner, e.g. because it was abandoned.
+    Abandoned,
+}
+
+impl GameOutcome {
+    /// Returns the outcome of a result.
+    ///
+    /// # Arguments
+    ///
+    /// - `is_winner` -> Whether the player won the game
+    /// - `has_winner` -> Whether any player of the game won
+    pub fn from_result(is_winner: bool, has_winner: bool) -> Self {
+        match (is_winner, has_winner) {
+            (true, _) => GameOutcome::Won,
+            (false, true) => GameOutcome::Lost,
+            (false, false) => GameOutcome::Abandoned,
+        }
+    }
+
+    /// Returns a string representation of the outcome.
+    ///
+    /// # Returns
+    /// A string slice representing the outcome.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameOutcome::Won => "won",
+            GameOutcome::Lost => "lost",
+            GameOutcome::Abandoned => "abandoned",
+        }
+    }
+}
+
+impl fmt::Display for GameOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display};
 
@@ -10,21 +11,43 @@ use std::fmt::{Debug, Display};
 /// - `Ended`: The game has concluded.
 /// - `WaitingForPlayers`: The game is waiting for players to join.
 /// - `Starting`: The game is in the process of starting, preparing for the first turn.
+/// - `Paused`: The host paused the game; no moves can be made.
+/// - `Abandoned`: Nobody plays the game anymore and it waits for the cleanup.
+/// - `RoundEnding`: A round is over and the challenge window is open before the next round.
 ///
 /// Each variant represents a distinct phase in the lifecycle of a game, allowing for clear
 /// management and transitions between states.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+///
+/// A variant is stored with its `as_str()` value in the database. The discriminant is the legacy
+/// integer code of older rows.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub enum GameState {
     /// The game is currently in progress.
-    InProgress,
+    InProgress = 0,
     /// The game has ended.
-    Ended,
+    Ended = 1,
     /// The game is waiting for players to join.
-    WaitingForPlayers,
+    WaitingForPlayers = 2,
     /// The game is starting, preparing for the first turn.
-    Starting,
+    Starting = 3,
+    /// The game was paused by the host.
+    Paused = 4,
+    /// The game was abandoned and is pending cleanup.
+    Abandoned = 5,
+    /// The round is ending; the brief challenge window between two rounds.
+    RoundEnding = 6,
 }
 
+impl_column_conversion!(GameState {
+    InProgress,
+    Ended,
+    WaitingForPlayers,
+    Starting,
+    Paused,
+    Abandoned,
+    RoundEnding,
+});
+
 impl GameState {
     /// Returns a string representation of the game state.
     ///
@@ -36,35 +59,73 @@ impl GameState {
             GameState::Ended => "Ended",
             GameState::WaitingForPlayers => "Waiting for Players",
             GameState::Starting => "Starting",
+            GameState::Paused => "Paused",
+            GameState::Abandoned => "Abandoned",
+            GameState::RoundEnding => "Round Ending",
         }
     }
 
-    /// Returns the index of the game state.
-    ///
-    /// # Returns
-    /// A `usize` representing the index of the game state.
+    /// Checks whether the lifecycle of a game allows a change from this state to the next one.
     ///
-    /// # Index Mapping
+    /// # Allowed transitions
     ///
-    /// - `InProgress` is mapped to index `0`.
-    /// - `Ended` is mapped to index `1`.
-    /// - `WaitingForPlayers` is mapped to index `2`.
-    /// - `Starting` is mapped to index `3`.
+    /// - `Starting` -> `WaitingForPlayers`, `InProgress`, `Abandoned`
+    /// - `WaitingForPlayers` -> `Starting`, `InProgress`, `Ended`, `Abandoned`
+    /// - `InProgress` -> `RoundEnding`, `Paused`, `Ended`, `Abandoned`
+    /// - `RoundEnding` -> `InProgress`, `Ended`, `Abandoned`
+    /// - `Paused` -> `InProgress`, `Ended`, `Abandoned`
+    /// - `Abandoned` -> `Ended`, or back to the state before if the host reclaims the game
+    /// - `Ended` is final
     ///
-    pub fn index(&self) -> usize {
+    /// Staying in the same state is always allowed.
+    pub fn can_transition_to(&self, next: &GameState) -> bool {
+        if self == next {
+            return true;
+        }
+
         match self {
-            GameState::InProgress => 0,
-            GameState::Ended => 1,
-            GameState::WaitingForPlayers => 2,
-            GameState::Starting => 3,
+            GameState::Starting => matches!(
+                next,
+                GameState::WaitingForPlayers | GameState::InProgress | GameState::Abandoned
+            ),
+            GameState::WaitingForPlayers => matches!(
+                next,
+                GameState::Starting
+                    | GameState::InProgress
+                    | GameState::Ended
+                    | GameState::Abandoned
+            ),
+            GameState::InProgress => matches!(
+                next,
+                GameState::RoundEnding
+                    | GameState::Paused
+                    | GameState::Ended
+                    | GameState::Abandoned
+            ),
+            GameState::RoundEnding => matches!(
+                next,
+                GameState::InProgress | GameState::Ended | GameState::Abandoned
+            ),
+            GameState::Paused => matches!(
+                next,
+                GameState::InProgress | GameState::Ended | GameState::Abandoned
+            ),
+            GameState::Abandoned => matches!(
+                next,
+                GameState::Ended
+                    | GameState::WaitingForPlayers
+                    | GameState::Starting
+                    | GameState::InProgress
+                    | GameState::RoundEnding
+                    | GameState::Paused
+            ),
+            GameState::Ended => false,
         }
     }
 
-    /// Simply returns the number of all enum variants of the `GameState` enum as a *usize*.
-    ///
-    /// Needs to be updated if the number of variants is modified!
-    pub fn number_of_values() -> usize {
-        4
+    /// Returns `true` if players are allowed to make moves in this state.
+    pub fn accepts_moves(&self) -> bool {
+        matches!(self, GameState::InProgress | GameState::RoundEnding)
     }
 }
 
@@ -0,0 +1,49 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Order in which a player wants to see the cards of their hand.
+///
+/// The hand is sorted by the server, so clients don't have to sort it on every update.
+///
+/// A variant is stored with its `as_str()` value in the database.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::hand_sort::HandSort;
+/// let hand_sort = HandSort::Rank;
+/// println!("{}", hand_sort);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub enum HandSort {
+    /// In the order the cards were dealt and picked up.
+    #[default]
+    Dealt = 0,
+    /// By card type from Two to Ace, then by suit; Jokers come last.
+    Rank = 1,
+    /// By suit, then by card type; Jokers come last.
+    Suit = 2,
+}
+
+impl_column_conversion!(HandSort { Dealt, Rank, Suit });
+
+impl HandSort {
+    /// Returns a string representation of the sort order.
+    ///
+    /// # Returns
+    /// A string slice representing the sort order.
+    pub fn as_str(&self) -> &str {
+        match self {
+            HandSort::Dealt => "Dealt",
+            HandSort::Rank => "Rank",
+            HandSort::Suit => "Suit",
+        }
+    }
+}
+
+impl fmt::Display for HandSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Span of time a leaderboard covers.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::leaderboard_period::LeaderboardPeriod;
+/// let period = LeaderboardPeriod::Weekly;
+/// println!("{}", period);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LeaderboardPeriod {
+    /// Games that ended in the current week, starting on Monday 00:00 UTC.
+    Weekly,
+    /// All games ever played.
+    #[default]
+    AllTime,
+    /// Games that ended in a season, bounded by the dates stored in the `seasons` table.
+    Season,
+}
+
+impl LeaderboardPeriod {
+    /// Every period following the calendar, each game counts towards all of them and towards its
+    /// season.
+    pub const ALL: [LeaderboardPeriod; 2] = [LeaderboardPeriod::Weekly, LeaderboardPeriod::AllTime];
+
+    /// Returns a string representation of the period, which is also its value in the database.
+    ///
+    /// # Returns
+    /// A string slice representing the period.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardPeriod::Weekly => "weekly",
+            LeaderboardPeriod::AllTime => "alltime",
+            LeaderboardPeriod::Season => "season",
+        }
+    }
+
+    /// Returns the bounds of the period containing a point in time.
+    ///
+    /// A season doesn't follow the calendar, its bounds come from `Season::bounds`.
+    ///
+    /// # Returns
+    ///
+    /// The inclusive start and the exclusive end; both `None` for `AllTime` and `Season`.
+    pub fn bounds(&self, at: DateTime<Utc>) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        match self {
+            LeaderboardPeriod::Weekly => {
+                let monday = at.date_naive()
+                    - Duration::days(i64::from(at.weekday().num_days_from_monday()));
+                let start = monday.and_time(NaiveTime::MIN).and_utc();
+
+                (Some(start), Some(start + Duration::weeks(1)))
+            }
+            LeaderboardPeriod::AllTime | LeaderboardPeriod::Season => (None, None),
+        }
+    }
+}
+
+impl fmt::Display for LeaderboardPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
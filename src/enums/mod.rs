@@ -1,2 +1,94 @@
+/// Implements the conversions between an enum and the values stored in a D1 column.
+///
+/// Enums are stored with their `as_str()` value, so the database stays human-readable and
+/// doesn't depend on the order of the variants. Older rows can still contain the integer code of a
+/// variant, which is its explicit discriminant.
+///
+/// Because `From` is implemented with an exhaustive `match`, the compiler complains as soon as a
+/// new variant isn't listed here.
+///
+/// `i32` is used instead of `i64`, since a `JsValue` created from an `i64` is a `BigInt`, which
+/// D1 doesn't accept as a binding.
+///
+/// # Example
+///
+/// ```rust
+/// impl_column_conversion!(Suit { Hearts, Diamonds, Clubs, Spades });
+/// let code = i32::from(&Suit::Clubs);
+/// let suit = Suit::try_from(code)?;
+/// let suit: Suit = "Clubs".parse()?;
+/// ```
+macro_rules! impl_column_conversion {
+    ($name:ident { $($variant:ident),+ $(,)? }) => {
+        impl From<&$name> for i32 {
+            fn from(value: &$name) -> i32 {
+                match value {
+                    $($name::$variant => $name::$variant as i32),+
+                }
+            }
+        }
+
+        impl From<$name> for i32 {
+            fn from(value: $name) -> i32 {
+                i32::from(&value)
+            }
+        }
+
+        impl TryFrom<i32> for $name {
+            type Error = crate::errors::enum_conversion_error::EnumConversionError;
+
+            fn try_from(value: i32) -> Result<Self, Self::Error> {
+                $(
+                    if value == $name::$variant as i32 {
+                        return Ok($name::$variant);
+                    }
+                )+
+
+                Err(crate::errors::enum_conversion_error::EnumConversionError::new(
+                    stringify!($name),
+                    value.to_string(),
+                ))
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = crate::errors::enum_conversion_error::EnumConversionError;
+
+            /// Parses the `as_str()` value or the name of a variant.
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                $(
+                    if value == $name::$variant.as_str() || value == stringify!($variant) {
+                        return Ok($name::$variant);
+                    }
+                )+
+
+                Err(crate::errors::enum_conversion_error::EnumConversionError::new(
+                    stringify!($name),
+                    value.to_string(),
+                ))
+            }
+        }
+    };
+}
+
+pub mod achievement;
+pub mod audit_action;
+pub mod backup_table;
 pub mod card_types;
+pub mod cleanup_policy;
+pub mod data_migration;
+pub mod deck_theme;
+pub mod deployment_environment;
+pub mod emote;
+pub mod feature;
+pub mod filter_action;
+pub mod game_mode;
+pub mod game_outcome;
 pub mod game_state;
+pub mod hand_sort;
+pub mod leaderboard_period;
+pub mod storage_mode;
+pub mod suit;
+pub mod tournament_state;
+pub mod visibility;
+pub mod webhook_event;
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use worker::Env;
+
+// constants
+/// Name of the variable selecting the storage mode of the live games.
+pub const STORAGE_MODE_VAR: &str = "GAME_STORAGE_MODE";
+
+/// Where the live state of a game is kept.
+///
+/// - `D1`: Every request reads and writes the `games` table directly.
+/// - `DurableObject`: The `GameRoom` Durable Object of a game holds its authoritative state and
+///   applies the updates one after another; D1 is kept in sync for the lobby and archives the
+///   finished game.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::storage_mode::StorageMode;
+/// let mode: StorageMode = "durable-object".parse()?;
+/// println!("{}", mode);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum StorageMode {
+    /// The games are read from and written to D1 only.
+    #[default]
+    D1 = 0,
+    /// The `GameRoom` Durable Object holds the live state of a game.
+    DurableObject = 1,
+}
+
+impl_column_conversion!(StorageMode { D1, DurableObject });
+
+impl StorageMode {
+    /// Returns a string representation of the storage mode, which is also its variable value.
+    ///
+    /// # Returns
+    /// A string slice representing the storage mode.
+    pub fn as_str(&self) -> &str {
+        match self {
+            StorageMode::D1 => "d1",
+            StorageMode::DurableObject => "durable-object",
+        }
+    }
+
+    /// Reads the storage mode from the `GAME_STORAGE_MODE` variable of the worker.
+    ///
+    /// Falls back to `D1` if the variable is missing or has an unknown value.
+    pub fn from_env(env: &Env) -> Self {
+        env.var(STORAGE_MODE_VAR)
+            .ok()
+            .and_then(|value| value.to_string().parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for StorageMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Suit of a card in a standard deck.
+///
+/// A `Joker` has no suit.
+///
+/// A variant is stored with its `as_str()` value in the database. The discriminant is the legacy
+/// integer code of older rows.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::suit::Suit;
+/// let suit = Suit::Hearts;
+/// println!("{}", suit);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub enum Suit {
+    /// Hearts suit.
+    Hearts = 0,
+    /// Diamonds suit.
+    Diamonds = 1,
+    /// Clubs suit.
+    Clubs = 2,
+    /// Spades suit.
+    Spades = 3,
+}
+
+impl_column_conversion!(Suit {
+    Hearts,
+    Diamonds,
+    Clubs,
+    Spades
+});
+
+impl Suit {
+    /// Returns a string representation of the suit.
+    ///
+    /// # Returns
+    /// A string slice representing the suit.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Suit::Hearts => "Hearts",
+            Suit::Diamonds => "Diamonds",
+            Suit::Clubs => "Clubs",
+            Suit::Spades => "Spades",
+        }
+    }
+
+    /// Returns all suits of a standard deck.
+    pub fn all() -> [Suit; 4] {
+        [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades]
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
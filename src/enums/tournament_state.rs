@@ -0,0 +1,59 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle of a tournament.
+///
+/// - `Registration`: Players can register; the tournament hasn't started yet.
+/// - `Running`: The games of the current round are played.
+/// - `Finished`: The winner of the final game won the tournament.
+/// - `Cancelled`: An operator called the tournament off.
+///
+/// A variant is stored with its `as_str()` value in the database.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::tournament_state::TournamentState;
+/// let state = TournamentState::Running;
+/// println!("{}", state);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum TournamentState {
+    /// Players can register.
+    #[default]
+    Registration = 0,
+    /// The games of the current round are played.
+    Running = 1,
+    /// The tournament has a winner.
+    Finished = 2,
+    /// The tournament was called off.
+    Cancelled = 3,
+}
+
+impl_column_conversion!(TournamentState {
+    Registration,
+    Running,
+    Finished,
+    Cancelled
+});
+
+impl TournamentState {
+    /// Returns a string representation of the state, which is also its value in the database.
+    ///
+    /// # Returns
+    /// A string slice representing the state.
+    pub fn as_str(&self) -> &str {
+        match self {
+            TournamentState::Registration => "Registration",
+            TournamentState::Running => "Running",
+            TournamentState::Finished => "Finished",
+            TournamentState::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl fmt::Display for TournamentState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Visibility of a game in the lobby.
+///
+/// A public game is listed in the lobby, a private game can only be found with its join code.
+///
+/// A variant is stored with its `as_str()` value in the database.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::visibility::Visibility;
+/// let visibility = Visibility::Private;
+/// println!("{}", visibility);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+pub enum Visibility {
+    /// Listed in the public lobby.
+    #[default]
+    Public = 0,
+    /// Only joinable with the join code.
+    Private = 1,
+}
+
+impl_column_conversion!(Visibility { Public, Private });
+
+impl Visibility {
+    /// Returns a string representation of the visibility.
+    ///
+    /// # Returns
+    /// A string slice representing the visibility.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Visibility::Public => "Public",
+            Visibility::Private => "Private",
+        }
+    }
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
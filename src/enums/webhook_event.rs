@@ -0,0 +1,42 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle event of a game that is sent to the registered webhooks.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::webhook_event::WebhookEvent;
+/// let event = WebhookEvent::GameStarted;
+/// println!("{}", event);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// The host started the game.
+    GameStarted,
+    /// The game ended, regularly or forced by an operator.
+    GameEnded,
+    /// The host kicked a player out of the game.
+    PlayerKicked,
+}
+
+impl WebhookEvent {
+    /// Returns a string representation of the event, which is also its name in the payload.
+    ///
+    /// # Returns
+    /// A string slice representing the event.
+    pub fn as_str(&self) -> &str {
+        match self {
+            WebhookEvent::GameStarted => "game_started",
+            WebhookEvent::GameEnded => "game_ended",
+            WebhookEvent::PlayerKicked => "player_kicked",
+        }
+    }
+}
+
+impl fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -12,4 +12,22 @@ pub trait ApplicationError: fmt::Display + error::Error + fmt::Debug {}
 /// Error object trait for data types that should be logged in the console or in the error message.
 ///
 /// In some error types the causing object is inbetted in the error message.
-pub trait ErrorObject<'a>: Deserialize<'a> + fmt::Display + fmt::Debug {}
+///
+/// Every error object needs to be redactable because it could end up in a response body.
+pub trait ErrorObject<'a>: Deserialize<'a> + fmt::Display + fmt::Debug + Redact {}
+
+/// Strips hidden-information fields from a data object.
+///
+/// Error types like `DatabaseQueryError` or `BadClientRequest` echo the causing object back to the
+/// client. Before that happens all data the client isn't allowed to see (e.g. the hands of other
+/// players or face-down cards of a claim) needs to be removed.
+///
+/// The default implementation leaves the object untouched, which is fine for types without any
+/// hidden information.
+pub trait Redact {
+    /// Removes all hidden information from the object in place.
+    fn redact(&mut self) {}
+}
+
+/// Unit type for error responses which don't echo any data object back to the client.
+impl Redact for () {}
@@ -0,0 +1,64 @@
+use std::fmt;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::errors::{application_error::ApplicationError, error_response::ErrorResponseBody};
+
+/// Error for a client who couldn't prove who they are.
+///
+/// Occurs if the session token is missing, malformed, tampered with or expired.
+///
+/// # Props
+///
+/// - `message` -> Description why the client couldn't be authenticated
+pub struct AuthenticationError {
+    /// Description why the client couldn't be authenticated
+    pub message: String,
+}
+
+impl AuthenticationError {
+    /// Resembling http status code for an unauthenticated client
+    pub const STATUS_CODE: StatusCode = StatusCode::UNAUTHORIZED;
+
+    /// Creates a new `AuthenticationError`.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` -> Description why the client couldn't be authenticated
+    pub fn new(message: String) -> Self {
+        AuthenticationError { message }
+    }
+}
+
+impl fmt::Display for AuthenticationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Client couldn't be authenticated! Error: {}",
+            self.message
+        )
+    }
+}
+
+impl fmt::Debug for AuthenticationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AuthenticationError {{ message: {} }}", self.message)
+    }
+}
+
+impl std::error::Error for AuthenticationError {}
+
+impl ApplicationError for AuthenticationError {}
+
+impl IntoResponse for AuthenticationError {
+    /// Converts the error into a `401 Unauthorized` response.
+    fn into_response(self) -> Response {
+        let body: ErrorResponseBody<()> = ErrorResponseBody::new(self.message, None);
+
+        (Self::STATUS_CODE, Json(body)).into_response()
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::errors::{application_error::ApplicationError, error_response::ErrorResponseBody};
+
+/// Error for a client who isn't allowed to perform an action.
+///
+/// # Props
+///
+/// - `message` -> Description why the action was denied
+/// - `player_id` -> Id of the player who tried to perform the action
+pub struct AuthorizationError {
+    /// Description why the action was denied
+    pub message: String,
+    /// Id of the player who tried to perform the action
+    pub player_id: String,
+}
+
+impl AuthorizationError {
+    /// Resembling http status code for a denied action
+    pub const STATUS_CODE: StatusCode = StatusCode::FORBIDDEN;
+
+    /// Creates a new `AuthorizationError`.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` -> Description why the action was denied
+    /// - `player_id` -> Id of the player who tried to perform the action
+    pub fn new(message: String, player_id: String) -> Self {
+        AuthorizationError { message, player_id }
+    }
+}
+
+impl fmt::Display for AuthorizationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Player {} isn't authorized! Error: {}",
+            self.player_id, self.message
+        )
+    }
+}
+
+impl fmt::Debug for AuthorizationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AuthorizationError {{ message: {}, player_id: {} }}",
+            self.message, self.player_id
+        )
+    }
+}
+
+impl std::error::Error for AuthorizationError {}
+
+impl ApplicationError for AuthorizationError {}
+
+impl IntoResponse for AuthorizationError {
+    /// Converts the error into a `403 Forbidden` response.
+    fn into_response(self) -> Response {
+        let body: ErrorResponseBody<()> = ErrorResponseBody::new(self.message, None);
+
+        (Self::STATUS_CODE, Json(body)).into_response()
+    }
+}
@@ -1,8 +1,13 @@
 use std::fmt::{self, Debug, Display};
 
-use axum::{http::StatusCode, Json};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
 
-use crate::errors::application_error::ErrorObject;
+use crate::errors::{application_error::ErrorObject, error_response::ErrorResponseBody};
 
 /// Error type for all request with invalid data a client sends to the backend.
 ///
@@ -70,3 +75,17 @@ impl<T: for<'a> ErrorObject<'a>> BadClientRequest<T> {
         BadClientRequest { message, bad_data }
     }
 }
+
+// ----- Implementation 'IntoResponse' for 'BadClientRequest' -----
+
+impl<T: for<'a> ErrorObject<'a> + Serialize> IntoResponse for BadClientRequest<T> {
+    /// Converts the error into a `400 Bad Request` response.
+    ///
+    /// The bad data is redacted before it is sent back to the client.
+    fn into_response(self) -> Response {
+        let Json(bad_data) = self.bad_data;
+        let body = ErrorResponseBody::new(self.message, Some(bad_data));
+
+        (Self::STATUS_CODE, Json(body)).into_response()
+    }
+}
@@ -1,6 +1,20 @@
-use crate::errors::application_error::{ApplicationError, ErrorObject};
+use crate::errors::{
+    application_error::{ApplicationError, ErrorObject},
+    error_response::ErrorResponseBody,
+};
 
-use axum::{http::StatusCode, Json};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// Marks a response caused by a failing database query.
+///
+/// Added to the extensions of the response, so the request metrics can count the failures.
+#[derive(Debug, Clone, Copy)]
+pub struct DatabaseFailure;
 
 /// This module defines a custom error type for handling database query errors.
 ///
@@ -92,3 +106,24 @@ impl<T: for<'a> ErrorObject<'a>> std::error::Error for DatabaseQueryError<T> {
 }
 
 impl<T: for<'a> ErrorObject<'a>> ApplicationError for DatabaseQueryError<T> {}
+
+// ----- Implementation 'IntoResponse' for 'DatabaseQueryError' -----
+
+impl<T: for<'a> ErrorObject<'a> + Serialize> IntoResponse for DatabaseQueryError<T> {
+    /// Converts the error into a response with its status code.
+    ///
+    /// The received data is redacted before it is sent back to the client.
+    ///
+    /// Server errors are marked with `DatabaseFailure`; a missing entry isn't a failure.
+    fn into_response(self) -> Response {
+        let status_code = self.status_code;
+        let body = ErrorResponseBody::new(self.message, self.received_data.map(|Json(data)| data));
+
+        let mut response = (status_code, Json(body)).into_response();
+        if status_code.is_server_error() {
+            response.extensions_mut().insert(DatabaseFailure);
+        }
+
+        response
+    }
+}
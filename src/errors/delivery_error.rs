@@ -0,0 +1,72 @@
+use std::fmt;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::errors::{application_error::ApplicationError, error_response::ErrorResponseBody};
+
+/// Error for a request to an external service that failed or was rejected.
+///
+/// # Props
+///
+/// - `message` -> Description of the failure
+/// - `target` -> Service or URL the request was sent to
+pub struct DeliveryError {
+    /// Description of the failure
+    pub message: String,
+    /// Service or URL the request was sent to
+    pub target: String,
+}
+
+impl DeliveryError {
+    /// Resembling http status code for a failed request to another service
+    pub const STATUS_CODE: StatusCode = StatusCode::BAD_GATEWAY;
+
+    /// Creates a new `DeliveryError`.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` -> Description of the failure
+    /// - `target` -> Service or URL the request was sent to
+    pub fn new(message: String, target: String) -> Self {
+        DeliveryError { message, target }
+    }
+}
+
+impl fmt::Display for DeliveryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Delivery to {} failed! Error: {}",
+            self.target, self.message
+        )
+    }
+}
+
+impl fmt::Debug for DeliveryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DeliveryError {{ message: {}, target: {} }}",
+            self.message, self.target
+        )
+    }
+}
+
+impl std::error::Error for DeliveryError {}
+
+impl ApplicationError for DeliveryError {}
+
+impl IntoResponse for DeliveryError {
+    /// Converts the error into a `502 Bad Gateway` response.
+    ///
+    /// The target isn't part of the body, since it can contain a token.
+    fn into_response(self) -> Response {
+        let body: ErrorResponseBody<()> = ErrorResponseBody::new(self.message, None);
+
+        (Self::STATUS_CODE, Json(body)).into_response()
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+use crate::errors::application_error::ApplicationError;
+
+/// Error for a stored or received value that doesn't belong to any variant of an enum.
+///
+/// # Props
+///
+/// - `enum_name` -> Name of the enum the value should be converted into
+/// - `value` -> The invalid value as text
+pub struct EnumConversionError {
+    /// Name of the target enum
+    pub enum_name: String,
+    /// The value that couldn't be converted
+    pub value: String,
+}
+
+impl EnumConversionError {
+    /// Creates a new `EnumConversionError`.
+    ///
+    /// # Arguments
+    ///
+    /// - `enum_name` -> Name of the enum the value should be converted into
+    /// - `value` -> The invalid value as text
+    pub fn new(enum_name: &str, value: String) -> Self {
+        EnumConversionError {
+            enum_name: enum_name.to_string(),
+            value,
+        }
+    }
+}
+
+impl fmt::Display for EnumConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The value '{}' isn't a valid variant of '{}'!",
+            self.value, self.enum_name
+        )
+    }
+}
+
+impl fmt::Debug for EnumConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "EnumConversionError {{ enum_name: {}, value: {} }}",
+            self.enum_name, self.value
+        )
+    }
+}
+
+impl std::error::Error for EnumConversionError {}
+
+impl ApplicationError for EnumConversionError {}
@@ -0,0 +1,40 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::errors::application_error::Redact;
+
+/// Body of an error response sent back to a client.
+///
+/// The optional `data` object is always redacted before it gets serialized.
+///
+/// # Props
+///
+/// - `message` -> Description of the error
+/// - `data` -> Redacted data object that caused the error
+#[derive(Serialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorResponseBody<T: Serialize + Redact> {
+    /// Description of what went wrong
+    pub message: String,
+    /// Data object that caused the error, stripped of all hidden information
+    #[schemars(with = "Option<serde_json::Value>")]
+    pub data: Option<T>,
+}
+
+impl<T: Serialize + Redact> ErrorResponseBody<T> {
+    /// Creates a new `ErrorResponseBody` and redacts the provided data object.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` -> Description of the error
+    /// - `data` -> Optional data object that caused the error
+    pub fn new(message: String, data: Option<T>) -> Self {
+        ErrorResponseBody {
+            message,
+            data: data.map(|mut object| {
+                object.redact();
+                object
+            }),
+        }
+    }
+}
@@ -1,5 +1,12 @@
 pub mod application_error;
+pub mod authentication_error;
+pub mod authorization_error;
 pub mod bad_client_request;
 pub mod database_query_error;
+pub mod delivery_error;
+pub mod enum_conversion_error;
+pub mod error_response;
 pub mod invalid_message;
 pub mod process_error;
+pub mod rate_limit_error;
+pub mod validation_errors;
@@ -1,6 +1,16 @@
 use std::{error::Error, fmt::Display};
 
-use crate::errors::application_error::{ApplicationError, ErrorObject};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::errors::{
+    application_error::{ApplicationError, ErrorObject},
+    error_response::ErrorResponseBody,
+};
 
 #[derive()]
 /// ## Error Struct
@@ -63,3 +73,17 @@ impl<T: for<'a> ErrorObject<'a>> Display for ProcessError<T> {
 impl<T: for<'a> ErrorObject<'a>> Error for ProcessError<T> {}
 
 impl<T: for<'a> ErrorObject<'a>> ApplicationError for ProcessError<T> {}
+
+// ----- Implementation of 'IntoResponse' trait for 'ProcessError' struct -----
+
+impl<T: for<'a> ErrorObject<'a> + Serialize> IntoResponse for ProcessError<T> {
+    /// Converts the error into a `409 Conflict` response.
+    ///
+    /// A process error in a request means the requested operation isn't possible in the current
+    /// situation, e.g. a game can't change into the requested state.
+    fn into_response(self) -> Response {
+        let body = ErrorResponseBody::new(self.message, self.bad_data);
+
+        (StatusCode::CONFLICT, Json(body)).into_response()
+    }
+}
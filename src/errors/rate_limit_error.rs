@@ -0,0 +1,78 @@
+use std::fmt;
+
+use axum::{
+    http::{header::RETRY_AFTER, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::errors::{application_error::ApplicationError, error_response::ErrorResponseBody};
+
+/// Error for a client who sends too many requests in a short time.
+///
+/// # Props
+///
+/// - `message` -> Description of the exceeded limit
+/// - `retry_after_seconds` -> Number of seconds until the client may try again
+pub struct RateLimitError {
+    /// Description of the exceeded limit
+    pub message: String,
+    /// Number of seconds until the client may try again
+    pub retry_after_seconds: i64,
+}
+
+impl RateLimitError {
+    /// Resembling http status code for an exceeded limit
+    pub const STATUS_CODE: StatusCode = StatusCode::TOO_MANY_REQUESTS;
+
+    /// Creates a new `RateLimitError`.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` -> Description of the exceeded limit
+    /// - `retry_after_seconds` -> Number of seconds until the client may try again
+    pub fn new(message: String, retry_after_seconds: i64) -> Self {
+        RateLimitError {
+            message,
+            retry_after_seconds,
+        }
+    }
+}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Rate limit exceeded! Retry after {} seconds. Error: {}",
+            self.retry_after_seconds, self.message
+        )
+    }
+}
+
+impl fmt::Debug for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RateLimitError {{ message: {}, retry_after_seconds: {} }}",
+            self.message, self.retry_after_seconds
+        )
+    }
+}
+
+impl std::error::Error for RateLimitError {}
+
+impl ApplicationError for RateLimitError {}
+
+impl IntoResponse for RateLimitError {
+    /// Converts the error into a `429 Too Many Requests` response with a `Retry-After` header.
+    fn into_response(self) -> Response {
+        let body: ErrorResponseBody<()> = ErrorResponseBody::new(self.message, None);
+
+        (
+            Self::STATUS_CODE,
+            [(RETRY_AFTER, self.retry_after_seconds.max(1).to_string())],
+            Json(body),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{errors::application_error::ApplicationError, i18n::locale::Locale};
+
+/// A single failed check on one field of a request object.
+///
+/// # Props
+///
+/// - `field` -> Name of the field that is invalid
+/// - `code` -> Machine readable code like `required` or `too_long`
+/// - `message` -> Human readable description of the problem
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldError {
+    /// Name of the invalid field
+    pub field: String,
+    /// Machine readable error code the frontend can react to
+    pub code: String,
+    /// Description of the problem
+    pub message: String,
+}
+
+/// Collection of all field-level failures found while validating a DTO.
+///
+/// Instead of stopping at the first problem, all checks are executed and every failure is
+/// collected, so a client can show all problems of a form at once.
+///
+/// # Example
+///
+/// ```rust
+/// let mut errors = ValidationErrors::new();
+/// errors.add("name", "required", "The name must not be empty!");
+/// let result = errors.into_result();
+/// ```
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationErrors {
+    /// All collected field errors
+    pub errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    /// Resembling http status code for a failed validation
+    pub const STATUS_CODE: StatusCode = StatusCode::BAD_REQUEST;
+
+    /// Creates an empty `ValidationErrors` instance.
+    pub fn new() -> Self {
+        ValidationErrors { errors: vec![] }
+    }
+
+    /// Adds a new failure to the collection.
+    ///
+    /// # Arguments
+    ///
+    /// - `field` -> Name of the invalid field
+    /// - `code` -> Machine readable error code
+    /// - `message` -> Description of the problem
+    pub fn add(&mut self, field: &str, code: &str, message: &str) {
+        self.errors.push(FieldError {
+            field: field.to_string(),
+            code: code.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    /// Returns `true` if no failure was collected.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Moves all failures of another collection into this one.
+    pub fn merge(&mut self, other: ValidationErrors) {
+        self.errors.extend(other.errors);
+    }
+
+    /// Turns the collection into a `Result`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if no failure was collected, otherwise `Err` with all failures.
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+// ----- Implementation 'ApplicationError' for 'ValidationErrors' -----
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Validation failed with {} error(s): ", self.errors.len())?;
+        for error in &self.errors {
+            write!(f, "[{}: {} - {}] ", error.field, error.code, error.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl ApplicationError for ValidationErrors {}
+
+// ----- Implementation 'IntoResponse' for 'ValidationErrors' -----
+
+impl IntoResponse for ValidationErrors {
+    /// Converts all collected failures into a `400 Bad Request` response.
+    fn into_response(self) -> Response {
+        (Self::STATUS_CODE, Json(self)).into_response()
+    }
+}
+
+/// Trait for request objects that can check their own data.
+///
+/// Implementations need to run every check and collect all failures in a `ValidationErrors`
+/// instance.
+pub trait Validate {
+    /// Validates the object.
+    ///
+    /// # Arguments
+    ///
+    /// - `locale` -> Language of the messages of the failures
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` when the object is valid, otherwise all found problems.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors>;
+}
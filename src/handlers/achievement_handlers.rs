@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+};
+use log::warn;
+
+use crate::{
+    enums::achievement::Achievement,
+    handlers::event_handlers::publish_event,
+    router::router_provider::AppState,
+    types::{achievement::AchievementList, game_event::GameEvent},
+};
+
+/// Lists every achievement and which of them a player unlocked.
+///
+/// Achievements belong to the identity of the player, so the list covers every game played from
+/// the same client.
+///
+/// URL endpoint: /player/{id}/achievements
+pub async fn get_player_achievements(
+    State(app_state): State<AppState>,
+    Path(player_id): Path<String>,
+) -> Result<AchievementList, Response> {
+    let player = app_state
+        .player_repository
+        .get_player(&player_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let unlocked = app_state
+        .achievement_repository
+        .get_unlocked(&player.identity())
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(AchievementList::new(unlocked))
+}
+
+/// Unlocks achievements a player earned and announces the new ones to the game.
+///
+/// The move or game that earned them already succeeded, so a failure is only logged. Achievements
+/// unlocked before are skipped silently.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the achievement repository
+/// - `game_id` -> Game in which the achievements were earned
+/// - `player_id` -> Player who earned them
+/// - `identity` -> Identity of the player, see `player_identity`
+/// - `achievements` -> The earned achievements
+pub async fn unlock_achievements(
+    app_state: &AppState,
+    game_id: &str,
+    player_id: &str,
+    identity: &str,
+    achievements: &[Achievement],
+) {
+    for achievement in achievements {
+        match app_state
+            .achievement_repository
+            .unlock(identity, *achievement, player_id, game_id)
+            .await
+        {
+            Ok(true) => {
+                let event = GameEvent::AchievementUnlocked {
+                    game_id: game_id.to_string(),
+                    player_id: player_id.to_string(),
+                    achievement: *achievement,
+                    title: achievement.title().to_string(),
+                };
+                publish_event(app_state, &event, None).await;
+            }
+            Ok(false) => {}
+            Err(err) => {
+                warn!("Achievement {achievement} of player {player_id} couldn't be unlocked: {err}")
+            }
+        }
+    }
+}
@@ -0,0 +1,425 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, NaiveTime};
+use serde::Deserialize;
+
+use crate::{
+    auth::admin::Admin,
+    enums::{audit_action::AuditAction, data_migration::DataMigration},
+    errors::validation_errors::{Validate, ValidationErrors},
+    handlers::{
+        audit_handlers::record_audit, chat_handlers::mute_in_game, game_handlers::load_game,
+    },
+    i18n::locale::Locale,
+    jobs::task_queue::enqueue_game_end_tasks,
+    metrics::prometheus::error_counts,
+    router::router_provider::AppState,
+    types::{
+        game::Game,
+        game_view::GameView,
+        maintenance::{MaintenanceMode, MigrationReport},
+        moderation::{Ban, CreateBanDTO, Report},
+        overview::AdminOverview,
+        webhook::{CreateWebhookDTO, RegisteredWebhook, Webhook, WebhookDeadLetter},
+    },
+    utils::{
+        ndjson::{accepts_ndjson, stream_ndjson},
+        time::server_time,
+    },
+};
+
+// constants
+/// Maximum number of reports returned to an operator at once.
+const REPORTS_PAGE_SIZE: usize = 100;
+
+/// Maximum number of failed webhook deliveries returned to an operator at once.
+const DEAD_LETTERS_PAGE_SIZE: usize = 100;
+
+/// Number of games listed at once if the operator doesn't ask for a page size.
+const DEFAULT_GAMES_PAGE_SIZE: usize = 50;
+
+/// Highest number of games listed at once; a streamed listing reads pages of this size.
+const MAX_GAMES_PAGE_SIZE: usize = 100;
+
+/// Days of ended games the average game duration of the overview is taken over.
+const AVERAGE_DURATION_DAYS: i64 = 7;
+
+/// Query parameters of the game listing.
+///
+/// # Props
+///
+/// - `after` -> ID of the last game of the previous page; the first page if `None`
+/// - `limit` -> Number of games, at most `MAX_GAMES_PAGE_SIZE`; ignored by a streamed listing
+#[derive(Deserialize)]
+pub struct GameListQuery {
+    /// ID of the last game of the previous page
+    pub after: Option<String>,
+    /// Number of games
+    pub limit: Option<usize>,
+}
+
+/// Key figures for a dashboard of the operators, read without direct access to D1.
+///
+/// Players count as online while their heartbeat is younger than `disconnect_after_seconds`. The
+/// error counts only cover the isolate that answers, like the `/metrics` endpoint.
+///
+/// URL endpoint: /admin/overview
+pub async fn get_overview(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+) -> Result<AdminOverview, Response> {
+    let now = server_time();
+    let day_start = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+
+    let counts = app_state
+        .maintenance_repository
+        .get_overview_counts(
+            now - Duration::seconds(app_state.config.disconnect_after_seconds),
+            day_start,
+            now - Duration::days(AVERAGE_DURATION_DAYS),
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(AdminOverview::new(counts, error_counts(), now))
+}
+
+/// Ends a game regardless of its host, e.g. if it got stuck.
+///
+/// URL endpoint: /admin/game/{id}/end
+pub async fn force_end_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    admin: Admin,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    let mut game = load_game(&app_state, &game_id).await?;
+    game.finish().map_err(IntoResponse::into_response)?;
+
+    let mut updated_game = app_state
+        .game_repository
+        .update_game(game.outcome_update(), &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    updated_game.players = game.players;
+
+    enqueue_game_end_tasks(&app_state, &updated_game).await;
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::ForceEndGame,
+        Some(&game_id),
+        None,
+    )
+    .await;
+
+    Ok(GameView::for_spectator(&updated_game, &locale))
+}
+
+/// Mutes a player of any game in the chat, regardless of its host.
+///
+/// The mute lasts `mute_minutes` of the configuration, like a mute of the host.
+///
+/// URL endpoint: /admin/game/{id}/mute/{player_id}
+pub async fn force_mute_player(
+    State(app_state): State<AppState>,
+    Path((game_id, muted_player_id)): Path<(String, String)>,
+    admin: Admin,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    let game = load_game(&app_state, &game_id).await?;
+    let game = mute_in_game(&app_state, game, &muted_player_id, &admin.identity).await?;
+
+    Ok(GameView::for_spectator(&game, &locale))
+}
+
+/// Returns a game with everything a `GameView` hides, for moderation and debugging.
+///
+/// Contains the hands of all players, the cards of all claims and the seed of a daily challenge.
+/// Every inspection is recorded in the audit log.
+///
+/// URL endpoint: /admin/game/{id}/full
+pub async fn inspect_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    admin: Admin,
+) -> Result<Json<Game>, Response> {
+    let game = load_game(&app_state, &game_id).await?;
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::InspectGame,
+        Some(&game_id),
+        None,
+    )
+    .await;
+
+    // serialized as it is, since `Game::into_response` would redact it
+    Ok(Json(game))
+}
+
+/// Returns the current maintenance mode.
+///
+/// URL endpoint: /admin/maintenance
+pub async fn get_maintenance(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+) -> Result<MaintenanceMode, Response> {
+    app_state
+        .maintenance_repository
+        .get_maintenance_mode()
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+/// Enables or disables the maintenance mode.
+///
+/// URL endpoint: /admin/maintenance
+pub async fn set_maintenance(
+    State(app_state): State<AppState>,
+    admin: Admin,
+    Json(mode): Json<MaintenanceMode>,
+) -> Result<MaintenanceMode, Response> {
+    let mode = app_state
+        .maintenance_repository
+        .set_maintenance_mode(mode)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::SetMaintenance,
+        None,
+        Some(mode.to_string()),
+    )
+    .await;
+
+    Ok(mode)
+}
+
+/// Runs a data migration by its name, e.g. `backfill-join-codes`.
+///
+/// URL endpoint: /admin/migrations/{name}
+pub async fn run_migration(
+    State(app_state): State<AppState>,
+    Path(name): Path<String>,
+    admin: Admin,
+) -> Result<MigrationReport, Response> {
+    let migration = name.parse::<DataMigration>().map_err(|err| {
+        let mut errors = ValidationErrors::new();
+        errors.add("name", "unknown", &err.to_string());
+        errors.into_response()
+    })?;
+
+    let affected_rows = app_state
+        .maintenance_repository
+        .run_migration(migration)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::RunMigration,
+        Some(migration.as_str()),
+        Some(format!("{affected_rows} affected rows")),
+    )
+    .await;
+
+    Ok(MigrationReport {
+        migration,
+        affected_rows,
+    })
+}
+
+/// Lists all stored games with their players, claims and chat, ordered by their ID.
+///
+/// Answers with a page of games by default. With `Accept: application/x-ndjson` every game after
+/// `after` is streamed as one line, up to `MAX_STREAMED_ROWS`.
+///
+/// URL endpoint: /admin/games
+pub async fn list_games(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+    Query(query): Query<GameListQuery>,
+    headers: HeaderMap,
+) -> Result<Response, Response> {
+    if accepts_ndjson(&headers) {
+        let game_repository = app_state.game_repository.clone();
+
+        return Ok(stream_ndjson(query.after, move |after: Option<String>| {
+            let game_repository = game_repository.clone();
+            async move {
+                let games = game_repository
+                    .get_all_games(after.as_deref(), MAX_GAMES_PAGE_SIZE)
+                    .await
+                    .map_err(|err| err.message)?;
+                let next_after = games
+                    .last()
+                    .filter(|_| games.len() == MAX_GAMES_PAGE_SIZE)
+                    .map(|game| game.id.clone());
+
+                Ok((games, next_after))
+            }
+        }));
+    }
+
+    let page_size = query
+        .limit
+        .unwrap_or(DEFAULT_GAMES_PAGE_SIZE)
+        .clamp(1, MAX_GAMES_PAGE_SIZE);
+
+    app_state
+        .game_repository
+        .get_all_games(query.after.as_deref(), page_size)
+        .await
+        .map(|games: Vec<Game>| Json(games).into_response())
+        .map_err(IntoResponse::into_response)
+}
+
+/// Lists the latest reports including the fingerprints of the reported clients.
+///
+/// URL endpoint: /admin/reports
+pub async fn get_reports(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+) -> Result<Json<Vec<Report>>, Response> {
+    app_state
+        .moderation_repository
+        .get_reports(REPORTS_PAGE_SIZE)
+        .await
+        .map(Json)
+        .map_err(IntoResponse::into_response)
+}
+
+/// Adds a client to the ban list.
+///
+/// URL endpoint: /admin/bans
+pub async fn add_ban(
+    State(app_state): State<AppState>,
+    admin: Admin,
+    locale: Locale,
+    Json(ban_data): Json<CreateBanDTO>,
+) -> Result<Ban, Response> {
+    let ban = ban_data.into_ban();
+    ban.validate(&locale).map_err(IntoResponse::into_response)?;
+
+    let ban = app_state
+        .moderation_repository
+        .add_ban(ban)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::AddBan,
+        Some(&ban.fingerprint),
+        Some(ban.reason.clone()),
+    )
+    .await;
+
+    Ok(ban)
+}
+
+/// Removes a client from the ban list.
+///
+/// URL endpoint: /admin/bans/{fingerprint}
+pub async fn remove_ban(
+    State(app_state): State<AppState>,
+    Path(fingerprint): Path<String>,
+    admin: Admin,
+) -> Result<StatusCode, Response> {
+    app_state
+        .moderation_repository
+        .remove_ban(&fingerprint)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::RemoveBan,
+        Some(&fingerprint),
+        None,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Registers a callback URL receiving the lifecycle events of all games, e.g. of a Discord bot.
+///
+/// The response contains the secret the payloads are signed with; it isn't shown again.
+///
+/// URL endpoint: /admin/webhooks
+pub async fn register_global_webhook(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+    locale: Locale,
+    Json(webhook_data): Json<CreateWebhookDTO>,
+) -> Result<RegisteredWebhook, Response> {
+    webhook_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    app_state
+        .webhook_repository
+        .add_webhook(webhook_data.into_webhook(None))
+        .await
+        .map(RegisteredWebhook::from)
+        .map_err(IntoResponse::into_response)
+}
+
+/// Lists the webhooks receiving the events of all games.
+///
+/// URL endpoint: /admin/webhooks
+pub async fn get_global_webhooks(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+) -> Result<Json<Vec<Webhook>>, Response> {
+    app_state
+        .webhook_repository
+        .get_webhooks(None)
+        .await
+        .map(Json)
+        .map_err(IntoResponse::into_response)
+}
+
+/// Lists the latest webhook deliveries that failed on every attempt, of all webhooks.
+///
+/// URL endpoint: /admin/webhooks/dead-letters
+pub async fn get_webhook_dead_letters(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+) -> Result<Json<Vec<WebhookDeadLetter>>, Response> {
+    app_state
+        .webhook_repository
+        .get_dead_letters(DEAD_LETTERS_PAGE_SIZE)
+        .await
+        .map(Json)
+        .map_err(IntoResponse::into_response)
+}
+
+/// Removes a webhook receiving the events of all games.
+///
+/// URL endpoint: /admin/webhooks/{id}
+pub async fn remove_global_webhook(
+    State(app_state): State<AppState>,
+    Path(webhook_id): Path<String>,
+    _admin: Admin,
+) -> Result<StatusCode, Response> {
+    app_state
+        .webhook_repository
+        .remove_webhook(&webhook_id, None)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,88 @@
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Response},
+};
+use log::warn;
+use serde::Deserialize;
+
+use crate::{
+    auth::admin::Admin,
+    enums::audit_action::AuditAction,
+    logging::log_context,
+    router::router_provider::AppState,
+    types::audit::{AuditLogPage, CreateAuditEntryDTO},
+};
+
+// constants
+/// Number of audit entries returned if the operator doesn't ask for a page size.
+const DEFAULT_AUDIT_PAGE_SIZE: usize = 50;
+
+/// Highest number of audit entries returned at once.
+const MAX_AUDIT_PAGE_SIZE: usize = 200;
+
+/// Query parameters of the audit log.
+///
+/// # Props
+///
+/// - `before` -> `nextBefore` of the previous page; the newest entries if `None`
+/// - `limit` -> Number of entries, at most `MAX_AUDIT_PAGE_SIZE`
+#[derive(Deserialize)]
+pub struct AuditLogQuery {
+    /// `nextBefore` of the previous page
+    pub before: Option<usize>,
+    /// Number of entries
+    pub limit: Option<usize>,
+}
+
+/// Lists the audit log, newest entries first.
+///
+/// URL endpoint: /admin/audit
+pub async fn get_audit_log(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<AuditLogPage, Response> {
+    let page_size = query
+        .limit
+        .unwrap_or(DEFAULT_AUDIT_PAGE_SIZE)
+        .clamp(1, MAX_AUDIT_PAGE_SIZE);
+
+    let entries = app_state
+        .audit_repository
+        .get_entries(query.before, page_size)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(AuditLogPage::new(entries, page_size))
+}
+
+/// Adds an action that was taken to the audit log.
+///
+/// The action already succeeded, so a failure is only logged.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the audit repository
+/// - `actor` -> Who acted; the identity of an operator or `player:<id>` for a host
+/// - `action` -> What was done
+/// - `target` -> What the action was applied to
+/// - `details` -> Additional information
+pub async fn record_audit(
+    app_state: &AppState,
+    actor: &str,
+    action: AuditAction,
+    target: Option<&str>,
+    details: Option<String>,
+) {
+    let entry_data = CreateAuditEntryDTO {
+        actor: actor.to_string(),
+        action,
+        target: target.map(str::to_string),
+        details,
+        request_id: log_context::current().request_id,
+    };
+
+    if let Err(err) = app_state.audit_repository.add_entry(entry_data).await {
+        warn!("Action {action} of {actor} couldn't be audited: {err}");
+    }
+}
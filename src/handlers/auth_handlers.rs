@@ -0,0 +1,317 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    auth::{
+        device_token::{issue_device_token, DeviceClaims},
+        rejoin_token::{issue_rejoin_token, verify_rejoin_token},
+        session_token::{issue_token, SessionClaims},
+    },
+    enums::game_state::GameState,
+    errors::{process_error::ProcessError, validation_errors::Validate},
+    handlers::game_handlers::load_game,
+    i18n::{catalog::Message, locale::Locale},
+    middleware::authentication::{AuthenticatedPlayer, ClientIdentity},
+    router::router_provider::AppState,
+    types::{
+        game::Game,
+        game_view::GameView,
+        player::{resolve_player_name, CreatePlayerDTO, Player, PlayerProfile, UpdatePlayerDTO},
+        resync::RejoinRequest,
+    },
+    utils::time::server_time,
+};
+
+/// Response body containing a signed session token.
+///
+/// # Props
+///
+/// - `token` -> Signed token which needs to be sent as bearer token
+/// - `expires_at` -> Point in time after which the token needs to be refreshed
+/// - `player` -> The player the token was issued for
+/// - `rejoin_token` -> Token to get a new session with after losing this one, only sent once
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionResponse {
+    /// Signed token which needs to be sent as bearer token
+    pub token: String,
+    /// Point in time after which the token is invalid
+    pub expires_at: DateTime<Utc>,
+    /// The player the token was issued for
+    pub player: Option<Player>,
+    /// Token for `/game/{id}/rejoin`, only sent when the player joins
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejoin_token: Option<String>,
+}
+
+impl SessionResponse {
+    /// Signs the claims and creates the response body.
+    ///
+    /// # Arguments
+    ///
+    /// - `claims` -> Claims of the new token
+    /// - `secret` -> Key of the worker used to sign all tokens
+    /// - `player` -> Optional player the token was issued for
+    pub fn new(claims: &SessionClaims, secret: &str, player: Option<Player>) -> Self {
+        SessionResponse {
+            token: issue_token(claims, secret),
+            expires_at: DateTime::from_timestamp(claims.expires_at, 0).unwrap_or_default(),
+            player,
+            rejoin_token: None,
+        }
+    }
+
+    /// Hands the rejoin token of a new player to the client.
+    ///
+    /// # Arguments
+    ///
+    /// - `rejoin_token` -> The token, whose hash was stored with the player
+    pub fn with_rejoin_token(mut self, rejoin_token: String) -> Self {
+        self.rejoin_token = Some(rejoin_token);
+        self
+    }
+}
+
+impl IntoResponse for SessionResponse {
+    /// Converts the `SessionResponse` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Response body of a rejoin request.
+///
+/// # Props
+///
+/// - `session` -> New session of the player
+/// - `game` -> The game from the perspective of the player, including their hand
+/// - `latest_sequence` -> Sequence number to pass as `after` to the event stream
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RejoinResponse {
+    /// New session of the player
+    pub session: SessionResponse,
+    /// The game from the perspective of the player, including their hand
+    pub game: GameView,
+    /// Sequence number to pass as `after` to the event stream
+    pub latest_sequence: usize,
+}
+
+impl IntoResponse for RejoinResponse {
+    /// Converts the `RejoinResponse` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Response body containing a signed device token.
+///
+/// # Props
+///
+/// - `device_token` -> Signed token which needs to be sent in the `X-Device-Token` header
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceResponse {
+    /// Signed token which needs to be sent in the `X-Device-Token` header
+    pub device_token: String,
+}
+
+impl IntoResponse for DeviceResponse {
+    /// Converts the `DeviceResponse` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Registers a new device and returns its device token.
+///
+/// The client keeps the token and sends it whenever it creates or joins a game, so the statistics,
+/// the history and the recent opponents of its players belong together. Banned clients are
+/// rejected.
+///
+/// URL endpoint: /auth/device
+pub async fn register_device(
+    State(app_state): State<AppState>,
+    _client: ClientIdentity,
+) -> DeviceResponse {
+    let claims = DeviceClaims::new(server_time());
+
+    DeviceResponse {
+        device_token: issue_device_token(&claims, &app_state.session_secret),
+    }
+}
+
+/// Lets a player join a game and exchanges the join request for a session token and a rejoin
+/// token.
+///
+/// Banned clients are rejected. A name another player of the game already uses is refused with
+/// the code `name_taken`, unless the request asks to number it.
+///
+/// URL endpoint: /auth/session
+pub async fn create_session(
+    State(app_state): State<AppState>,
+    client: ClientIdentity,
+    locale: Locale,
+    Json(mut join_request): Json<CreatePlayerDTO>,
+) -> Result<SessionResponse, Response> {
+    join_request
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let game = app_state
+        .game_repository
+        .get_game_by_id(&join_request.game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    if game.state != GameState::WaitingForPlayers && game.state != GameState::Starting {
+        return Err(ProcessError::new(
+            Message::GameNotJoinable.text(&locale),
+            "create_session".to_string(),
+            Some(Game::from_ref(&game)),
+        )
+        .into_response());
+    }
+
+    let players = app_state
+        .player_repository
+        .get_all_players(Some(game.id.clone()))
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if players.len() >= game.settings.max_players {
+        return Err(ProcessError::new(
+            Message::GameFull.text(&locale),
+            "create_session".to_string(),
+            Some(Game::from_ref(&game)),
+        )
+        .into_response());
+    }
+
+    let taken_names = app_state
+        .player_repository
+        .get_player_names(&game.id, None)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    join_request.name = resolve_player_name(
+        "name",
+        &join_request.name,
+        &taken_names,
+        join_request.auto_suffix,
+        &locale,
+    )
+    .map_err(IntoResponse::into_response)?;
+
+    let (rejoin_token, rejoin_token_hash) = issue_rejoin_token();
+    let mut player = join_request.into_player();
+    player.fingerprint = client.fingerprint;
+    player.rejoin_token_hash = Some(rejoin_token_hash);
+    let player = app_state
+        .player_repository
+        .add_player(player)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let claims = SessionClaims::new(
+        player.id.clone(),
+        player.game_id.clone(),
+        server_time(),
+        app_state.config.session_lifetime_minutes,
+    );
+
+    Ok(
+        SessionResponse::new(&claims, &app_state.session_secret, Some(player))
+            .with_rejoin_token(rejoin_token),
+    )
+}
+
+/// Extends the session of an authenticated player.
+///
+/// URL endpoint: /auth/refresh
+pub async fn refresh_session(
+    State(app_state): State<AppState>,
+    player: AuthenticatedPlayer,
+) -> SessionResponse {
+    let claims = SessionClaims::new(
+        player.player_id,
+        player.game_id,
+        server_time(),
+        app_state.config.session_lifetime_minutes,
+    );
+
+    SessionResponse::new(&claims, &app_state.session_secret, None)
+}
+
+/// Lets a client that lost its session, e.g. by a refreshed tab, take its seat again.
+///
+/// The client proves the seat is its own with the rejoin token it got when the player joined, so
+/// no second player is created. The player is marked as active again and gets a new session, the
+/// current game including their hand and the sequence number to continue the event stream after.
+/// Banned clients are rejected.
+///
+/// URL endpoint: /game/{id}/rejoin
+pub async fn rejoin_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    _client: ClientIdentity,
+    locale: Locale,
+    Json(rejoin_request): Json<RejoinRequest>,
+) -> Result<RejoinResponse, Response> {
+    let player = match app_state
+        .player_repository
+        .get_player(&rejoin_request.player_id)
+        .await
+    {
+        Ok(player) if player.game_id == game_id => Some(player),
+        Ok(_) => None,
+        Err(err) if err.status_code == StatusCode::NOT_FOUND => None,
+        Err(err) => return Err(err.into_response()),
+    };
+    // an unknown player and a wrong token are refused alike, so ids can't be probed
+    verify_rejoin_token(
+        &rejoin_request.rejoin_token,
+        player
+            .as_ref()
+            .and_then(|player| player.rejoin_token_hash.as_deref()),
+    )
+    .map_err(IntoResponse::into_response)?;
+    let player_id = rejoin_request.player_id;
+
+    app_state
+        .player_repository
+        .update_player(UpdatePlayerDTO::new(
+            player_id.clone(),
+            None,
+            None,
+            None,
+            Some(server_time()),
+            PlayerProfile::default(),
+        ))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let latest_sequence = app_state
+        .game_event_repository
+        .get_latest_sequence(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let game = load_game(&app_state, &game_id).await?;
+
+    let claims = SessionClaims::new(
+        player_id.clone(),
+        game_id,
+        server_time(),
+        app_state.config.session_lifetime_minutes,
+    );
+
+    Ok(RejoinResponse {
+        session: SessionResponse::new(&claims, &app_state.session_secret, None),
+        game: GameView::for_player(&game, &player_id, &locale),
+        latest_sequence,
+    })
+}
@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Query, State},
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::{
+    auth::admin::Admin,
+    enums::{audit_action::AuditAction, backup_table::BackupTable},
+    errors::process_error::ProcessError,
+    handlers::audit_handlers::record_audit,
+    router::router_provider::AppState,
+    types::{
+        backup::{BackupRecord, ImportFailure, ImportSummary, BACKUP_NEXT_AFTER_HEADER},
+        game::Game,
+        game_archive::ARCHIVE_CONTENT_TYPE,
+    },
+};
+
+// constants
+/// Number of games exported at once if the operator doesn't ask for a page size.
+const DEFAULT_EXPORT_PAGE_SIZE: usize = 50;
+
+/// Highest number of games exported at once, so a page fits into the memory of an isolate.
+const MAX_EXPORT_PAGE_SIZE: usize = 200;
+
+/// Query parameters of a bulk export.
+///
+/// # Props
+///
+/// - `after` -> `Backup-Next-After` header of the previous page; the first page if `None`
+/// - `limit` -> Number of games, at most `MAX_EXPORT_PAGE_SIZE`
+#[derive(Deserialize)]
+pub struct BackupExportQuery {
+    /// `Backup-Next-After` header of the previous page
+    pub after: Option<String>,
+    /// Number of games
+    pub limit: Option<usize>,
+}
+
+/// Exports a page of games with all their players, cards, claims, chats, events, results and
+/// invitations as newline-delimited JSON.
+///
+/// The `Backup-Next-After` header of the response is passed as `after` to get the next page.
+///
+/// URL endpoint: /admin/backup/export
+pub async fn export_games(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+    Query(query): Query<BackupExportQuery>,
+) -> Result<Response, Response> {
+    let page_size = query
+        .limit
+        .unwrap_or(DEFAULT_EXPORT_PAGE_SIZE)
+        .clamp(1, MAX_EXPORT_PAGE_SIZE);
+
+    let mut export = String::new();
+    let mut exported_games = 0;
+    let mut last_game_id = None;
+
+    for table in BackupTable::ALL {
+        let rows = app_state
+            .backup_repository
+            .get_rows(table, query.after.as_deref(), page_size)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        if table == BackupTable::Games {
+            exported_games = rows.len();
+            last_game_id = rows
+                .last()
+                .and_then(|row| row.get("id"))
+                .and_then(|id| id.as_str())
+                .map(str::to_string);
+        }
+
+        for row in rows {
+            let line = serde_json::to_string(&BackupRecord { table, row }).map_err(|err| {
+                ProcessError::<Game>::new(err.to_string(), "export_games".to_string(), None)
+                    .into_response()
+            })?;
+            export.push_str(&line);
+            export.push('\n');
+        }
+    }
+
+    let mut response = (
+        StatusCode::OK,
+        [(CONTENT_TYPE, ARCHIVE_CONTENT_TYPE)],
+        export,
+    )
+        .into_response();
+
+    // a page that isn't full is the last one
+    let next_after = last_game_id
+        .filter(|_| exported_games == page_size)
+        .and_then(|id| HeaderValue::from_str(&id).ok());
+    if let Some(next_after) = next_after {
+        response
+            .headers_mut()
+            .insert(BACKUP_NEXT_AFTER_HEADER, next_after);
+    }
+
+    Ok(response)
+}
+
+/// Imports games exported by `export_games`, replacing stored rows with the same keys.
+///
+/// Lines are imported in their order; a line that can't be imported is reported and skipped.
+/// The events of an imported game replace its stored events.
+///
+/// URL endpoint: /admin/backup/import
+pub async fn import_games(
+    State(app_state): State<AppState>,
+    admin: Admin,
+    body: String,
+) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    let mut imported_game_ids = Vec::new();
+    let mut cleared_event_logs = HashSet::new();
+
+    for (index, line) in body.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record = match serde_json::from_str::<BackupRecord>(line) {
+            Ok(record) => record,
+            Err(err) => {
+                summary.failures.push(ImportFailure {
+                    line: line_number,
+                    message: err.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if record.table == BackupTable::GameEvents {
+            let game_id = record
+                .row
+                .get("game_id")
+                .and_then(|id| id.as_str())
+                .unwrap_or_default()
+                .to_string();
+            if !cleared_event_logs.contains(&game_id) {
+                if let Err(err) = app_state
+                    .backup_repository
+                    .delete_game_events(&game_id)
+                    .await
+                {
+                    summary.failures.push(ImportFailure {
+                        line: line_number,
+                        message: err.message,
+                    });
+                    continue;
+                }
+                cleared_event_logs.insert(game_id);
+            }
+        }
+
+        match app_state.backup_repository.put_row(&record).await {
+            Ok(()) => {
+                summary.imported += 1;
+                if record.table == BackupTable::Games {
+                    summary.games += 1;
+                    if let Some(game_id) = record.row.get("id").and_then(|id| id.as_str()) {
+                        imported_game_ids.push(game_id.to_string());
+                    }
+                }
+            }
+            Err(err) => summary.failures.push(ImportFailure {
+                line: line_number,
+                message: err.message,
+            }),
+        }
+    }
+
+    // the cache would serve the replaced games until it expires
+    for game_id in &imported_game_ids {
+        app_state
+            .game_repository
+            .invalidate_cached_game(game_id)
+            .await;
+    }
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::ImportGames,
+        None,
+        Some(format!(
+            "{} rows of {} games, {} failed lines",
+            summary.imported,
+            summary.games,
+            summary.failures.len()
+        )),
+    )
+    .await;
+
+    summary
+}
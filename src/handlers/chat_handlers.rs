@@ -0,0 +1,479 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Duration;
+
+use crate::{
+    enums::audit_action::AuditAction,
+    errors::{
+        authorization_error::AuthorizationError,
+        database_query_error::DatabaseQueryError,
+        validation_errors::{Validate, ValidationErrors},
+    },
+    handlers::{
+        audit_handlers::record_audit, event_handlers::publish_event, game_handlers::load_game,
+    },
+    i18n::{catalog::Message, locale::Locale},
+    logic::{
+        chat_filter::{moderation_notice, Moderation},
+        mentions::find_mentions,
+    },
+    middleware::{
+        authentication::AuthenticatedPlayer,
+        authorization::{ensure_host, ensure_owner, ensure_same_game, Owned},
+    },
+    router::router_provider::AppState,
+    types::{
+        chat::{Chat, ChatMessage, EditChatMessageDTO, SendChatMessageDTO},
+        game::Game,
+        game_event::GameEvent,
+        game_view::GameView,
+        player::Player,
+    },
+    utils::time::server_time,
+};
+
+/// Sends a message in the chat of a game.
+///
+/// The message runs through the rate cap and the word filter first. A filtered message is masked
+/// or dropped, and a moderation notice is added to the chat. If the chat is full, the oldest
+/// message is removed.
+///
+/// Every player the message mentions with `@name` additionally receives a `mentioned` event on
+/// their event stream.
+///
+/// A message with a recipient is whispered instead: it only reaches the event streams of the
+/// sender and the recipient, never the public chat, and the stored whisper is returned.
+///
+/// Muted players can't send messages until their mute ends.
+///
+/// URL endpoint: /game/{id}/chat
+pub async fn send_chat_message(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    locale: Locale,
+    Owned {
+        player,
+        data: message_data,
+    }: Owned<SendChatMessageDTO>,
+) -> Result<Response, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+    ensure_not_muted(&app_state, &player.player_id, &locale).await?;
+
+    let message = message_data
+        .into_message()
+        .map_err(IntoResponse::into_response)?;
+    message
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let mut chat = app_state
+        .chat_repository
+        .get_or_create_chat_of_game(&game_id, &app_state.chat_message_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    app_state
+        .chat_filter
+        .check_rate(&chat, &message.player_id, server_time())
+        .map_err(IntoResponse::into_response)?;
+
+    if message.recipient_player_id.is_some() {
+        return whisper(&app_state, &game_id, &chat, message, &locale)
+            .await
+            .map(IntoResponse::into_response);
+    }
+
+    let moderation = app_state.chat_filter.moderate(message);
+    let notice = moderation_notice(&moderation);
+    let sent_message = match moderation {
+        Moderation::Clean(message) | Moderation::Censored(message) => {
+            store_message(&app_state, &mut chat, message.clone()).await?;
+            Some(message)
+        }
+        Moderation::Rejected(_) => None,
+    };
+    if let Some(notice) = notice {
+        store_message(&app_state, &mut chat, notice).await?;
+    }
+
+    app_state
+        .chat_repository
+        .update_number_of_messages(&chat)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    if let Some(message) = sent_message {
+        notify_mentions(&app_state, &game_id, &message).await;
+    }
+
+    Ok(chat.localized(&locale).into_response())
+}
+
+/// Tells the other players that a player types a chat message.
+///
+/// Clients call it on every few keystrokes; a notification within `TYPING_DEBOUNCE_SECONDS` of
+/// the last counted one is dropped. The indicator is never stored: it lives in the memory of the
+/// `TypingIndicator` of the game, and the event streams send a `playerTyping` event for the
+/// player until `PLAYER_TYPING_TTL_SECONDS` passed without a notification.
+///
+/// URL endpoint: /game/{id}/chat/typing
+pub async fn notify_typing(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+) -> Result<StatusCode, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    if let Some(typing_indicators) = &app_state.typing_indicators {
+        typing_indicators
+            .mark_typing(&game_id, &player.player_id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Changes the content of a chat message.
+///
+/// Only the author can edit a message, within `MESSAGE_EDIT_WINDOW_SECONDS` of sending it. The new
+/// content runs through the word filter like a new message, and muted players can't edit.
+///
+/// URL endpoint: /chat/message/{id}
+pub async fn edit_chat_message(
+    State(app_state): State<AppState>,
+    Path(message_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+    Json(edit): Json<EditChatMessageDTO>,
+) -> Result<ChatMessage, Response> {
+    ensure_not_muted(&app_state, &player.player_id, &locale).await?;
+    let mut message = load_own_message(&app_state, &player, &message_id, &locale).await?;
+
+    let now = server_time();
+    message.edit(edit.content, now);
+    message
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+    let message = match app_state.chat_filter.moderate(message) {
+        Moderation::Clean(message) | Moderation::Censored(message) => message,
+        Moderation::Rejected(_) => return Err(banned_words_error(&locale)),
+    };
+
+    app_state
+        .chat_message_repository
+        .update_message(&message)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let event = GameEvent::ChatMessageEdited {
+        game_id: player.game_id,
+        message: message.clone(),
+    };
+    publish_chat_event(&app_state, &event, &message).await;
+
+    Ok(message)
+}
+
+/// Deletes a chat message.
+///
+/// Only the author can delete a message, within `MESSAGE_EDIT_WINDOW_SECONDS` of sending it. The
+/// message stays in the chat as a tombstone without content.
+///
+/// URL endpoint: /chat/message/{id}
+pub async fn delete_chat_message(
+    State(app_state): State<AppState>,
+    Path(message_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<ChatMessage, Response> {
+    let mut message = load_own_message(&app_state, &player, &message_id, &locale).await?;
+
+    message.delete(server_time());
+    app_state
+        .chat_message_repository
+        .update_message(&message)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let event = GameEvent::ChatMessageDeleted {
+        game_id: player.game_id,
+        message_id: message.id.clone(),
+        deleted_at: message.deleted_at,
+    };
+    publish_chat_event(&app_state, &event, &message).await;
+
+    Ok(message)
+}
+
+/// Mutes a player of the game in the chat.
+///
+/// Only the host is allowed to mute players. The player can't send or edit messages for
+/// `mute_minutes` of the configuration; muting them again restarts the mute. Everyone in the game
+/// learns about the mute with a `playerMuted` event.
+///
+/// URL endpoint: /game/{id}/mute/{player_id}
+pub async fn mute_player(
+    State(app_state): State<AppState>,
+    Path((game_id, muted_player_id)): Path<(String, String)>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    let game = mute_in_game(
+        &app_state,
+        game,
+        &muted_player_id,
+        &format!("player:{}", player.player_id),
+    )
+    .await?;
+
+    Ok(GameView::for_player(&game, &player.player_id, &locale))
+}
+
+/// Mutes a player of a game in the chat for `mute_minutes` of the configuration.
+///
+/// Publishes the `playerMuted` event and records the mute in the audit log.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `game` -> Fully hydrated game of the player
+/// - `muted_player_id` -> Id of the player to mute
+/// - `actor` -> Who muted the player, as it is written to the audit log
+///
+/// # Returns
+///
+/// The game with the updated mute of the player.
+///
+/// # Errors
+///
+/// Returns a 404 response if the player isn't part of the game, or the response of the failing
+/// database query.
+pub async fn mute_in_game(
+    app_state: &AppState,
+    mut game: Game,
+    muted_player_id: &str,
+    actor: &str,
+) -> Result<Game, Response> {
+    let Some(muted_player) = game
+        .players
+        .iter_mut()
+        .find(|player| player.id == muted_player_id)
+    else {
+        return Err(DatabaseQueryError::<Player>::new(
+            "The player isn't part of this game!".to_string(),
+            None,
+            StatusCode::NOT_FOUND,
+        )
+        .into_response());
+    };
+
+    let muted_until = server_time() + Duration::minutes(app_state.config.mute_minutes);
+    app_state
+        .player_repository
+        .set_muted_until(muted_player_id, muted_until)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    muted_player.muted_until = Some(muted_until);
+
+    let event = GameEvent::PlayerMuted {
+        game_id: game.id.clone(),
+        player_id: muted_player_id.to_string(),
+        muted_until,
+    };
+    publish_event(app_state, &event, None).await;
+    record_audit(
+        app_state,
+        actor,
+        AuditAction::MutePlayer,
+        Some(muted_player_id),
+        Some(format!("game {}", game.id)),
+    )
+    .await;
+
+    Ok(game)
+}
+
+/// Refuses a chat action of a player whose mute didn't end yet.
+async fn ensure_not_muted(
+    app_state: &AppState,
+    player_id: &str,
+    locale: &Locale,
+) -> Result<(), Response> {
+    let player = app_state
+        .player_repository
+        .get_player(player_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    match player.muted_until {
+        Some(muted_until) if player.is_muted(server_time()) => Err(AuthorizationError::new(
+            Message::PlayerMuted.format(locale, &[("until", &muted_until.to_rfc3339())]),
+            player.id,
+        )
+        .into_response()),
+        _ => Ok(()),
+    }
+}
+
+/// Whispers a message to another player of the game.
+///
+/// The whisper runs through the word filter; a filtered whisper is masked or refused, since a
+/// public moderation notice would reveal it.
+async fn whisper(
+    app_state: &AppState,
+    game_id: &str,
+    chat: &Chat,
+    message: ChatMessage,
+    locale: &Locale,
+) -> Result<ChatMessage, Response> {
+    let recipient_player_id = message.recipient_player_id.clone().unwrap_or_default();
+    let recipient = app_state
+        .player_repository
+        .get_player(&recipient_player_id)
+        .await
+        .ok()
+        .filter(|recipient| recipient.game_id == game_id);
+    if recipient.is_none() {
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "recipient_player_id",
+            "unknown_player",
+            &Message::UnknownRecipient.text(locale),
+        );
+        return Err(errors.into_response());
+    }
+
+    let message = match app_state.chat_filter.moderate(message) {
+        Moderation::Clean(message) | Moderation::Censored(message) => message,
+        Moderation::Rejected(_) => return Err(banned_words_error(locale)),
+    };
+    let message = app_state
+        .chat_message_repository
+        .add_message(message, &chat.id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    app_state
+        .chat_message_repository
+        .prune_messages(Some(&chat.id))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let event = GameEvent::Whisper {
+        game_id: game_id.to_string(),
+        message: message.clone(),
+    };
+    publish_chat_event(app_state, &event, &message).await;
+
+    Ok(message)
+}
+
+/// Publishes an event about a chat message to everyone who can read the message.
+///
+/// Events about a whisper only go to its sender and its recipient.
+async fn publish_chat_event(app_state: &AppState, event: &GameEvent, message: &ChatMessage) {
+    match message.whisper_players() {
+        Some(player_ids) => {
+            for player_id in player_ids {
+                publish_event(app_state, event, Some(player_id)).await;
+            }
+        }
+        None => publish_event(app_state, event, None).await,
+    }
+}
+
+/// Tells every player a chat message mentions about it with a `mentioned` event.
+///
+/// The message is already stored, so a failing lookup of the players only skips the events.
+async fn notify_mentions(app_state: &AppState, game_id: &str, message: &ChatMessage) {
+    if !message.content.contains('@') {
+        return;
+    }
+
+    let players = app_state
+        .player_repository
+        .get_all_players(Some(game_id.to_string()))
+        .await
+        .unwrap_or_default();
+    for player_id in find_mentions(&message.content, &players, &message.player_id) {
+        let event = GameEvent::Mentioned {
+            game_id: game_id.to_string(),
+            message: message.clone(),
+        };
+        publish_event(app_state, &event, Some(&player_id)).await;
+    }
+}
+
+/// Refuses a message the word filter rejected.
+fn banned_words_error(locale: &Locale) -> Response {
+    let mut errors = ValidationErrors::new();
+    errors.add(
+        "content",
+        "banned_words",
+        &Message::MessageBannedWords.text(locale),
+    );
+    errors.into_response()
+}
+
+/// Loads a message of the chat of the game of a player, which the player can still change.
+async fn load_own_message(
+    app_state: &AppState,
+    player: &AuthenticatedPlayer,
+    message_id: &str,
+    locale: &Locale,
+) -> Result<ChatMessage, Response> {
+    let chat = app_state
+        .chat_repository
+        .get_or_create_chat_of_game(&player.game_id, &app_state.chat_message_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let message = app_state
+        .chat_message_repository
+        .get_message_of_chat(message_id, &chat.id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    ensure_owner(player, &message.player_id).map_err(IntoResponse::into_response)?;
+    message
+        .ensure_changeable(server_time(), locale)
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(message)
+}
+
+/// Adds a message to the chat and stores it.
+///
+/// Deletes the oldest messages from the database if the chat was full.
+async fn store_message(
+    app_state: &AppState,
+    chat: &mut Chat,
+    message: ChatMessage,
+) -> Result<(), Response> {
+    if let Err(err) = chat.add_chat_message(message.clone()) {
+        let mut errors = ValidationErrors::new();
+        errors.add("content", "invalid", &err.message);
+        return Err(errors.into_response());
+    }
+
+    app_state
+        .chat_message_repository
+        .add_message(message, &chat.id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    app_state
+        .chat_message_repository
+        .prune_messages(Some(&chat.id))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(())
+}
@@ -0,0 +1,416 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::warn;
+use serde::Serialize;
+
+use crate::{
+    enums::achievement::Achievement,
+    errors::{
+        process_error::ProcessError,
+        validation_errors::{Validate, ValidationErrors},
+    },
+    handlers::{
+        achievement_handlers::unlock_achievements, event_handlers::publish_event,
+        game_handlers::load_game, score_handlers::record_round_scores,
+    },
+    i18n::{catalog::Message, locale::Locale},
+    logic::{
+        achievements::is_flawless_round,
+        rules::{
+            ensure_retractable, resolve_challenge, select_placed_cards, validate_declaration,
+            ChallengeOutcome,
+        },
+    },
+    metrics::analytics::{record_metric, Metric},
+    middleware::{
+        authentication::AuthenticatedPlayer,
+        authorization::{ensure_same_game, Owned},
+    },
+    router::router_provider::AppState,
+    types::{
+        claim::{ChallengeDTO, Claim, CreateClaimDTO},
+        game::{Game, UpdateGameDTO},
+        game_event::GameEvent,
+        game_view::GameView,
+        player::MoveTally,
+        round_summary::RoundSummary,
+    },
+    utils::time::server_time,
+};
+
+/// Outcome of a challenge.
+///
+/// # Props
+///
+/// - `claim` -> The challenged claim with its revealed cards
+/// - `truthful` -> Whether all placed cards matched the card to play
+/// - `loser_id` -> Id of the player who picked up the stack
+/// - `game` -> The game after the challenge, seen by the challenger
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengeResult {
+    /// The challenged claim with its revealed cards
+    pub claim: Claim,
+    /// Whether all placed cards matched the card to play
+    pub truthful: bool,
+    /// Id of the player who picked up the stack
+    pub loser_id: String,
+    /// The game after the challenge
+    pub game: GameView,
+}
+
+impl IntoResponse for ChallengeResult {
+    /// Converts the `ChallengeResult` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Places cards from the hand of a player face-down on the stack.
+///
+/// The player declares the placed cards, e.g. "three Kings"; the declaration is shown to everyone
+/// while the cards stay hidden. Only the player whose turn it is can make a claim. Afterwards the
+/// next player has to move.
+///
+/// URL endpoint: /game/{id}/claim
+pub async fn make_claim(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    locale: Locale,
+    Owned {
+        player,
+        data: claim_data,
+    }: Owned<CreateClaimDTO>,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    if claim_data.game_id != game_id {
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "game_id",
+            "mismatch",
+            "The claim has to be made in the requested game!",
+        );
+        return Err(errors.into_response());
+    }
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_players_turn(&game, &player.player_id, "make_claim", &locale)?;
+
+    // only the cards stored on the server are used, the client just names them
+    let hand = game
+        .players
+        .iter()
+        .find(|p| p.id == player.player_id)
+        .map(|p| p.assigned_cards.clone())
+        .unwrap_or_default();
+    let placed_cards =
+        select_placed_cards(&hand, &claim_data.cards).map_err(IntoResponse::into_response)?;
+
+    let claim = CreateClaimDTO {
+        cards: placed_cards,
+        ..claim_data
+    }
+    .into_claim(game.round_number, &game.card_to_play)
+    .map_err(IntoResponse::into_response)?;
+    claim
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+    validate_declaration(&claim, &game.card_to_play).map_err(IntoResponse::into_response)?;
+    let placed_cards = claim.number_of_cards;
+    let truthful = claim.is_truthful(&game.card_to_play);
+
+    // claim the revision first, so a conflicting move is rejected before anything is stored
+    let game_data = UpdateGameDTO {
+        id: game.id.clone(),
+        which_player_turn: game.next_player_id(&player.player_id),
+        expected_revision: Some(game.revision),
+        ..Default::default()
+    };
+    app_state
+        .game_repository
+        .update_game(game_data, &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    app_state
+        .claims_repository
+        .create_claim(claim, &app_state.card_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    record_metric(
+        &app_state,
+        Metric::ClaimPlayed {
+            cards: placed_cards,
+        },
+    );
+    // the player played on instead of challenging, so a bluff of the previous claim got away
+    if let Some(previous_claim) = game.claims.last() {
+        if previous_claim.created_by != player.player_id
+            && !previous_claim.is_truthful(&game.card_to_play)
+        {
+            add_to_tally(
+                &app_state,
+                &previous_claim.created_by,
+                MoveTally::successful_bluff(),
+            )
+            .await;
+        }
+    }
+    add_to_tally(&app_state, &player.player_id, MoveTally::claim(truthful)).await;
+
+    let game = load_game(&app_state, &game.id).await?;
+    publish_event(&app_state, &GameEvent::turn_changed(&game), None).await;
+
+    Ok(GameView::for_player(&game, &player.player_id, &locale))
+}
+
+/// Doubts the last claim of the round.
+///
+/// The cards of the claim are revealed. If the claim was a lie, the player who made it picks up the
+/// stack, otherwise the challenger does. The winner of the challenge starts the next round.
+///
+/// URL endpoint: /game/{id}/challenge
+pub async fn challenge_claim(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    locale: Locale,
+    Owned {
+        player,
+        data: challenge,
+    }: Owned<ChallengeDTO>,
+) -> Result<ChallengeResult, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let mut game = load_game(&app_state, &game_id).await?;
+    ensure_players_turn(&game, &challenge.player_id, "challenge_claim", &locale)?;
+
+    let mut claim = app_state
+        .claims_repository
+        .get_last_claim_of_round(
+            game.id.clone(),
+            game.round_number,
+            &app_state.card_repository,
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    if claim.created_by == challenge.player_id {
+        return Err(ProcessError::new(
+            Message::ChallengeOwnClaim.text(&locale),
+            "challenge_claim".to_string(),
+            Some(claim),
+        )
+        .into_response());
+    }
+
+    let ChallengeOutcome {
+        truthful,
+        loser_id,
+        winner_id,
+    } = resolve_challenge(&claim, &challenge.player_id, &game.card_to_play);
+    let summary = RoundSummary::new(
+        &game,
+        &claim,
+        &challenge.player_id,
+        &ChallengeOutcome {
+            truthful,
+            loser_id: loser_id.clone(),
+            winner_id: winner_id.clone(),
+        },
+    );
+    let flawless = truthful
+        && is_flawless_round(
+            &game.claims,
+            &claim.created_by,
+            game.round_number,
+            &game.card_to_play,
+        );
+    let challenged_round = game.round_number;
+
+    // claim the revision first, so a conflicting move is rejected before anything is stored
+    game.prep_for_new_round()
+        .map_err(IntoResponse::into_response)?;
+    game.which_player_turn = winner_id;
+
+    let game_data = UpdateGameDTO {
+        id: game.id.clone(),
+        which_player_turn: Some(game.which_player_turn.clone()),
+        round_number: Some(game.round_number),
+        card_to_play: Some(game.card_to_play.clone()),
+        expected_revision: Some(game.revision),
+        ..Default::default()
+    };
+    app_state
+        .game_repository
+        .update_game(game_data, &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    app_state
+        .claims_repository
+        .reveal_claim(&claim.id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    claim.revealed = true;
+    app_state
+        .card_repository
+        .move_stack_to_player(&game.id, challenged_round, &loser_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    record_metric(
+        &app_state,
+        Metric::ChallengeResolved {
+            bluff_caught: !truthful,
+        },
+    );
+    add_to_tally(
+        &app_state,
+        &challenge.player_id,
+        MoveTally::challenge(!truthful),
+    )
+    .await;
+    if flawless {
+        if let Some(claimant) = game.players.iter().find(|p| p.id == claim.created_by) {
+            unlock_achievements(
+                &app_state,
+                &game.id,
+                &claimant.id,
+                &claimant.identity(),
+                &[Achievement::FlawlessRound],
+            )
+            .await;
+        }
+    }
+
+    let game = load_game(&app_state, &game.id).await?;
+    record_round_scores(&app_state, &game, summary.round_number).await;
+    publish_event(&app_state, &GameEvent::RoundSummary(summary), None).await;
+    publish_event(&app_state, &GameEvent::turn_changed(&game), None).await;
+
+    Ok(ChallengeResult {
+        claim,
+        truthful,
+        loser_id,
+        game: GameView::for_player(&game, &challenge.player_id, &locale),
+    })
+}
+
+/// Takes back the last claim of the round, if the game uses the undo house rule.
+///
+/// Only the author can take back a claim, and only within the grace window set by the host and as
+/// long as nobody challenged or played on. The placed cards return to the hand of the author, who
+/// is to move again.
+///
+/// URL endpoint: /game/{id}/claim/undo
+pub async fn undo_claim(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_accepts_moves(&game, "undo_claim", &locale)?;
+    let claim_id = ensure_retractable(&game, &player.player_id, server_time(), &locale)
+        .map_err(IntoResponse::into_response)?
+        .id
+        .clone();
+
+    // the turn goes back to the author, whose countdown starts over; the revision is claimed
+    // before the claim is taken back, so a conflicting move is rejected first
+    let game_data = UpdateGameDTO {
+        id: game.id.clone(),
+        which_player_turn: Some(player.player_id.clone()),
+        expected_revision: Some(game.revision),
+        ..Default::default()
+    };
+    app_state
+        .game_repository
+        .update_game(game_data, &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    app_state
+        .claims_repository
+        .retract_claim(&claim_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game.id).await?;
+    publish_event(
+        &app_state,
+        &GameEvent::claim_retracted(&game, &claim_id),
+        None,
+    )
+    .await;
+
+    Ok(GameView::for_player(&game, &player.player_id, &locale))
+}
+
+// ----- Helper functions of the claim handlers -----
+
+/// Makes sure that the game accepts moves and that it is the turn of the player.
+///
+/// # Errors
+///
+/// Returns a `409 Conflict` response if the player isn't allowed to move.
+fn ensure_players_turn(
+    game: &Game,
+    player_id: &str,
+    name_of_function: &str,
+    locale: &Locale,
+) -> Result<(), Response> {
+    ensure_accepts_moves(game, name_of_function, locale)?;
+
+    if game.which_player_turn != player_id {
+        return Err(ProcessError::new(
+            Message::NotPlayersTurn.text(locale),
+            name_of_function.to_string(),
+            Some(Game::from_ref(game)),
+        )
+        .into_response());
+    }
+
+    Ok(())
+}
+
+/// Makes sure that the game accepts moves, e.g. that it isn't paused.
+///
+/// # Errors
+///
+/// Returns a `409 Conflict` response if no moves can be made.
+fn ensure_accepts_moves(
+    game: &Game,
+    name_of_function: &str,
+    locale: &Locale,
+) -> Result<(), Response> {
+    if !game.state.accepts_moves() {
+        return Err(ProcessError::new(
+            Message::NoMovesInState.format(locale, &[("state", game.state.as_str())]),
+            name_of_function.to_string(),
+            Some(Game::from_ref(game)),
+        )
+        .into_response());
+    }
+
+    Ok(())
+}
+
+/// Adds moves to the bluff and challenge counters of a player.
+///
+/// The counters only feed the statistics, so a failure is logged and the move stands.
+async fn add_to_tally(app_state: &AppState, player_id: &str, tally: MoveTally) {
+    if let Err(err) = app_state
+        .player_repository
+        .add_to_tally(player_id, tally)
+        .await
+    {
+        warn!("Moves of player {player_id} couldn't be counted: {err}");
+    }
+}
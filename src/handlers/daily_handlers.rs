@@ -0,0 +1,54 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    logic::daily_challenge::generate_daily_seed,
+    router::router_provider::AppState,
+    types::daily_challenge::{DailyChallenge, DailyChallengeView},
+    utils::time::server_time,
+};
+
+// constants
+/// Number of results returned with the daily challenge.
+const DAILY_RESULTS_SIZE: usize = 20;
+
+/// Returns the daily challenge of today with its best results so far.
+///
+/// The template of a day is created by the first request of that day. A game plays it if it is
+/// created with `daily` set.
+///
+/// URL endpoint: /daily
+pub async fn get_daily(State(app_state): State<AppState>) -> Result<DailyChallengeView, Response> {
+    let challenge = todays_challenge(&app_state).await?;
+    let games_played = app_state
+        .daily_challenge_repository
+        .count_games(challenge.date)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let results = app_state
+        .daily_challenge_repository
+        .get_results(challenge.date, DAILY_RESULTS_SIZE)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(DailyChallengeView::new(challenge, games_played, results))
+}
+
+/// Gets the daily challenge of the current UTC day and creates it if needed.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+///
+/// # Errors
+///
+/// Returns the response of the failing query.
+pub async fn todays_challenge(app_state: &AppState) -> Result<DailyChallenge, Response> {
+    app_state
+        .daily_challenge_repository
+        .get_or_create_challenge(server_time().date_naive(), generate_daily_seed())
+        .await
+        .map_err(IntoResponse::into_response)
+}
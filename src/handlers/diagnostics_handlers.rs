@@ -0,0 +1,129 @@
+use axum::extract::State;
+use worker::Env;
+
+use crate::{
+    auth::{
+        admin::{Admin, ADMIN_TOKEN_BINDING},
+        session_token::SESSION_SECRET_BINDING,
+        turnstile::TURNSTILE_SECRET_BINDING,
+    },
+    config::feature_flags::FEATURE_FLAGS_KV_BINDING,
+    durable::game_room::GAME_ROOM_BINDING,
+    jobs::{task_queue::TASK_QUEUE_BINDING, webhooks::WEBHOOK_SECRET_BINDING},
+    logic::chat_filter::CHAT_FILTER_KV_BINDING,
+    mail::mailer::MAILCHANNELS_API_KEY_BINDING,
+    metrics::analytics::METRICS_DATASET_BINDING,
+    repositories::{archive_storage::ARCHIVE_BUCKET_BINDING, game_cache::GAME_CACHE_KV_BINDING},
+    router::router_provider::AppState,
+    types::diagnostics::{BindingStatus, BuildInfo, Diagnostics},
+    utils::time::server_time,
+};
+
+// constants
+/// Name of the D1 database binding.
+const DATABASE_BINDING: &str = "DB";
+
+/// Reports the build, the configured bindings and the health of D1 to an operator.
+///
+/// A failing query doesn't fail the request, the error is part of the report instead.
+///
+/// URL endpoint: /admin/diagnostics
+pub async fn get_diagnostics(State(app_state): State<AppState>, _admin: Admin) -> Diagnostics {
+    let started_at = server_time();
+    let ping_result = app_state.maintenance_repository.ping().await;
+    let database_latency_ms = (server_time() - started_at).num_milliseconds();
+
+    let (database_latency_ms, database_error) = match ping_result {
+        Ok(()) => (Some(database_latency_ms), None),
+        Err(err) => (None, Some(err.to_string())),
+    };
+
+    let latest_migration = app_state
+        .maintenance_repository
+        .get_latest_schema_migration()
+        .await
+        .ok()
+        .flatten();
+
+    Diagnostics {
+        build: BuildInfo::current(),
+        environment: app_state.config.environment,
+        bindings: binding_statuses(&app_state.env),
+        latest_migration,
+        database_latency_ms,
+        database_error,
+    }
+}
+
+/// Checks which bindings and secrets the worker can access.
+fn binding_statuses(env: &Env) -> Vec<BindingStatus> {
+    let status = |name: &'static str, kind: &'static str, present: bool| BindingStatus {
+        name,
+        kind,
+        present,
+    };
+
+    vec![
+        status(DATABASE_BINDING, "d1", env.d1(DATABASE_BINDING).is_ok()),
+        status(
+            GAME_CACHE_KV_BINDING,
+            "kv",
+            env.kv(GAME_CACHE_KV_BINDING).is_ok(),
+        ),
+        status(
+            FEATURE_FLAGS_KV_BINDING,
+            "kv",
+            env.kv(FEATURE_FLAGS_KV_BINDING).is_ok(),
+        ),
+        status(
+            CHAT_FILTER_KV_BINDING,
+            "kv",
+            env.kv(CHAT_FILTER_KV_BINDING).is_ok(),
+        ),
+        status(
+            GAME_ROOM_BINDING,
+            "durable_object",
+            env.durable_object(GAME_ROOM_BINDING).is_ok(),
+        ),
+        status(
+            TASK_QUEUE_BINDING,
+            "queue",
+            env.queue(TASK_QUEUE_BINDING).is_ok(),
+        ),
+        status(
+            METRICS_DATASET_BINDING,
+            "analytics_engine",
+            env.analytics_engine(METRICS_DATASET_BINDING).is_ok(),
+        ),
+        status(
+            ARCHIVE_BUCKET_BINDING,
+            "r2",
+            env.bucket(ARCHIVE_BUCKET_BINDING).is_ok(),
+        ),
+        status(
+            SESSION_SECRET_BINDING,
+            "secret",
+            env.secret(SESSION_SECRET_BINDING).is_ok(),
+        ),
+        status(
+            TURNSTILE_SECRET_BINDING,
+            "secret",
+            env.secret(TURNSTILE_SECRET_BINDING).is_ok(),
+        ),
+        status(
+            ADMIN_TOKEN_BINDING,
+            "secret",
+            env.secret(ADMIN_TOKEN_BINDING).is_ok(),
+        ),
+        status(
+            WEBHOOK_SECRET_BINDING,
+            "secret",
+            env.secret(WEBHOOK_SECRET_BINDING).is_ok(),
+        ),
+        status(
+            MAILCHANNELS_API_KEY_BINDING,
+            "secret",
+            env.secret(MAILCHANNELS_API_KEY_BINDING).is_ok(),
+        ),
+    ]
+}
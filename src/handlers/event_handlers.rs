@@ -0,0 +1,261 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::warn;
+use serde::Deserialize;
+
+use crate::{
+    auth::fingerprint::client_fingerprint,
+    handlers::{game_handlers::load_game, matchmaking_handlers::sse_message},
+    i18n::locale::Locale,
+    metrics::prometheus::record_subscriber,
+    middleware::{
+        authentication::{AuthenticatedPlayer, Viewer},
+        authorization::{ensure_can_view, ensure_same_game},
+    },
+    router::router_provider::AppState,
+    types::{
+        game_event::GameEvent,
+        game_view::GameView,
+        resync::{ResyncRequest, ResyncResponse},
+        round_summary::RoundSummary,
+    },
+    utils::time::server_time,
+};
+
+// constants
+/// Maximum number of events sent in one response of the event stream.
+const EVENT_BATCH_SIZE: usize = 100;
+
+/// Milliseconds an `EventSource` waits before it reconnects to the stream.
+pub const RECONNECT_DELAY_MS: u32 = 1000;
+
+/// Header an `EventSource` sends with the id of the last received event when it reconnects.
+const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+
+/// Most missed events replayed by a resync; a client that missed more gets a fresh view.
+const MAX_RESYNC_EVENTS: usize = 50;
+
+/// Query parameters of the event stream.
+///
+/// # Props
+///
+/// - `after` -> Sequence number of the last received event, used on the first connection
+#[derive(Deserialize)]
+pub struct EventStreamQuery {
+    /// Sequence number of the last received event
+    pub after: Option<usize>,
+}
+
+/// Server-sent event stream of a game for its players and spectators.
+///
+/// A Worker can't keep a connection open to push events from other requests, so every response
+/// contains the events published since the last one the client received and then ends. The
+/// `EventSource` of the client reconnects after `RECONNECT_DELAY_MS` and sends the id of the
+/// last event in the `Last-Event-ID` header, so no event is lost in between.
+///
+/// Spectators only receive the events meant for everyone. Players who type in the chat are
+/// appended as `playerTyping` events without an id, since they aren't part of the event log.
+///
+/// URL endpoint: /game/{id}/events
+pub async fn get_events(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    viewer: Viewer,
+    headers: HeaderMap,
+    Query(query): Query<EventStreamQuery>,
+) -> Result<Response, Response> {
+    ensure_can_view(&viewer, &game_id).map_err(IntoResponse::into_response)?;
+
+    let subscriber = match viewer.player_id() {
+        Some(player_id) => player_id.to_string(),
+        None => format!(
+            "spectator:{}",
+            client_fingerprint(&headers).unwrap_or_default()
+        ),
+    };
+    record_subscriber(&game_id, &subscriber, server_time());
+
+    let after_sequence = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .or(query.after)
+        .unwrap_or_default();
+
+    let events = app_state
+        .game_event_repository
+        .get_events_since(
+            &game_id,
+            viewer.player_id(),
+            after_sequence,
+            EVENT_BATCH_SIZE,
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let mut body = format!("retry: {RECONNECT_DELAY_MS}\n\n");
+    if events.is_empty() {
+        body.push_str(": no new events\n\n");
+    }
+    for event in &events {
+        body.push_str(&event.to_sse_message());
+    }
+    for player_id in typing_players(&app_state, &game_id, viewer.player_id()).await {
+        let event = GameEvent::PlayerTyping {
+            game_id: game_id.clone(),
+            player_id,
+        };
+        body.push_str(&sse_message(event.event_type(), &event));
+    }
+
+    Ok((
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, "text/event-stream"),
+            (CACHE_CONTROL, "no-cache"),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Brings a reconnecting player back in sync with their game.
+///
+/// A player who missed up to `MAX_RESYNC_EVENTS` events gets them to replay. A player who missed
+/// more or doesn't know any event gets the current view instead; the latest sequence number is
+/// read before the game, so events published in between are delivered again by the stream
+/// rather than lost.
+///
+/// URL endpoint: /game/{id}/resync
+pub async fn resync_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+    Json(request): Json<ResyncRequest>,
+) -> Result<ResyncResponse, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    if request.last_sequence > 0 {
+        let events = app_state
+            .game_event_repository
+            .get_events_since(
+                &game_id,
+                Some(&player.player_id),
+                request.last_sequence,
+                MAX_RESYNC_EVENTS + 1,
+            )
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        if events.len() <= MAX_RESYNC_EVENTS {
+            let latest_sequence = events
+                .last()
+                .map_or(request.last_sequence, |event| event.sequence);
+
+            return Ok(ResyncResponse::Events {
+                events,
+                latest_sequence,
+            });
+        }
+    }
+
+    let latest_sequence = app_state
+        .game_event_repository
+        .get_latest_sequence(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let game = load_game(&app_state, &game_id).await?;
+
+    Ok(ResyncResponse::Snapshot {
+        game: Box::new(GameView::for_player(&game, &player.player_id, &locale)),
+        latest_sequence,
+    })
+}
+
+/// Gets the summary of a round that ended with a challenge.
+///
+/// The summary is read from the event log, so players and spectators who missed the event can
+/// look it up later.
+///
+/// URL endpoint: /game/{id}/rounds/{round_number}
+pub async fn get_round_summary(
+    State(app_state): State<AppState>,
+    Path((game_id, round_number)): Path<(String, usize)>,
+    viewer: Viewer,
+) -> Result<RoundSummary, Response> {
+    ensure_can_view(&viewer, &game_id).map_err(IntoResponse::into_response)?;
+
+    app_state
+        .game_event_repository
+        .get_round_summary(&game_id, round_number)
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+/// Publishes an event to the stream of its game.
+///
+/// The action that caused the event already succeeded, so the event is stored after the response
+/// was sent and a failure is only logged.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the event repository
+/// - `event` -> The event to be published
+/// - `recipient_player_id` -> Only this player receives the event; everyone if `None`
+pub async fn publish_event(
+    app_state: &AppState,
+    event: &GameEvent,
+    recipient_player_id: Option<&str>,
+) {
+    let repository = app_state.game_event_repository.clone();
+    let event = event.clone();
+    let recipient_player_id = recipient_player_id.map(str::to_string);
+
+    app_state
+        .execution
+        .defer(async move {
+            if let Err(err) = repository
+                .add_event(&event, recipient_player_id.as_deref())
+                .await
+            {
+                warn!(
+                    "Event {} of game {} couldn't be published: {err}",
+                    event.event_type(),
+                    event.game_id()
+                );
+            }
+        })
+        .await;
+}
+
+/// Gets the players of a game who type in the chat right now, except the viewer.
+///
+/// The indicators are cosmetic, so a failure is only logged and nobody is shown as typing.
+async fn typing_players(
+    app_state: &AppState,
+    game_id: &str,
+    viewer_id: Option<&str>,
+) -> Vec<String> {
+    let Some(typing_indicators) = &app_state.typing_indicators else {
+        return vec![];
+    };
+
+    match typing_indicators.typing_players(game_id).await {
+        Ok(player_ids) => player_ids
+            .into_iter()
+            .filter(|player_id| Some(player_id.as_str()) != viewer_id)
+            .collect(),
+        Err(err) => {
+            warn!("Typing players of game {game_id} couldn't be read: {err}");
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use chrono::DateTime;
+use serde::Deserialize;
+
+use crate::{
+    auth::export_token::{issue_export_token, verify_export_token, ExportClaims},
+    durable::game_room::is_archived_state,
+    errors::{
+        authentication_error::AuthenticationError, database_query_error::DatabaseQueryError,
+        process_error::ProcessError,
+    },
+    jobs::game_export::export_game,
+    middleware::{authentication::AuthenticatedPlayer, authorization::ensure_same_game},
+    router::router_provider::AppState,
+    types::{
+        game::Game,
+        game_archive::{ExportLink, ARCHIVE_CONTENT_TYPE},
+    },
+    utils::time::server_time,
+};
+
+/// Query parameters of a download link.
+///
+/// # Props
+///
+/// - `token` -> Signed export token of the link
+#[derive(Deserialize)]
+pub struct ExportDownloadQuery {
+    /// Signed export token of the link
+    pub token: String,
+}
+
+/// Mints a short-lived download link of a finished game for one of its players.
+///
+/// The game is exported right away if the export job didn't run yet.
+///
+/// URL endpoint: /game/{id}/export
+pub async fn get_export_link(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+) -> Result<ExportLink, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = app_state
+        .game_repository
+        .get_game_by_id(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if !is_archived_state(&game.state) {
+        return Err(ProcessError::new(
+            "Only finished games can be exported!".to_string(),
+            "get_export_link".to_string(),
+            Some(game),
+        )
+        .into_response());
+    }
+
+    let exported = match &app_state.archive_storage {
+        Some(archive_storage) => archive_storage
+            .has_archive(&game_id)
+            .await
+            .map_err(IntoResponse::into_response)?,
+        None => false,
+    };
+    if !exported {
+        export_game(&app_state, &game_id).await?;
+    }
+
+    let claims = ExportClaims::new(
+        game_id.clone(),
+        server_time(),
+        app_state.config.export_link_lifetime_minutes,
+    );
+    let token = issue_export_token(&claims, &app_state.session_secret);
+
+    Ok(ExportLink {
+        url: format!("/game/{game_id}/export/download?token={token}"),
+        expires_at: DateTime::from_timestamp(claims.expires_at, 0).unwrap_or_default(),
+    })
+}
+
+/// Downloads the export of a game as newline-delimited JSON.
+///
+/// Authorized by the token of the download link, so the link can be opened directly in a browser.
+///
+/// URL endpoint: /game/{id}/export/download
+pub async fn download_export(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    Query(query): Query<ExportDownloadQuery>,
+) -> Result<Response, Response> {
+    let claims = verify_export_token(&query.token, &app_state.session_secret, server_time())
+        .map_err(IntoResponse::into_response)?;
+    if claims.game_id != game_id {
+        return Err(AuthenticationError::new(
+            "The download link belongs to another game!".to_string(),
+        )
+        .into_response());
+    }
+
+    let not_found = || {
+        DatabaseQueryError::<Game>::new(
+            "The game wasn't exported!".to_string(),
+            None,
+            StatusCode::NOT_FOUND,
+        )
+        .into_response()
+    };
+    let archive_storage = app_state.archive_storage.as_ref().ok_or_else(not_found)?;
+    let archive = archive_storage
+        .get_archive(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?
+        .ok_or_else(not_found)?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, ARCHIVE_CONTENT_TYPE.to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"game-{game_id}.ndjson\""),
+            ),
+        ],
+        archive,
+    )
+        .into_response())
+}
@@ -0,0 +1,12 @@
+use axum::extract::State;
+
+use crate::{config::feature_flags::FeatureFlags, router::router_provider::AppState};
+
+/// Lists the experimental features and whether they are enabled in this environment.
+///
+/// Lets the frontend hide everything the backend doesn't offer.
+///
+/// URL endpoint: /features
+pub async fn get_features(State(app_state): State<AppState>) -> FeatureFlags {
+    app_state.features
+}
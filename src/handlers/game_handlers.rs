@@ -1,13 +1,175 @@
 // TODO: Set up all necessary handler functions regarding serving  with the game instance
 
 use axum::{
-    extract::Request,
-    http::{self, StatusCode},
+    extract::{Path, Query, Request, State},
+    http::{self, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use axum_macros::debug_handler;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
-use crate::types::game::Game;
+use crate::{
+    auth::{
+        fingerprint::CONNECTING_IP_HEADER,
+        rejoin_token::issue_rejoin_token,
+        session_token::SessionClaims,
+        spectator_token::{issue_spectator_token, SpectatorClaims},
+        turnstile::verify_turnstile_token,
+    },
+    enums::{
+        audit_action::AuditAction, game_state::GameState, visibility::Visibility,
+        webhook_event::WebhookEvent,
+    },
+    errors::{
+        database_query_error::DatabaseQueryError,
+        process_error::ProcessError,
+        validation_errors::{Validate, ValidationErrors},
+    },
+    handlers::{
+        audit_handlers::record_audit, auth_handlers::SessionResponse,
+        daily_handlers::todays_challenge, event_handlers::publish_event,
+        waitlist_handlers::promote_from_waitlist,
+    },
+    i18n::{catalog::Message, locale::Locale},
+    jobs::{task_queue::enqueue_game_end_tasks, webhooks::notify_webhooks},
+    logging::log_context,
+    logic::deck::Deck,
+    metrics::analytics::{record_metric, Metric},
+    middleware::{
+        authentication::{AuthenticatedPlayer, ClientIdentity, Viewer},
+        authorization::{ensure_can_view, ensure_host, ensure_same_game},
+    },
+    router::router_provider::AppState,
+    types::{
+        game::{CreateGameDTO, Game, UpdateGameDTO},
+        game_event::GameEvent,
+        game_settings::{GameSettings, MIN_PLAYERS},
+        game_view::GameView,
+        includes::Includes,
+        lobby::{LobbyFilter, LobbyGame, QuickPlayDTO},
+        player::{resolve_player_name, Player},
+        seating::SeatOrderDTO,
+    },
+    utils::{
+        etag::conditional_json,
+        game_service::{new_rng, normalize_join_code},
+        time::server_time,
+    },
+};
+
+// constants
+/// Open games a quick-play request tries to join before it creates a new one.
+const QUICK_PLAY_CANDIDATES: usize = 5;
+
+/// Response body of a newly created game.
+///
+/// # Props
+///
+/// - `game` -> The new game seen from the perspective of the host
+/// - `session` -> Session token of the host
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedGameResponse {
+    /// The new game seen from the perspective of the host
+    pub game: GameView,
+    /// Session token of the host
+    pub session: SessionResponse,
+}
+
+impl IntoResponse for CreatedGameResponse {
+    /// Converts the `CreatedGameResponse` into a response with status code 201.
+    fn into_response(self) -> Response {
+        (StatusCode::CREATED, Json(self)).into_response()
+    }
+}
+
+/// Response body of a quick-play request.
+///
+/// # Props
+///
+/// - `game` -> The joined or created game seen from the perspective of the new player
+/// - `session` -> Session and rejoin token of the new player
+/// - `created` -> Whether no open game was found and the player hosts a new one
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickPlayResponse {
+    /// The game seen from the perspective of the new player
+    pub game: GameView,
+    /// Session and rejoin token of the new player
+    pub session: SessionResponse,
+    /// Whether the game was created for the player
+    pub created: bool,
+}
+
+impl IntoResponse for QuickPlayResponse {
+    /// Converts the `QuickPlayResponse` into a response with status code 201 for a created game
+    /// and 200 for a joined one.
+    fn into_response(self) -> Response {
+        let status_code = match self.created {
+            true => StatusCode::CREATED,
+            false => StatusCode::OK,
+        };
+
+        (status_code, Json(self)).into_response()
+    }
+}
+
+/// Response body of a minted spectator link.
+///
+/// # Props
+///
+/// - `token` -> Signed spectator token
+/// - `url` -> Path of the redacted game view including the token
+/// - `events_url` -> Path of the event stream including the token
+/// - `expires_at` -> Point in time after which the link is invalid
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectatorLink {
+    /// Signed spectator token
+    pub token: String,
+    /// Path of the redacted game view including the token
+    pub url: String,
+    /// Path of the event stream including the token
+    pub events_url: String,
+    /// Point in time after which the link is invalid
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IntoResponse for SpectatorLink {
+    /// Converts the `SpectatorLink` into a response with status code 201.
+    fn into_response(self) -> Response {
+        (StatusCode::CREATED, Json(self)).into_response()
+    }
+}
+
+/// Query parameters of the game reads.
+///
+/// # Props
+///
+/// - `include` -> Comma-separated parts hydrated next to the game, `players`, `claims` and `chat`;
+///   all of them if `None`
+#[derive(Deserialize)]
+pub struct GameReadQuery {
+    /// Comma-separated parts hydrated next to the game
+    pub include: Option<String>,
+}
+
+impl GameReadQuery {
+    /// Returns the requested parts of the game.
+    ///
+    /// # Errors
+    ///
+    /// Returns the response of the `ValidationErrors` if a part is unknown.
+    pub fn includes(&self) -> Result<Includes, Response> {
+        match &self.include {
+            Some(include) => Includes::parse(include).map_err(IntoResponse::into_response),
+            None => Ok(Includes::default()),
+        }
+    }
+}
 
 /// Updates a game instance and modifies the database entries by using the provided id.
 ///
@@ -18,3 +180,1054 @@ pub async fn update_game(request: Request) -> Result<Json<Game>, StatusCode> {
 
     Err(http::StatusCode::OK)
 }
+
+/// Returns a game from the perspective of the requesting player.
+///
+/// The hands of all other players are hidden. Answers with `304 Not Modified` if the
+/// `If-None-Match` header carries the `ETag` of the current view. Clients which only follow the
+/// turn and the state pass e.g. `?include=players` to skip the claims and the chat; the parts
+/// left out are empty in the view.
+///
+/// URL endpoint: /game/{id}
+pub async fn get_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    Query(query): Query<GameReadQuery>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+    headers: HeaderMap,
+) -> Result<Response, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game_with(&app_state, &game_id, query.includes()?).await?;
+    let view = GameView::for_player(&game, &player.player_id, &locale);
+
+    Ok(conditional_json(&headers, game.revision, &view))
+}
+
+/// Returns a game to one of its players or to a spectator.
+///
+/// Spectators see neither the hand of any player nor the join code. Supports `If-None-Match`
+/// and `include` like `get_game`.
+///
+/// URL endpoint: /game/{id}/spectate
+pub async fn spectate_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    Query(query): Query<GameReadQuery>,
+    viewer: Viewer,
+    locale: Locale,
+    headers: HeaderMap,
+) -> Result<Response, Response> {
+    ensure_can_view(&viewer, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game_with(&app_state, &game_id, query.includes()?).await?;
+    let view = match viewer.player_id() {
+        Some(player_id) => GameView::for_player(&game, player_id, &locale),
+        None => GameView::for_spectator(&game, &locale),
+    };
+
+    Ok(conditional_json(&headers, game.revision, &view))
+}
+
+/// Lists the public games matching the filters of the query, those waiting for players by default.
+///
+/// Private games are left out. The filters `state`, `mode`, `language`, `region`, `hasOpenSeats`
+/// and `createdAfter` can be combined, e.g. `/games?language=de&hasOpenSeats=true`.
+///
+/// URL endpoint: /game/lobby or /games
+pub async fn get_lobby(
+    State(app_state): State<AppState>,
+    Query(filter): Query<LobbyFilter>,
+) -> Result<Json<Vec<LobbyGame>>, DatabaseQueryError<Game>> {
+    let games = app_state.game_repository.get_lobby_games(&filter).await?;
+
+    Ok(Json(games))
+}
+
+/// Resolves a join code to the game it belongs to.
+///
+/// The returned game id is then used to join the game with `/auth/session`.
+///
+/// URL endpoint: /game/join-by-code/{code}
+pub async fn join_by_code(
+    State(app_state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<LobbyGame, DatabaseQueryError<Game>> {
+    app_state
+        .game_repository
+        .get_lobby_game_by_join_code(&normalize_join_code(&code))
+        .await
+}
+
+/// Seats the requesting player in an open public game, or creates a new one if none has a seat.
+///
+/// One call for the "Play now" button. The casual games closest to being full are tried first, so
+/// they can start sooner. A taken name is numbered, since the client didn't choose the game. Only
+/// if a game has to be created the Turnstile token is checked; the player becomes its host then.
+/// Banned clients are rejected.
+///
+/// URL endpoint: /quick-play
+pub async fn quick_play(
+    State(app_state): State<AppState>,
+    client: ClientIdentity,
+    locale: Locale,
+    headers: HeaderMap,
+    Json(quick_play_data): Json<QuickPlayDTO>,
+) -> Result<QuickPlayResponse, Response> {
+    quick_play_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let candidates = app_state
+        .game_repository
+        .get_quick_play_games(QUICK_PLAY_CANDIDATES)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    for candidate in candidates {
+        // the summary can be outdated, another player may have taken the last seat meanwhile
+        let mut game = load_game(&app_state, &candidate.id).await?;
+        if game.state != GameState::WaitingForPlayers
+            || game.players.len() >= game.settings.max_players
+        {
+            continue;
+        }
+
+        let taken_names: Vec<String> = game
+            .players
+            .iter()
+            .map(|player| player.name.clone())
+            .collect();
+        let name = resolve_player_name("name", &quick_play_data.name, &taken_names, true, &locale)
+            .map_err(IntoResponse::into_response)?;
+        let mut player = Player::new(name, game.id.clone());
+        player.profile = quick_play_data.profile.clone();
+        player.fingerprint = client.fingerprint.clone();
+
+        let (player, session) = add_player_with_session(&app_state, player).await?;
+        game.players.push(player.clone());
+
+        return Ok(QuickPlayResponse {
+            game: GameView::for_player(&game, &player.id, &locale),
+            session,
+            created: false,
+        });
+    }
+
+    let remote_ip = headers
+        .get(CONNECTING_IP_HEADER)
+        .and_then(|value| value.to_str().ok());
+    verify_turnstile_token(
+        quick_play_data.turnstile_token.as_deref(),
+        &app_state.turnstile_secret,
+        remote_ip,
+    )
+    .await
+    .map_err(IntoResponse::into_response)?;
+
+    let game_data = CreateGameDTO {
+        host_name: quick_play_data.name,
+        settings: Some(app_state.config.default_settings()),
+        turnstile_token: None,
+        daily: false,
+    };
+    let (game, mut host) = game_data.into_game();
+    host.profile = quick_play_data.profile;
+    host.fingerprint = client.fingerprint;
+
+    let mut created_game = app_state
+        .game_repository
+        .add_game(game)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let (host, session) = add_player_with_session(&app_state, host).await?;
+    created_game.players = vec![host.clone()];
+    record_metric(&app_state, Metric::GameCreated);
+
+    Ok(QuickPlayResponse {
+        game: GameView::for_player(&created_game, &host.id, &locale),
+        session,
+        created: true,
+    })
+}
+
+/// Creates a new game with the requesting player as its host.
+///
+/// The host receives a session token and a rejoin token right away. The client has to pass a
+/// Turnstile challenge first, which keeps bots from flooding the lobby. Banned clients are
+/// rejected.
+///
+/// With `daily` set the game plays the daily challenge of today: its deck and its cards to play
+/// are the same as in every other daily game of the day.
+///
+/// URL endpoint: /game/create
+pub async fn create_game(
+    State(app_state): State<AppState>,
+    client: ClientIdentity,
+    locale: Locale,
+    headers: HeaderMap,
+    Json(mut game_data): Json<CreateGameDTO>,
+) -> Result<CreatedGameResponse, Response> {
+    let remote_ip = headers
+        .get(CONNECTING_IP_HEADER)
+        .and_then(|value| value.to_str().ok());
+    verify_turnstile_token(
+        game_data.turnstile_token.as_deref(),
+        &app_state.turnstile_secret,
+        remote_ip,
+    )
+    .await
+    .map_err(IntoResponse::into_response)?;
+
+    game_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+    let settings = game_data
+        .settings
+        .get_or_insert_with(|| app_state.config.default_settings());
+    app_state
+        .config
+        .validate_settings(settings)
+        .map_err(IntoResponse::into_response)?;
+
+    let daily = game_data.daily;
+    let (mut game, mut host) = game_data.into_game();
+    if daily {
+        let challenge = todays_challenge(&app_state).await?;
+        game.daily_date = Some(challenge.date);
+        game.daily_seed = Some(challenge.seed);
+    }
+    host.fingerprint = client.fingerprint;
+    let (rejoin_token, rejoin_token_hash) = issue_rejoin_token();
+    host.rejoin_token_hash = Some(rejoin_token_hash);
+    let host_id = host.id.clone();
+
+    let mut created_game = app_state
+        .game_repository
+        .add_game(game)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let host = app_state
+        .player_repository
+        .add_player(host)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    created_game.players = vec![host];
+    record_metric(&app_state, Metric::GameCreated);
+
+    let claims = SessionClaims::new(
+        host_id.clone(),
+        created_game.id.clone(),
+        server_time(),
+        app_state.config.session_lifetime_minutes,
+    );
+
+    Ok(CreatedGameResponse {
+        game: GameView::for_player(&created_game, &host_id, &locale),
+        session: SessionResponse::new(&claims, &app_state.session_secret, None)
+            .with_rejoin_token(rejoin_token),
+    })
+}
+
+/// Starts a game and deals the cards to all players.
+///
+/// Only the host is allowed to start the game. If the host chose to randomize the seats, the
+/// players are seated in a random order first and told with a `seatsChanged` event. The player on
+/// the first seat makes the first move.
+///
+/// URL endpoint: /game/{id}/start
+pub async fn start_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let mut game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    if game.players.len() < MIN_PLAYERS {
+        return Err(ProcessError::new(
+            Message::NotEnoughPlayers.format(&locale, &[("count", &MIN_PLAYERS.to_string())]),
+            "start_game".to_string(),
+            Some(Game::from_ref(&game)),
+        )
+        .into_response());
+    }
+    game.transition_to(GameState::InProgress)
+        .map_err(IntoResponse::into_response)?;
+    if game.settings.randomize_seats {
+        game.shuffle_seats(&mut new_rng());
+    }
+
+    // claim the revision first, so a second start is rejected before seats or hands are stored
+    let game_data = UpdateGameDTO {
+        id: game.id.clone(),
+        state: Some(GameState::InProgress),
+        which_player_turn: Some(game.first_seat_player_id()),
+        card_to_play: Some(game.card_for_round()),
+        expected_revision: Some(game.revision),
+        ..Default::default()
+    };
+    let mut updated_game = app_state
+        .game_repository
+        .update_game(game_data, &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    if game.settings.randomize_seats {
+        app_state
+            .player_repository
+            .set_seats(&game.players)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        publish_event(&app_state, &GameEvent::seats_changed(&game), None).await;
+    }
+    deal_cards(&app_state, &mut game).await?;
+    updated_game.players = game.players;
+    publish_event(&app_state, &GameEvent::turn_changed(&updated_game), None).await;
+    notify_webhooks(
+        &app_state,
+        &game_id,
+        WebhookEvent::GameStarted,
+        json!({
+            "hostPlayerId": updated_game.host_player_id,
+            "playerCount": updated_game.players.len(),
+        }),
+    )
+    .await;
+
+    Ok(GameView::for_player(
+        &updated_game,
+        &player.player_id,
+        &locale,
+    ))
+}
+
+/// Starts a running game over without creating a new lobby.
+///
+/// Only the host is allowed to reset the game. All claims, hands and the score history are
+/// cleared, new hands are dealt and the game continues with the first round, where the player on
+/// the first seat moves first. The players, the join code and the chat stay as they are.
+///
+/// URL endpoint: /game/{id}/reset
+pub async fn reset_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let mut game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+    game.reset(&locale).map_err(IntoResponse::into_response)?;
+
+    // claim the revision first, so a conflicting move is rejected before anything is cleared
+    let mut updated_game = app_state
+        .game_repository
+        .update_game(game.reset_update(), &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    app_state
+        .claims_repository
+        .delete_claims_of_game(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    app_state
+        .score_history_repository
+        .delete_history(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    for seated_player in &game.players {
+        app_state
+            .card_repository
+            .delete_cards_of_player(&seated_player.id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+    }
+    deal_cards(&app_state, &mut game).await?;
+    updated_game.players = game.players;
+    updated_game.chat = game.chat;
+
+    let reset_event = GameEvent::GameReset {
+        game_id: game_id.clone(),
+        which_player_turn: updated_game.which_player_turn.clone(),
+    };
+    publish_event(&app_state, &reset_event, None).await;
+    publish_event(&app_state, &GameEvent::turn_changed(&updated_game), None).await;
+    record_audit(
+        &app_state,
+        &format!("player:{}", player.player_id),
+        AuditAction::ResetGame,
+        Some(&game_id),
+        None,
+    )
+    .await;
+
+    Ok(GameView::for_player(
+        &updated_game,
+        &player.player_id,
+        &locale,
+    ))
+}
+
+/// Brings back a game that was abandoned while the host was away.
+///
+/// Only the host is allowed to reclaim the game, within `reclaim_grace_minutes` after it was
+/// abandoned and only while they still hold their seat. The game returns to the state it had
+/// before, which is read from the event log; the player to move gets a fresh turn.
+///
+/// URL endpoint: /game/{id}/reclaim
+pub async fn reclaim_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let mut game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    let abandonment = app_state
+        .game_event_repository
+        .get_abandonment(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    game.reclaim(
+        &abandonment,
+        server_time(),
+        app_state.config.reclaim_grace_minutes,
+        &locale,
+    )
+    .map_err(IntoResponse::into_response)?;
+
+    let mut updated_game = app_state
+        .game_repository
+        .update_game(game.reclaim_update(), &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    updated_game.players = game.players;
+    updated_game.claims = game.claims;
+    updated_game.chat = game.chat;
+
+    let reclaimed_event = GameEvent::GameReclaimed {
+        game_id,
+        state: updated_game.state.clone(),
+    };
+    publish_event(&app_state, &reclaimed_event, None).await;
+
+    Ok(GameView::for_player(
+        &updated_game,
+        &player.player_id,
+        &locale,
+    ))
+}
+
+/// Mints a short-lived link to watch a game without joining it.
+///
+/// Only the host is allowed to share spectator links. A spectator has read-only access to the
+/// redacted game view and the event stream and doesn't occupy a seat.
+///
+/// URL endpoint: /game/{id}/spectator-link
+pub async fn create_spectator_link(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+) -> Result<SpectatorLink, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = app_state
+        .game_repository
+        .get_game_by_id(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    let claims = SpectatorClaims::new(
+        game_id.clone(),
+        server_time(),
+        app_state.config.spectator_lifetime_minutes,
+    );
+    let token = issue_spectator_token(&claims, &app_state.session_secret);
+
+    Ok(SpectatorLink {
+        url: format!("/game/{game_id}/spectate?token={token}"),
+        events_url: format!("/game/{game_id}/events?token={token}"),
+        expires_at: DateTime::from_timestamp(claims.expires_at, 0).unwrap_or_default(),
+        token,
+    })
+}
+
+/// Removes a player and the cards in their hand from a game.
+///
+/// Only the host is allowed to kick players and the host can't kick themselves. If it was the
+/// turn of the kicked player, the next player in line has to move. The free seat goes to the
+/// first client on the waitlist of the game.
+///
+/// URL endpoint: /game/{id}/kick/{player_id}
+pub async fn kick_player(
+    State(app_state): State<AppState>,
+    Path((game_id, kicked_player_id)): Path<(String, String)>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    if game.is_host(&kicked_player_id) {
+        return Err(ProcessError::new(
+            Message::HostKicksThemself.text(&locale),
+            "kick_player".to_string(),
+            Some(Game::from_ref(&game)),
+        )
+        .into_response());
+    }
+
+    let Some(kicked_player_name) = game
+        .players
+        .iter()
+        .find(|player| player.id == kicked_player_id)
+        .map(|player| player.name.clone())
+    else {
+        return Err(DatabaseQueryError::<Player>::new(
+            "The player isn't part of this game!".to_string(),
+            None,
+            StatusCode::NOT_FOUND,
+        )
+        .into_response());
+    };
+
+    let updated_game =
+        remove_player_from_game(&app_state, game, &kicked_player_id, "kicked").await?;
+    let updated_game = promote_from_waitlist(&app_state, updated_game).await;
+    notify_webhooks(
+        &app_state,
+        &game_id,
+        WebhookEvent::PlayerKicked,
+        json!({
+            "playerId": kicked_player_id,
+            "playerName": kicked_player_name,
+        }),
+    )
+    .await;
+    record_audit(
+        &app_state,
+        &format!("player:{}", player.player_id),
+        AuditAction::KickPlayer,
+        Some(&kicked_player_id),
+        Some(format!("game {game_id}")),
+    )
+    .await;
+
+    Ok(GameView::for_player(
+        &updated_game,
+        &player.player_id,
+        &locale,
+    ))
+}
+
+/// Lets a player leave their game for good.
+///
+/// If it was the turn of the player, the next player in line has to move; if the player was the
+/// host, the player on the first seat becomes the new host. The free seat goes to the first client
+/// on the waitlist of the game.
+///
+/// URL endpoint: /game/{id}/leave
+pub async fn leave_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+) -> Result<StatusCode, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    let updated_game = remove_player_from_game(&app_state, game, &player.player_id, "left").await?;
+    promote_from_waitlist(&app_state, updated_game).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Changes the settings of a game that hasn't started yet.
+///
+/// Only the host is allowed to change the settings. If the deck theme changed, all players are
+/// told with a `deckThemeChanged` event, so every client renders the same cards.
+///
+/// URL endpoint: /game/{id}/settings
+pub async fn update_settings(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+    Json(settings): Json<GameSettings>,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    if game.state != GameState::WaitingForPlayers && game.state != GameState::Starting {
+        return Err(ProcessError::new(
+            Message::SettingsLocked.text(&locale),
+            "update_settings".to_string(),
+            Some(Game::from_ref(&game)),
+        )
+        .into_response());
+    }
+
+    let mut errors = match settings.validate(&locale) {
+        Ok(()) => ValidationErrors::new(),
+        Err(errors) => errors,
+    };
+    if let Err(limit_errors) = app_state.config.validate_settings(&settings) {
+        errors.merge(limit_errors);
+    }
+    if settings.max_players < game.players.len() {
+        errors.add(
+            "max_players",
+            "out_of_range",
+            "The maximum number of players can't be lower than the number of joined players!",
+        );
+    }
+    errors.into_result().map_err(IntoResponse::into_response)?;
+
+    let theme_changed = settings.deck_theme != game.settings.deck_theme;
+    let game_data = UpdateGameDTO {
+        id: game.id.clone(),
+        settings: Some(settings),
+        expected_revision: Some(game.revision),
+        ..Default::default()
+    };
+
+    let mut updated_game = app_state
+        .game_repository
+        .update_game(game_data, &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    updated_game.players = game.players;
+
+    if theme_changed {
+        let event = GameEvent::DeckThemeChanged {
+            game_id,
+            deck_theme: updated_game.settings.deck_theme.clone(),
+        };
+        publish_event(&app_state, &event, None).await;
+    }
+
+    Ok(GameView::for_player(
+        &updated_game,
+        &player.player_id,
+        &locale,
+    ))
+}
+
+/// Rearranges the seats of a game that hasn't started yet.
+///
+/// Only the host is allowed to rearrange the seats. The body has to list every player of the
+/// game exactly once; the players move in this order once the game runs. All players are told
+/// with a `seatsChanged` event.
+///
+/// URL endpoint: /game/{id}/seats
+pub async fn reorder_seats(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+    Json(seat_order): Json<SeatOrderDTO>,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+    seat_order
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let mut game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    if game.state != GameState::WaitingForPlayers && game.state != GameState::Starting {
+        return Err(ProcessError::new(
+            Message::SeatsLocked.text(&locale),
+            "reorder_seats".to_string(),
+            Some(Game::from_ref(&game)),
+        )
+        .into_response());
+    }
+    if !game.seat_players(&seat_order.player_ids) {
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "player_ids",
+            "mismatch",
+            "Every player of the game has to get exactly one seat!",
+        );
+        return Err(errors.into_response());
+    }
+
+    app_state
+        .player_repository
+        .set_seats(&game.players)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    publish_event(&app_state, &GameEvent::seats_changed(&game), None).await;
+
+    Ok(GameView::for_player(&game, &player.player_id, &locale))
+}
+
+/// Ends a game early and records its outcome.
+///
+/// Only the host is allowed to force the end of a game.
+///
+/// URL endpoint: /game/{id}/end
+pub async fn end_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let mut game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    game.finish().map_err(IntoResponse::into_response)?;
+
+    let mut updated_game = app_state
+        .game_repository
+        .update_game(game.outcome_update(), &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    updated_game.players = game.players;
+
+    enqueue_game_end_tasks(&app_state, &updated_game).await;
+
+    Ok(GameView::for_player(
+        &updated_game,
+        &player.player_id,
+        &locale,
+    ))
+}
+
+/// Pauses a running game, e.g. while a player briefly drops out of a friendly game.
+///
+/// No moves can be made and the countdown of the turn stands still until the game is resumed.
+/// Only the host is allowed to pause a game.
+///
+/// URL endpoint: /game/{id}/pause
+pub async fn pause_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    change_pause(&app_state, &game_id, &player, &locale, Game::pause).await
+}
+
+/// Resumes a paused game; the player to move keeps the time that was left of the turn.
+///
+/// Only the host is allowed to resume a game.
+///
+/// URL endpoint: /game/{id}/resume
+pub async fn resume_game(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+) -> Result<GameView, Response> {
+    change_pause(&app_state, &game_id, &player, &locale, Game::resume).await
+}
+
+// ----- Helper functions of the game handlers -----
+
+/// Pauses or resumes a game for its host and notifies everyone.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `game_id` -> Identifier of the game
+/// - `player` -> Player authenticated by the session token
+/// - `locale` -> Language of the texts of the response
+/// - `change` -> `Game::pause` or `Game::resume`
+///
+/// # Errors
+///
+/// Returns a response if the player isn't the host, the game can't change into the new state or
+/// a database query fails.
+async fn change_pause(
+    app_state: &AppState,
+    game_id: &str,
+    player: &AuthenticatedPlayer,
+    locale: &Locale,
+    change: fn(&mut Game, &Locale) -> Result<(), ProcessError<Game>>,
+) -> Result<GameView, Response> {
+    ensure_same_game(player, game_id).map_err(IntoResponse::into_response)?;
+
+    let mut game = load_game(app_state, game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    change(&mut game, locale).map_err(IntoResponse::into_response)?;
+
+    let mut updated_game = app_state
+        .game_repository
+        .update_game(game.pause_update(), &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    updated_game.players = game.players;
+    updated_game.claims = game.claims;
+    updated_game.chat = game.chat;
+
+    publish_event(app_state, &GameEvent::pause_changed(&updated_game), None).await;
+
+    Ok(GameView::for_player(
+        &updated_game,
+        &player.player_id,
+        locale,
+    ))
+}
+
+/// Deals a freshly shuffled deck to the players of a game and stores their hands.
+///
+/// A daily challenge deals the cards of its day.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `game` -> Fully hydrated game whose players have empty hands
+///
+/// # Errors
+///
+/// Returns a response if the deck can't be dealt or a database query fails.
+async fn deal_cards(app_state: &AppState, game: &mut Game) -> Result<(), Response> {
+    let mut deck = Deck::combined(game.settings.deck_count, 0);
+    deck.shuffle(&mut game.deck_rng());
+    match game.settings.hand_size {
+        Some(hand_size) => deck.deal_hands(&mut game.players, hand_size),
+        None => deck.deal(&mut game.players),
+    }
+    .map_err(IntoResponse::into_response)?;
+
+    for player in &game.players {
+        for card in &player.assigned_cards {
+            app_state
+                .card_repository
+                .create_card(card.clone(), player.id.clone())
+                .await
+                .map_err(IntoResponse::into_response)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes a player and the cards in their hand from a game and notifies everyone else.
+///
+/// If it was the turn of the removed player, the next player in line has to move. If the host is
+/// removed, the player on the first seat becomes the new host.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `game` -> Fully hydrated game the player is part of
+/// - `player_id` -> Id of the player to remove
+/// - `reason` -> Why the player is removed, sent along with the event
+///
+/// # Errors
+///
+/// Returns the response of the first failing database query.
+pub async fn remove_player_from_game(
+    app_state: &AppState,
+    mut game: Game,
+    player_id: &str,
+    reason: &str,
+) -> Result<Game, Response> {
+    let mut game_data = UpdateGameDTO {
+        id: game.id.clone(),
+        expected_revision: Some(game.revision),
+        ..Default::default()
+    };
+    let next_player_id = game.next_player_id(player_id);
+    if let Some(index) = game
+        .players
+        .iter()
+        .position(|player| player.id == player_id)
+    {
+        game.players.remove(index);
+
+        if game.which_player_turn == player_id && !game.players.is_empty() {
+            game_data.which_player_turn = next_player_id;
+        }
+    }
+    if game.is_host(player_id) && !game.players.is_empty() {
+        game_data.host_player_id = Some(game.first_seat_player_id());
+    }
+
+    // claim the revision first, so a conflicting move is rejected before the player is deleted
+    let mut updated_game = app_state
+        .game_repository
+        .update_game(game_data, &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    app_state
+        .card_repository
+        .delete_cards_of_player(player_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    app_state
+        .player_repository
+        .delete_player(player_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    updated_game.players = game.players;
+    updated_game.claims = game.claims;
+
+    let removed_event = GameEvent::PlayerRemoved {
+        game_id: updated_game.id.clone(),
+        player_id: player_id.to_string(),
+        reason: reason.to_string(),
+    };
+    publish_event(app_state, &removed_event, None).await;
+    publish_event(app_state, &GameEvent::turn_changed(&updated_game), None).await;
+
+    Ok(updated_game)
+}
+
+/// Adds a new player to a game and issues their session and rejoin token.
+///
+/// # Arguments
+///
+/// - `app_state` -> State holding all database repositories
+/// - `player` -> The new player
+///
+/// # Errors
+///
+/// Returns the response of the failing database query.
+async fn add_player_with_session(
+    app_state: &AppState,
+    mut player: Player,
+) -> Result<(Player, SessionResponse), Response> {
+    let (rejoin_token, rejoin_token_hash) = issue_rejoin_token();
+    player.rejoin_token_hash = Some(rejoin_token_hash);
+    let player = app_state
+        .player_repository
+        .add_player(player)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let claims = SessionClaims::new(
+        player.id.clone(),
+        player.game_id.clone(),
+        server_time(),
+        app_state.config.session_lifetime_minutes,
+    );
+    let session = SessionResponse::new(&claims, &app_state.session_secret, Some(player.clone()))
+        .with_rejoin_token(rejoin_token);
+
+    Ok((player, session))
+}
+
+/// Loads a game together with its players, their hands, the claims of the current round and the
+/// chat in a single round trip to D1.
+///
+/// # Arguments
+///
+/// - `app_state` -> State holding all database repositories
+/// - `game_id` -> Identifier of the game
+///
+/// # Errors
+///
+/// Returns the response of the failing database query.
+pub async fn load_game(app_state: &AppState, game_id: &str) -> Result<Game, Response> {
+    load_game_with(app_state, game_id, Includes::all()).await
+}
+
+/// Loads a game with only the requested parts hydrated, see `load_game`.
+///
+/// # Arguments
+///
+/// - `app_state` -> State holding all database repositories
+/// - `game_id` -> Identifier of the game
+/// - `includes` -> Parts hydrated next to the row of the game
+///
+/// # Errors
+///
+/// Returns the response of the failing database query.
+pub async fn load_game_with(
+    app_state: &AppState,
+    game_id: &str,
+    includes: Includes,
+) -> Result<Game, Response> {
+    log_context::set_game_id(game_id);
+
+    app_state
+        .game_repository
+        .get_snapshot(game_id, includes)
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+/// Creates a private game with a player for every seat; the first seat becomes the host.
+///
+/// Used for the games the server assembles, e.g. from the matchmaking queue or for a tournament.
+///
+/// # Arguments
+///
+/// - `app_state` -> State holding all database repositories
+/// - `seats` -> Name and fingerprint of every player of the game
+///
+/// # Returns
+///
+/// The game and its players in the order of the seats.
+///
+/// # Errors
+///
+/// Returns the message of the first failing database query.
+pub async fn create_private_game(
+    app_state: &AppState,
+    seats: &[(String, Option<String>)],
+) -> Result<(Game, Vec<Player>), String> {
+    let Some(((host_name, host_fingerprint), other_seats)) = seats.split_first() else {
+        return Err("A game needs at least one player!".to_string());
+    };
+    let mut settings = app_state.config.default_settings();
+    settings.visibility = Visibility::Private;
+    settings.max_players = seats.len();
+
+    let (game, mut host) = CreateGameDTO {
+        host_name: host_name.clone(),
+        settings: Some(settings),
+        turnstile_token: None,
+        daily: false,
+    }
+    .into_game();
+    host.fingerprint = host_fingerprint.clone();
+
+    let mut game = app_state
+        .game_repository
+        .add_game(game)
+        .await
+        .map_err(|err| err.message)?;
+    record_metric(app_state, Metric::GameCreated);
+
+    let mut players = vec![host];
+    for (name, fingerprint) in other_seats {
+        let taken_names: Vec<String> = players.iter().map(|player| player.name.clone()).collect();
+        let name = resolve_player_name("name", name, &taken_names, true, &Locale::En)
+            .unwrap_or_else(|_| name.clone());
+        let mut player = Player::new(name, game.id.clone());
+        player.fingerprint = fingerprint.clone();
+        players.push(player);
+    }
+    for player in players {
+        let player = app_state
+            .player_repository
+            .add_player(player)
+            .await
+            .map_err(|err| err.message)?;
+        game.players.push(player);
+    }
+
+    let players = game.players.clone();
+    Ok((game, players))
+}
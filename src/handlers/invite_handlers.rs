@@ -0,0 +1,243 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Duration;
+
+use crate::{
+    enums::game_state::GameState,
+    errors::{
+        database_query_error::DatabaseQueryError, error_response::ErrorResponseBody,
+        process_error::ProcessError, rate_limit_error::RateLimitError, validation_errors::Validate,
+    },
+    handlers::{
+        event_handlers::publish_event, game_handlers::load_game,
+        player_handlers::MAX_RECENT_OPPONENTS,
+    },
+    i18n::locale::Locale,
+    mail::invite_template::{render_invite, InviteDetails},
+    middleware::{
+        authentication::AuthenticatedPlayer,
+        authorization::{ensure_host, ensure_same_game},
+    },
+    router::router_provider::AppState,
+    types::{
+        game::Game,
+        game_event::GameEvent,
+        invitation::{CreateInvitationDTO, Invitation},
+        player::Player,
+    },
+    utils::time::server_time,
+};
+
+/// Sends an email with the join code and a deep link of a game to a friend.
+///
+/// Only the host is allowed to invite players, as long as the game waits for players. Each host
+/// may send `invites_per_hour` invitations within one hour.
+///
+/// URL endpoint: /game/{id}/invite
+pub async fn invite_player(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+    Json(invitation_data): Json<CreateInvitationDTO>,
+) -> Result<Invitation, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+    invitation_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let (Some(mailer), Some(frontend_url)) = (&app_state.mailer, &app_state.config.frontend_url)
+    else {
+        let body: ErrorResponseBody<()> =
+            ErrorResponseBody::new("Invitations can't be sent right now!".to_string(), None);
+        return Err((StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response());
+    };
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_invitable(&app_state, &game, &player.player_id, "invite_player").await?;
+    let Some(join_code) = &game.join_code else {
+        return Err(ProcessError::new(
+            "The game has no join code!".to_string(),
+            "invite_player".to_string(),
+            Some(Game::from_ref(&game)),
+        )
+        .into_response());
+    };
+
+    let host_name = game
+        .players
+        .iter()
+        .find(|seated| game.is_host(&seated.id))
+        .map(|host| host.name.as_str())
+        .unwrap_or("A friend");
+    let join_link = format!("{frontend_url}/join/{join_code}");
+    let email = render_invite(
+        invitation_data.email.trim(),
+        &InviteDetails {
+            host_name,
+            join_code,
+            join_link: &join_link,
+            note: invitation_data.trimmed_note(),
+        },
+    );
+    mailer
+        .send(&email)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    app_state
+        .invitation_repository
+        .add_invitation(Invitation::new(game_id, player.player_id))
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+/// Invites a recent opponent into the game with an event on the stream of their current game.
+///
+/// The same rules as for invitation emails apply: only the host may invite players while the game
+/// waits for players, and the invitation counts towards `invites_per_hour`. The invited player has
+/// to be one of the recent opponents of the host and play a game that hasn't ended.
+///
+/// URL endpoint: /game/{id}/invite-player/{other_id}
+pub async fn invite_recent_opponent(
+    State(app_state): State<AppState>,
+    Path((game_id, other_id)): Path<(String, String)>,
+    player: AuthenticatedPlayer,
+) -> Result<Invitation, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_invitable(
+        &app_state,
+        &game,
+        &player.player_id,
+        "invite_recent_opponent",
+    )
+    .await?;
+
+    let host = game
+        .players
+        .iter()
+        .find(|seated| seated.id == player.player_id)
+        .ok_or_else(|| {
+            DatabaseQueryError::<Player>::new(
+                "The host couldn't be found!".to_string(),
+                None,
+                StatusCode::NOT_FOUND,
+            )
+            .into_response()
+        })?;
+
+    // only players who shared a game with the host can be reached
+    let opponents = app_state
+        .game_result_repository
+        .get_recent_opponents(&host.identity(), MAX_RECENT_OPPONENTS)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let Some(opponent_game_id) = opponents
+        .into_iter()
+        .find(|opponent| opponent.active_player_id.as_deref() == Some(other_id.as_str()))
+        .and_then(|opponent| opponent.active_game_id)
+    else {
+        return Err(DatabaseQueryError::<Player>::new(
+            "The player isn't a recent opponent who is online!".to_string(),
+            None,
+            StatusCode::NOT_FOUND,
+        )
+        .into_response());
+    };
+    if opponent_game_id == game.id {
+        return Err(ProcessError::new(
+            "The player already plays this game!".to_string(),
+            "invite_recent_opponent".to_string(),
+            Some(Game::from_ref(&game)),
+        )
+        .into_response());
+    }
+
+    let invitation = app_state
+        .invitation_repository
+        .add_invitation(Invitation::new(game_id, player.player_id))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let event = GameEvent::PlayerInvited {
+        game_id: opponent_game_id,
+        player_id: other_id.clone(),
+        invited_to_game_id: game.id.clone(),
+        join_code: game.join_code.clone(),
+        invited_by: host.name.clone(),
+    };
+    publish_event(&app_state, &event, Some(&other_id)).await;
+
+    Ok(invitation)
+}
+
+// ----- Helper functions of the invite handlers -----
+
+/// Checks whether a player may send an invitation for a game right now.
+///
+/// Only the host may invite players, as long as the game waits for players and isn't full. Each
+/// host may send `invites_per_hour` invitations within one hour.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the invitation repository
+/// - `game` -> The game players are invited to
+/// - `player_id` -> Id of the player sending the invitation
+/// - `action` -> Name of the action reported in a `ProcessError`
+///
+/// # Errors
+///
+/// Returns the error response if the invitation isn't allowed.
+async fn ensure_invitable(
+    app_state: &AppState,
+    game: &Game,
+    player_id: &str,
+    action: &str,
+) -> Result<(), Response> {
+    ensure_host(game, player_id).map_err(IntoResponse::into_response)?;
+
+    let not_possible = |message: &str| {
+        ProcessError::new(
+            message.to_string(),
+            action.to_string(),
+            Some(Game::from_ref(game)),
+        )
+        .into_response()
+    };
+    if game.state != GameState::WaitingForPlayers {
+        return Err(not_possible(
+            "Players can only be invited while the game waits for players!",
+        ));
+    }
+    if game.players.len() >= game.settings.max_players {
+        return Err(not_possible("The game is already full!"));
+    }
+
+    // count the invitations of the last hour towards the limit of the host
+    let now = server_time();
+    let window = Duration::hours(1);
+    let recent_invitations = app_state
+        .invitation_repository
+        .get_invitations_since(player_id, now - window)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if recent_invitations.len() >= app_state.config.invites_per_hour {
+        let retry_after = recent_invitations
+            .first()
+            .map(|oldest| (oldest.sent_at + window - now).num_seconds())
+            .unwrap_or(window.num_seconds());
+        return Err(RateLimitError::new(
+            "Too many invitations sent! Please wait a moment.".to_string(),
+            retry_after,
+        )
+        .into_response());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::{
+    enums::leaderboard_period::LeaderboardPeriod, router::router_provider::AppState,
+    types::leaderboard::LeaderboardPage, utils::time::server_time,
+};
+
+// constants
+/// Number of standings returned if the client doesn't ask for a page size.
+const DEFAULT_LEADERBOARD_PAGE_SIZE: usize = 50;
+
+/// Highest number of standings returned at once.
+const MAX_LEADERBOARD_PAGE_SIZE: usize = 200;
+
+/// Query parameters of the leaderboard.
+///
+/// # Props
+///
+/// - `period` -> `weekly`, `alltime` or `season`; `alltime` if `None`
+/// - `season` -> Number of a past or the current season; implies the `season` period, the current
+///   season if `None`
+/// - `offset` -> `nextOffset` of the previous page; the best players if `None`
+/// - `limit` -> Number of standings, at most `MAX_LEADERBOARD_PAGE_SIZE`
+#[derive(Deserialize)]
+pub struct LeaderboardQuery {
+    /// `weekly`, `alltime` or `season`
+    #[serde(default)]
+    pub period: LeaderboardPeriod,
+    /// Number of a season
+    pub season: Option<usize>,
+    /// `nextOffset` of the previous page
+    pub offset: Option<usize>,
+    /// Number of standings
+    pub limit: Option<usize>,
+}
+
+/// Lists the players with the most wins, the weekly leaderboard starts on Monday 00:00 UTC.
+///
+/// Every season has its own leaderboard, which stays available after the season ended. Standings
+/// are updated once the statistics of a finished game were aggregated.
+///
+/// URL endpoint: /leaderboard
+pub async fn get_leaderboard(
+    State(app_state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<LeaderboardPage, Response> {
+    let page_size = query
+        .limit
+        .unwrap_or(DEFAULT_LEADERBOARD_PAGE_SIZE)
+        .clamp(1, MAX_LEADERBOARD_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0);
+    let period = match query.season {
+        Some(_) => LeaderboardPeriod::Season,
+        None => query.period,
+    };
+
+    let season = match (period, query.season) {
+        (LeaderboardPeriod::Season, Some(number)) => {
+            Some(app_state.season_repository.get_season(number).await)
+        }
+        (LeaderboardPeriod::Season, None) => {
+            Some(app_state.season_repository.get_current_season().await)
+        }
+        _ => None,
+    }
+    .transpose()
+    .map_err(IntoResponse::into_response)?;
+    let (period_start, _) = match &season {
+        Some(season) => season.bounds(),
+        None => period.bounds(server_time()),
+    };
+
+    let entries = app_state
+        .leaderboard_repository
+        .get_entries(period, period_start, offset, page_size)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let mut page = LeaderboardPage::new(period, period_start, entries, offset, page_size);
+    page.season = season;
+
+    Ok(page)
+}
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::warn;
+use serde::Serialize;
+
+use crate::{
+    auth::session_token::SessionClaims,
+    errors::{
+        database_query_error::DatabaseQueryError, error_response::ErrorResponseBody,
+        validation_errors::Validate,
+    },
+    handlers::{
+        auth_handlers::SessionResponse, event_handlers::RECONNECT_DELAY_MS,
+        game_handlers::create_private_game,
+    },
+    i18n::locale::Locale,
+    middleware::authentication::ClientIdentity,
+    repositories::matchmaking::Matchmaking,
+    router::router_provider::AppState,
+    types::matchmaking::{EnterQueueDTO, MatchAssignment, QueueTicket, TicketStatus},
+    utils::time::server_time,
+};
+
+/// Data of the `matchFound` event telling a waiting player which game to join.
+///
+/// # Props
+///
+/// - `game_id` -> The assembled game
+/// - `session` -> Session token of the player created for the ticket
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchFound {
+    /// The assembled game
+    pub game_id: String,
+    /// Session token of the player created for the ticket
+    pub session: SessionResponse,
+}
+
+/// Puts a solo player into the public matchmaking queue.
+///
+/// Once `MAX_MATCH_PLAYERS` players wait, or at least `MIN_MATCH_PLAYERS` waited for
+/// `MATCHMAKING_TIMEOUT_SECONDS`, they are put into a new private game. The player learns about it
+/// on the event stream of the returned ticket. Banned clients are rejected.
+///
+/// URL endpoint: /matchmaking/queue
+pub async fn enter_queue(
+    State(app_state): State<AppState>,
+    client: ClientIdentity,
+    locale: Locale,
+    Json(queue_data): Json<EnterQueueDTO>,
+) -> Result<TicketStatus, Response> {
+    queue_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+    let matchmaking = require_matchmaking(&app_state)?;
+
+    let ticket = QueueTicket::new(queue_data.player_name, client.fingerprint);
+    let status = matchmaking
+        .enter(&ticket)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if !assemble_matches(&app_state, matchmaking).await {
+        return Ok(status);
+    }
+
+    // the new ticket may have completed a game
+    matchmaking
+        .status(&ticket.id)
+        .await
+        .map_err(IntoResponse::into_response)
+        .map(|current| current.unwrap_or(status))
+}
+
+/// Takes a ticket out of the matchmaking queue.
+///
+/// URL endpoint: /matchmaking/queue/{ticket_id}
+pub async fn leave_queue(
+    State(app_state): State<AppState>,
+    Path(ticket_id): Path<String>,
+) -> Result<StatusCode, Response> {
+    let matchmaking = require_matchmaking(&app_state)?;
+
+    matchmaking
+        .leave(&ticket_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Server-sent event stream of a ticket in the matchmaking queue.
+///
+/// Like the event stream of a game, every response ends right away and the `EventSource`
+/// reconnects after `RECONNECT_DELAY_MS`. Each response contains a `queueStatus` event with the
+/// position of the ticket, or the `matchFound` event with the game and a session token once the
+/// ticket was assigned. Every connection also assembles games whose players waited long enough.
+///
+/// URL endpoint: /matchmaking/queue/{ticket_id}/events
+pub async fn get_queue_events(
+    State(app_state): State<AppState>,
+    Path(ticket_id): Path<String>,
+) -> Result<Response, Response> {
+    let matchmaking = require_matchmaking(&app_state)?;
+    assemble_matches(&app_state, matchmaking).await;
+
+    let status = matchmaking
+        .status(&ticket_id)
+        .await
+        .map_err(IntoResponse::into_response)?
+        .ok_or_else(|| {
+            DatabaseQueryError::<QueueTicket>::new(
+                "The ticket isn't in the matchmaking queue!".to_string(),
+                None,
+                StatusCode::NOT_FOUND,
+            )
+            .into_response()
+        })?;
+
+    let mut body = format!("retry: {RECONNECT_DELAY_MS}\n\n");
+    match &status.assignment {
+        Some(assignment) => {
+            let claims = SessionClaims::new(
+                assignment.player_id.clone(),
+                assignment.game_id.clone(),
+                server_time(),
+                app_state.config.session_lifetime_minutes,
+            );
+            let match_found = MatchFound {
+                game_id: assignment.game_id.clone(),
+                session: SessionResponse::new(&claims, &app_state.session_secret, None),
+            };
+            body.push_str(&sse_message("matchFound", &match_found));
+        }
+        None => body.push_str(&sse_message("queueStatus", &status)),
+    }
+
+    Ok((
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, "text/event-stream"),
+            (CACHE_CONTROL, "no-cache"),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Assembles games from the waiting players until the queue holds no complete game anymore.
+///
+/// Tickets of a game that couldn't be created are put back into the queue.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `matchmaking` -> Client of the queue
+///
+/// # Returns
+///
+/// `true` if at least one game was assembled.
+pub async fn assemble_matches(app_state: &AppState, matchmaking: &Matchmaking) -> bool {
+    let mut assembled = false;
+
+    loop {
+        let tickets = match matchmaking.take().await {
+            Ok(tickets) if !tickets.is_empty() => tickets,
+            Ok(_) => return assembled,
+            Err(err) => {
+                warn!("Tickets couldn't be taken from the matchmaking queue: {err}");
+                return assembled;
+            }
+        };
+
+        match create_match(app_state, &tickets).await {
+            Ok(assignments) => {
+                assembled = true;
+                if let Err(err) = matchmaking.assign(&assignments).await {
+                    warn!("Assembled game couldn't be handed to its players: {err}");
+                }
+            }
+            Err(message) => {
+                warn!("Game of the matchmaking queue couldn't be created: {message}");
+                for ticket in &tickets {
+                    if let Err(err) = matchmaking.enter(ticket).await {
+                        warn!("Ticket couldn't be put back into the matchmaking queue: {err}");
+                    }
+                }
+                return assembled;
+            }
+        }
+    }
+}
+
+// ----- Helper functions of the matchmaking handlers -----
+
+/// Creates a private game with a player for every ticket; the oldest ticket becomes the host.
+///
+/// # Returns
+///
+/// The assignment of every ticket, keyed by the id of the ticket.
+async fn create_match(
+    app_state: &AppState,
+    tickets: &[QueueTicket],
+) -> Result<HashMap<String, MatchAssignment>, String> {
+    let seats: Vec<(String, Option<String>)> = tickets
+        .iter()
+        .map(|ticket| (ticket.player_name.clone(), ticket.fingerprint.clone()))
+        .collect();
+    let (game, players) = create_private_game(app_state, &seats).await?;
+
+    let now = server_time();
+    Ok(tickets
+        .iter()
+        .zip(players)
+        .map(|(ticket, player)| {
+            (
+                ticket.id.clone(),
+                MatchAssignment {
+                    game_id: game.id.clone(),
+                    player_id: player.id,
+                    assigned_at: now,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Returns the client of the matchmaking queue.
+///
+/// # Errors
+///
+/// Returns a `503 Service Unavailable` response if the queue isn't bound.
+fn require_matchmaking(app_state: &AppState) -> Result<&Matchmaking, Response> {
+    app_state.matchmaking.as_ref().ok_or_else(|| {
+        let body: ErrorResponseBody<()> =
+            ErrorResponseBody::new("Matchmaking isn't available right now!".to_string(), None);
+        (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
+    })
+}
+
+/// Formats a message of a `text/event-stream` without an id.
+pub fn sse_message<T: Serialize>(event_type: &str, data: &T) -> String {
+    format!(
+        "event: {event_type}\ndata: {}\n\n",
+        serde_json::to_string(data).unwrap_or_default()
+    )
+}
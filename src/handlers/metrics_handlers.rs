@@ -0,0 +1,39 @@
+use axum::{
+    extract::State,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    auth::admin::Admin, metrics::prometheus::render_metrics, router::router_provider::AppState,
+    utils::time::server_time,
+};
+
+// constants
+/// Content type of the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Exposes the counters and histograms of the worker to a Prometheus scraper.
+///
+/// Every isolate keeps its own counters, so a scrape only covers the isolate that answers it;
+/// only the number of active games is read from D1. Analytics Engine holds the totals of every
+/// isolate.
+///
+/// URL endpoint: /metrics
+pub async fn get_metrics(
+    State(app_state): State<AppState>,
+    _admin: Admin,
+) -> Result<Response, Response> {
+    let active_games = app_state
+        .game_repository
+        .count_active_games()
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok((
+        StatusCode::OK,
+        [(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        render_metrics(active_games, server_time()),
+    )
+        .into_response())
+}
@@ -1,4 +1,28 @@
+pub mod achievement_handlers;
+pub mod admin_handlers;
+pub mod audit_handlers;
+pub mod auth_handlers;
+pub mod backup_handlers;
 pub mod chat_handlers;
+pub mod claim_handlers;
+pub mod daily_handlers;
+pub mod diagnostics_handlers;
+pub mod event_handlers;
+pub mod export_handlers;
+pub mod feature_handlers;
 pub mod game_handlers;
+pub mod invite_handlers;
+pub mod leaderboard_handlers;
+pub mod matchmaking_handlers;
+pub mod metrics_handlers;
+pub mod moderation_handlers;
 pub mod player_handlers;
+pub mod reaction_handlers;
+pub mod schema_handlers;
+pub mod score_handlers;
+pub mod season_handlers;
+pub mod share_handlers;
 pub mod status_handlers;
+pub mod tournament_handlers;
+pub mod waitlist_handlers;
+pub mod webhook_handlers;
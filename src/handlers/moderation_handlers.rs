@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    errors::validation_errors::{Validate, ValidationErrors},
+    i18n::locale::Locale,
+    middleware::authorization::{ensure_same_game, Owned},
+    router::router_provider::AppState,
+    types::moderation::{CreateReportDTO, Report},
+};
+
+/// Reports another player of the same game for an admin to review.
+///
+/// URL endpoint: /game/{id}/report
+pub async fn report_player(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    locale: Locale,
+    Owned {
+        player,
+        data: report_data,
+    }: Owned<CreateReportDTO>,
+) -> Result<Report, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+    report_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let reported_player = app_state
+        .player_repository
+        .get_player(&report_data.reported_player_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if reported_player.game_id != game_id {
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "reported_player_id",
+            "not_in_game",
+            "Only players of the same game can be reported!",
+        );
+        return Err(errors.into_response());
+    }
+
+    app_state
+        .moderation_repository
+        .add_report(report_data.into_report(game_id, reported_player.fingerprint))
+        .await
+        .map_err(IntoResponse::into_response)
+}
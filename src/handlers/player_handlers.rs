@@ -0,0 +1,240 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::{
+    errors::validation_errors::{Validate, ValidationErrors},
+    i18n::locale::Locale,
+    middleware::authorization::Owned,
+    router::router_provider::AppState,
+    types::{
+        game_history::{GameHistoryEntry, GameHistoryPage, HistoryCursor},
+        player::{resolve_player_name, Player, UpdatePlayerDTO},
+        player_stats::PlayerStats,
+        recent_opponent::RecentOpponentList,
+    },
+    utils::ndjson::{accepts_ndjson, stream_ndjson},
+};
+
+// constants
+/// Number of games returned if the client doesn't ask for a page size.
+const DEFAULT_HISTORY_PAGE_SIZE: usize = 20;
+
+/// Highest number of games returned at once.
+const MAX_HISTORY_PAGE_SIZE: usize = 100;
+
+/// Number of recent opponents returned if the client doesn't ask for a limit.
+const DEFAULT_RECENT_OPPONENTS: usize = 20;
+
+/// Highest number of recent opponents returned at once.
+pub const MAX_RECENT_OPPONENTS: usize = 50;
+
+/// Query parameters of the game history.
+///
+/// # Props
+///
+/// - `limit` -> Number of games, at most `MAX_HISTORY_PAGE_SIZE`
+/// - `cursor` -> `nextCursor` of the previous page; the newest games if `None`
+#[derive(Deserialize)]
+pub struct GameHistoryQuery {
+    /// Number of games
+    pub limit: Option<usize>,
+    /// `nextCursor` of the previous page
+    pub cursor: Option<String>,
+}
+
+/// Query parameters of the recent opponents.
+///
+/// # Props
+///
+/// - `limit` -> Number of opponents, at most `MAX_RECENT_OPPONENTS`
+#[derive(Deserialize)]
+pub struct RecentOpponentsQuery {
+    /// Number of opponents
+    pub limit: Option<usize>,
+}
+
+/// Updates the name, the profile or the hand order of the requesting player.
+///
+/// The score, the hand and the heartbeat timestamp are managed by the server and ignored. A name
+/// another player of the game already uses is refused with the code `name_taken`.
+///
+/// URL endpoint: /player/update
+pub async fn update_player(
+    State(app_state): State<AppState>,
+    locale: Locale,
+    Owned {
+        player,
+        data: mut player_data,
+    }: Owned<UpdatePlayerDTO>,
+) -> Result<Player, Response> {
+    player_data.score = None;
+    player_data.assigned_cards = None;
+    player_data.last_time_update_requested = None;
+    player_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    if let Some(name) = &player_data.name {
+        let taken_names = app_state
+            .player_repository
+            .get_player_names(&player.game_id, Some(&player.player_id))
+            .await
+            .map_err(IntoResponse::into_response)?;
+        resolve_player_name("name", name, &taken_names, false, &locale)
+            .map_err(IntoResponse::into_response)?;
+    }
+
+    app_state
+        .player_repository
+        .update_player(player_data)
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+/// Returns the lifetime statistics of a player.
+///
+/// The statistics belong to the identity of the player, so they cover every game played from the
+/// same client, not only the game of the player id. They change once a finished game was
+/// aggregated.
+///
+/// URL endpoint: /player/{id}/stats
+pub async fn get_player_stats(
+    State(app_state): State<AppState>,
+    Path(player_id): Path<String>,
+) -> Result<PlayerStats, Response> {
+    let player = app_state
+        .player_repository
+        .get_player(&player_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let identity = player.identity();
+
+    let stats = app_state
+        .game_result_repository
+        .get_player_stats(&identity)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(stats
+        .unwrap_or_else(|| PlayerStats::empty(identity, player.name))
+        .with_rates())
+}
+
+/// Lists the finished games of a player, newest first.
+///
+/// Like the statistics, the history covers every game played from the same client. With
+/// `Accept: application/x-ndjson` every game after `cursor` is streamed as one line instead of a
+/// page, up to `MAX_STREAMED_ROWS`.
+///
+/// URL endpoint: /player/{id}/games
+pub async fn get_player_games(
+    State(app_state): State<AppState>,
+    Path(player_id): Path<String>,
+    Query(query): Query<GameHistoryQuery>,
+    headers: HeaderMap,
+) -> Result<Response, Response> {
+    let page_size = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_PAGE_SIZE)
+        .clamp(1, MAX_HISTORY_PAGE_SIZE);
+    let cursor = match query.cursor.as_deref() {
+        Some(cursor) => match HistoryCursor::decode(cursor) {
+            Some(cursor) => Some(cursor),
+            None => {
+                let mut errors = ValidationErrors::new();
+                errors.add(
+                    "cursor",
+                    "invalid",
+                    "The cursor has to be the `nextCursor` of a previous page!",
+                );
+                return Err(errors.into_response());
+            }
+        },
+        None => None,
+    };
+
+    let player = app_state
+        .player_repository
+        .get_player(&player_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    if accepts_ndjson(&headers) {
+        let game_result_repository = app_state.game_result_repository.clone();
+        let identity = player.identity();
+
+        return Ok(stream_ndjson(
+            cursor,
+            move |cursor: Option<HistoryCursor>| {
+                let game_result_repository = game_result_repository.clone();
+                let identity = identity.clone();
+                async move {
+                    let games: Vec<GameHistoryEntry> = game_result_repository
+                        .get_history(&identity, cursor.as_ref(), MAX_HISTORY_PAGE_SIZE)
+                        .await
+                        .map_err(|err| err.message)?
+                        .into_iter()
+                        .map(GameHistoryEntry::from)
+                        .collect();
+                    let next_cursor = games
+                        .last()
+                        .filter(|_| games.len() == MAX_HISTORY_PAGE_SIZE)
+                        .map(|game| HistoryCursor {
+                            finished_at: game.finished_at,
+                            game_id: game.game_id.clone(),
+                        });
+
+                    Ok((games, next_cursor))
+                }
+            },
+        ));
+    }
+
+    let games = app_state
+        .game_result_repository
+        .get_history(&player.identity(), cursor.as_ref(), page_size)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(GameHistoryPage::new(
+        games.into_iter().map(GameHistoryEntry::from).collect(),
+        page_size,
+    )
+    .into_response())
+}
+
+/// Lists the players who finished games together with a player, latest shared game first.
+///
+/// Like the statistics, the list covers every game played from the same client. Opponents who
+/// currently play come with their player id, which can be passed to
+/// `/game/{id}/invite-player/{other_id}`.
+///
+/// URL endpoint: /player/{id}/recent-opponents
+pub async fn get_recent_opponents(
+    State(app_state): State<AppState>,
+    Path(player_id): Path<String>,
+    Query(query): Query<RecentOpponentsQuery>,
+) -> Result<RecentOpponentList, Response> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_RECENT_OPPONENTS)
+        .clamp(1, MAX_RECENT_OPPONENTS);
+
+    let player = app_state
+        .player_repository
+        .get_player(&player_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let opponents = app_state
+        .game_result_repository
+        .get_recent_opponents(&player.identity(), limit)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(RecentOpponentList { opponents })
+}
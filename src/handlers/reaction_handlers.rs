@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    enums::emote::Emote,
+    errors::rate_limit_error::RateLimitError,
+    handlers::event_handlers::publish_event,
+    middleware::{authentication::AuthenticatedPlayer, authorization::ensure_same_game},
+    router::router_provider::AppState,
+    types::game_event::{GameEvent, REACTION_EVENT},
+};
+
+// constants
+
+/// Most reactions a player can send within `REACTION_WINDOW_SECONDS`.
+pub const MAX_REACTIONS_PER_WINDOW: usize = 5;
+
+/// Length of the window the reactions of a player are counted in, in seconds.
+pub const REACTION_WINDOW_SECONDS: i64 = 10;
+
+/// Body of a reaction.
+///
+/// # Props
+///
+/// - `emote` -> The emote the player reacts with
+#[derive(Deserialize, Debug)]
+pub struct ReactionRequest {
+    /// The emote the player reacts with
+    pub emote: Emote,
+}
+
+/// Reacts with an emote at the table.
+///
+/// The reaction is pushed to the event streams of all players and spectators but never added to
+/// the chat; it expires after a few seconds. A player can send `MAX_REACTIONS_PER_WINDOW`
+/// reactions within `REACTION_WINDOW_SECONDS`.
+///
+/// URL endpoint: /game/{id}/react
+pub async fn react(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    Json(request): Json<ReactionRequest>,
+) -> Result<StatusCode, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let recent_reactions = app_state
+        .game_event_repository
+        .count_recent_events(
+            &game_id,
+            REACTION_EVENT,
+            &player.player_id,
+            REACTION_WINDOW_SECONDS,
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if recent_reactions >= MAX_REACTIONS_PER_WINDOW {
+        return Err(RateLimitError::new(
+            format!(
+                "Only {MAX_REACTIONS_PER_WINDOW} reactions are allowed within {REACTION_WINDOW_SECONDS} seconds!"
+            ),
+            REACTION_WINDOW_SECONDS,
+        )
+        .into_response());
+    }
+
+    let event = GameEvent::Reaction {
+        game_id,
+        player_id: player.player_id,
+        emote: request.emote,
+    };
+    publish_event(&app_state, &event, None).await;
+
+    Ok(StatusCode::ACCEPTED)
+}
@@ -0,0 +1,92 @@
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::{schema::RootSchema, schema_for};
+
+use crate::{
+    errors::error_response::ErrorResponseBody,
+    types::{
+        card::{Card, UpdateCardDTO},
+        chat::{Chat, ChatMessage, EditChatMessageDTO, SendChatMessageDTO},
+        claim::{ChallengeDTO, Claim, CreateClaimDTO},
+        game::{CreateGameDTO, Game, UpdateGameDTO},
+        game_settings::GameSettings,
+        player::{CreatePlayerDTO, Player, PlayerProfile, UpdatePlayerDTO},
+    },
+};
+
+// constants
+/// Names of all types a JSON Schema can be requested for.
+const SCHEMA_TYPES: [&str; 17] = [
+    "Game",
+    "CreateGameDTO",
+    "UpdateGameDTO",
+    "GameSettings",
+    "Player",
+    "PlayerProfile",
+    "CreatePlayerDTO",
+    "UpdatePlayerDTO",
+    "Claim",
+    "CreateClaimDTO",
+    "ChallengeDTO",
+    "Chat",
+    "ChatMessage",
+    "SendChatMessageDTO",
+    "EditChatMessageDTO",
+    "Card",
+    "UpdateCardDTO",
+    "ErrorResponseBody",
+];
+
+/// Returns the JSON Schema of a type the API sends or receives.
+///
+/// Lets clients validate payloads and run contract tests against the deployed backend.
+///
+/// # Errors
+///
+/// Returns `404 Not Found` listing all known types if the type is unknown.
+///
+/// URL endpoint: /schemas/{type}
+pub async fn get_schema(Path(type_name): Path<String>) -> Result<Json<RootSchema>, Response> {
+    schema_of(&type_name).map(Json).ok_or_else(|| {
+        let body = ErrorResponseBody::<()>::new(
+            format!(
+                "There is no schema for '{type_name}'! Known types: {}",
+                SCHEMA_TYPES.join(", ")
+            ),
+            None,
+        );
+
+        (StatusCode::NOT_FOUND, Json(body)).into_response()
+    })
+}
+
+/// Generates the JSON Schema of a type by its name in `SCHEMA_TYPES`.
+fn schema_of(type_name: &str) -> Option<RootSchema> {
+    let schema = match type_name {
+        "Game" => schema_for!(Game),
+        "CreateGameDTO" => schema_for!(CreateGameDTO),
+        "UpdateGameDTO" => schema_for!(UpdateGameDTO),
+        "GameSettings" => schema_for!(GameSettings),
+        "Player" => schema_for!(Player),
+        "PlayerProfile" => schema_for!(PlayerProfile),
+        "CreatePlayerDTO" => schema_for!(CreatePlayerDTO),
+        "UpdatePlayerDTO" => schema_for!(UpdatePlayerDTO),
+        "Claim" => schema_for!(Claim),
+        "CreateClaimDTO" => schema_for!(CreateClaimDTO),
+        "ChallengeDTO" => schema_for!(ChallengeDTO),
+        "Chat" => schema_for!(Chat),
+        "ChatMessage" => schema_for!(ChatMessage),
+        "SendChatMessageDTO" => schema_for!(SendChatMessageDTO),
+        "EditChatMessageDTO" => schema_for!(EditChatMessageDTO),
+        "Card" => schema_for!(Card),
+        "UpdateCardDTO" => schema_for!(UpdateCardDTO),
+        "ErrorResponseBody" => schema_for!(ErrorResponseBody<()>),
+        _ => return None,
+    };
+
+    Some(schema)
+}
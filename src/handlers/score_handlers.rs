@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+};
+use log::warn;
+
+use crate::{
+    middleware::{authentication::Viewer, authorization::ensure_can_view},
+    router::router_provider::AppState,
+    types::{
+        game::Game,
+        score_history::{ScoreHistory, ScoreSnapshot},
+    },
+};
+
+/// Gets the score progression of a game over its rounds.
+///
+/// Every round that ended with a challenge has a snapshot of the score and the hand of each
+/// player, so the end screen can plot how the game went. Players who left keep their series.
+///
+/// URL endpoint: /game/{id}/scores
+pub async fn get_score_history(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    viewer: Viewer,
+) -> Result<ScoreHistory, Response> {
+    ensure_can_view(&viewer, &game_id).map_err(IntoResponse::into_response)?;
+
+    let snapshots = app_state
+        .score_history_repository
+        .get_history(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(ScoreHistory::new(game_id, snapshots))
+}
+
+/// Records the score and the hand of every player after a round.
+///
+/// The challenge that ended the round already succeeded, so a failure is only logged; the plot
+/// misses the round then.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the score history repository
+/// - `game` -> Fully hydrated game right after the round ended
+/// - `round_number` -> The round that ended
+pub async fn record_round_scores(app_state: &AppState, game: &Game, round_number: usize) {
+    let snapshots = ScoreSnapshot::for_round(game, round_number);
+
+    if let Err(err) = app_state
+        .score_history_repository
+        .record_round(&snapshots)
+        .await
+    {
+        warn!(
+            "Scores of round {round_number} of game {} couldn't be recorded: {err}",
+            game.id
+        );
+    }
+}
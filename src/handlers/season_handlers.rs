@@ -0,0 +1,80 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    auth::admin::Admin,
+    enums::audit_action::AuditAction,
+    errors::validation_errors::Validate,
+    handlers::audit_handlers::record_audit,
+    i18n::locale::Locale,
+    router::router_provider::AppState,
+    types::season::{RollOverSeasonDTO, Season, SeasonList},
+    utils::time::server_time,
+};
+
+/// Lists all seasons, the current one first.
+///
+/// The leaderboard of every season is available with `/leaderboard?season=<number>`.
+///
+/// URL endpoint: /seasons
+pub async fn get_seasons(State(app_state): State<AppState>) -> Result<SeasonList, Response> {
+    let current_season = app_state
+        .season_repository
+        .get_current_season()
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let seasons = app_state
+        .season_repository
+        .get_seasons()
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(SeasonList {
+        current_season: current_season.number,
+        seasons,
+    })
+}
+
+/// Ends the current season and starts the next one with an empty leaderboard.
+///
+/// Games that end from now on count towards the new season.
+///
+/// URL endpoint: /admin/seasons/rollover
+pub async fn roll_over_season(
+    State(app_state): State<AppState>,
+    admin: Admin,
+    locale: Locale,
+    Json(season_data): Json<RollOverSeasonDTO>,
+) -> Result<Season, Response> {
+    season_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let current_season = app_state
+        .season_repository
+        .get_current_season()
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let next_season = app_state
+        .season_repository
+        .roll_over(
+            &current_season,
+            season_data.into_next_season(&current_season, server_time()),
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::RollOverSeason,
+        Some(&next_season.number.to_string()),
+        Some(format!("{current_season} ended, {next_season} started")),
+    )
+    .await;
+
+    Ok(next_season)
+}
@@ -0,0 +1,43 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    repositories::share_cache::{cache_share_payload, get_cached_share_payload},
+    router::router_provider::AppState,
+    types::share::SharePayload,
+    utils::time::server_time,
+};
+
+/// Returns the link preview of a game for chat apps unfurling a shared link.
+///
+/// Public, since unfurling clients don't hold a session. The join code is only part of the
+/// preview of a public game.
+///
+/// URL endpoint: /game/{id}/share
+pub async fn get_share_payload(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<SharePayload, Response> {
+    let now = server_time();
+    if let Some(payload) = get_cached_share_payload(&game_id, now) {
+        return Ok(payload);
+    }
+
+    let mut game = app_state
+        .game_repository
+        .get_game_by_id(&game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    game.players = app_state
+        .player_repository
+        .get_all_players(Some(game_id.clone()))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let payload = SharePayload::for_game(&game, app_state.config.frontend_url.as_deref());
+    cache_share_payload(&game_id, &payload, now);
+
+    Ok(payload)
+}
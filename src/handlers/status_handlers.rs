@@ -1 +1,118 @@
-// TODO: Implement the status update endpoints
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use log::warn;
+
+use crate::{
+    handlers::{event_handlers::publish_event, game_handlers::load_game},
+    i18n::locale::Locale,
+    jobs::disconnects::pass_turn_if_disconnected,
+    middleware::authorization::{ensure_same_game, Owned},
+    router::router_provider::AppState,
+    types::{
+        game_event::GameEvent,
+        game_view::GameView,
+        player::{PlayerProfile, UpdatePlayerDTO},
+        status::{ServerTime, StatusUpdate, StatusUpdateRequest},
+    },
+    utils::time::server_time,
+};
+
+/// Current time of the server.
+///
+/// Clients compare it with their own clock, half the round trip included, and count the turns
+/// down with the offset.
+///
+/// URL endpoint: /time
+pub async fn get_time() -> ServerTime {
+    ServerTime::now()
+}
+
+/// Heartbeat of a client.
+///
+/// Marks the player as active and returns the current game from their perspective. If the player
+/// was removed from the game in the meantime, the response says so.
+///
+/// The heartbeats of the players also drive the dead-man's switch: if the player to move stopped
+/// sending them, their turn is passed, see `pass_turn_if_disconnected`. A disconnected player who
+/// sends a heartbeat again is marked as connected and the others are notified.
+///
+/// URL endpoint: /status
+pub async fn request_status_update(
+    State(app_state): State<AppState>,
+    locale: Locale,
+    Owned {
+        player,
+        data: status_request,
+    }: Owned<StatusUpdateRequest>,
+) -> Result<StatusUpdate, Response> {
+    ensure_same_game(&player, &status_request.game_id).map_err(IntoResponse::into_response)?;
+
+    let own_player = match app_state
+        .player_repository
+        .get_player(&status_request.player_id)
+        .await
+    {
+        Ok(own_player) => own_player,
+        Err(err) if err.status_code == StatusCode::NOT_FOUND => {
+            return Ok(StatusUpdate::new(None, None, true))
+        }
+        Err(err) => return Err(err.into_response()),
+    };
+
+    app_state
+        .player_repository
+        .update_player(UpdatePlayerDTO::new(
+            status_request.player_id.clone(),
+            None,
+            None,
+            None,
+            Some(server_time()),
+            PlayerProfile::default(),
+        ))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    if own_player.disconnected_at.is_some() {
+        app_state
+            .player_repository
+            .set_disconnected_at(&own_player.id, None)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let reconnected_event = GameEvent::PlayerReconnected {
+            game_id: own_player.game_id.clone(),
+            player_id: own_player.id.clone(),
+        };
+        publish_event(&app_state, &reconnected_event, None).await;
+    }
+
+    let mut game = load_game(&app_state, &status_request.game_id).await?;
+    // a failing switch mustn't fail the heartbeat, the next one or the cron run tries again
+    match pass_turn_if_disconnected(&app_state, &game, server_time()).await {
+        Ok(Some(passed_game)) => game = passed_game,
+        Ok(None) => {}
+        Err(response) => warn!(
+            "Stalled turn of game {} couldn't be passed, status {}",
+            game.id,
+            response.status()
+        ),
+    }
+    let own_player = game
+        .players
+        .iter()
+        .find(|p| p.id == status_request.player_id)
+        .cloned();
+
+    Ok(StatusUpdate::new(
+        Some(GameView::for_player(
+            &game,
+            &status_request.player_id,
+            &locale,
+        )),
+        own_player,
+        false,
+    ))
+}
@@ -0,0 +1,487 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{admin::Admin, session_token::SessionClaims},
+    enums::{audit_action::AuditAction, game_state::GameState, tournament_state::TournamentState},
+    errors::{
+        database_query_error::DatabaseQueryError,
+        process_error::ProcessError,
+        validation_errors::{Validate, ValidationErrors},
+    },
+    handlers::{
+        audit_handlers::record_audit, auth_handlers::SessionResponse, game_handlers::load_game,
+    },
+    i18n::locale::Locale,
+    jobs::tournaments::start_round,
+    logic::bracket::rank_entrants,
+    middleware::authentication::ClientIdentity,
+    router::router_provider::AppState,
+    types::{
+        game_settings::MIN_PLAYERS,
+        tournament::{
+            CreateTournamentDTO, RegisterEntrantDTO, Tournament, TournamentDetails,
+            TournamentEntrant, TournamentList, TournamentMatchView, TournamentStandings,
+            UpdateTournamentDTO,
+        },
+    },
+    utils::time::server_time,
+};
+
+// constants
+/// Highest number of tournaments returned at once.
+const TOURNAMENTS_PAGE_SIZE: usize = 50;
+
+/// Query parameters of the tournament list.
+///
+/// # Props
+///
+/// - `state` -> State of the tournaments; `Registration` if `None`
+#[derive(Deserialize)]
+pub struct TournamentListQuery {
+    /// State of the tournaments
+    #[serde(default)]
+    pub state: TournamentState,
+}
+
+/// Where an entrant has to play next.
+///
+/// # Props
+///
+/// - `entrant` -> The entrant with the round they were eliminated in
+/// - `round` -> Current round of the tournament
+/// - `game_id` -> Game of the entrant in the current round; `None` after a bye or elimination
+/// - `session` -> Session of the player of the entrant while the game is played
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EntrantStatus {
+    /// The entrant
+    pub entrant: TournamentEntrant,
+    /// Current round of the tournament
+    pub round: usize,
+    /// Game of the entrant in the current round
+    pub game_id: Option<String>,
+    /// Session of the player of the entrant
+    pub session: Option<SessionResponse>,
+}
+
+impl IntoResponse for EntrantStatus {
+    /// Converts the `EntrantStatus` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Lists the newest tournaments in a state, those open for registration by default.
+///
+/// URL endpoint: /tournaments
+pub async fn list_tournaments(
+    State(app_state): State<AppState>,
+    Query(query): Query<TournamentListQuery>,
+) -> Result<TournamentList, Response> {
+    let tournaments = app_state
+        .tournament_repository
+        .get_tournaments(query.state, TOURNAMENTS_PAGE_SIZE)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(TournamentList { tournaments })
+}
+
+/// Returns a tournament with the matches of all its rounds.
+///
+/// URL endpoint: /tournament/{id}
+pub async fn get_tournament(
+    State(app_state): State<AppState>,
+    Path(tournament_id): Path<String>,
+) -> Result<TournamentDetails, Response> {
+    let repository = &app_state.tournament_repository;
+    let tournament = repository
+        .get_tournament(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let entrants = repository
+        .get_entrants(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let matches = repository
+        .get_matches(&tournament_id, None)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let seats = repository
+        .get_seats(&tournament_id, None)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(TournamentDetails {
+        tournament,
+        entrant_count: entrants.len(),
+        matches: matches
+            .iter()
+            .map(|tournament_match| TournamentMatchView::new(tournament_match, &seats))
+            .collect(),
+    })
+}
+
+/// Ranks the entrants of a tournament by how far they got.
+///
+/// URL endpoint: /tournament/{id}/standings
+pub async fn get_tournament_standings(
+    State(app_state): State<AppState>,
+    Path(tournament_id): Path<String>,
+) -> Result<TournamentStandings, Response> {
+    let repository = &app_state.tournament_repository;
+    let tournament = repository
+        .get_tournament(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let entrants = repository
+        .get_entrants(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let matches = repository
+        .get_matches(&tournament_id, None)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(TournamentStandings {
+        standings: rank_entrants(&entrants, &matches, tournament.winner_entrant_id.as_deref()),
+        tournament_id: tournament.id,
+        state: tournament.state,
+        current_round: tournament.current_round,
+    })
+}
+
+/// Registers a player for a tournament that didn't start yet.
+///
+/// A client can register once per tournament. The id of the returned entrant is needed to get the
+/// sessions of its games, so the client has to keep it.
+///
+/// URL endpoint: /tournament/{id}/entrants
+pub async fn register_entrant(
+    State(app_state): State<AppState>,
+    Path(tournament_id): Path<String>,
+    client: ClientIdentity,
+    locale: Locale,
+    Json(registration): Json<RegisterEntrantDTO>,
+) -> Result<TournamentEntrant, Response> {
+    registration
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let repository = &app_state.tournament_repository;
+    let tournament = repository
+        .get_tournament(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if tournament.state != TournamentState::Registration {
+        return Err(ProcessError::new(
+            "The registration of the tournament is closed!".to_string(),
+            "register_entrant".to_string(),
+            Some(tournament),
+        )
+        .into_response());
+    }
+
+    if client.fingerprint.is_some() {
+        let entrants = repository
+            .get_entrants(&tournament_id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        if entrants
+            .iter()
+            .any(|entrant| entrant.fingerprint == client.fingerprint)
+        {
+            return Err(ProcessError::new(
+                "You already registered for the tournament!".to_string(),
+                "register_entrant".to_string(),
+                Some(tournament),
+            )
+            .into_response());
+        }
+    }
+
+    let entrant =
+        TournamentEntrant::new(tournament_id, registration.player_name, client.fingerprint);
+    match repository
+        .add_entrant(entrant, tournament.max_entrants)
+        .await
+        .map_err(IntoResponse::into_response)?
+    {
+        Some(entrant) => Ok(entrant),
+        None => Err(ProcessError::new(
+            "The tournament is already full!".to_string(),
+            "register_entrant".to_string(),
+            Some(tournament),
+        )
+        .into_response()),
+    }
+}
+
+/// Returns where an entrant plays in the current round.
+///
+/// While the game of the entrant is played, the response holds a session for the player the
+/// entrant was seated as, so the client can join the game right away. The entrant id is the
+/// secret, the caller doesn't need to be authenticated otherwise.
+///
+/// URL endpoint: /tournament/{id}/entrants/{entrant_id}
+pub async fn get_entrant_status(
+    State(app_state): State<AppState>,
+    Path((tournament_id, entrant_id)): Path<(String, String)>,
+) -> Result<EntrantStatus, Response> {
+    let repository = &app_state.tournament_repository;
+    let tournament = repository
+        .get_tournament(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let Some(entrant) = repository
+        .get_entrants(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?
+        .into_iter()
+        .find(|entrant| entrant.id == entrant_id)
+    else {
+        return Err(DatabaseQueryError::<TournamentEntrant>::new(
+            "Entrant not found".to_string(),
+            None,
+            StatusCode::NOT_FOUND,
+        )
+        .into_response());
+    };
+
+    let round = tournament.current_round;
+    let seat = repository
+        .get_seats(&tournament_id, Some(round))
+        .await
+        .map_err(IntoResponse::into_response)?
+        .into_iter()
+        .find(|seat| seat.entrant_id == entrant.id);
+    let game_id = match &seat {
+        Some(seat) => repository
+            .get_matches(&tournament_id, Some(round))
+            .await
+            .map_err(IntoResponse::into_response)?
+            .into_iter()
+            .find(|tournament_match| tournament_match.slot == seat.slot)
+            .and_then(|tournament_match| tournament_match.game_id),
+        None => None,
+    };
+
+    let mut session = None;
+    if let (Some(game_id), Some(player_id)) = (&game_id, seat.and_then(|seat| seat.player_id)) {
+        let game = load_game(&app_state, game_id).await?;
+        let still_playing = game.state != GameState::Ended
+            && game.state != GameState::Abandoned
+            && game.players.iter().any(|player| player.id == player_id);
+        if still_playing {
+            let claims = SessionClaims::new(
+                player_id,
+                game.id,
+                server_time(),
+                app_state.config.session_lifetime_minutes,
+            );
+            session = Some(SessionResponse::new(
+                &claims,
+                &app_state.session_secret,
+                None,
+            ));
+        }
+    }
+
+    Ok(EntrantStatus {
+        entrant,
+        round,
+        game_id,
+        session,
+    })
+}
+
+// ----- Admin endpoints of the tournaments -----
+
+/// Creates a tournament and opens its registration.
+///
+/// URL endpoint: /admin/tournaments
+pub async fn create_tournament(
+    State(app_state): State<AppState>,
+    admin: Admin,
+    locale: Locale,
+    Json(tournament_data): Json<CreateTournamentDTO>,
+) -> Result<Tournament, Response> {
+    tournament_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let tournament = app_state
+        .tournament_repository
+        .add_tournament(tournament_data.into_tournament())
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::CreateTournament,
+        Some(&tournament.id),
+        Some(tournament.name.clone()),
+    )
+    .await;
+
+    Ok(tournament)
+}
+
+/// Changes the name or the limits of a tournament while it is open for registration.
+///
+/// URL endpoint: /admin/tournaments/{id}
+pub async fn update_tournament(
+    State(app_state): State<AppState>,
+    Path(tournament_id): Path<String>,
+    admin: Admin,
+    locale: Locale,
+    Json(update): Json<UpdateTournamentDTO>,
+) -> Result<Tournament, Response> {
+    update
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let repository = &app_state.tournament_repository;
+    let tournament = repository
+        .get_tournament(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let Some(updated_tournament) = repository
+        .update_tournament(&tournament_id, &update)
+        .await
+        .map_err(IntoResponse::into_response)?
+    else {
+        return Err(ProcessError::new(
+            "Only a tournament open for registration can be changed!".to_string(),
+            "update_tournament".to_string(),
+            Some(tournament),
+        )
+        .into_response());
+    };
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::UpdateTournament,
+        Some(&tournament_id),
+        Some(update.to_string()),
+    )
+    .await;
+
+    Ok(updated_tournament)
+}
+
+/// Closes the registration of a tournament and creates the games of its first round.
+///
+/// URL endpoint: /admin/tournaments/{id}/start
+pub async fn start_tournament(
+    State(app_state): State<AppState>,
+    Path(tournament_id): Path<String>,
+    admin: Admin,
+) -> Result<Tournament, Response> {
+    let repository = &app_state.tournament_repository;
+    let entrants = repository
+        .get_entrants(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if entrants.len() < MIN_PLAYERS {
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "entrants",
+            "too_few",
+            &format!("A tournament needs at least {MIN_PLAYERS} entrants to start!"),
+        );
+        return Err(errors.into_response());
+    }
+
+    let started = repository
+        .change_state(
+            &tournament_id,
+            &[TournamentState::Registration],
+            TournamentState::Running,
+            None,
+            server_time(),
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let tournament = repository
+        .get_tournament(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if !started {
+        return Err(ProcessError::new(
+            "The tournament already started!".to_string(),
+            "start_tournament".to_string(),
+            Some(tournament),
+        )
+        .into_response());
+    }
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::StartTournament,
+        Some(&tournament_id),
+        Some(format!("{} entrants", entrants.len())),
+    )
+    .await;
+
+    // a round that can't be set up now is set up by the next cron run
+    start_round(&app_state, &tournament, 1, &entrants).await?;
+
+    repository
+        .get_tournament(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+/// Calls off a tournament that isn't over yet; games that are already played aren't touched.
+///
+/// URL endpoint: /admin/tournaments/{id}
+pub async fn cancel_tournament(
+    State(app_state): State<AppState>,
+    Path(tournament_id): Path<String>,
+    admin: Admin,
+) -> Result<StatusCode, Response> {
+    let repository = &app_state.tournament_repository;
+    let tournament = repository
+        .get_tournament(&tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let cancelled = repository
+        .change_state(
+            &tournament_id,
+            &[TournamentState::Registration, TournamentState::Running],
+            TournamentState::Cancelled,
+            None,
+            server_time(),
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if !cancelled {
+        return Err(ProcessError::new(
+            "The tournament is already over!".to_string(),
+            "cancel_tournament".to_string(),
+            Some(tournament),
+        )
+        .into_response());
+    }
+
+    record_audit(
+        &app_state,
+        &admin.identity,
+        AuditAction::CancelTournament,
+        Some(&tournament_id),
+        None,
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,360 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{
+        rejoin_token::{issue_rejoin_token, verify_rejoin_token},
+        session_token::SessionClaims,
+    },
+    enums::game_state::GameState,
+    errors::{
+        authentication_error::AuthenticationError, process_error::ProcessError,
+        validation_errors::Validate,
+    },
+    handlers::{
+        auth_handlers::SessionResponse,
+        event_handlers::{publish_event, RECONNECT_DELAY_MS},
+        game_handlers::load_game,
+        matchmaking_handlers::sse_message,
+    },
+    i18n::{catalog::Message, locale::Locale},
+    middleware::authentication::ClientIdentity,
+    router::router_provider::AppState,
+    types::{
+        game::Game,
+        game_event::GameEvent,
+        player::{resolve_player_name, Player},
+        waitlist::{JoinWaitlistDTO, WaitlistEntry, WaitlistStatus},
+    },
+    utils::time::server_time,
+};
+
+/// Data of the `seatAssigned` event telling a waiting client that it got a seat.
+///
+/// # Props
+///
+/// - `game_id` -> The game the client now plays in
+/// - `session` -> Session and rejoin token of the player created for the entry
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatAssigned {
+    /// The game the client now plays in
+    pub game_id: String,
+    /// Session and rejoin token of the player created for the entry
+    pub session: SessionResponse,
+}
+
+/// Query parameters of the requests of a waiting client.
+///
+/// The token is sent in the query, since an `EventSource` can't set headers.
+///
+/// # Props
+///
+/// - `token` -> Token the client got when it joined the waitlist
+#[derive(Deserialize)]
+pub struct WaitlistTokenQuery {
+    /// Token the client got when it joined the waitlist
+    pub token: String,
+}
+
+/// Puts a client on the waitlist of a full game.
+///
+/// Only a game that still accepts players and has no free seat has a waitlist. The client learns
+/// about its seat on the event stream of the returned entry. The returned token is only sent
+/// once and has to be sent along to leave the waitlist or to follow the entry. Banned clients are
+/// rejected.
+///
+/// URL endpoint: /game/{id}/waitlist
+pub async fn join_waitlist(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    client: ClientIdentity,
+    locale: Locale,
+    Json(waitlist_data): Json<JoinWaitlistDTO>,
+) -> Result<WaitlistStatus, Response> {
+    waitlist_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    if !accepts_players(&game) {
+        return Err(refuse(&game, Message::GameNotJoinable, &locale));
+    }
+    if game.players.len() < game.settings.max_players {
+        return Err(refuse(&game, Message::SeatAvailable, &locale));
+    }
+
+    let (token, token_hash) = issue_rejoin_token();
+    let entry = app_state
+        .waitlist_repository
+        .add_entry(waitlist_data.into_entry(game_id, client.fingerprint, token_hash))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let mut status = app_state
+        .waitlist_repository
+        .get_status(&entry)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    status.token = Some(token);
+
+    Ok(status)
+}
+
+/// Takes a client off the waitlist of a game.
+///
+/// An entry that was already promoted keeps its seat; the player has to leave the game instead.
+///
+/// URL endpoint: /game/{id}/waitlist/{entry_id}?token={token}
+pub async fn leave_waitlist(
+    State(app_state): State<AppState>,
+    Path((game_id, entry_id)): Path<(String, String)>,
+    Query(query): Query<WaitlistTokenQuery>,
+) -> Result<StatusCode, Response> {
+    let entry = load_own_entry(&app_state, &game_id, &entry_id, &query.token).await?;
+
+    app_state
+        .waitlist_repository
+        .remove_entry(&entry.id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Server-sent event stream of an entry on the waitlist of a game.
+///
+/// Like the event stream of a game, every response ends right away and the `EventSource`
+/// reconnects after `RECONNECT_DELAY_MS`. Each response contains a `waitlistStatus` event with the
+/// position of the entry, or the `seatAssigned` event with a session and a rejoin token once the
+/// entry was promoted. Every connection also gives away seats that freed up without a leave or
+/// kick, e.g. of players who timed out.
+///
+/// The `seatAssigned` event is delivered exactly once: the rejoin token is stored and the entry
+/// taken off the waitlist in one transaction, so the client closes the stream then and later
+/// connections get a `404`.
+///
+/// URL endpoint: /game/{id}/waitlist/{entry_id}/events?token={token}
+pub async fn get_waitlist_events(
+    State(app_state): State<AppState>,
+    Path((game_id, entry_id)): Path<(String, String)>,
+    locale: Locale,
+    Query(query): Query<WaitlistTokenQuery>,
+) -> Result<Response, Response> {
+    let mut entry = load_own_entry(&app_state, &game_id, &entry_id, &query.token).await?;
+
+    if entry.player_id.is_none() {
+        let game = load_game(&app_state, &game_id).await?;
+        if !accepts_players(&game) {
+            return Err(refuse(&game, Message::GameNotJoinable, &locale));
+        }
+        promote_from_waitlist(&app_state, game).await;
+
+        entry = app_state
+            .waitlist_repository
+            .get_entry(&entry_id, &game_id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+    }
+
+    let mut body = format!("retry: {RECONNECT_DELAY_MS}\n\n");
+    match &entry.player_id {
+        Some(player_id) => {
+            let player = app_state
+                .player_repository
+                .get_player(player_id)
+                .await
+                .map_err(IntoResponse::into_response)?;
+
+            // only the connection taking the entry delivers the seat
+            let (rejoin_token, rejoin_token_hash) = issue_rejoin_token();
+            app_state
+                .waitlist_repository
+                .hand_over_entry(&entry.id, &player.id, &rejoin_token_hash)
+                .await
+                .map_err(IntoResponse::into_response)?;
+
+            let claims = SessionClaims::new(
+                player.id.clone(),
+                player.game_id.clone(),
+                server_time(),
+                app_state.config.session_lifetime_minutes,
+            );
+            let seat_assigned = SeatAssigned {
+                game_id: player.game_id.clone(),
+                session: SessionResponse::new(&claims, &app_state.session_secret, Some(player))
+                    .with_rejoin_token(rejoin_token),
+            };
+            body.push_str(&sse_message("seatAssigned", &seat_assigned));
+        }
+        None => {
+            let status = app_state
+                .waitlist_repository
+                .get_status(&entry)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            body.push_str(&sse_message("waitlistStatus", &status));
+        }
+    }
+
+    Ok((
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, "text/event-stream"),
+            (CACHE_CONTROL, "no-cache"),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Gives the free seats of a game to the clients on its waitlist, longest waiting first.
+///
+/// Called whenever a seat frees up. A client whose name was taken in the meantime gets it
+/// numbered, since it isn't around to choose another one. A failing promotion is logged and the
+/// seat is given away on the next chance.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `game` -> Fully hydrated game that may have free seats
+///
+/// # Returns
+///
+/// The game including the promoted players.
+pub async fn promote_from_waitlist(app_state: &AppState, mut game: Game) -> Game {
+    while accepts_players(&game) && game.players.len() < game.settings.max_players {
+        match promote_next(app_state, &game).await {
+            Ok(Some(player)) => game.players.push(player),
+            Ok(None) => break,
+            Err(response) => {
+                warn!(
+                    "Promotion from the waitlist of game {} failed with status {}",
+                    game.id,
+                    response.status()
+                );
+                break;
+            }
+        }
+    }
+
+    game
+}
+
+/// Promotes the entry of a game that waits the longest to a player.
+///
+/// # Returns
+///
+/// The new player, or `None` if nobody waits, another request promoted the entry first or
+/// another request took the last seat.
+async fn promote_next(app_state: &AppState, game: &Game) -> Result<Option<Player>, Response> {
+    let Some(entry) = app_state
+        .waitlist_repository
+        .get_first_waiting(&game.id)
+        .await
+        .map_err(IntoResponse::into_response)?
+    else {
+        return Ok(None);
+    };
+
+    let taken_names: Vec<String> = game
+        .players
+        .iter()
+        .map(|player| player.name.clone())
+        .collect();
+    let name = resolve_player_name("name", &entry.name, &taken_names, true, &Locale::En)
+        .unwrap_or_else(|_| entry.name.clone());
+    let player = entry.to_player(name);
+
+    let promoted = app_state
+        .waitlist_repository
+        .promote_entry(
+            &entry.id,
+            &player.id,
+            game.settings.max_players,
+            server_time(),
+        )
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if !promoted {
+        return Ok(None);
+    }
+
+    let player = match app_state.player_repository.add_player(player).await {
+        Ok(player) => player,
+        Err(err) => {
+            // the entry waits for the next free seat again
+            if let Err(release_err) = app_state.waitlist_repository.release_entry(&entry.id).await {
+                warn!(
+                    "Waitlist entry {} couldn't be released: {release_err}",
+                    entry.id
+                );
+            }
+            return Err(err.into_response());
+        }
+    };
+
+    let promoted_event = GameEvent::WaitlistPromoted {
+        game_id: game.id.clone(),
+        player_id: player.id.clone(),
+        name: player.name.clone(),
+    };
+    publish_event(app_state, &promoted_event, None).await;
+
+    Ok(Some(player))
+}
+
+/// Loads an entry of the waitlist of a game on behalf of the client it belongs to.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `game_id` -> Id of the game
+/// - `entry_id` -> Id of the entry
+/// - `token` -> Token the client got when it joined the waitlist
+///
+/// # Returns
+///
+/// The entry, or an error with status `401` if the token doesn't belong to it.
+async fn load_own_entry(
+    app_state: &AppState,
+    game_id: &str,
+    entry_id: &str,
+    token: &str,
+) -> Result<WaitlistEntry, Response> {
+    let entry = app_state
+        .waitlist_repository
+        .get_entry(entry_id, game_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    verify_rejoin_token(token, entry.token_hash.as_deref()).map_err(|_| {
+        AuthenticationError::new("The waitlist token is invalid!".to_string()).into_response()
+    })?;
+
+    Ok(entry)
+}
+
+/// Checks whether a game still accepts new players.
+fn accepts_players(game: &Game) -> bool {
+    game.state == GameState::WaitingForPlayers || game.state == GameState::Starting
+}
+
+/// Refuses a waitlist request for a game with a message in the locale of the client.
+fn refuse(game: &Game, message: Message, locale: &Locale) -> Response {
+    ProcessError::new(
+        message.text(locale),
+        "waitlist".to_string(),
+        Some(Game::from_ref(game)),
+    )
+    .into_response()
+}
@@ -0,0 +1,108 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    errors::{process_error::ProcessError, validation_errors::Validate},
+    handlers::game_handlers::load_game,
+    i18n::locale::Locale,
+    middleware::{
+        authentication::AuthenticatedPlayer,
+        authorization::{ensure_host, ensure_same_game},
+    },
+    router::router_provider::AppState,
+    types::webhook::{CreateWebhookDTO, RegisteredWebhook, Webhook, MAX_WEBHOOKS_PER_GAME},
+};
+
+/// Registers a callback URL receiving the lifecycle events of a game.
+///
+/// Only the host is allowed to register webhooks. The response contains the secret the payloads
+/// are signed with; it isn't shown again.
+///
+/// URL endpoint: /game/{id}/webhooks
+pub async fn register_webhook(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+    locale: Locale,
+    Json(webhook_data): Json<CreateWebhookDTO>,
+) -> Result<RegisteredWebhook, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+    webhook_data
+        .validate(&locale)
+        .map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    let webhooks = app_state
+        .webhook_repository
+        .get_webhooks(Some(&game_id))
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if webhooks.len() >= MAX_WEBHOOKS_PER_GAME {
+        return Err(ProcessError::<Webhook>::new(
+            format!("A game can't have more than {MAX_WEBHOOKS_PER_GAME} webhooks!"),
+            "register_webhook".to_string(),
+            None,
+        )
+        .into_response());
+    }
+
+    app_state
+        .webhook_repository
+        .add_webhook(webhook_data.into_webhook(Some(game_id)))
+        .await
+        .map(RegisteredWebhook::from)
+        .map_err(IntoResponse::into_response)
+}
+
+/// Lists the webhooks registered for a game.
+///
+/// Only the host is allowed to see the webhooks.
+///
+/// URL endpoint: /game/{id}/webhooks
+pub async fn get_webhooks(
+    State(app_state): State<AppState>,
+    Path(game_id): Path<String>,
+    player: AuthenticatedPlayer,
+) -> Result<Json<Vec<Webhook>>, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    app_state
+        .webhook_repository
+        .get_webhooks(Some(&game_id))
+        .await
+        .map(Json)
+        .map_err(IntoResponse::into_response)
+}
+
+/// Removes a webhook of a game.
+///
+/// Only the host is allowed to remove webhooks.
+///
+/// URL endpoint: /game/{id}/webhooks/{webhook_id}
+pub async fn remove_webhook(
+    State(app_state): State<AppState>,
+    Path((game_id, webhook_id)): Path<(String, String)>,
+    player: AuthenticatedPlayer,
+) -> Result<StatusCode, Response> {
+    ensure_same_game(&player, &game_id).map_err(IntoResponse::into_response)?;
+
+    let game = load_game(&app_state, &game_id).await?;
+    ensure_host(&game, &player.player_id).map_err(IntoResponse::into_response)?;
+
+    app_state
+        .webhook_repository
+        .remove_webhook(&webhook_id, Some(&game_id))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
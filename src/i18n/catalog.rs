@@ -0,0 +1,241 @@
+use crate::i18n::locale::Locale;
+
+/// Texts the server sends to players, with a template for every `Locale`.
+///
+/// Templates name their dynamic parts in braces, e.g. `{max}`. Every text is written in the locale
+/// the handler negotiated for the request; only system messages of the chat are stored in English
+/// and written again for every reader.
+///
+/// Texts that aren't catalogued yet are sent in English.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::catalog::Message;
+/// use your_crate::locale::Locale;
+/// let text = Message::NameTooLong.format(&Locale::De, &[("max", "20")]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Message {
+    // system messages in the chat
+    /// Notice about a message whose offensive words were masked.
+    ChatCensoredNotice,
+    /// Notice about a message that was dropped by the word filter.
+    ChatRejectedNotice,
+
+    // validation errors
+    /// A chat message without content.
+    MessageContentEmpty,
+    /// A chat message the word filter rejected.
+    MessageBannedWords,
+    /// A whisper to the sender.
+    WhisperToSelf,
+    /// A whisper to somebody outside of the game.
+    UnknownRecipient,
+    /// A chat message of a player who is muted until `{until}`.
+    PlayerMuted,
+    /// A player without a name.
+    NameEmpty,
+    /// A player name over the limit `{max}`.
+    NameTooLong,
+    /// A player name another player of the game already uses.
+    NameTaken,
+    /// A claim with too few or too many cards, up to `{max}`.
+    ClaimCardsOutOfRange,
+    /// A claim whose cards don't match its number of cards.
+    ClaimCardsMismatch,
+    /// A claim declaring a Joker.
+    DeclaredJoker,
+
+    // rule violations
+    /// A move out of turn.
+    NotPlayersTurn,
+    /// A move while the game is in the state `{state}`.
+    NoMovesInState,
+    /// A challenge of the own claim.
+    ChallengeOwnClaim,
+    /// Joining a game that already started.
+    GameNotJoinable,
+    /// Joining a full game.
+    GameFull,
+    /// Joining the waitlist of a game with a free seat.
+    SeatAvailable,
+    /// Starting a game with fewer than `{count}` players.
+    NotEnoughPlayers,
+    /// The host kicking themself.
+    HostKicksThemself,
+    /// Changing the settings of a started game.
+    SettingsLocked,
+    /// Rearranging the seats of a started game.
+    SeatsLocked,
+    /// Pausing a game that isn't running.
+    PauseNotInProgress,
+    /// Resuming a game that isn't paused.
+    ResumeNotPaused,
+    /// Resetting a game that isn't running.
+    ResetNotRunning,
+    /// Reclaiming a game that wasn't abandoned, too late or without a seat.
+    ReclaimUnavailable,
+    /// Taking back a claim in a game without the undo house rule.
+    UndoDisabled,
+    /// Taking back a claim in a round without claims.
+    UndoNoClaim,
+    /// Taking back a claim which isn't the own last one.
+    UndoNotLastClaim,
+    /// Taking back a claim after the grace window.
+    UndoTooLate,
+    /// Changing a message of the server.
+    SystemMessageLocked,
+    /// Changing a deleted message.
+    MessageAlreadyDeleted,
+    /// Changing a message after the edit window.
+    MessageTooOld,
+}
+
+impl Message {
+    /// Returns the template of the text in a locale.
+    pub fn template(&self, locale: &Locale) -> &'static str {
+        match locale {
+            Locale::En => self.english(),
+            Locale::De => self.german(),
+        }
+    }
+
+    /// Writes the text in a locale.
+    ///
+    /// # Arguments
+    ///
+    /// - `locale` -> Language of the text
+    /// - `args` -> Values of the placeholders, by their name
+    pub fn format(&self, locale: &Locale, args: &[(&str, &str)]) -> String {
+        args.iter()
+            .fold(self.template(locale).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+
+    /// Writes a text without placeholders in a locale.
+    pub fn text(&self, locale: &Locale) -> String {
+        self.template(locale).to_string()
+    }
+
+    /// Finds the notice a system message of the chat was stored with.
+    ///
+    /// # Arguments
+    ///
+    /// - `content` -> Stored content of the system message, in English
+    ///
+    /// # Returns
+    ///
+    /// The notice, or `None` if the content isn't one.
+    pub fn chat_notice(content: &str) -> Option<Message> {
+        [Message::ChatCensoredNotice, Message::ChatRejectedNotice]
+            .into_iter()
+            .find(|notice| notice.english() == content)
+    }
+
+    fn english(&self) -> &'static str {
+        match self {
+            Message::ChatCensoredNotice => "Offensive words were removed from a message.",
+            Message::ChatRejectedNotice => "A message was removed for offensive content.",
+            Message::MessageContentEmpty => "The message content must not be empty!",
+            Message::MessageBannedWords => {
+                "The message contains words which aren't allowed in the chat!"
+            }
+            Message::WhisperToSelf => "A player can't whisper to themself!",
+            Message::UnknownRecipient => "The recipient isn't a player of this game!",
+            Message::PlayerMuted => "The player is muted in the chat until {until}!",
+            Message::NameEmpty => "The name must not be empty!",
+            Message::NameTooLong => "The name must not be longer than {max} characters!",
+            Message::NameTaken => "Another player of the game already uses this name!",
+            Message::ClaimCardsOutOfRange => "A claim must contain between 1 and {max} cards!",
+            Message::ClaimCardsMismatch => {
+                "The number of placed cards doesn't match the 'number_of_cards'!"
+            }
+            Message::DeclaredJoker => {
+                "A Joker can't be declared, it only stands in for other cards!"
+            }
+            Message::NotPlayersTurn => "It's not the turn of this player!",
+            Message::NoMovesInState => {
+                "No moves can be made while the game is in the state '{state}'!"
+            }
+            Message::ChallengeOwnClaim => "A player can't challenge their own claim!",
+            Message::GameNotJoinable => "The game can't be joined anymore!",
+            Message::GameFull => "The game is already full!",
+            Message::SeatAvailable => "The game has a free seat, join it directly!",
+            Message::NotEnoughPlayers => "At least {count} players are needed to start the game!",
+            Message::HostKicksThemself => "The host can't kick themselves out of the game!",
+            Message::SettingsLocked => "The settings can only be changed before the game started!",
+            Message::SeatsLocked => "The seats can only be rearranged before the game started!",
+            Message::PauseNotInProgress => "Only a game in progress can be paused!",
+            Message::ResumeNotPaused => "Only a paused game can be resumed!",
+            Message::ResetNotRunning => "Only a running game can be reset!",
+            Message::ReclaimUnavailable => "The game can't be reclaimed anymore!",
+            Message::UndoDisabled => "Claims can't be taken back in this game!",
+            Message::UndoNoClaim => "No claim was made in this round!",
+            Message::UndoNotLastClaim => {
+                "Only the last claim of the round can be taken back by its author!"
+            }
+            Message::UndoTooLate => "The time to take back the claim is over!",
+            Message::SystemMessageLocked => "Messages of the server can't be changed!",
+            Message::MessageAlreadyDeleted => "The message was already deleted!",
+            Message::MessageTooOld => "The message is too old to be changed!",
+        }
+    }
+
+    fn german(&self) -> &'static str {
+        match self {
+            Message::ChatCensoredNotice => "Anstößige Wörter wurden aus einer Nachricht entfernt.",
+            Message::ChatRejectedNotice => {
+                "Eine Nachricht wurde wegen anstößigen Inhalts entfernt."
+            }
+            Message::MessageContentEmpty => "Die Nachricht darf nicht leer sein!",
+            Message::MessageBannedWords => {
+                "Die Nachricht enthält Wörter, die im Chat nicht erlaubt sind!"
+            }
+            Message::WhisperToSelf => "Man kann sich nicht selbst etwas zuflüstern!",
+            Message::UnknownRecipient => "Der Empfänger spielt nicht in diesem Spiel mit!",
+            Message::PlayerMuted => "Der Spieler ist im Chat bis {until} stummgeschaltet!",
+            Message::NameEmpty => "Der Name darf nicht leer sein!",
+            Message::NameTooLong => "Der Name darf höchstens {max} Zeichen lang sein!",
+            Message::NameTaken => "Ein anderer Spieler des Spiels verwendet diesen Namen bereits!",
+            Message::ClaimCardsOutOfRange => {
+                "Eine Ansage muss zwischen 1 und {max} Karten enthalten!"
+            }
+            Message::ClaimCardsMismatch => {
+                "Die Anzahl der gelegten Karten passt nicht zu 'number_of_cards'!"
+            }
+            Message::DeclaredJoker => {
+                "Ein Joker kann nicht angesagt werden, er vertritt nur andere Karten!"
+            }
+            Message::NotPlayersTurn => "Dieser Spieler ist nicht am Zug!",
+            Message::NoMovesInState => "Im Zustand '{state}' des Spiels sind keine Züge möglich!",
+            Message::ChallengeOwnClaim => "Die eigene Ansage kann nicht angezweifelt werden!",
+            Message::GameNotJoinable => "Dem Spiel kann nicht mehr beigetreten werden!",
+            Message::GameFull => "Das Spiel ist bereits voll!",
+            Message::SeatAvailable => "Im Spiel ist ein Platz frei, tritt direkt bei!",
+            Message::NotEnoughPlayers => {
+                "Zum Starten des Spiels werden mindestens {count} Spieler gebraucht!"
+            }
+            Message::HostKicksThemself => "Der Gastgeber kann sich nicht selbst entfernen!",
+            Message::SettingsLocked => {
+                "Die Einstellungen können nur vor dem Start des Spiels geändert werden!"
+            }
+            Message::SeatsLocked => {
+                "Die Sitzplätze können nur vor dem Start des Spiels getauscht werden!"
+            }
+            Message::PauseNotInProgress => "Nur ein laufendes Spiel kann pausiert werden!",
+            Message::ResumeNotPaused => "Nur ein pausiertes Spiel kann fortgesetzt werden!",
+            Message::ResetNotRunning => "Nur ein laufendes Spiel kann neu begonnen werden!",
+            Message::ReclaimUnavailable => "Das Spiel kann nicht mehr zurückgeholt werden!",
+            Message::UndoDisabled => "In diesem Spiel können Ansagen nicht zurückgenommen werden!",
+            Message::UndoNoClaim => "In dieser Runde wurde noch keine Ansage gemacht!",
+            Message::UndoNotLastClaim => {
+                "Nur die letzte Ansage der Runde kann von ihrem Urheber zurückgenommen werden!"
+            }
+            Message::UndoTooLate => "Die Zeit zum Zurücknehmen der Ansage ist abgelaufen!",
+            Message::SystemMessageLocked => "Nachrichten des Servers können nicht geändert werden!",
+            Message::MessageAlreadyDeleted => "Die Nachricht wurde bereits gelöscht!",
+            Message::MessageTooOld => "Die Nachricht ist zu alt, um sie zu ändern!",
+        }
+    }
+}
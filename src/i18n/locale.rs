@@ -0,0 +1,108 @@
+use std::{convert::Infallible, fmt};
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header::ACCEPT_LANGUAGE, request::Parts, HeaderMap},
+};
+use serde::{Deserialize, Serialize};
+
+/// Languages the server writes its messages in.
+///
+/// Used as an extractor, it yields the locale negotiated from the `Accept-Language` header of the
+/// request, which the handlers pass on to everything that writes a text of the catalog.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::locale::Locale;
+/// let locale = Locale::negotiate("de-DE,de;q=0.9,en;q=0.8");
+/// assert_eq!(locale, Locale::De);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Locale {
+    /// English, the default language.
+    #[default]
+    En,
+    /// German.
+    De,
+}
+
+impl Locale {
+    /// Returns the language tag of the locale.
+    ///
+    /// # Returns
+    /// A string slice representing the locale.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    /// Picks the locale a client prefers from an `Accept-Language` header value.
+    ///
+    /// Only the primary subtag is compared, so `de-AT` is answered in German. Languages are
+    /// ranked by their quality value; of two equally ranked languages the first one wins.
+    ///
+    /// # Arguments
+    ///
+    /// - `accept_language` -> Value of the header, e.g. `de-DE,de;q=0.9,en;q=0.8`
+    ///
+    /// # Returns
+    ///
+    /// The preferred supported locale, or English if the client accepts none of them.
+    pub fn negotiate(accept_language: &str) -> Self {
+        let mut preferred: Option<(Locale, f32)> = None;
+
+        for range in accept_language.split(',') {
+            let mut parameters = range.split(';').map(str::trim);
+            let tag = parameters.next().unwrap_or_default();
+            let quality = parameters
+                .find_map(|parameter| parameter.strip_prefix("q="))
+                .map_or(Some(1.0), |quality| quality.parse::<f32>().ok())
+                .unwrap_or(0.0);
+
+            let primary = tag.split('-').next().unwrap_or_default();
+            let Some(locale) = Locale::from_primary_subtag(primary) else {
+                continue;
+            };
+            if quality > 0.0 && preferred.is_none_or(|(_, best)| quality > best) {
+                preferred = Some((locale, quality));
+            }
+        }
+
+        preferred.map(|(locale, _)| locale).unwrap_or_default()
+    }
+
+    /// Picks the locale a client prefers from the headers of its request.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .map(Locale::negotiate)
+            .unwrap_or_default()
+    }
+
+    /// Maps the primary subtag of a language tag to a supported locale.
+    fn from_primary_subtag(subtag: &str) -> Option<Self> {
+        match subtag.to_ascii_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "de" => Some(Locale::De),
+            _ => None,
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Locale {
+    type Rejection = Infallible;
+
+    /// Negotiates the locale of the request; a missing or malformed header means English.
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Locale::from_headers(&parts.headers))
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,30 @@
+use axum::{
+    extract::Request,
+    http::{
+        header::{CONTENT_TYPE, VARY},
+        HeaderValue,
+    },
+    middleware::Next,
+    response::Response,
+};
+
+/// Marks JSON responses as depending on the `Accept-Language` header of the request.
+///
+/// The handlers write their texts in the `Locale` negotiated from the header, so caches must not
+/// hand the response of one language to a client asking for another one.
+pub async fn vary_by_locale(request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+    if is_json {
+        response
+            .headers_mut()
+            .append(VARY, HeaderValue::from_static("Accept-Language"));
+    }
+
+    response
+}
@@ -0,0 +1,140 @@
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+
+use crate::{
+    enums::game_state::GameState,
+    handlers::{event_handlers::publish_event, game_handlers::load_game},
+    router::router_provider::AppState,
+    types::{
+        game::{Game, UpdateGameDTO},
+        game_event::GameEvent,
+    },
+};
+
+/// Passes the turns of players whose heartbeat stopped in all running games.
+///
+/// Runs on the cron trigger of the worker. The status update requests of the other players catch
+/// a stopped heartbeat much sooner; the cron run covers tables where every client went quiet.
+///
+/// A failing game is logged and skipped, so one broken game doesn't block the others.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `now` -> Point in time of the run
+pub async fn pass_stalled_turns(app_state: &AppState, now: DateTime<Utc>) {
+    let games = match app_state.game_repository.get_active_games().await {
+        Ok(games) => games,
+        Err(err) => {
+            warn!("Active games couldn't be loaded to pass stalled turns: {err}");
+            return;
+        }
+    };
+
+    let mut passed_turns = 0;
+    for game in games {
+        let result = match load_game(app_state, &game.id).await {
+            Ok(game) => pass_turn_if_disconnected(app_state, &game, now).await,
+            Err(response) => Err(response),
+        };
+        match result {
+            Ok(Some(_)) => passed_turns += 1,
+            Ok(None) => {}
+            Err(response) => warn!(
+                "Stalled turn of game {} couldn't be passed, status {}",
+                game.id,
+                response.status()
+            ),
+        }
+    }
+
+    info!("Passed {passed_turns} turns of disconnected players");
+}
+
+/// Dead-man's switch of the player to move.
+///
+/// If the player to move didn't request a status update for `disconnect_after_seconds`, the turn
+/// goes to the next player whose heartbeat is still alive. Every player skipped that way is marked
+/// as disconnected and the others are notified with a `PlayerDisconnected` event, followed by the
+/// `TurnChanged` event. Paused games are left alone, nobody is expected to act in them.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `game` -> Fully hydrated game
+/// - `now` -> Current point in time
+///
+/// # Returns
+///
+/// The game after the turn was passed, or `None` if the player to move is still connected or
+/// nobody is left to pass the turn to.
+pub async fn pass_turn_if_disconnected(
+    app_state: &AppState,
+    game: &Game,
+    now: DateTime<Utc>,
+) -> Result<Option<Game>, Response> {
+    if game.state != GameState::InProgress {
+        return Ok(None);
+    }
+    let disconnect_after_seconds = app_state.config.disconnect_after_seconds;
+
+    // walk the seats from the player to move until a connected player is found
+    let mut skipped = vec![];
+    let mut current_id = game.which_player_turn.clone();
+    let next_player_id = loop {
+        let Some(current) = game.players.iter().find(|player| player.id == current_id) else {
+            return Ok(None);
+        };
+        if !current.is_unresponsive(now, disconnect_after_seconds) {
+            break current_id;
+        }
+        skipped.push(current);
+        // nobody is left to pass the turn to if every heartbeat stopped
+        if skipped.len() == game.players.len() {
+            return Ok(None);
+        }
+        let Some(next_id) = game.next_player_id(&current_id) else {
+            return Ok(None);
+        };
+        current_id = next_id;
+    };
+    if skipped.is_empty() {
+        return Ok(None);
+    }
+
+    let game_data = UpdateGameDTO {
+        id: game.id.clone(),
+        which_player_turn: Some(next_player_id.clone()),
+        expected_revision: Some(game.revision),
+        ..Default::default()
+    };
+    app_state
+        .game_repository
+        .update_game(game_data, &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    for player in skipped
+        .iter()
+        .filter(|player| player.disconnected_at.is_none())
+    {
+        app_state
+            .player_repository
+            .set_disconnected_at(&player.id, Some(now))
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let disconnected_event = GameEvent::PlayerDisconnected {
+            game_id: game.id.clone(),
+            player_id: player.id.clone(),
+            which_player_turn: next_player_id.clone(),
+        };
+        publish_event(app_state, &disconnected_event, None).await;
+    }
+
+    let game = load_game(app_state, &game.id).await?;
+    publish_event(app_state, &GameEvent::turn_changed(&game), None).await;
+
+    Ok(Some(game))
+}
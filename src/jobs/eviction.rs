@@ -0,0 +1,287 @@
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Duration, Utc};
+use log::{info, warn};
+
+use crate::{
+    config::app_config::AppConfig,
+    enums::{cleanup_policy::CleanupPolicy, game_state::GameState, webhook_event::WebhookEvent},
+    handlers::{
+        event_handlers::publish_event,
+        game_handlers::{load_game, remove_player_from_game},
+    },
+    jobs::{
+        task_queue::enqueue_game_end_tasks,
+        webhooks::{game_ended_data, notify_webhooks},
+    },
+    metrics::analytics::{record_metric, Metric},
+    router::router_provider::AppState,
+    types::{
+        abandonment::Abandonment,
+        game::{Game, UpdateGameDTO},
+        game_event::GameEvent,
+        game_settings::MIN_PLAYERS,
+    },
+    utils::time::server_time,
+};
+
+/// Outcome of one eviction run.
+///
+/// # Props
+///
+/// - `removed_players` -> Number of players removed because they timed out
+/// - `closed_games` -> Number of games that were ended or abandoned
+/// - `reaped_games` -> Number of games that were abandoned for exceeding a `CleanupPolicy`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EvictionSummary {
+    /// Number of players removed because they timed out
+    pub removed_players: usize,
+    /// Number of games that were ended or abandoned
+    pub closed_games: usize,
+    /// Number of games that were abandoned for exceeding a `CleanupPolicy`, part of `closed_games`
+    pub reaped_games: usize,
+}
+
+/// Removes timed out players and closes games nobody plays anymore.
+///
+/// Runs on the cron trigger of the worker.
+///
+/// -> Every player without a status update request for `player_timeout_minutes` is removed
+/// -> A game waiting for players for more than `stale_lobby_hours`, or without any players left,
+///    is abandoned
+/// -> A running game with fewer than `MIN_PLAYERS` players left is ended
+/// -> A game exceeding a `CleanupPolicy` is abandoned and its players are freed, so their clients
+///    can join another game
+///
+/// All limits are read from the `AppConfig` of the worker.
+///
+/// A failing game is logged and skipped, so one broken game doesn't block the others.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `now` -> Point in time of the run
+pub async fn evict_stale_players_and_games(
+    app_state: &AppState,
+    now: DateTime<Utc>,
+) -> EvictionSummary {
+    let mut summary = EvictionSummary::default();
+
+    let games = match app_state.game_repository.get_active_games().await {
+        Ok(games) => games,
+        Err(err) => {
+            warn!("Active games couldn't be loaded for the eviction: {err}");
+            return summary;
+        }
+    };
+
+    for game in games {
+        match evict_game(app_state, &game.id, now).await {
+            Ok(game_summary) => {
+                summary.removed_players += game_summary.removed_players;
+                summary.closed_games += game_summary.closed_games;
+                summary.reaped_games += game_summary.reaped_games;
+            }
+            Err(response) => warn!(
+                "Eviction of game {} failed with status {}",
+                game.id,
+                response.status()
+            ),
+        }
+    }
+
+    info!(
+        "Eviction removed {} players and closed {} games, {} of them reaped by a cleanup policy",
+        summary.removed_players, summary.closed_games, summary.reaped_games
+    );
+
+    summary
+}
+
+/// Deletes the short-lived events of all games, like reactions, once they expired.
+///
+/// Runs on the cron trigger of the worker; a failure is logged and retried on the next run.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the event repository
+pub async fn prune_expired_events(app_state: &AppState) {
+    match app_state
+        .game_event_repository
+        .delete_expired_events()
+        .await
+    {
+        Ok(deleted) => info!("Pruning removed {deleted} expired events"),
+        Err(err) => warn!("Expired events couldn't be pruned: {err}"),
+    }
+}
+
+/// Deletes the oldest messages of every chat beyond the cap of a chat.
+///
+/// Messages are pruned when they are sent as well; the cron run catches chats which grew past
+/// the cap otherwise, e.g. through failed requests or older versions of the worker.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the chat message repository
+pub async fn prune_chat_messages(app_state: &AppState) {
+    match app_state.chat_message_repository.prune_messages(None).await {
+        Ok(deleted) => info!("Pruning removed {deleted} chat messages"),
+        Err(err) => warn!("Chat messages couldn't be pruned: {err}"),
+    }
+}
+
+/// Removes the timed out players of one game and closes it if necessary.
+///
+/// Players of a paused game are kept, since nobody is expected to act until it's resumed.
+async fn evict_game(
+    app_state: &AppState,
+    game_id: &str,
+    now: DateTime<Utc>,
+) -> Result<EvictionSummary, Response> {
+    let mut summary = EvictionSummary::default();
+    let mut game = load_game(app_state, game_id).await?;
+    let config = &app_state.config;
+
+    let paused = game.state == GameState::Paused;
+    let timed_out: Vec<String> = game
+        .players
+        .iter()
+        .filter(|player| !paused && player.is_timed_out(now, config.player_timeout_minutes))
+        .map(|player| player.id.clone())
+        .collect();
+    for player_id in timed_out {
+        game = remove_player_from_game(app_state, game, &player_id, "timedOut").await?;
+        summary.removed_players += 1;
+    }
+
+    if let Some(policy) = exceeded_policy(&game, config, now) {
+        close_game(app_state, &mut game, GameState::Abandoned).await?;
+        free_players(app_state, &mut game).await?;
+        record_metric(app_state, Metric::GameReaped { policy });
+        info!("Game {} was reaped by the policy {policy}", game.id);
+        summary.closed_games += 1;
+        summary.reaped_games += 1;
+        return Ok(summary);
+    }
+
+    let stale_lobby = game.state == GameState::WaitingForPlayers
+        && now - opened_at(&game) > Duration::hours(config.stale_lobby_hours);
+    let running = matches!(
+        game.state,
+        GameState::InProgress | GameState::RoundEnding | GameState::Paused
+    );
+
+    if game.players.is_empty() || stale_lobby {
+        close_game(app_state, &mut game, GameState::Abandoned).await?;
+        summary.closed_games += 1;
+    } else if running && game.players.len() < MIN_PLAYERS {
+        close_game(app_state, &mut game, GameState::Ended).await?;
+        summary.closed_games += 1;
+    }
+
+    Ok(summary)
+}
+
+/// Finds the first `CleanupPolicy` a game exceeds.
+///
+/// A waiting game is idle since the last player joined it, or since it was opened if nobody
+/// joined yet. A limit set to `0` is never exceeded.
+fn exceeded_policy(game: &Game, config: &AppConfig, now: DateTime<Utc>) -> Option<CleanupPolicy> {
+    let last_join = game
+        .players
+        .iter()
+        .map(|player| player.joined_at)
+        .fold(opened_at(game), DateTime::max);
+
+    if config.max_game_age_hours > 0
+        && now - opened_at(game) > Duration::hours(config.max_game_age_hours)
+    {
+        Some(CleanupPolicy::MaxGameAge)
+    } else if config.lobby_idle_minutes > 0
+        && game.state == GameState::WaitingForPlayers
+        && now - last_join > Duration::minutes(config.lobby_idle_minutes)
+    {
+        Some(CleanupPolicy::LobbyIdle)
+    } else if config.max_rounds > 0 && game.round_number > config.max_rounds {
+        Some(CleanupPolicy::MaxRounds)
+    } else {
+        None
+    }
+}
+
+/// Point in time the cleanup limits of a game count from: its creation, or the last time the
+/// host reclaimed it.
+fn opened_at(game: &Game) -> DateTime<Utc> {
+    game.reclaimed_at.unwrap_or(game.started_at)
+}
+
+/// Deletes the remaining players of an abandoned game together with their hands.
+async fn free_players(app_state: &AppState, game: &mut Game) -> Result<(), Response> {
+    for player in game.players.drain(..) {
+        app_state
+            .card_repository
+            .delete_cards_of_player(&player.id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        app_state
+            .player_repository
+            .delete_player(&player.id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+    }
+
+    Ok(())
+}
+
+/// Ends or abandons a game and notifies the remaining players.
+///
+/// The state of an abandoned game is kept in the event log, so the host can reclaim the game
+/// within `reclaim_grace_minutes`.
+async fn close_game(
+    app_state: &AppState,
+    game: &mut Game,
+    final_state: GameState,
+) -> Result<(), Response> {
+    let abandonment = match final_state {
+        GameState::Abandoned => Some(Abandonment::new(game, server_time())),
+        _ => None,
+    };
+    let game_data = if final_state == GameState::Ended {
+        game.finish().map_err(IntoResponse::into_response)?;
+        game.outcome_update()
+    } else {
+        game.transition_to(final_state)
+            .map_err(IntoResponse::into_response)?;
+        game.finished_at = Some(server_time());
+        UpdateGameDTO {
+            id: game.id.clone(),
+            state: Some(game.state.clone()),
+            finished_at: game.finished_at,
+            expected_revision: Some(game.revision),
+            ..Default::default()
+        }
+    };
+
+    app_state
+        .game_repository
+        .update_game(game_data, &app_state.player_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if let Some(abandonment) = abandonment {
+        publish_event(app_state, &GameEvent::GameAbandoned(abandonment), None).await;
+    }
+    publish_event(app_state, &GameEvent::game_ended(game), None).await;
+    if final_state == GameState::Ended {
+        enqueue_game_end_tasks(app_state, game).await;
+    } else {
+        notify_webhooks(
+            app_state,
+            &game.id,
+            WebhookEvent::GameEnded,
+            game_ended_data(game),
+        )
+        .await;
+    }
+
+    Ok(())
+}
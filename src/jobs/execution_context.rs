@@ -0,0 +1,55 @@
+use std::{future::Future, sync::Arc};
+
+use worker::Context;
+
+use crate::logging::log_context;
+
+/// Schedules work that doesn't affect the response of a request.
+///
+/// In the `fetch` handler the work is handed to `Context::wait_until`, so the response is sent
+/// right away and the worker stays alive until the work is done. Scheduled runs and the queue
+/// consumer have no response to speed up and run the work inline.
+///
+/// The deferred work runs after the handler returned, so it must not borrow from the request;
+/// clone the `AppState` and everything else it needs into the future.
+#[derive(Clone, Default)]
+pub struct ExecutionContext {
+    /// Context of the `fetch` event; `None` runs the work inline
+    context: Option<Arc<Context>>,
+}
+
+impl ExecutionContext {
+    /// Defers work to the lifetime of the passed `fetch` event.
+    ///
+    /// # Arguments
+    ///
+    /// - `context` -> Context of the `fetch` event
+    pub fn new(context: Context) -> Self {
+        ExecutionContext {
+            context: Some(Arc::new(context)),
+        }
+    }
+
+    /// Runs all work inline; used outside of the `fetch` handler.
+    pub fn inline() -> Self {
+        ExecutionContext { context: None }
+    }
+
+    /// Runs work after the response was sent or, without a `fetch` context, right away.
+    ///
+    /// Failures need to be handled inside of the work; there is no one left to report them to.
+    ///
+    /// # Arguments
+    ///
+    /// - `work` -> Future owning everything it needs
+    pub async fn defer<F>(&self, work: F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        match &self.context {
+            // the work is polled outside of the request, it takes the ids of the log lines along
+            Some(context) => context.wait_until(log_context::current().scope(work)),
+            None => work.await,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    handlers::game_handlers::load_game,
+    i18n::locale::Locale,
+    router::router_provider::AppState,
+    types::{game::Game, game_archive::build_archive, game_view::GameView},
+};
+
+// constants
+/// Maximum number of events written into one export.
+const MAX_EXPORTED_EVENTS: usize = 10_000;
+
+/// Serializes a finished game and writes it to the `ARCHIVES` bucket.
+///
+/// The export contains the final state of the game, all public events and the chat. Exporting a
+/// game again replaces the previous export.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `game_id` -> Id of the finished game
+///
+/// # Errors
+///
+/// Returns the response of the first failing query or a `503` error if the worker has no bucket.
+pub async fn export_game(app_state: &AppState, game_id: &str) -> Result<(), Response> {
+    let Some(archive_storage) = &app_state.archive_storage else {
+        return Err(DatabaseQueryError::<Game>::new(
+            "Games can't be exported right now!".to_string(),
+            None,
+            StatusCode::SERVICE_UNAVAILABLE,
+        )
+        .into_response());
+    };
+
+    // the chat is written line by line instead of as part of the game
+    let game = load_game(app_state, game_id).await?;
+    let chat = app_state
+        .chat_repository
+        .get_or_create_chat_of_game(game_id, &app_state.chat_message_repository)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let events = app_state
+        .game_event_repository
+        .get_events_since(game_id, None, 0, MAX_EXPORTED_EVENTS)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let view = GameView::for_spectator(&game, &Locale::En);
+    let archive = build_archive(&view, &events, &chat.messages).map_err(|err| {
+        DatabaseQueryError::<Game>::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            .into_response()
+    })?;
+
+    archive_storage
+        .put_archive(game_id, archive)
+        .await
+        .map_err(IntoResponse::into_response)
+}
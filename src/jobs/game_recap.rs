@@ -0,0 +1,36 @@
+use log::warn;
+
+use crate::{
+    handlers::event_handlers::publish_event, logic::recap::build_recap,
+    router::router_provider::AppState, types::game_event::GameEvent,
+};
+
+// constants
+/// Maximum number of events the recap of a game is derived from.
+const MAX_RECAP_EVENTS: usize = 10_000;
+
+/// Derives the highlights of a finished game from its event log and publishes them as a
+/// `gameRecap` event.
+///
+/// The game already ended, so a failure is only logged; the clients show no recap then.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the event repository
+/// - `game_id` -> Id of the finished game
+pub async fn publish_game_recap(app_state: &AppState, game_id: &str) {
+    let events = match app_state
+        .game_event_repository
+        .get_events_since(game_id, None, 0, MAX_RECAP_EVENTS)
+        .await
+    {
+        Ok(events) => events,
+        Err(err) => {
+            warn!("The recap of game {game_id} couldn't be derived: {err}");
+            return;
+        }
+    };
+
+    let recap = build_recap(game_id, &events);
+    publish_event(app_state, &GameEvent::GameRecap(recap), None).await;
+}
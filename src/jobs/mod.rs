@@ -0,0 +1,8 @@
+pub mod disconnects;
+pub mod eviction;
+pub mod execution_context;
+pub mod game_export;
+pub mod game_recap;
+pub mod task_queue;
+pub mod tournaments;
+pub mod webhooks;
@@ -0,0 +1,239 @@
+use axum::response::{IntoResponse, Response};
+use log::warn;
+use worker::{
+    send::{SendFuture, SendWrapper},
+    Env, MessageBuilder, Queue,
+};
+
+use crate::{
+    enums::{leaderboard_period::LeaderboardPeriod, webhook_event::WebhookEvent},
+    handlers::{achievement_handlers::unlock_achievements, game_handlers::load_game},
+    jobs::{
+        game_export::export_game,
+        game_recap::publish_game_recap,
+        tournaments::advance_tournament,
+        webhooks::{deliver_webhook, game_ended_data, notify_webhooks},
+    },
+    logic::achievements::earned_by_stats,
+    router::router_provider::AppState,
+    types::{background_task::BackgroundTask, game::Game, game_result::GameResult},
+};
+
+// constants
+/// Name of the queue binding the background tasks are sent to.
+pub const TASK_QUEUE_BINDING: &str = "TASKS";
+
+/// Producer of the `TASKS` queue.
+#[derive(Clone)]
+pub struct TaskQueue {
+    /// Queue the tasks are sent to
+    queue: SendWrapper<Queue>,
+}
+
+impl TaskQueue {
+    /// Creates the producer from the queue binding of the worker.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the queue isn't bound; the tasks are then run before the response is sent.
+    pub fn from_env(env: &Env) -> Option<Self> {
+        match env.queue(TASK_QUEUE_BINDING) {
+            Ok(queue) => Some(TaskQueue {
+                queue: SendWrapper(queue),
+            }),
+            Err(err) => {
+                warn!("Task queue not found, tasks run inline: {err}");
+                None
+            }
+        }
+    }
+}
+
+/// Hands a task over to the queue consumer.
+///
+/// Runs the task right away if the worker has no queue or the task couldn't be sent, so nothing
+/// gets lost. A failing task is only logged; the request which caused it already succeeded.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the queue and all repositories
+/// - `task` -> Task to process
+pub async fn enqueue_task(app_state: &AppState, task: BackgroundTask) {
+    if let Some(task_queue) = &app_state.task_queue {
+        let queue = task_queue.queue.clone();
+        let message = task.clone();
+
+        match SendFuture::new(async move { queue.send(message).await }).await {
+            Ok(()) => return,
+            Err(err) => warn!("'{task}' couldn't be enqueued, running it inline: {err}"),
+        }
+    }
+
+    if let Err(response) = run_task(app_state, &task).await {
+        warn!("'{task}' failed with status {}", response.status());
+    }
+}
+
+/// Hands a task over to the queue consumer, which gets it after a delay.
+///
+/// Unlike `enqueue_task`, the task isn't run inline if there's no queue, since the delay couldn't
+/// be kept.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the queue
+/// - `task` -> Task to process
+/// - `delay_seconds` -> Seconds the queue holds the task back
+///
+/// # Returns
+///
+/// `true` if the task was enqueued.
+pub async fn enqueue_delayed_task(
+    app_state: &AppState,
+    task: BackgroundTask,
+    delay_seconds: u32,
+) -> bool {
+    let Some(task_queue) = &app_state.task_queue else {
+        return false;
+    };
+    let queue = task_queue.queue.clone();
+    let message = MessageBuilder::new(task.clone())
+        .delay_seconds(delay_seconds)
+        .build();
+
+    match SendFuture::new(async move { queue.send(message).await }).await {
+        Ok(()) => true,
+        Err(err) => {
+            warn!("'{task}' couldn't be enqueued with a delay: {err}");
+            false
+        }
+    }
+}
+
+/// Hands the work after the end of a game over to the queue consumer.
+///
+/// The tasks are enqueued after the response was sent, right after the recap of the game was
+/// published, so the clients can show it without waiting for the queue. Also notifies the webhooks
+/// subscribed to the game.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the queue and all repositories
+/// - `game` -> The finished game
+pub async fn enqueue_game_end_tasks(app_state: &AppState, game: &Game) {
+    let state = app_state.clone();
+    let game_id = game.id.clone();
+
+    app_state
+        .execution
+        .defer(async move {
+            publish_game_recap(&state, &game_id).await;
+            enqueue_task(
+                &state,
+                BackgroundTask::AggregateStats {
+                    game_id: game_id.clone(),
+                },
+            )
+            .await;
+            enqueue_task(
+                &state,
+                BackgroundTask::ExportGame {
+                    game_id: game_id.clone(),
+                },
+            )
+            .await;
+            enqueue_task(&state, BackgroundTask::AdvanceTournament { game_id }).await;
+        })
+        .await;
+    notify_webhooks(
+        app_state,
+        &game.id,
+        WebhookEvent::GameEnded,
+        game_ended_data(game),
+    )
+    .await;
+}
+
+/// Processes a single task.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `task` -> Task to process
+///
+/// # Errors
+///
+/// Returns the response of the first failing query; the queue delivers the task again.
+pub async fn run_task(app_state: &AppState, task: &BackgroundTask) -> Result<(), Response> {
+    match task {
+        BackgroundTask::AggregateStats { game_id } => {
+            let game = load_game(app_state, game_id).await?;
+            let results = GameResult::from_game(&game);
+
+            app_state
+                .game_result_repository
+                .add_results(results.clone())
+                .await
+                .map_err(IntoResponse::into_response)?;
+            let season = match results.first() {
+                Some(result) => app_state
+                    .season_repository
+                    .get_season_at(result.finished_at)
+                    .await
+                    .map_err(IntoResponse::into_response)?,
+                None => None,
+            };
+            for result in &results {
+                let identity = result.identity();
+                for period in LeaderboardPeriod::ALL {
+                    app_state
+                        .leaderboard_repository
+                        .refresh_entry(period, period.bounds(result.finished_at), &identity)
+                        .await
+                        .map_err(IntoResponse::into_response)?;
+                }
+                if let Some(season) = &season {
+                    app_state
+                        .leaderboard_repository
+                        .refresh_entry(LeaderboardPeriod::Season, season.bounds(), &identity)
+                        .await
+                        .map_err(IntoResponse::into_response)?;
+                }
+
+                let stats = app_state
+                    .game_result_repository
+                    .get_player_stats(&identity)
+                    .await
+                    .map_err(IntoResponse::into_response)?;
+                if let Some(stats) = stats {
+                    unlock_achievements(
+                        app_state,
+                        &game.id,
+                        &result.player_id,
+                        &identity,
+                        &earned_by_stats(&stats),
+                    )
+                    .await;
+                }
+            }
+        }
+        BackgroundTask::ExportGame { game_id } => export_game(app_state, game_id).await?,
+        BackgroundTask::DeliverWebhook {
+            webhook_id,
+            payload,
+            failed_attempts,
+        } => deliver_webhook(app_state, webhook_id, payload, *failed_attempts).await?,
+        BackgroundTask::AdvanceTournament { game_id } => {
+            let tournament_id = app_state
+                .tournament_repository
+                .get_tournament_id_of_game(game_id)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            if let Some(tournament_id) = tournament_id {
+                advance_tournament(app_state, &tournament_id).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
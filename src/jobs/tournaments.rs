@@ -0,0 +1,300 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use log::{info, warn};
+
+use crate::{
+    enums::{game_state::GameState, tournament_state::TournamentState},
+    errors::database_query_error::DatabaseQueryError,
+    handlers::game_handlers::{create_private_game, load_game},
+    logic::bracket::{group_entrants, match_winner},
+    router::router_provider::AppState,
+    types::tournament::{Tournament, TournamentEntrant, TournamentMatch, TournamentSeat},
+    utils::time::server_time,
+};
+
+// constants
+/// Most running tournaments advanced by one cron run.
+const TOURNAMENTS_PER_RUN: usize = 100;
+
+/// Decides the finished matches of every running tournament and starts their next rounds.
+///
+/// Runs on the cron trigger of the worker. Games that end normally advance their tournament right
+/// away with the `AdvanceTournament` task; the cron run catches abandoned games and rounds that
+/// couldn't be set up.
+///
+/// A failing tournament is logged and skipped, so one broken tournament doesn't block the others.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+pub async fn advance_running_tournaments(app_state: &AppState) {
+    let tournaments = match app_state
+        .tournament_repository
+        .get_tournaments(TournamentState::Running, TOURNAMENTS_PER_RUN)
+        .await
+    {
+        Ok(tournaments) => tournaments,
+        Err(err) => {
+            warn!("Running tournaments couldn't be loaded: {err}");
+            return;
+        }
+    };
+
+    for tournament in &tournaments {
+        if let Err(response) = advance_tournament(app_state, &tournament.id).await {
+            warn!(
+                "Tournament {} couldn't be advanced, status {}",
+                tournament.id,
+                response.status()
+            );
+        }
+    }
+}
+
+/// Decides the finished matches of the current round and sets up the next round once all of them
+/// are decided.
+///
+/// -> The winner of every ended or abandoned game advances, see `match_winner`
+/// -> The other entrants of the game are eliminated in the round
+/// -> Once a single entrant is left, they win the tournament
+///
+/// Every step only changes what wasn't changed before, so advancing a tournament twice is
+/// harmless.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `tournament_id` -> The tournament to advance
+///
+/// # Errors
+///
+/// Returns the response of the first failing query.
+pub async fn advance_tournament(app_state: &AppState, tournament_id: &str) -> Result<(), Response> {
+    let repository = &app_state.tournament_repository;
+    let tournament = repository
+        .get_tournament(tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if tournament.state != TournamentState::Running {
+        return Ok(());
+    }
+
+    let round = tournament.current_round;
+    let matches = repository
+        .get_matches(tournament_id, Some(round))
+        .await
+        .map_err(IntoResponse::into_response)?;
+    let seats = repository
+        .get_seats(tournament_id, Some(round))
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let mut undecided = 0;
+    for tournament_match in &matches {
+        if tournament_match.winner_entrant_id.is_some() {
+            continue;
+        }
+        if !decide_match(app_state, tournament_match, &seats).await? {
+            undecided += 1;
+        }
+    }
+    if undecided > 0 {
+        return Ok(());
+    }
+
+    let remaining: Vec<TournamentEntrant> = repository
+        .get_entrants(tournament_id)
+        .await
+        .map_err(IntoResponse::into_response)?
+        .into_iter()
+        .filter(|entrant| entrant.eliminated_in_round.is_none())
+        .collect();
+
+    match remaining.as_slice() {
+        [] => Ok(()),
+        [champion] => {
+            repository
+                .change_state(
+                    tournament_id,
+                    &[TournamentState::Running],
+                    TournamentState::Finished,
+                    Some(&champion.id),
+                    server_time(),
+                )
+                .await
+                .map_err(IntoResponse::into_response)?;
+            info!(
+                "Tournament {tournament_id} was won by {}",
+                champion.player_name
+            );
+            Ok(())
+        }
+        _ => start_round(app_state, &tournament, round + 1, &remaining).await,
+    }
+}
+
+/// Sets up the matches of a round and creates their games.
+///
+/// The round is claimed first, so only one worker creates its games. If a game can't be created,
+/// the round is removed again and set up by the next cron run.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding all repositories
+/// - `tournament` -> The running tournament
+/// - `round` -> The round to set up, one after the current round of the tournament
+/// - `entrants` -> Entrants of the round, best seed first
+///
+/// # Errors
+///
+/// Returns the response of the first failing query.
+pub async fn start_round(
+    app_state: &AppState,
+    tournament: &Tournament,
+    round: usize,
+    entrants: &[TournamentEntrant],
+) -> Result<(), Response> {
+    let repository = &app_state.tournament_repository;
+    let claimed = repository
+        .claim_round(&tournament.id, round - 1, round)
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if !claimed {
+        return Ok(());
+    }
+
+    let groups = group_entrants(entrants, tournament.players_per_game);
+    for (index, group) in groups.iter().enumerate() {
+        if let Err(response) = add_match(app_state, tournament, round, index + 1, group).await {
+            warn!(
+                "Round {round} of tournament {} couldn't be set up with status {}, it is tried again",
+                tournament.id,
+                response.status()
+            );
+            if let Err(err) = repository.discard_round(&tournament.id, round).await {
+                warn!(
+                    "Round {round} of tournament {} couldn't be removed: {err}",
+                    tournament.id
+                );
+            }
+            return Err(response);
+        }
+    }
+
+    info!(
+        "Round {round} of tournament {} started with {} matches",
+        tournament.id,
+        groups.len()
+    );
+
+    Ok(())
+}
+
+// ----- Helper functions of the tournament jobs -----
+
+/// Records the winner of a match if its game is over.
+///
+/// # Returns
+///
+/// `true` if the match is decided now.
+async fn decide_match(
+    app_state: &AppState,
+    tournament_match: &TournamentMatch,
+    seats: &[TournamentSeat],
+) -> Result<bool, Response> {
+    // a bye is decided when it is created
+    let Some(game_id) = &tournament_match.game_id else {
+        return Ok(false);
+    };
+    let game = match load_game(app_state, game_id).await {
+        Ok(game) if game.state != GameState::Ended && game.state != GameState::Abandoned => {
+            return Ok(false)
+        }
+        Ok(game) => Some(game),
+        // the cleanup already removed the game
+        Err(response) if response.status() == StatusCode::NOT_FOUND => None,
+        Err(response) => return Err(response),
+    };
+
+    let seats_of_match: Vec<TournamentSeat> = seats
+        .iter()
+        .filter(|seat| seat.slot == tournament_match.slot)
+        .cloned()
+        .collect();
+    let Some(winner) = match_winner(&seats_of_match, game.as_ref()) else {
+        return Ok(false);
+    };
+
+    app_state
+        .tournament_repository
+        .finish_match(tournament_match, &winner.entrant_id, server_time())
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(true)
+}
+
+/// Stores a match of a round; a single entrant gets a bye, the others get a new game.
+async fn add_match(
+    app_state: &AppState,
+    tournament: &Tournament,
+    round: usize,
+    slot: usize,
+    entrants: &[TournamentEntrant],
+) -> Result<(), Response> {
+    let now = server_time();
+    let (tournament_match, seats) = match entrants {
+        [bye] => (
+            TournamentMatch {
+                tournament_id: tournament.id.clone(),
+                round,
+                slot,
+                game_id: None,
+                winner_entrant_id: Some(bye.id.clone()),
+                finished_at: Some(now),
+            },
+            vec![(bye.id.clone(), None)],
+        ),
+        _ => {
+            let players: Vec<(String, Option<String>)> = entrants
+                .iter()
+                .map(|entrant| (entrant.player_name.clone(), entrant.fingerprint.clone()))
+                .collect();
+            let (game, players) = match create_private_game(app_state, &players).await {
+                Ok(created) => created,
+                Err(message) => {
+                    return Err(DatabaseQueryError::<TournamentMatch>::new(
+                        message,
+                        None,
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    )
+                    .into_response())
+                }
+            };
+
+            (
+                TournamentMatch {
+                    tournament_id: tournament.id.clone(),
+                    round,
+                    slot,
+                    game_id: Some(game.id),
+                    winner_entrant_id: None,
+                    finished_at: None,
+                },
+                entrants
+                    .iter()
+                    .zip(players)
+                    .map(|(entrant, player)| (entrant.id.clone(), Some(player.id)))
+                    .collect(),
+            )
+        }
+    };
+
+    app_state
+        .tournament_repository
+        .add_match(&tournament_match, &seats)
+        .await
+        .map_err(IntoResponse::into_response)
+}
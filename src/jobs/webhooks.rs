@@ -0,0 +1,251 @@
+use axum::response::{IntoResponse, Response};
+use log::warn;
+use serde_json::{json, Value};
+use wasm_bindgen::JsValue;
+use worker::{send::SendFuture, Fetch, Headers, Method, Request, RequestInit};
+
+use crate::{
+    auth::session_token::sign,
+    enums::webhook_event::WebhookEvent,
+    errors::delivery_error::DeliveryError,
+    jobs::task_queue::{enqueue_delayed_task, enqueue_task},
+    router::router_provider::AppState,
+    types::{
+        background_task::BackgroundTask,
+        game::Game,
+        webhook::{Webhook, WebhookDeadLetter, WebhookPayload},
+    },
+};
+
+// constants
+/// Name of the worker secret signing the payloads of webhooks registered without their own secret.
+pub const WEBHOOK_SECRET_BINDING: &str = "WEBHOOK_SECRET";
+
+/// Header carrying the signature of a payload, `sha256=<hex>`.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Header carrying the name of the event.
+pub const WEBHOOK_EVENT_HEADER: &str = "X-LueLue-Event";
+
+/// Header carrying the id of the delivery, the same for every retry.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-LueLue-Delivery";
+
+/// Number of attempts to deliver a payload before it's moved to the dead-letter log.
+pub const MAX_WEBHOOK_ATTEMPTS: u32 = 5;
+
+/// Seconds before the first retry of a delivery; every further retry waits twice as long.
+pub const WEBHOOK_RETRY_BASE_SECONDS: u32 = 30;
+
+/// Sends an event of a game to every webhook subscribed to it.
+///
+/// Each webhook gets its own `DeliverWebhook` task, so a failing receiver is retried without
+/// sending the event to the others again.
+///
+/// The subscriptions are looked up after the response was sent.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the queue and all repositories
+/// - `game_id` -> Game the event happened in
+/// - `event` -> Lifecycle event
+/// - `data` -> Details of the event
+pub async fn notify_webhooks(
+    app_state: &AppState,
+    game_id: &str,
+    event: WebhookEvent,
+    data: Value,
+) {
+    let state = app_state.clone();
+    let game_id = game_id.to_string();
+
+    app_state
+        .execution
+        .defer(async move {
+            let webhooks = match state
+                .webhook_repository
+                .get_subscribed_webhooks(&game_id)
+                .await
+            {
+                Ok(webhooks) => webhooks,
+                Err(err) => {
+                    warn!(
+                        "Webhooks of game {game_id} couldn't be loaded: {}",
+                        err.message
+                    );
+                    return;
+                }
+            };
+
+            let payload = WebhookPayload::new(event, game_id, data);
+            for webhook in webhooks {
+                enqueue_task(
+                    &state,
+                    BackgroundTask::DeliverWebhook {
+                        webhook_id: webhook.id,
+                        payload: payload.clone(),
+                        failed_attempts: 0,
+                    },
+                )
+                .await;
+            }
+        })
+        .await;
+}
+
+/// Posts a signed payload to a webhook.
+///
+/// The payload is signed with the secret of the webhook, or with the `WEBHOOK_SECRET` of the
+/// worker for webhooks registered before they had their own; a webhook without either is skipped.
+/// A webhook that was removed in the meantime is skipped as well.
+///
+/// A failed attempt is sent to the queue again, waiting `WEBHOOK_RETRY_BASE_SECONDS` before the
+/// first retry and twice as long before every further one. After `MAX_WEBHOOK_ATTEMPTS`, or if
+/// the worker has no queue to wait in, the delivery is logged as a dead letter instead.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the secret and all repositories
+/// - `webhook_id` -> Id of the receiving webhook
+/// - `payload` -> Event to send
+/// - `failed_attempts` -> Number of attempts that already failed
+///
+/// # Errors
+///
+/// Returns the response of a failing query; the queue delivers the task again.
+pub async fn deliver_webhook(
+    app_state: &AppState,
+    webhook_id: &str,
+    payload: &WebhookPayload,
+    failed_attempts: u32,
+) -> Result<(), Response> {
+    let Some(webhook) = app_state
+        .webhook_repository
+        .get_webhook(webhook_id)
+        .await
+        .map_err(IntoResponse::into_response)?
+    else {
+        return Ok(());
+    };
+    let Some(secret) = webhook
+        .secret
+        .as_ref()
+        .or(app_state.webhook_secret.as_ref())
+    else {
+        warn!(
+            "Delivery {} dropped, webhook {webhook_id} has no secret",
+            payload.delivery_id
+        );
+        return Ok(());
+    };
+
+    let Err(err) = post_payload(&webhook, secret, payload).await else {
+        return Ok(());
+    };
+    let attempts = failed_attempts + 1;
+
+    if attempts < MAX_WEBHOOK_ATTEMPTS {
+        let retry = BackgroundTask::DeliverWebhook {
+            webhook_id: webhook_id.to_string(),
+            payload: payload.clone(),
+            failed_attempts: attempts,
+        };
+        if enqueue_delayed_task(app_state, retry, retry_delay_seconds(attempts)).await {
+            return Ok(());
+        }
+    }
+
+    warn!(
+        "Delivery {} to webhook {webhook_id} failed after {attempts} attempts: {}",
+        payload.delivery_id, err.message
+    );
+    app_state
+        .webhook_repository
+        .add_dead_letter(WebhookDeadLetter::new(
+            webhook_id.to_string(),
+            payload,
+            attempts,
+            err.message,
+        ))
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+/// Seconds a delivery waits before its next attempt.
+///
+/// # Arguments
+///
+/// - `failed_attempts` -> Number of attempts that already failed, at least `1`
+pub fn retry_delay_seconds(failed_attempts: u32) -> u32 {
+    WEBHOOK_RETRY_BASE_SECONDS
+        .saturating_mul(2u32.saturating_pow(failed_attempts.saturating_sub(1)))
+}
+
+/// Builds the details of a `game_ended` event.
+///
+/// # Arguments
+///
+/// - `game` -> The ended or abandoned game
+pub fn game_ended_data(game: &Game) -> Value {
+    json!({
+        "state": game.state,
+        "winnerPlayerId": game.winner_player_id,
+        "finishedAt": game.finished_at,
+    })
+}
+
+/// Posts a payload with its signature to the URL of a webhook.
+///
+/// # Errors
+///
+/// Returns a `DeliveryError` if the receiver couldn't be reached or didn't answer with a `2xx`
+/// status.
+async fn post_payload(
+    webhook: &Webhook,
+    secret: &str,
+    payload: &WebhookPayload,
+) -> Result<(), DeliveryError> {
+    let failed = |message: String| DeliveryError::new(message, format!("webhook {}", webhook.id));
+
+    let body = serde_json::to_string(payload).map_err(|err| failed(err.to_string()))?;
+    let signature = format!("sha256={}", to_hex(&sign(body.as_bytes(), secret)));
+
+    let headers = Headers::new();
+    for (name, value) in [
+        ("Content-Type", "application/json"),
+        (WEBHOOK_SIGNATURE_HEADER, signature.as_str()),
+        (WEBHOOK_EVENT_HEADER, payload.event.as_str()),
+        (WEBHOOK_DELIVERY_HEADER, payload.delivery_id.as_str()),
+    ] {
+        headers
+            .set(name, value)
+            .map_err(|err| failed(err.to_string()))?;
+    }
+    let mut init = RequestInit::new();
+    init.with_method(Method::Post)
+        .with_headers(headers)
+        .with_body(Some(JsValue::from_str(&body)));
+    let request =
+        Request::new_with_init(&webhook.url, &init).map_err(|err| failed(err.to_string()))?;
+
+    let status = SendFuture::new(async move {
+        Fetch::Request(request)
+            .send()
+            .await
+            .map(|response| response.status_code())
+    })
+    .await
+    .map_err(|err| failed(err.to_string()))?;
+
+    if !(200..300).contains(&status) {
+        return Err(failed(format!(
+            "The receiver answered with status {status}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Encodes bytes as lowercase hex, the format receivers usually compare signatures in.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -1,12 +1,24 @@
 // crates inclusion
+pub mod auth;
+pub mod config;
+pub mod durable;
 pub mod enums;
 pub mod errors;
 pub mod handlers;
+pub mod i18n;
+pub mod jobs;
+pub mod logging;
 pub mod logic;
+pub mod mail;
+pub mod metrics;
 pub mod middleware;
 pub mod repositories;
 pub mod router;
+#[cfg(any(test, feature = "simulation"))]
+pub mod simulation;
 pub mod status;
+#[cfg(any(test, feature = "test_support"))]
+pub mod test_support;
 pub mod types;
 pub mod utils;
 
@@ -16,29 +28,306 @@ use tower_service::Service;
 use worker::*;
 
 use crate::{
-    repositories::game_repository::GameRepository,
+    auth::{
+        admin::ADMIN_TOKEN_BINDING, session_token::SESSION_SECRET_BINDING,
+        turnstile::TURNSTILE_SECRET_BINDING,
+    },
+    config::{app_config::AppConfig, feature_flags::FeatureFlags},
+    enums::{deployment_environment::DeploymentEnvironment, feature::Feature},
+    jobs::{
+        disconnects::pass_stalled_turns,
+        eviction::{evict_stale_players_and_games, prune_chat_messages, prune_expired_events},
+        execution_context::ExecutionContext,
+        task_queue::{run_task, TaskQueue},
+        tournaments::advance_running_tournaments,
+        webhooks::WEBHOOK_SECRET_BINDING,
+    },
+    logging::{json_logger, log_context::LogContext},
+    logic::chat_filter::ChatFilterConfig,
+    mail::mailer::Mailer,
+    metrics::analytics::MetricsRecorder,
+    repositories::{
+        achievement_repository::AchievementRepository,
+        archive_storage::ArchiveStorage,
+        audit_repository::AuditRepository,
+        backup_repository::BackupRepository,
+        card_repository::CardRepository,
+        chat::{chat_message_repository::ChatMessageRepository, chat_repository::ChatRepository},
+        claim_repository::ClaimsRepository,
+        daily_challenge_repository::DailyChallengeRepository,
+        database::{ChaosSettings, Database},
+        game_cache::GameCache,
+        game_event_repository::GameEventRepository,
+        game_repository::GameRepository,
+        game_result_repository::GameResultRepository,
+        game_rooms::GameRooms,
+        invitation_repository::InvitationRepository,
+        leaderboard_repository::LeaderboardRepository,
+        maintenance_repository::MaintenanceRepository,
+        matchmaking::Matchmaking,
+        moderation_repository::ModerationRepository,
+        player_repository::PlayerRepository,
+        score_history_repository::ScoreHistoryRepository,
+        season_repository::SeasonRepository,
+        tournament_repository::TournamentRepository,
+        typing_indicators::TypingIndicators,
+        waitlist_repository::WaitlistRepository,
+        webhook_repository::WebhookRepository,
+    },
     router::router_provider::{self, AppState},
+    types::background_task::BackgroundTask,
+    utils::time::server_time,
 };
 
+// constants
+/// Header carrying the id Cloudflare assigned to a request.
+const CF_RAY_HEADER: &str = "cf-ray";
+
 #[event(fetch)]
 async fn fetch(
     req: HttpRequest,
     env: Env,
-    _ctx: Context,
+    ctx: Context,
 ) -> Result<axum::http::Response<axum::body::Body>> {
-    // TODO: Set up database repositories for all types relevant for direct data exchange
+    console_error_panic_hook::set_once();
 
+    // Tag every log line of this request with its id
+    let request_id = req
+        .headers()
+        .get(CF_RAY_HEADER)
+        .and_then(|ray| ray.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    LogContext::for_request(request_id)
+        .scope(handle_request(req, env, ctx))
+        .await
+}
+
+/// Routes a request of the `fetch` event.
+async fn handle_request(
+    req: HttpRequest,
+    env: Env,
+    ctx: Context,
+) -> Result<axum::http::Response<axum::body::Body>> {
     // Get the database binding -> access to D1 database
-    let _database = env.d1("DB").map_err(|err| {
+    let database = get_database(&env)?;
+
+    // Side effects of the request run after the response was sent
+    let execution = ExecutionContext::new(ctx);
+
+    // The repositories of this request share the handle, the state doesn't borrow from `fetch`
+    let app_state = build_app_state(&env, database, execution).await?;
+
+    Ok(router_provider::router(app_state).call(req).await?)
+}
+
+#[event(scheduled)]
+async fn scheduled(_event: ScheduledEvent, env: Env, _ctx: ScheduleContext) {
+    console_error_panic_hook::set_once();
+
+    let database = match get_database(&env) {
+        Ok(database) => database,
+        Err(err) => {
+            warn!("Scheduled run skipped: {err}");
+            return;
+        }
+    };
+    let app_state = match build_app_state(&env, database, ExecutionContext::inline()).await {
+        Ok(app_state) => app_state,
+        Err(err) => {
+            warn!("Scheduled run skipped: {err}");
+            return;
+        }
+    };
+
+    // Remove players who stopped requesting status updates and close dead games
+    evict_stale_players_and_games(&app_state, server_time()).await;
+
+    // Pass the turns of players whose heartbeat stopped, so no table waits for them
+    pass_stalled_turns(&app_state, server_time()).await;
+
+    // Drop reactions and other short-lived events once nobody can receive them anymore
+    prune_expired_events(&app_state).await;
+
+    // Keep every chat within its message cap in the database
+    prune_chat_messages(&app_state).await;
+
+    // Decide the matches of abandoned tournament games and retry rounds that couldn't be set up
+    advance_running_tournaments(&app_state).await;
+}
+
+#[event(queue)]
+async fn queue(batch: MessageBatch<BackgroundTask>, env: Env, _ctx: Context) -> Result<()> {
+    console_error_panic_hook::set_once();
+
+    let database = get_database(&env)?;
+    let app_state = build_app_state(&env, database, ExecutionContext::inline()).await?;
+
+    // failing tasks are delivered again, the others are done
+    for message in batch.messages()? {
+        let task = run_task(&app_state, message.body());
+        match LogContext::for_request(message.id()).scope(task).await {
+            Ok(()) => message.ack(),
+            Err(response) => {
+                warn!(
+                    "'{}' failed with status {}, retrying",
+                    message.body(),
+                    response.status()
+                );
+                message.retry();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the D1 database of the worker.
+fn get_database(env: &Env) -> Result<Database> {
+    env.d1("DB").map(Database::new).map_err(|err| {
         warn!("{err}");
         worker::Error::RustError("DB binding not found".to_string())
-    })?;
-    console_error_panic_hook::set_once();
-    Ok(router_provider::router(AppState {
-        game_repository: GameRepository::new(&_database),
     })
-    .call(req)
-    .await?)
+}
+
+/// Builds the state of the app from the bindings of the worker.
+///
+/// # Arguments
+///
+/// - `env` -> Environment of the worker holding secrets and bindings
+/// - `database` -> Handle of the D1 database, every repository gets a clone of it
+/// - `execution` -> Context the side effects of the requests are deferred to
+///
+/// # Errors
+///
+/// Returns an error if a required secret is missing.
+async fn build_app_state(
+    env: &Env,
+    database: Database,
+    execution: ExecutionContext,
+) -> Result<AppState> {
+    // Read the tunables first, the bindings below depend on them
+    let config = AppConfig::from_env(env);
+
+    // Write the log lines as JSON with the configured verbosity
+    json_logger::init(config.log_level);
+
+    // Switch the experimental subsystems of this environment on or off
+    let features = FeatureFlags::load(env, config.environment).await;
+
+    // Get the key to sign the session tokens of the players
+    let session_secret = env
+        .secret(SESSION_SECRET_BINDING)
+        .map_err(|err| {
+            warn!("{err}");
+            worker::Error::RustError("Session secret not found".to_string())
+        })?
+        .to_string();
+
+    // Get the key to validate the Turnstile tokens on game creation
+    let turnstile_secret = env
+        .secret(TURNSTILE_SECRET_BINDING)
+        .map_err(|err| {
+            warn!("{err}");
+            worker::Error::RustError("Turnstile secret not found".to_string())
+        })?
+        .to_string();
+
+    // Get the token of the operators; the admin endpoints stay locked without it
+    let admin_token = env
+        .secret(ADMIN_TOKEN_BINDING)
+        .map(|secret| secret.to_string())
+        .ok();
+
+    // Get the key to sign the payloads of webhooks without their own secret
+    let webhook_secret = env
+        .secret(WEBHOOK_SECRET_BINDING)
+        .map(|secret| secret.to_string())
+        .ok();
+
+    // Get the banned words and the rate cap of the chat
+    let chat_filter = ChatFilterConfig::load(env, &config).await;
+
+    // Cache the games every client polls; the worker reads D1 directly without the namespace
+    let game_cache = GameCache::from_env(env, execution.clone());
+
+    // Keep the live games in their Durable Objects in the `durable-object` storage mode
+    let game_rooms = GameRooms::from_env(env, config.storage_mode);
+
+    // Hand work that doesn't affect the response over to the queue consumer
+    let task_queue = TaskQueue::from_env(env);
+
+    // Write the metrics of the dashboards to Analytics Engine
+    let metrics = MetricsRecorder::from_env(env);
+
+    // Time every query of the repositories
+    let database = database.with_metrics(metrics.clone(), config.slow_query_ms);
+
+    // Degrade the queries on purpose for load tests; never in production
+    let database = if features.is_enabled(Feature::Chaos)
+        && config.environment != DeploymentEnvironment::Production
+    {
+        database.with_chaos(ChaosSettings {
+            latency_ms: config.chaos_latency_ms,
+            failure_percent: config.chaos_failure_percent,
+        })
+    } else {
+        database
+    };
+
+    // Send the invitation emails through MailChannels
+    let mailer = Mailer::from_env(env, config.invite_sender_email.clone());
+
+    // Store the exports of finished games in R2
+    let archive_storage = ArchiveStorage::from_env(env);
+
+    // Assemble games from solo players in the matchmaking queue
+    let matchmaking = Matchmaking::from_env(env);
+
+    // Show who types in the chat without storing it
+    let typing_indicators = TypingIndicators::from_env(env);
+
+    Ok(AppState {
+        game_repository: GameRepository::new(database.clone())
+            .with_cache(game_cache)
+            .with_rooms(game_rooms),
+        player_repository: PlayerRepository::new(database.clone()),
+        card_repository: CardRepository::new(database.clone()),
+        claims_repository: ClaimsRepository::new(database.clone()),
+        chat_repository: ChatRepository::new(database.clone()),
+        chat_message_repository: ChatMessageRepository::new(database.clone()),
+        game_event_repository: GameEventRepository::new(database.clone()),
+        game_result_repository: GameResultRepository::new(database.clone()),
+        invitation_repository: InvitationRepository::new(database.clone()),
+        leaderboard_repository: LeaderboardRepository::new(database.clone()),
+        achievement_repository: AchievementRepository::new(database.clone()),
+        maintenance_repository: MaintenanceRepository::new(database.clone()),
+        moderation_repository: ModerationRepository::new(database.clone()),
+        webhook_repository: WebhookRepository::new(database.clone()),
+        audit_repository: AuditRepository::new(database.clone()),
+        tournament_repository: TournamentRepository::new(database.clone()),
+        season_repository: SeasonRepository::new(database.clone()),
+        waitlist_repository: WaitlistRepository::new(database.clone()),
+        daily_challenge_repository: DailyChallengeRepository::new(database.clone()),
+        score_history_repository: ScoreHistoryRepository::new(database.clone()),
+        backup_repository: BackupRepository::new(database),
+        config,
+        features,
+        session_secret,
+        turnstile_secret,
+        webhook_secret,
+        admin_token,
+        chat_filter,
+        task_queue,
+        execution,
+        metrics,
+        mailer,
+        archive_storage,
+        matchmaking,
+        typing_indicators,
+        env: env.clone(),
+    })
 }
 
 // Documentation
@@ -61,5 +350,3 @@ async fn fetch(
 // endpoints -> implement endpoints
 // refactor -> refactor code
 // => all are merge into dev
-
-// TODO: Need extra thread for checking if a player is offline and discard him / her from the game
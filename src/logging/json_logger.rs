@@ -0,0 +1,83 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde_json::{json, Map, Value};
+use worker::{console_debug, console_error, console_log, console_warn};
+
+use crate::logging::log_context;
+use crate::utils::time::server_time;
+
+// constants
+/// The logger of the worker; installed once per isolate.
+static LOGGER: JsonLogger = JsonLogger;
+
+/// Writes every record of the `log` macros as one JSON object to the console of the worker.
+///
+/// Workers Logs indexes the fields of JSON lines, so they can be filtered by level, request, game
+/// or player. The ids are taken from the `LogContext` of the current request; a missing id is
+/// left out.
+///
+/// ```json
+/// {"timestamp":"...","level":"WARN","module":"lue_lue_backend::jobs::webhooks","message":"...","request_id":"...","game_id":"..."}
+/// ```
+pub struct JsonLogger;
+
+impl Log for JsonLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format_record(record).to_string();
+        match record.level() {
+            Level::Error => console_error!("{line}"),
+            Level::Warn => console_warn!("{line}"),
+            Level::Info => console_log!("{line}"),
+            Level::Debug | Level::Trace => console_debug!("{line}"),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger and sets the level of the records it writes.
+///
+/// Can be called on every invocation; the logger is only installed once per isolate, the level
+/// is updated every time.
+///
+/// # Arguments
+///
+/// - `level` -> Most verbose level that is written
+pub fn init(level: LevelFilter) {
+    // fails if the isolate already installed the logger in an earlier invocation
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
+
+/// Builds the JSON object of a record.
+fn format_record(record: &Record) -> Value {
+    let context = log_context::current();
+
+    let mut line = Map::new();
+    line.insert("timestamp".to_string(), json!(server_time().to_rfc3339()));
+    line.insert("level".to_string(), json!(record.level().as_str()));
+    line.insert(
+        "module".to_string(),
+        json!(record.module_path().unwrap_or(record.target())),
+    );
+    line.insert("message".to_string(), json!(record.args().to_string()));
+
+    for (key, value) in [
+        ("request_id", context.request_id),
+        ("game_id", context.game_id),
+        ("player_id", context.player_id),
+    ] {
+        if let Some(value) = value {
+            line.insert(key.to_string(), json!(value));
+        }
+    }
+
+    Value::Object(line)
+}
@@ -0,0 +1,93 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+thread_local! {
+    /// Context of the request whose future is currently polled.
+    static CURRENT: RefCell<LogContext> = RefCell::new(LogContext::default());
+}
+
+/// Ids attached to every log line written while a request is handled.
+///
+/// An isolate handles several requests at once on its single thread, so the context can't simply
+/// be set at the start of a request. `WithLogContext` swaps it in before each poll of the request
+/// future and takes it back afterwards; every line is tagged with the request it came from.
+///
+/// # Props
+///
+/// - `request_id` -> Id of the request, the `cf-ray` header if present
+/// - `game_id` -> Game the request is about
+/// - `player_id` -> Authenticated player who sent the request
+#[derive(Debug, Clone, Default)]
+pub struct LogContext {
+    /// Id of the request, the `cf-ray` header if present
+    pub request_id: Option<String>,
+    /// Game the request is about
+    pub game_id: Option<String>,
+    /// Authenticated player who sent the request
+    pub player_id: Option<String>,
+}
+
+impl LogContext {
+    /// Creates the context of a new request.
+    ///
+    /// # Arguments
+    ///
+    /// - `request_id` -> Id of the request
+    pub fn for_request(request_id: String) -> Self {
+        LogContext {
+            request_id: Some(request_id),
+            ..Default::default()
+        }
+    }
+
+    /// Runs a future with this context attached to its log lines.
+    pub fn scope<F: Future>(self, future: F) -> WithLogContext<F> {
+        WithLogContext {
+            inner: Box::pin(future),
+            context: self,
+        }
+    }
+}
+
+/// Future running with a `LogContext`; created by `LogContext::scope`.
+pub struct WithLogContext<F> {
+    /// The wrapped future
+    inner: Pin<Box<F>>,
+    /// Context swapped in while the future is polled
+    context: LogContext,
+}
+
+impl<F: Future> Future for WithLogContext<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        // the ids set while polling belong to this request, so they are taken back afterwards
+        let previous = CURRENT.with(|current| current.replace(mem::take(&mut this.context)));
+        let result = this.inner.as_mut().poll(cx);
+        this.context = CURRENT.with(|current| current.replace(previous));
+
+        result
+    }
+}
+
+/// Returns a copy of the context of the current request.
+pub fn current() -> LogContext {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Attaches a game to the log lines of the current request.
+pub fn set_game_id(game_id: &str) {
+    CURRENT.with(|current| current.borrow_mut().game_id = Some(game_id.to_string()));
+}
+
+/// Attaches a player to the log lines of the current request.
+pub fn set_player_id(player_id: &str) {
+    CURRENT.with(|current| current.borrow_mut().player_id = Some(player_id.to_string()));
+}
@@ -0,0 +1,2 @@
+pub mod json_logger;
+pub mod log_context;
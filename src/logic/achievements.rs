@@ -0,0 +1,69 @@
+use crate::{
+    enums::{achievement::Achievement, card_types::CardType},
+    types::{claim::Claim, player_stats::PlayerStats},
+};
+
+// constants
+/// Successful bluffs needed for `Achievement::TenSuccessfulBluffs`.
+const BLUFFS_FOR_ACHIEVEMENT: usize = 10;
+
+/// Successful challenges needed for `Achievement::TenSuccessfulChallenges`.
+const CHALLENGES_FOR_ACHIEVEMENT: usize = 10;
+
+/// Finished games needed for `Achievement::Veteran`.
+const GAMES_FOR_VETERAN: usize = 25;
+
+/// Claims of one player a round needs to count as flawless.
+const CLAIMS_FOR_FLAWLESS_ROUND: usize = 2;
+
+/// Returns the achievements the lifetime statistics of a player qualify for.
+///
+/// Evaluated once a finished game was aggregated; achievements that were unlocked before are
+/// returned again and ignored when they are stored.
+///
+/// # Arguments
+///
+/// - `stats` -> Lifetime statistics of the player including the finished game
+pub fn earned_by_stats(stats: &PlayerStats) -> Vec<Achievement> {
+    Achievement::ALL
+        .into_iter()
+        .filter(|achievement| match achievement {
+            Achievement::FirstWin => stats.wins > 0,
+            Achievement::TenSuccessfulBluffs => stats.successful_bluffs >= BLUFFS_FOR_ACHIEVEMENT,
+            Achievement::TenSuccessfulChallenges => {
+                stats.successful_challenges >= CHALLENGES_FOR_ACHIEVEMENT
+            }
+            Achievement::Veteran => stats.games_played >= GAMES_FOR_VETERAN,
+            // earned by a move, see `is_flawless_round`
+            Achievement::FlawlessRound => false,
+        })
+        .collect()
+}
+
+/// Checks whether a challenged player played a flawless round.
+///
+/// A round is flawless if the player made at least `CLAIMS_FOR_FLAWLESS_ROUND` claims in it, all
+/// of them truthful, and the challenge ending the round failed.
+///
+/// # Arguments
+///
+/// - `claims` -> Claims of the game, at least those of the round
+/// - `player_id` -> The player whose claim was challenged
+/// - `round_number` -> The round the challenge ended
+/// - `card_to_play` -> Card type of the round
+pub fn is_flawless_round(
+    claims: &[Claim],
+    player_id: &str,
+    round_number: usize,
+    card_to_play: &CardType,
+) -> bool {
+    let claims_of_player: Vec<&Claim> = claims
+        .iter()
+        .filter(|claim| claim.round_number == round_number && claim.created_by == player_id)
+        .collect();
+
+    claims_of_player.len() >= CLAIMS_FOR_FLAWLESS_ROUND
+        && claims_of_player
+            .iter()
+            .all(|claim| claim.is_truthful(card_to_play))
+}
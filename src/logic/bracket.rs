@@ -0,0 +1,128 @@
+use crate::types::{
+    game::Game,
+    tournament::{TournamentEntrant, TournamentMatch, TournamentSeat, TournamentStanding},
+};
+
+/// Spreads the entrants of a round over its games.
+///
+/// The number of games is the smallest one keeping every game at most `players_per_game` players.
+/// The entrants are dealt out one by one in the order they are passed, so the best seeds end up in
+/// different games and the sizes of the games differ by one at most. A game of a single entrant is
+/// a bye.
+///
+/// # Arguments
+///
+/// - `entrants` -> Entrants of the round, best seed first
+/// - `players_per_game` -> Most players of one game
+///
+/// # Returns
+///
+/// The entrants of every game, in the order of the slots.
+pub fn group_entrants<T: Clone>(entrants: &[T], players_per_game: usize) -> Vec<Vec<T>> {
+    let game_count = entrants.len().div_ceil(players_per_game.max(1));
+    let mut groups = vec![Vec::new(); game_count];
+
+    for (index, entrant) in entrants.iter().enumerate() {
+        groups[index % game_count].push(entrant.clone());
+    }
+
+    groups
+}
+
+/// Decides which entrant of a finished match advances.
+///
+/// -> The winner of the game, if it has one
+/// -> Otherwise the seated entrant with the fewest cards in hand; ties go to the better seed
+/// -> Otherwise, e.g. if everyone left an abandoned game, the entrant with the best seed
+///
+/// # Arguments
+///
+/// - `seats` -> Seats of the match
+/// - `game` -> The ended or abandoned game of the match with its remaining players; `None` if the
+///   game was already cleaned up
+///
+/// # Returns
+///
+/// The seat of the advancing entrant, or `None` if the match has no seats.
+pub fn match_winner<'a>(
+    seats: &'a [TournamentSeat],
+    game: Option<&Game>,
+) -> Option<&'a TournamentSeat> {
+    let seat_of_player = |player_id: &str| {
+        seats
+            .iter()
+            .find(|seat| seat.player_id.as_deref() == Some(player_id))
+    };
+    let best_seed = || seats.iter().min_by_key(|seat| seat.seed);
+    let Some(game) = game else {
+        return best_seed();
+    };
+
+    if let Some(winner) = game.winner_player_id.as_deref().and_then(seat_of_player) {
+        return Some(winner);
+    }
+
+    game.players
+        .iter()
+        .filter_map(|player| {
+            seat_of_player(&player.id).map(|seat| (player.assigned_cards.len(), seat))
+        })
+        .min_by_key(|(cards, seat)| (*cards, seat.seed))
+        .map(|(_, seat)| seat)
+        .or_else(best_seed)
+}
+
+/// Ranks the entrants of a tournament.
+///
+/// The winner comes first, followed by the entrants who are still in the tournament and then by
+/// the round they were eliminated in, latest first. Within the same round more won games and then
+/// the better seed rank higher.
+///
+/// # Arguments
+///
+/// - `entrants` -> All entrants of the tournament
+/// - `matches` -> All matches of the tournament
+/// - `winner_entrant_id` -> Entrant who won the tournament, if it is finished
+pub fn rank_entrants(
+    entrants: &[TournamentEntrant],
+    matches: &[TournamentMatch],
+    winner_entrant_id: Option<&str>,
+) -> Vec<TournamentStanding> {
+    let mut standings: Vec<TournamentStanding> = entrants
+        .iter()
+        .map(|entrant| TournamentStanding {
+            rank: 0,
+            seed: entrant.seed,
+            player_name: entrant.player_name.clone(),
+            games_won: matches
+                .iter()
+                .filter(|tournament_match| {
+                    tournament_match.game_id.is_some()
+                        && tournament_match.winner_entrant_id.as_deref()
+                            == Some(entrant.id.as_str())
+                })
+                .count(),
+            eliminated_in_round: entrant.eliminated_in_round,
+            is_winner: winner_entrant_id == Some(entrant.id.as_str()),
+        })
+        .collect();
+
+    standings.sort_by(|a, b| {
+        b.is_winner
+            .cmp(&a.is_winner)
+            .then_with(|| {
+                // `None` ranks above every round an entrant was eliminated in
+                let round = |standing: &TournamentStanding| {
+                    standing.eliminated_in_round.unwrap_or(usize::MAX)
+                };
+                round(b).cmp(&round(a))
+            })
+            .then_with(|| b.games_won.cmp(&a.games_won))
+            .then_with(|| a.seed.cmp(&b.seed))
+    });
+    for (index, standing) in standings.iter_mut().enumerate() {
+        standing.rank = index + 1;
+    }
+
+    standings
+}
@@ -0,0 +1,285 @@
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use worker::Env;
+
+use crate::{
+    config::app_config::{
+        AppConfig, DEFAULT_CHAT_MAX_MESSAGES_PER_WINDOW, DEFAULT_CHAT_RATE_WINDOW_SECONDS,
+    },
+    enums::filter_action::FilterAction,
+    errors::rate_limit_error::RateLimitError,
+    i18n::catalog::Message,
+    types::chat::{Chat, ChatMessage},
+};
+
+// constants
+/// Name of the KV namespace holding the configuration of the chat filter.
+pub const CHAT_FILTER_KV_BINDING: &str = "CHAT_FILTER";
+
+/// Key of the configuration inside the KV namespace.
+pub const CHAT_FILTER_CONFIG_KEY: &str = "config";
+
+/// Character replacing every character of a banned word.
+const MASK_CHARACTER: char = '*';
+
+/// Configuration of the chat filter.
+///
+/// Stored as JSON in the `CHAT_FILTER` KV namespace, so the list of banned words can be changed
+/// without a new deployment.
+///
+/// # Props
+///
+/// - `banned_words` -> Words that aren't allowed in the chat, compared case-insensitively
+/// - `action` -> What happens to a message containing a banned word
+/// - `max_messages_per_window` -> Number of messages a player may send within one window
+/// - `rate_window_seconds` -> Length of the rate window in seconds
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChatFilterConfig {
+    /// Words that aren't allowed in the chat, compared case-insensitively
+    #[serde(alias = "banned_words")]
+    pub banned_words: Vec<String>,
+    /// What happens to a message containing a banned word
+    pub action: FilterAction,
+    /// Number of messages a player may send within one window
+    #[serde(alias = "max_messages_per_window")]
+    pub max_messages_per_window: usize,
+    /// Length of the rate window in seconds
+    #[serde(alias = "rate_window_seconds")]
+    pub rate_window_seconds: i64,
+}
+
+/// Result of running a message through the chat filter.
+///
+/// # Variants
+///
+/// - `Clean` -> The message contains no banned word
+/// - `Censored` -> The banned words of the message were masked
+/// - `Rejected` -> The message must not be sent
+#[derive(Debug, Clone)]
+pub enum Moderation {
+    /// The message contains no banned word.
+    Clean(ChatMessage),
+    /// The banned words of the message were masked.
+    Censored(ChatMessage),
+    /// The message must not be sent.
+    Rejected(ChatMessage),
+}
+
+impl Default for ChatFilterConfig {
+    fn default() -> Self {
+        ChatFilterConfig {
+            banned_words: vec![],
+            action: FilterAction::default(),
+            max_messages_per_window: DEFAULT_CHAT_MAX_MESSAGES_PER_WINDOW,
+            rate_window_seconds: DEFAULT_CHAT_RATE_WINDOW_SECONDS,
+        }
+    }
+}
+
+impl ChatFilterConfig {
+    /// Loads the configuration from KV.
+    ///
+    /// Falls back to a configuration which bans no words if the namespace or the key is missing,
+    /// so the chat keeps working. Rate limits which aren't set in KV are taken from the
+    /// `AppConfig` of the worker.
+    ///
+    /// # Arguments
+    ///
+    /// - `env` -> Environment of the worker
+    /// - `app_config` -> Configuration of the worker holding the default rate limits
+    pub async fn load(env: &Env, app_config: &AppConfig) -> Self {
+        let fallback = ChatFilterConfig {
+            max_messages_per_window: app_config.chat_max_messages_per_window,
+            rate_window_seconds: app_config.chat_rate_window_seconds,
+            ..Default::default()
+        };
+
+        let store = match env.kv(CHAT_FILTER_KV_BINDING) {
+            Ok(store) => store,
+            Err(err) => {
+                warn!("Chat filter namespace not found: {err}");
+                return fallback;
+            }
+        };
+
+        let stored = match store.get(CHAT_FILTER_CONFIG_KEY).json::<Value>().await {
+            Ok(Some(stored)) => stored,
+            Ok(None) => return fallback,
+            Err(err) => {
+                warn!("Chat filter configuration couldn't be read: {err}");
+                return fallback;
+            }
+        };
+
+        match serde_json::from_value::<ChatFilterConfig>(with_rate_limits(stored, &fallback)) {
+            Ok(config) => config.normalized(),
+            Err(err) => {
+                warn!("Chat filter configuration is invalid: {err}");
+                fallback
+            }
+        }
+    }
+
+    /// Lowercases and trims the banned words, dropping empty entries.
+    fn normalized(mut self) -> Self {
+        self.banned_words = self
+            .banned_words
+            .iter()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        self
+    }
+
+    /// Checks that the player didn't exceed the message rate in a chat.
+    ///
+    /// Only messages of the player themselves are counted; system messages are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `chat` -> Chat including its latest messages
+    /// - `player_id` -> Id of the player who wants to send a message
+    /// - `now` -> Point in time the new message is sent at
+    ///
+    /// # Errors
+    ///
+    /// Returns a `RateLimitError` telling the player when they may send the next message.
+    pub fn check_rate(
+        &self,
+        chat: &Chat,
+        player_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), RateLimitError> {
+        let window_start = now - Duration::seconds(self.rate_window_seconds);
+        let recent: Vec<&DateTime<Utc>> = chat
+            .messages
+            .iter()
+            .filter(|message| {
+                !message.is_system
+                    && message.player_id == player_id
+                    && message.sent_at > window_start
+            })
+            .map(|message| &message.sent_at)
+            .collect();
+
+        if recent.len() < self.max_messages_per_window {
+            return Ok(());
+        }
+
+        let oldest = recent.into_iter().min().copied().unwrap_or(now);
+        Err(RateLimitError::new(
+            format!(
+                "Only {} messages are allowed within {} seconds!",
+                self.max_messages_per_window, self.rate_window_seconds
+            ),
+            (oldest + Duration::seconds(self.rate_window_seconds) - now).num_seconds(),
+        ))
+    }
+
+    /// Runs a message through the word filter.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` -> Message a player wants to send
+    ///
+    /// # Returns
+    ///
+    /// The `Moderation` of the message, depending on the configured `action`.
+    pub fn moderate(&self, mut message: ChatMessage) -> Moderation {
+        let censored = self.censor(&message.content);
+        if censored == message.content {
+            return Moderation::Clean(message);
+        }
+
+        match self.action {
+            FilterAction::Replace => {
+                message.content = censored;
+                Moderation::Censored(message)
+            }
+            FilterAction::Reject => Moderation::Rejected(message),
+        }
+    }
+
+    /// Masks every banned word of a text.
+    ///
+    /// Words are compared case-insensitively and only as a whole, so harmless words containing a
+    /// banned word stay untouched.
+    pub fn censor(&self, text: &str) -> String {
+        if self.banned_words.is_empty() {
+            return text.to_string();
+        }
+
+        let mut censored = String::with_capacity(text.len());
+        let mut word = String::new();
+        for character in text.chars() {
+            if character.is_alphanumeric() {
+                word.push(character);
+                continue;
+            }
+            self.push_word(&mut censored, &word);
+            word.clear();
+            censored.push(character);
+        }
+        self.push_word(&mut censored, &word);
+
+        censored
+    }
+
+    /// Appends a word to the text, masked if it is banned.
+    fn push_word(&self, text: &mut String, word: &str) {
+        if self.banned_words.contains(&word.to_lowercase()) {
+            text.extend(word.chars().map(|_| MASK_CHARACTER));
+        } else {
+            text.push_str(word);
+        }
+    }
+}
+
+/// Creates the notice which is sent in the chat instead of, or next to, a filtered message.
+///
+/// # Arguments
+///
+/// - `moderation` -> Result of the word filter
+///
+/// # Returns
+///
+/// A system message about the sender, or `None` if the message was clean.
+pub fn moderation_notice(moderation: &Moderation) -> Option<ChatMessage> {
+    match moderation {
+        Moderation::Clean(_) => None,
+        Moderation::Censored(message) => Some(ChatMessage::system(
+            message.player_id.clone(),
+            Message::ChatCensoredNotice,
+        )),
+        Moderation::Rejected(message) => Some(ChatMessage::system(
+            message.player_id.clone(),
+            Message::ChatRejectedNotice,
+        )),
+    }
+}
+
+/// Adds the rate limits of the fallback to a stored configuration that doesn't set them.
+fn with_rate_limits(mut stored: Value, fallback: &ChatFilterConfig) -> Value {
+    if let Some(fields) = stored.as_object_mut() {
+        if !fields.contains_key("maxMessagesPerWindow")
+            && !fields.contains_key("max_messages_per_window")
+        {
+            fields.insert(
+                "maxMessagesPerWindow".to_string(),
+                json!(fallback.max_messages_per_window),
+            );
+        }
+        if !fields.contains_key("rateWindowSeconds") && !fields.contains_key("rate_window_seconds")
+        {
+            fields.insert(
+                "rateWindowSeconds".to_string(),
+                json!(fallback.rate_window_seconds),
+            );
+        }
+    }
+
+    stored
+}
@@ -0,0 +1,58 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha8Rng,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{enums::card_types::CardType, utils::game_service::new_rng};
+
+// constants
+/// Stream of the seeded generator the deck is shuffled with; round `n` uses stream `n`.
+const DECK_STREAM: u64 = 0;
+
+/// Generates the secret seed of a new daily challenge.
+///
+/// The seed is random instead of derived from the date alone, so nobody can compute the hands of
+/// a day in advance.
+pub fn generate_daily_seed() -> String {
+    let mut seed = [0u8; 32];
+    new_rng().fill_bytes(&mut seed);
+
+    URL_SAFE_NO_PAD.encode(seed)
+}
+
+/// Creates the generator shuffling the deck of a daily challenge.
+///
+/// Every game of the same day gets the same generator, so all players are dealt the same cards
+/// for the same number of players.
+///
+/// # Arguments
+///
+/// - `seed` -> Seed of the daily challenge
+pub fn daily_deck_rng(seed: &str) -> ChaCha8Rng {
+    seeded_rng(seed, DECK_STREAM)
+}
+
+/// Selects the card to play of a round of a daily challenge.
+///
+/// Like `select_new_card_to_be_played` a Joker is never selected.
+///
+/// # Arguments
+///
+/// - `seed` -> Seed of the daily challenge
+/// - `round` -> Round the card is played in, starting at 1
+pub fn daily_card_to_play(seed: &str, round: usize) -> CardType {
+    let ranks = CardType::ranks();
+    let index = (seeded_rng(seed, round as u64).next_u32() % ranks.len() as u32) as usize;
+
+    ranks[index].clone()
+}
+
+/// Creates a generator from the seed of a daily challenge and selects one of its streams.
+fn seeded_rng(seed: &str, stream: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::from_seed(Sha256::digest(seed.as_bytes()).into());
+    rng.set_stream(stream);
+
+    rng
+}
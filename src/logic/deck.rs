@@ -0,0 +1,160 @@
+use rand_chacha::rand_core::RngCore;
+
+use crate::{
+    enums::{card_types::CardType, suit::Suit},
+    errors::process_error::ProcessError,
+    types::{card::Card, player::Player},
+};
+
+// constants
+
+/// Number of cards in a standard deck without jokers.
+pub const STANDARD_DECK_SIZE: usize = 52;
+
+/// A deck of cards used to deal the hands of the players.
+///
+/// A standard deck contains every combination of the 13 ranks and 4 suits and optionally some
+/// jokers. Games with more players shuffle several standard decks together, so the same rank and
+/// suit can occur more than once; the cards are told apart by their ids only.
+///
+/// # Example
+///
+/// ```rust
+/// let mut deck = Deck::standard(2);
+/// deck.shuffle(&mut new_rng());
+/// deck.deal(&mut game.players)?;
+/// ```
+pub struct Deck {
+    /// Cards remaining in the deck; the last card is on top.
+    pub cards: Vec<Card>,
+}
+
+impl Deck {
+    /// Creates a new unshuffled standard deck.
+    ///
+    /// # Arguments
+    ///
+    /// - `number_of_jokers` -> How many jokers are added to the 52 cards
+    pub fn standard(number_of_jokers: usize) -> Self {
+        Deck::combined(1, number_of_jokers)
+    }
+
+    /// Creates a new unshuffled deck out of several standard decks.
+    ///
+    /// # Arguments
+    ///
+    /// - `number_of_decks` -> How many standard decks of 52 cards are combined
+    /// - `number_of_jokers` -> How many jokers are added in total
+    pub fn combined(number_of_decks: usize, number_of_jokers: usize) -> Self {
+        let mut cards = Vec::with_capacity(STANDARD_DECK_SIZE * number_of_decks + number_of_jokers);
+
+        for _ in 0..number_of_decks {
+            for suit in Suit::all() {
+                for rank in CardType::ranks() {
+                    cards.push(Card::with_suit(rank, suit.clone()));
+                }
+            }
+        }
+
+        for _ in 0..number_of_jokers {
+            cards.push(Card::new(CardType::Joker));
+        }
+
+        Deck { cards }
+    }
+
+    /// Shuffles the deck with the Fisher-Yates algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// - `rng` -> Random number generator; should be a CSPRNG like the one of `new_rng()`
+    pub fn shuffle(&mut self, rng: &mut impl RngCore) {
+        for i in (1..self.cards.len()).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            self.cards.swap(i, j);
+        }
+    }
+
+    /// Deals all cards of the deck to the players one by one.
+    ///
+    /// The first player gets the first card. If the cards can't be split evenly the first players
+    /// receive one card more.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` if there are no players to deal to.
+    pub fn deal(&mut self, players: &mut [Player]) -> Result<(), ProcessError<Card>> {
+        if players.is_empty() {
+            return Err(ProcessError::new(
+                "Can't deal the cards! There are no players in the game!".to_string(),
+                "Deck::deal".to_string(),
+                None,
+            ));
+        }
+
+        let mut index = 0;
+        while let Some(card) = self.cards.pop() {
+            players[index % players.len()].assigned_cards.push(card);
+            index += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Deals the same number of cards to every player one by one.
+    ///
+    /// Cards left over stay in the deck and are out of the game.
+    ///
+    /// # Arguments
+    ///
+    /// - `players` -> The players receiving a hand
+    /// - `hand_size` -> Number of cards every player receives
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` if there are no players or not enough cards for every hand.
+    pub fn deal_hands(
+        &mut self,
+        players: &mut [Player],
+        hand_size: usize,
+    ) -> Result<(), ProcessError<Card>> {
+        if players.is_empty() {
+            return Err(ProcessError::new(
+                "Can't deal the cards! There are no players in the game!".to_string(),
+                "Deck::deal_hands".to_string(),
+                None,
+            ));
+        }
+        if players.len() * hand_size > self.cards.len() {
+            return Err(ProcessError::new(
+                format!(
+                    "Can't deal {hand_size} cards to {} players with {} cards!",
+                    players.len(),
+                    self.cards.len()
+                ),
+                "Deck::deal_hands".to_string(),
+                None,
+            ));
+        }
+
+        for _ in 0..hand_size {
+            for player in players.iter_mut() {
+                if let Some(card) = self.cards.pop() {
+                    player.assigned_cards.push(card);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of cards left in the deck.
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Returns `true` if all cards were dealt.
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+}
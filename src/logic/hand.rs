@@ -0,0 +1,72 @@
+use serde::Serialize;
+
+use crate::{
+    enums::{card_types::CardType, hand_sort::HandSort},
+    types::card::Card,
+};
+
+/// Number of cards of one card type in a hand.
+///
+/// # Props
+///
+/// - `card_type` -> The card type
+/// - `count` -> Number of cards of the type in the hand
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CardGroup {
+    /// The card type
+    pub card_type: CardType,
+    /// Number of cards of the type in the hand
+    pub count: usize,
+}
+
+/// Sorts the cards of a hand in the order a player prefers.
+///
+/// The sort is stable, so cards of the same type and suit, e.g. from different decks, keep the
+/// order they were dealt in.
+///
+/// # Arguments
+///
+/// - `cards` -> The hand of the player
+/// - `order` -> The preferred order
+pub fn sort_hand(cards: &mut [Card], order: &HandSort) {
+    match order {
+        HandSort::Dealt => {}
+        HandSort::Rank => cards.sort_by_key(|card| (rank_of(card), suit_of(card))),
+        HandSort::Suit => cards.sort_by_key(|card| (suit_of(card), rank_of(card))),
+    }
+}
+
+/// Counts the cards of a hand by their type, from Two to Ace with the Jokers last.
+///
+/// # Arguments
+///
+/// - `cards` -> The hand of the player
+pub fn group_hand(cards: &[Card]) -> Vec<CardGroup> {
+    let mut groups: Vec<CardGroup> = vec![];
+
+    for card_type in CardType::ranks().into_iter().chain([CardType::Joker]) {
+        let count = cards
+            .iter()
+            .filter(|card| card.card_type == card_type)
+            .count();
+        if count > 0 {
+            groups.push(CardGroup { card_type, count });
+        }
+    }
+
+    groups
+}
+
+/// Position of the card type of a card from Two to Ace; Jokers come after all ranks.
+fn rank_of(card: &Card) -> usize {
+    CardType::ranks()
+        .iter()
+        .position(|rank| *rank == card.card_type)
+        .unwrap_or(CardType::ranks().len())
+}
+
+/// Position of the suit of a card; cards without a suit come last.
+fn suit_of(card: &Card) -> i32 {
+    card.suit.as_ref().map_or(i32::MAX, i32::from)
+}
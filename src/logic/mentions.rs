@@ -0,0 +1,58 @@
+use crate::types::player::Player;
+
+// constants
+/// Character starting a mention in a chat message.
+const MENTION_CHARACTER: char = '@';
+
+/// Finds the players a chat message mentions with `@name`.
+///
+/// Names are compared case-insensitively and may contain spaces, so the longest name following an
+/// `@` wins: `@Alice 2` mentions `Alice 2` even if another player is called `Alice`. A name only
+/// counts if no letter or digit follows it, so `@Bobby` doesn't mention `Bob`.
+///
+/// # Arguments
+///
+/// - `content` -> Content of the message
+/// - `players` -> Players of the game
+/// - `author_id` -> Id of the author, who can't mention themself
+///
+/// # Returns
+///
+/// The ids of the mentioned players, each once, in the order of their first mention.
+pub fn find_mentions(content: &str, players: &[Player], author_id: &str) -> Vec<String> {
+    let mut candidates: Vec<&Player> = players
+        .iter()
+        .filter(|player| player.id != author_id && !player.name.trim().is_empty())
+        .collect();
+    candidates.sort_by_key(|player| std::cmp::Reverse(player.name.trim().chars().count()));
+
+    let mut mentioned: Vec<String> = vec![];
+    for (index, _) in content.match_indices(MENTION_CHARACTER) {
+        let rest = &content[index + MENTION_CHARACTER.len_utf8()..];
+        let player = candidates
+            .iter()
+            .find(|player| mentions_name(rest, player.name.trim()));
+
+        if let Some(player) = player {
+            if !mentioned.contains(&player.id) {
+                mentioned.push(player.id.clone());
+            }
+        }
+    }
+
+    mentioned
+}
+
+/// Checks whether a text starts with a name that isn't continued by another letter or digit.
+fn mentions_name(text: &str, name: &str) -> bool {
+    let mut text_chars = text.chars();
+
+    for name_char in name.chars() {
+        match text_chars.next() {
+            Some(text_char) if text_char.to_lowercase().eq(name_char.to_lowercase()) => {}
+            _ => return false,
+        }
+    }
+
+    !text_chars.next().is_some_and(char::is_alphanumeric)
+}
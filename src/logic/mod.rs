@@ -0,0 +1,9 @@
+pub mod achievements;
+pub mod bracket;
+pub mod chat_filter;
+pub mod daily_challenge;
+pub mod deck;
+pub mod hand;
+pub mod mentions;
+pub mod recap;
+pub mod rules;
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::types::{
+    game_event::StoredGameEvent,
+    game_recap::{BluffHighlight, ChallengerHighlight, GameRecap, RoundHighlight, TurnHighlight},
+    round_summary::RoundSummary,
+};
+
+/// The parts of the logged events the recap is derived from.
+///
+/// Uses the same tags as `GameEvent`, so every other event falls into `Other`.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum LoggedEvent {
+    /// Another player needs to make a move.
+    #[serde(rename_all = "camelCase")]
+    TurnChanged {
+        /// Id of the player who needs to make a move
+        which_player_turn: String,
+        /// Current round of the game
+        round_number: usize,
+    },
+    /// The author of a claim took it back and is to move again.
+    #[serde(rename_all = "camelCase")]
+    ClaimRetracted {
+        /// Id of the author of the claim
+        which_player_turn: String,
+    },
+    /// The host resumed the game and the countdown of the turn starts over.
+    #[serde(rename_all = "camelCase")]
+    GameResumed {
+        /// Id of the player who needs to make a move
+        which_player_turn: String,
+    },
+    /// A round ended with a challenge.
+    RoundSummary(RoundSummary),
+    /// The game started over; everything before doesn't count.
+    GameReset {},
+    /// The host paused the game during a turn.
+    GamePaused {},
+    /// A player was kicked, left or timed out, possibly during their turn.
+    PlayerRemoved {},
+    /// The turn of a disconnected player was passed.
+    PlayerDisconnected {},
+    /// The game ended during a turn.
+    GameEnded {},
+    /// Any other event.
+    #[serde(other)]
+    Other,
+}
+
+/// Turn that started but wasn't finished with a move yet.
+struct OpenTurn {
+    /// Id of the player to move
+    player_id: String,
+    /// Round of the turn
+    round_number: usize,
+    /// Point in time the turn started
+    started_at: DateTime<Utc>,
+}
+
+/// Derives the highlights of a game from its event log.
+///
+/// A turn lasts from the event that gave the player the turn to the next turn change or the
+/// challenge ending the round. Turns interrupted by a pause, a removal, a disconnect or the end of
+/// the game don't count, since the player didn't move. Events which can't be read are skipped.
+///
+/// # Arguments
+///
+/// - `game_id` -> Id of the game
+/// - `events` -> Events of the game meant for everyone, ordered by their sequence
+pub fn build_recap(game_id: &str, events: &[StoredGameEvent]) -> GameRecap {
+    let mut recap = GameRecap::new(game_id);
+    let mut challengers: Vec<ChallengerHighlight> = vec![];
+    let mut round_starts: HashMap<usize, DateTime<Utc>> = HashMap::new();
+    let mut open_turn: Option<OpenTurn> = None;
+
+    for event in events {
+        let Ok(logged) = serde_json::from_str::<LoggedEvent>(&event.payload) else {
+            continue;
+        };
+
+        match logged {
+            LoggedEvent::TurnChanged {
+                which_player_turn,
+                round_number,
+            } => {
+                finish_turn(&mut recap, open_turn.take(), event.created_at);
+                round_starts.entry(round_number).or_insert(event.created_at);
+                open_turn = Some(OpenTurn {
+                    player_id: which_player_turn,
+                    round_number,
+                    started_at: event.created_at,
+                });
+            }
+            LoggedEvent::ClaimRetracted { which_player_turn }
+            | LoggedEvent::GameResumed { which_player_turn } => {
+                let round_number = open_turn
+                    .take()
+                    .map(|turn| turn.round_number)
+                    .or_else(|| round_starts.keys().max().copied())
+                    .unwrap_or(1);
+                open_turn = Some(OpenTurn {
+                    player_id: which_player_turn,
+                    round_number,
+                    started_at: event.created_at,
+                });
+            }
+            LoggedEvent::RoundSummary(summary) => {
+                finish_turn(&mut recap, open_turn.take(), event.created_at);
+                add_round(
+                    &mut recap,
+                    &mut challengers,
+                    &round_starts,
+                    &summary,
+                    event.created_at,
+                );
+            }
+            LoggedEvent::GameReset {} => {
+                recap = GameRecap::new(game_id);
+                challengers.clear();
+                round_starts.clear();
+                open_turn = None;
+            }
+            LoggedEvent::GamePaused {}
+            | LoggedEvent::PlayerRemoved {}
+            | LoggedEvent::PlayerDisconnected {}
+            | LoggedEvent::GameEnded {} => open_turn = None,
+            LoggedEvent::Other => {}
+        }
+    }
+
+    // the first challenger reaching the best tally keeps the highlight
+    recap.top_challenger = challengers
+        .into_iter()
+        .filter(|challenger| challenger.caught_bluffs > 0)
+        .reduce(|best, challenger| {
+            let is_better = challenger.caught_bluffs > best.caught_bluffs
+                || (challenger.caught_bluffs == best.caught_bluffs
+                    && challenger.challenges < best.challenges);
+            match is_better {
+                true => challenger,
+                false => best,
+            }
+        });
+
+    recap
+}
+
+/// Records a turn that ended with a move, if it was the fastest one so far.
+fn finish_turn(recap: &mut GameRecap, turn: Option<OpenTurn>, ended_at: DateTime<Utc>) {
+    let Some(turn) = turn else {
+        return;
+    };
+
+    let milliseconds = (ended_at - turn.started_at).num_milliseconds();
+    let is_fastest = recap
+        .fastest_turn
+        .as_ref()
+        .is_none_or(|fastest| milliseconds < fastest.milliseconds);
+    if is_fastest {
+        recap.fastest_turn = Some(TurnHighlight {
+            player_id: turn.player_id,
+            round_number: turn.round_number,
+            milliseconds,
+        });
+    }
+}
+
+/// Adds a round that ended with a challenge to the recap.
+fn add_round(
+    recap: &mut GameRecap,
+    challengers: &mut Vec<ChallengerHighlight>,
+    round_starts: &HashMap<usize, DateTime<Utc>>,
+    summary: &RoundSummary,
+    ended_at: DateTime<Utc>,
+) {
+    recap.rounds += 1;
+
+    if let Some(bluffer_id) = &summary.bluffer_id {
+        let card_count = summary.revealed_cards.len();
+        let is_biggest = recap
+            .biggest_bluff
+            .as_ref()
+            .is_none_or(|biggest| card_count > biggest.card_count);
+        if is_biggest {
+            recap.biggest_bluff = Some(BluffHighlight {
+                player_id: bluffer_id.clone(),
+                round_number: summary.round_number,
+                card_count,
+            });
+        }
+    }
+
+    let challenger = match challengers
+        .iter()
+        .position(|challenger| challenger.player_id == summary.challenger_id)
+    {
+        Some(index) => &mut challengers[index],
+        None => {
+            challengers.push(ChallengerHighlight {
+                player_id: summary.challenger_id.clone(),
+                caught_bluffs: 0,
+                challenges: 0,
+            });
+            challengers.last_mut().expect("a challenger was just added")
+        }
+    };
+    challenger.challenges += 1;
+    if summary.bluffer_id.is_some() {
+        challenger.caught_bluffs += 1;
+    }
+
+    if let Some(started_at) = round_starts.get(&summary.round_number) {
+        let milliseconds = (ended_at - *started_at).num_milliseconds();
+        let is_longest = recap
+            .longest_round
+            .as_ref()
+            .is_none_or(|longest| milliseconds > longest.milliseconds);
+        if is_longest {
+            recap.longest_round = Some(RoundHighlight {
+                round_number: summary.round_number,
+                milliseconds,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+    use crate::{
+        enums::card_types::CardType,
+        types::{card::Card, game_event::GameEvent},
+    };
+
+    /// Stores an event as if it was published a number of seconds into the game.
+    fn logged(sequence: usize, seconds: i64, event: GameEvent) -> StoredGameEvent {
+        StoredGameEvent {
+            sequence,
+            game_id: event.game_id().to_string(),
+            recipient_player_id: None,
+            event_type: event.event_type().to_string(),
+            payload: serde_json::to_string(&event).unwrap(),
+            created_at: Utc.with_ymd_and_hms(2025, 9, 20, 12, 0, 0).unwrap()
+                + Duration::seconds(seconds),
+        }
+    }
+
+    /// Gives a player the turn in a round.
+    fn turn(player_id: &str, round_number: usize) -> GameEvent {
+        GameEvent::TurnChanged {
+            game_id: "game".to_string(),
+            which_player_turn: player_id.to_string(),
+            round_number,
+            hands: vec![],
+        }
+    }
+
+    /// Ends a round with a challenge of a claim with a number of cards.
+    fn challenge(
+        round_number: usize,
+        challenger: &str,
+        bluffer: Option<&str>,
+        cards: usize,
+    ) -> GameEvent {
+        GameEvent::RoundSummary(RoundSummary {
+            game_id: "game".to_string(),
+            round_number,
+            card_to_play: CardType::King,
+            claimant_id: bluffer.unwrap_or("claimant").to_string(),
+            challenger_id: challenger.to_string(),
+            bluffer_id: bluffer.map(str::to_string),
+            revealed_cards: (0..cards).map(|_| Card::new(CardType::Ace)).collect(),
+            loser_id: challenger.to_string(),
+            winner_id: challenger.to_string(),
+            score_deltas: vec![],
+        })
+    }
+
+    #[test]
+    fn recap_picks_the_highlights_of_the_log() {
+        let events = vec![
+            logged(1, 0, turn("alice", 1)),
+            logged(2, 12, turn("bob", 1)),
+            logged(3, 15, challenge(1, "bob", Some("alice"), 3)),
+            logged(4, 15, turn("bob", 2)),
+            logged(5, 60, turn("alice", 2)),
+            logged(6, 70, challenge(2, "alice", Some("bob"), 2)),
+            logged(7, 70, turn("alice", 3)),
+            logged(8, 72, challenge(3, "bob", Some("alice"), 1)),
+        ];
+
+        let recap = build_recap("game", &events);
+
+        assert_eq!(recap.rounds, 3);
+        let biggest_bluff = recap.biggest_bluff.unwrap();
+        assert_eq!(
+            (biggest_bluff.player_id.as_str(), biggest_bluff.card_count),
+            ("alice", 3)
+        );
+        let top_challenger = recap.top_challenger.unwrap();
+        assert_eq!(
+            (
+                top_challenger.player_id.as_str(),
+                top_challenger.caught_bluffs
+            ),
+            ("bob", 2)
+        );
+        let fastest_turn = recap.fastest_turn.unwrap();
+        assert_eq!(
+            (fastest_turn.player_id.as_str(), fastest_turn.milliseconds),
+            ("alice", 2_000)
+        );
+        let longest_round = recap.longest_round.unwrap();
+        assert_eq!(
+            (longest_round.round_number, longest_round.milliseconds),
+            (2, 55_000)
+        );
+    }
+
+    #[test]
+    fn recap_ignores_interrupted_turns_and_everything_before_a_reset() {
+        let events = vec![
+            logged(1, 0, turn("alice", 1)),
+            logged(2, 1, challenge(1, "bob", Some("alice"), 4)),
+            logged(
+                3,
+                5,
+                GameEvent::GameReset {
+                    game_id: "game".to_string(),
+                    which_player_turn: "alice".to_string(),
+                },
+            ),
+            logged(4, 5, turn("alice", 1)),
+            logged(
+                5,
+                6,
+                GameEvent::GamePaused {
+                    game_id: "game".to_string(),
+                    paused_at: None,
+                },
+            ),
+            logged(6, 30, turn("bob", 1)),
+            logged(7, 40, challenge(1, "bob", None, 1)),
+        ];
+
+        let recap = build_recap("game", &events);
+
+        assert_eq!(recap.rounds, 1);
+        assert_eq!(recap.biggest_bluff, None);
+        assert_eq!(recap.top_challenger, None);
+        let fastest_turn = recap.fastest_turn.unwrap();
+        assert_eq!(
+            (fastest_turn.player_id.as_str(), fastest_turn.milliseconds),
+            ("bob", 10_000)
+        );
+    }
+}
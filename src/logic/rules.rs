@@ -0,0 +1,667 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    enums::card_types::CardType,
+    errors::{process_error::ProcessError, validation_errors::ValidationErrors},
+    i18n::{catalog::Message, locale::Locale},
+    types::{card::Card, claim::Claim, game::Game},
+};
+
+/// Outcome of a challenged claim.
+///
+/// # Props
+///
+/// - `truthful` -> Whether all placed cards matched the card to play
+/// - `loser_id` -> Id of the player who picks up the stack
+/// - `winner_id` -> Id of the player who starts the next round
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChallengeOutcome {
+    /// Whether all placed cards matched the card to play
+    pub truthful: bool,
+    /// Id of the player who picks up the stack
+    pub loser_id: String,
+    /// Id of the player who starts the next round
+    pub winner_id: String,
+}
+
+/// Picks the cards a player wants to place out of their hand.
+///
+/// Only the cards stored on the server are used, the client just names them by their id.
+///
+/// # Arguments
+///
+/// - `hand` -> Cards the player holds
+/// - `named` -> Cards named by the client
+///
+/// # Errors
+///
+/// Returns `ValidationErrors` if a named card isn't in the hand or is named twice.
+pub fn select_placed_cards(hand: &[Card], named: &[Card]) -> Result<Vec<Card>, ValidationErrors> {
+    let mut placed_cards: Vec<Card> = vec![];
+
+    for card in named {
+        match hand.iter().find(|held| held.id == card.id) {
+            Some(held) if !placed_cards.iter().any(|placed| placed.id == held.id) => {
+                placed_cards.push(held.clone())
+            }
+            _ => {
+                let mut errors = ValidationErrors::new();
+                errors.add(
+                    "cards",
+                    "not_in_hand",
+                    "Only cards from the own hand can be placed, each card once!",
+                );
+                return Err(errors);
+            }
+        }
+    }
+
+    Ok(placed_cards)
+}
+
+/// Decides who picks up the stack when a claim is challenged.
+///
+/// If the claim was a lie, the player who made it loses, otherwise the challenger does. The other
+/// one of both wins and starts the next round.
+///
+/// # Arguments
+///
+/// - `claim` -> The challenged claim
+/// - `challenger_id` -> Id of the player who doubts the claim
+/// - `card_to_play` -> Card type of the round
+pub fn resolve_challenge(
+    claim: &Claim,
+    challenger_id: &str,
+    card_to_play: &CardType,
+) -> ChallengeOutcome {
+    let truthful = claim.is_truthful(card_to_play);
+    let (loser_id, winner_id) = if truthful {
+        (challenger_id.to_string(), claim.created_by.clone())
+    } else {
+        (claim.created_by.clone(), challenger_id.to_string())
+    };
+
+    ChallengeOutcome {
+        truthful,
+        loser_id,
+        winner_id,
+    }
+}
+
+/// Checks the declaration of a claim against the rules of the round.
+///
+/// A player always declares the card to play of the round, and the number of cards everyone saw
+/// hitting the stack. Whether the hidden cards match the declaration is only uncovered by a
+/// challenge.
+///
+/// # Arguments
+///
+/// - `claim` -> The declared claim
+/// - `card_to_play` -> Card type of the round
+///
+/// # Errors
+///
+/// Returns `ValidationErrors` if the declaration breaks the rules of the round.
+pub fn validate_declaration(
+    claim: &Claim,
+    card_to_play: &CardType,
+) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+
+    if claim
+        .declared_card_type
+        .as_ref()
+        .is_some_and(|declared| declared != card_to_play)
+    {
+        errors.add(
+            "declared_card_type",
+            "not_card_to_play",
+            &format!("Only the card to play of the round can be declared: {card_to_play}!"),
+        );
+    }
+
+    if claim
+        .declared_count
+        .is_some_and(|declared| declared != claim.number_of_cards)
+    {
+        errors.add(
+            "declared_count",
+            "count_mismatch",
+            "The declared number of cards has to match the placed cards, everyone can count them!",
+        );
+    }
+
+    errors.into_result()
+}
+
+/// Removes placed cards from the hand of a player.
+///
+/// # Arguments
+///
+/// - `hand` -> Cards the player holds
+/// - `placed` -> Cards the player placed on the stack
+pub fn remove_from_hand(hand: &mut Vec<Card>, placed: &[Card]) {
+    hand.retain(|held| !placed.iter().any(|card| card.id == held.id));
+}
+
+/// Moves the cards of all claims of the round into the hand of the loser of a challenge.
+///
+/// Does for a game held in memory what `CardRepository::move_stack_to_player` does in the
+/// database; the claims of the round are emptied.
+///
+/// # Arguments
+///
+/// - `game` -> The game whose stack is picked up
+/// - `player_id` -> Id of the player who picks up the stack
+pub fn pick_up_stack(game: &mut Game, player_id: &str) {
+    let stack: Vec<Card> = game
+        .claims
+        .drain(..)
+        .flat_map(|claim| claim.cards)
+        .collect();
+
+    if let Some(player) = game
+        .players
+        .iter_mut()
+        .find(|player| player.id == player_id)
+    {
+        player.assigned_cards.extend(stack);
+    }
+}
+
+/// Checks that a player can take back the last claim of the round.
+///
+/// Only allowed if the game uses the undo house rule, the player made the last claim, nobody
+/// challenged or played on since and the grace window of the claim is still open.
+///
+/// # Arguments
+///
+/// - `game` -> The game with the claims of the current round
+/// - `player_id` -> Id of the player who wants to take back the claim
+/// - `now` -> Point in time of the request
+/// - `locale` -> Language of the reason
+///
+/// # Errors
+///
+/// Returns a `ProcessError` naming the reason if the claim can't be taken back.
+pub fn ensure_retractable<'a>(
+    game: &'a Game,
+    player_id: &str,
+    now: DateTime<Utc>,
+    locale: &Locale,
+) -> Result<&'a Claim, ProcessError<Claim>> {
+    let refuse = |message: Message, claim: Option<&Claim>| {
+        Err(ProcessError::new(
+            message.text(locale),
+            "ensure_retractable".to_string(),
+            claim.cloned(),
+        ))
+    };
+
+    if game.settings.undo_seconds == 0 {
+        return refuse(Message::UndoDisabled, None);
+    }
+
+    let Some(claim) = game
+        .claims
+        .iter()
+        .filter(|claim| claim.round_number == game.round_number)
+        .max_by_key(|claim| claim.created_at)
+    else {
+        return refuse(Message::UndoNoClaim, None);
+    };
+
+    if claim.created_by != player_id || claim.revealed {
+        return refuse(Message::UndoNotLastClaim, Some(claim));
+    }
+
+    if now - claim.created_at > Duration::seconds(game.settings.undo_seconds as i64) {
+        return refuse(Message::UndoTooLate, Some(claim));
+    }
+
+    Ok(claim)
+}
+
+/// Takes back a claim: its cards return to the hand of its author, who is to move again.
+///
+/// Does for a game held in memory what `ClaimsRepository::retract_claim` does in the database.
+///
+/// # Arguments
+///
+/// - `game` -> The game the claim was made in
+/// - `claim_id` -> Id of the retracted claim
+pub fn retract_claim(game: &mut Game, claim_id: &str) {
+    let Some(position) = game.claims.iter().position(|claim| claim.id == claim_id) else {
+        return;
+    };
+    let claim = game.claims.remove(position);
+
+    if let Some(player) = game
+        .players
+        .iter_mut()
+        .find(|player| player.id == claim.created_by)
+    {
+        player.assigned_cards.extend(claim.cards);
+    }
+    game.which_player_turn = claim.created_by;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        rc::Rc,
+    };
+
+    use proptest::prelude::*;
+    use rand_chacha::{rand_core::SeedableRng, ChaCha8Rng};
+
+    use super::*;
+    use crate::{
+        enums::{game_state::GameState, suit::Suit},
+        errors::validation_errors::Validate,
+        test_support::{
+            claim_builder::ClaimBuilder, game_builder::GameBuilder, player_builder::PlayerBuilder,
+        },
+        types::round_summary::RoundSummary,
+        utils::time::{server_time, set_clock, FixedClock},
+    };
+
+    /// Ids of all cards in the hands of the players and on the stack, sorted.
+    fn all_card_ids(game: &Game) -> Vec<String> {
+        let mut ids: Vec<String> = game
+            .players
+            .iter()
+            .flat_map(|player| player.assigned_cards.iter())
+            .chain(game.claims.iter().flat_map(|claim| claim.cards.iter()))
+            .map(|card| card.id.clone())
+            .collect();
+        ids.sort();
+
+        ids
+    }
+
+    /// Index of a player in the seating order of the game.
+    fn seat_of(game: &Game, player_id: &str) -> usize {
+        game.players
+            .iter()
+            .position(|player| player.id == player_id)
+            .unwrap()
+    }
+
+    fn card_type() -> impl Strategy<Value = CardType> {
+        (0..CardType::ranks().len()).prop_map(|index| CardType::ranks()[index].clone())
+    }
+
+    fn cards() -> impl Strategy<Value = Vec<Card>> {
+        prop::collection::vec((card_type(), 0..4usize, any::<bool>()), 1..=4).prop_map(|cards| {
+            cards
+                .into_iter()
+                .map(|(card_type, suit, joker)| match joker {
+                    true => Card::new(CardType::Joker),
+                    false => Card::with_suit(card_type, Suit::all()[suit].clone()),
+                })
+                .collect()
+        })
+    }
+
+    proptest! {
+        /// Plays random sequences of legal moves: the player to move either places up to four of
+        /// their cards or challenges the last claim of another player.
+        #[test]
+        fn legal_moves_conserve_cards_rotate_turns_and_end_rounds(
+            player_count in 2..=5usize,
+            decks in 1..=3usize,
+            jokers in 0..=3usize,
+            seed in any::<u64>(),
+            moves in prop::collection::vec((any::<bool>(), any::<usize>()), 1..300),
+        ) {
+            // a seeded game picks its cards to play deterministically, so failures can be replayed
+            let mut game = GameBuilder::new()
+                .players(player_count)
+                .in_progress()
+                .daily_seed(&seed.to_string())
+                .decks(decks, None)
+                .dealt(seed, jokers)
+                .build();
+            let dealt_ids = all_card_ids(&game);
+            let total_cards = dealt_ids.len();
+            prop_assert_eq!(total_cards, 52 * decks + jokers);
+            // cards of different decks share their rank and suit, but never their id
+            prop_assert_eq!(dealt_ids.iter().collect::<HashSet<_>>().len(), total_cards);
+
+            let mut round_starter = seat_of(&game, &game.which_player_turn);
+            for (wants_to_claim, pick) in moves {
+                let current_id = game.which_player_turn.clone();
+                let current = seat_of(&game, &current_id);
+                let hand = game.players[current].assigned_cards.clone();
+                let can_challenge = game
+                    .claims
+                    .last()
+                    .is_some_and(|claim| claim.created_by != current_id);
+
+                if !hand.is_empty() && (wants_to_claim || !can_challenge) {
+                    // turn order: the claims of a round go around the table from its starter
+                    prop_assert_eq!(current, (round_starter + game.claims.len()) % player_count);
+
+                    let count = 1 + pick % hand.len().min(4);
+                    let offset = pick % hand.len();
+                    let named: Vec<Card> =
+                        hand.iter().cycle().skip(offset).take(count).cloned().collect();
+                    let placed = select_placed_cards(&hand, &named).unwrap();
+                    prop_assert_eq!(placed.len(), count);
+
+                    let claim = ClaimBuilder::new()
+                        .in_game(&game)
+                        .by(&current_id)
+                        .cards(placed)
+                        .build();
+                    prop_assert!(claim.validate(&Locale::En).is_ok());
+                    remove_from_hand(&mut game.players[current].assigned_cards, &claim.cards);
+                    game.claims.push(claim);
+                    game.which_player_turn = game.next_player_id(&current_id).unwrap();
+
+                    // termination: every claim takes a card from a hand, so a round can't last
+                    // longer than the cards of the deck
+                    prop_assert!(game.claims.len() <= total_cards);
+                } else if can_challenge {
+                    let claim = game.claims.last().unwrap().clone();
+                    let outcome = resolve_challenge(&claim, &current_id, &game.card_to_play);
+                    let round_number = game.round_number;
+
+                    // the loser picks up exactly the cards the others placed during the round
+                    let summary = RoundSummary::new(&game, &claim, &current_id, &outcome);
+                    prop_assert_eq!(summary.score_deltas.len(), player_count);
+                    prop_assert_eq!(
+                        summary.score_deltas.iter().map(|delta| delta.cards).sum::<i64>(),
+                        0
+                    );
+                    prop_assert_eq!(summary.bluffer_id.is_some(), !outcome.truthful);
+
+                    pick_up_stack(&mut game, &outcome.loser_id);
+                    game.prep_for_new_round().unwrap();
+                    game.which_player_turn = outcome.winner_id.clone();
+                    round_starter = seat_of(&game, &outcome.winner_id);
+
+                    prop_assert_eq!(game.round_number, round_number + 1);
+                    prop_assert!(game.claims.is_empty());
+                    prop_assert_ne!(game.card_to_play.clone(), CardType::Joker);
+                } else {
+                    // the player to move has no cards and nothing to challenge
+                    break;
+                }
+
+                // card conservation: no card is lost, duplicated or made up by a move
+                prop_assert_eq!(all_card_ids(&game), dealt_ids.clone());
+            }
+
+            // every game can be ended from any position reached by legal moves
+            let rounds = game.round_number;
+            prop_assert!(game.finish().is_ok());
+            prop_assert_eq!(game.state, GameState::Ended);
+            if let Some(winner_id) = &game.winner_player_id {
+                let winner_cards = game.players[seat_of(&game, winner_id)].assigned_cards.len();
+                prop_assert!(game
+                    .players
+                    .iter()
+                    .filter(|player| player.id != *winner_id)
+                    .all(|player| player.assigned_cards.len() > winner_cards));
+            }
+            prop_assert_eq!(game.total_rounds, Some(rounds));
+            prop_assert!(game.finished_at.is_some());
+        }
+
+        /// A fixed hand size gives every player the same number of cards, and no rank and suit is
+        /// dealt more often than there are decks.
+        #[test]
+        fn fixed_hands_are_even_and_respect_the_decks(
+            player_count in 2..=5usize,
+            decks in 1..=3usize,
+            hand_size in 1..=10usize,
+            seed in any::<u64>(),
+        ) {
+            let game = GameBuilder::new()
+                .players(player_count)
+                .decks(decks, Some(hand_size))
+                .dealt(seed, 0)
+                .build();
+
+            for player in &game.players {
+                prop_assert_eq!(player.assigned_cards.len(), hand_size);
+            }
+            let dealt_ids = all_card_ids(&game);
+            prop_assert_eq!(dealt_ids.len(), player_count * hand_size);
+            prop_assert_eq!(dealt_ids.iter().collect::<HashSet<_>>().len(), dealt_ids.len());
+
+            let mut identities: HashMap<(String, Option<String>), usize> = HashMap::new();
+            for card in game.players.iter().flat_map(|player| player.assigned_cards.iter()) {
+                let suit = card.suit.as_ref().map(Suit::to_string);
+                *identities
+                    .entry((card.card_type.to_string(), suit))
+                    .or_default() += 1;
+            }
+            prop_assert!(identities.values().all(|count| *count <= decks));
+        }
+
+        /// Every player moves exactly once before the first player moves again.
+        #[test]
+        fn turn_order_visits_every_player_once_per_lap(
+            player_count in 2..=5usize,
+            start in any::<prop::sample::Index>(),
+        ) {
+            let game = GameBuilder::new().players(player_count).build();
+            let start_id = game.players[start.index(game.players.len())].id.clone();
+
+            let mut seen = HashSet::new();
+            let mut current = start_id.clone();
+            for _ in 0..game.players.len() {
+                prop_assert!(seen.insert(current.clone()));
+                current = game.next_player_id(&current).unwrap();
+            }
+
+            prop_assert_eq!(current, start_id);
+            prop_assert_eq!(seen.len(), game.players.len());
+        }
+
+        /// After the seats were shuffled, every player moves after the player on the seat before
+        /// them, no matter in which order the players are listed.
+        #[test]
+        fn turn_order_follows_the_seats(player_count in 2..=5usize, seed in any::<u64>()) {
+            let mut game = GameBuilder::new().players(player_count).build();
+            game.shuffle_seats(&mut ChaCha8Rng::seed_from_u64(seed));
+            game.players.reverse();
+
+            let mut seats: Vec<usize> =
+                game.players.iter().map(|player| player.seat_index).collect();
+            seats.sort();
+            prop_assert_eq!(seats, (0..player_count).collect::<Vec<usize>>());
+
+            for player in &game.players {
+                let next_id = game.next_player_id(&player.id).unwrap();
+                let next = game.players.iter().find(|seated| seated.id == next_id).unwrap();
+                prop_assert_eq!(next.seat_index, (player.seat_index + 1) % player_count);
+            }
+        }
+
+        /// After the seats were shuffled and the game was reset, the player on the first seat
+        /// moves first, even if someone else hosts the game.
+        #[test]
+        fn first_seat_moves_first(player_count in 2..=5usize, seed in any::<u64>()) {
+            let mut game = GameBuilder::new()
+                .players(player_count)
+                .host(player_count - 1)
+                .in_progress()
+                .build();
+            game.shuffle_seats(&mut ChaCha8Rng::seed_from_u64(seed));
+            game.players.reverse();
+
+            game.reset(&Locale::En).unwrap();
+            let first = game.seating_order()[0];
+            prop_assert_eq!(first.seat_index, 0);
+            prop_assert_eq!(&game.first_seat_player_id(), &first.id);
+            prop_assert_eq!(&game.which_player_turn, &first.id);
+        }
+
+        /// The claimant and the challenger always split the outcome between them, and whether the
+        /// claim was a lie decides which of both picks up the stack.
+        #[test]
+        fn challenge_outcomes_are_symmetric(
+            placed in cards(),
+            card_to_play in card_type(),
+        ) {
+            let claimant = PlayerBuilder::new().name("Claimant").build();
+            let challenger = PlayerBuilder::new().name("Challenger").build();
+            let claim = ClaimBuilder::new().by(&claimant.id).cards(placed).build();
+
+            let outcome = resolve_challenge(&claim, &challenger.id, &card_to_play);
+            prop_assert_eq!(outcome.truthful, claim.is_truthful(&card_to_play));
+            prop_assert_ne!(&outcome.loser_id, &outcome.winner_id);
+            let mut players = vec![outcome.loser_id.clone(), outcome.winner_id.clone()];
+            players.sort();
+            let mut expected = vec![claimant.id.clone(), challenger.id.clone()];
+            expected.sort();
+            prop_assert_eq!(players, expected);
+
+            // the same claim with the opposite truthfulness swaps the roles
+            let flipped = match outcome.truthful {
+                true => ClaimBuilder::new().by(&claimant.id).lie(&card_to_play, 1).build(),
+                false => ClaimBuilder::new().by(&claimant.id).truthful(&card_to_play, 1).build(),
+            };
+            let flipped_outcome = resolve_challenge(&flipped, &challenger.id, &card_to_play);
+            prop_assert_eq!(flipped_outcome.truthful, !outcome.truthful);
+            prop_assert_eq!(flipped_outcome.loser_id, outcome.winner_id);
+            prop_assert_eq!(flipped_outcome.winner_id, outcome.loser_id);
+        }
+
+        /// A declaration is accepted exactly if it names the card to play and the number of placed
+        /// cards, no matter whether the hidden cards match it.
+        #[test]
+        fn declarations_follow_the_round_not_the_hidden_cards(
+            placed in cards(),
+            card_to_play in card_type(),
+            declared_card_type in card_type(),
+            declared_count in 1..=4usize,
+        ) {
+            let claim = ClaimBuilder::new()
+                .cards(placed)
+                .build()
+                .declare(declared_card_type.clone(), declared_count);
+
+            let valid = declared_card_type == card_to_play && declared_count == claim.cards.len();
+            prop_assert_eq!(validate_declaration(&claim, &card_to_play).is_ok(), valid);
+        }
+
+        /// Taking back a claim within the grace window restores the hand and the turn exactly as
+        /// they were before the claim, and nobody else can take it back.
+        #[test]
+        fn retracted_claims_restore_hand_and_turn(
+            player_count in 2..=5usize,
+            seed in any::<u64>(),
+            count in 1..=4usize,
+            elapsed in 0..=60i64,
+            undo_seconds in 0..=30usize,
+        ) {
+            let mut game = GameBuilder::new()
+                .players(player_count)
+                .in_progress()
+                .daily_seed(&seed.to_string())
+                .dealt(seed, 0)
+                .build();
+            game.settings.undo_seconds = undo_seconds;
+            let before = Game::from_ref(&game);
+            let claimant_id = game.which_player_turn.clone();
+            let claimant = seat_of(&game, &claimant_id);
+
+            let hand = game.players[claimant].assigned_cards.clone();
+            let placed = select_placed_cards(&hand, &hand[..count]).unwrap();
+            let claim = ClaimBuilder::new()
+                .in_game(&game)
+                .by(&claimant_id)
+                .cards(placed)
+                .build();
+            remove_from_hand(&mut game.players[claimant].assigned_cards, &claim.cards);
+            let now = claim.created_at + Duration::seconds(elapsed);
+            game.claims.push(claim.clone());
+            game.which_player_turn = game.next_player_id(&claimant_id).unwrap();
+
+            let next_id = game.which_player_turn.clone();
+            prop_assert!(ensure_retractable(&game, &next_id, now, &Locale::En).is_err());
+
+            let allowed = undo_seconds > 0 && elapsed <= undo_seconds as i64;
+            prop_assert_eq!(ensure_retractable(&game, &claimant_id, now, &Locale::En).is_ok(), allowed);
+
+            retract_claim(&mut game, &claim.id);
+            prop_assert!(game.claims.is_empty());
+            prop_assert_eq!(&game.which_player_turn, &before.which_player_turn);
+            prop_assert_eq!(all_card_ids(&game), all_card_ids(&before));
+            let mut hand_ids: Vec<String> = game.players[claimant]
+                .assigned_cards
+                .iter()
+                .map(|card| card.id.clone())
+                .collect();
+            let mut expected_ids: Vec<String> = hand.iter().map(|card| card.id.clone()).collect();
+            hand_ids.sort();
+            expected_ids.sort();
+            prop_assert_eq!(hand_ids, expected_ids);
+        }
+    }
+
+    /// On a fixed clock the grace window closes exactly after the seconds of the house rule.
+    #[test]
+    fn grace_window_closes_on_a_fixed_clock() {
+        let clock = Rc::new(FixedClock::new(DateTime::UNIX_EPOCH));
+        set_clock(clock.clone());
+
+        let mut game = GameBuilder::new().players(2).in_progress().build();
+        game.settings.undo_seconds = 10;
+        let claimant_id = game.which_player_turn.clone();
+        let claim = ClaimBuilder::new()
+            .in_game(&game)
+            .by(&claimant_id)
+            .truthful(&game.card_to_play, 1)
+            .build();
+        game.claims.push(claim);
+
+        clock.advance(Duration::seconds(10));
+        assert!(ensure_retractable(&game, &claimant_id, server_time(), &Locale::En).is_ok());
+
+        clock.advance(Duration::seconds(1));
+        assert!(ensure_retractable(&game, &claimant_id, server_time(), &Locale::En).is_err());
+    }
+
+    /// The player who got rid of all cards wins the game, no matter what the others hold.
+    #[test]
+    fn emptied_hand_wins_the_game() {
+        let mut game = GameBuilder::new()
+            .players(3)
+            .in_progress()
+            .dealt(7, 2)
+            .build();
+        let winner_id = game.players[1].id.clone();
+        game.players[1].assigned_cards.clear();
+
+        game.finish().unwrap();
+        assert_eq!(game.winner_player_id, Some(winner_id));
+    }
+
+    /// A game ended early goes to the player with the fewest cards; a draw has no winner.
+    #[test]
+    fn fewest_cards_win_an_early_end() {
+        let ended_with = |hands: [usize; 3]| {
+            let mut game = GameBuilder::new()
+                .players(3)
+                .in_progress()
+                .dealt(7, 2)
+                .build();
+            for (player, cards) in game.players.iter_mut().zip(hands) {
+                player.assigned_cards.truncate(cards);
+            }
+            game.finish().unwrap();
+            game
+        };
+
+        let game = ended_with([2, 3, 3]);
+        assert_eq!(game.winner_player_id, Some(game.players[0].id.clone()));
+        assert_eq!(ended_with([2, 2, 3]).winner_player_id, None);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::mail::mailer::Email;
+
+// constants
+/// Subject line of an invitation.
+const INVITE_SUBJECT_TEMPLATE: &str = "{{host_name}} invites you to a game of Lue Lue";
+
+/// Plain text body of an invitation.
+const INVITE_TEXT_TEMPLATE: &str = "Hi!
+
+{{host_name}} invites you to a game of Lue Lue.{{note}}
+
+Join the game: {{join_link}}
+Or enter the join code {{join_code}} in the lobby.
+
+Have fun bluffing!";
+
+/// HTML body of an invitation.
+const INVITE_HTML_TEMPLATE: &str = "<p>Hi!</p>
+<p><strong>{{host_name}}</strong> invites you to a game of Lue Lue.</p>{{note}}
+<p><a href=\"{{join_link}}\">Join the game</a></p>
+<p>Or enter the join code <strong>{{join_code}}</strong> in the lobby.</p>
+<p>Have fun bluffing!</p>";
+
+/// Values filled into the templates of an invitation.
+///
+/// # Props
+///
+/// - `host_name` -> Name of the host sending the invitation
+/// - `join_code` -> Join code of the game
+/// - `join_link` -> Deep link opening the game in the frontend
+/// - `note` -> Optional personal note of the host
+#[derive(Debug, Clone)]
+pub struct InviteDetails<'a> {
+    /// Name of the host sending the invitation
+    pub host_name: &'a str,
+    /// Join code of the game
+    pub join_code: &'a str,
+    /// Deep link opening the game in the frontend
+    pub join_link: &'a str,
+    /// Optional personal note of the host
+    pub note: Option<&'a str>,
+}
+
+/// Renders the invitation email for a recipient.
+///
+/// All values are escaped in the HTML body, since the host name and the note are chosen by a
+/// player.
+///
+/// # Arguments
+///
+/// - `to` -> Address of the recipient
+/// - `details` -> Values filled into the templates
+pub fn render_invite(to: &str, details: &InviteDetails) -> Email {
+    let text_note = details
+        .note
+        .map(|note| format!("\n\n\"{note}\""))
+        .unwrap_or_default();
+    let html_note = details
+        .note
+        .map(|note| format!("\n<blockquote>{}</blockquote>", escape_html(note)))
+        .unwrap_or_default();
+
+    Email {
+        to: to.to_string(),
+        subject: render(INVITE_SUBJECT_TEMPLATE, &[("host_name", details.host_name)]),
+        text: render(
+            INVITE_TEXT_TEMPLATE,
+            &[
+                ("host_name", details.host_name),
+                ("note", &text_note),
+                ("join_link", details.join_link),
+                ("join_code", details.join_code),
+            ],
+        ),
+        html: render(
+            INVITE_HTML_TEMPLATE,
+            &[
+                ("host_name", &escape_html(details.host_name)),
+                ("note", &html_note),
+                ("join_link", &escape_html(details.join_link)),
+                ("join_code", &escape_html(details.join_code)),
+            ],
+        ),
+    }
+}
+
+/// Replaces every `{{key}}` placeholder of a template with its value.
+fn render(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |rendered, (key, value)| {
+            rendered.replace(&format!("{{{{{key}}}}}"), value)
+        })
+}
+
+/// Escapes the characters with a meaning in HTML.
+fn escape_html(text: &str) -> String {
+    text.chars()
+        .map(|character| match character {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&#39;".to_string(),
+            _ => character.to_string(),
+        })
+        .collect()
+}
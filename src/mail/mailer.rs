@@ -0,0 +1,171 @@
+use log::warn;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+use worker::{send::SendFuture, Env, Fetch, Headers, Method, Request, RequestInit};
+
+use crate::errors::delivery_error::DeliveryError;
+
+// constants
+/// Name of the worker secret holding the API key of MailChannels.
+pub const MAILCHANNELS_API_KEY_BINDING: &str = "MAILCHANNELS_API_KEY";
+
+/// Endpoint of MailChannels which sends an email.
+pub const MAILCHANNELS_SEND_URL: &str = "https://api.mailchannels.net/tx/v1/send";
+
+/// Name shown as the sender of every email.
+const SENDER_NAME: &str = "Lue Lue";
+
+/// Rendered email ready to be sent.
+///
+/// # Props
+///
+/// - `to` -> Address of the recipient
+/// - `subject` -> Subject line
+/// - `text` -> Plain text body
+/// - `html` -> HTML body
+#[derive(Debug, Clone)]
+pub struct Email {
+    /// Address of the recipient
+    pub to: String,
+    /// Subject line
+    pub subject: String,
+    /// Plain text body
+    pub text: String,
+    /// HTML body
+    pub html: String,
+}
+
+/// Body of a request to the send API of MailChannels.
+#[derive(Serialize)]
+struct SendRequest<'a> {
+    personalizations: [Personalization<'a>; 1],
+    from: Address<'a>,
+    subject: &'a str,
+    content: [Content<'a>; 2],
+}
+
+/// Recipients of an email.
+#[derive(Serialize)]
+struct Personalization<'a> {
+    to: [Address<'a>; 1],
+}
+
+/// Address with an optional display name.
+#[derive(Serialize)]
+struct Address<'a> {
+    email: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+}
+
+/// Body of an email in one format.
+#[derive(Serialize)]
+struct Content<'a> {
+    #[serde(rename = "type")]
+    content_type: &'a str,
+    value: &'a str,
+}
+
+/// Client of the MailChannels send API.
+///
+/// # Props
+///
+/// - `api_key` -> API key of MailChannels
+/// - `sender` -> Address the emails are sent from, see `AppConfig`
+#[derive(Clone)]
+pub struct Mailer {
+    /// API key of MailChannels
+    api_key: String,
+    /// Address the emails are sent from
+    sender: String,
+}
+
+impl Mailer {
+    /// Creates the client from the secret of the worker.
+    ///
+    /// # Arguments
+    ///
+    /// - `env` -> Environment of the worker
+    /// - `sender` -> Address the emails are sent from
+    ///
+    /// # Returns
+    ///
+    /// `None` if the API key or the sender is missing; no emails can be sent then.
+    pub fn from_env(env: &Env, sender: Option<String>) -> Option<Self> {
+        let api_key = match env.secret(MAILCHANNELS_API_KEY_BINDING) {
+            Ok(api_key) => api_key.to_string(),
+            Err(err) => {
+                warn!("MailChannels API key not found, emails are disabled: {err}");
+                return None;
+            }
+        };
+        let Some(sender) = sender else {
+            warn!("No sender address configured, emails are disabled");
+            return None;
+        };
+
+        Some(Mailer { api_key, sender })
+    }
+
+    /// Sends an email.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DeliveryError` if MailChannels couldn't be reached or rejected the email.
+    pub async fn send(&self, email: &Email) -> Result<(), DeliveryError> {
+        let failed = |message: String| DeliveryError::new(message, "MailChannels".to_string());
+
+        let body = serde_json::to_string(&SendRequest {
+            personalizations: [Personalization {
+                to: [Address {
+                    email: &email.to,
+                    name: None,
+                }],
+            }],
+            from: Address {
+                email: &self.sender,
+                name: Some(SENDER_NAME),
+            },
+            subject: &email.subject,
+            content: [
+                Content {
+                    content_type: "text/plain",
+                    value: &email.text,
+                },
+                Content {
+                    content_type: "text/html",
+                    value: &email.html,
+                },
+            ],
+        })
+        .map_err(|err| failed(err.to_string()))?;
+
+        let headers = Headers::new();
+        headers
+            .set("Content-Type", "application/json")
+            .and_then(|_| headers.set("X-Api-Key", &self.api_key))
+            .map_err(|err| failed(err.to_string()))?;
+        let mut init = RequestInit::new();
+        init.with_method(Method::Post)
+            .with_headers(headers)
+            .with_body(Some(JsValue::from_str(&body)));
+        let request = Request::new_with_init(MAILCHANNELS_SEND_URL, &init)
+            .map_err(|err| failed(err.to_string()))?;
+
+        let status = SendFuture::new(async move {
+            Fetch::Request(request)
+                .send()
+                .await
+                .map(|response| response.status_code())
+        })
+        .await
+        .map_err(|err| failed(err.to_string()))?;
+
+        if !(200..300).contains(&status) {
+            warn!("MailChannels rejected an email with status {status}");
+            return Err(failed("The email couldn't be sent!".to_string()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod invite_template;
+pub mod mailer;
@@ -0,0 +1,187 @@
+use log::warn;
+use worker::{send::SendWrapper, AnalyticsEngineDataPointBuilder, AnalyticsEngineDataset, Env};
+
+use crate::{enums::cleanup_policy::CleanupPolicy, router::router_provider::AppState};
+
+// constants
+/// Name of the Analytics Engine dataset binding the metrics are written to.
+pub const METRICS_DATASET_BINDING: &str = "METRICS";
+
+/// Event worth counting on a dashboard.
+///
+/// Every metric is written as one data point:
+///
+/// - `index1` / `blob1` -> Name of the metric, e.g. `claim_played`
+/// - `blob2` / `blob3` -> Details of the metric, e.g. the route and the HTTP method
+/// - `double1` -> Value of the metric; `1` for plain counters, the latency for requests
+/// - `double2` -> Additional number, e.g. the status code of a request
+///
+/// # Example
+///
+/// ```sql
+/// SELECT blob2 AS route, AVG(double1) AS latency_ms
+/// FROM METRICS WHERE index1 = 'request_completed' GROUP BY route
+///
+/// SELECT blob2 AS query, quantileWeighted(0.95)(double1, _sample_interval) AS p95_ms
+/// FROM METRICS WHERE index1 = 'query_completed' GROUP BY query ORDER BY p95_ms DESC
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum Metric<'a> {
+    /// A new game was created.
+    GameCreated,
+    /// A player placed a claim with the number of cards.
+    ClaimPlayed {
+        /// Number of placed cards
+        cards: usize,
+    },
+    /// A claim was challenged.
+    ChallengeResolved {
+        /// `true` if the claim was a lie
+        bluff_caught: bool,
+    },
+    /// A request was answered.
+    RequestCompleted {
+        /// HTTP method of the request
+        method: &'a str,
+        /// Matched route, e.g. `/game/{id}/claim`
+        route: &'a str,
+        /// Status code of the response
+        status: u16,
+        /// Time until the response was ready in milliseconds
+        latency_ms: f64,
+    },
+    /// A request failed because of a database query.
+    DatabaseError {
+        /// Matched route, e.g. `/game/{id}/claim`
+        route: &'a str,
+        /// Status code of the response
+        status: u16,
+    },
+    /// D1 answered a query.
+    QueryCompleted {
+        /// Name of the query, e.g. `game.get_stored_game`
+        query: &'static str,
+        /// Time until D1 answered in milliseconds
+        latency_ms: f64,
+        /// `true` if the query failed
+        failed: bool,
+    },
+    /// The cleanup run abandoned a game.
+    GameReaped {
+        /// Limit the game exceeded
+        policy: CleanupPolicy,
+    },
+}
+
+impl Metric<'_> {
+    /// Returns the name of the metric, which is the index of its data points.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::GameCreated => "game_created",
+            Metric::ClaimPlayed { .. } => "claim_played",
+            Metric::ChallengeResolved { .. } => "challenge_resolved",
+            Metric::RequestCompleted { .. } => "request_completed",
+            Metric::DatabaseError { .. } => "database_error",
+            Metric::QueryCompleted { .. } => "query_completed",
+            Metric::GameReaped { .. } => "game_reaped",
+        }
+    }
+
+    /// Returns `blob2` and `blob3` of the data point.
+    fn details(&self) -> (String, String) {
+        match self {
+            Metric::GameCreated | Metric::ClaimPlayed { .. } => (String::new(), String::new()),
+            Metric::ChallengeResolved { bluff_caught } => {
+                let outcome = if *bluff_caught {
+                    "bluff_caught"
+                } else {
+                    "truthful"
+                };
+                (outcome.to_string(), String::new())
+            }
+            Metric::RequestCompleted { method, route, .. } => {
+                (route.to_string(), method.to_string())
+            }
+            Metric::DatabaseError { route, .. } => (route.to_string(), String::new()),
+            Metric::QueryCompleted { query, .. } => (query.to_string(), String::new()),
+            Metric::GameReaped { policy } => (policy.to_string(), String::new()),
+        }
+    }
+
+    /// Returns `double1` and `double2` of the data point.
+    fn values(&self) -> (f64, f64) {
+        match self {
+            Metric::GameCreated | Metric::ChallengeResolved { .. } | Metric::GameReaped { .. } => {
+                (1.0, 0.0)
+            }
+            Metric::ClaimPlayed { cards } => (1.0, *cards as f64),
+            Metric::RequestCompleted {
+                status, latency_ms, ..
+            } => (*latency_ms, f64::from(*status)),
+            Metric::DatabaseError { status, .. } => (1.0, f64::from(*status)),
+            Metric::QueryCompleted {
+                latency_ms, failed, ..
+            } => (*latency_ms, f64::from(u8::from(*failed))),
+        }
+    }
+}
+
+/// Writer of the data points into the `METRICS` dataset.
+#[derive(Clone)]
+pub struct MetricsRecorder {
+    /// Dataset the data points are written to
+    dataset: SendWrapper<AnalyticsEngineDataset>,
+}
+
+impl MetricsRecorder {
+    /// Creates the recorder from the dataset binding of the worker.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the dataset isn't bound; no metrics are recorded then.
+    pub fn from_env(env: &Env) -> Option<Self> {
+        match env.analytics_engine(METRICS_DATASET_BINDING) {
+            Ok(dataset) => Some(MetricsRecorder {
+                dataset: SendWrapper(dataset),
+            }),
+            Err(err) => {
+                warn!("Metrics dataset not found: {err}");
+                None
+            }
+        }
+    }
+
+    /// Writes a metric as a data point.
+    ///
+    /// Writing doesn't wait for the dataset, a failure is only logged.
+    pub fn record(&self, metric: Metric) {
+        let name = metric.name();
+        let (detail, extra_detail) = metric.details();
+        let (value, extra_value) = metric.values();
+
+        let result = AnalyticsEngineDataPointBuilder::new()
+            .indexes([name])
+            .add_blob(name)
+            .add_blob(detail.as_str())
+            .add_blob(extra_detail.as_str())
+            .add_double(value)
+            .add_double(extra_value)
+            .write_to(&self.dataset);
+
+        if let Err(err) = result {
+            warn!("Metric '{name}' couldn't be written: {err}");
+        }
+    }
+}
+
+/// Records a metric if the worker has a metrics dataset.
+///
+/// # Arguments
+///
+/// - `app_state` -> State of the app holding the recorder
+/// - `metric` -> Metric to record
+pub fn record_metric(app_state: &AppState, metric: Metric) {
+    if let Some(metrics) = &app_state.metrics {
+        metrics.record(metric);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod analytics;
+pub mod prometheus;
+pub mod query_timings;
+pub mod request_tracking;
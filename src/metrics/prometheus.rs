@@ -0,0 +1,274 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{metrics::query_timings::query_latencies, types::overview::ErrorCounts};
+
+// constants
+/// Upper bounds of the buckets of the request latency histogram in milliseconds.
+const LATENCY_BUCKETS_MS: [f64; 10] = [
+    5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+];
+
+/// Seconds after its last poll an event stream still counts as subscribed.
+///
+/// An `EventSource` reconnects every second, so a viewer who left drops out quickly.
+const SUBSCRIBER_WINDOW_SECONDS: i64 = 30;
+
+thread_local! {
+    /// Counters of the requests answered by this isolate.
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::default());
+}
+
+/// Counters and histograms collected since the isolate started.
+#[derive(Default)]
+struct Registry {
+    /// Answered requests by method, route and status
+    requests: BTreeMap<(String, String, u16), u64>,
+    /// Latencies of the requests by route
+    latencies: BTreeMap<String, Histogram>,
+    /// Requests failed because of a database query, by route
+    database_errors: BTreeMap<String, u64>,
+    /// Last poll of every event stream, keyed by game and viewer
+    subscribers: HashMap<(String, String), DateTime<Utc>>,
+}
+
+/// Cumulative histogram with the buckets of `LATENCY_BUCKETS_MS`.
+#[derive(Default)]
+struct Histogram {
+    /// Observations per bucket; an observation is counted in the first bucket it fits into
+    buckets: [u64; LATENCY_BUCKETS_MS.len()],
+    /// Number of observations
+    count: u64,
+    /// Sum of all observations
+    sum: f64,
+}
+
+impl Histogram {
+    /// Adds an observation.
+    fn observe(&mut self, value: f64) {
+        if let Some(bucket) = LATENCY_BUCKETS_MS.iter().position(|bound| value <= *bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+}
+
+/// Counts an answered request.
+///
+/// # Arguments
+///
+/// - `method` -> HTTP method of the request
+/// - `route` -> Matched route, e.g. `/game/{id}/claim`
+/// - `status` -> Status code of the response
+/// - `latency_ms` -> Time until the response was ready in milliseconds
+pub fn record_request(method: &str, route: &str, status: u16, latency_ms: f64) {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+
+        *registry
+            .requests
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default() += 1;
+        registry
+            .latencies
+            .entry(route.to_string())
+            .or_default()
+            .observe(latency_ms);
+    });
+}
+
+/// Counts a request that failed because of a database query.
+pub fn record_database_error(route: &str) {
+    REGISTRY.with(|registry| {
+        *registry
+            .borrow_mut()
+            .database_errors
+            .entry(route.to_string())
+            .or_default() += 1;
+    });
+}
+
+/// Notes that a viewer polled the event stream of a game.
+///
+/// # Arguments
+///
+/// - `game_id` -> Game of the stream
+/// - `viewer` -> Id of the player, or any stable key of a spectator
+/// - `now` -> Point in time of the poll
+pub fn record_subscriber(game_id: &str, viewer: &str, now: DateTime<Utc>) {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+
+        registry
+            .subscribers
+            .insert((game_id.to_string(), viewer.to_string()), now);
+        registry.subscribers.retain(|_, last_poll| {
+            now - *last_poll <= Duration::seconds(SUBSCRIBER_WINDOW_SECONDS)
+        });
+    });
+}
+
+/// Counts the failed requests answered by this isolate.
+pub fn error_counts() -> ErrorCounts {
+    REGISTRY.with(|registry| {
+        let registry = registry.borrow();
+        let mut counts = ErrorCounts {
+            database_errors: registry.database_errors.values().sum(),
+            ..Default::default()
+        };
+
+        for ((_, _, status), count) in &registry.requests {
+            match status {
+                500..=599 => counts.server_errors += count,
+                400..=499 => counts.client_errors += count,
+                _ => {}
+            }
+        }
+
+        counts
+    })
+}
+
+/// Renders the metrics of this isolate in the Prometheus text exposition format.
+///
+/// # Arguments
+///
+/// - `active_games` -> Games that haven't ended, counted in D1 across all isolates
+/// - `now` -> Point in time of the scrape
+pub fn render_metrics(active_games: usize, now: DateTime<Utc>) -> String {
+    let mut output = String::new();
+
+    REGISTRY.with(|registry| {
+        let registry = registry.borrow();
+
+        write_header(
+            &mut output,
+            "luelue_requests_total",
+            "counter",
+            "Requests answered by this isolate.",
+        );
+        for ((method, route, status), count) in &registry.requests {
+            let _ = writeln!(
+                output,
+                "luelue_requests_total{{method=\"{}\",route=\"{}\",status=\"{status}\"}} {count}",
+                escape_label(method),
+                escape_label(route),
+            );
+        }
+
+        write_header(
+            &mut output,
+            "luelue_request_duration_ms",
+            "histogram",
+            "Time until the response of a request was ready.",
+        );
+        for (route, histogram) in &registry.latencies {
+            let route = escape_label(route);
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS_MS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    output,
+                    "luelue_request_duration_ms_bucket{{route=\"{route}\",le=\"{bound}\"}} {cumulative}",
+                );
+            }
+            let _ = writeln!(
+                output,
+                "luelue_request_duration_ms_bucket{{route=\"{route}\",le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(
+                output,
+                "luelue_request_duration_ms_sum{{route=\"{route}\"}} {}",
+                histogram.sum
+            );
+            let _ = writeln!(
+                output,
+                "luelue_request_duration_ms_count{{route=\"{route}\"}} {}",
+                histogram.count
+            );
+        }
+
+        write_header(
+            &mut output,
+            "luelue_database_errors_total",
+            "counter",
+            "Requests failed because of a database query.",
+        );
+        for (route, count) in &registry.database_errors {
+            let _ = writeln!(
+                output,
+                "luelue_database_errors_total{{route=\"{}\"}} {count}",
+                escape_label(route)
+            );
+        }
+
+        write_header(
+            &mut output,
+            "luelue_sse_subscribers",
+            "gauge",
+            "Viewers who polled an event stream of this isolate recently.",
+        );
+        let subscribers = registry
+            .subscribers
+            .values()
+            .filter(|last_poll| now - **last_poll <= Duration::seconds(SUBSCRIBER_WINDOW_SECONDS))
+            .count();
+        let _ = writeln!(output, "luelue_sse_subscribers {subscribers}");
+    });
+
+    write_header(
+        &mut output,
+        "luelue_active_games",
+        "gauge",
+        "Games that haven't ended yet.",
+    );
+    let _ = writeln!(output, "luelue_active_games {active_games}");
+
+    write_header(
+        &mut output,
+        "luelue_d1_query_duration_ms",
+        "summary",
+        "Time until D1 answered a query, over the recent runs of this isolate.",
+    );
+    for latency in query_latencies() {
+        let query = escape_label(latency.query);
+        for (quantile, value) in [
+            ("0.5", latency.p50_ms),
+            ("0.95", latency.p95_ms),
+            ("0.99", latency.p99_ms),
+        ] {
+            let _ = writeln!(
+                output,
+                "luelue_d1_query_duration_ms{{query=\"{query}\",quantile=\"{quantile}\"}} {value}",
+            );
+        }
+        let _ = writeln!(
+            output,
+            "luelue_d1_query_duration_ms_count{{query=\"{query}\"}} {}",
+            latency.count
+        );
+    }
+
+    output
+}
+
+/// Writes the `HELP` and `TYPE` lines of a metric.
+fn write_header(output: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(output, "# HELP {name} {help}");
+    let _ = writeln!(output, "# TYPE {name} {kind}");
+}
+
+/// Escapes a label value as required by the exposition format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
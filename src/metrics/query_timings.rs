@@ -0,0 +1,103 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+};
+
+use serde::Serialize;
+
+// constants
+/// Number of recent latencies kept per query; older samples are dropped.
+const SAMPLES_PER_QUERY: usize = 512;
+
+thread_local! {
+    /// Recent latencies of every query run by this isolate, keyed by the name of the query.
+    static TIMINGS: RefCell<BTreeMap<&'static str, QuerySamples>> = RefCell::new(BTreeMap::new());
+}
+
+/// Latencies of one query.
+#[derive(Default)]
+struct QuerySamples {
+    /// Number of runs since the isolate started
+    count: u64,
+    /// Latencies of the most recent runs in milliseconds
+    recent: VecDeque<f64>,
+}
+
+/// Latency percentiles of one query.
+///
+/// Only covers the isolate that answers the request; Analytics Engine holds the data points of
+/// every isolate.
+///
+/// # Props
+///
+/// - `query` -> Name of the query, `<repository>.<method>`
+/// - `count` -> Runs since the isolate started
+/// - `p50_ms` / `p95_ms` / `p99_ms` -> Percentiles of the recent runs in milliseconds
+/// - `max_ms` -> Slowest of the recent runs in milliseconds
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryLatency {
+    /// Name of the query
+    pub query: &'static str,
+    /// Runs since the isolate started
+    pub count: u64,
+    /// Median of the recent runs in milliseconds
+    pub p50_ms: f64,
+    /// 95th percentile of the recent runs in milliseconds
+    pub p95_ms: f64,
+    /// 99th percentile of the recent runs in milliseconds
+    pub p99_ms: f64,
+    /// Slowest of the recent runs in milliseconds
+    pub max_ms: f64,
+}
+
+/// Adds the latency of a finished query to the aggregates of this isolate.
+///
+/// # Arguments
+///
+/// - `query` -> Name of the query
+/// - `latency_ms` -> Time until D1 answered in milliseconds
+pub fn record_query_latency(query: &'static str, latency_ms: f64) {
+    TIMINGS.with(|timings| {
+        let mut timings = timings.borrow_mut();
+        let samples = timings.entry(query).or_default();
+
+        samples.count += 1;
+        if samples.recent.len() == SAMPLES_PER_QUERY {
+            samples.recent.pop_front();
+        }
+        samples.recent.push_back(latency_ms);
+    });
+}
+
+/// Returns the latency percentiles of every query run by this isolate, ordered by name.
+pub fn query_latencies() -> Vec<QueryLatency> {
+    TIMINGS.with(|timings| {
+        timings
+            .borrow()
+            .iter()
+            .map(|(query, samples)| {
+                let mut sorted: Vec<f64> = samples.recent.iter().copied().collect();
+                sorted.sort_by(f64::total_cmp);
+
+                QueryLatency {
+                    query,
+                    count: samples.count,
+                    p50_ms: percentile(&sorted, 0.5),
+                    p95_ms: percentile(&sorted, 0.95),
+                    p99_ms: percentile(&sorted, 0.99),
+                    max_ms: sorted.last().copied().unwrap_or_default(),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Picks the nearest-rank percentile of sorted samples; `0` without samples.
+fn percentile(sorted: &[f64], quantile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
@@ -0,0 +1,61 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::{
+    errors::database_query_error::DatabaseFailure,
+    metrics::{
+        analytics::{record_metric, Metric},
+        prometheus::{record_database_error, record_request},
+    },
+    router::router_provider::AppState,
+    utils::time::server_time,
+};
+
+/// Records the latency and the status of every routed request.
+///
+/// The counters of `/metrics` are updated as well.
+///
+/// Responses of failed database queries are counted separately, so the error rate of D1 can be
+/// put next to the total number of requests.
+pub async fn track_request(
+    State(app_state): State<AppState>,
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    let route = matched_path
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let started_at = server_time();
+
+    let response = next.run(request).await;
+
+    let status = response.status().as_u16();
+    let latency_ms = (server_time() - started_at).num_milliseconds() as f64;
+    record_request(&method, &route, status, latency_ms);
+    record_metric(
+        &app_state,
+        Metric::RequestCompleted {
+            method: &method,
+            route: &route,
+            status,
+            latency_ms,
+        },
+    );
+    if response.extensions().get::<DatabaseFailure>().is_some() {
+        record_database_error(&route);
+        record_metric(
+            &app_state,
+            Metric::DatabaseError {
+                route: &route,
+                status,
+            },
+        );
+    }
+
+    response
+}
@@ -1 +1,267 @@
-// TODO: Add client authentication -> https://rust-classes.com/chapter_7_2
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    auth::{
+        device_token::{verify_device_token, DEVICE_TOKEN_HEADER},
+        fingerprint::client_fingerprint,
+        session_token::{verify_token, SessionClaims},
+        spectator_token::verify_spectator_token,
+    },
+    errors::{
+        authentication_error::AuthenticationError, authorization_error::AuthorizationError,
+        error_response::ErrorResponseBody,
+    },
+    logging::log_context,
+    router::router_provider::AppState,
+    utils::time::server_time,
+};
+
+/// Player identified by the session token of a request.
+///
+/// Used as an extractor by every protected endpoint. The token is expected in the
+/// `Authorization: Bearer <token>` header. The token of a player who was kicked or left the game
+/// is rejected, even though it didn't expire yet.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the authenticated player
+/// - `game_id` -> Id of the game the token was issued for
+#[derive(Debug, Clone)]
+pub struct AuthenticatedPlayer {
+    /// Id of the authenticated player
+    pub player_id: String,
+    /// Id of the game the token was issued for
+    pub game_id: String,
+}
+
+impl FromRequestParts<AppState> for AuthenticatedPlayer {
+    type Rejection = Response;
+
+    /// Reads the bearer token from the headers and verifies it with the secret of the worker.
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(&parts.headers).map_err(IntoResponse::into_response)?;
+        let claims = verify_token(token, &state.session_secret, server_time())
+            .map_err(IntoResponse::into_response)?;
+        ensure_in_game(state, &claims).await?;
+        log_context::set_game_id(&claims.game_id);
+        log_context::set_player_id(&claims.player_id);
+
+        Ok(AuthenticatedPlayer {
+            player_id: claims.player_id,
+            game_id: claims.game_id,
+        })
+    }
+}
+
+/// Anyone who is allowed to watch a game: one of its players or a spectator.
+///
+/// Used as an extractor by the read-only endpoints. Besides the `Authorization` header the token
+/// can be passed as `token` query parameter, since an `EventSource` can't set headers.
+///
+/// # Variants
+///
+/// - `Player` -> A player with a session token
+/// - `Spectator` -> A client with a spectator link, who isn't a player of the game
+#[derive(Debug, Clone)]
+pub enum Viewer {
+    /// A player with a session token.
+    Player(AuthenticatedPlayer),
+    /// A client with a spectator link, who isn't a player of the game.
+    Spectator {
+        /// Id of the game that can be watched
+        game_id: String,
+    },
+}
+
+/// Query parameters which can carry a token.
+#[derive(Deserialize)]
+struct TokenQuery {
+    /// Session or spectator token
+    token: Option<String>,
+}
+
+impl Viewer {
+    /// Returns the id of the game the viewer may watch.
+    pub fn game_id(&self) -> &str {
+        match self {
+            Viewer::Player(player) => &player.game_id,
+            Viewer::Spectator { game_id } => game_id,
+        }
+    }
+
+    /// Returns the id of the player, or `None` for a spectator.
+    pub fn player_id(&self) -> Option<&str> {
+        match self {
+            Viewer::Player(player) => Some(&player.player_id),
+            Viewer::Spectator { .. } => None,
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for Viewer {
+    type Rejection = Response;
+
+    /// Accepts a session token first and a spectator token otherwise.
+    ///
+    /// The session token of a player who is no longer part of the game is rejected instead of
+    /// being treated as a spectator token.
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let query_token = Query::<TokenQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|Query(query)| query.token);
+        let token = match bearer_token(&parts.headers) {
+            Ok(token) => token.to_string(),
+            Err(err) => query_token.ok_or_else(|| err.into_response())?,
+        };
+
+        let now = server_time();
+        if let Ok(claims) = verify_token(&token, &state.session_secret, now) {
+            ensure_in_game(state, &claims).await?;
+            log_context::set_game_id(&claims.game_id);
+            log_context::set_player_id(&claims.player_id);
+            return Ok(Viewer::Player(AuthenticatedPlayer {
+                player_id: claims.player_id,
+                game_id: claims.game_id,
+            }));
+        }
+
+        let claims = verify_spectator_token(&token, &state.session_secret, now)
+            .map_err(IntoResponse::into_response)?;
+        log_context::set_game_id(&claims.game_id);
+        Ok(Viewer::Spectator {
+            game_id: claims.game_id,
+        })
+    }
+}
+
+/// Client of a request that isn't on the ban list.
+///
+/// Used as an extractor by the endpoints creating or joining a game, which don't require a session
+/// token yet. While the maintenance mode is enabled, every client is turned away.
+///
+/// # Props
+///
+/// - `fingerprint` -> Fingerprint of the device or the client, `None` if it couldn't be derived
+#[derive(Debug, Clone)]
+pub struct ClientIdentity {
+    /// Fingerprint of the client, `None` if it couldn't be derived
+    pub fingerprint: Option<String>,
+}
+
+impl FromRequestParts<AppState> for ClientIdentity {
+    type Rejection = Response;
+
+    /// Derives the fingerprint of the client and rejects it with `403 Forbidden` if it is banned.
+    ///
+    /// A valid device token takes precedence over the client id, so the fingerprint stays the same
+    /// across games. Since a new device token is easy to get, the fingerprint of the headers is
+    /// checked against the ban list as well. Responds with `401 Unauthorized` if the device token
+    /// is invalid and with `503 Service Unavailable` during maintenance.
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let maintenance = state
+            .maintenance_repository
+            .get_maintenance_mode()
+            .await
+            .map_err(IntoResponse::into_response)?;
+        if maintenance.enabled {
+            let message = maintenance.message.unwrap_or_else(|| {
+                "The game is under maintenance! Please try again later.".to_string()
+            });
+            let body: ErrorResponseBody<()> = ErrorResponseBody::new(message, None);
+            return Err((StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response());
+        }
+
+        let header_fingerprint = client_fingerprint(&parts.headers);
+        let device_fingerprint = match parts.headers.get(DEVICE_TOKEN_HEADER) {
+            Some(token) => {
+                let token = token.to_str().map_err(|_| {
+                    AuthenticationError::new("The device token is malformed!".to_string())
+                        .into_response()
+                })?;
+                let claims = verify_device_token(token.trim(), &state.session_secret)
+                    .map_err(IntoResponse::into_response)?;
+                Some(claims.fingerprint())
+            }
+            None => None,
+        };
+
+        for fingerprint in [&device_fingerprint, &header_fingerprint]
+            .into_iter()
+            .flatten()
+        {
+            let ban = state
+                .moderation_repository
+                .get_active_ban(fingerprint, server_time())
+                .await
+                .map_err(IntoResponse::into_response)?;
+
+            if let Some(ban) = ban {
+                return Err(AuthorizationError::new(
+                    format!("This client is banned! Reason: {}", ban.reason),
+                    String::new(),
+                )
+                .into_response());
+            }
+        }
+
+        Ok(ClientIdentity {
+            fingerprint: device_fingerprint.or(header_fingerprint),
+        })
+    }
+}
+
+/// Makes sure that the player of a session token still takes part in the game it was issued for.
+///
+/// Kicked players and players who left the game are deleted, so their tokens stop working right
+/// away instead of at their expiry.
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` response if the player is gone or belongs to another game, and
+/// the response of the database query if it fails otherwise.
+async fn ensure_in_game(state: &AppState, claims: &SessionClaims) -> Result<(), Response> {
+    match state.player_repository.get_player(&claims.player_id).await {
+        Ok(player) if player.game_id == claims.game_id => Ok(()),
+        Ok(_) => Err(removed_player_error()),
+        Err(err) if err.status_code == StatusCode::NOT_FOUND => Err(removed_player_error()),
+        Err(err) => Err(err.into_response()),
+    }
+}
+
+/// Error for a session token whose player is no longer part of the game.
+fn removed_player_error() -> Response {
+    AuthenticationError::new(
+        "The player of this session is no longer part of the game!".to_string(),
+    )
+    .into_response()
+}
+
+/// Extracts the token of the `Authorization` header.
+///
+/// # Errors
+///
+/// Returns an `AuthenticationError` if the header is missing or isn't a bearer token.
+pub fn bearer_token(headers: &HeaderMap) -> Result<&str, AuthenticationError> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| AuthenticationError::new("A bearer session token is required!".to_string()))
+}
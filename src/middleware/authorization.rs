@@ -0,0 +1,154 @@
+use axum::{
+    extract::{FromRequest, FromRequestParts, Request},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    errors::authorization_error::AuthorizationError,
+    middleware::authentication::{AuthenticatedPlayer, Viewer},
+    router::router_provider::AppState,
+    types::game::Game,
+};
+
+/// Makes sure that the session of a player belongs to the requested game.
+///
+/// # Arguments
+///
+/// - `player` -> Player authenticated by the session token
+/// - `game_id` -> Id of the game the request targets
+///
+/// # Errors
+///
+/// Returns an `AuthorizationError` if the token was issued for another game.
+pub fn ensure_same_game(
+    player: &AuthenticatedPlayer,
+    game_id: &str,
+) -> Result<(), AuthorizationError> {
+    if player.game_id == game_id {
+        return Ok(());
+    }
+
+    Err(AuthorizationError::new(
+        "The session doesn't belong to this game!".to_string(),
+        player.player_id.clone(),
+    ))
+}
+
+/// Makes sure that a player or spectator may watch the requested game.
+///
+/// # Arguments
+///
+/// - `viewer` -> Player or spectator authenticated by their token
+/// - `game_id` -> Id of the game the request targets
+///
+/// # Errors
+///
+/// Returns an `AuthorizationError` if the token was issued for another game.
+pub fn ensure_can_view(viewer: &Viewer, game_id: &str) -> Result<(), AuthorizationError> {
+    if viewer.game_id() == game_id {
+        return Ok(());
+    }
+
+    Err(AuthorizationError::new(
+        "The token doesn't belong to this game!".to_string(),
+        viewer.player_id().unwrap_or_default().to_string(),
+    ))
+}
+
+/// Makes sure that the acting player is the host of the game.
+///
+/// Used by all endpoints that manage a game, like starting it, kicking players, changing the
+/// settings or ending it early.
+///
+/// # Arguments
+///
+/// - `game` -> Game the action should be performed on
+/// - `player_id` -> Id of the player who requested the action
+///
+/// # Errors
+///
+/// Returns an `AuthorizationError` if the player isn't the host of the game.
+pub fn ensure_host(game: &Game, player_id: &str) -> Result<(), AuthorizationError> {
+    if game.is_host(player_id) {
+        return Ok(());
+    }
+
+    Err(AuthorizationError::new(
+        "Only the host of the game is allowed to perform this action!".to_string(),
+        player_id.to_string(),
+    ))
+}
+
+/// Request data which is sent on behalf of a single player.
+///
+/// Implemented by all request bodies of player actions, so the `Owned` extractor can bind them to
+/// the session token of the request.
+pub trait ActingPlayer {
+    /// Id of the player the request acts for.
+    fn acting_player_id(&self) -> &str;
+}
+
+/// Makes sure that a player only acts for themself.
+///
+/// # Arguments
+///
+/// - `player` -> Player authenticated by the session token
+/// - `acting_player_id` -> Id of the player named in the body or path of the request
+///
+/// # Errors
+///
+/// Returns an `AuthorizationError` if the ids differ.
+pub fn ensure_owner(
+    player: &AuthenticatedPlayer,
+    acting_player_id: &str,
+) -> Result<(), AuthorizationError> {
+    if player.player_id == acting_player_id {
+        return Ok(());
+    }
+
+    Err(AuthorizationError::new(
+        "A player is only allowed to act for themself!".to_string(),
+        player.player_id.clone(),
+    ))
+}
+
+/// JSON body of a request which was sent on behalf of the authenticated player.
+///
+/// Extracting it fails if the session token is missing or invalid, if the body can't be parsed or
+/// if the body acts for another player than the one the token was issued for.
+///
+/// # Props
+///
+/// - `player` -> Player authenticated by the session token
+/// - `data` -> Parsed body of the request
+pub struct Owned<T> {
+    /// Player authenticated by the session token
+    pub player: AuthenticatedPlayer,
+    /// Parsed body of the request
+    pub data: T,
+}
+
+impl<T> FromRequest<AppState> for Owned<T>
+where
+    T: DeserializeOwned + ActingPlayer,
+{
+    type Rejection = Response;
+
+    /// Authenticates the request, parses the body and checks the ownership.
+    async fn from_request(request: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        let (mut parts, body) = request.into_parts();
+        let player = AuthenticatedPlayer::from_request_parts(&mut parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let Json(data) = Json::<T>::from_request(Request::from_parts(parts, body), state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        ensure_owner(&player, data.acting_player_id()).map_err(IntoResponse::into_response)?;
+
+        Ok(Owned { player, data })
+    }
+}
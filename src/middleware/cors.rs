@@ -0,0 +1,88 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{
+        header::{
+            ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+            ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+            ORIGIN, VARY,
+        },
+        HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::Response,
+};
+
+use crate::{
+    auth::{
+        admin::ADMIN_TOKEN_HEADER, device_token::DEVICE_TOKEN_HEADER, fingerprint::CLIENT_ID_HEADER,
+    },
+    router::router_provider::AppState,
+};
+
+// constants
+/// Methods the frontends may use.
+const ALLOWED_METHODS: &str = "GET, POST, PUT, DELETE, OPTIONS";
+
+/// Seconds a browser may cache the answer to a preflight request.
+const PREFLIGHT_MAX_AGE_SECONDS: &str = "86400";
+
+/// Response headers the frontends may read; the `ETag` is sent back in `If-None-Match`.
+const EXPOSED_HEADERS: &str = "ETag";
+
+/// Allows the frontends listed in `CORS_ALLOWED_ORIGINS` to call the worker.
+///
+/// -> A preflight request of an allowed origin is answered with `204` right away
+/// -> Every other response to an allowed origin gets the `Access-Control-Allow-Origin` header
+/// -> Requests of other origins pass unchanged, so the browser blocks their responses
+pub async fn apply_cors(
+    State(app_state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let origin = request
+        .headers()
+        .get(ORIGIN)
+        .and_then(|origin| origin.to_str().ok())
+        .filter(|origin| app_state.config.allows_origin(origin))
+        .and_then(|origin| HeaderValue::from_str(origin).ok());
+
+    let Some(origin) = origin else {
+        return next.run(request).await;
+    };
+
+    let mut response = if request.method() == Method::OPTIONS {
+        let mut preflight = Response::new(Body::empty());
+        *preflight.status_mut() = StatusCode::NO_CONTENT;
+
+        let headers = preflight.headers_mut();
+        headers.insert(
+            ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_static(ALLOWED_METHODS),
+        );
+        headers.insert(
+            ACCESS_CONTROL_ALLOW_HEADERS,
+            HeaderValue::from_str(&format!(
+                "Authorization, Content-Type, If-None-Match, Last-Event-ID, {ADMIN_TOKEN_HEADER}, {CLIENT_ID_HEADER}, {DEVICE_TOKEN_HEADER}"
+            ))
+            .expect("allowed headers are valid header characters"),
+        );
+        headers.insert(
+            ACCESS_CONTROL_MAX_AGE,
+            HeaderValue::from_static(PREFLIGHT_MAX_AGE_SECONDS),
+        );
+        preflight
+    } else {
+        next.run(request).await
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    headers.insert(
+        ACCESS_CONTROL_EXPOSE_HEADERS,
+        HeaderValue::from_static(EXPOSED_HEADERS),
+    );
+    headers.append(VARY, HeaderValue::from_static("Origin"));
+
+    response
+}
@@ -1 +1,3 @@
 pub mod authentication;
+pub mod authorization;
+pub mod cors;
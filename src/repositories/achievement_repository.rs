@@ -0,0 +1,118 @@
+use axum::http::StatusCode;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    enums::achievement::Achievement, errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database, types::achievement::UnlockedAchievement,
+    utils::time::server_time,
+};
+
+/// A database repository for interacting with the `achievements` table.
+#[derive(Clone)]
+pub struct AchievementRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl AchievementRepository {
+    /// Returns a fresh instance of `AchievementRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        AchievementRepository { db }
+    }
+
+    /// Unlocks an achievement for a player, unless it was unlocked before.
+    ///
+    /// # Arguments
+    ///
+    /// - `identity` -> Identity of the player, see `player_identity`
+    /// - `achievement` -> The achievement to unlock
+    /// - `player_id` -> Player who earned the achievement
+    /// - `game_id` -> Game in which the achievement was earned
+    ///
+    /// # Returns `true` if the achievement was newly unlocked or an error if the query fails.
+    pub async fn unlock(
+        &self,
+        identity: &str,
+        achievement: Achievement,
+        player_id: &str,
+        game_id: &str,
+    ) -> Result<bool, DatabaseQueryError<UnlockedAchievement>> {
+        let query_result = self
+            .db
+            .query(
+                "achievement.unlock",
+                "INSERT OR IGNORE INTO achievements (identity, achievement, player_id, game_id, unlocked_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5);",
+            )
+            .bind(&[
+                JsValue::from(identity),
+                JsValue::from(achievement.as_str()),
+                JsValue::from(player_id),
+                JsValue::from(game_id),
+                JsValue::from(server_time().to_rfc3339()),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(result) => Ok(result
+                .meta()
+                .ok()
+                .flatten()
+                .and_then(|meta| meta.changes)
+                .unwrap_or_default()
+                > 0),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the achievements a player unlocked, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// - `identity` -> Identity of the player, see `player_identity`
+    ///
+    /// # Returns the unlocked achievements or an error if the query fails.
+    pub async fn get_unlocked(
+        &self,
+        identity: &str,
+    ) -> Result<Vec<UnlockedAchievement>, DatabaseQueryError<UnlockedAchievement>> {
+        let query_result = self
+            .db
+            .query(
+                "achievement.get_unlocked",
+                "SELECT achievement, game_id, unlocked_at FROM achievements WHERE identity = ?
+                    ORDER BY unlocked_at ASC;",
+            )
+            .bind(&[JsValue::from(identity)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_achievements) => match fetched_achievements.results::<UnlockedAchievement>()
+            {
+                Ok(achievements) => Ok(achievements),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
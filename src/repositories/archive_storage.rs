@@ -0,0 +1,118 @@
+use axum::http::StatusCode;
+use worker::{
+    send::{SendFuture, SendWrapper},
+    Bucket, Env, HttpMetadata,
+};
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    types::{game::Game, game_archive::ARCHIVE_CONTENT_TYPE},
+};
+
+// constants
+/// Name of the R2 bucket binding holding the exported games.
+pub const ARCHIVE_BUCKET_BINDING: &str = "ARCHIVES";
+
+/// Storage of the exported games in R2.
+///
+/// Every game is stored under `games/{id}.ndjson`.
+#[derive(Clone)]
+pub struct ArchiveStorage {
+    /// Bucket holding the exports
+    bucket: SendWrapper<Bucket>,
+}
+
+impl ArchiveStorage {
+    /// Creates the storage from the bucket binding of the worker.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the bucket isn't bound; games can't be exported then.
+    pub fn from_env(env: &Env) -> Option<Self> {
+        env.bucket(ARCHIVE_BUCKET_BINDING)
+            .ok()
+            .map(|bucket| ArchiveStorage {
+                bucket: SendWrapper(bucket),
+            })
+    }
+
+    /// Stores the export of a game, replacing an older one.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the exported game
+    /// - `archive` -> Newline-delimited JSON of the game
+    pub async fn put_archive(
+        &self,
+        game_id: &str,
+        archive: String,
+    ) -> Result<(), DatabaseQueryError<Game>> {
+        let bucket = self.bucket.clone();
+        let key = archive_key(game_id);
+
+        SendFuture::new(async move {
+            bucket
+                .put(&key, archive)
+                .http_metadata(HttpMetadata {
+                    content_type: Some(ARCHIVE_CONTENT_TYPE.to_string()),
+                    ..Default::default()
+                })
+                .execute()
+                .await
+        })
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Checks whether a game was exported already.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    pub async fn has_archive(&self, game_id: &str) -> Result<bool, DatabaseQueryError<Game>> {
+        let bucket = self.bucket.clone();
+        let key = archive_key(game_id);
+
+        SendFuture::new(async move { bucket.head(&key).await })
+            .await
+            .map(|object| object.is_some())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+
+    /// Gets the export of a game.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the game wasn't exported yet.
+    pub async fn get_archive(
+        &self,
+        game_id: &str,
+    ) -> Result<Option<Vec<u8>>, DatabaseQueryError<Game>> {
+        let bucket = self.bucket.clone();
+        let key = archive_key(game_id);
+
+        SendFuture::new(async move {
+            match bucket.get(&key).execute().await? {
+                Some(object) => match object.body() {
+                    Some(body) => Ok(Some(body.bytes().await?)),
+                    None => Ok(None),
+                },
+                None => Ok(None),
+            }
+        })
+        .await
+        .map_err(|err: worker::Error| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+}
+
+/// Builds the object key of an exported game.
+fn archive_key(game_id: &str) -> String {
+    format!("games/{game_id}.ndjson")
+}
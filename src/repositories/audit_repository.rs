@@ -0,0 +1,114 @@
+use axum::{http::StatusCode, Json};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::audit::{AuditEntry, CreateAuditEntryDTO},
+    utils::{d1_conversions::optional_binding, time::server_time},
+};
+
+/// A database repository for interacting with the `audit_log` table.
+#[derive(Clone)]
+pub struct AuditRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl AuditRepository {
+    /// Returns a fresh instance of `AuditRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        AuditRepository { db }
+    }
+
+    /// Appends an action to the audit log.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_data` -> The action to be recorded.
+    ///
+    /// # Returns the stored entry or an error if the insertion fails.
+    pub async fn add_entry(
+        &self,
+        entry_data: CreateAuditEntryDTO,
+    ) -> Result<AuditEntry, DatabaseQueryError<CreateAuditEntryDTO>> {
+        let query_result = self
+            .db
+            .query(
+                "audit.add_entry",
+                "INSERT INTO audit_log (actor, action, target, details, request_id, created_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6) RETURNING *;",
+            )
+            .bind(&[
+                JsValue::from(entry_data.actor.clone()),
+                JsValue::from(entry_data.action.as_str()),
+                optional_binding(entry_data.target.clone()),
+                optional_binding(entry_data.details.clone()),
+                optional_binding(entry_data.request_id.clone()),
+                JsValue::from(server_time().to_rfc3339()),
+            ])
+            .unwrap()
+            .first::<AuditEntry>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(entry)) => Ok(entry),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to store the audit entry".to_string(),
+                Some(Json(entry_data)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(entry_data)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets a page of the audit log, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// - `before` -> Only entries with a lower sequence number; the newest ones if `None`.
+    /// - `limit` -> Maximum number of entries to return.
+    ///
+    /// # Returns the entries or an error if the query fails.
+    pub async fn get_entries(
+        &self,
+        before: Option<usize>,
+        limit: usize,
+    ) -> Result<Vec<AuditEntry>, DatabaseQueryError<AuditEntry>> {
+        let query_result = self
+            .db
+            .query(
+                "audit.get_entries",
+                "SELECT * FROM audit_log WHERE (?1 IS NULL OR sequence < ?1)
+                    ORDER BY sequence DESC LIMIT ?2;",
+            )
+            .bind(&[optional_binding(before), JsValue::from(limit)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_entries) => match fetched_entries.results::<AuditEntry>() {
+                Ok(entries) => Ok(entries),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
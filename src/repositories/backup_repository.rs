@@ -0,0 +1,180 @@
+use axum::http::StatusCode;
+use serde_json::{Map, Value};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    enums::backup_table::BackupTable,
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::backup::BackupRecord,
+    utils::d1_conversions::{json_binding, optional_binding},
+};
+
+/// A database repository reading and writing the raw rows of games and their children for bulk
+/// exports and imports.
+#[derive(Clone)]
+pub struct BackupRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl BackupRepository {
+    /// Returns a fresh instance of `BackupRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        BackupRepository { db }
+    }
+
+    /// Gets the rows of a table that belong to one page of games, ordered by the game id.
+    ///
+    /// # Arguments
+    ///
+    /// - `table` -> Table to read
+    /// - `after` -> Last game id of the previous page; the first page if `None`
+    /// - `limit` -> Number of games of the page
+    ///
+    /// # Returns the raw rows or an error if the query fails.
+    pub async fn get_rows(
+        &self,
+        table: BackupTable,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Map<String, Value>>, DatabaseQueryError<BackupRecord>> {
+        let query_result = self
+            .db
+            .query("backup.get_rows", table.export_query())
+            .bind(&[optional_binding(after), JsValue::from(limit)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_rows) => match fetched_rows.results::<Map<String, Value>>() {
+                Ok(rows) => Ok(rows),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                format!("Failed to export {table}: {err}"),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Writes a raw row, replacing a stored row with the same key.
+    ///
+    /// # Arguments
+    ///
+    /// - `record` -> Row and the table it belongs to
+    ///
+    /// # Returns an error if a column name is invalid or the insertion fails.
+    pub async fn put_row(
+        &self,
+        record: &BackupRecord,
+    ) -> Result<(), DatabaseQueryError<BackupRecord>> {
+        let generated_columns = record.table.generated_columns();
+        let columns = record
+            .row
+            .iter()
+            .filter(|(column, _)| !generated_columns.contains(&column.as_str()))
+            .collect::<Vec<_>>();
+
+        // column names can't be bound, so only plain identifiers end up in the query
+        if let Some((column, _)) = columns.iter().find(|(column, _)| !is_identifier(column)) {
+            return Err(DatabaseQueryError::new(
+                format!("'{column}' isn't a valid column name!"),
+                None,
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+        if columns.is_empty() {
+            return Err(DatabaseQueryError::new(
+                "The row has no columns!".to_string(),
+                None,
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+
+        let column_list = columns
+            .iter()
+            .map(|(column, _)| column.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let placeholders = (1..=columns.len())
+            .map(|position| format!("?{position}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let assignments = columns
+            .iter()
+            .map(|(column, _)| format!("{column} = excluded.{column}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let bindings = columns
+            .iter()
+            .map(|(_, value)| json_binding(value))
+            .collect::<Vec<_>>();
+
+        let query_result = self
+            .db
+            .query(
+                "backup.put_row",
+                // an upsert instead of `INSERT OR REPLACE`, which would delete a row other rows
+                // still reference
+                format!(
+                    "INSERT INTO {} ({column_list}) VALUES ({placeholders})
+                        ON CONFLICT DO UPDATE SET {assignments};",
+                    record.table
+                ),
+            )
+            .bind(&bindings)
+            .unwrap()
+            .run()
+            .await;
+
+        query_result.map(|_| ()).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Deletes all events of a game, so an import doesn't add them a second time.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Game whose events are imported
+    ///
+    /// # Returns an error if the deletion fails.
+    pub async fn delete_game_events(
+        &self,
+        game_id: &str,
+    ) -> Result<(), DatabaseQueryError<BackupRecord>> {
+        let query_result = self
+            .db
+            .query(
+                "backup.delete_game_events",
+                "DELETE FROM game_events WHERE game_id = ?;",
+            )
+            .bind(&[JsValue::from(game_id)])
+            .unwrap()
+            .run()
+            .await;
+
+        query_result.map(|_| ()).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+}
+
+/// Checks whether a column name consists of lowercase letters, digits and underscores only.
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|character| {
+            character.is_ascii_lowercase() || character.is_ascii_digit() || character == '_'
+        })
+}
@@ -2,11 +2,12 @@
 
 use axum::{http::StatusCode, Json};
 use wasm_bindgen::JsValue;
-use worker::D1Database;
 
 use crate::{
     errors::{database_query_error::DatabaseQueryError, process_error::ProcessError},
+    repositories::database::Database,
     types::card::{Card, UpdateCardDTO},
+    utils::d1_conversions::optional_binding,
 };
 
 /// A database repository for interacting with the `cards` table.
@@ -15,12 +16,12 @@ use crate::{
 ///
 /// It will be accessible in the context element in the handler functions.
 #[derive(Clone)]
-pub struct CardRepository<'a> {
+pub struct CardRepository {
     /// Database pointer to execute queries.
-    db: &'a D1Database,
+    db: Database,
 }
 
-impl<'a> CardRepository<'a> {
+impl CardRepository {
     /// Returns a fresh instance of `CardRepository` struct.
     ///
     /// # Arguments
@@ -28,7 +29,7 @@ impl<'a> CardRepository<'a> {
     /// - `db` -> Database service pointer to execute queries.
     ///
     /// # Returns a `CardRepository` instance.
-    pub fn new(db: &'a D1Database) -> Self {
+    pub fn new(db: Database) -> Self {
         CardRepository { db }
     }
 
@@ -68,7 +69,13 @@ impl<'a> CardRepository<'a> {
 
         query.push(';');
 
-        let query_result = self.db.prepare(&query).bind(&params).unwrap().all().await;
+        let query_result = self
+            .db
+            .query("card.get_all_cards", &query)
+            .bind(&params)
+            .unwrap()
+            .all()
+            .await;
 
         match query_result {
             Ok(fetched_cards) => {
@@ -93,6 +100,91 @@ impl<'a> CardRepository<'a> {
         }
     }
 
+    /// Gets the hand of a player.
+    ///
+    /// Cards which were placed in a claim still belong to the player who placed them, but they
+    /// aren't part of the hand anymore.
+    ///
+    /// # Arguments
+    ///
+    /// - `player_id` -> Identifier of the `Player` object.
+    ///
+    /// # Returns all cards the player holds or an error if the query fails.
+    pub async fn get_hand_of_player(
+        &self,
+        player_id: &str,
+    ) -> Result<Vec<Card>, DatabaseQueryError<Card>> {
+        let query_result = self
+            .db
+            .query(
+                "card.get_hand_of_player",
+                "SELECT * FROM cards WHERE player_id = ? AND claim_id IS NULL;",
+            )
+            .bind(&[JsValue::from(player_id)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_cards) => match fetched_cards.results::<Card>() {
+                Ok(cards) => Ok(cards),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Moves all cards placed in the claims of a round into the hand of a player.
+    ///
+    /// Used when a player has to pick up the stack after a challenge.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    /// - `round_number` -> Round whose stack is picked up.
+    /// - `player_id` -> Identifier of the player who picks up the stack.
+    ///
+    /// # Returns `Ok(())` if the update was successful, or an error if the query fails.
+    pub async fn move_stack_to_player(
+        &self,
+        game_id: &str,
+        round_number: usize,
+        player_id: &str,
+    ) -> Result<(), DatabaseQueryError<Card>> {
+        let query = "UPDATE cards SET player_id = ?1, claim_id = NULL 
+            WHERE claim_id IN (SELECT id FROM claims WHERE game_id = ?2 AND round_number = ?3);";
+        let params = vec![
+            JsValue::from(player_id),
+            JsValue::from(game_id),
+            JsValue::from(round_number),
+        ];
+
+        let query_result = self
+            .db
+            .query("card.move_stack_to_player", query)
+            .bind(&params)
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
     /// Gets a `Card` struct from the database by its ID.
     ///
     /// # Arguments
@@ -106,7 +198,7 @@ impl<'a> CardRepository<'a> {
 
         let query_result = self
             .db
-            .prepare(query)
+            .query("card.get_card_by_id", query)
             .bind(&params)
             .unwrap()
             .first::<Card>(None)
@@ -140,7 +232,45 @@ impl<'a> CardRepository<'a> {
         let query = "DELETE FROM cards WHERE id = ?;";
         let params = vec![JsValue::from(id)];
 
-        let query_result = self.db.prepare(query).bind(&params).unwrap().run().await;
+        let query_result = self
+            .db
+            .query("card.delete_card", query)
+            .bind(&params)
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Deletes all cards in the hand of a player.
+    ///
+    /// # Arguments
+    ///
+    /// - `player_id` -> Identifier of the `Player` whose cards are removed.
+    ///
+    /// # Returns `Ok(())` if the deletion was successful, or an error if the query fails.
+    pub async fn delete_cards_of_player(
+        &self,
+        player_id: &str,
+    ) -> Result<(), DatabaseQueryError<Card>> {
+        let query = "DELETE FROM cards WHERE player_id = ? AND claim_id IS NULL;";
+        let params = vec![JsValue::from(player_id)];
+
+        let query_result = self
+            .db
+            .query("card.delete_cards_of_player", query)
+            .bind(&params)
+            .unwrap()
+            .run()
+            .await;
 
         match query_result {
             Ok(_) => Ok(()),
@@ -165,16 +295,17 @@ impl<'a> CardRepository<'a> {
         card: Card,
         player_id: String,
     ) -> Result<Card, DatabaseQueryError<Card>> {
-        let query = "INSERT INTO cards (id, card_type, player_id) VALUES (1?, 2?, 3?) RETURN *;";
+        let query = "INSERT INTO cards (id, card_type, suit, player_id) VALUES (?1, ?2, ?3, ?4) RETURNING *;";
         let params = vec![
             JsValue::from(card.id.clone()),
-            JsValue::from(card.card_type.index()),
+            JsValue::from(card.card_type.as_str()),
+            optional_binding(card.suit.as_ref().map(|suit| suit.as_str())),
             JsValue::from(player_id),
         ];
 
         let query_result = self
             .db
-            .prepare(query)
+            .query("card.create_card", query)
             .bind(&params)
             .unwrap()
             .first::<Card>(None)
@@ -221,7 +352,7 @@ impl<'a> CardRepository<'a> {
 
         let query_result = self
             .db
-            .prepare(&query)
+            .query("card.update_card", &query)
             .bind(&params)
             .unwrap()
             .first::<Card>(None)
@@ -274,7 +405,7 @@ impl<'a> CardRepository<'a> {
 
         if let Some(card_type) = &card_data.card_type {
             query.push_str("card_type = ?, ");
-            params.push(JsValue::from(card_type.index()));
+            params.push(JsValue::from(card_type.as_str()));
         }
 
         if let Some(player_id) = &card_data.player_id {
@@ -0,0 +1,293 @@
+use axum::{http::StatusCode, Json};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::chat::{ChatMessage, MAX_CHAT_MESSAGE_LENGTH},
+    utils::d1_conversions::optional_binding,
+};
+
+/// A database repository for interacting with the `chat_messages` table.
+///
+/// Contains the utility functions for the `ChatMessage` struct.
+#[derive(Clone)]
+pub struct ChatMessageRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl ChatMessageRepository {
+    /// Returns a fresh instance of `ChatMessageRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        ChatMessageRepository { db }
+    }
+
+    /// Gets all public messages of a chat, oldest first.
+    ///
+    /// Whispers are left out.
+    ///
+    /// # Arguments
+    ///
+    /// - `chat_id` -> Identifier of the chat.
+    ///
+    /// # Returns the messages of the chat or an error if the query fails.
+    pub async fn get_messages_of_chat(
+        &self,
+        chat_id: &str,
+    ) -> Result<Vec<ChatMessage>, DatabaseQueryError<ChatMessage>> {
+        let query_result = self
+            .db
+            .query(
+                "chat_message.get_messages_of_chat",
+                "SELECT * FROM chat_messages WHERE chat_id = ? AND recipient_player_id IS NULL
+                    ORDER BY sent_at ASC;",
+            )
+            .bind(&[JsValue::from(chat_id)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_messages) => match fetched_messages.results::<ChatMessage>() {
+                Ok(messages) => Ok(messages),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Stores a new message in a chat.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` -> The message to be inserted.
+    /// - `chat_id` -> Identifier of the chat the message was sent in.
+    ///
+    /// # Returns the stored message or an error if the insertion fails.
+    pub async fn add_message(
+        &self,
+        message: ChatMessage,
+        chat_id: &str,
+    ) -> Result<ChatMessage, DatabaseQueryError<ChatMessage>> {
+        let query =
+            "INSERT INTO chat_messages (id, player_id, content, sent_at, chat_id, is_system,
+                recipient_player_id) 
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7) RETURNING *;";
+        let params = vec![
+            JsValue::from(message.id.clone()),
+            JsValue::from(message.player_id.clone()),
+            JsValue::from(message.content.clone()),
+            JsValue::from(message.sent_at.to_rfc3339()),
+            JsValue::from(chat_id),
+            JsValue::from(i32::from(message.is_system)),
+            optional_binding(message.recipient_player_id.clone()),
+        ];
+
+        let query_result = self
+            .db
+            .query("chat_message.add_message", query)
+            .bind(&params)
+            .unwrap()
+            .first::<ChatMessage>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(stored_message)) => Ok(stored_message),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to store the chat message".to_string(),
+                Some(Json(message)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(message)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets a single message of a chat.
+    ///
+    /// # Arguments
+    ///
+    /// - `message_id` -> Identifier of the message.
+    /// - `chat_id` -> Identifier of the chat the message has to belong to.
+    ///
+    /// # Returns the message or an error with status code 404 if the chat has no such message.
+    pub async fn get_message_of_chat(
+        &self,
+        message_id: &str,
+        chat_id: &str,
+    ) -> Result<ChatMessage, DatabaseQueryError<ChatMessage>> {
+        let query_result = self
+            .db
+            .query(
+                "chat_message.get_message_of_chat",
+                "SELECT * FROM chat_messages WHERE id = ?1 AND chat_id = ?2;",
+            )
+            .bind(&[JsValue::from(message_id), JsValue::from(chat_id)])
+            .unwrap()
+            .first::<ChatMessage>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(message)) => Ok(message),
+            Ok(None) => Err(DatabaseQueryError::new(
+                format!("No message with id {} found in the chat!", message_id),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Stores the content and the edit or deletion time of a message.
+    ///
+    /// # Arguments
+    ///
+    /// - `message` -> The changed message.
+    ///
+    /// # Returns `Ok(())` if the update was successful, or an error if the query fails.
+    pub async fn update_message(
+        &self,
+        message: &ChatMessage,
+    ) -> Result<(), DatabaseQueryError<ChatMessage>> {
+        let query = "UPDATE chat_messages SET content = ?1, edited_at = ?2, deleted_at = ?3
+            WHERE id = ?4;";
+        let params = vec![
+            JsValue::from(message.content.clone()),
+            optional_binding(message.edited_at.map(|edited_at| edited_at.to_rfc3339())),
+            optional_binding(message.deleted_at.map(|deleted_at| deleted_at.to_rfc3339())),
+            JsValue::from(message.id.clone()),
+        ];
+
+        let query_result = self
+            .db
+            .query("chat_message.update_message", query)
+            .bind(&params)
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(message.clone())),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Deletes the oldest messages of a chat beyond `MAX_CHAT_MESSAGE_LENGTH`.
+    ///
+    /// The public messages and the whispers of a chat are capped separately. The message count of
+    /// the pruned chats is refreshed in the same transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `chat_id` -> Identifier of the chat to prune; `None` prunes every chat.
+    ///
+    /// # Returns the number of deleted messages or an error if a query fails.
+    pub async fn prune_messages(
+        &self,
+        chat_id: Option<&str>,
+    ) -> Result<usize, DatabaseQueryError<ChatMessage>> {
+        let statements = vec![
+            self.db
+                .query(
+                    "chat_message.prune_messages",
+                    "DELETE FROM chat_messages WHERE id IN (
+                        SELECT id FROM (
+                            SELECT id, ROW_NUMBER() OVER (
+                                PARTITION BY chat_id, recipient_player_id IS NULL
+                                ORDER BY sent_at DESC, rowid DESC
+                            ) AS position
+                            FROM chat_messages WHERE ?1 IS NULL OR chat_id = ?1
+                        ) WHERE position > ?2
+                    );",
+                )
+                .bind(&[
+                    optional_binding(chat_id),
+                    JsValue::from(MAX_CHAT_MESSAGE_LENGTH as u32),
+                ])
+                .unwrap(),
+            self.db
+                .query(
+                    "chat_message.prune_messages",
+                    "UPDATE chats SET number_of_messages = (SELECT COUNT(*) FROM chat_messages
+                        WHERE chat_messages.chat_id = chats.id
+                        AND chat_messages.recipient_player_id IS NULL)
+                    WHERE ?1 IS NULL OR id = ?1;",
+                )
+                .bind(&[optional_binding(chat_id)])
+                .unwrap(),
+        ];
+
+        match self
+            .db
+            .batch("chat_message.prune_messages", statements)
+            .await
+        {
+            Ok(results) => Ok(results
+                .first()
+                .and_then(|result| result.meta().ok().flatten())
+                .and_then(|meta| meta.changes)
+                .unwrap_or_default()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Deletes a message by its ID.
+    ///
+    /// # Arguments
+    ///
+    /// - `message_id` -> Identifier of the message to be deleted.
+    ///
+    /// # Returns `Ok(())` if the deletion was successful, or an error if the query fails.
+    pub async fn delete_message(
+        &self,
+        message_id: &str,
+    ) -> Result<(), DatabaseQueryError<ChatMessage>> {
+        let query_result = self
+            .db
+            .query(
+                "chat_message.delete_message",
+                "DELETE FROM chat_messages WHERE id = ?;",
+            )
+            .bind(&[JsValue::from(message_id)])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use axum::http::StatusCode;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    repositories::chat::chat_message_repository::ChatMessageRepository,
+    repositories::database::Database,
+    types::chat::{Chat, ChatMessage},
+};
+
+/// A database repository for interacting with the `chats` table.
+///
+/// Every game has exactly one chat, which is created with the first message.
+#[derive(Clone)]
+pub struct ChatRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl ChatRepository {
+    /// Returns a fresh instance of `ChatRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        ChatRepository { db }
+    }
+
+    /// Gets the chat of a game together with its messages.
+    ///
+    /// A new chat is created if the game doesn't have one yet.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    /// - `message_repository` -> Reference to the `ChatMessageRepository` to fetch the messages.
+    ///
+    /// # Returns the chat of the game or an error if a query fails.
+    pub async fn get_or_create_chat_of_game(
+        &self,
+        game_id: &str,
+        message_repository: &ChatMessageRepository,
+    ) -> Result<Chat, DatabaseQueryError<ChatMessage>> {
+        let query_result = self
+            .db
+            .query(
+                "chat.get_or_create_chat_of_game",
+                "SELECT * FROM chats WHERE game_id = ?;",
+            )
+            .bind(&[JsValue::from(game_id)])
+            .unwrap()
+            .first::<Chat>(None)
+            .await;
+
+        let mut chat = match query_result {
+            Ok(Some(chat)) => chat,
+            Ok(None) => return self.create_chat(game_id).await,
+            Err(err) => {
+                return Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ))
+            }
+        };
+
+        chat.messages = message_repository.get_messages_of_chat(&chat.id).await?;
+
+        Ok(chat)
+    }
+
+    /// Creates an empty chat for a game.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    ///
+    /// # Returns the new chat or an error if the insertion fails.
+    pub async fn create_chat(
+        &self,
+        game_id: &str,
+    ) -> Result<Chat, DatabaseQueryError<ChatMessage>> {
+        let chat = Chat::new();
+
+        let query_result = self
+            .db
+            .query(
+                "chat.create_chat",
+                "INSERT INTO chats (id, game_id, number_of_messages) VALUES (?1, ?2, ?3);",
+            )
+            .bind(&[
+                JsValue::from(chat.id.clone()),
+                JsValue::from(game_id),
+                JsValue::from(chat.number_of_messages),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(chat),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Stores the number of messages of a chat.
+    ///
+    /// # Arguments
+    ///
+    /// - `chat` -> Chat with the new number of messages.
+    ///
+    /// # Returns `Ok(())` if the update was successful, or an error if the query fails.
+    pub async fn update_number_of_messages(
+        &self,
+        chat: &Chat,
+    ) -> Result<(), DatabaseQueryError<ChatMessage>> {
+        let query_result = self
+            .db
+            .query(
+                "chat.update_number_of_messages",
+                "UPDATE chats SET number_of_messages = ?1 WHERE id = ?2;",
+            )
+            .bind(&[
+                JsValue::from(chat.number_of_messages),
+                JsValue::from(chat.id.clone()),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use axum::{http::StatusCode, Json};
 use wasm_bindgen::JsValue;
-use worker::D1Database;
 
 use crate::{
+    enums::card_types::CardType,
     errors::database_query_error::DatabaseQueryError,
     repositories::card_repository::CardRepository,
+    repositories::database::Database,
     types::{card::UpdateCardDTO, claim::Claim},
+    utils::d1_conversions::optional_binding,
 };
 
 /// A database repository for interacting with the `claims` table.
@@ -14,19 +16,19 @@ use crate::{
 ///
 /// It will be accessable in the context element in the handler functions.
 #[derive(Clone)]
-pub struct ClaimsRepository<'a> {
-    db: &'a D1Database,
+pub struct ClaimsRepository {
+    db: Database,
 }
 
 // ----- Implementation of the 'ClaimsRepository' struct -----
 
-impl<'a> ClaimsRepository<'a> {
+impl ClaimsRepository {
     /// Returns a fresh instance of `ClaimsRepository` struct.
     ///
     /// # Arguments
     ///
     /// - `db` -> Database service pointer to execute queries.
-    pub fn new(db: &'a D1Database) -> Self {
+    pub fn new(db: Database) -> Self {
         ClaimsRepository { db }
     }
 
@@ -40,7 +42,10 @@ impl<'a> ClaimsRepository<'a> {
     pub async fn get_claim_by_id(&self, id: String) -> Result<Claim, DatabaseQueryError<Claim>> {
         let query_result = self
             .db
-            .prepare("SELECT * FROM claims WHERE id = ?;")
+            .query(
+                "claim.get_claim_by_id",
+                "SELECT * FROM claims WHERE id = ?;",
+            )
             .bind(&[JsValue::from(id.clone())])
             .unwrap()
             .first::<Claim>(None)
@@ -79,7 +84,7 @@ impl<'a> ClaimsRepository<'a> {
         &self,
         game_id: Option<String>,
         player_id: Option<String>,
-        card_repository: &CardRepository<'_>,
+        card_repository: &CardRepository,
     ) -> Result<Vec<Claim>, DatabaseQueryError<Claim>> {
         let mut query = "SELECT * FROM claims".to_string();
         let mut params: Vec<JsValue> = Vec::new();
@@ -94,7 +99,13 @@ impl<'a> ClaimsRepository<'a> {
 
         query.push_str(";");
 
-        let query_result = self.db.prepare(&query).bind(&params).unwrap().all().await;
+        let query_result = self
+            .db
+            .query("claim.get_all_claims", &query)
+            .bind(&params)
+            .unwrap()
+            .all()
+            .await;
 
         match query_result {
             Ok(fetched_claims) => {
@@ -139,6 +150,70 @@ impl<'a> ClaimsRepository<'a> {
         }
     }
 
+    /// Gets the most recent claim made in a round of a game.
+    ///
+    /// A challenge always targets this claim.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    /// - `round_number` -> Round of the game.
+    /// - `card_repository` -> Reference to the `CardRepository` to fetch the placed cards.
+    ///
+    /// # Returns the latest `Claim` of the round or an error if there is none.
+    pub async fn get_last_claim_of_round(
+        &self,
+        game_id: String,
+        round_number: usize,
+        card_repository: &CardRepository,
+    ) -> Result<Claim, DatabaseQueryError<Claim>> {
+        let query_result = self
+            .db
+            .query(
+                "claim.get_last_claim_of_round",
+                "SELECT * FROM claims WHERE game_id = ?1 AND round_number = ?2 
+                    ORDER BY created_at DESC LIMIT 1;",
+            )
+            .bind(&[JsValue::from(game_id.clone()), JsValue::from(round_number)])
+            .unwrap()
+            .first::<Claim>(None)
+            .await;
+
+        let mut claim = match query_result {
+            Ok(Some(claim)) => claim,
+            Ok(None) => {
+                return Err(DatabaseQueryError::new(
+                    format!("No claim was made in round {round_number} of the game {game_id}!"),
+                    None,
+                    StatusCode::NOT_FOUND,
+                ))
+            }
+            Err(err) => {
+                return Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ))
+            }
+        };
+
+        claim.cards = match card_repository
+            .get_all_cards(Some(claim.id.clone()), None)
+            .await
+        {
+            Ok(cards) => cards,
+            Err(err) => {
+                return Err(DatabaseQueryError::new(
+                    err.message,
+                    Some(Json(claim)),
+                    err.status_code,
+                ))
+            }
+        };
+
+        Ok(claim)
+    }
+
     /// Uses a `Claim` struct to create a new claim entry in the database.
     ///
     /// # Arguments
@@ -151,17 +226,28 @@ impl<'a> ClaimsRepository<'a> {
     pub async fn create_claim(
         &self,
         claim: Claim,
-        card_repository: &CardRepository<'_>,
+        card_repository: &CardRepository,
     ) -> Result<Claim, DatabaseQueryError<Claim>> {
-        let query =
-            "INSERT INTO claims (id, created_by, number_of_cards, cards) VALUES (?, ?, ?, ?);";
+        let query = "INSERT INTO claims (id, created_by, game_id, round_number, created_at, number_of_cards, declared_card_type, declared_count) 
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);";
         let params = vec![
             JsValue::from(claim.id.clone()),
             JsValue::from(claim.created_by.clone()),
+            JsValue::from(claim.game_id.clone()),
+            JsValue::from(claim.round_number),
+            JsValue::from(claim.created_at.to_rfc3339()),
             JsValue::from(claim.number_of_cards as i32),
+            optional_binding(claim.declared_card_type.as_ref().map(CardType::as_str)),
+            optional_binding(claim.declared_count.map(|count| count as i32)),
         ];
 
-        let query_result = self.db.prepare(query).bind(&params).unwrap().run().await;
+        let query_result = self
+            .db
+            .query("claim.create_claim", query)
+            .bind(&params)
+            .unwrap()
+            .run()
+            .await;
 
         // cards need to be stored separatly
         for card in &claim.cards {
@@ -208,7 +294,180 @@ impl<'a> ClaimsRepository<'a> {
     pub async fn delete_claim(&self, claim_id: String) -> Result<(), DatabaseQueryError<Claim>> {
         let query_result = self
             .db
-            .prepare("DELETE FROM claims WHERE id = ?;")
+            .query("claim.delete_claim", "DELETE FROM claims WHERE id = ?;")
+            .bind(&[JsValue::from(claim_id)])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Deletes all claims of a game together with the cards placed with them.
+    ///
+    /// Both happen in one transaction, so no claim is left without its cards.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    ///
+    /// # Returns `Ok(())` if the deletion is successful, or an error if it fails.
+    pub async fn delete_claims_of_game(
+        &self,
+        game_id: &str,
+    ) -> Result<(), DatabaseQueryError<Claim>> {
+        let statements = vec![
+            self.db
+                .query(
+                    "claim.delete_claims_of_game",
+                    "DELETE FROM cards WHERE claim_id IN (SELECT id FROM claims WHERE game_id = ?);",
+                )
+                .bind(&[JsValue::from(game_id)])
+                .unwrap(),
+            self.db
+                .query(
+                    "claim.delete_claims_of_game",
+                    "DELETE FROM claims WHERE game_id = ?;",
+                )
+                .bind(&[JsValue::from(game_id)])
+                .unwrap(),
+        ];
+
+        match self
+            .db
+            .batch("claim.delete_claims_of_game", statements)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets all claims of a round together with their placed cards.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    /// - `round_number` -> Round of the game.
+    /// - `card_repository` -> Reference to the `CardRepository` to fetch the placed cards.
+    ///
+    /// # Returns the claims in the order they were made or an error if a query fails.
+    pub async fn get_claims_of_round(
+        &self,
+        game_id: &str,
+        round_number: usize,
+        card_repository: &CardRepository,
+    ) -> Result<Vec<Claim>, DatabaseQueryError<Claim>> {
+        let query_result = self
+            .db
+            .query(
+                "claim.get_claims_of_round",
+                "SELECT * FROM claims WHERE game_id = ?1 AND round_number = ?2 
+                    ORDER BY created_at ASC;",
+            )
+            .bind(&[JsValue::from(game_id), JsValue::from(round_number)])
+            .unwrap()
+            .all()
+            .await;
+
+        let mut claims = match query_result {
+            Ok(fetched_claims) => match fetched_claims.results::<Claim>() {
+                Ok(claims) => claims,
+                Err(err) => {
+                    return Err(DatabaseQueryError::new(
+                        err.to_string(),
+                        None,
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    ))
+                }
+            },
+            Err(err) => {
+                return Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ))
+            }
+        };
+
+        for claim in claims.iter_mut() {
+            claim.cards = match card_repository
+                .get_all_cards(Some(claim.id.clone()), None)
+                .await
+            {
+                Ok(cards) => cards,
+                Err(err) => {
+                    return Err(DatabaseQueryError::new(
+                        err.message,
+                        Some(Json(claim.clone())),
+                        err.status_code,
+                    ))
+                }
+            };
+        }
+
+        Ok(claims)
+    }
+
+    /// Takes back a claim: its cards return to the hand of its author and the claim is deleted.
+    ///
+    /// Both happen in one transaction, so no card is lost if one of them fails.
+    ///
+    /// # Arguments
+    ///
+    /// - `claim_id` -> Identifier of the `Claim` object to be taken back.
+    ///
+    /// # Returns `Ok(())` if the claim was taken back, or an error if a query fails.
+    pub async fn retract_claim(&self, claim_id: &str) -> Result<(), DatabaseQueryError<Claim>> {
+        let statements = vec![
+            self.db
+                .query(
+                    "claim.retract_claim",
+                    "UPDATE cards SET claim_id = NULL WHERE claim_id = ?;",
+                )
+                .bind(&[JsValue::from(claim_id)])
+                .unwrap(),
+            self.db
+                .query("claim.retract_claim", "DELETE FROM claims WHERE id = ?;")
+                .bind(&[JsValue::from(claim_id)])
+                .unwrap(),
+        ];
+
+        match self.db.batch("claim.retract_claim", statements).await {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Turns the cards of a claim face-up.
+    ///
+    /// # Arguments
+    ///
+    /// - `claim_id` -> Identifier of the `Claim` object to be revealed.
+    ///
+    /// # Returns `Ok(())` if the update is successful, or an error if it fails.
+    pub async fn reveal_claim(&self, claim_id: &str) -> Result<(), DatabaseQueryError<Claim>> {
+        let query_result = self
+            .db
+            .query(
+                "claim.reveal_claim",
+                "UPDATE claims SET revealed = 1 WHERE id = ?;",
+            )
             .bind(&[JsValue::from(claim_id)])
             .unwrap()
             .run()
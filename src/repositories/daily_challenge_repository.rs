@@ -0,0 +1,147 @@
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::daily_challenge::{DailyChallenge, DailyResult},
+    utils::time::server_time,
+};
+
+/// A database repository for interacting with the `daily_challenges` table and the results of
+/// the daily challenges.
+#[derive(Clone)]
+pub struct DailyChallengeRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl DailyChallengeRepository {
+    /// Returns a fresh instance of `DailyChallengeRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        DailyChallengeRepository { db }
+    }
+
+    /// Retrieves the template of a day and creates it if it doesn't exist yet.
+    ///
+    /// Concurrent requests of a new day all get the template that was stored first.
+    ///
+    /// # Arguments
+    ///
+    /// - `date` -> Day of the challenge
+    /// - `seed` -> Seed used if the template is created now
+    ///
+    /// # Returns the template or an error if the query fails.
+    pub async fn get_or_create_challenge(
+        &self,
+        date: NaiveDate,
+        seed: String,
+    ) -> Result<DailyChallenge, DatabaseQueryError<DailyChallenge>> {
+        // the no-op update makes `RETURNING` hand back an existing row as well
+        let query_result = self
+            .db
+            .query(
+                "daily_challenge.get_or_create_challenge",
+                "INSERT INTO daily_challenges (date, seed, created_at) VALUES (?1, ?2, ?3)
+                    ON CONFLICT(date) DO UPDATE SET date = excluded.date RETURNING *;",
+            )
+            .bind(&[
+                JsValue::from(date.to_string()),
+                JsValue::from(seed),
+                JsValue::from(server_time().to_rfc3339()),
+            ])
+            .unwrap()
+            .first::<DailyChallenge>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(challenge)) => Ok(challenge),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to store the daily challenge".to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the best results of the daily challenge of a day.
+    ///
+    /// Winners come first, then higher scores, then fewer rounds, then earlier games.
+    ///
+    /// # Arguments
+    ///
+    /// - `date` -> Day of the challenge
+    /// - `limit` -> Maximum number of results to return
+    ///
+    /// # Returns the results without their rank or an error if the query fails.
+    pub async fn get_results(
+        &self,
+        date: NaiveDate,
+        limit: usize,
+    ) -> Result<Vec<DailyResult>, DatabaseQueryError<DailyResult>> {
+        let query_result = self
+            .db
+            .query(
+                "daily_challenge.get_results",
+                "SELECT player_name, score, is_winner, rounds_played, player_count FROM game_results
+                    WHERE daily_date = ?1
+                    ORDER BY is_winner DESC, score DESC, rounds_played ASC, finished_at ASC LIMIT ?2;",
+            )
+            .bind(&[JsValue::from(date.to_string()), JsValue::from(limit)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_results) => match fetched_results.results::<DailyResult>() {
+                Ok(results) => Ok(results),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Counts the finished games of the daily challenge of a day.
+    pub async fn count_games(
+        &self,
+        date: NaiveDate,
+    ) -> Result<usize, DatabaseQueryError<DailyResult>> {
+        let query_result = self
+            .db
+            .query(
+                "daily_challenge.count_games",
+                "SELECT COUNT(DISTINCT game_id) AS count FROM game_results WHERE daily_date = ?;",
+            )
+            .bind(&[JsValue::from(date.to_string())])
+            .unwrap()
+            .first::<usize>(Some("count"))
+            .await;
+
+        match query_result {
+            Ok(count) => Ok(count.unwrap_or_default()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
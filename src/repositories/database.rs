@@ -0,0 +1,262 @@
+use std::{cell::RefCell, collections::HashMap, future::Future, sync::Arc, time::Duration};
+
+use log::warn;
+use rand_chacha::rand_core::RngCore;
+use serde::Deserialize;
+use wasm_bindgen::JsValue;
+use worker::{
+    send::{SendFuture, SendWrapper},
+    D1Database, D1PreparedStatement, D1Result, Delay,
+};
+
+use crate::{
+    metrics::{
+        analytics::{Metric, MetricsRecorder},
+        query_timings::record_query_latency,
+    },
+    utils::{game_service::new_rng, time::server_time},
+};
+
+// constants
+/// Most distinct SQL strings whose prepared statements are kept for the rest of a request.
+///
+/// Statements built from dynamic SQL, e.g. the updates of a game, can differ on every call; once
+/// the cache is full they are prepared without being kept.
+const MAX_CACHED_STATEMENTS: usize = 64;
+
+/// Faults injected into every query to rehearse a degraded database.
+///
+/// Only applied while the `chaos` feature is switched on outside of production. The latency and
+/// the failures count towards the metrics like real ones, so timeouts, retries and the event
+/// streams can be observed under load.
+///
+/// # Props
+///
+/// - `latency_ms` -> Most milliseconds added to a query; every query waits a random share of it
+/// - `failure_percent` -> Percentage of queries failed before they reach D1
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChaosSettings {
+    /// Most milliseconds added to a query
+    pub latency_ms: u64,
+    /// Percentage of queries failed before they reach D1
+    pub failure_percent: u32,
+}
+
+impl ChaosSettings {
+    /// Returns `true` if any fault is injected.
+    pub fn is_active(&self) -> bool {
+        self.latency_ms > 0 || self.failure_percent > 0
+    }
+
+    /// Delays a query and decides whether it fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `worker::Error` for the share of queries that should fail.
+    async fn inject(&self, name: &'static str) -> worker::Result<()> {
+        let mut rng = new_rng();
+
+        if self.latency_ms > 0 {
+            let latency_ms = rng.next_u64() % (self.latency_ms + 1);
+            SendFuture::new(Delay::from(Duration::from_millis(latency_ms))).await;
+        }
+
+        if rng.next_u32() % 100 < self.failure_percent {
+            return Err(worker::Error::RustError(format!(
+                "Injected failure of the query '{name}'"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Shared handle of the D1 database.
+///
+/// Every repository owns a clone of the handle, so the `AppState` holding them is `'static` and
+/// can be passed to the router. The binding is only used on the single thread of the worker;
+/// `SendWrapper` satisfies the `Send + Sync` bounds of axum.
+///
+/// Queries are only run through `query`, which times every statement. The handle is created for
+/// every request, so the statements it prepared are reused by all repositories of the request.
+#[derive(Clone)]
+pub struct Database {
+    /// D1 binding of the worker
+    inner: Arc<SendWrapper<D1Database>>,
+    /// Writer of the latency data points; `None` only aggregates them in the isolate
+    metrics: Option<MetricsRecorder>,
+    /// Milliseconds after which a query is logged as slow
+    slow_query_ms: i64,
+    /// Faults injected into every query; `None` outside of load tests
+    chaos: Option<ChaosSettings>,
+    /// Prepared statements of the request, keyed by their SQL
+    statements: Arc<SendWrapper<RefCell<HashMap<String, D1PreparedStatement>>>>,
+}
+
+impl Database {
+    /// Wraps the D1 binding of the worker.
+    ///
+    /// # Arguments
+    ///
+    /// - `database` -> D1 binding obtained from the `Env`
+    pub fn new(database: D1Database) -> Self {
+        Database {
+            inner: Arc::new(SendWrapper(database)),
+            metrics: None,
+            slow_query_ms: i64::MAX,
+            chaos: None,
+            statements: Arc::new(SendWrapper(RefCell::new(HashMap::new()))),
+        }
+    }
+
+    /// Writes the latency of every query to Analytics Engine and logs the slow ones.
+    ///
+    /// # Arguments
+    ///
+    /// - `metrics` -> Recorder of the data points; `None` if the dataset isn't bound
+    /// - `slow_query_ms` -> Milliseconds after which a query is logged as slow
+    pub fn with_metrics(mut self, metrics: Option<MetricsRecorder>, slow_query_ms: i64) -> Self {
+        self.metrics = metrics;
+        self.slow_query_ms = slow_query_ms;
+        self
+    }
+
+    /// Injects artificial latency and failures into every query.
+    ///
+    /// # Arguments
+    ///
+    /// - `chaos` -> Faults to inject; inactive settings leave the queries alone
+    pub fn with_chaos(mut self, chaos: ChaosSettings) -> Self {
+        self.chaos = Some(chaos).filter(ChaosSettings::is_active);
+        self
+    }
+
+    /// Prepares a statement whose execution is timed.
+    ///
+    /// A statement is only prepared once per request; later calls with the same SQL reuse it.
+    /// Binding creates a new statement, so the cached one never carries the values of a call.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` -> Name of the query in the metrics, `<repository>.<method>`
+    /// - `sql` -> SQL of the statement
+    pub fn query(&self, name: &'static str, sql: impl Into<String>) -> Statement<'_> {
+        let sql = sql.into();
+        let mut statements = self.statements.borrow_mut();
+
+        let statement = match statements.get(&sql) {
+            Some(statement) => statement.clone(),
+            None => {
+                let statement = self.inner.prepare(sql.as_str());
+                if statements.len() < MAX_CACHED_STATEMENTS {
+                    statements.insert(sql, statement.clone());
+                }
+                statement
+            }
+        };
+
+        Statement {
+            database: self,
+            name,
+            statement,
+        }
+    }
+
+    /// Executes statements in a single transaction; if one fails, none of them is applied.
+    ///
+    /// The batch is timed as a whole under `name`.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` -> Name of the batch in the metrics, `<repository>.<method>`
+    /// - `statements` -> Bound statements, executed in order
+    pub async fn batch(
+        &self,
+        name: &'static str,
+        statements: Vec<Statement<'_>>,
+    ) -> worker::Result<Vec<D1Result>> {
+        let statements = statements
+            .into_iter()
+            .map(|statement| statement.statement)
+            .collect();
+
+        self.timed(name, self.inner.batch(statements)).await
+    }
+
+    // ----- utility functions of the 'Database' struct -----
+
+    /// Runs a query and records how long D1 took to answer.
+    ///
+    /// Injected faults are applied first, so they show up in the metrics like real ones.
+    async fn timed<T>(
+        &self,
+        name: &'static str,
+        query: impl Future<Output = worker::Result<T>>,
+    ) -> worker::Result<T> {
+        let started_at = server_time();
+        let result = match self.chaos {
+            Some(chaos) => match chaos.inject(name).await {
+                Ok(()) => query.await,
+                Err(err) => Err(err),
+            },
+            None => query.await,
+        };
+        let latency_ms = (server_time() - started_at).num_milliseconds();
+
+        record_query_latency(name, latency_ms as f64);
+        if let Some(metrics) = &self.metrics {
+            metrics.record(Metric::QueryCompleted {
+                query: name,
+                latency_ms: latency_ms as f64,
+                failed: result.is_err(),
+            });
+        }
+        if latency_ms >= self.slow_query_ms {
+            warn!("Slow query '{name}' took {latency_ms} ms");
+        }
+
+        result
+    }
+}
+
+/// Prepared statement of a named query.
+///
+/// Mirrors the methods of `D1PreparedStatement` the repositories use; the executing ones are timed.
+pub struct Statement<'a> {
+    /// Database the statement was prepared on
+    database: &'a Database,
+    /// Name of the query in the metrics
+    name: &'static str,
+    /// The prepared statement
+    statement: D1PreparedStatement,
+}
+
+impl Statement<'_> {
+    /// Binds the parameters of the statement.
+    pub fn bind(self, values: &[JsValue]) -> worker::Result<Self> {
+        Ok(Statement {
+            statement: self.statement.bind(values)?,
+            ..self
+        })
+    }
+
+    /// Returns the first row of the results, `None` if there is none.
+    pub async fn first<T>(&self, col_name: Option<&str>) -> worker::Result<Option<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.database
+            .timed(self.name, self.statement.first(col_name))
+            .await
+    }
+
+    /// Executes the statement and returns all rows.
+    pub async fn all(&self) -> worker::Result<D1Result> {
+        self.database.timed(self.name, self.statement.all()).await
+    }
+
+    /// Executes the statement and only returns the metadata.
+    pub async fn run(&self) -> worker::Result<D1Result> {
+        self.database.timed(self.name, self.statement.run()).await
+    }
+}
@@ -0,0 +1,135 @@
+use log::warn;
+use serde_json::Value;
+use worker::{
+    kv::KvStore,
+    send::{SendFuture, SendWrapper},
+    Env,
+};
+
+use crate::jobs::execution_context::ExecutionContext;
+
+// constants
+/// Name of the KV namespace caching the rows of the `games` table.
+pub const GAME_CACHE_KV_BINDING: &str = "GAME_CACHE";
+
+/// Seconds a cached game row stays valid.
+///
+/// 60 seconds is the shortest expiration KV accepts.
+pub const GAME_CACHE_TTL_SECONDS: u64 = 60;
+
+/// Prefix of the keys of cached games.
+const GAME_CACHE_KEY_PREFIX: &str = "game:";
+
+/// Read-through cache for rows of the `games` table.
+///
+/// Stores the raw row as it was returned by D1, so a cached entry is deserialized exactly like a
+/// fresh one.
+///
+/// Every failing KV operation is only logged; the repository then falls back to D1.
+///
+/// KV is eventually consistent across locations. Entries are deleted on every mutation, the
+/// short TTL bounds how long another location may still see an outdated row.
+#[derive(Clone)]
+pub struct GameCache {
+    /// KV namespace holding the cached rows.
+    store: SendWrapper<KvStore>,
+    /// Defers filling the cache until after the response.
+    execution: ExecutionContext,
+}
+
+impl GameCache {
+    /// Creates the cache from the KV namespace of the worker.
+    ///
+    /// # Arguments
+    ///
+    /// - `env` -> Environment of the worker
+    /// - `execution` -> Context the writes of missed rows are deferred to
+    ///
+    /// # Returns
+    ///
+    /// `None` if the namespace isn't bound, so the worker keeps working without a cache.
+    pub fn from_env(env: &Env, execution: ExecutionContext) -> Option<Self> {
+        match env.kv(GAME_CACHE_KV_BINDING) {
+            Ok(store) => Some(GameCache {
+                store: SendWrapper(store),
+                execution,
+            }),
+            Err(err) => {
+                warn!("Game cache namespace not found: {err}");
+                None
+            }
+        }
+    }
+
+    /// Gets the cached row of a game.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    pub async fn get(&self, game_id: &str) -> Option<Value> {
+        let store = self.store.clone();
+        let key = cache_key(game_id);
+
+        match SendFuture::new(async move { store.get(&key).json::<Value>().await }).await {
+            Ok(row) => row,
+            Err(err) => {
+                warn!("Cached game {game_id} couldn't be read: {err}");
+                None
+            }
+        }
+    }
+
+    /// Caches the row of a game for `GAME_CACHE_TTL_SECONDS`.
+    ///
+    /// The row is written after the response was sent; the caller already has it.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    /// - `row` -> Row of the `games` table
+    pub async fn put(&self, game_id: &str, row: &Value) {
+        let store = self.store.clone();
+        let key = cache_key(game_id);
+        let game_id = game_id.to_string();
+        let row = row.clone();
+
+        self.execution
+            .defer(async move {
+                let result = SendFuture::new(async move {
+                    store
+                        .put(&key, row)?
+                        .expiration_ttl(GAME_CACHE_TTL_SECONDS)
+                        .execute()
+                        .await
+                })
+                .await;
+
+                if let Err(err) = result {
+                    warn!("Game {game_id} couldn't be cached: {err}");
+                }
+            })
+            .await;
+    }
+
+    /// Removes the cached row of a game after it changed.
+    ///
+    /// Awaited before the response, so the next request of the client doesn't read the outdated
+    /// row.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    pub async fn invalidate(&self, game_id: &str) {
+        let store = self.store.clone();
+        let key = cache_key(game_id);
+
+        if let Err(err) = SendFuture::new(async move { store.delete(&key).await }).await {
+            warn!("Cached game {game_id} couldn't be removed: {err}");
+        }
+    }
+}
+
+/// Builds the KV key of a game.
+fn cache_key(game_id: &str) -> String {
+    format!("{GAME_CACHE_KEY_PREFIX}{game_id}")
+}
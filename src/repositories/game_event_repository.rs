@@ -0,0 +1,355 @@
+use axum::http::StatusCode;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::{
+        abandonment::Abandonment,
+        game_event::{GameEvent, StoredGameEvent, GAME_ABANDONED_EVENT, ROUND_SUMMARY_EVENT},
+        round_summary::RoundSummary,
+    },
+    utils::d1_conversions::optional_binding,
+};
+
+/// A database repository for interacting with the `game_events` table.
+///
+/// The table is the log all event streams are read from.
+#[derive(Clone)]
+pub struct GameEventRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl GameEventRepository {
+    /// Returns a fresh instance of `GameEventRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        GameEventRepository { db }
+    }
+
+    /// Appends an event to the log of its game.
+    ///
+    /// A short-lived event gets an expiry, after which it isn't delivered anymore.
+    ///
+    /// # Arguments
+    ///
+    /// - `event` -> The event to be published.
+    /// - `recipient_player_id` -> Only this player receives the event; everyone if `None`.
+    ///
+    /// # Returns the stored event or an error if the insertion fails.
+    pub async fn add_event(
+        &self,
+        event: &GameEvent,
+        recipient_player_id: Option<&str>,
+    ) -> Result<StoredGameEvent, DatabaseQueryError<StoredGameEvent>> {
+        let payload = serde_json::to_string(event).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })?;
+
+        let query_result = self
+            .db
+            .query(
+                "game_event.add_event",
+                "INSERT INTO game_events (game_id, recipient_player_id, event_type, payload, expires_at) 
+                    VALUES (?1, ?2, ?3, ?4, datetime('now', ?5)) RETURNING *;",
+            )
+            .bind(&[
+                JsValue::from(event.game_id()),
+                optional_binding(recipient_player_id),
+                JsValue::from(event.event_type()),
+                JsValue::from(payload),
+                optional_binding(
+                    event
+                        .ttl_seconds()
+                        .map(|seconds| format!("+{seconds} seconds")),
+                ),
+            ])
+            .unwrap()
+            .first::<StoredGameEvent>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(stored_event)) => Ok(stored_event),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to store the game event".to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the events of a game published after a sequence number, oldest first.
+    ///
+    /// Expired events are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    /// - `viewer_player_id` -> Player reading the events; `None` for a spectator, who only
+    ///   receives the events meant for everyone.
+    /// - `after_sequence` -> Sequence number of the last event the viewer received.
+    /// - `limit` -> Maximum number of events to return.
+    ///
+    /// # Returns the events or an error if the query fails.
+    pub async fn get_events_since(
+        &self,
+        game_id: &str,
+        viewer_player_id: Option<&str>,
+        after_sequence: usize,
+        limit: usize,
+    ) -> Result<Vec<StoredGameEvent>, DatabaseQueryError<StoredGameEvent>> {
+        let query_result = self
+            .db
+            .query(
+                "game_event.get_events_since",
+                "SELECT * FROM game_events 
+                    WHERE game_id = ?1 AND sequence > ?2 
+                    AND (recipient_player_id IS NULL OR recipient_player_id = ?3) 
+                    AND (expires_at IS NULL OR expires_at > datetime('now')) 
+                    ORDER BY sequence ASC LIMIT ?4;",
+            )
+            .bind(&[
+                JsValue::from(game_id),
+                JsValue::from(after_sequence),
+                optional_binding(viewer_player_id),
+                JsValue::from(limit),
+            ])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_events) => match fetched_events.results::<StoredGameEvent>() {
+                Ok(events) => Ok(events),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Returns the sequence number of the latest event of a game.
+    ///
+    /// Events meant for other players count as well; a client continuing after it can't miss an
+    /// event, since only later ones are read.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    ///
+    /// # Returns the sequence number, `0` if nothing was published yet, or an error if the query
+    /// fails.
+    pub async fn get_latest_sequence(
+        &self,
+        game_id: &str,
+    ) -> Result<usize, DatabaseQueryError<StoredGameEvent>> {
+        let query_result = self
+            .db
+            .query(
+                "game_event.get_latest_sequence",
+                "SELECT COALESCE(MAX(sequence), 0) AS sequence FROM game_events WHERE game_id = ?1;",
+            )
+            .bind(&[JsValue::from(game_id)])
+            .unwrap()
+            .first::<usize>(Some("sequence"))
+            .await;
+
+        match query_result {
+            Ok(sequence) => Ok(sequence.unwrap_or_default()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Counts the events of a type a player caused in a game within the last seconds.
+    ///
+    /// Used to rate-limit actions which are only recorded as events, like reactions.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    /// - `event_type` -> Name of the counted events.
+    /// - `player_id` -> Player named as `playerId` in the payload of the events.
+    /// - `window_seconds` -> Length of the window, ending now.
+    ///
+    /// # Returns the number of events or an error if the query fails.
+    pub async fn count_recent_events(
+        &self,
+        game_id: &str,
+        event_type: &str,
+        player_id: &str,
+        window_seconds: i64,
+    ) -> Result<usize, DatabaseQueryError<StoredGameEvent>> {
+        let query_result = self
+            .db
+            .query(
+                "game_event.count_recent_events",
+                "SELECT COUNT(*) AS count FROM game_events 
+                    WHERE game_id = ?1 AND event_type = ?2 
+                    AND json_extract(payload, '$.playerId') = ?3 
+                    AND created_at > datetime('now', ?4);",
+            )
+            .bind(&[
+                JsValue::from(game_id),
+                JsValue::from(event_type),
+                JsValue::from(player_id),
+                JsValue::from(format!("-{window_seconds} seconds")),
+            ])
+            .unwrap()
+            .first::<usize>(Some("count"))
+            .await;
+
+        match query_result {
+            Ok(count) => Ok(count.unwrap_or_default()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Deletes the short-lived events of all games which expired.
+    ///
+    /// # Returns the number of deleted events or an error if the query fails.
+    pub async fn delete_expired_events(
+        &self,
+    ) -> Result<usize, DatabaseQueryError<StoredGameEvent>> {
+        let query_result = self
+            .db
+            .query(
+                "game_event.delete_expired_events",
+                "DELETE FROM game_events WHERE expires_at <= datetime('now');",
+            )
+            .run()
+            .await;
+
+        match query_result {
+            Ok(result) => Ok(result
+                .meta()
+                .ok()
+                .flatten()
+                .and_then(|meta| meta.changes)
+                .unwrap_or_default()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the summary of a round from the event log.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    /// - `round_number` -> The round whose summary is read.
+    ///
+    /// # Returns the summary, or an error if the round didn't end yet or the query fails.
+    pub async fn get_round_summary(
+        &self,
+        game_id: &str,
+        round_number: usize,
+    ) -> Result<RoundSummary, DatabaseQueryError<StoredGameEvent>> {
+        let query_result = self
+            .db
+            .query(
+                "game_event.get_round_summary",
+                "SELECT * FROM game_events 
+                    WHERE game_id = ?1 AND event_type = ?2 AND recipient_player_id IS NULL 
+                    AND json_extract(payload, '$.roundNumber') = ?3 
+                    ORDER BY sequence DESC LIMIT 1;",
+            )
+            .bind(&[
+                JsValue::from(game_id),
+                JsValue::from(ROUND_SUMMARY_EVENT),
+                JsValue::from(round_number),
+            ])
+            .unwrap()
+            .first::<StoredGameEvent>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(event)) => {
+                serde_json::from_str::<RoundSummary>(&event.payload).map_err(|err| {
+                    DatabaseQueryError::new(
+                        err.to_string(),
+                        None,
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    )
+                })
+            }
+            Ok(None) => Err(DatabaseQueryError::new(
+                format!("Round {round_number} of the game {game_id} didn't end yet!"),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the latest abandonment of a game from the event log.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Identifier of the game.
+    ///
+    /// # Returns the abandonment, or an error if the game was never abandoned or the query fails.
+    pub async fn get_abandonment(
+        &self,
+        game_id: &str,
+    ) -> Result<Abandonment, DatabaseQueryError<StoredGameEvent>> {
+        let query_result = self
+            .db
+            .query(
+                "game_event.get_abandonment",
+                "SELECT * FROM game_events 
+                    WHERE game_id = ?1 AND event_type = ?2 AND recipient_player_id IS NULL 
+                    ORDER BY sequence DESC LIMIT 1;",
+            )
+            .bind(&[JsValue::from(game_id), JsValue::from(GAME_ABANDONED_EVENT)])
+            .unwrap()
+            .first::<StoredGameEvent>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(event)) => serde_json::from_str::<Abandonment>(&event.payload).map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            }),
+            Ok(None) => Err(DatabaseQueryError::new(
+                format!("The game {game_id} wasn't abandoned!"),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
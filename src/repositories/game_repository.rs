@@ -1,16 +1,39 @@
 use crate::{
+    durable::game_room::is_archived_state,
+    enums::{game_mode::GameMode, game_state::GameState, visibility::Visibility},
     errors::database_query_error::DatabaseQueryError,
-    repositories::{claim_repository::ClaimsRepository, player_repository::PlayerRepository},
+    repositories::{
+        claim_repository::ClaimsRepository, database::Database, game_cache::GameCache,
+        game_rooms::GameRooms, player_repository::PlayerRepository, query_builder::QueryBuilder,
+    },
     types::{
-        chat::Chat,
+        card::Card,
+        chat::{Chat, ChatMessage},
         claim::Claim,
         game::{Game, UpdateGameDTO},
+        includes::Includes,
+        lobby::{LobbyFilter, LobbyGame},
+        pile_summary::{PileRow, PileSummary},
         player::Player,
     },
+    utils::{d1_conversions::optional_binding, time::server_time},
 };
 use axum::{http::StatusCode, Json};
+use log::warn;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
 use wasm_bindgen::JsValue;
-use worker::D1Database;
+use worker::D1Result;
+
+// constants
+/// Selects the summary of games for the lobby.
+///
+/// The `game_summaries` table is kept in sync with the games and the players by triggers of the
+/// database. Needs to be completed with a `WHERE` clause by the callers.
+const LOBBY_GAME_SELECT: &str = "SELECT game_id AS id, max_players, visibility, state, started_at,
+        host_name, player_count, language, region
+    FROM game_summaries";
 
 /// Represents a repository for managing game data in the D1 database.
 ///
@@ -19,25 +42,71 @@ use worker::D1Database;
 ///
 /// # Properties
 ///
-/// `db`: An instance of `D1Database` that provides access to the D1 database.
+/// `db`: Shared `Database` handle that provides access to the D1 database.
+/// `cache`: Optional KV cache in front of `get_game_by_id`.
+/// `rooms`: Optional Durable Objects holding the live state of the games.
 #[derive(Clone)]
-pub struct GameRepository<'a> {
+pub struct GameRepository {
     /// The D1 database instance used for accessing game data.
-    db: &'a D1Database,
+    db: Database,
+    /// Read-through cache of single games; `None` reads every game from D1.
+    cache: Option<GameCache>,
+    /// Rooms holding the authoritative live state in the `durable-object` storage mode.
+    rooms: Option<GameRooms>,
 }
 
-impl<'a> GameRepository<'a> {
+impl GameRepository {
     /// Creates a new `GameRepository` instance with the provided D1 database.
     ///
     /// # Arguments
     ///
-    /// * `db` - An instance of `D1Database` to be used for database operations.
+    /// * `db` - Shared `Database` handle to be used for database operations.
     ///
     /// # Returns
     ///
     /// A new `GameRepository` instance.
-    pub fn new(db: &'a D1Database) -> Self {
-        GameRepository { db }
+    pub fn new(db: Database) -> Self {
+        GameRepository {
+            db,
+            cache: None,
+            rooms: None,
+        }
+    }
+
+    /// Puts a read-through cache in front of `get_game_by_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - KV cache of the game rows; `None` keeps reading from D1 only.
+    pub fn with_cache(mut self, cache: Option<GameCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Keeps the live state of the games in their `GameRoom` Durable Objects.
+    ///
+    /// D1 is still written, so the lobby can list the games and finished games are archived.
+    ///
+    /// # Arguments
+    ///
+    /// * `rooms` - Client of the rooms; `None` keeps D1 as the only storage.
+    pub fn with_rooms(mut self, rooms: Option<GameRooms>) -> Self {
+        self.rooms = rooms;
+        self
+    }
+
+    /// Removes the cached entry of a game.
+    ///
+    /// Needs to be called after every query changing a row of the `games` table outside of this
+    /// repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - ID of the changed game.
+    pub async fn invalidate_cached_game(&self, game_id: &str) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(game_id).await;
+        }
     }
 
     // pub fn db(&self) -> &D1Database {
@@ -56,17 +125,32 @@ impl<'a> GameRepository<'a> {
     pub async fn add_game(&self, game: Game) -> Result<Game, DatabaseQueryError<Game>> {
         let added_game = self
             .db
-            .prepare(
-                "INSERT INTO games (id, started_at, round_number, state, which_players_turn, card_to_play) 
-                    VALUES (1?, 2?, 3?, 4?, 5?, 6?) RETURNING *;",
+            .query(
+                "game.add_game",
+                "INSERT INTO games (id, started_at, round_number, state, which_player_turn, card_to_play, max_players, host_player_id, visibility, join_code, daily_date, daily_seed, turn_seconds, undo_seconds, deck_count, hand_size, language, region, deck_theme, randomize_seats) 
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20) RETURNING *;",
             )
             .bind(&[
                 JsValue::from(game.id),
-                JsValue::from(game.started_at),
+                JsValue::from(game.started_at.to_rfc3339()),
                 JsValue::from(game.round_number),
-                JsValue::from(game.state.index()),
+                JsValue::from(game.state.as_str()),
                 JsValue::from(game.which_player_turn),
-                JsValue::from(game.card_to_play.index()),
+                JsValue::from(game.card_to_play.as_str()),
+                JsValue::from(game.settings.max_players),
+                JsValue::from(game.host_player_id),
+                JsValue::from(game.settings.visibility.as_str()),
+                optional_binding(game.join_code),
+                optional_binding(game.daily_date.map(|date| date.to_string())),
+                optional_binding(game.daily_seed),
+                JsValue::from(game.settings.turn_seconds),
+                JsValue::from(game.settings.undo_seconds),
+                JsValue::from(game.settings.deck_count),
+                optional_binding(game.settings.hand_size),
+                optional_binding(game.settings.language),
+                optional_binding(game.settings.region),
+                JsValue::from(game.settings.deck_theme.as_str()),
+                JsValue::from(i32::from(game.settings.randomize_seats)),
             ]).unwrap().first::<Game>(None).await;
 
         match added_game {
@@ -98,24 +182,52 @@ impl<'a> GameRepository<'a> {
     pub async fn update_game(
         &self,
         game_data: UpdateGameDTO,
-        player_repo: &PlayerRepository<'_>
+        player_repo: &PlayerRepository
     ) -> Result<Game, DatabaseQueryError<UpdateGameDTO>> {
+        // the room or the revision column rejects the update if another request changed the game in
+        // the meantime
+        let live_game = match &self.rooms {
+            Some(rooms) => match rooms.apply(&game_data).await? {
+                Some(live_game) => Some(live_game),
+                None => {
+                    self.get_game_by_id(&game_data.id).await.map_err(|err| {
+                        DatabaseQueryError::new(err.message, None, err.status_code)
+                    })?;
+                    rooms.apply(&game_data).await?
+                }
+            },
+            None => None,
+        };
+
         let (query, bindings) = self.get_update_query_string_and_bindings(&game_data);
 
         let mut query_result = self
             .db
-            .prepare(&query)
+            .query("game.update_game", &query)
             .bind(&bindings)
             .unwrap()
             .first::<Game>(None)
             .await;
 
         // TODO: Handle relations like claims, chat with other queries
-        
+
+        self.invalidate_cached_game(&game_data.id).await;
+
         match query_result {
             Ok(game) => match game {
                 Some(mut updated_game) => {
-                    updated_game.players = match self.update_players_in_game(&game_data, &player_repo).await {
+                    // only sync the players if a new list was passed
+                    let players = match game_data.players {
+                        Some(_) => self.update_players_in_game(&game_data, &player_repo).await,
+                        None => player_repo
+                            .get_all_players(Some(game_data.id.clone()))
+                            .await
+                            .map_err(|err| {
+                                DatabaseQueryError::new(err.message, None, err.status_code)
+                            }),
+                    };
+
+                    updated_game.players = match players {
                         Ok(players) => players,
                         Err(err) => return Err(DatabaseQueryError::new(err.message, match err.received_data {
                             None => None,
@@ -123,8 +235,26 @@ impl<'a> GameRepository<'a> {
                         }, err.status_code))
                     };  
 
+                    if let Some(live_game) = &live_game {
+                        updated_game.revision = live_game.revision;
+                    }
+                    // finished games are archived in D1 only
+                    if let Some(rooms) = &self.rooms {
+                        if is_archived_state(&updated_game.state) {
+                            rooms.archive(&updated_game.id).await;
+                        }
+                    }
+
                     return Ok(updated_game);
                 },
+                None if self.rooms.is_none() && game_data.expected_revision.is_some() => {
+                    Err(DatabaseQueryError::new(
+                        "The game changed in the meantime! Please reload it and try again."
+                            .to_string(),
+                        None,
+                        axum::http::StatusCode::CONFLICT,
+                    ))
+                }
                 None => Err(DatabaseQueryError::new(
                     "Failed to update game in the database".to_string(),
                     None,
@@ -153,17 +283,62 @@ impl<'a> GameRepository<'a> {
         &self,
         game_id: &str,
     ) -> Result<Game, DatabaseQueryError<Game>> {
+        let Some(rooms) = &self.rooms else {
+            return self.get_stored_game(game_id).await;
+        };
+
+        if let Some(game) = rooms.get(game_id).await? {
+            return Ok(game);
+        }
+
+        // the room is empty -> hand the game over unless it is already archived
+        let game = self.get_stored_game(game_id).await?;
+        if is_archived_state(&game.state) {
+            return Ok(game);
+        }
+
+        rooms.seed(&game).await
+    }
+
+    /// Retrieves the row of a game from the KV cache or the D1 database.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - A string slice representing the ID of the game to be retrieved.
+    async fn get_stored_game(&self, game_id: &str) -> Result<Game, DatabaseQueryError<Game>> {
+        if let Some(cache) = &self.cache {
+            if let Some(row) = cache.get(game_id).await {
+                match serde_json::from_value::<Game>(row) {
+                    Ok(game) => return Ok(game),
+                    // an outdated entry is replaced by the fresh row below
+                    Err(err) => warn!("Cached game {game_id} is invalid: {err}"),
+                }
+            }
+        }
+
         let query_result = self
             .db
-            .prepare("SELECT * FROM games WHERE id = ?;")
+            .query("game.get_stored_game", "SELECT * FROM games WHERE id = ?;")
             .bind(&[JsValue::from(game_id)])
             .unwrap()
-            .first::<Game>(None)
+            .first::<Value>(None)
             .await;
 
         match query_result {
-            Ok(game) => match game {
-                Some(game) => Ok(game),
+            Ok(row) => match row {
+                Some(row) => {
+                    if let Some(cache) = &self.cache {
+                        cache.put(game_id, &row).await;
+                    }
+
+                    serde_json::from_value::<Game>(row).map_err(|err| {
+                        DatabaseQueryError::new(
+                            err.to_string(),
+                            None,
+                            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        )
+                    })
+                }
                 None => Err(DatabaseQueryError::new(
                     "Game not found".to_string(),
                     None,
@@ -178,87 +353,417 @@ impl<'a> GameRepository<'a> {
         }
     }
 
-    /// Retrieves all games from the D1 database.
+    /// Retrieves a game together with the parts a `GameView` shows in a single round trip.
+    ///
+    /// The row of the game and the requested parts - its players with their hands, the claims of
+    /// the current round with their cards and the chat with its messages - are read in one batch
+    /// instead of one query per table. The claims are selected with the round number stored in the
+    /// same batch, so they always belong to the returned round. The summary of the stack is always
+    /// read, even without the claims. In the `durable-object` storage
+    /// mode the live state of the room replaces the stored row.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - ID of the game to be retrieved.
+    /// * `includes` - Parts hydrated next to the row; the others stay empty.
+    ///
+    /// # Returns
+    ///
+    /// The hydrated game, or a `DatabaseQueryError` if the game doesn't exist or the batch fails.
+    pub async fn get_snapshot(
+        &self,
+        game_id: &str,
+        includes: Includes,
+    ) -> Result<Game, DatabaseQueryError<Game>> {
+        // the statements of a batch are only timed as a whole
+        let bind = |sql: &str| {
+            self.db
+                .query("game.get_snapshot", sql)
+                .bind(&[JsValue::from(game_id)])
+                .unwrap()
+        };
+        let mut statements = vec![
+            bind("SELECT * FROM games WHERE id = ?1;"),
+            // without a declaration the last claim announced the card to play and its cards
+            bind(
+                "SELECT
+                    (SELECT COALESCE(SUM(number_of_cards), 0) FROM claims WHERE game_id = ?1
+                        AND round_number = games.round_number) AS pile_size,
+                    last_claim.created_by AS last_claim_by,
+                    COALESCE(last_claim.declared_card_type, games.card_to_play)
+                        AS last_declared_card_type,
+                    COALESCE(last_claim.declared_count, last_claim.number_of_cards)
+                        AS last_declared_count
+                FROM games
+                LEFT JOIN (SELECT * FROM claims WHERE game_id = ?1
+                    AND round_number = (SELECT round_number FROM games WHERE id = ?1)
+                    ORDER BY created_at DESC LIMIT 1) AS last_claim ON 1 = 1
+                WHERE games.id = ?1;",
+            ),
+        ];
+        if includes.contains(Includes::PLAYERS) {
+            statements.push(bind(
+                "SELECT * FROM players WHERE game_id = ?1 ORDER BY seat_index, joined_at;",
+            ));
+            statements.push(bind(
+                "SELECT * FROM cards WHERE claim_id IS NULL
+                    AND player_id IN (SELECT id FROM players WHERE game_id = ?1);",
+            ));
+        }
+        if includes.contains(Includes::CLAIMS) {
+            statements.push(bind(
+                "SELECT * FROM claims WHERE game_id = ?1
+                    AND round_number = (SELECT round_number FROM games WHERE id = ?1)
+                    ORDER BY created_at ASC;",
+            ));
+            statements.push(bind(
+                "SELECT * FROM cards WHERE claim_id IN (SELECT id FROM claims WHERE game_id = ?1
+                    AND round_number = (SELECT round_number FROM games WHERE id = ?1));",
+            ));
+        }
+        if includes.contains(Includes::CHAT) {
+            statements.push(bind("SELECT * FROM chats WHERE game_id = ?1;"));
+            statements.push(bind(
+                "SELECT * FROM chat_messages
+                    WHERE chat_id IN (SELECT id FROM chats WHERE game_id = ?1)
+                    AND recipient_player_id IS NULL
+                    ORDER BY sent_at ASC;",
+            ));
+        }
+        let expected_results = statements.len();
+
+        let results = self
+            .db
+            .batch("game.get_snapshot", statements)
+            .await
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })?;
+        if results.len() != expected_results {
+            return Err(DatabaseQueryError::new(
+                "The snapshot batch returned an unexpected number of results".to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+        // the results are taken in the order the statements were pushed
+        let mut results = results.into_iter();
+        let mut next = || results.next().expect("the number of results was checked");
+
+        let mut game = match rows::<Value>(&next())?.into_iter().next() {
+            Some(row) => serde_json::from_value::<Game>(row).map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })?,
+            None => {
+                return Err(DatabaseQueryError::new(
+                    "Game not found".to_string(),
+                    None,
+                    StatusCode::NOT_FOUND,
+                ))
+            }
+        };
+        if self.rooms.is_some() {
+            game = self.get_game_by_id(game_id).await?;
+        }
+        game.pile = rows::<PileRow>(&next())?
+            .into_iter()
+            .next()
+            .map(PileSummary::from);
+
+        if includes.contains(Includes::PLAYERS) {
+            game.players = rows::<Player>(&next())?;
+            let mut hands = cards_by(rows::<Value>(&next())?, "player_id")?;
+            for player in game.players.iter_mut() {
+                player.assigned_cards = hands.remove(&player.id).unwrap_or_default();
+            }
+        }
+
+        if includes.contains(Includes::CLAIMS) {
+            game.claims = rows::<Claim>(&next())?;
+            let mut placed_cards = cards_by(rows::<Value>(&next())?, "claim_id")?;
+            for claim in game.claims.iter_mut() {
+                claim.cards = placed_cards.remove(&claim.id).unwrap_or_default();
+            }
+        }
+
+        if includes.contains(Includes::CHAT) {
+            let chat = rows::<Chat>(&next())?.into_iter().next();
+            let messages = rows::<ChatMessage>(&next())?;
+            if let Some(mut chat) = chat {
+                chat.messages = messages;
+                game.chat = chat;
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Retrieves a page of all stored games, ordered by their ID.
+    ///
+    /// Every game is hydrated like `get_snapshot`, so a page is read with one round trip per game;
+    /// large listings are streamed page by page instead of being loaded at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - ID of the last game of the previous page; `None` for the first page.
+    /// * `limit` - Number of games of the page.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the games of the page, or a `DatabaseQueryError` if an error occurs.
+    pub async fn get_all_games(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Game>, DatabaseQueryError<Game>> {
+        let query_result = self
+            .db
+            .query(
+                "game.get_all_games",
+                "SELECT id FROM games WHERE ?1 IS NULL OR id > ?1 ORDER BY id ASC LIMIT ?2;",
+            )
+            .bind(&[
+                optional_binding(after.map(str::to_string)),
+                JsValue::from(limit),
+            ])
+            .unwrap()
+            .all()
+            .await;
+
+        let game_ids = match query_result {
+            Ok(collected_ids) => collected_ids.results::<Value>().map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })?,
+            Err(err) => {
+                return Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ))
+            }
+        };
+
+        let mut games = Vec::with_capacity(game_ids.len());
+        for game_id in game_ids
+            .iter()
+            .filter_map(|row| row.get("id").and_then(Value::as_str))
+        {
+            games.push(self.get_snapshot(game_id, Includes::all()).await?);
+        }
+
+        Ok(games)
+    }
+
+    /// Retrieves the public casual games a quick-play player could join.
+    ///
+    /// Only games waiting for players with a free seat are returned. The games closest to being
+    /// full come first, so they start sooner; among them the oldest one.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Highest number of games returned.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the lobby summaries of the games, best candidate first.
+    pub async fn get_quick_play_games(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<LobbyGame>, DatabaseQueryError<Game>> {
+        let (query, bindings) = QueryBuilder::new(LOBBY_GAME_SELECT)
+            .condition("visibility = ?", Visibility::Public.as_str())
+            .condition("state = ?", GameState::WaitingForPlayers.as_str())
+            .fixed_condition("player_count < max_players")
+            .fixed_condition(mode_condition(GameMode::Casual))
+            .order_by("max_players - player_count, started_at")
+            .limit(limit)
+            .build();
+
+        self.db
+            .query("game.get_quick_play_games", query)
+            .bind(&bindings)
+            .unwrap()
+            .all()
+            .await
+            .and_then(|result| result.results::<LobbyGame>())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+
+    /// Retrieves the public games matching the filters of the lobby.
+    ///
+    /// Private games are never listed; they can only be found with their join code. All filters
+    /// are compiled into a single query, so the lobby only receives the games it shows.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Filters chosen by the client; games waiting for players if none is set.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the lobby summaries of the games, newest first.
+    pub async fn get_lobby_games(
+        &self,
+        filter: &LobbyFilter,
+    ) -> Result<Vec<LobbyGame>, DatabaseQueryError<Game>> {
+        let mut query = QueryBuilder::new(LOBBY_GAME_SELECT)
+            .condition("visibility = ?", Visibility::Public.as_str())
+            .condition("state = ?", filter.state().as_str())
+            .optional_condition("language = ?", filter.language())
+            .optional_condition("region = ?", filter.region())
+            .optional_condition(
+                "started_at > ?",
+                filter
+                    .created_after
+                    .map(|created_after| created_after.to_rfc3339()),
+            )
+            .order_by("started_at DESC");
+
+        if let Some(mode) = filter.mode {
+            query = query.fixed_condition(mode_condition(mode));
+        }
+        match filter.has_open_seats {
+            Some(true) => query = query.fixed_condition("player_count < max_players"),
+            Some(false) => query = query.fixed_condition("player_count >= max_players"),
+            None => {}
+        }
+        let (query, bindings) = query.build();
+
+        let query_result = self
+            .db
+            .query("game.get_lobby_games", query)
+            .bind(&bindings)
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(collected_games) => match collected_games.results::<LobbyGame>() {
+                Ok(games) => Ok(games),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Retrieves all games which haven't ended or been abandoned yet.
+    ///
+    /// Only the rows of the `games` table are fetched; players, claims and the chat need to be
+    /// loaded separately.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a vector of `Game` instances if successful, or a `DatabaseQueryError`
-    /// if an error occurs.
-    pub async fn get_all_games(&self) -> Result<Vec<Game>, DatabaseQueryError<Game>> {
+    /// A `Result` containing the active games, oldest first.
+    pub async fn get_active_games(&self) -> Result<Vec<Game>, DatabaseQueryError<Game>> {
         let query_result = self
             .db
-            .prepare("SELECT * FROM games;")
-            .bind(&[])
+            .query(
+                "game.get_active_games",
+                "SELECT * FROM games WHERE state NOT IN (?1, ?2) ORDER BY started_at ASC;",
+            )
+            .bind(&[
+                JsValue::from(GameState::Ended.as_str()),
+                JsValue::from(GameState::Abandoned.as_str()),
+            ])
             .unwrap()
             .all()
             .await;
 
         match query_result {
-            Ok(collected_games) => {
-                let mut output: Vec<Game> = collected_games.results::<Game>().unwrap();
+            Ok(collected_games) => match collected_games.results::<Game>() {
+                Ok(games) => Ok(games),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
 
-                if output.is_empty() {
-                    Err(DatabaseQueryError::new(
-                        "No games found".to_string(),
-                        None,
-                        axum::http::StatusCode::NOT_FOUND,
-                    ))
-                } else {
-                    // TODO: Replace the database query with repository functions for each
-                    // structure
-
-                    // Retrieve all other necessary game data (players, claims, chat) here
-                    output.iter_mut().map(async |game| {
-                        // players
-                        let players = self
-                            .db
-                            .prepare("SELECT * FROM players WHERE game_id = ?;")
-                            .bind(&[JsValue::from(game.id.clone())])
-                            .unwrap()
-                            .all()
-                            .await
-                            .unwrap()
-                            .results::<Player>()
-                            .unwrap();
-
-                        // Assign players to the game
-                        game.players = players;
-
-                        // claims
-                        let claims = self
-                            .db
-                            .prepare("SELECT * FROM claims WHERE game_id = ?;")
-                            .bind(&[JsValue::from(game.id.clone())])
-                            .unwrap()
-                            .all()
-                            .await
-                            .unwrap()
-                            .results::<Claim>()
-                            .unwrap();
-
-                        // Assign claims to the game
-                        game.claims = claims;
-
-                        // Retrieve chat for the game
-                        let chat = self
-                            .db
-                            .prepare("SELECT * FROM chats WHERE game_id = ?;")
-                            .bind(&[JsValue::from(game.id.clone())])
-                            .unwrap()
-                            .first::<Chat>(None)
-                            .await
-                            .unwrap();
-                        // Assign chat to the game
-                        game.chat = chat.unwrap_or_default();
-                    });
+    /// Counts the games which haven't ended or been abandoned yet.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of active games.
+    pub async fn count_active_games(&self) -> Result<usize, DatabaseQueryError<Game>> {
+        let query_result = self
+            .db
+            .query(
+                "game.count_active_games",
+                "SELECT COUNT(*) AS count FROM games WHERE state NOT IN (?1, ?2);",
+            )
+            .bind(&[
+                JsValue::from(GameState::Ended.as_str()),
+                JsValue::from(GameState::Abandoned.as_str()),
+            ])
+            .unwrap()
+            .first::<usize>(Some("count"))
+            .await;
 
-                    Ok(output)
-                }
-            }
+        match query_result {
+            Ok(count) => Ok(count.unwrap_or_default()),
             Err(err) => Err(DatabaseQueryError::new(
                 err.to_string(),
                 None,
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Resolves a join code to the summary of its game.
+    ///
+    /// Works for public and private games.
+    ///
+    /// # Arguments
+    ///
+    /// * `join_code` - Normalized join code typed by a player.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the lobby summary of the game or a `DatabaseQueryError` with status
+    /// code 404 if no game has the code.
+    pub async fn get_lobby_game_by_join_code(
+        &self,
+        join_code: &str,
+    ) -> Result<LobbyGame, DatabaseQueryError<Game>> {
+        let query = format!("{LOBBY_GAME_SELECT} WHERE join_code = ?1;");
+
+        let query_result = self
+            .db
+            .query("game.get_lobby_game_by_join_code", &query)
+            .bind(&[JsValue::from(join_code)])
+            .unwrap()
+            .first::<LobbyGame>(None)
+            .await;
+
+        match query_result {
+            Ok(game) => match game {
+                Some(game) => Ok(game),
+                None => Err(DatabaseQueryError::new(
+                    "No game found for this join code".to_string(),
+                    None,
+                    StatusCode::NOT_FOUND,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
             )),
         }
     }
@@ -275,12 +780,17 @@ impl<'a> GameRepository<'a> {
     pub async fn delete_game(&self, game_id: &str) -> Result<(), DatabaseQueryError<Game>> {
         let query_result = self
             .db
-            .prepare("DELETE FROM games WHERE id = ?;")
+            .query("game.delete_game", "DELETE FROM games WHERE id = ?;")
             .bind(&[JsValue::from(game_id)])
             .unwrap()
             .run()
             .await;
 
+        self.invalidate_cached_game(game_id).await;
+        if let Some(rooms) = &self.rooms {
+            rooms.archive(game_id).await;
+        }
+
         match query_result {
             Ok(_) => Ok(()),
             Err(err) => Err(DatabaseQueryError::new(
@@ -307,10 +817,18 @@ impl<'a> GameRepository<'a> {
         let mut output_query = "UPDATE games SET ".to_string();
         let mut output_bindings = vec![];
 
-        // game state
+        // game state; the pause timestamp is only kept while the game is paused
         if let Some(state) = &game_data.state {
-            output_query.push_str("state = ?, ");
-            output_bindings.push(JsValue::from(state.index()));
+            output_query.push_str("state = ?, paused_at = ?, ");
+            output_bindings.push(JsValue::from(state.as_str()));
+            output_bindings.push(match state {
+                GameState::Paused => JsValue::from(server_time().to_rfc3339()),
+                _ => JsValue::NULL,
+            });
+            // a reclaimed game isn't over anymore
+            if !is_archived_state(state) {
+                output_query.push_str("finished_at = NULL, ");
+            }
         }
 
         // round number
@@ -322,18 +840,77 @@ impl<'a> GameRepository<'a> {
         // card to play
         if let Some(card) = &game_data.card_to_play {
             output_query.push_str("card_to_play = ?, ");
-            output_bindings.push(JsValue::from(card.index()));
+            output_bindings.push(JsValue::from(card.as_str()));
         }
 
-        // which players turn it is
+        // which players turn it is; the countdown of the turn starts over
         if let Some(player) = &game_data.which_player_turn {
-            output_query.push_str("which_player_turn = ?, ");
+            output_query.push_str("which_player_turn = ?, turn_started_at = ?, ");
             output_bindings.push(JsValue::from(player));
+            output_bindings.push(JsValue::from(server_time().to_rfc3339()));
+        } else if let Some(turn_started_at) = &game_data.turn_started_at {
+            output_query.push_str("turn_started_at = ?, ");
+            output_bindings.push(JsValue::from(turn_started_at.to_rfc3339()));
         }
 
-        output_query.truncate(output_query.len() - 2);
-        output_query.push_str(" WHERE id = ? RETURNING *;");
+        // settings
+        if let Some(settings) = &game_data.settings {
+            output_query.push_str("max_players = ?, visibility = ?, turn_seconds = ?, undo_seconds = ?, deck_count = ?, hand_size = ?, language = ?, region = ?, deck_theme = ?, randomize_seats = ?, ");
+            output_bindings.push(JsValue::from(settings.max_players));
+            output_bindings.push(JsValue::from(settings.visibility.as_str()));
+            output_bindings.push(JsValue::from(settings.turn_seconds));
+            output_bindings.push(JsValue::from(settings.undo_seconds));
+            output_bindings.push(JsValue::from(settings.deck_count));
+            output_bindings.push(optional_binding(settings.hand_size));
+            output_bindings.push(optional_binding(settings.language.clone()));
+            output_bindings.push(optional_binding(settings.region.clone()));
+            output_bindings.push(JsValue::from(settings.deck_theme.as_str()));
+            output_bindings.push(JsValue::from(i32::from(settings.randomize_seats)));
+        }
+
+        // outcome of an ended game
+        if let Some(winner) = &game_data.winner_player_id {
+            output_query.push_str("winner_player_id = ?, ");
+            output_bindings.push(JsValue::from(winner));
+        }
+
+        if let Some(finished_at) = &game_data.finished_at {
+            output_query.push_str("finished_at = ?, ");
+            output_bindings.push(JsValue::from(finished_at.to_rfc3339()));
+        }
+
+        if let Some(total_rounds) = game_data.total_rounds {
+            output_query.push_str("total_rounds = ?, ");
+            output_bindings.push(JsValue::from(total_rounds));
+        }
+
+        // host who took over from a player that left
+        if let Some(host) = &game_data.host_player_id {
+            output_query.push_str("host_player_id = ?, ");
+            output_bindings.push(JsValue::from(host));
+        }
+
+        if let Some(reclaimed_at) = &game_data.reclaimed_at {
+            output_query.push_str("reclaimed_at = ?, ");
+            output_bindings.push(JsValue::from(reclaimed_at.to_rfc3339()));
+        }
+
+        // nothing to change -> just return the current entry
+        if output_bindings.is_empty() && game_data.expected_revision.is_none() {
+            return (
+                "SELECT * FROM games WHERE id = ?;".to_string(),
+                vec![JsValue::from(game_data.id.clone())],
+            );
+        }
+
+        // every update claims the next revision; the room already checked the expected one
+        output_query.push_str("revision = revision + 1 WHERE id = ?");
         output_bindings.push(JsValue::from(game_data.id.clone()));
+        if let (Some(expected_revision), None) = (game_data.expected_revision, &self.rooms) {
+            output_query.push_str(" AND revision = ?");
+            output_bindings.push(JsValue::from(expected_revision as f64));
+        }
+        output_query.push_str(" RETURNING *;");
 
         (output_query, output_bindings)
     }
@@ -352,7 +929,7 @@ impl<'a> GameRepository<'a> {
     async fn update_players_in_game(
         &self,
         game_data: &UpdateGameDTO,
-        player_repo: &PlayerRepository<'_>,
+        player_repo: &PlayerRepository,
     ) -> Result<Vec<Player>, DatabaseQueryError<UpdateGameDTO>> {
         // just to make sure that the needed data was provided
         let new_players = match &game_data.players {
@@ -442,3 +1019,55 @@ impl<'a> GameRepository<'a> {
     /// 
     async fn update_claims_of_game(&self, game_data: &UpdateGameDTO, claims_repo: &ClaimsRepository) -> Result<Vec<Claim>, DatabaseQueryError<UpdateGameDTO>> {}
 }
+
+/// Reads the rows of one statement of a batch.
+fn rows<T>(result: &D1Result) -> Result<Vec<T>, DatabaseQueryError<Game>>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    result.results::<T>().map_err(|err| {
+        DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+    })
+}
+
+/// Condition of the lobby query matching the games of a mode.
+///
+/// The mode isn't stored with the summaries, it's derived from the games and the tournament
+/// matches.
+fn mode_condition(mode: GameMode) -> &'static str {
+    match mode {
+        GameMode::Casual => {
+            "game_id NOT IN (SELECT id FROM games WHERE daily_date IS NOT NULL)
+                AND game_id NOT IN (SELECT game_id FROM tournament_matches WHERE game_id IS NOT NULL)"
+        }
+        GameMode::Daily => "game_id IN (SELECT id FROM games WHERE daily_date IS NOT NULL)",
+        GameMode::Tournament => {
+            "game_id IN (SELECT game_id FROM tournament_matches WHERE game_id IS NOT NULL)"
+        }
+    }
+}
+
+/// Groups card rows by the player or the claim they belong to.
+///
+/// # Arguments
+///
+/// - `rows` -> Rows of the `cards` table
+/// - `column` -> Column to group by, `player_id` or `claim_id`
+fn cards_by(
+    rows: Vec<Value>,
+    column: &str,
+) -> Result<HashMap<String, Vec<Card>>, DatabaseQueryError<Game>> {
+    let mut groups: HashMap<String, Vec<Card>> = HashMap::new();
+
+    for row in rows {
+        let Some(owner) = row.get(column).and_then(Value::as_str).map(str::to_string) else {
+            continue;
+        };
+        let card = serde_json::from_value::<Card>(row).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })?;
+        groups.entry(owner).or_default().push(card);
+    }
+
+    Ok(groups)
+}
@@ -0,0 +1,248 @@
+use axum::http::StatusCode;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    enums::game_state::GameState,
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::{
+        game_history::{GameHistoryRow, HistoryCursor},
+        game_result::GameResult,
+        player_stats::PlayerStats,
+        recent_opponent::RecentOpponent,
+    },
+    utils::{d1_conversions::optional_binding, time::server_time},
+};
+
+/// A database repository for interacting with the `game_results` and `player_stats` tables.
+#[derive(Clone)]
+pub struct GameResultRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl GameResultRepository {
+    /// Returns a fresh instance of `GameResultRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        GameResultRepository { db }
+    }
+
+    /// Stores the results of a finished game and rebuilds the lifetime statistics of its players.
+    ///
+    /// Both happen in one transaction, so the statistics never disagree with the results.
+    /// Existing results of the same players are replaced and the statistics are computed from
+    /// scratch, so recording a game twice is harmless.
+    ///
+    /// # Arguments
+    ///
+    /// - `results` -> Results of all players of the game.
+    ///
+    /// # Returns the number of stored results or an error if the transaction fails.
+    pub async fn add_results(
+        &self,
+        results: Vec<GameResult>,
+    ) -> Result<usize, DatabaseQueryError<GameResult>> {
+        let insert_query = "INSERT OR REPLACE INTO game_results (game_id, player_id, player_name, fingerprint, score, is_winner, rounds_played, finished_at, bluffs, successful_bluffs, challenges, successful_challenges, started_at, player_count, daily_date) 
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15);";
+        let stats_query = "INSERT OR REPLACE INTO player_stats (identity, player_name, games_played, wins, bluffs, successful_bluffs, challenges, successful_challenges, updated_at)
+            SELECT ?1,
+                (SELECT player_name FROM game_results WHERE COALESCE(fingerprint, 'name:' || player_name) = ?1 ORDER BY finished_at DESC LIMIT 1),
+                COUNT(*), SUM(is_winner), SUM(bluffs), SUM(successful_bluffs), SUM(challenges), SUM(successful_challenges), ?2
+            FROM game_results WHERE COALESCE(fingerprint, 'name:' || player_name) = ?1
+            HAVING COUNT(*) > 0;";
+        let now = server_time().to_rfc3339();
+
+        let mut statements = Vec::with_capacity(results.len() * 2);
+        for result in &results {
+            let params = vec![
+                JsValue::from(result.game_id.clone()),
+                JsValue::from(result.player_id.clone()),
+                JsValue::from(result.player_name.clone()),
+                optional_binding(result.fingerprint.clone()),
+                JsValue::from(result.score),
+                JsValue::from(i32::from(result.is_winner)),
+                JsValue::from(result.rounds_played),
+                JsValue::from(result.finished_at.to_rfc3339()),
+                JsValue::from(result.bluffs),
+                JsValue::from(result.successful_bluffs),
+                JsValue::from(result.challenges),
+                JsValue::from(result.successful_challenges),
+                optional_binding(result.started_at.map(|started_at| started_at.to_rfc3339())),
+                JsValue::from(result.player_count),
+                optional_binding(result.daily_date.map(|date| date.to_string())),
+            ];
+
+            statements.push(
+                self.db
+                    .query("game_result.add_results", insert_query)
+                    .bind(&params)
+                    .unwrap(),
+            );
+        }
+        // the statistics are rebuilt after all results of the game are inserted
+        for result in &results {
+            statements.push(
+                self.db
+                    .query("game_result.add_results", stats_query)
+                    .bind(&[
+                        JsValue::from(result.identity()),
+                        JsValue::from(now.as_str()),
+                    ])
+                    .unwrap(),
+            );
+        }
+
+        match self.db.batch("game_result.add_results", statements).await {
+            Ok(_) => Ok(results.len()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the lifetime statistics of a player.
+    ///
+    /// # Arguments
+    ///
+    /// - `identity` -> Identity of the player, see `player_identity`
+    ///
+    /// # Returns the statistics, `None` if the player never finished a game, or an error if the
+    /// query fails.
+    pub async fn get_player_stats(
+        &self,
+        identity: &str,
+    ) -> Result<Option<PlayerStats>, DatabaseQueryError<PlayerStats>> {
+        let query_result = self
+            .db
+            .query(
+                "game_result.get_player_stats",
+                "SELECT * FROM player_stats WHERE identity = ?;",
+            )
+            .bind(&[JsValue::from(identity)])
+            .unwrap()
+            .first::<PlayerStats>(None)
+            .await;
+
+        query_result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Gets a page of the finished games of a player, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// - `identity` -> Identity of the player, see `player_identity`
+    /// - `cursor` -> Only games before this position; the newest ones if `None`
+    /// - `limit` -> Maximum number of games to return
+    ///
+    /// # Returns the results of the player or an error if the query fails.
+    pub async fn get_history(
+        &self,
+        identity: &str,
+        cursor: Option<&HistoryCursor>,
+        limit: usize,
+    ) -> Result<Vec<GameHistoryRow>, DatabaseQueryError<GameHistoryRow>> {
+        let query = "SELECT r.game_id, r.player_name, r.score, r.is_winner, r.rounds_played, r.player_count, r.started_at, r.finished_at,
+                EXISTS (SELECT 1 FROM game_results w WHERE w.game_id = r.game_id AND w.is_winner = 1) AS has_winner
+            FROM game_results r
+            WHERE COALESCE(r.fingerprint, 'name:' || r.player_name) = ?1
+                AND (?2 IS NULL OR r.finished_at < ?2 OR (r.finished_at = ?2 AND r.game_id < ?3))
+            ORDER BY r.finished_at DESC, r.game_id DESC LIMIT ?4;";
+
+        let query_result = self
+            .db
+            .query("game_result.get_history", query)
+            .bind(&[
+                JsValue::from(identity),
+                optional_binding(cursor.map(|cursor| cursor.finished_at.to_rfc3339())),
+                optional_binding(cursor.map(|cursor| cursor.game_id.clone())),
+                JsValue::from(limit),
+            ])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_rows) => match fetched_rows.results::<GameHistoryRow>() {
+                Ok(rows) => Ok(rows),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Retrieves the players who finished games together with a player, latest shared game first.
+    ///
+    /// Opponents are grouped by their identity. An opponent with a fingerprint is looked up in the
+    /// games that haven't ended, so they can be invited; players without one can't be told apart
+    /// by name alone.
+    ///
+    /// # Arguments
+    ///
+    /// - `identity` -> Identity of the player, see `Player::identity`
+    /// - `limit` -> Highest number of opponents
+    pub async fn get_recent_opponents(
+        &self,
+        identity: &str,
+        limit: usize,
+    ) -> Result<Vec<RecentOpponent>, DatabaseQueryError<RecentOpponent>> {
+        // the bare columns next to MAX() are taken from the row holding the maximum
+        let query = "SELECT o.player_name, COUNT(*) AS games_together, o.game_id AS last_game_id, MAX(o.finished_at) AS last_played_at,
+                (SELECT p.id FROM players p JOIN games g ON g.id = p.game_id
+                    WHERE o.fingerprint IS NOT NULL AND p.fingerprint = o.fingerprint AND g.state NOT IN (?3, ?4)
+                    ORDER BY p.joined_at DESC LIMIT 1) AS active_player_id,
+                (SELECT p.game_id FROM players p JOIN games g ON g.id = p.game_id
+                    WHERE o.fingerprint IS NOT NULL AND p.fingerprint = o.fingerprint AND g.state NOT IN (?3, ?4)
+                    ORDER BY p.joined_at DESC LIMIT 1) AS active_game_id
+            FROM game_results r
+            JOIN game_results o ON o.game_id = r.game_id
+                AND COALESCE(o.fingerprint, 'name:' || o.player_name) != ?1
+            WHERE COALESCE(r.fingerprint, 'name:' || r.player_name) = ?1
+            GROUP BY COALESCE(o.fingerprint, 'name:' || o.player_name)
+            ORDER BY last_played_at DESC LIMIT ?2;";
+
+        let query_result = self
+            .db
+            .query("game_result.get_recent_opponents", query)
+            .bind(&[
+                JsValue::from(identity),
+                JsValue::from(limit),
+                JsValue::from(GameState::Ended.as_str()),
+                JsValue::from(GameState::Abandoned.as_str()),
+            ])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_rows) => match fetched_rows.results::<RecentOpponent>() {
+                Ok(opponents) => Ok(opponents),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use axum::http::StatusCode;
+use log::warn;
+use serde_json::json;
+use wasm_bindgen::JsValue;
+use worker::{
+    send::{SendFuture, SendWrapper},
+    Env, Method, ObjectNamespace, Request, RequestInit,
+};
+
+use crate::{
+    durable::game_room::{RoomSnapshot, GAME_ROOM_BINDING},
+    enums::storage_mode::StorageMode,
+    errors::database_query_error::DatabaseQueryError,
+    types::game::{Game, UpdateGameDTO},
+};
+
+// constants
+/// Base URL of the internal requests to a room; the host is ignored by Durable Objects.
+const ROOM_URL: &str = "https://game-room";
+
+/// Client of the `GameRoom` Durable Objects.
+///
+/// Only used in the `durable-object` storage mode.
+#[derive(Clone)]
+pub struct GameRooms {
+    /// Namespace of the `GameRoom` Durable Objects
+    namespace: SendWrapper<ObjectNamespace>,
+}
+
+impl GameRooms {
+    /// Creates the client if the worker runs in the `durable-object` storage mode.
+    ///
+    /// # Arguments
+    ///
+    /// - `env` -> Environment of the worker
+    /// - `storage_mode` -> Storage mode of the `AppConfig`
+    ///
+    /// # Returns
+    ///
+    /// `None` in the `d1` storage mode or if the binding is missing.
+    pub fn from_env(env: &Env, storage_mode: StorageMode) -> Option<Self> {
+        if storage_mode != StorageMode::DurableObject {
+            return None;
+        }
+
+        match env.durable_object(GAME_ROOM_BINDING) {
+            Ok(namespace) => Some(GameRooms {
+                namespace: SendWrapper(namespace),
+            }),
+            Err(err) => {
+                warn!("Game room binding not found, falling back to D1: {err}");
+                None
+            }
+        }
+    }
+
+    /// Gets the live state of a game.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the room of the game is empty.
+    pub async fn get(&self, game_id: &str) -> Result<Option<Game>, DatabaseQueryError<Game>> {
+        let (status, snapshot) = self.send(game_id, Method::Get, "/state", None).await?;
+
+        match status {
+            200 => Ok(snapshot.map(RoomSnapshot::into_game)),
+            404 => Ok(None),
+            _ => Err(room_error(status)),
+        }
+    }
+
+    /// Hands a game loaded from D1 to its room.
+    ///
+    /// A room that already holds the game keeps its state, an empty one carries on with the
+    /// revision stored in D1.
+    ///
+    /// # Returns
+    ///
+    /// The live state of the game.
+    pub async fn seed(&self, game: &Game) -> Result<Game, DatabaseQueryError<Game>> {
+        let seed = json!({ "revision": game.revision, "game": game });
+        let (status, snapshot) = self
+            .send(&game.id, Method::Post, "/seed", Some(seed.to_string()))
+            .await?;
+
+        match (status, snapshot) {
+            (200, Some(snapshot)) => Ok(snapshot.into_game()),
+            _ => Err(room_error(status)),
+        }
+    }
+
+    /// Applies an update to the live state of a game.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the room of the game is empty and needs to be seeded first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `409` error if the game changed since the revision the update is based on.
+    pub async fn apply(
+        &self,
+        game_data: &UpdateGameDTO,
+    ) -> Result<Option<Game>, DatabaseQueryError<UpdateGameDTO>> {
+        let body = serde_json::to_string(game_data).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })?;
+        let (status, snapshot) = self
+            .send(&game_data.id, Method::Post, "/apply", Some(body))
+            .await
+            .map_err(|err| DatabaseQueryError::new(err.message, None, err.status_code))?;
+
+        match (status, snapshot) {
+            (200, Some(snapshot)) => Ok(Some(snapshot.into_game())),
+            (404, _) => Ok(None),
+            (409, _) => Err(DatabaseQueryError::new(
+                "The game changed in the meantime! Please reload it and try again.".to_string(),
+                None,
+                StatusCode::CONFLICT,
+            )),
+            _ => {
+                let err = room_error(status);
+                Err(DatabaseQueryError::new(err.message, None, err.status_code))
+            }
+        }
+    }
+
+    /// Empties the room of a game after it was archived in D1.
+    ///
+    /// A failure is only logged; the room is seeded from D1 again on the next access.
+    pub async fn archive(&self, game_id: &str) {
+        if let Err(err) = self.send(game_id, Method::Delete, "/state", None).await {
+            warn!(
+                "Room of game {game_id} couldn't be emptied: {}",
+                err.message
+            );
+        }
+    }
+
+    // ----- utility functions of the 'GameRooms' struct -----
+
+    /// Sends an internal request to the room of a game.
+    ///
+    /// # Returns
+    ///
+    /// The status code of the room and the snapshot, if the room returned one.
+    async fn send(
+        &self,
+        game_id: &str,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<(u16, Option<RoomSnapshot>), DatabaseQueryError<Game>> {
+        let namespace = self.namespace.clone();
+        let game_id = game_id.to_string();
+        let url = format!("{ROOM_URL}{path}");
+
+        let result = SendFuture::new(async move {
+            let mut init = RequestInit::new();
+            init.with_method(method);
+            if let Some(body) = body {
+                init.with_body(Some(JsValue::from_str(&body)));
+            }
+            let request = Request::new_with_init(&url, &init)?;
+
+            let stub = namespace.id_from_name(&game_id)?.get_stub()?;
+            let mut response = stub.fetch_with_request(request).await?;
+            let status = response.status_code();
+            let snapshot = match status {
+                200 => Some(response.json::<RoomSnapshot>().await?),
+                _ => None,
+            };
+
+            Ok::<_, worker::Error>((status, snapshot))
+        })
+        .await;
+
+        result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+}
+
+/// Creates the error of an unexpected status code returned by a room.
+fn room_error(status: u16) -> DatabaseQueryError<Game> {
+    DatabaseQueryError::new(
+        format!("The game room answered with the unexpected status {status}"),
+        None,
+        StatusCode::INTERNAL_SERVER_ERROR,
+    )
+}
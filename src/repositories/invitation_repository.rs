@@ -0,0 +1,100 @@
+use axum::{http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError, repositories::database::Database,
+    types::invitation::Invitation,
+};
+
+/// A database repository for interacting with the `invitations` table.
+#[derive(Clone)]
+pub struct InvitationRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl InvitationRepository {
+    /// Returns a fresh instance of `InvitationRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        InvitationRepository { db }
+    }
+
+    /// Stores a sent invitation.
+    ///
+    /// # Arguments
+    ///
+    /// - `invitation` -> The invitation to be inserted.
+    ///
+    /// # Returns the stored invitation or an error if the insertion fails.
+    pub async fn add_invitation(
+        &self,
+        invitation: Invitation,
+    ) -> Result<Invitation, DatabaseQueryError<Invitation>> {
+        let query = "INSERT INTO invitations (id, game_id, sender_id, sent_at) 
+            VALUES (?1, ?2, ?3, ?4) RETURNING *;";
+        let params = vec![
+            JsValue::from(invitation.id.clone()),
+            JsValue::from(invitation.game_id.clone()),
+            JsValue::from(invitation.sender_id.clone()),
+            JsValue::from(invitation.sent_at.to_rfc3339()),
+        ];
+
+        let query_result = self
+            .db
+            .query("invitation.add_invitation", query)
+            .bind(&params)
+            .unwrap()
+            .first::<Invitation>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(stored_invitation)) => Ok(stored_invitation),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to store the invitation".to_string(),
+                Some(Json(invitation)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(invitation)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the invitations a player sent since a point in time, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// - `sender_id` -> Id of the player.
+    /// - `since` -> Start of the time window.
+    ///
+    /// # Returns the invitations or an error if the query fails.
+    pub async fn get_invitations_since(
+        &self,
+        sender_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Invitation>, DatabaseQueryError<Invitation>> {
+        let query_result = self
+            .db
+            .query(
+                "invitation.get_invitations_since",
+                "SELECT * FROM invitations WHERE sender_id = ?1 AND sent_at > ?2 ORDER BY sent_at;",
+            )
+            .bind(&[JsValue::from(sender_id), JsValue::from(since.to_rfc3339())])
+            .unwrap()
+            .all()
+            .await;
+
+        query_result
+            .and_then(|fetched_invitations| fetched_invitations.results::<Invitation>())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+}
@@ -0,0 +1,144 @@
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    enums::leaderboard_period::LeaderboardPeriod,
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::leaderboard::LeaderboardEntry,
+    utils::{d1_conversions::optional_binding, time::server_time},
+};
+
+/// A database repository for interacting with the `leaderboards` table.
+#[derive(Clone)]
+pub struct LeaderboardRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl LeaderboardRepository {
+    /// Returns a fresh instance of `LeaderboardRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        LeaderboardRepository { db }
+    }
+
+    /// Rebuilds the standing of one player from the stored game results.
+    ///
+    /// The row is computed from scratch instead of incremented, so processing a finished game
+    /// twice doesn't count it twice.
+    ///
+    /// # Arguments
+    ///
+    /// - `period` -> Leaderboard to update
+    /// - `bounds` -> Start and end of the period containing the game, see
+    ///   `LeaderboardPeriod::bounds` and `Season::bounds`
+    /// - `identity` -> Identity of the player, see `player_identity`
+    ///
+    /// # Returns nothing or an error if the query fails.
+    pub async fn refresh_entry(
+        &self,
+        period: LeaderboardPeriod,
+        bounds: (Option<DateTime<Utc>>, Option<DateTime<Utc>>),
+        identity: &str,
+    ) -> Result<(), DatabaseQueryError<LeaderboardEntry>> {
+        let (start, end) = bounds;
+        let query = "INSERT OR REPLACE INTO leaderboards (period, period_start, identity, player_name, games_played, wins, losses, successful_bluffs, successful_challenges, updated_at)
+            SELECT ?1, ?2, ?3,
+                (SELECT player_name FROM game_results WHERE COALESCE(fingerprint, 'name:' || player_name) = ?3 ORDER BY finished_at DESC LIMIT 1),
+                COUNT(*), SUM(is_winner), COUNT(*) - SUM(is_winner), SUM(successful_bluffs), SUM(successful_challenges), ?6
+            FROM game_results
+            WHERE COALESCE(fingerprint, 'name:' || player_name) = ?3
+                AND (?4 IS NULL OR finished_at >= ?4) AND (?5 IS NULL OR finished_at < ?5)
+            HAVING COUNT(*) > 0;";
+
+        let query_result = self
+            .db
+            .query("leaderboard.refresh_entry", query)
+            .bind(&[
+                JsValue::from(period.as_str()),
+                JsValue::from(period_start_key(start)),
+                JsValue::from(identity),
+                optional_binding(start.map(|start| start.to_rfc3339())),
+                optional_binding(end.map(|end| end.to_rfc3339())),
+                JsValue::from(server_time().to_rfc3339()),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets a page of a leaderboard, best players first.
+    ///
+    /// Players are ranked by wins, then by successful bluffs and challenges, then by fewer games
+    /// played.
+    ///
+    /// # Arguments
+    ///
+    /// - `period` -> Leaderboard to read
+    /// - `start` -> First moment of the period, `None` for `AllTime`
+    /// - `offset` -> Number of standings to skip
+    /// - `limit` -> Maximum number of standings to return
+    ///
+    /// # Returns the standings without their rank or an error if the query fails.
+    pub async fn get_entries(
+        &self,
+        period: LeaderboardPeriod,
+        start: Option<DateTime<Utc>>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<LeaderboardEntry>, DatabaseQueryError<LeaderboardEntry>> {
+        let query_result = self
+            .db
+            .query(
+                "leaderboard.get_entries",
+                "SELECT player_name, games_played, wins, losses, successful_bluffs, successful_challenges
+                    FROM leaderboards WHERE period = ?1 AND period_start = ?2
+                    ORDER BY wins DESC, successful_bluffs + successful_challenges DESC, games_played ASC, player_name ASC
+                    LIMIT ?3 OFFSET ?4;",
+            )
+            .bind(&[
+                JsValue::from(period.as_str()),
+                JsValue::from(period_start_key(start)),
+                JsValue::from(limit),
+                JsValue::from(offset),
+            ])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_entries) => match fetched_entries.results::<LeaderboardEntry>() {
+                Ok(entries) => Ok(entries),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
+
+/// Returns the value of the `period_start` column; empty for periods without a start.
+fn period_start_key(start: Option<DateTime<Utc>>) -> String {
+    start.map(|start| start.to_rfc3339()).unwrap_or_default()
+}
@@ -0,0 +1,299 @@
+use axum::{http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    enums::{data_migration::DataMigration, game_state::GameState},
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::{maintenance::MaintenanceMode, overview::OverviewCounts},
+    utils::game_service::generate_join_code,
+};
+
+// constants
+/// Key of the maintenance mode in the `app_settings` table.
+const MAINTENANCE_SETTING_KEY: &str = "maintenance";
+
+/// Row of the `app_settings` table.
+#[derive(Deserialize)]
+struct SettingRow {
+    /// JSON value of the setting
+    value: String,
+}
+
+/// Row containing only an id.
+#[derive(Deserialize)]
+struct IdRow {
+    /// Identifier of the row
+    id: String,
+}
+
+/// A database repository for operator tasks: the `app_settings` table and data migrations.
+#[derive(Clone)]
+pub struct MaintenanceRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl MaintenanceRepository {
+    /// Returns a fresh instance of `MaintenanceRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        MaintenanceRepository { db }
+    }
+
+    /// Gets the current maintenance mode.
+    ///
+    /// # Returns the stored mode, the disabled default if none was stored yet, or an error if the
+    /// query fails.
+    pub async fn get_maintenance_mode(
+        &self,
+    ) -> Result<MaintenanceMode, DatabaseQueryError<MaintenanceMode>> {
+        let query_result = self
+            .db
+            .query(
+                "maintenance.get_maintenance_mode",
+                "SELECT value FROM app_settings WHERE key = ?;",
+            )
+            .bind(&[JsValue::from(MAINTENANCE_SETTING_KEY)])
+            .unwrap()
+            .first::<SettingRow>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(row)) => serde_json::from_str(&row.value).map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            }),
+            Ok(None) => Ok(MaintenanceMode::default()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Stores a new maintenance mode.
+    ///
+    /// # Arguments
+    ///
+    /// - `mode` -> The mode to be stored.
+    ///
+    /// # Returns the stored mode or an error if the query fails.
+    pub async fn set_maintenance_mode(
+        &self,
+        mode: MaintenanceMode,
+    ) -> Result<MaintenanceMode, DatabaseQueryError<MaintenanceMode>> {
+        let value = serde_json::to_string(&mode).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })?;
+
+        let query_result = self
+            .db
+            .query(
+                "maintenance.set_maintenance_mode",
+                "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, CURRENT_TIMESTAMP) 
+                    ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at;",
+            )
+            .bind(&[JsValue::from(MAINTENANCE_SETTING_KEY), JsValue::from(value)])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(mode),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(mode)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the name of the last schema migration applied by `wrangler d1 migrations apply`.
+    ///
+    /// # Returns the name, `None` if no migration was applied, or an error if the query fails.
+    pub async fn get_latest_schema_migration(
+        &self,
+    ) -> Result<Option<String>, DatabaseQueryError<MaintenanceMode>> {
+        let query_result = self
+            .db
+            .query(
+                "maintenance.get_latest_schema_migration",
+                "SELECT name FROM d1_migrations ORDER BY id DESC LIMIT 1;",
+            )
+            .first::<String>(Some("name"))
+            .await;
+
+        query_result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Runs the cheapest possible query to measure the round trip to D1.
+    ///
+    /// # Returns an error if the query fails.
+    pub async fn ping(&self) -> Result<(), DatabaseQueryError<MaintenanceMode>> {
+        let query_result = self
+            .db
+            .query("maintenance.ping", "SELECT 1 AS ok;")
+            .first::<u8>(Some("ok"))
+            .await;
+
+        query_result.map(|_| ()).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Reads the figures of the overview of the operators in a single query.
+    ///
+    /// # Arguments
+    ///
+    /// - `online_since` -> Earliest heartbeat of a player who counts as online
+    /// - `day_start` -> Midnight UTC of the current day
+    /// - `finished_since` -> Earliest end of a game counted in the average duration
+    ///
+    /// # Returns the figures or an error if the query fails.
+    pub async fn get_overview_counts(
+        &self,
+        online_since: DateTime<Utc>,
+        day_start: DateTime<Utc>,
+        finished_since: DateTime<Utc>,
+    ) -> Result<OverviewCounts, DatabaseQueryError<MaintenanceMode>> {
+        let query = "SELECT
+            (SELECT COUNT(*) FROM games WHERE state NOT IN (?1, ?2)) AS active_games,
+            (SELECT COUNT(*) FROM players WHERE last_time_update_requested >= ?3
+                AND disconnected_at IS NULL) AS players_online,
+            (SELECT COUNT(*) FROM games WHERE started_at >= ?4) AS games_created_today,
+            (SELECT AVG((julianday(finished_at) - julianday(started_at)) * 86400) FROM games
+                WHERE state = ?1 AND finished_at >= ?5) AS average_game_seconds,
+            (SELECT COUNT(*) FROM webhook_dead_letters WHERE failed_at >= ?4)
+                AS failed_webhook_deliveries_today;";
+        let params = [
+            JsValue::from(GameState::Ended.as_str()),
+            JsValue::from(GameState::Abandoned.as_str()),
+            JsValue::from(online_since.to_rfc3339()),
+            JsValue::from(day_start.to_rfc3339()),
+            JsValue::from(finished_since.to_rfc3339()),
+        ];
+
+        let query_result = self
+            .db
+            .query("maintenance.get_overview_counts", query)
+            .bind(&params)
+            .unwrap()
+            .first::<OverviewCounts>(None)
+            .await;
+
+        query_result.map(Option::unwrap_or_default).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Runs a data migration.
+    ///
+    /// Every migration is idempotent, so running it twice doesn't change anything the second
+    /// time.
+    ///
+    /// # Arguments
+    ///
+    /// - `migration` -> The migration to run.
+    ///
+    /// # Returns the number of changed rows or an error if a query fails.
+    pub async fn run_migration(
+        &self,
+        migration: DataMigration,
+    ) -> Result<usize, DatabaseQueryError<MaintenanceMode>> {
+        match migration {
+            DataMigration::BackfillJoinCodes => self.backfill_join_codes().await,
+            DataMigration::RecountChatMessages => {
+                self.run_statement(
+                    "UPDATE chats SET number_of_messages = 
+                        (SELECT COUNT(*) FROM chat_messages WHERE chat_messages.chat_id = chats.id
+                            AND chat_messages.recipient_player_id IS NULL);",
+                )
+                .await
+            }
+        }
+    }
+
+    /// Generates a join code for every game without one.
+    async fn backfill_join_codes(&self) -> Result<usize, DatabaseQueryError<MaintenanceMode>> {
+        let query_result = self
+            .db
+            .query(
+                "maintenance.backfill_join_codes.select",
+                "SELECT id FROM games WHERE join_code IS NULL;",
+            )
+            .all()
+            .await;
+
+        let games = match query_result {
+            Ok(fetched_games) => fetched_games.results::<IdRow>().map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })?,
+            Err(err) => {
+                return Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ))
+            }
+        };
+
+        let mut affected_rows = 0;
+        for game in games {
+            let query_result = self
+                .db
+                .query(
+                    "maintenance.backfill_join_codes.update",
+                    "UPDATE games SET join_code = ?1 WHERE id = ?2 AND join_code IS NULL;",
+                )
+                .bind(&[JsValue::from(generate_join_code()), JsValue::from(game.id)])
+                .unwrap()
+                .run()
+                .await;
+
+            if let Err(err) = query_result {
+                return Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ));
+            }
+            affected_rows += 1;
+        }
+
+        Ok(affected_rows)
+    }
+
+    /// Runs a statement without bindings and returns the number of changed rows.
+    async fn run_statement(
+        &self,
+        statement: &str,
+    ) -> Result<usize, DatabaseQueryError<MaintenanceMode>> {
+        let query_result = self
+            .db
+            .query("maintenance.run_statement", statement)
+            .run()
+            .await;
+
+        match query_result {
+            Ok(result) => Ok(result
+                .meta()
+                .ok()
+                .flatten()
+                .and_then(|meta| meta.changes)
+                .unwrap_or_default()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
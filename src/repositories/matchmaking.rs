@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use axum::http::StatusCode;
+use log::warn;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::JsValue;
+use worker::{
+    send::{SendFuture, SendWrapper},
+    Env, Method, ObjectNamespace, Request, RequestInit,
+};
+
+use crate::{
+    durable::matchmaking_queue::{MATCHMAKING_QUEUE_BINDING, MATCHMAKING_QUEUE_NAME},
+    errors::database_query_error::DatabaseQueryError,
+    types::matchmaking::{MatchAssignment, QueueTicket, TicketStatus},
+};
+
+// constants
+/// Base URL of the internal requests to the queue; the host is ignored by Durable Objects.
+const QUEUE_URL: &str = "https://matchmaking-queue";
+
+/// Client of the `MatchmakingQueue` Durable Object.
+#[derive(Clone)]
+pub struct Matchmaking {
+    /// Namespace of the `MatchmakingQueue` Durable Object
+    namespace: SendWrapper<ObjectNamespace>,
+}
+
+impl Matchmaking {
+    /// Creates the client if the queue is bound.
+    ///
+    /// # Arguments
+    ///
+    /// - `env` -> Environment of the worker
+    ///
+    /// # Returns
+    ///
+    /// `None` if the binding is missing; matchmaking isn't available then.
+    pub fn from_env(env: &Env) -> Option<Self> {
+        match env.durable_object(MATCHMAKING_QUEUE_BINDING) {
+            Ok(namespace) => Some(Matchmaking {
+                namespace: SendWrapper(namespace),
+            }),
+            Err(err) => {
+                warn!("Matchmaking queue binding not found, matchmaking is disabled: {err}");
+                None
+            }
+        }
+    }
+
+    /// Adds a solo player to the queue.
+    ///
+    /// A ticket of the same client that is still waiting is replaced.
+    ///
+    /// # Returns
+    ///
+    /// The status of the new ticket.
+    pub async fn enter(
+        &self,
+        ticket: &QueueTicket,
+    ) -> Result<TicketStatus, DatabaseQueryError<QueueTicket>> {
+        let body = serde_json::to_string(ticket).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })?;
+
+        match self.send(Method::Post, "/enter", Some(body)).await? {
+            (200, Some(status)) => Ok(status),
+            (status, _) => Err(queue_error(status)),
+        }
+    }
+
+    /// Gets where a ticket stands.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the ticket isn't waiting and wasn't assigned recently.
+    pub async fn status(
+        &self,
+        ticket_id: &str,
+    ) -> Result<Option<TicketStatus>, DatabaseQueryError<QueueTicket>> {
+        let path = format!("/ticket/{ticket_id}");
+
+        match self.send(Method::Get, &path, None).await? {
+            (200, status) => Ok(status),
+            (404, _) => Ok(None),
+            (status, _) => Err(queue_error(status)),
+        }
+    }
+
+    /// Removes a ticket from the queue.
+    pub async fn leave(&self, ticket_id: &str) -> Result<(), DatabaseQueryError<QueueTicket>> {
+        let path = format!("/ticket/{ticket_id}");
+
+        match self.send::<()>(Method::Delete, &path, None).await? {
+            (204, _) => Ok(()),
+            (status, _) => Err(queue_error(status)),
+        }
+    }
+
+    /// Takes the tickets of the next game out of the queue.
+    ///
+    /// # Returns
+    ///
+    /// The tickets, oldest first; empty if not enough players wait yet.
+    pub async fn take(&self) -> Result<Vec<QueueTicket>, DatabaseQueryError<QueueTicket>> {
+        match self.send(Method::Post, "/take", None).await? {
+            (200, tickets) => Ok(tickets.unwrap_or_default()),
+            (status, _) => Err(queue_error(status)),
+        }
+    }
+
+    /// Tells the tickets of an assembled game which game and player they got.
+    ///
+    /// # Arguments
+    ///
+    /// - `assignments` -> Assignment of every ticket of the game, keyed by the id of the ticket
+    pub async fn assign(
+        &self,
+        assignments: &HashMap<String, MatchAssignment>,
+    ) -> Result<(), DatabaseQueryError<QueueTicket>> {
+        let body = serde_json::to_string(assignments).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })?;
+
+        match self.send::<()>(Method::Post, "/assign", Some(body)).await? {
+            (204, _) => Ok(()),
+            (status, _) => Err(queue_error(status)),
+        }
+    }
+
+    // ----- utility functions of the 'Matchmaking' struct -----
+
+    /// Sends an internal request to the queue.
+    ///
+    /// # Returns
+    ///
+    /// The status code of the queue and its JSON body, if it answered with `200`.
+    async fn send<T: DeserializeOwned + 'static>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<(u16, Option<T>), DatabaseQueryError<QueueTicket>> {
+        let namespace = self.namespace.clone();
+        let url = format!("{QUEUE_URL}{path}");
+
+        let result = SendFuture::new(async move {
+            let mut init = RequestInit::new();
+            init.with_method(method);
+            if let Some(body) = body {
+                init.with_body(Some(JsValue::from_str(&body)));
+            }
+            let request = Request::new_with_init(&url, &init)?;
+
+            let stub = namespace.id_from_name(MATCHMAKING_QUEUE_NAME)?.get_stub()?;
+            let mut response = stub.fetch_with_request(request).await?;
+            let status = response.status_code();
+            let body = match status {
+                200 => Some(response.json::<T>().await?),
+                _ => None,
+            };
+
+            Ok::<_, worker::Error>((status, body))
+        })
+        .await;
+
+        result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+}
+
+/// Creates the error of an unexpected status code returned by the queue.
+fn queue_error(status: u16) -> DatabaseQueryError<QueueTicket> {
+    DatabaseQueryError::new(
+        format!("The matchmaking queue answered with the unexpected status {status}"),
+        None,
+        StatusCode::INTERNAL_SERVER_ERROR,
+    )
+}
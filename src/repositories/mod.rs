@@ -1,7 +1,30 @@
 // Helper GitHub repository: https://github.com/jeastham1993/serverless-cloudflare
 
+pub mod achievement_repository;
+pub mod archive_storage;
+pub mod audit_repository;
+pub mod backup_repository;
 pub mod card_repository;
 pub mod chat;
 pub mod claim_repository;
+pub mod daily_challenge_repository;
+pub mod database;
+pub mod game_cache;
+pub mod game_event_repository;
 pub mod game_repository;
+pub mod game_result_repository;
+pub mod game_rooms;
+pub mod invitation_repository;
+pub mod leaderboard_repository;
+pub mod maintenance_repository;
+pub mod matchmaking;
+pub mod moderation_repository;
 pub mod player_repository;
+pub mod query_builder;
+pub mod score_history_repository;
+pub mod season_repository;
+pub mod share_cache;
+pub mod tournament_repository;
+pub mod typing_indicators;
+pub mod waitlist_repository;
+pub mod webhook_repository;
@@ -0,0 +1,213 @@
+use axum::{http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::moderation::{Ban, Report},
+    utils::d1_conversions::optional_binding,
+};
+
+/// A database repository for interacting with the `reports` and `bans` tables.
+#[derive(Clone)]
+pub struct ModerationRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl ModerationRepository {
+    /// Returns a fresh instance of `ModerationRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        ModerationRepository { db }
+    }
+
+    /// Stores a new report.
+    ///
+    /// # Arguments
+    ///
+    /// - `report` -> The report to be inserted.
+    ///
+    /// # Returns the stored report or an error if the insertion fails.
+    pub async fn add_report(&self, report: Report) -> Result<Report, DatabaseQueryError<Report>> {
+        let query = "INSERT INTO reports (id, game_id, reporter_id, reported_player_id, reported_fingerprint, reason, created_at) 
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7) RETURNING *;";
+        let params = vec![
+            JsValue::from(report.id.clone()),
+            JsValue::from(report.game_id.clone()),
+            JsValue::from(report.reporter_id.clone()),
+            JsValue::from(report.reported_player_id.clone()),
+            optional_binding(report.reported_fingerprint.clone()),
+            JsValue::from(report.reason.clone()),
+            JsValue::from(report.created_at.to_rfc3339()),
+        ];
+
+        let query_result = self
+            .db
+            .query("moderation.add_report", query)
+            .bind(&params)
+            .unwrap()
+            .first::<Report>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(stored_report)) => Ok(stored_report),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to store the report".to_string(),
+                Some(Json(report)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(report)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the latest reports, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// - `limit` -> Maximum number of reports to return.
+    ///
+    /// # Returns the reports or an error if the query fails.
+    pub async fn get_reports(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<Report>, DatabaseQueryError<Report>> {
+        let query_result = self
+            .db
+            .query(
+                "moderation.get_reports",
+                "SELECT * FROM reports ORDER BY created_at DESC LIMIT ?;",
+            )
+            .bind(&[JsValue::from(limit)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_reports) => match fetched_reports.results::<Report>() {
+                Ok(reports) => Ok(reports),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the ban of a client if it is still active.
+    ///
+    /// # Arguments
+    ///
+    /// - `fingerprint` -> Fingerprint of the client.
+    /// - `now` -> Point in time the ban has to be active at.
+    ///
+    /// # Returns the active ban, `None` if the client isn't banned, or an error if the query fails.
+    pub async fn get_active_ban(
+        &self,
+        fingerprint: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Ban>, DatabaseQueryError<Ban>> {
+        let query_result = self
+            .db
+            .query(
+                "moderation.get_active_ban",
+                "SELECT * FROM bans WHERE fingerprint = ?;",
+            )
+            .bind(&[JsValue::from(fingerprint)])
+            .unwrap()
+            .first::<Ban>(None)
+            .await;
+
+        match query_result {
+            Ok(ban) => Ok(ban.filter(|ban| ban.is_active(now))),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Adds a client to the ban list or replaces its existing ban.
+    ///
+    /// # Arguments
+    ///
+    /// - `ban` -> The ban to be stored.
+    ///
+    /// # Returns the stored ban or an error if the insertion fails.
+    pub async fn add_ban(&self, ban: Ban) -> Result<Ban, DatabaseQueryError<Ban>> {
+        let query = "INSERT OR REPLACE INTO bans (fingerprint, reason, banned_at, expires_at) 
+            VALUES (?1, ?2, ?3, ?4) RETURNING *;";
+        let params = vec![
+            JsValue::from(ban.fingerprint.clone()),
+            JsValue::from(ban.reason.clone()),
+            JsValue::from(ban.banned_at.to_rfc3339()),
+            optional_binding(ban.expires_at.map(|expires_at| expires_at.to_rfc3339())),
+        ];
+
+        let query_result = self
+            .db
+            .query("moderation.add_ban", query)
+            .bind(&params)
+            .unwrap()
+            .first::<Ban>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(stored_ban)) => Ok(stored_ban),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to store the ban".to_string(),
+                Some(Json(ban)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(ban)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Removes a client from the ban list.
+    ///
+    /// # Arguments
+    ///
+    /// - `fingerprint` -> Fingerprint of the client.
+    ///
+    /// # Returns `Ok(())` if the deletion was successful, or an error if the query fails.
+    pub async fn remove_ban(&self, fingerprint: &str) -> Result<(), DatabaseQueryError<Ban>> {
+        let query_result = self
+            .db
+            .query(
+                "moderation.remove_ban",
+                "DELETE FROM bans WHERE fingerprint = ?;",
+            )
+            .bind(&[JsValue::from(fingerprint)])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
@@ -1,9 +1,12 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
 use wasm_bindgen::JsValue;
-use worker::D1Database;
 
 use crate::{
     errors::database_query_error::DatabaseQueryError,
-    types::player::{Player, UpdatePlayerDTO},
+    repositories::database::Database,
+    types::player::{MoveTally, Player, UpdatePlayerDTO},
+    utils::d1_conversions::optional_binding,
 };
 
 /// Represents a repository for managing player data in the D1 database.
@@ -13,26 +16,26 @@ use crate::{
 ///
 /// # Properties
 ///
-/// `db`: An instance of `D1Database` that provides access to the D1 database.
+/// `db`: Shared `Database` handle that provides access to the D1 database.
 #[derive(Clone)]
-pub struct PlayerRepository<'a> {
+pub struct PlayerRepository {
     /// The D1 database instance used for accessing player data.
-    db: &'a D1Database,
+    db: Database,
 }
 
 // ----- Implementation of 'PlayerRepository' -----
 
-impl<'a> PlayerRepository<'a> {
+impl PlayerRepository {
     /// Creates a new `PlayerRepository` instance with the provided D1 database.
     ///
     /// # Arguments
     ///
-    /// * `db` - An instance of `D1Database` to be used for database operations.
+    /// * `db` - Shared `Database` handle to be used for database operations.
     ///
     /// # Returns
     ///
     /// A new `PlayerRepository` instance.
-    pub fn new(db: &'a D1Database) -> Self {
+    pub fn new(db: Database) -> Self {
         PlayerRepository { db }
     }
 
@@ -54,15 +57,24 @@ impl<'a> PlayerRepository<'a> {
     pub async fn add_player(&self, player: Player) -> Result<Player, DatabaseQueryError<Player>> {
         let added_player = self
             .db
-            .prepare(
-                "INSERT INTO players (id, name, game_id, joined_at) 
-                    VALUES (1?, 2?, 3?, 4?) RETURNING *;",
+            .query(
+                "player.add_player",
+                "INSERT INTO players (id, name, game_id, joined_at, last_time_update_requested, avatar_url, color, emoji, fingerprint, rejoin_token_hash, seat_index) 
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
+                        (SELECT COALESCE(MAX(seat_index) + 1, 0) FROM players WHERE game_id = ?3))
+                    RETURNING *;",
             )
             .bind(&[
                 JsValue::from(player.id.clone()),
                 JsValue::from(player.name.clone()),
                 JsValue::from(player.game_id.clone()),
-                JsValue::from(player.joined_at.clone()),
+                JsValue::from(player.joined_at.to_rfc3339()),
+                JsValue::from(player.last_time_update_requested.to_rfc3339()),
+                optional_binding(player.profile.avatar_url.clone()),
+                optional_binding(player.profile.color.clone()),
+                optional_binding(player.profile.emoji.clone()),
+                optional_binding(player.fingerprint.clone()),
+                optional_binding(player.rejoin_token_hash.clone()),
             ])
             .unwrap()
             .first::<Player>(None)
@@ -115,7 +127,7 @@ impl<'a> PlayerRepository<'a> {
 
         let updated_player = self
             .db
-            .prepare(&query)
+            .query("player.update_player", &query)
             .bind(&bindings)
             .unwrap()
             .first::<Player>(None)
@@ -138,6 +150,175 @@ impl<'a> PlayerRepository<'a> {
         }
     }
 
+    /// Adds bluffs and challenges to the counters of a player.
+    ///
+    /// The counters are incremented in place, so concurrent moves don't overwrite each other.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_id` - ID of the player who made the moves.
+    /// * `tally` - Moves to add to the counters.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure of the operation.
+    pub async fn add_to_tally(
+        &self,
+        player_id: &str,
+        tally: MoveTally,
+    ) -> Result<(), DatabaseQueryError<Player>> {
+        let query_result = self
+            .db
+            .query(
+                "player.add_to_tally",
+                "UPDATE players SET bluffs = bluffs + ?2,
+                    successful_bluffs = successful_bluffs + ?3,
+                    challenges = challenges + ?4,
+                    successful_challenges = successful_challenges + ?5
+                    WHERE id = ?1;",
+            )
+            .bind(&[
+                JsValue::from(player_id),
+                JsValue::from(tally.bluffs),
+                JsValue::from(tally.successful_bluffs),
+                JsValue::from(tally.challenges),
+                JsValue::from(tally.successful_challenges),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        query_result.map(|_| ()).map_err(|err| {
+            DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        })
+    }
+
+    /// Marks a player as disconnected or connected again.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_id` - ID of the player.
+    /// * `disconnected_at` - Point in time the heartbeat of the player stopped; `None` once it's
+    ///   back.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure of the operation.
+    pub async fn set_disconnected_at(
+        &self,
+        player_id: &str,
+        disconnected_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DatabaseQueryError<Player>> {
+        let query_result = self
+            .db
+            .query(
+                "player.set_disconnected_at",
+                "UPDATE players SET disconnected_at = ?2 WHERE id = ?1;",
+            )
+            .bind(&[
+                JsValue::from(player_id),
+                optional_binding(disconnected_at.map(|time| time.to_rfc3339())),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        query_result.map(|_| ()).map_err(|err| {
+            DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        })
+    }
+
+    /// Mutes a player in the chat.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_id` - ID of the player.
+    /// * `muted_until` - Point in time the mute ends.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure of the operation.
+    pub async fn set_muted_until(
+        &self,
+        player_id: &str,
+        muted_until: DateTime<Utc>,
+    ) -> Result<(), DatabaseQueryError<Player>> {
+        let query_result = self
+            .db
+            .query(
+                "player.set_muted_until",
+                "UPDATE players SET muted_until = ?2 WHERE id = ?1;",
+            )
+            .bind(&[
+                JsValue::from(player_id),
+                JsValue::from(muted_until.to_rfc3339()),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        query_result.map(|_| ()).map_err(|err| {
+            DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        })
+    }
+
+    /// Stores the seats of the players of a game.
+    ///
+    /// All seats are written in one transaction, so no two players end up on the same seat.
+    ///
+    /// # Arguments
+    ///
+    /// * `players` - Players of the game with their new `seat_index`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure of the operation.
+    pub async fn set_seats(&self, players: &[Player]) -> Result<(), DatabaseQueryError<Player>> {
+        if players.is_empty() {
+            return Ok(());
+        }
+
+        let statements = players
+            .iter()
+            .map(|player| {
+                self.db
+                    .query(
+                        "player.set_seats",
+                        "UPDATE players SET seat_index = ?2 WHERE id = ?1;",
+                    )
+                    .bind(&[
+                        JsValue::from(player.id.clone()),
+                        JsValue::from(player.seat_index),
+                    ])
+                    .unwrap()
+            })
+            .collect();
+
+        self.db
+            .batch("player.set_seats", statements)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                )
+            })
+    }
+
     /// Prepare the SQL statement to update the player
     ///
     /// # Arguments
@@ -171,7 +352,27 @@ impl<'a> PlayerRepository<'a> {
 
         if let Some(last_time_update_requested) = &player.last_time_update_requested {
             query.push_str("last_time_update_requested = ?, ");
-            bindings.push(JsValue::from(last_time_update_requested));
+            bindings.push(JsValue::from(last_time_update_requested.to_rfc3339()));
+        }
+
+        if let Some(avatar_url) = &player.profile.avatar_url {
+            query.push_str("avatar_url = ?, ");
+            bindings.push(JsValue::from(avatar_url));
+        }
+
+        if let Some(color) = &player.profile.color {
+            query.push_str("color = ?, ");
+            bindings.push(JsValue::from(color));
+        }
+
+        if let Some(emoji) = &player.profile.emoji {
+            query.push_str("emoji = ?, ");
+            bindings.push(JsValue::from(emoji));
+        }
+
+        if let Some(hand_sort) = &player.hand_sort {
+            query.push_str("hand_sort = ?, ");
+            bindings.push(JsValue::from(hand_sort.as_str()));
         }
 
         // Remove the trailing comma and space
@@ -199,7 +400,7 @@ impl<'a> PlayerRepository<'a> {
     pub async fn delete_player(&self, player_id: &str) -> Result<(), DatabaseQueryError<Player>> {
         let deleted_player = self
             .db
-            .prepare("DELETE FROM players WHERE id = ?;")
+            .query("player.delete_player", "DELETE FROM players WHERE id = ?;")
             .bind(&[JsValue::from(player_id)])
             .unwrap()
             .run()
@@ -229,7 +430,7 @@ impl<'a> PlayerRepository<'a> {
     pub async fn get_player(&self, player_id: &str) -> Result<Player, DatabaseQueryError<Player>> {
         let player = self
             .db
-            .prepare("SELECT * FROM players WHERE id = ?;")
+            .query("player.get_player", "SELECT * FROM players WHERE id = ?;")
             .bind(&[JsValue::from(player_id)])
             .unwrap()
             .first::<Player>(None)
@@ -272,7 +473,7 @@ impl<'a> PlayerRepository<'a> {
         let query_result = match game_id {
             None => {
                 self.db
-                    .prepare("SELECT * FROM players;")
+                    .query("player.get_all_players", "SELECT * FROM players;")
                     .bind(&[])
                     .unwrap()
                     .all()
@@ -280,7 +481,10 @@ impl<'a> PlayerRepository<'a> {
             }
             Some(_game_id) => {
                 self.db
-                    .prepare("SELECT * FROM players WHERE game_id = ?;")
+                    .query(
+                        "player.get_all_players.of_game",
+                        "SELECT * FROM players WHERE game_id = ? ORDER BY seat_index, joined_at;",
+                    )
                     .bind(&[JsValue::from(_game_id)])
                     .unwrap()
                     .all()
@@ -319,4 +523,54 @@ impl<'a> PlayerRepository<'a> {
             )),
         }
     }
+
+    /// Gets the names of all players of a game.
+    ///
+    /// Used to keep the names within a game unique before a player joins or renames themself.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    /// - `except_player_id` -> Id of a player whose name is left out, e.g. the one who renames
+    ///   themself
+    ///
+    /// # Returns
+    ///
+    /// The names of the players, or a `DatabaseQueryError` if the query fails.
+    pub async fn get_player_names(
+        &self,
+        game_id: &str,
+        except_player_id: Option<&str>,
+    ) -> Result<Vec<String>, DatabaseQueryError<Player>> {
+        let query_result = self
+            .db
+            .query(
+                "player.get_player_names",
+                "SELECT name FROM players WHERE game_id = ?1 AND (?2 IS NULL OR id != ?2);",
+            )
+            .bind(&[JsValue::from(game_id), optional_binding(except_player_id)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(collected_names) => match collected_names.results::<Value>() {
+                Ok(rows) => Ok(rows
+                    .iter()
+                    .filter_map(|row| row.get("name").and_then(Value::as_str))
+                    .map(str::to_string)
+                    .collect()),
+                Err(e) => Err(DatabaseQueryError::new(
+                    e.to_string(),
+                    None,
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(e) => Err(DatabaseQueryError::new(
+                e.to_string(),
+                None,
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
 }
@@ -0,0 +1,126 @@
+use wasm_bindgen::JsValue;
+
+/// Puts a `SELECT` together from the filters a client chose.
+///
+/// Conditions are joined with `AND`. Their values are always bound as parameters and never
+/// written into the SQL, so whatever a client sends can't change the query itself. Every condition
+/// with a value contains exactly one `?`, which is bound in the order the conditions were added.
+///
+/// # Example
+///
+/// ```rust
+/// let (sql, bindings) = QueryBuilder::new("SELECT * FROM game_summaries")
+///     .condition("visibility = ?", "Public")
+///     .optional_condition("language = ?", Some("de"))
+///     .fixed_condition("player_count < max_players")
+///     .order_by("started_at DESC")
+///     .build();
+/// ```
+pub struct QueryBuilder {
+    /// `SELECT ... FROM ...` part without a `WHERE` clause
+    select: String,
+    /// Conditions of the `WHERE` clause
+    conditions: Vec<String>,
+    /// Values of the placeholders, in the order of the conditions
+    bindings: Vec<JsValue>,
+    /// Sort order of the rows
+    order_by: Option<String>,
+    /// Highest number of rows
+    limit: Option<usize>,
+}
+
+impl QueryBuilder {
+    /// Starts a query without any conditions.
+    ///
+    /// # Arguments
+    ///
+    /// - `select` -> `SELECT ... FROM ...` part of the query
+    pub fn new(select: &str) -> Self {
+        QueryBuilder {
+            select: select.to_string(),
+            conditions: vec![],
+            bindings: vec![],
+            order_by: None,
+            limit: None,
+        }
+    }
+
+    /// Adds a condition comparing a column to a value.
+    ///
+    /// # Arguments
+    ///
+    /// - `sql` -> Condition with a single `?` for the value
+    /// - `value` -> Value bound to the placeholder
+    pub fn condition(mut self, sql: &str, value: impl Into<JsValue>) -> Self {
+        self.conditions.push(sql.to_string());
+        self.bindings.push(value.into());
+        self
+    }
+
+    /// Adds a condition only if the client chose a value for it.
+    ///
+    /// # Arguments
+    ///
+    /// - `sql` -> Condition with a single `?` for the value
+    /// - `value` -> Value bound to the placeholder; `None` leaves the condition out
+    pub fn optional_condition<T: Into<JsValue>>(self, sql: &str, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.condition(sql, value),
+            None => self,
+        }
+    }
+
+    /// Adds a condition without a value, e.g. a comparison of two columns.
+    ///
+    /// # Arguments
+    ///
+    /// - `sql` -> Condition without any placeholder
+    pub fn fixed_condition(mut self, sql: &str) -> Self {
+        self.conditions.push(sql.to_string());
+        self
+    }
+
+    /// Sorts the rows.
+    ///
+    /// # Arguments
+    ///
+    /// - `order_by` -> Content of the `ORDER BY` clause
+    pub fn order_by(mut self, order_by: &str) -> Self {
+        self.order_by = Some(order_by.to_string());
+        self
+    }
+
+    /// Returns at most a number of rows.
+    ///
+    /// # Arguments
+    ///
+    /// - `limit` -> Highest number of rows
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Writes the SQL of the query.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the SQL and the values to bind, in the order of their placeholders.
+    pub fn build(self) -> (String, Vec<JsValue>) {
+        let mut sql = self.select;
+
+        if !self.conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.conditions.join(" AND "));
+        }
+        if let Some(order_by) = self.order_by {
+            sql.push_str(" ORDER BY ");
+            sql.push_str(&order_by);
+        }
+        if let Some(limit) = self.limit {
+            sql.push_str(&format!(" LIMIT {limit}"));
+        }
+        sql.push(';');
+
+        (sql, self.bindings)
+    }
+}
@@ -0,0 +1,147 @@
+use axum::http::StatusCode;
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError, repositories::database::Database,
+    types::score_history::ScoreSnapshot, utils::time::server_time,
+};
+
+/// A database repository for interacting with the `score_history` table.
+#[derive(Clone)]
+pub struct ScoreHistoryRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl ScoreHistoryRepository {
+    /// Returns a fresh instance of `ScoreHistoryRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        ScoreHistoryRepository { db }
+    }
+
+    /// Stores the snapshots of all players after a round.
+    ///
+    /// All snapshots are written in one transaction. A round recorded twice keeps the latest
+    /// snapshots.
+    ///
+    /// # Arguments
+    ///
+    /// - `snapshots` -> One snapshot per player of the round
+    ///
+    /// # Returns an error if the query fails.
+    pub async fn record_round(
+        &self,
+        snapshots: &[ScoreSnapshot],
+    ) -> Result<(), DatabaseQueryError<ScoreSnapshot>> {
+        if snapshots.is_empty() {
+            return Ok(());
+        }
+
+        let recorded_at = server_time().to_rfc3339();
+        let statements = snapshots
+            .iter()
+            .map(|snapshot| {
+                self.db
+                    .query(
+                        "score_history.record_round",
+                        "INSERT OR REPLACE INTO score_history (game_id, round_number, player_id, player_name, score, card_count, recorded_at)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);",
+                    )
+                    .bind(&[
+                        JsValue::from(snapshot.game_id.clone()),
+                        JsValue::from(snapshot.round_number),
+                        JsValue::from(snapshot.player_id.clone()),
+                        JsValue::from(snapshot.player_name.clone()),
+                        JsValue::from(snapshot.score),
+                        JsValue::from(snapshot.card_count),
+                        JsValue::from(recorded_at.clone()),
+                    ])
+                    .unwrap()
+            })
+            .collect();
+
+        match self
+            .db
+            .batch("score_history.record_round", statements)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets all snapshots of a game, oldest round first.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    ///
+    /// # Returns the snapshots or an error if the query fails.
+    pub async fn get_history(
+        &self,
+        game_id: &str,
+    ) -> Result<Vec<ScoreSnapshot>, DatabaseQueryError<ScoreSnapshot>> {
+        let query_result = self
+            .db
+            .query(
+                "score_history.get_history",
+                "SELECT game_id, round_number, player_id, player_name, score, card_count
+                    FROM score_history WHERE game_id = ? ORDER BY round_number ASC, recorded_at ASC;",
+            )
+            .bind(&[JsValue::from(game_id)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_snapshots) => match fetched_snapshots.results::<ScoreSnapshot>() {
+                Ok(snapshots) => Ok(snapshots),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Deletes all snapshots of a game, e.g. when it starts over.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    ///
+    /// # Returns an error if the deletion fails.
+    pub async fn delete_history(
+        &self,
+        game_id: &str,
+    ) -> Result<(), DatabaseQueryError<ScoreSnapshot>> {
+        let query_result = self
+            .db
+            .query(
+                "score_history.delete_history",
+                "DELETE FROM score_history WHERE game_id = ?;",
+            )
+            .bind(&[JsValue::from(game_id)])
+            .unwrap()
+            .run()
+            .await;
+
+        query_result.map(|_| ()).map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+}
@@ -0,0 +1,205 @@
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError, repositories::database::Database,
+    types::season::Season,
+};
+
+// constants
+/// Key of the number of the current season in the `app_settings` table.
+const CURRENT_SEASON_SETTING_KEY: &str = "current_season";
+
+/// A database repository for interacting with the `seasons` table.
+#[derive(Clone)]
+pub struct SeasonRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl SeasonRepository {
+    /// Returns a fresh instance of `SeasonRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        SeasonRepository { db }
+    }
+
+    /// Retrieves the season referenced by the `current_season` setting.
+    ///
+    /// # Returns the current season or a `404 Not Found` error if no season was started yet.
+    pub async fn get_current_season(&self) -> Result<Season, DatabaseQueryError<Season>> {
+        let query_result = self
+            .db
+            .query(
+                "season.get_current_season",
+                "SELECT seasons.* FROM seasons
+                    JOIN app_settings ON app_settings.key = ?1 AND seasons.number = CAST(app_settings.value AS INTEGER);",
+            )
+            .bind(&[JsValue::from(CURRENT_SEASON_SETTING_KEY)])
+            .unwrap()
+            .first::<Season>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(season)) => Ok(season),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "No season was started yet!".to_string(),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Retrieves a season by its number.
+    ///
+    /// # Returns the season or a `404 Not Found` error if it doesn't exist.
+    pub async fn get_season(&self, number: usize) -> Result<Season, DatabaseQueryError<Season>> {
+        let query_result = self
+            .db
+            .query(
+                "season.get_season",
+                "SELECT * FROM seasons WHERE number = ?;",
+            )
+            .bind(&[JsValue::from(number)])
+            .unwrap()
+            .first::<Season>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(season)) => Ok(season),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "The season couldn't be found!".to_string(),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Retrieves the season containing a point in time.
+    ///
+    /// # Returns the season or `None` if the point in time is before the first season.
+    pub async fn get_season_at(
+        &self,
+        at: DateTime<Utc>,
+    ) -> Result<Option<Season>, DatabaseQueryError<Season>> {
+        let query_result = self
+            .db
+            .query(
+                "season.get_season_at",
+                "SELECT * FROM seasons WHERE starts_at <= ?1 AND (ends_at IS NULL OR ends_at > ?1)
+                    ORDER BY number DESC LIMIT 1;",
+            )
+            .bind(&[JsValue::from(at.to_rfc3339())])
+            .unwrap()
+            .first::<Season>(None)
+            .await;
+
+        query_result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Retrieves all seasons, newest first.
+    pub async fn get_seasons(&self) -> Result<Vec<Season>, DatabaseQueryError<Season>> {
+        let query_result = self
+            .db
+            .query(
+                "season.get_seasons",
+                "SELECT * FROM seasons ORDER BY number DESC;",
+            )
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_seasons) => match fetched_seasons.results::<Season>() {
+                Ok(seasons) => Ok(seasons),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Ends the current season and makes the next one current, in one transaction.
+    ///
+    /// The number of the next season is its primary key, so only one of two concurrent roll-overs
+    /// succeeds.
+    ///
+    /// # Arguments
+    ///
+    /// - `current` -> The season to end
+    /// - `next` -> The season to start; it starts when the current one ends
+    ///
+    /// # Returns the started season or an error if a query fails.
+    pub async fn roll_over(
+        &self,
+        current: &Season,
+        next: Season,
+    ) -> Result<Season, DatabaseQueryError<Season>> {
+        let starts_at = next.starts_at.to_rfc3339();
+        let statements = vec![
+            self.db
+                .query(
+                    "season.roll_over",
+                    "INSERT INTO seasons (number, name, starts_at) VALUES (?1, ?2, ?3);",
+                )
+                .bind(&[
+                    JsValue::from(next.number),
+                    JsValue::from(next.name.as_str()),
+                    JsValue::from(starts_at.as_str()),
+                ])
+                .unwrap(),
+            self.db
+                .query(
+                    "season.roll_over",
+                    "UPDATE seasons SET ends_at = ?1 WHERE number = ?2 AND ends_at IS NULL;",
+                )
+                .bind(&[
+                    JsValue::from(starts_at.as_str()),
+                    JsValue::from(current.number),
+                ])
+                .unwrap(),
+            self.db
+                .query(
+                    "season.roll_over",
+                    "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, CURRENT_TIMESTAMP)
+                        ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at;",
+                )
+                .bind(&[
+                    JsValue::from(CURRENT_SEASON_SETTING_KEY),
+                    JsValue::from(next.number.to_string()),
+                ])
+                .unwrap(),
+        ];
+
+        match self.db.batch("season.roll_over", statements).await {
+            Ok(_) => Ok(next),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
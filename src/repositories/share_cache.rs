@@ -0,0 +1,59 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::types::share::{SharePayload, SHARE_CACHE_SECONDS};
+
+// constants
+/// Highest number of previews kept by an isolate; a burst of shares of a few links fits easily.
+const MAX_CACHED_PAYLOADS: usize = 256;
+
+thread_local! {
+    /// Recently rendered previews of this isolate, keyed by the id of the game.
+    static SHARE_CACHE: RefCell<HashMap<String, (DateTime<Utc>, SharePayload)>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Gets the preview of a game if this isolate rendered it within `SHARE_CACHE_SECONDS`.
+///
+/// A shared link is fetched by every client of a chat at once, so most unfurls are answered
+/// without touching D1.
+///
+/// # Arguments
+///
+/// - `game_id` -> Game of the preview
+/// - `now` -> Point in time of the request
+pub fn get_cached_share_payload(game_id: &str, now: DateTime<Utc>) -> Option<SharePayload> {
+    SHARE_CACHE.with(|cache| {
+        cache
+            .borrow()
+            .get(game_id)
+            .filter(|(cached_at, _)| is_fresh(*cached_at, now))
+            .map(|(_, payload)| payload.clone())
+    })
+}
+
+/// Keeps the preview of a game for `SHARE_CACHE_SECONDS`.
+///
+/// Expired previews are dropped first; the preview isn't kept if the cache is still full.
+///
+/// # Arguments
+///
+/// - `game_id` -> Game of the preview
+/// - `payload` -> The rendered preview
+/// - `now` -> Point in time the preview was rendered
+pub fn cache_share_payload(game_id: &str, payload: &SharePayload, now: DateTime<Utc>) {
+    SHARE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+
+        cache.retain(|_, (cached_at, _)| is_fresh(*cached_at, now));
+        if cache.len() < MAX_CACHED_PAYLOADS {
+            cache.insert(game_id.to_string(), (now, payload.clone()));
+        }
+    });
+}
+
+/// Checks whether a preview cached at a point in time may still be served.
+fn is_fresh(cached_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now - cached_at < Duration::seconds(SHARE_CACHE_SECONDS)
+}
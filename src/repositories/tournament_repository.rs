@@ -0,0 +1,642 @@
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    enums::tournament_state::TournamentState,
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::tournament::{
+        Tournament, TournamentEntrant, TournamentMatch, TournamentSeat, UpdateTournamentDTO,
+    },
+    utils::d1_conversions::optional_binding,
+};
+
+/// A database repository for interacting with the `tournaments` table and the tables of their
+/// entrants, matches and seats.
+#[derive(Clone)]
+pub struct TournamentRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl TournamentRepository {
+    /// Returns a fresh instance of `TournamentRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        TournamentRepository { db }
+    }
+
+    /// Adds a new tournament to the database.
+    ///
+    /// # Returns the stored tournament or an error if the query fails.
+    pub async fn add_tournament(
+        &self,
+        tournament: Tournament,
+    ) -> Result<Tournament, DatabaseQueryError<Tournament>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.add_tournament",
+                "INSERT INTO tournaments (id, name, state, players_per_game, max_entrants, current_round, created_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7) RETURNING *;",
+            )
+            .bind(&[
+                JsValue::from(tournament.id.as_str()),
+                JsValue::from(tournament.name.as_str()),
+                JsValue::from(tournament.state.as_str()),
+                JsValue::from(tournament.players_per_game),
+                JsValue::from(tournament.max_entrants),
+                JsValue::from(tournament.current_round),
+                JsValue::from(tournament.created_at.to_rfc3339()),
+            ])
+            .unwrap()
+            .first::<Tournament>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(tournament)) => Ok(tournament),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to add tournament to the database".to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Retrieves a tournament by its id.
+    ///
+    /// # Returns the tournament or a `404 Not Found` error if it doesn't exist.
+    pub async fn get_tournament(
+        &self,
+        tournament_id: &str,
+    ) -> Result<Tournament, DatabaseQueryError<Tournament>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.get_tournament",
+                "SELECT * FROM tournaments WHERE id = ?;",
+            )
+            .bind(&[JsValue::from(tournament_id)])
+            .unwrap()
+            .first::<Tournament>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(tournament)) => Ok(tournament),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "The tournament couldn't be found!".to_string(),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Retrieves the newest tournaments in a state.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` -> State of the tournaments
+    /// - `limit` -> Highest number of tournaments
+    pub async fn get_tournaments(
+        &self,
+        state: TournamentState,
+        limit: usize,
+    ) -> Result<Vec<Tournament>, DatabaseQueryError<Tournament>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.get_tournaments",
+                "SELECT * FROM tournaments WHERE state = ?1 ORDER BY created_at DESC LIMIT ?2;",
+            )
+            .bind(&[JsValue::from(state.as_str()), JsValue::from(limit)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_tournaments) => match fetched_tournaments.results::<Tournament>() {
+                Ok(tournaments) => Ok(tournaments),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Retrieves the tournament a game was played for.
+    ///
+    /// # Returns the id of the tournament or `None` if the game doesn't belong to one.
+    pub async fn get_tournament_id_of_game(
+        &self,
+        game_id: &str,
+    ) -> Result<Option<String>, DatabaseQueryError<TournamentMatch>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.get_tournament_id_of_game",
+                "SELECT tournament_id FROM tournament_matches WHERE game_id = ?;",
+            )
+            .bind(&[JsValue::from(game_id)])
+            .unwrap()
+            .first::<String>(Some("tournament_id"))
+            .await;
+
+        query_result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Changes the name or the limits of a tournament that didn't start yet.
+    ///
+    /// # Returns the changed tournament, `None` if it isn't open for registration anymore.
+    pub async fn update_tournament(
+        &self,
+        tournament_id: &str,
+        update: &UpdateTournamentDTO,
+    ) -> Result<Option<Tournament>, DatabaseQueryError<Tournament>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.update_tournament",
+                "UPDATE tournaments SET name = COALESCE(?2, name), players_per_game = COALESCE(?3, players_per_game),
+                    max_entrants = COALESCE(?4, max_entrants)
+                    WHERE id = ?1 AND state = ?5 RETURNING *;",
+            )
+            .bind(&[
+                JsValue::from(tournament_id),
+                optional_binding(update.name.as_deref().map(str::trim)),
+                optional_binding(update.players_per_game),
+                optional_binding(update.max_entrants),
+                JsValue::from(TournamentState::Registration.as_str()),
+            ])
+            .unwrap()
+            .first::<Tournament>(None)
+            .await;
+
+        query_result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Moves a tournament from one of the passed states into another one.
+    ///
+    /// Starting a tournament sets `started_at`, finishing or cancelling it sets `finished_at`.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_id` -> The tournament
+    /// - `from` -> States the tournament has to be in
+    /// - `to` -> The new state
+    /// - `winner_entrant_id` -> Winner of a finished tournament
+    /// - `at` -> Point in time of the change
+    ///
+    /// # Returns `true` if the tournament changed, `false` if it wasn't in one of the states.
+    pub async fn change_state(
+        &self,
+        tournament_id: &str,
+        from: &[TournamentState],
+        to: TournamentState,
+        winner_entrant_id: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<bool, DatabaseQueryError<Tournament>> {
+        let (started_at, finished_at) = match to {
+            TournamentState::Running => (Some(at.to_rfc3339()), None),
+            TournamentState::Finished | TournamentState::Cancelled => (None, Some(at.to_rfc3339())),
+            TournamentState::Registration => (None, None),
+        };
+        let placeholders = (0..from.len())
+            .map(|index| format!("?{}", index + 6))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let query = format!(
+            "UPDATE tournaments SET state = ?2, winner_entrant_id = COALESCE(?3, winner_entrant_id),
+                started_at = COALESCE(?4, started_at), finished_at = COALESCE(?5, finished_at)
+                WHERE id = ?1 AND state IN ({placeholders});"
+        );
+
+        let mut bindings = vec![
+            JsValue::from(tournament_id),
+            JsValue::from(to.as_str()),
+            optional_binding(winner_entrant_id),
+            optional_binding(started_at),
+            optional_binding(finished_at),
+        ];
+        bindings.extend(from.iter().map(|state| JsValue::from(state.as_str())));
+
+        let query_result = self
+            .db
+            .query("tournament.change_state", query)
+            .bind(&bindings)
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(result) => Ok(result
+                .meta()
+                .ok()
+                .flatten()
+                .and_then(|meta| meta.changes)
+                .unwrap_or_default()
+                > 0),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Moves a running tournament into the next round.
+    ///
+    /// Only one worker can claim a round, so its games are never created twice.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_id` -> The tournament
+    /// - `previous_round` -> Round the tournament has to be in
+    /// - `round` -> The new round
+    ///
+    /// # Returns `true` if this call claimed the round.
+    pub async fn claim_round(
+        &self,
+        tournament_id: &str,
+        previous_round: usize,
+        round: usize,
+    ) -> Result<bool, DatabaseQueryError<Tournament>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.claim_round",
+                "UPDATE tournaments SET current_round = ?3 WHERE id = ?1 AND current_round = ?2 AND state = ?4;",
+            )
+            .bind(&[
+                JsValue::from(tournament_id),
+                JsValue::from(previous_round),
+                JsValue::from(round),
+                JsValue::from(TournamentState::Running.as_str()),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        match query_result {
+            Ok(result) => Ok(result
+                .meta()
+                .ok()
+                .flatten()
+                .and_then(|meta| meta.changes)
+                .unwrap_or_default()
+                > 0),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Removes the matches of a round that couldn't be set up and moves the tournament back.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_id` -> The tournament
+    /// - `round` -> The round to remove
+    pub async fn discard_round(
+        &self,
+        tournament_id: &str,
+        round: usize,
+    ) -> Result<(), DatabaseQueryError<TournamentMatch>> {
+        let statements = vec![
+            self.db
+                .query(
+                    "tournament.discard_round",
+                    "DELETE FROM tournament_seats WHERE tournament_id = ?1 AND round = ?2;",
+                )
+                .bind(&[JsValue::from(tournament_id), JsValue::from(round)])
+                .unwrap(),
+            self.db
+                .query(
+                    "tournament.discard_round",
+                    "DELETE FROM tournament_matches WHERE tournament_id = ?1 AND round = ?2;",
+                )
+                .bind(&[JsValue::from(tournament_id), JsValue::from(round)])
+                .unwrap(),
+            self.db
+                .query(
+                    "tournament.discard_round",
+                    "UPDATE tournaments SET current_round = ?3 WHERE id = ?1 AND current_round = ?2;",
+                )
+                .bind(&[
+                    JsValue::from(tournament_id),
+                    JsValue::from(round),
+                    JsValue::from(round - 1),
+                ])
+                .unwrap(),
+        ];
+
+        self.db
+            .batch("tournament.discard_round", statements)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+
+    /// Stores a match together with the seats of its entrants.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_match` -> The match; a bye already has its winner
+    /// - `seats` -> Entrant and player of every seat of the match
+    pub async fn add_match(
+        &self,
+        tournament_match: &TournamentMatch,
+        seats: &[(String, Option<String>)],
+    ) -> Result<(), DatabaseQueryError<TournamentMatch>> {
+        let mut statements = vec![self
+            .db
+            .query(
+                "tournament.add_match",
+                "INSERT INTO tournament_matches (tournament_id, round, slot, game_id, winner_entrant_id, finished_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6);",
+            )
+            .bind(&[
+                JsValue::from(tournament_match.tournament_id.as_str()),
+                JsValue::from(tournament_match.round),
+                JsValue::from(tournament_match.slot),
+                optional_binding(tournament_match.game_id.as_deref()),
+                optional_binding(tournament_match.winner_entrant_id.as_deref()),
+                optional_binding(
+                    tournament_match
+                        .finished_at
+                        .map(|finished_at| finished_at.to_rfc3339()),
+                ),
+            ])
+            .unwrap()];
+        for (entrant_id, player_id) in seats {
+            statements.push(
+                self.db
+                    .query(
+                        "tournament.add_match",
+                        "INSERT INTO tournament_seats (tournament_id, round, slot, entrant_id, player_id)
+                            VALUES (?1, ?2, ?3, ?4, ?5);",
+                    )
+                    .bind(&[
+                        JsValue::from(tournament_match.tournament_id.as_str()),
+                        JsValue::from(tournament_match.round),
+                        JsValue::from(tournament_match.slot),
+                        JsValue::from(entrant_id.as_str()),
+                        optional_binding(player_id.as_deref()),
+                    ])
+                    .unwrap(),
+            );
+        }
+
+        self.db
+            .batch("tournament.add_match", statements)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+
+    /// Records the winner of a match and eliminates the other entrants of it.
+    ///
+    /// A match that already has a winner isn't changed, so deciding a match twice is harmless.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_match` -> The decided match
+    /// - `winner_entrant_id` -> Entrant who advances
+    /// - `finished_at` -> Point in time the match was decided
+    pub async fn finish_match(
+        &self,
+        tournament_match: &TournamentMatch,
+        winner_entrant_id: &str,
+        finished_at: DateTime<Utc>,
+    ) -> Result<(), DatabaseQueryError<TournamentMatch>> {
+        let statements = vec![
+            self.db
+                .query(
+                    "tournament.finish_match",
+                    "UPDATE tournament_entrants SET eliminated_in_round = ?2
+                        WHERE eliminated_in_round IS NULL AND id != ?4 AND id IN (
+                            SELECT entrant_id FROM tournament_seats WHERE tournament_id = ?1 AND round = ?2 AND slot = ?3
+                        )
+                        AND EXISTS (
+                            SELECT 1 FROM tournament_matches
+                            WHERE tournament_id = ?1 AND round = ?2 AND slot = ?3 AND winner_entrant_id IS NULL
+                        );",
+                )
+                .bind(&[
+                    JsValue::from(tournament_match.tournament_id.as_str()),
+                    JsValue::from(tournament_match.round),
+                    JsValue::from(tournament_match.slot),
+                    JsValue::from(winner_entrant_id),
+                ])
+                .unwrap(),
+            self.db
+                .query(
+                    "tournament.finish_match",
+                    "UPDATE tournament_matches SET winner_entrant_id = ?4, finished_at = ?5
+                        WHERE tournament_id = ?1 AND round = ?2 AND slot = ?3 AND winner_entrant_id IS NULL;",
+                )
+                .bind(&[
+                    JsValue::from(tournament_match.tournament_id.as_str()),
+                    JsValue::from(tournament_match.round),
+                    JsValue::from(tournament_match.slot),
+                    JsValue::from(winner_entrant_id),
+                    JsValue::from(finished_at.to_rfc3339()),
+                ])
+                .unwrap(),
+        ];
+
+        self.db
+            .batch("tournament.finish_match", statements)
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+
+    /// Retrieves the matches of a tournament by round and slot.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_id` -> The tournament
+    /// - `round` -> Only the matches of this round; all matches if `None`
+    pub async fn get_matches(
+        &self,
+        tournament_id: &str,
+        round: Option<usize>,
+    ) -> Result<Vec<TournamentMatch>, DatabaseQueryError<TournamentMatch>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.get_matches",
+                "SELECT * FROM tournament_matches WHERE tournament_id = ?1 AND (?2 IS NULL OR round = ?2)
+                    ORDER BY round ASC, slot ASC;",
+            )
+            .bind(&[JsValue::from(tournament_id), optional_binding(round)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_matches) => match fetched_matches.results::<TournamentMatch>() {
+                Ok(matches) => Ok(matches),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Retrieves the seats of a tournament with the seed and name of their entrants.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_id` -> The tournament
+    /// - `round` -> Only the seats of this round; all seats if `None`
+    pub async fn get_seats(
+        &self,
+        tournament_id: &str,
+        round: Option<usize>,
+    ) -> Result<Vec<TournamentSeat>, DatabaseQueryError<TournamentSeat>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.get_seats",
+                "SELECT s.round, s.slot, s.entrant_id, s.player_id, e.seed, e.player_name
+                    FROM tournament_seats s JOIN tournament_entrants e ON e.id = s.entrant_id
+                    WHERE s.tournament_id = ?1 AND (?2 IS NULL OR s.round = ?2)
+                    ORDER BY s.round ASC, s.slot ASC, e.seed ASC;",
+            )
+            .bind(&[JsValue::from(tournament_id), optional_binding(round)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_seats) => match fetched_seats.results::<TournamentSeat>() {
+                Ok(seats) => Ok(seats),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Registers an entrant and assigns the next seed, as long as the tournament isn't full.
+    ///
+    /// # Arguments
+    ///
+    /// - `entrant` -> The new entrant
+    /// - `max_entrants` -> Most entrants of the tournament
+    ///
+    /// # Returns the stored entrant, `None` if the tournament is already full.
+    pub async fn add_entrant(
+        &self,
+        entrant: TournamentEntrant,
+        max_entrants: usize,
+    ) -> Result<Option<TournamentEntrant>, DatabaseQueryError<TournamentEntrant>> {
+        // counting and inserting in one statement keeps concurrent registrations below the limit
+        let query_result = self
+            .db
+            .query(
+                "tournament.add_entrant",
+                "INSERT INTO tournament_entrants (id, tournament_id, player_name, fingerprint, seed, registered_at)
+                    SELECT ?1, ?2, ?3, ?4, COALESCE(MAX(seed), 0) + 1, ?5
+                    FROM tournament_entrants WHERE tournament_id = ?2
+                    HAVING COUNT(*) < ?6
+                    RETURNING *;",
+            )
+            .bind(&[
+                JsValue::from(entrant.id.as_str()),
+                JsValue::from(entrant.tournament_id.as_str()),
+                JsValue::from(entrant.player_name.as_str()),
+                optional_binding(entrant.fingerprint.as_deref()),
+                JsValue::from(entrant.registered_at.to_rfc3339()),
+                JsValue::from(max_entrants),
+            ])
+            .unwrap()
+            .first::<TournamentEntrant>(None)
+            .await;
+
+        query_result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Retrieves the entrants of a tournament, best seed first.
+    pub async fn get_entrants(
+        &self,
+        tournament_id: &str,
+    ) -> Result<Vec<TournamentEntrant>, DatabaseQueryError<TournamentEntrant>> {
+        let query_result = self
+            .db
+            .query(
+                "tournament.get_entrants",
+                "SELECT * FROM tournament_entrants WHERE tournament_id = ? ORDER BY seed ASC;",
+            )
+            .bind(&[JsValue::from(tournament_id)])
+            .unwrap()
+            .all()
+            .await;
+
+        match query_result {
+            Ok(fetched_entrants) => match fetched_entrants.results::<TournamentEntrant>() {
+                Ok(entrants) => Ok(entrants),
+                Err(err) => Err(DatabaseQueryError::new(
+                    err.to_string(),
+                    None,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            },
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+}
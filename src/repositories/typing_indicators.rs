@@ -0,0 +1,127 @@
+use axum::http::StatusCode;
+use log::warn;
+use worker::{
+    send::{SendFuture, SendWrapper},
+    Env, Method, ObjectNamespace, Request, RequestInit,
+};
+
+use crate::{
+    durable::typing_indicator::TYPING_INDICATOR_BINDING,
+    errors::database_query_error::DatabaseQueryError, types::player::Player,
+};
+
+// constants
+/// Base URL of the internal requests to an indicator; the host is ignored by Durable Objects.
+const INDICATOR_URL: &str = "https://typing-indicator";
+
+/// Client of the `TypingIndicator` Durable Objects.
+#[derive(Clone)]
+pub struct TypingIndicators {
+    /// Namespace of the `TypingIndicator` Durable Objects
+    namespace: SendWrapper<ObjectNamespace>,
+}
+
+impl TypingIndicators {
+    /// Creates the client if the indicators are bound.
+    ///
+    /// # Arguments
+    ///
+    /// - `env` -> Environment of the worker
+    ///
+    /// # Returns
+    ///
+    /// `None` if the binding is missing; nobody is shown as typing then.
+    pub fn from_env(env: &Env) -> Option<Self> {
+        match env.durable_object(TYPING_INDICATOR_BINDING) {
+            Ok(namespace) => Some(TypingIndicators {
+                namespace: SendWrapper(namespace),
+            }),
+            Err(err) => {
+                warn!("Typing indicator binding not found, typing indicators are disabled: {err}");
+                None
+            }
+        }
+    }
+
+    /// Marks a player of a game as typing.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    /// - `player_id` -> Id of the typing player
+    pub async fn mark_typing(
+        &self,
+        game_id: &str,
+        player_id: &str,
+    ) -> Result<(), DatabaseQueryError<Player>> {
+        let path = format!("/typing/{player_id}");
+
+        match self.send(game_id, Method::Post, &path).await? {
+            (204, _) => Ok(()),
+            (status, _) => Err(indicator_error(status)),
+        }
+    }
+
+    /// Gets the players of a game who are typing right now.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    pub async fn typing_players(
+        &self,
+        game_id: &str,
+    ) -> Result<Vec<String>, DatabaseQueryError<Player>> {
+        match self.send(game_id, Method::Get, "/typing").await? {
+            (200, player_ids) => Ok(player_ids.unwrap_or_default()),
+            (status, _) => Err(indicator_error(status)),
+        }
+    }
+
+    // ----- utility functions of the 'TypingIndicators' struct -----
+
+    /// Sends an internal request to the indicator of a game.
+    ///
+    /// # Returns
+    ///
+    /// The status code of the indicator and the ids of the typing players, if it returned them.
+    async fn send(
+        &self,
+        game_id: &str,
+        method: Method,
+        path: &str,
+    ) -> Result<(u16, Option<Vec<String>>), DatabaseQueryError<Player>> {
+        let namespace = self.namespace.clone();
+        let game_id = game_id.to_string();
+        let url = format!("{INDICATOR_URL}{path}");
+
+        let result = SendFuture::new(async move {
+            let mut init = RequestInit::new();
+            init.with_method(method);
+            let request = Request::new_with_init(&url, &init)?;
+
+            let stub = namespace.id_from_name(&game_id)?.get_stub()?;
+            let mut response = stub.fetch_with_request(request).await?;
+            let status = response.status_code();
+            let player_ids = match status {
+                200 => Some(response.json::<Vec<String>>().await?),
+                _ => None,
+            };
+
+            Ok::<_, worker::Error>((status, player_ids))
+        })
+        .await;
+
+        result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+}
+
+/// Creates the error of an unexpected status code returned by an indicator.
+fn indicator_error(status: u16) -> DatabaseQueryError<Player> {
+    DatabaseQueryError::new(
+        format!("The typing indicator answered with the unexpected status {status}"),
+        None,
+        StatusCode::INTERNAL_SERVER_ERROR,
+    )
+}
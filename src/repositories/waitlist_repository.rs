@@ -0,0 +1,378 @@
+use axum::{http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use wasm_bindgen::JsValue;
+use worker::D1Result;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::waitlist::{WaitlistEntry, WaitlistStatus},
+    utils::d1_conversions::optional_binding,
+};
+
+/// Position of an entry as counted by the database.
+#[derive(Deserialize)]
+struct PositionRow {
+    /// Waiting entries that joined before the entry or at the same time, including itself
+    position: usize,
+    /// Waiting entries of the game in total
+    waiting: usize,
+}
+
+/// A database repository for interacting with the `waitlist` table.
+#[derive(Clone)]
+pub struct WaitlistRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl WaitlistRepository {
+    /// Returns a fresh instance of `WaitlistRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        WaitlistRepository { db }
+    }
+
+    /// Puts a client on the waitlist of a game.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry` -> The entry to be inserted.
+    ///
+    /// # Returns the stored entry or an error if the insertion fails.
+    pub async fn add_entry(
+        &self,
+        entry: WaitlistEntry,
+    ) -> Result<WaitlistEntry, DatabaseQueryError<WaitlistEntry>> {
+        let query = "INSERT INTO waitlist (id, game_id, name, avatar_url, color, emoji, fingerprint, joined_at, token_hash)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) RETURNING *;";
+        let params = vec![
+            JsValue::from(entry.id.clone()),
+            JsValue::from(entry.game_id.clone()),
+            JsValue::from(entry.name.clone()),
+            optional_binding(entry.profile.avatar_url.clone()),
+            optional_binding(entry.profile.color.clone()),
+            optional_binding(entry.profile.emoji.clone()),
+            optional_binding(entry.fingerprint.clone()),
+            JsValue::from(entry.joined_at.to_rfc3339()),
+            optional_binding(entry.token_hash.clone()),
+        ];
+
+        let query_result = self
+            .db
+            .query("waitlist.add_entry", query)
+            .bind(&params)
+            .unwrap()
+            .first::<WaitlistEntry>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(stored_entry)) => Ok(stored_entry),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to put the client on the waitlist".to_string(),
+                Some(Json(entry)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(entry)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets an entry of the waitlist of a game.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_id` -> Id of the entry.
+    /// - `game_id` -> Id of the game the entry has to belong to.
+    ///
+    /// # Returns the entry, or an error with status `404` if the game has no such entry.
+    pub async fn get_entry(
+        &self,
+        entry_id: &str,
+        game_id: &str,
+    ) -> Result<WaitlistEntry, DatabaseQueryError<WaitlistEntry>> {
+        let query_result = self
+            .db
+            .query(
+                "waitlist.get_entry",
+                "SELECT * FROM waitlist WHERE id = ?1 AND game_id = ?2;",
+            )
+            .bind(&[JsValue::from(entry_id), JsValue::from(game_id)])
+            .unwrap()
+            .first::<WaitlistEntry>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(entry)) => Ok(entry),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "The entry isn't on the waitlist of the game!".to_string(),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets the entry of a game that waits the longest.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game.
+    ///
+    /// # Returns the entry, `None` if nobody waits, or an error if the query fails.
+    pub async fn get_first_waiting(
+        &self,
+        game_id: &str,
+    ) -> Result<Option<WaitlistEntry>, DatabaseQueryError<WaitlistEntry>> {
+        self.db
+            .query(
+                "waitlist.get_first_waiting",
+                "SELECT * FROM waitlist WHERE game_id = ?1 AND player_id IS NULL
+                    ORDER BY joined_at, id LIMIT 1;",
+            )
+            .bind(&[JsValue::from(game_id)])
+            .unwrap()
+            .first::<WaitlistEntry>(None)
+            .await
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+
+    /// Counts where an entry stands in the waitlist of its game.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry` -> The entry.
+    ///
+    /// # Returns the status of the entry or an error if the query fails.
+    pub async fn get_status(
+        &self,
+        entry: &WaitlistEntry,
+    ) -> Result<WaitlistStatus, DatabaseQueryError<WaitlistEntry>> {
+        let query_result = self
+            .db
+            .query(
+                "waitlist.get_status",
+                "SELECT COALESCE(SUM(joined_at < ?2 OR (joined_at = ?2 AND id <= ?3)), 0) AS position,
+                    COUNT(*) AS waiting
+                    FROM waitlist WHERE game_id = ?1 AND player_id IS NULL;",
+            )
+            .bind(&[
+                JsValue::from(entry.game_id.clone()),
+                JsValue::from(entry.joined_at.to_rfc3339()),
+                JsValue::from(entry.id.clone()),
+            ])
+            .unwrap()
+            .first::<PositionRow>(None)
+            .await
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })?;
+        let counts = query_result.unwrap_or(PositionRow {
+            position: 0,
+            waiting: 0,
+        });
+
+        Ok(WaitlistStatus {
+            entry_id: entry.id.clone(),
+            game_id: entry.game_id.clone(),
+            position: match entry.player_id {
+                Some(_) => None,
+                None => Some(counts.position),
+            },
+            waiting: counts.waiting,
+            token: None,
+        })
+    }
+
+    /// Marks a waiting entry as promoted to a player.
+    ///
+    /// Only an entry that still waits is promoted, so two seats freeing up at the same time can't
+    /// promote the same entry twice. The seats are counted in the same statement, including the
+    /// promoted entries whose player isn't added yet, so racing promotions can't seat more than
+    /// `max_players`.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_id` -> Id of the entry.
+    /// - `player_id` -> Id of the player taking the seat.
+    /// - `max_players` -> Seats of the game.
+    /// - `now` -> Point in time of the promotion.
+    ///
+    /// # Returns `true` if the entry was promoted, `false` if it was promoted or removed before or
+    /// the game has no free seat.
+    pub async fn promote_entry(
+        &self,
+        entry_id: &str,
+        player_id: &str,
+        max_players: usize,
+        now: DateTime<Utc>,
+    ) -> Result<bool, DatabaseQueryError<WaitlistEntry>> {
+        let query_result = self
+            .db
+            .query(
+                "waitlist.promote_entry",
+                "UPDATE waitlist SET player_id = ?2, promoted_at = ?3
+                    WHERE id = ?1 AND player_id IS NULL
+                    AND (SELECT COUNT(*) FROM players WHERE game_id = waitlist.game_id)
+                        + (SELECT COUNT(*) FROM waitlist AS promoted WHERE promoted.game_id = waitlist.game_id
+                            AND promoted.player_id IS NOT NULL
+                            AND promoted.player_id NOT IN (SELECT id FROM players WHERE game_id = waitlist.game_id))
+                        < ?4;",
+            )
+            .bind(&[
+                JsValue::from(entry_id),
+                JsValue::from(player_id),
+                JsValue::from(now.to_rfc3339()),
+                JsValue::from(max_players),
+            ])
+            .unwrap()
+            .run()
+            .await;
+
+        changed_any(query_result)
+    }
+
+    /// Puts a promoted entry back in line, e.g. when its player couldn't be added.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_id` -> Id of the entry.
+    ///
+    /// # Returns an error if the query fails.
+    pub async fn release_entry(
+        &self,
+        entry_id: &str,
+    ) -> Result<(), DatabaseQueryError<WaitlistEntry>> {
+        self.db
+            .query(
+                "waitlist.release_entry",
+                "UPDATE waitlist SET player_id = NULL, promoted_at = NULL WHERE id = ?1;",
+            )
+            .bind(&[JsValue::from(entry_id)])
+            .unwrap()
+            .run()
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+
+    /// Hands the seat of a promoted entry to the client and takes the entry off the waitlist.
+    ///
+    /// The rejoin token of the player is stored before the entry is deleted, both in one
+    /// transaction, so the entry is only gone once the session of the client is usable. Only one
+    /// request can take the entry, so its session is delivered exactly once.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_id` -> Id of the entry.
+    /// - `player_id` -> Id of the player the entry was promoted to.
+    /// - `rejoin_token_hash` -> Hash of the rejoin token handed to the client.
+    ///
+    /// # Returns an error with status `404` if the entry waits or was taken before.
+    pub async fn hand_over_entry(
+        &self,
+        entry_id: &str,
+        player_id: &str,
+        rejoin_token_hash: &str,
+    ) -> Result<(), DatabaseQueryError<WaitlistEntry>> {
+        let statements = vec![
+            self.db
+                .query(
+                    "waitlist.hand_over_entry",
+                    "UPDATE players SET rejoin_token_hash = ?3 WHERE id = ?2
+                        AND EXISTS (SELECT 1 FROM waitlist WHERE id = ?1 AND player_id = ?2);",
+                )
+                .bind(&[
+                    JsValue::from(entry_id),
+                    JsValue::from(player_id),
+                    JsValue::from(rejoin_token_hash),
+                ])
+                .unwrap(),
+            self.db
+                .query(
+                    "waitlist.hand_over_entry",
+                    "DELETE FROM waitlist WHERE id = ?1 AND player_id = ?2;",
+                )
+                .bind(&[JsValue::from(entry_id), JsValue::from(player_id)])
+                .unwrap(),
+        ];
+
+        let results = self
+            .db
+            .batch("waitlist.hand_over_entry", statements)
+            .await
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })?;
+
+        // the delete ran last, so its result tells whether this request took the entry
+        match results.into_iter().last() {
+            Some(result) if changed_any(Ok(result))? => Ok(()),
+            _ => Err(DatabaseQueryError::new(
+                "The entry isn't on the waitlist of the game!".to_string(),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+        }
+    }
+
+    /// Takes an entry off the waitlist.
+    ///
+    /// # Arguments
+    ///
+    /// - `entry_id` -> Id of the entry.
+    ///
+    /// # Returns an error if the query fails.
+    pub async fn remove_entry(
+        &self,
+        entry_id: &str,
+    ) -> Result<(), DatabaseQueryError<WaitlistEntry>> {
+        self.db
+            .query(
+                "waitlist.remove_entry",
+                "DELETE FROM waitlist WHERE id = ?1;",
+            )
+            .bind(&[JsValue::from(entry_id)])
+            .unwrap()
+            .run()
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+}
+
+/// Returns whether a statement changed any row.
+fn changed_any(
+    query_result: worker::Result<D1Result>,
+) -> Result<bool, DatabaseQueryError<WaitlistEntry>> {
+    match query_result {
+        Ok(result) => Ok(result
+            .meta()
+            .ok()
+            .flatten()
+            .and_then(|meta| meta.changes)
+            .is_some_and(|changes| changes > 0)),
+        Err(err) => Err(DatabaseQueryError::new(
+            err.to_string(),
+            None,
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )),
+    }
+}
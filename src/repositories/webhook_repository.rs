@@ -0,0 +1,276 @@
+use axum::{http::StatusCode, Json};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    errors::database_query_error::DatabaseQueryError,
+    repositories::database::Database,
+    types::webhook::{Webhook, WebhookDeadLetter},
+    utils::d1_conversions::optional_binding,
+};
+
+/// A database repository for interacting with the `webhooks` and `webhook_dead_letters` tables.
+#[derive(Clone)]
+pub struct WebhookRepository {
+    /// Database pointer to execute queries.
+    db: Database,
+}
+
+impl WebhookRepository {
+    /// Returns a fresh instance of `WebhookRepository` struct.
+    ///
+    /// # Arguments
+    ///
+    /// - `db` -> Database service pointer to execute queries.
+    pub fn new(db: Database) -> Self {
+        WebhookRepository { db }
+    }
+
+    /// Registers a new webhook.
+    ///
+    /// # Arguments
+    ///
+    /// - `webhook` -> The webhook to be inserted.
+    ///
+    /// # Returns the stored webhook or an error if the insertion fails.
+    pub async fn add_webhook(
+        &self,
+        webhook: Webhook,
+    ) -> Result<Webhook, DatabaseQueryError<Webhook>> {
+        let query = "INSERT INTO webhooks (id, game_id, url, created_at, secret) 
+            VALUES (?1, ?2, ?3, ?4, ?5) RETURNING *;";
+        let params = vec![
+            JsValue::from(webhook.id.clone()),
+            optional_binding(webhook.game_id.clone()),
+            JsValue::from(webhook.url.clone()),
+            JsValue::from(webhook.created_at.to_rfc3339()),
+            optional_binding(webhook.secret.clone()),
+        ];
+
+        let query_result = self
+            .db
+            .query("webhook.add_webhook", query)
+            .bind(&params)
+            .unwrap()
+            .first::<Webhook>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(stored_webhook)) => Ok(stored_webhook),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "Failed to store the webhook".to_string(),
+                Some(Json(webhook)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(webhook)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Gets a webhook by its id.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` -> Id of the webhook.
+    ///
+    /// # Returns the webhook, `None` if it was removed, or an error if the query fails.
+    pub async fn get_webhook(
+        &self,
+        id: &str,
+    ) -> Result<Option<Webhook>, DatabaseQueryError<Webhook>> {
+        let query_result = self
+            .db
+            .query(
+                "webhook.get_webhook",
+                "SELECT * FROM webhooks WHERE id = ?;",
+            )
+            .bind(&[JsValue::from(id)])
+            .unwrap()
+            .first::<Webhook>(None)
+            .await;
+
+        query_result.map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Gets the webhooks registered by a game or, with `None`, by the operators.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game; `None` for the webhooks of the operators.
+    ///
+    /// # Returns the webhooks, oldest first, or an error if the query fails.
+    pub async fn get_webhooks(
+        &self,
+        game_id: Option<&str>,
+    ) -> Result<Vec<Webhook>, DatabaseQueryError<Webhook>> {
+        let query_result = self
+            .db
+            .query(
+                "webhook.get_webhooks",
+                "SELECT * FROM webhooks WHERE game_id IS ? ORDER BY created_at;",
+            )
+            .bind(&[optional_binding(game_id)])
+            .unwrap()
+            .all()
+            .await;
+
+        collect_webhooks(query_result)
+    }
+
+    /// Gets every webhook that receives the events of a game.
+    ///
+    /// Includes the webhooks of the game itself and the ones of the operators.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game.
+    ///
+    /// # Returns the webhooks or an error if the query fails.
+    pub async fn get_subscribed_webhooks(
+        &self,
+        game_id: &str,
+    ) -> Result<Vec<Webhook>, DatabaseQueryError<Webhook>> {
+        let query_result = self
+            .db
+            .query(
+                "webhook.get_subscribed_webhooks",
+                "SELECT * FROM webhooks WHERE game_id = ? OR game_id IS NULL;",
+            )
+            .bind(&[JsValue::from(game_id)])
+            .unwrap()
+            .all()
+            .await;
+
+        collect_webhooks(query_result)
+    }
+
+    /// Removes a webhook.
+    ///
+    /// The webhook is only removed if it belongs to the passed game, so a host can't remove the
+    /// webhooks of other games or of the operators.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` -> Id of the webhook.
+    /// - `game_id` -> Id of the game; `None` for a webhook of the operators.
+    ///
+    /// # Returns `Ok(())` if the webhook was removed, a `404` error if there was no such webhook,
+    /// or an error if the query fails.
+    pub async fn remove_webhook(
+        &self,
+        id: &str,
+        game_id: Option<&str>,
+    ) -> Result<(), DatabaseQueryError<Webhook>> {
+        let query_result = self
+            .db
+            .query(
+                "webhook.remove_webhook",
+                "DELETE FROM webhooks WHERE id = ?1 AND game_id IS ?2 RETURNING *;",
+            )
+            .bind(&[JsValue::from(id), optional_binding(game_id)])
+            .unwrap()
+            .first::<Webhook>(None)
+            .await;
+
+        match query_result {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err(DatabaseQueryError::new(
+                "The webhook doesn't exist!".to_string(),
+                None,
+                StatusCode::NOT_FOUND,
+            )),
+            Err(err) => Err(DatabaseQueryError::new(
+                err.to_string(),
+                None,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    /// Logs a delivery that failed on every attempt.
+    ///
+    /// # Arguments
+    ///
+    /// - `dead_letter` -> The failed delivery.
+    ///
+    /// # Returns `Ok(())` or an error if the insertion fails.
+    pub async fn add_dead_letter(
+        &self,
+        dead_letter: WebhookDeadLetter,
+    ) -> Result<(), DatabaseQueryError<WebhookDeadLetter>> {
+        let query = "INSERT INTO webhook_dead_letters
+            (id, webhook_id, delivery_id, event, game_id, payload, attempts, last_error, failed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9);";
+        let params = vec![
+            JsValue::from(dead_letter.id.clone()),
+            JsValue::from(dead_letter.webhook_id.clone()),
+            JsValue::from(dead_letter.delivery_id.clone()),
+            JsValue::from(dead_letter.event.as_str()),
+            JsValue::from(dead_letter.game_id.clone()),
+            JsValue::from(dead_letter.payload.clone()),
+            JsValue::from(dead_letter.attempts),
+            JsValue::from(dead_letter.last_error.clone()),
+            JsValue::from(dead_letter.failed_at.to_rfc3339()),
+        ];
+
+        let query_result = self
+            .db
+            .query("webhook.add_dead_letter", query)
+            .bind(&params)
+            .unwrap()
+            .run()
+            .await;
+
+        query_result.map(|_| ()).map_err(|err| {
+            DatabaseQueryError::new(
+                err.to_string(),
+                Some(Json(dead_letter)),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        })
+    }
+
+    /// Gets the latest deliveries that failed on every attempt.
+    ///
+    /// # Arguments
+    ///
+    /// - `limit` -> Highest number of entries.
+    ///
+    /// # Returns the entries, latest first, or an error if the query fails.
+    pub async fn get_dead_letters(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<WebhookDeadLetter>, DatabaseQueryError<WebhookDeadLetter>> {
+        let query_result = self
+            .db
+            .query(
+                "webhook.get_dead_letters",
+                "SELECT * FROM webhook_dead_letters ORDER BY failed_at DESC LIMIT ?;",
+            )
+            .bind(&[JsValue::from(limit)])
+            .unwrap()
+            .all()
+            .await;
+
+        query_result
+            .and_then(|fetched_entries| fetched_entries.results::<WebhookDeadLetter>())
+            .map_err(|err| {
+                DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+            })
+    }
+}
+
+/// Deserializes the rows of a webhook query.
+fn collect_webhooks(
+    query_result: worker::Result<worker::D1Result>,
+) -> Result<Vec<Webhook>, DatabaseQueryError<Webhook>> {
+    query_result
+        .and_then(|fetched_webhooks| fetched_webhooks.results::<Webhook>())
+        .map_err(|err| {
+            DatabaseQueryError::new(err.to_string(), None, StatusCode::INTERNAL_SERVER_ERROR)
+        })
+}
@@ -1,10 +1,89 @@
 // use statements
-use axum::routing::put;
+use axum::middleware;
+use axum::routing::{delete, get, post, put};
 use axum::Router;
+use worker::Env;
 
-use crate::handlers::game_handlers::update_game;
+use crate::config::app_config::AppConfig;
+use crate::config::feature_flags::FeatureFlags;
+use crate::handlers::achievement_handlers::get_player_achievements;
+use crate::handlers::admin_handlers::{
+    add_ban, force_end_game, force_mute_player, get_global_webhooks, get_maintenance, get_overview,
+    get_reports, get_webhook_dead_letters, inspect_game, list_games, register_global_webhook,
+    remove_ban, remove_global_webhook, run_migration, set_maintenance,
+};
+use crate::handlers::audit_handlers::get_audit_log;
+use crate::handlers::auth_handlers::{
+    create_session, refresh_session, register_device, rejoin_game,
+};
+use crate::handlers::backup_handlers::{export_games, import_games};
+use crate::handlers::chat_handlers::{
+    delete_chat_message, edit_chat_message, mute_player, notify_typing, send_chat_message,
+};
+use crate::handlers::claim_handlers::{challenge_claim, make_claim, undo_claim};
+use crate::handlers::daily_handlers::get_daily;
+use crate::handlers::diagnostics_handlers::get_diagnostics;
+use crate::handlers::event_handlers::{get_events, get_round_summary, resync_game};
+use crate::handlers::export_handlers::{download_export, get_export_link};
+use crate::handlers::feature_handlers::get_features;
+use crate::handlers::game_handlers::{
+    create_game, create_spectator_link, end_game, get_game, get_lobby, join_by_code, kick_player,
+    leave_game, pause_game, quick_play, reclaim_game, reorder_seats, reset_game, resume_game,
+    spectate_game, start_game, update_game, update_settings,
+};
+use crate::handlers::invite_handlers::{invite_player, invite_recent_opponent};
+use crate::handlers::leaderboard_handlers::get_leaderboard;
+use crate::handlers::matchmaking_handlers::{enter_queue, get_queue_events, leave_queue};
+use crate::handlers::metrics_handlers::get_metrics;
+use crate::handlers::moderation_handlers::report_player;
+use crate::handlers::player_handlers::{
+    get_player_games, get_player_stats, get_recent_opponents, update_player,
+};
+use crate::handlers::reaction_handlers::react;
+use crate::handlers::schema_handlers::get_schema;
+use crate::handlers::score_handlers::get_score_history;
+use crate::handlers::season_handlers::{get_seasons, roll_over_season};
+use crate::handlers::share_handlers::get_share_payload;
+use crate::handlers::status_handlers::{get_time, request_status_update};
+use crate::handlers::tournament_handlers::{
+    cancel_tournament, create_tournament, get_entrant_status, get_tournament,
+    get_tournament_standings, list_tournaments, register_entrant, start_tournament,
+    update_tournament,
+};
+use crate::handlers::waitlist_handlers::{get_waitlist_events, join_waitlist, leave_waitlist};
+use crate::handlers::webhook_handlers::{get_webhooks, register_webhook, remove_webhook};
+use crate::i18n::localize::vary_by_locale;
+use crate::jobs::execution_context::ExecutionContext;
+use crate::jobs::task_queue::TaskQueue;
+use crate::logic::chat_filter::ChatFilterConfig;
+use crate::mail::mailer::Mailer;
+use crate::metrics::analytics::MetricsRecorder;
+use crate::metrics::request_tracking::track_request;
+use crate::middleware::cors::apply_cors;
+use crate::repositories::achievement_repository::AchievementRepository;
+use crate::repositories::archive_storage::ArchiveStorage;
+use crate::repositories::audit_repository::AuditRepository;
+use crate::repositories::backup_repository::BackupRepository;
+use crate::repositories::card_repository::CardRepository;
+use crate::repositories::chat::chat_message_repository::ChatMessageRepository;
+use crate::repositories::chat::chat_repository::ChatRepository;
+use crate::repositories::claim_repository::ClaimsRepository;
+use crate::repositories::daily_challenge_repository::DailyChallengeRepository;
+use crate::repositories::game_event_repository::GameEventRepository;
 use crate::repositories::game_repository::GameRepository;
+use crate::repositories::game_result_repository::GameResultRepository;
+use crate::repositories::invitation_repository::InvitationRepository;
+use crate::repositories::leaderboard_repository::LeaderboardRepository;
+use crate::repositories::maintenance_repository::MaintenanceRepository;
+use crate::repositories::matchmaking::Matchmaking;
+use crate::repositories::moderation_repository::ModerationRepository;
 use crate::repositories::player_repository::PlayerRepository;
+use crate::repositories::score_history_repository::ScoreHistoryRepository;
+use crate::repositories::season_repository::SeasonRepository;
+use crate::repositories::tournament_repository::TournamentRepository;
+use crate::repositories::typing_indicators::TypingIndicators;
+use crate::repositories::waitlist_repository::WaitlistRepository;
+use crate::repositories::webhook_repository::WebhookRepository;
 
 /// Application state for the Axum application.
 ///
@@ -12,19 +91,169 @@ use crate::repositories::player_repository::PlayerRepository;
 ///
 /// # Properties
 ///     
-/// `db`: An instance of `D1Database` that provides access to the D1 database.:w
+/// The repositories own a clone of the shared `Database` handle, so the state is `'static` and
+/// can be handed to the router built for every request.
 ///
 #[derive(Clone)]
-pub struct AppState<'a> {
+pub struct AppState {
     // Add application state properties here, e.g., database connection, configuration, etc.
     // For example:
     // pub db: D1Database,
-    pub game_repository: GameRepository<'a>,
+    pub game_repository: GameRepository,
 
     /// The database repository providing utility methods for interacting with the `players` table.
     ///
     /// Lives aslong as the app is running.
-    pub player_repository: PlayerRepository<'a>,
+    pub player_repository: PlayerRepository,
+
+    /// The database repository providing utility methods for interacting with the `cards` table.
+    pub card_repository: CardRepository,
+
+    /// The database repository providing utility methods for interacting with the `claims` table.
+    pub claims_repository: ClaimsRepository,
+
+    /// The database repository providing utility methods for interacting with the `chats` table.
+    pub chat_repository: ChatRepository,
+
+    /// The database repository providing utility methods for interacting with the `chat_messages`
+    /// table.
+    pub chat_message_repository: ChatMessageRepository,
+
+    /// The database repository providing utility methods for interacting with the `game_events`
+    /// table.
+    pub game_event_repository: GameEventRepository,
+
+    /// The database repository providing utility methods for interacting with the `game_results`
+    /// and `player_stats` tables.
+    pub game_result_repository: GameResultRepository,
+
+    /// The database repository providing utility methods for interacting with the `invitations`
+    /// table.
+    pub invitation_repository: InvitationRepository,
+
+    /// The database repository providing utility methods for interacting with the `leaderboards`
+    /// table.
+    pub leaderboard_repository: LeaderboardRepository,
+
+    /// The database repository providing utility methods for interacting with the `achievements`
+    /// table.
+    pub achievement_repository: AchievementRepository,
+
+    /// The database repository for operator tasks like the maintenance mode and data migrations.
+    pub maintenance_repository: MaintenanceRepository,
+
+    /// The database repository providing utility methods for interacting with the `reports` and
+    /// `bans` tables.
+    pub moderation_repository: ModerationRepository,
+
+    /// The database repository providing utility methods for interacting with the `webhooks`
+    /// table.
+    pub webhook_repository: WebhookRepository,
+
+    /// The database repository providing utility methods for interacting with the `audit_log`
+    /// table.
+    pub audit_repository: AuditRepository,
+
+    /// The database repository providing utility methods for interacting with the tournament
+    /// tables.
+    pub tournament_repository: TournamentRepository,
+
+    /// The database repository providing utility methods for interacting with the `seasons`
+    /// table.
+    pub season_repository: SeasonRepository,
+
+    /// The database repository providing utility methods for interacting with the `waitlist`
+    /// table.
+    pub waitlist_repository: WaitlistRepository,
+
+    /// The database repository providing utility methods for interacting with the
+    /// `daily_challenges` table.
+    pub daily_challenge_repository: DailyChallengeRepository,
+
+    /// The database repository providing utility methods for interacting with the
+    /// `score_history` table.
+    pub score_history_repository: ScoreHistoryRepository,
+
+    /// The database repository reading and writing the raw rows of games for bulk exports and
+    /// imports.
+    pub backup_repository: BackupRepository,
+
+    /// Tunables of the worker like timeouts, limits and the allowed origins.
+    ///
+    /// Read from the variables of the worker on every request.
+    pub config: AppConfig,
+
+    /// Switches of the experimental subsystems of the environment.
+    ///
+    /// Read from the `FEATURE_*` variables and the `FEATURE_FLAGS` KV namespace.
+    pub features: FeatureFlags,
+
+    /// Key to sign and verify the session tokens of all players.
+    ///
+    /// Read from the `SESSION_SECRET` secret of the worker.
+    pub session_secret: String,
+
+    /// Secret key of the Turnstile widget guarding the game creation.
+    ///
+    /// Read from the `TURNSTILE_SECRET` secret of the worker.
+    pub turnstile_secret: String,
+
+    /// Key to sign the payloads posted to webhooks registered without their own secret.
+    ///
+    /// Read from the `WEBHOOK_SECRET` secret of the worker. Such webhooks aren't notified if it is
+    /// missing.
+    pub webhook_secret: Option<String>,
+
+    /// Token operators need to send in the `Admin-Token` header.
+    ///
+    /// Read from the `ADMIN_TOKEN` secret of the worker. All admin endpoints are locked if it is
+    /// missing.
+    pub admin_token: Option<String>,
+
+    /// Banned words and rate cap of the chat.
+    ///
+    /// Loaded from the `CHAT_FILTER` KV namespace.
+    pub chat_filter: ChatFilterConfig,
+
+    /// Producer of the queue processing the background tasks.
+    ///
+    /// `None` if the `TASKS` queue isn't bound; the tasks then run inline.
+    pub task_queue: Option<TaskQueue>,
+
+    /// Runs the side effects of a request after its response was sent.
+    ///
+    /// Runs them inline in scheduled runs and in the queue consumer.
+    pub execution: ExecutionContext,
+
+    /// Writer of the metrics into the `METRICS` Analytics Engine dataset.
+    ///
+    /// `None` if the dataset isn't bound.
+    pub metrics: Option<MetricsRecorder>,
+
+    /// Client sending the invitation emails through MailChannels.
+    ///
+    /// `None` if the `MAILCHANNELS_API_KEY` secret or the sender address is missing.
+    pub mailer: Option<Mailer>,
+
+    /// Storage of the exported games in the `ARCHIVES` R2 bucket.
+    ///
+    /// `None` if the bucket isn't bound; games can't be exported then.
+    pub archive_storage: Option<ArchiveStorage>,
+
+    /// Client of the public matchmaking queue in its Durable Object.
+    ///
+    /// `None` if the `MATCHMAKING_QUEUE` binding is missing; matchmaking isn't available then.
+    pub matchmaking: Option<Matchmaking>,
+
+    /// Client of the typing indicators of the chats in their Durable Objects.
+    ///
+    /// `None` if the `TYPING_INDICATOR` binding is missing; nobody is shown as typing then.
+    pub typing_indicators: Option<TypingIndicators>,
+
+    /// Environment of the worker holding its bindings and secrets.
+    ///
+    /// Only inspected by the diagnostics of the operators; everything else gets its binding above.
+    pub env: Env,
 }
 
 /// Router provider for the Axum application.
@@ -32,7 +261,146 @@ pub struct AppState<'a> {
 /// This module defines the router for the Axum application, setting up the routes
 pub fn router(app_state: AppState) -> Router {
     Router::new()
+        // capabilities of this deployment
+        .route("/features", get(get_features))
+        .route("/time", get(get_time))
+        .route("/schemas/{type}", get(get_schema))
+        // statistics across games
+        .route("/leaderboard", get(get_leaderboard))
+        .route("/seasons", get(get_seasons))
+        .route("/daily", get(get_daily))
+        .route("/player/{id}/stats", get(get_player_stats))
+        .route("/player/{id}/achievements", get(get_player_achievements))
+        .route("/player/{id}/games", get(get_player_games))
+        .route("/player/{id}/recent-opponents", get(get_recent_opponents))
+        // tournaments
+        .route("/tournaments", get(list_tournaments))
+        .route("/tournament/{id}", get(get_tournament))
+        .route("/tournament/{id}/standings", get(get_tournament_standings))
+        .route("/tournament/{id}/entrants", post(register_entrant))
+        .route(
+            "/tournament/{id}/entrants/{entrant_id}",
+            get(get_entrant_status),
+        )
+        // session endpoints
+        .route("/auth/session", post(create_session))
+        .route("/auth/refresh", post(refresh_session))
+        .route("/auth/device", post(register_device))
+        .route("/game/{id}/rejoin", post(rejoin_game))
         // game instance endpoints
+        .route("/game/create", post(create_game))
+        .route("/game/lobby", get(get_lobby))
+        .route("/games", get(get_lobby))
+        .route("/quick-play", post(quick_play))
+        .route("/game/join-by-code/{code}", post(join_by_code))
+        .route("/matchmaking/queue", post(enter_queue))
+        .route("/matchmaking/queue/{ticket_id}", delete(leave_queue))
+        .route(
+            "/matchmaking/queue/{ticket_id}/events",
+            get(get_queue_events),
+        )
+        .route("/game/{id}/waitlist", post(join_waitlist))
+        .route("/game/{id}/waitlist/{entry_id}", delete(leave_waitlist))
+        .route(
+            "/game/{id}/waitlist/{entry_id}/events",
+            get(get_waitlist_events),
+        )
         .route("/game/update", put(update_game))
+        .route("/game/{id}", get(get_game))
+        // read-only endpoints for players and spectators
+        .route("/game/{id}/spectate", get(spectate_game))
+        .route("/game/{id}/events", get(get_events))
+        .route("/game/{id}/rounds/{round_number}", get(get_round_summary))
+        .route("/game/{id}/scores", get(get_score_history))
+        .route("/game/{id}/export", get(get_export_link))
+        .route("/game/{id}/export/download", get(download_export))
+        // link previews of chat apps
+        .route("/game/{id}/share", get(get_share_payload))
+        // player action endpoints
+        .route("/game/{id}/claim", post(make_claim))
+        .route("/game/{id}/claim/undo", post(undo_claim))
+        .route("/game/{id}/challenge", post(challenge_claim))
+        .route("/game/{id}/react", post(react))
+        .route("/game/{id}/chat", post(send_chat_message))
+        .route("/game/{id}/chat/typing", post(notify_typing))
+        .route(
+            "/chat/message/{id}",
+            put(edit_chat_message).delete(delete_chat_message),
+        )
+        .route("/game/{id}/resync", post(resync_game))
+        .route("/game/{id}/report", post(report_player))
+        .route("/player/update", put(update_player))
+        .route("/status", post(request_status_update))
+        // host only endpoints
+        .route("/game/{id}/start", post(start_game))
+        .route("/game/{id}/kick/{player_id}", post(kick_player))
+        .route("/game/{id}/mute/{player_id}", post(mute_player))
+        .route("/game/{id}/leave", post(leave_game))
+        .route("/game/{id}/settings", put(update_settings))
+        .route("/game/{id}/seats", put(reorder_seats))
+        .route("/game/{id}/end", post(end_game))
+        .route("/game/{id}/pause", post(pause_game))
+        .route("/game/{id}/resume", post(resume_game))
+        .route("/game/{id}/reset", post(reset_game))
+        .route("/game/{id}/reclaim", post(reclaim_game))
+        .route("/game/{id}/spectator-link", post(create_spectator_link))
+        .route("/game/{id}/invite", post(invite_player))
+        .route(
+            "/game/{id}/invite-player/{other_id}",
+            post(invite_recent_opponent),
+        )
+        .route(
+            "/game/{id}/webhooks",
+            get(get_webhooks).post(register_webhook),
+        )
+        .route("/game/{id}/webhooks/{webhook_id}", delete(remove_webhook))
+        // admin endpoints
+        .route("/admin/overview", get(get_overview))
+        .route("/admin/games", get(list_games))
+        .route("/admin/game/{id}/end", post(force_end_game))
+        .route("/admin/game/{id}/full", get(inspect_game))
+        .route("/admin/game/{id}/mute/{player_id}", post(force_mute_player))
+        .route(
+            "/admin/maintenance",
+            get(get_maintenance).put(set_maintenance),
+        )
+        .route("/admin/migrations/{name}", post(run_migration))
+        .route("/admin/reports", get(get_reports))
+        .route("/admin/bans", post(add_ban))
+        .route("/admin/bans/{fingerprint}", delete(remove_ban))
+        .route(
+            "/admin/webhooks",
+            get(get_global_webhooks).post(register_global_webhook),
+        )
+        .route(
+            "/admin/webhooks/dead-letters",
+            get(get_webhook_dead_letters),
+        )
+        .route("/admin/webhooks/{id}", delete(remove_global_webhook))
+        .route("/admin/audit", get(get_audit_log))
+        .route("/admin/backup/export", get(export_games))
+        .route("/admin/backup/import", post(import_games))
+        .route("/admin/diagnostics", get(get_diagnostics))
+        .route("/admin/tournaments", post(create_tournament))
+        .route(
+            "/admin/tournaments/{id}",
+            put(update_tournament).delete(cancel_tournament),
+        )
+        .route("/admin/tournaments/{id}/start", post(start_tournament))
+        .route("/admin/seasons/rollover", post(roll_over_season))
+        // scraped by Prometheus with the credentials of an operator
+        .route("/metrics", get(get_metrics))
+        // metrics of every matched route
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            track_request,
+        ))
+        // keeps caches from mixing up the languages of the `Accept-Language` header
+        .layer(middleware::from_fn(vary_by_locale))
+        // CORS headers and preflight requests, including unmatched routes
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            apply_cors,
+        ))
         .with_state(app_state)
 }
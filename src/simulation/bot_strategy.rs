@@ -0,0 +1,188 @@
+use std::{fmt, str::FromStr};
+
+use rand_chacha::{rand_core::RngCore, ChaCha8Rng};
+
+use crate::{
+    enums::card_types::CardType, errors::enum_conversion_error::EnumConversionError,
+    types::card::Card,
+};
+
+// constants
+/// Most cards a bot places with one claim, like the limit of a `Claim`.
+const MAX_CARDS_PER_CLAIM: usize = 4;
+
+/// Way a simulated player decides its moves.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::bot_strategy::BotStrategy;
+/// let strategy: BotStrategy = "bluffer".parse()?;
+/// println!("{}", strategy);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BotStrategy {
+    /// Only places matching cards and doubts a claim when it has none.
+    Honest,
+    /// Pads its claims with other cards half of the time and rarely doubts.
+    Bluffer,
+    /// Doubts every big claim and half of the others, otherwise plays honestly.
+    Skeptic,
+    /// Moves at random.
+    Random,
+}
+
+/// What a bot sees when it is its turn.
+///
+/// # Props
+///
+/// - `hand` -> Cards the bot holds
+/// - `card_to_play` -> Card type of the round
+/// - `last_claim_size` -> Number of cards of the last claim of another player; `None` if there is
+///   nothing to doubt
+/// - `stack_size` -> Number of cards on the stack
+pub struct TurnView<'a> {
+    /// Cards the bot holds
+    pub hand: &'a [Card],
+    /// Card type of the round
+    pub card_to_play: &'a CardType,
+    /// Number of cards of the last claim of another player
+    pub last_claim_size: Option<usize>,
+    /// Number of cards on the stack
+    pub stack_size: usize,
+}
+
+/// Move a bot decided on.
+#[derive(Debug, Clone)]
+pub enum BotMove {
+    /// Places the cards face-down on the stack.
+    Claim(Vec<Card>),
+    /// Doubts the last claim.
+    Challenge,
+}
+
+impl BotStrategy {
+    /// All strategies, in the order they are listed in reports.
+    pub const ALL: [BotStrategy; 4] = [
+        BotStrategy::Honest,
+        BotStrategy::Bluffer,
+        BotStrategy::Skeptic,
+        BotStrategy::Random,
+    ];
+
+    /// Returns a string representation of the strategy.
+    ///
+    /// # Returns
+    /// A string slice representing the strategy.
+    pub fn as_str(&self) -> &str {
+        match self {
+            BotStrategy::Honest => "honest",
+            BotStrategy::Bluffer => "bluffer",
+            BotStrategy::Skeptic => "skeptic",
+            BotStrategy::Random => "random",
+        }
+    }
+
+    /// Decides the move of a bot.
+    ///
+    /// A bot with an empty hand always doubts, a bot without anything to doubt always claims.
+    ///
+    /// # Arguments
+    ///
+    /// - `turn` -> What the bot sees; the hand mustn't be empty if there is nothing to doubt
+    /// - `rng` -> Generator of the simulation, so the same seed leads to the same moves
+    pub fn decide(&self, turn: &TurnView, rng: &mut ChaCha8Rng) -> BotMove {
+        let can_challenge = turn.last_claim_size.is_some();
+        if turn.hand.is_empty() {
+            return BotMove::Challenge;
+        }
+
+        let (matching, other): (Vec<&Card>, Vec<&Card>) = turn.hand.iter().partition(|card| {
+            card.card_type == *turn.card_to_play || card.card_type == CardType::Joker
+        });
+
+        match self {
+            BotStrategy::Honest => {
+                if matching.is_empty() && can_challenge {
+                    return BotMove::Challenge;
+                }
+                BotMove::Claim(honest_claim(&matching, &other))
+            }
+            BotStrategy::Bluffer => {
+                if can_challenge && chance(rng, 5) {
+                    return BotMove::Challenge;
+                }
+                if chance(rng, 2) && !other.is_empty() {
+                    let padding = 1 + rng.next_u32() as usize % other.len().min(2);
+                    let mut cards: Vec<Card> = matching
+                        .iter()
+                        .take(MAX_CARDS_PER_CLAIM - padding)
+                        .map(|card| (*card).clone())
+                        .collect();
+                    cards.extend(other.iter().take(padding).map(|card| (*card).clone()));
+
+                    return BotMove::Claim(cards);
+                }
+                BotMove::Claim(honest_claim(&matching, &other))
+            }
+            BotStrategy::Skeptic => {
+                let big_claim = turn.last_claim_size.is_some_and(|size| size >= 3);
+                if can_challenge && (big_claim || matching.is_empty() || chance(rng, 2)) {
+                    return BotMove::Challenge;
+                }
+                BotMove::Claim(honest_claim(&matching, &other))
+            }
+            BotStrategy::Random => {
+                if can_challenge && chance(rng, 3) {
+                    return BotMove::Challenge;
+                }
+                let count = 1 + rng.next_u32() as usize % turn.hand.len().min(MAX_CARDS_PER_CLAIM);
+                let offset = rng.next_u32() as usize % turn.hand.len();
+
+                BotMove::Claim(
+                    turn.hand
+                        .iter()
+                        .cycle()
+                        .skip(offset)
+                        .take(count)
+                        .cloned()
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+impl fmt::Display for BotStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for BotStrategy {
+    type Err = EnumConversionError;
+
+    /// Parses the `as_str()` value of a strategy.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        BotStrategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.as_str() == value)
+            .ok_or_else(|| EnumConversionError::new("BotStrategy", value.to_string()))
+    }
+}
+
+/// Places up to four matching cards, or a single other card if there is no matching one.
+fn honest_claim(matching: &[&Card], other: &[&Card]) -> Vec<Card> {
+    match matching.is_empty() {
+        true => other.iter().take(1).map(|card| (*card).clone()).collect(),
+        false => matching
+            .iter()
+            .take(MAX_CARDS_PER_CLAIM)
+            .map(|card| (*card).clone())
+            .collect(),
+    }
+}
+
+/// Returns `true` with a probability of `1 / one_in`.
+fn chance(rng: &mut ChaCha8Rng, one_in: u32) -> bool {
+    rng.next_u32() % one_in == 0
+}
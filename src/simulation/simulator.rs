@@ -0,0 +1,370 @@
+use std::fmt;
+
+use rand_chacha::{rand_core::SeedableRng, ChaCha8Rng};
+
+use crate::{
+    enums::game_state::GameState,
+    logic::{
+        deck::Deck,
+        rules::{pick_up_stack, remove_from_hand, resolve_challenge, select_placed_cards},
+    },
+    simulation::bot_strategy::{BotMove, BotStrategy, TurnView},
+    types::{claim::Claim, game::Game, player::Player},
+};
+
+// constants
+/// Rounds after which a simulated game ends without a winner.
+pub const DEFAULT_MAX_ROUNDS: usize = 500;
+
+/// Settings of a simulation run.
+///
+/// # Props
+///
+/// - `games` -> Number of games to play
+/// - `seed` -> Seed of the first game; game `n` is played with `seed + n`
+/// - `strategies` -> Strategies of the seats; they take turns on the first seat from game to game
+/// - `jokers` -> Number of jokers added to the deck
+/// - `max_rounds` -> Rounds after which a game ends without a winner
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    /// Number of games to play
+    pub games: usize,
+    /// Seed of the first game
+    pub seed: u64,
+    /// Strategies of the seats
+    pub strategies: Vec<BotStrategy>,
+    /// Number of jokers added to the deck
+    pub jokers: usize,
+    /// Rounds after which a game ends without a winner
+    pub max_rounds: usize,
+}
+
+impl Default for SimulationConfig {
+    /// One thousand games between one bot of every strategy with the deck of a real game.
+    fn default() -> Self {
+        SimulationConfig {
+            games: 1000,
+            seed: 0,
+            strategies: BotStrategy::ALL.to_vec(),
+            jokers: 0,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+        }
+    }
+}
+
+/// Outcome of a single simulated game.
+///
+/// # Props
+///
+/// - `winner_seat` -> Seat of the player who got rid of all cards; `None` if the round limit was
+///   hit
+/// - `rounds` -> Number of played rounds
+/// - `claims` -> Number of claims
+/// - `bluffs` -> Claims which weren't truthful
+/// - `caught_bluffs` -> Challenges which uncovered a bluff
+/// - `failed_challenges` -> Challenges of truthful claims
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameReport {
+    /// Seat of the player who got rid of all cards
+    pub winner_seat: Option<usize>,
+    /// Number of played rounds
+    pub rounds: usize,
+    /// Number of claims
+    pub claims: usize,
+    /// Claims which weren't truthful
+    pub bluffs: usize,
+    /// Challenges which uncovered a bluff
+    pub caught_bluffs: usize,
+    /// Challenges of truthful claims
+    pub failed_challenges: usize,
+}
+
+/// Statistics of a simulation run.
+///
+/// # Props
+///
+/// - `config` -> Settings of the run
+/// - `wins` -> Won games of every strategy, in the order of `config.strategies`
+/// - `draws` -> Games which hit the round limit
+/// - `rounds` -> Rounds of all games
+/// - `claims` -> Claims of all games
+/// - `bluffs` -> Bluffs of all games
+/// - `caught_bluffs` -> Uncovered bluffs of all games
+/// - `failed_challenges` -> Challenges of truthful claims of all games
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    /// Settings of the run
+    pub config: SimulationConfig,
+    /// Won games of every strategy
+    pub wins: Vec<usize>,
+    /// Games which hit the round limit
+    pub draws: usize,
+    /// Rounds of all games
+    pub rounds: usize,
+    /// Claims of all games
+    pub claims: usize,
+    /// Bluffs of all games
+    pub bluffs: usize,
+    /// Uncovered bluffs of all games
+    pub caught_bluffs: usize,
+    /// Challenges of truthful claims of all games
+    pub failed_challenges: usize,
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let games = self.config.games.max(1) as f64;
+        let challenges = (self.caught_bluffs + self.failed_challenges).max(1) as f64;
+
+        writeln!(
+            f,
+            "{} games, seed {}, {} jokers",
+            self.config.games, self.config.seed, self.config.jokers
+        )?;
+        for (strategy, wins) in self.config.strategies.iter().zip(&self.wins) {
+            writeln!(
+                f,
+                "  {strategy:<10} {wins:>6} wins ({:.1}%)",
+                *wins as f64 * 100.0 / games
+            )?;
+        }
+        writeln!(
+            f,
+            "  {:<10} {:>6} games ({:.1}%)",
+            "no winner",
+            self.draws,
+            self.draws as f64 * 100.0 / games
+        )?;
+        writeln!(f, "rounds per game:     {:.1}", self.rounds as f64 / games)?;
+        writeln!(f, "claims per game:     {:.1}", self.claims as f64 / games)?;
+        writeln!(
+            f,
+            "bluff rate:          {:.1}%",
+            self.bluffs as f64 * 100.0 / self.claims.max(1) as f64
+        )?;
+        write!(
+            f,
+            "caught bluffs:       {:.1}% of the challenges",
+            self.caught_bluffs as f64 * 100.0 / challenges
+        )
+    }
+}
+
+/// Plays games between bots and sums up their outcomes.
+///
+/// Nothing is stored and no request is sent, and the same config always leads to the same report.
+///
+/// # Arguments
+///
+/// - `config` -> Settings of the run
+pub fn run_simulation(config: SimulationConfig) -> SimulationReport {
+    let mut report = SimulationReport {
+        wins: vec![0; config.strategies.len()],
+        draws: 0,
+        rounds: 0,
+        claims: 0,
+        bluffs: 0,
+        caught_bluffs: 0,
+        failed_challenges: 0,
+        config,
+    };
+    let seats = report.config.strategies.len();
+
+    for index in 0..report.config.games {
+        // rotate the seats, so no strategy profits from always moving first
+        let mut strategies = report.config.strategies.clone();
+        strategies.rotate_left(index % seats.max(1));
+
+        let game = simulate_game(
+            &strategies,
+            report.config.jokers,
+            report.config.max_rounds,
+            report.config.seed.wrapping_add(index as u64),
+        );
+
+        match game.winner_seat {
+            Some(seat) => report.wins[(seat + index) % seats] += 1,
+            None => report.draws += 1,
+        }
+        report.rounds += game.rounds;
+        report.claims += game.claims;
+        report.bluffs += game.bluffs;
+        report.caught_bluffs += game.caught_bluffs;
+        report.failed_challenges += game.failed_challenges;
+    }
+
+    report
+}
+
+/// Plays one game between bots with the rules of the claim and challenge handlers.
+///
+/// A player wins as soon as their hand is empty and their last claim can't be doubted anymore,
+/// i.e. the next player played on or lost the challenge. With their empty hand `Game::finish`
+/// records them as the winner of the game.
+///
+/// # Arguments
+///
+/// - `strategies` -> Strategy of every seat; at least two
+/// - `jokers` -> Number of jokers added to the deck
+/// - `max_rounds` -> Rounds after which the game ends without a winner
+/// - `seed` -> Seed of the deck, the cards to play and the decisions of the bots
+///
+/// # Panics
+///
+/// Panics if a move breaks the rules, which is exactly what the simulation looks for.
+pub fn simulate_game(
+    strategies: &[BotStrategy],
+    jokers: usize,
+    max_rounds: usize,
+    seed: u64,
+) -> GameReport {
+    let mut game = Game::new();
+    game.players = strategies
+        .iter()
+        .enumerate()
+        .map(|(seat, strategy)| Player::new(format!("{strategy} {}", seat + 1), game.id.clone()))
+        .collect();
+    game.host_player_id = game.players[0].id.clone();
+    game.which_player_turn = game.players[0].id.clone();
+    // the seed of a daily challenge makes the deck and the cards to play deterministic
+    game.daily_seed = Some(format!("simulation-{seed}"));
+    game.card_to_play = game.card_for_round();
+    game.transition_to(GameState::InProgress)
+        .expect("A new game can always be started");
+
+    let mut deck = Deck::standard(jokers);
+    deck.shuffle(&mut game.deck_rng());
+    deck.deal(&mut game.players)
+        .expect("A game with players can always be dealt");
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut report = GameReport::default();
+
+    while game.round_number <= max_rounds {
+        if let Some(winner) = finished_seat(&game) {
+            report.winner_seat = Some(winner);
+            break;
+        }
+
+        let current = seat_of(&game, &game.which_player_turn);
+        let current_id = game.which_player_turn.clone();
+        let stack_size = game.claims.iter().map(|claim| claim.number_of_cards).sum();
+        let last_claim_size = game
+            .claims
+            .last()
+            .filter(|claim| claim.created_by != current_id)
+            .map(|claim| claim.number_of_cards);
+        let turn = TurnView {
+            hand: &game.players[current].assigned_cards,
+            card_to_play: &game.card_to_play,
+            last_claim_size,
+            stack_size,
+        };
+
+        match strategies[current].decide(&turn, &mut rng) {
+            BotMove::Claim(named) => {
+                let placed = select_placed_cards(&game.players[current].assigned_cards, &named)
+                    .expect("A bot can only place cards of its hand");
+                let claim = Claim::new(
+                    current_id.clone(),
+                    game.id.clone(),
+                    game.round_number,
+                    placed.len(),
+                    placed,
+                )
+                .expect("A bot places at most four cards");
+
+                report.claims += 1;
+                if !claim.is_truthful(&game.card_to_play) {
+                    report.bluffs += 1;
+                }
+                remove_from_hand(&mut game.players[current].assigned_cards, &claim.cards);
+                game.claims.push(claim);
+                game.which_player_turn = game
+                    .next_player_id(&current_id)
+                    .expect("The player to move is seated");
+            }
+            BotMove::Challenge => {
+                let claim = game
+                    .claims
+                    .last()
+                    .cloned()
+                    .expect("A bot only doubts if there is a claim");
+                let outcome = resolve_challenge(&claim, &current_id, &game.card_to_play);
+
+                match outcome.truthful {
+                    true => report.failed_challenges += 1,
+                    false => report.caught_bluffs += 1,
+                }
+                pick_up_stack(&mut game, &outcome.loser_id);
+                game.prep_for_new_round()
+                    .expect("A game with players can always start a new round");
+                game.which_player_turn = outcome.winner_id;
+            }
+        }
+    }
+
+    report.rounds = game.round_number.min(max_rounds);
+    game.finish().expect("A running game can always be ended");
+
+    report
+}
+
+/// Returns the seat of the player who got rid of all cards and can't be doubted anymore.
+fn finished_seat(game: &Game) -> Option<usize> {
+    let last_claimant = game.claims.last().map(|claim| claim.created_by.as_str());
+
+    game.players.iter().position(|player| {
+        player.assigned_cards.is_empty() && last_claimant != Some(player.id.as_str())
+    })
+}
+
+/// Index of a player in the seating order of the game.
+fn seat_of(game: &Game, player_id: &str) -> usize {
+    game.players
+        .iter()
+        .position(|player| player.id == player_id)
+        .expect("The player to move is seated")
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        /// Fuzzes the state machine: random tables of bots never break a rule and always end.
+        #[test]
+        fn simulated_games_end_without_panics(
+            strategies in prop::collection::vec(
+                prop::sample::select(BotStrategy::ALL.to_vec()),
+                2..=5,
+            ),
+            jokers in 0..=3usize,
+            seed in any::<u64>(),
+        ) {
+            let report = simulate_game(&strategies, jokers, 200, seed);
+
+            prop_assert!(report.rounds <= 200);
+            prop_assert!(report.bluffs <= report.claims);
+            prop_assert!(report.winner_seat.is_none_or(|seat| seat < strategies.len()));
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_report() {
+        let config = SimulationConfig {
+            games: 20,
+            seed: 42,
+            ..Default::default()
+        };
+
+        let first = run_simulation(config.clone());
+        let second = run_simulation(config);
+
+        assert_eq!(first.wins, second.wins);
+        assert_eq!(first.claims, second.claims);
+        assert_eq!(first.wins.iter().sum::<usize>() + first.draws, 20);
+    }
+}
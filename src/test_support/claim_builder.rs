@@ -0,0 +1,111 @@
+use crate::{
+    enums::{card_types::CardType, suit::Suit},
+    types::{card::Card, claim::Claim, game::Game},
+    utils::time::server_time,
+};
+
+/// Builds a `Claim` for tests with sensible defaults.
+///
+/// A fresh builder creates an unrevealed claim of a single King in round 1 with a random id.
+/// The number of cards always matches the placed cards.
+///
+/// # Example usage:
+///
+/// ```rust
+/// let claim = ClaimBuilder::new()
+///     .in_game(&game)
+///     .by(&player.id)
+///     .lie(&game.card_to_play, 2)
+///     .build();
+/// ```
+pub struct ClaimBuilder {
+    /// The claim built so far
+    claim: Claim,
+}
+
+impl Default for ClaimBuilder {
+    fn default() -> Self {
+        ClaimBuilder::new()
+    }
+}
+
+impl ClaimBuilder {
+    /// Creates a builder with the default claim.
+    pub fn new() -> Self {
+        ClaimBuilder {
+            claim: Claim {
+                id: uuid::Uuid::new_v4().to_string(),
+                created_by: String::new(),
+                game_id: String::new(),
+                round_number: 1,
+                created_at: server_time(),
+                number_of_cards: 1,
+                cards: vec![Card::with_suit(CardType::King, Suit::all()[0].clone())],
+                revealed: false,
+                declared_card_type: None,
+                declared_count: None,
+            },
+        }
+    }
+
+    /// Sets the player who made the claim.
+    pub fn by(mut self, player_id: &str) -> Self {
+        self.claim.created_by = player_id.to_string();
+        self
+    }
+
+    /// Places the claim in the current round of a game.
+    pub fn in_game(mut self, game: &Game) -> Self {
+        self.claim.game_id = game.id.clone();
+        self.claim.round_number = game.round_number;
+        self
+    }
+
+    /// Sets the round of the claim.
+    pub fn round_number(mut self, round_number: usize) -> Self {
+        self.claim.round_number = round_number;
+        self
+    }
+
+    /// Sets the placed cards.
+    pub fn cards(mut self, cards: Vec<Card>) -> Self {
+        self.claim.number_of_cards = cards.len();
+        self.claim.cards = cards;
+        self
+    }
+
+    /// Places a number of cards of the card to play, so the claim is truthful.
+    pub fn truthful(self, card_to_play: &CardType, count: usize) -> Self {
+        let suits = Suit::all();
+        let cards = (0..count)
+            .map(|index| Card::with_suit(card_to_play.clone(), suits[index % suits.len()].clone()))
+            .collect();
+
+        self.cards(cards)
+    }
+
+    /// Places a number of cards of another rank than the card to play, so the claim is a lie.
+    pub fn lie(self, card_to_play: &CardType, count: usize) -> Self {
+        let other = CardType::ranks()
+            .into_iter()
+            .find(|rank| rank != card_to_play)
+            .unwrap_or(CardType::King);
+        let suits = Suit::all();
+        let cards = (0..count)
+            .map(|index| Card::with_suit(other.clone(), suits[index % suits.len()].clone()))
+            .collect();
+
+        self.cards(cards)
+    }
+
+    /// Marks the cards of the claim as revealed.
+    pub fn revealed(mut self) -> Self {
+        self.claim.revealed = true;
+        self
+    }
+
+    /// Returns the built claim.
+    pub fn build(self) -> Claim {
+        self.claim
+    }
+}
@@ -0,0 +1,169 @@
+use rand_chacha::{rand_core::SeedableRng, ChaCha8Rng};
+
+use crate::{
+    enums::{card_types::CardType, game_state::GameState},
+    logic::deck::Deck,
+    test_support::player_builder::PlayerBuilder,
+    types::{claim::Claim, game::Game, player::Player},
+};
+
+/// Builds a `Game` for tests with sensible defaults.
+///
+/// A fresh builder creates a game waiting for players in round 1 with a random id and no
+/// players. On `build` every player is moved into the game, the first player becomes the host
+/// and starts unless someone else was chosen.
+///
+/// # Example usage:
+///
+/// ```rust
+/// let game = GameBuilder::new().players(3).in_progress().dealt(42, 2).build();
+/// ```
+pub struct GameBuilder {
+    /// The game built so far
+    game: Game,
+    /// Seed and number of jokers of the decks dealt on `build`
+    deal: Option<(u64, usize)>,
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        GameBuilder::new()
+    }
+}
+
+impl GameBuilder {
+    /// Creates a builder with the default game.
+    pub fn new() -> Self {
+        let mut game = Game::new();
+        game.state = GameState::WaitingForPlayers;
+
+        GameBuilder { game, deal: None }
+    }
+
+    /// Sets the id of the game.
+    pub fn id(mut self, id: &str) -> Self {
+        self.game.id = id.to_string();
+        self
+    }
+
+    /// Sets the state of the game.
+    pub fn state(mut self, state: GameState) -> Self {
+        self.game.state = state;
+        self
+    }
+
+    /// Moves the game into the `InProgress` state.
+    pub fn in_progress(self) -> Self {
+        self.state(GameState::InProgress)
+    }
+
+    /// Adds a number of default players named `Player 1`, `Player 2`, ...
+    pub fn players(mut self, count: usize) -> Self {
+        let offset = self.game.players.len();
+        for index in 1..=count {
+            let player = PlayerBuilder::new()
+                .name(&format!("Player {}", offset + index))
+                .build();
+            self.game.players.push(player);
+        }
+        self
+    }
+
+    /// Adds a player built by the caller.
+    pub fn player(mut self, player: Player) -> Self {
+        self.game.players.push(player);
+        self
+    }
+
+    /// Sets the host of the game by the index of the player.
+    pub fn host(mut self, index: usize) -> Self {
+        self.game.host_player_id = self.game.players[index].id.clone();
+        self
+    }
+
+    /// Gives the turn to the player with the passed index.
+    pub fn turn_of(mut self, index: usize) -> Self {
+        self.game.which_player_turn = self.game.players[index].id.clone();
+        self
+    }
+
+    /// Sets the round of the game.
+    pub fn round_number(mut self, round_number: usize) -> Self {
+        self.game.round_number = round_number;
+        self
+    }
+
+    /// Sets the card to play of the current round.
+    pub fn card_to_play(mut self, card_to_play: CardType) -> Self {
+        self.game.card_to_play = card_to_play;
+        self
+    }
+
+    /// Adds a claim to the current round.
+    pub fn claim(mut self, claim: Claim) -> Self {
+        self.game.claims.push(claim);
+        self
+    }
+
+    /// Lets the game play a daily challenge, so the cards to play of later rounds are
+    /// deterministic.
+    pub fn daily_seed(mut self, seed: &str) -> Self {
+        self.game.daily_seed = Some(seed.to_string());
+        self
+    }
+
+    /// Sets the number of standard decks shuffled together and the cards dealt to every player.
+    ///
+    /// # Arguments
+    ///
+    /// - `deck_count` -> Number of standard decks of the game
+    /// - `hand_size` -> Cards dealt to every player; `None` deals the whole deck
+    pub fn decks(mut self, deck_count: usize, hand_size: Option<usize>) -> Self {
+        self.game.settings.deck_count = deck_count;
+        self.game.settings.hand_size = hand_size;
+        self
+    }
+
+    /// Deals the decks of the game shuffled with a seed to the players on `build`.
+    ///
+    /// # Arguments
+    ///
+    /// - `seed` -> Seed of the shuffle, the same seed deals the same hands
+    /// - `jokers` -> Number of jokers added to the deck
+    pub fn dealt(mut self, seed: u64, jokers: usize) -> Self {
+        self.deal = Some((seed, jokers));
+        self
+    }
+
+    /// Returns the built game.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a deck should be dealt to a game without players.
+    pub fn build(mut self) -> Game {
+        // the players sit in the order they were added, like players who joined one by one
+        for (seat_index, player) in self.game.players.iter_mut().enumerate() {
+            player.game_id = self.game.id.clone();
+            player.seat_index = seat_index;
+        }
+        if let Some(first) = self.game.players.first() {
+            if self.game.host_player_id.is_empty() {
+                self.game.host_player_id = first.id.clone();
+            }
+            if self.game.which_player_turn.is_empty() {
+                self.game.which_player_turn = first.id.clone();
+            }
+        }
+        if let Some((seed, jokers)) = self.deal {
+            let mut deck = Deck::combined(self.game.settings.deck_count, jokers);
+            deck.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+            match self.game.settings.hand_size {
+                Some(hand_size) => deck.deal_hands(&mut self.game.players, hand_size),
+                None => deck.deal(&mut self.game.players),
+            }
+            .expect("A deck can only be dealt to a game with players and enough cards");
+        }
+
+        self.game
+    }
+}
@@ -0,0 +1,3 @@
+pub mod claim_builder;
+pub mod game_builder;
+pub mod player_builder;
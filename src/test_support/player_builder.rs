@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+
+use crate::types::{card::Card, player::Player};
+
+/// Builds a `Player` for tests with sensible defaults.
+///
+/// A fresh builder creates a player named `Player` with a random id, no cards and a score of 0
+/// who joined just now. Every field can be overridden fluently.
+///
+/// # Example usage:
+///
+/// ```rust
+/// let player = PlayerBuilder::new().name("Alice").score(3).build();
+/// ```
+pub struct PlayerBuilder {
+    /// The player built so far
+    player: Player,
+}
+
+impl Default for PlayerBuilder {
+    fn default() -> Self {
+        PlayerBuilder::new()
+    }
+}
+
+impl PlayerBuilder {
+    /// Creates a builder with the default player.
+    pub fn new() -> Self {
+        PlayerBuilder {
+            player: Player::new("Player".to_string(), String::new()),
+        }
+    }
+
+    /// Sets the id of the player.
+    pub fn id(mut self, id: &str) -> Self {
+        self.player.id = id.to_string();
+        self
+    }
+
+    /// Sets the name of the player.
+    pub fn name(mut self, name: &str) -> Self {
+        self.player.name = name.to_string();
+        self
+    }
+
+    /// Sets the game the player joined.
+    pub fn game_id(mut self, game_id: &str) -> Self {
+        self.player.game_id = game_id.to_string();
+        self
+    }
+
+    /// Sets the score of the player.
+    pub fn score(mut self, score: usize) -> Self {
+        self.player.score = score;
+        self
+    }
+
+    /// Sets the hand of the player.
+    pub fn cards(mut self, cards: Vec<Card>) -> Self {
+        self.player.assigned_cards = cards;
+        self
+    }
+
+    /// Sets the fingerprint of the client of the player.
+    pub fn fingerprint(mut self, fingerprint: &str) -> Self {
+        self.player.fingerprint = Some(fingerprint.to_string());
+        self
+    }
+
+    /// Sets the point in time the player joined and last requested an update.
+    pub fn joined_at(mut self, joined_at: DateTime<Utc>) -> Self {
+        self.player.joined_at = joined_at;
+        self.player.last_time_update_requested = joined_at;
+        self
+    }
+
+    /// Returns the built player.
+    pub fn build(self) -> Player {
+        self.player
+    }
+}
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums::game_state::GameState, types::game::Game};
+
+/// Record of a game that was abandoned while its players may still come back.
+///
+/// Published as a `gameAbandoned` event and kept in the event log, so the host can reclaim the
+/// game within the grace period.
+///
+/// # Props
+///
+/// - `game_id` -> Id of the game
+/// - `previous_state` -> State of the game before it was abandoned
+/// - `abandoned_at` -> Point in time the game was abandoned
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Abandonment {
+    /// Id of the game
+    pub game_id: String,
+    /// State of the game before it was abandoned, which a reclaim restores
+    pub previous_state: GameState,
+    /// Point in time the game was abandoned
+    pub abandoned_at: DateTime<Utc>,
+}
+
+impl Abandonment {
+    /// Records the abandonment of a game.
+    ///
+    /// # Arguments
+    ///
+    /// - `game` -> The game, still in the state it had before it was abandoned
+    /// - `abandoned_at` -> Point in time the game is abandoned
+    pub fn new(game: &Game, abandoned_at: DateTime<Utc>) -> Self {
+        Abandonment {
+            game_id: game.id.clone(),
+            previous_state: game.state.clone(),
+            abandoned_at,
+        }
+    }
+
+    /// Checks whether the game can still be reclaimed.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` -> Point in time of the reclaim
+    /// - `grace_minutes` -> Minutes after the abandonment the game can be reclaimed
+    pub fn is_reclaimable(&self, now: DateTime<Utc>, grace_minutes: i64) -> bool {
+        now - self.abandoned_at <= Duration::minutes(grace_minutes)
+    }
+}
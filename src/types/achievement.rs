@@ -0,0 +1,126 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    enums::achievement::Achievement,
+    errors::application_error::{ErrorObject, Redact},
+    utils::d1_conversions::{datetime_from_text, enum_from_column},
+};
+
+/// An achievement a player unlocked, as it is stored in the `achievements` table.
+///
+/// # Props
+///
+/// - `achievement` -> The unlocked achievement
+/// - `game_id` -> Game in which the achievement was unlocked
+/// - `unlocked_at` -> Point in time the achievement was unlocked
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockedAchievement {
+    /// The unlocked achievement
+    #[serde(deserialize_with = "enum_from_column")]
+    pub achievement: Achievement,
+    /// Game in which the achievement was unlocked
+    #[serde(alias = "game_id", default)]
+    pub game_id: Option<String>,
+    /// Point in time the achievement was unlocked
+    #[serde(alias = "unlocked_at", deserialize_with = "datetime_from_text")]
+    pub unlocked_at: DateTime<Utc>,
+}
+
+impl fmt::Display for UnlockedAchievement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Achievement: {}, Unlocked at: {}",
+            self.achievement, self.unlocked_at
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for UnlockedAchievement {}
+
+impl Redact for UnlockedAchievement {}
+
+/// An achievement and whether a player unlocked it.
+///
+/// # Props
+///
+/// - `id` -> The achievement
+/// - `title` -> Name of the achievement
+/// - `description` -> What a player has to do to unlock it
+/// - `game_id` -> Game in which the achievement was unlocked
+/// - `unlocked_at` -> Point in time the achievement was unlocked; `None` while it's locked
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AchievementStatus {
+    /// The achievement
+    pub id: Achievement,
+    /// Name of the achievement
+    pub title: &'static str,
+    /// What a player has to do to unlock it
+    pub description: &'static str,
+    /// Game in which the achievement was unlocked
+    pub game_id: Option<String>,
+    /// Point in time the achievement was unlocked
+    pub unlocked_at: Option<DateTime<Utc>>,
+}
+
+/// All achievements and which of them a player unlocked.
+///
+/// # Props
+///
+/// - `unlocked` -> Number of achievements the player unlocked
+/// - `achievements` -> Every achievement, in the order of `Achievement::ALL`
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AchievementList {
+    /// Number of achievements the player unlocked
+    pub unlocked: usize,
+    /// Every achievement
+    pub achievements: Vec<AchievementStatus>,
+}
+
+impl AchievementList {
+    /// Lists every achievement with the unlocks of a player.
+    ///
+    /// # Arguments
+    ///
+    /// - `unlocked` -> Achievements the player unlocked
+    pub fn new(unlocked: Vec<UnlockedAchievement>) -> Self {
+        let achievements = Achievement::ALL
+            .into_iter()
+            .map(|achievement| {
+                let unlock = unlocked
+                    .iter()
+                    .find(|unlock| unlock.achievement == achievement);
+
+                AchievementStatus {
+                    id: achievement,
+                    title: achievement.title(),
+                    description: achievement.description(),
+                    game_id: unlock.and_then(|unlock| unlock.game_id.clone()),
+                    unlocked_at: unlock.map(|unlock| unlock.unlocked_at),
+                }
+            })
+            .collect();
+
+        AchievementList {
+            unlocked: unlocked.len(),
+            achievements,
+        }
+    }
+}
+
+impl IntoResponse for AchievementList {
+    /// Converts the `AchievementList` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
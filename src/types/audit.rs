@@ -0,0 +1,143 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    enums::audit_action::AuditAction,
+    errors::application_error::{ErrorObject, Redact},
+    utils::d1_conversions::{datetime_from_text, enum_from_column},
+};
+
+/// Entry of the audit log.
+///
+/// # Props
+///
+/// - `sequence` -> Position of the entry in the log, newer entries have a higher one
+/// - `actor` -> Who acted; the identity of an operator or `player:<id>` for a host
+/// - `action` -> What was done
+/// - `target` -> What the action was applied to, e.g. the id of a game or a fingerprint
+/// - `details` -> Additional information, e.g. the number of rows a migration changed
+/// - `request_id` -> Id of the request, to find its log lines
+/// - `created_at` -> Point in time the action was taken
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// Position of the entry in the log
+    pub sequence: usize,
+    /// Who acted
+    pub actor: String,
+    /// What was done
+    #[serde(deserialize_with = "enum_from_column")]
+    pub action: AuditAction,
+    /// What the action was applied to
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Additional information
+    #[serde(default)]
+    pub details: Option<String>,
+    /// Id of the request
+    #[serde(alias = "request_id", default)]
+    pub request_id: Option<String>,
+    /// Point in time the action was taken
+    #[serde(alias = "created_at", deserialize_with = "datetime_from_text")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Audit entry #{}: {} by {}",
+            self.sequence, self.action, self.actor
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for AuditEntry {}
+
+impl Redact for AuditEntry {}
+
+// ----- DTO for recording an action -----
+
+/// DTO type for an action that is added to the audit log.
+///
+/// # Props
+///
+/// - `actor` -> Who acted
+/// - `action` -> What was done
+/// - `target` -> What the action was applied to
+/// - `details` -> Additional information
+/// - `request_id` -> Id of the request
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAuditEntryDTO {
+    /// Who acted
+    pub actor: String,
+    /// What was done
+    pub action: AuditAction,
+    /// What the action was applied to
+    pub target: Option<String>,
+    /// Additional information
+    pub details: Option<String>,
+    /// Id of the request
+    pub request_id: Option<String>,
+}
+
+impl fmt::Display for CreateAuditEntryDTO {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Audit entry: {} by {}", self.action, self.actor)
+    }
+}
+
+impl<'a> ErrorObject<'a> for CreateAuditEntryDTO {}
+
+impl Redact for CreateAuditEntryDTO {}
+
+// ----- Page of the audit log -----
+
+/// One page of the audit log, newest entries first.
+///
+/// # Props
+///
+/// - `entries` -> Entries of the page
+/// - `next_before` -> Pass as `before` to get the next page; `None` on the last page
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogPage {
+    /// Entries of the page
+    pub entries: Vec<AuditEntry>,
+    /// Pass as `before` to get the next page; `None` on the last page
+    pub next_before: Option<usize>,
+}
+
+impl AuditLogPage {
+    /// Creates a page from the entries returned for a page size.
+    ///
+    /// # Arguments
+    ///
+    /// - `entries` -> Entries of the page, newest first
+    /// - `page_size` -> Requested number of entries; a full page may be followed by another one
+    pub fn new(entries: Vec<AuditEntry>, page_size: usize) -> Self {
+        let next_before = match entries.len() == page_size {
+            true => entries.last().map(|entry| entry.sequence),
+            false => None,
+        };
+
+        AuditLogPage {
+            entries,
+            next_before,
+        }
+    }
+}
+
+impl IntoResponse for AuditLogPage {
+    /// Converts the `AuditLogPage` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
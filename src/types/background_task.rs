@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::types::webhook::WebhookPayload;
+
+/// Work that doesn't need to finish before a player gets the response.
+///
+/// Sent to the `TASKS` queue and processed by the queue consumer of the worker. A task needs to
+/// be idempotent, since a failing batch is delivered again.
+///
+/// # Variants
+///
+/// - `AggregateStats` -> Stores the results of the players of a finished game
+/// - `ExportGame` -> Writes a finished game to the `ARCHIVES` bucket
+/// - `DeliverWebhook` -> Posts an event of a game to one webhook
+/// - `AdvanceTournament` -> Decides the match of a finished tournament game
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum BackgroundTask {
+    /// Stores the results of the players of a finished game.
+    #[serde(rename_all = "camelCase")]
+    AggregateStats {
+        /// Id of the finished game
+        #[serde(alias = "game_id")]
+        game_id: String,
+    },
+    /// Writes a finished game to the `ARCHIVES` bucket.
+    #[serde(rename_all = "camelCase")]
+    ExportGame {
+        /// Id of the finished game
+        #[serde(alias = "game_id")]
+        game_id: String,
+    },
+    /// Posts an event of a game to one webhook.
+    ///
+    /// A failed attempt is sent to the queue again with a growing delay, see `deliver_webhook`.
+    #[serde(rename_all = "camelCase")]
+    DeliverWebhook {
+        /// Id of the receiving webhook
+        #[serde(alias = "webhook_id")]
+        webhook_id: String,
+        /// Event to send
+        payload: WebhookPayload,
+        /// Number of attempts that already failed
+        #[serde(alias = "failed_attempts", default)]
+        failed_attempts: u32,
+    },
+    /// Decides the match of a finished tournament game and starts the next round if it was the
+    /// last match of its round.
+    #[serde(rename_all = "camelCase")]
+    AdvanceTournament {
+        /// Id of the finished game
+        #[serde(alias = "game_id")]
+        game_id: String,
+    },
+}
+
+impl fmt::Display for BackgroundTask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackgroundTask::AggregateStats { game_id } => {
+                write!(f, "Aggregate stats of game {game_id}")
+            }
+            BackgroundTask::ExportGame { game_id } => write!(f, "Export game {game_id}"),
+            BackgroundTask::DeliverWebhook {
+                webhook_id,
+                payload,
+                ..
+            } => write!(
+                f,
+                "Deliver '{}' of game {} to webhook {webhook_id}",
+                payload.event, payload.game_id
+            ),
+            BackgroundTask::AdvanceTournament { game_id } => {
+                write!(f, "Advance the tournament of game {game_id}")
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+
+use crate::{
+    enums::backup_table::BackupTable,
+    errors::application_error::{ErrorObject, Redact},
+};
+
+// constants
+/// Header of an export page carrying the `after` parameter of the next page.
+///
+/// Missing on the last page.
+pub const BACKUP_NEXT_AFTER_HEADER: &str = "Backup-Next-After";
+
+/// One line of a bulk export of games.
+///
+/// An export is newline-delimited JSON holding the raw rows of every table in the order of
+/// `BackupTable::ALL`, so it can be imported line by line.
+///
+/// ```json
+/// {"table":"games","row":{"id":"...","state":"Ended", ...}}
+/// {"table":"players","row":{"id":"...","game_id":"...", ...}}
+/// ```
+///
+/// # Props
+///
+/// - `table` -> Table the row belongs to
+/// - `row` -> Columns of the row as they are stored in D1
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BackupRecord {
+    /// Table the row belongs to
+    pub table: BackupTable,
+    /// Columns of the row as they are stored in D1
+    pub row: Map<String, Value>,
+}
+
+impl fmt::Display for BackupRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Backup record of {}", self.table)
+    }
+}
+
+impl<'a> ErrorObject<'a> for BackupRecord {}
+
+impl Redact for BackupRecord {}
+
+/// Result of a bulk import of games.
+///
+/// Every line is imported on its own, so a broken line doesn't stop the import.
+///
+/// # Props
+///
+/// - `imported` -> Number of imported rows
+/// - `games` -> Number of imported games
+/// - `failures` -> Lines that couldn't be imported
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    /// Number of imported rows
+    pub imported: usize,
+    /// Number of imported games
+    pub games: usize,
+    /// Lines that couldn't be imported
+    pub failures: Vec<ImportFailure>,
+}
+
+impl IntoResponse for ImportSummary {
+    /// Converts the `ImportSummary` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Line of an import that couldn't be imported.
+///
+/// # Props
+///
+/// - `line` -> Number of the line, starting at 1
+/// - `message` -> Why the line was rejected
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportFailure {
+    /// Number of the line, starting at 1
+    pub line: usize,
+    /// Why the line was rejected
+    pub message: String,
+}
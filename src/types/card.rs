@@ -3,12 +3,19 @@ use std::{
     fmt::{self, Display},
 };
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // using statements
 use crate::{
-    enums::card_types::CardType,
-    errors::{application_error::ErrorObject, process_error::ProcessError},
+    enums::{card_types::CardType, suit::Suit},
+    errors::{
+        application_error::{ErrorObject, Redact},
+        process_error::ProcessError,
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::locale::Locale,
+    utils::d1_conversions::{enum_from_column, optional_enum_from_column},
 };
 
 // This module defines the `Card` struct, which represents a card in a card game.
@@ -19,12 +26,18 @@ use crate::{
 ///
 /// # Fields
 /// - `card_type`: An enum representing the type of the card, such as King, Queen, Jack, Ace, or
-#[derive(Deserialize, Serialize)]
+/// - `suit`: The suit of the card; a Joker has no suit.
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct Card {
     /// The unique identifier for the card, typically a string.
     pub id: String,
     /// The type of the card, represented by the `CardType` enum.
+    #[serde(alias = "card_type", deserialize_with = "enum_from_column")]
     pub card_type: CardType,
+    /// The suit of the card, `None` for a Joker.
+    #[serde(default, deserialize_with = "optional_enum_from_column")]
+    pub suit: Option<Suit>,
 }
 
 impl Card {
@@ -39,6 +52,23 @@ impl Card {
         Card {
             id: uuid::Uuid::new_v4().to_string(),
             card_type,
+            suit: None,
+        }
+    }
+
+    /// Creates a new `Card` instance of a standard deck with a card type and a suit.
+    ///
+    /// # Arguments
+    /// - `card_type`: The type of the card, represented by the `CardType` enum.
+    /// - `suit`: The suit of the card.
+    ///
+    /// # Returns
+    /// A new `Card` instance.
+    pub fn with_suit(card_type: CardType, suit: Suit) -> Self {
+        Card {
+            id: uuid::Uuid::new_v4().to_string(),
+            card_type,
+            suit: Some(suit),
         }
     }
 }
@@ -55,7 +85,7 @@ impl Default for Card {
 
 impl fmt::Debug for Card {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Card Type: {}", self.card_type)
+        write!(f, "Card Type: {}, Suit: {:?}", self.card_type, self.suit)
     }
 }
 
@@ -63,25 +93,26 @@ impl clone::Clone for Card {
     fn clone(&self) -> Self {
         Card {
             id: self.id.clone(),
-            card_type: match self.card_type {
-                CardType::Ace => CardType::Ace,
-                CardType::King => CardType::King,
-                CardType::Joker => CardType::Jack,
-                CardType::Queen => CardType::Queen,
-                CardType::Jack => CardType::Jack,
-            },
+            card_type: self.card_type.clone(),
+            suit: self.suit.clone(),
         }
     }
 }
 
 impl Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Card Type: {}, ID: {}", self.card_type, self.id)
+        write!(
+            f,
+            "Card Type: {}, Suit: {:?}, ID: {}",
+            self.card_type, self.suit, self.id
+        )
     }
 }
 
 impl<'a> ErrorObject<'a> for Card {}
 
+impl Redact for Card {}
+
 // ----- Implementation of the 'UpdateCardDTO' struct -----
 
 /// The `UpdateCardDTO` struct is used to represent the data transfer object for updating a card.
@@ -95,7 +126,7 @@ impl<'a> ErrorObject<'a> for Card {}
 /// - `card_type`: The new type of the card, if it is being updated.
 /// - `player_id`: The ID of the player associated with the card, if applicable.
 /// - `claim_id`: The ID of the claim associated with the card, if applicable.
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateCardDTO {
     /// The unique identifier for the card to be updated.
@@ -152,6 +183,7 @@ impl UpdateCardDTO {
         Card {
             id: self.id.clone(),
             card_type: self.card_type.as_ref().unwrap_or(&CardType::King).clone(), // Default to King if not specified
+            suit: None,
         }
     }
 }
@@ -167,3 +199,34 @@ impl Display for UpdateCardDTO {
 }
 
 impl<'a> ErrorObject<'a> for UpdateCardDTO {}
+
+impl Redact for UpdateCardDTO {}
+
+impl Validate for UpdateCardDTO {
+    /// Checks the id and that at least one property should be changed.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.id.is_empty() {
+            errors.add("id", "required", "The card id must not be empty!");
+        }
+
+        if self.card_type.is_none() && self.player_id.is_none() && self.claim_id.is_none() {
+            errors.add("", "no_changes", "No new data was provided!");
+        }
+
+        if let Some(player_id) = &self.player_id {
+            if player_id.is_empty() {
+                errors.add("playerId", "required", "The player id must not be empty!");
+            }
+        }
+
+        if let Some(claim_id) = &self.claim_id {
+            if claim_id.is_empty() {
+                errors.add("claimId", "required", "The claim id must not be empty!");
+            }
+        }
+
+        errors.into_result()
+    }
+}
@@ -1,16 +1,37 @@
-use axum::Json;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use log::debug;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::errors::{
-    application_error::ErrorObject, bad_client_request::BadClientRequest,
+    application_error::{ErrorObject, Redact},
+    bad_client_request::BadClientRequest,
     invalid_message::InvalidMessageError,
+    process_error::ProcessError,
+    validation_errors::{Validate, ValidationErrors},
+};
+use crate::i18n::{catalog::Message, locale::Locale};
+use crate::middleware::authorization::ActingPlayer;
+use crate::utils::d1_conversions::{
+    bool_from_number, datetime_from_text, optional_datetime_from_text,
 };
+use crate::utils::time::server_time;
 
 // constants
 
 /// The maximum number of messages that can be stored in a chat.
-const MAX_CHAT_MESSAGE_LENGTH: usize = 50;
+///
+/// Also enforced in the database, where the whispers of a chat are capped separately.
+pub const MAX_CHAT_MESSAGE_LENGTH: usize = 50;
+
+/// Seconds after sending in which the author can still edit or delete a message.
+pub const MESSAGE_EDIT_WINDOW_SECONDS: i64 = 300;
 
 /// Represents a chat in the game, containing messages exchanged between players.
 ///
@@ -27,15 +48,20 @@ const MAX_CHAT_MESSAGE_LENGTH: usize = 50;
 ///    id: "9fd2151d-432e-4549-99bf-b684b5be9555".to_string()
 ///    };
 /// ```
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct Chat {
     /// Identifier of a chat instance
     pub id: String,
     /// List of all messages sent in the chat of a game
+    ///
+    /// Isn't a column of the `chats` table and needs to be fetched separately.
+    #[serde(default)]
     pub messages: Vec<ChatMessage>,
     /// Total number of all messages
     ///
     /// Maximal number: 50
+    #[serde(alias = "number_of_messages")]
     pub number_of_messages: usize,
 }
 
@@ -54,19 +80,50 @@ pub struct Chat {
 ///    id: Uuid::new_v4().to_string(),
 ///    player_id: Uuid::new_v4().to_string(),
 ///    content: String::from("Hello, world!"),
-///    sent_at: Utc::now().to_string(),
+///    sent_at: Utc::now(),
+///    is_system: false,
+///    edited_at: None,
+///    deleted_at: None,
+///    recipient_player_id: None,
 ///    };
 /// ```  
-#[derive(Deserialize, Serialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ChatMessage {
     /// Identifier of the ChatMessage
     pub id: String,
     /// ID of the player, who sent the message
+    #[serde(alias = "player_id")]
     pub player_id: String,
     /// Content of the message
     pub content: String,
-    /// Date string, when the message was sent by the user
-    pub sent_at: String, // as chrono::DateTime<chrono::Utc>,
+    /// Point in time, when the message was sent by the user
+    #[serde(alias = "sent_at", deserialize_with = "datetime_from_text")]
+    pub sent_at: DateTime<Utc>,
+    /// Whether the message was created by the server, e.g. as a moderation notice
+    ///
+    /// System messages are still attributed to the player they are about.
+    #[serde(default, alias = "is_system", deserialize_with = "bool_from_number")]
+    pub is_system: bool,
+    /// Point in time the author last edited the message; `None` if it was never edited
+    #[serde(
+        alias = "edited_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub edited_at: Option<DateTime<Utc>>,
+    /// Point in time the author deleted the message; the content of a deleted message is empty
+    #[serde(
+        alias = "deleted_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Id of the only player who receives the message; `None` for a message to everyone
+    ///
+    /// Whispers are never part of the public chat of a game.
+    #[serde(alias = "recipient_player_id", default)]
+    pub recipient_player_id: Option<String>,
 }
 
 // Implementation of 'Chat' struct
@@ -113,7 +170,7 @@ impl Chat {
     pub fn add_chat_message(&mut self, message: ChatMessage) -> Result<(), InvalidMessageError> {
         // message needs to be long enough
         if message.content.is_empty() {
-            println!("The message is too short to be added to the chat!");
+            debug!("The message is too short to be added to the chat!");
             return Err(InvalidMessageError {
                 message: "Too short message content! Must not be of length 0!".to_string(),
                 origin_message: message,
@@ -122,7 +179,7 @@ impl Chat {
 
         // check if the maximum number of messages was reached
         if self.number_of_messages >= MAX_CHAT_MESSAGE_LENGTH {
-            println!("Maximum number of chat messages has been reached! Deleting oldest message to add the new one.");
+            debug!("Maximum number of chat messages has been reached! Deleting oldest message to add the new one.");
             self.messages.remove(0);
             self.messages.push(message);
             return Ok(());
@@ -134,6 +191,25 @@ impl Chat {
 
         Ok(())
     }
+
+    /// Writes the system messages of the chat in a locale.
+    ///
+    /// # Returns
+    ///
+    /// The chat as a reader with the `locale` sees it.
+    pub fn localized(mut self, locale: &Locale) -> Self {
+        for message in &mut self.messages {
+            message.localize(locale);
+        }
+        self
+    }
+}
+
+impl IntoResponse for Chat {
+    /// Converts the `Chat` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
 }
 
 // Implementation of 'ChatMessage' struct
@@ -152,15 +228,19 @@ impl ChatMessage {
         id: String,
         player_id: String,
         content: String,
-        sent_at: String,
+        sent_at: DateTime<Utc>,
     ) -> Result<Self, BadClientRequest<ChatMessage>> {
-        if content.is_empty() || player_id.is_empty() || sent_at.is_empty() {
+        if content.is_empty() || player_id.is_empty() {
             return Err::<ChatMessage, BadClientRequest<_>>(BadClientRequest {
                 bad_data: Json(ChatMessage {
                     id: id.clone(),
                     player_id: player_id.clone(),
-                    sent_at: sent_at.clone(),
+                    sent_at,
                     content: content.clone(),
+                    is_system: false,
+                    edited_at: None,
+                    deleted_at: None,
+                    recipient_player_id: None,
                 }),
                 message: format!(
                     "The provided data by player with id: {} for a chat message was not valid!",
@@ -173,8 +253,105 @@ impl ChatMessage {
             player_id,
             content,
             sent_at,
+            is_system: false,
+            edited_at: None,
+            deleted_at: None,
+            recipient_player_id: None,
         })
     }
+
+    /// Creates a message of the server about a player, e.g. a moderation notice.
+    ///
+    /// # Arguments
+    ///
+    /// - `player_id` -> Id of the player the message is about
+    /// - `notice` -> Text of the message, which is stored in English
+    pub fn system(player_id: String, notice: Message) -> Self {
+        ChatMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            player_id,
+            content: notice.text(&Locale::En),
+            sent_at: server_time(),
+            is_system: true,
+            edited_at: None,
+            deleted_at: None,
+            recipient_player_id: None,
+        }
+    }
+
+    /// Writes a system message in a locale; the messages of the players are kept as they are.
+    pub fn localize(&mut self, locale: &Locale) {
+        let notice = self
+            .is_system
+            .then(|| Message::chat_notice(&self.content))
+            .flatten();
+        if let Some(notice) = notice {
+            self.content = notice.text(locale);
+        }
+    }
+
+    /// Returns the ids of the players a whisper is delivered to, the sender and the recipient.
+    ///
+    /// `None` if the message is sent to everyone.
+    pub fn whisper_players(&self) -> Option<[&str; 2]> {
+        self.recipient_player_id
+            .as_deref()
+            .map(|recipient_player_id| [self.player_id.as_str(), recipient_player_id])
+    }
+
+    /// Makes sure that the author can still change the message.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` -> Point in time of the change
+    /// - `locale` -> Language of the error
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` if the message was sent by the server, was already deleted or was
+    /// sent more than `MESSAGE_EDIT_WINDOW_SECONDS` ago.
+    pub fn ensure_changeable(
+        &self,
+        now: DateTime<Utc>,
+        locale: &Locale,
+    ) -> Result<(), ProcessError<ChatMessage>> {
+        let message = if self.is_system {
+            Message::SystemMessageLocked
+        } else if self.deleted_at.is_some() {
+            Message::MessageAlreadyDeleted
+        } else if now - self.sent_at > Duration::seconds(MESSAGE_EDIT_WINDOW_SECONDS) {
+            Message::MessageTooOld
+        } else {
+            return Ok(());
+        };
+
+        Err(ProcessError::new(
+            message.text(locale),
+            "ensure_changeable".to_string(),
+            Some(self.clone()),
+        ))
+    }
+
+    /// Replaces the content of the message.
+    ///
+    /// # Arguments
+    ///
+    /// - `content` -> The new text of the message
+    /// - `now` -> Point in time of the edit
+    pub fn edit(&mut self, content: String, now: DateTime<Utc>) {
+        self.content = content;
+        self.edited_at = Some(now);
+    }
+
+    /// Turns the message into a tombstone, which keeps its place in the chat without content.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` -> Point in time of the deletion
+    pub fn delete(&mut self, now: DateTime<Utc>) {
+        self.content = String::new();
+        self.deleted_at = Some(now);
+    }
 }
 impl fmt::Display for ChatMessage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -206,4 +383,114 @@ impl fmt::Debug for ChatMessage {
     }
 }
 
+impl IntoResponse for ChatMessage {
+    /// Converts the `ChatMessage` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
 impl<'a> ErrorObject<'a> for ChatMessage {}
+
+impl Redact for ChatMessage {}
+
+impl Validate for ChatMessage {
+    /// Checks that the sender, the content and the timestamp are provided.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.player_id.is_empty() {
+            errors.add(
+                "player_id",
+                "required",
+                "The sender of a message is mandatory!",
+            );
+        }
+        if self.content.trim().is_empty() {
+            errors.add(
+                "content",
+                "required",
+                &Message::MessageContentEmpty.text(locale),
+            );
+        }
+        if self.sent_at > server_time() {
+            errors.add(
+                "sent_at",
+                "in_future",
+                "A message can't be sent in the future!",
+            );
+        }
+        if self.recipient_player_id.as_deref() == Some(self.player_id.as_str()) {
+            errors.add(
+                "recipient_player_id",
+                "self",
+                &Message::WhisperToSelf.text(locale),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+// ----- DTO for sending a chat message -----
+
+/// DTO type for a player who sends a message in the chat of a game.
+///
+/// The id and the timestamp of the message are generated by the server.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the player who sends the message
+/// - `content` -> Text of the message
+/// - `recipient_player_id` -> Id of the player to whisper to; `None` to write to everyone
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SendChatMessageDTO {
+    /// Id of the player who sends the message
+    #[serde(alias = "player_id")]
+    pub player_id: String,
+    /// Text of the message
+    pub content: String,
+    /// Id of the player to whisper to; `None` to write to everyone
+    #[serde(alias = "recipient_player_id", default)]
+    pub recipient_player_id: Option<String>,
+}
+
+impl SendChatMessageDTO {
+    /// Creates the `ChatMessage` with a generated id, sent right now.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `BadClientRequest` if the sender or the content is missing.
+    pub fn into_message(self) -> Result<ChatMessage, BadClientRequest<ChatMessage>> {
+        let mut message = ChatMessage::new(
+            uuid::Uuid::new_v4().to_string(),
+            self.player_id,
+            self.content,
+            server_time(),
+        )?;
+        message.recipient_player_id = self.recipient_player_id;
+
+        Ok(message)
+    }
+}
+
+impl ActingPlayer for SendChatMessageDTO {
+    fn acting_player_id(&self) -> &str {
+        &self.player_id
+    }
+}
+
+// ----- DTO for editing a chat message -----
+
+/// DTO type for the author who edits one of their chat messages.
+///
+/// # Props
+///
+/// - `content` -> The new text of the message
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EditChatMessageDTO {
+    /// The new text of the message
+    pub content: String,
+}
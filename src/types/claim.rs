@@ -3,13 +3,30 @@
 
 use std::fmt;
 
-use axum::Json;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // using statements
 use crate::{
-    errors::{application_error::ErrorObject, bad_client_request::BadClientRequest},
+    enums::card_types::CardType,
+    errors::{
+        application_error::{ErrorObject, Redact},
+        bad_client_request::BadClientRequest,
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::{catalog::Message, locale::Locale},
+    middleware::authorization::ActingPlayer,
     types::card::Card,
+    utils::{
+        d1_conversions::{bool_from_number, datetime_from_text, optional_enum_from_column},
+        time::server_time,
+    },
 };
 
 // constants
@@ -22,18 +39,55 @@ const MAX_CARDS_PER_CLAIM: usize = 4;
 /// It contains information about the player who made the claim and the number of cards claimed.
 ///
 /// # Fields
+/// - `id`: The unique identifier of the claim.
 /// - `created_by`: The unique identifier of the player who made the claim.
+/// - `game_id`: The unique identifier of the game the claim was made in.
+/// - `round_number`: The round of the game the claim was made in.
+/// - `created_at`: Timestamp when the claim was made.
 /// - `number_of_cards`: The number of cards claimed by the player.
-#[derive(Deserialize, Serialize, Clone)]
+/// - `declared_card_type`: The card type the player announced, e.g. the Kings of "three Kings".
+/// - `declared_count`: The number of cards the player announced.
+#[derive(Deserialize, Serialize, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct Claim {
     /// Unique identifier for the claim
     pub id: String,
     /// Id of the user that placed the claim on the stack
+    #[serde(alias = "created_by")]
     pub created_by: String,
+    /// Id of the game the claim belongs to
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// Round of the game in which the claim was made
+    #[serde(alias = "round_number")]
+    pub round_number: usize,
+    /// Timestamp when the claim was made
+    #[serde(alias = "created_at", deserialize_with = "datetime_from_text")]
+    pub created_at: DateTime<Utc>,
     /// Number of cards used in the claim
+    #[serde(alias = "number_of_cards")]
     pub number_of_cards: usize,
     /// List of placed cards in the claim
+    ///
+    /// Isn't a column of the `claims` table and needs to be fetched separately.
+    #[serde(default)]
     pub cards: Vec<Card>,
+    /// Whether the placed cards were turned face-up, e.g. after a challenge
+    #[serde(default, deserialize_with = "bool_from_number")]
+    pub revealed: bool,
+    /// Card type the player announced with the claim
+    ///
+    /// Visible to everyone, while the placed cards stay face-down until a challenge. `None` for
+    /// claims made before declarations were recorded.
+    #[serde(
+        alias = "declared_card_type",
+        default,
+        deserialize_with = "optional_enum_from_column"
+    )]
+    pub declared_card_type: Option<CardType>,
+    /// Number of cards the player announced with the claim
+    #[serde(alias = "declared_count", default)]
+    pub declared_count: Option<usize>,
 }
 
 impl Claim {
@@ -41,6 +95,8 @@ impl Claim {
     ///
     /// # Arguments
     /// - `created_by`: The unique identifier of the player making the claim.
+    /// - `game_id`: The unique identifier of the game the claim is made in.
+    /// - `round_number`: The current round of the game.
     /// - `number_of_cards`: The number of cards claimed by the player.
     /// - 'cards' : List of cards with a maximum number of 4
     ///
@@ -52,29 +108,68 @@ impl Claim {
     /// A new `Claim` instance.
     pub fn new(
         created_by: String,
+        game_id: String,
+        round_number: usize,
         number_of_cards: usize,
         cards: Vec<Card>,
     ) -> Result<Self, BadClientRequest<Claim>> {
+        let created_at = server_time();
+
         if number_of_cards > MAX_CARDS_PER_CLAIM {
             return Err::<Claim, BadClientRequest<Claim>>(BadClientRequest {
                 message: "The user handed in an invalid claim object!".to_string(),
                 bad_data: Json(Claim {
                     id: "No ID".to_string(),
                     created_by: created_by.clone(),
+                    game_id: game_id.clone(),
+                    round_number,
+                    created_at,
                     number_of_cards,
                     cards: cards.clone(),
+                    revealed: false,
+                    declared_card_type: None,
+                    declared_count: None,
                 }),
             });
         };
         Ok(Claim {
             id: uuid::Uuid::new_v4().to_string(),
             created_by,
+            game_id,
+            round_number,
+            created_at,
             number_of_cards,
             cards,
+            revealed: false,
+            declared_card_type: None,
+            declared_count: None,
         })
     }
 }
 
+impl Claim {
+    /// Returns `true` if all placed cards match the card to play of the round.
+    ///
+    /// A Joker matches every card type.
+    pub fn is_truthful(&self, card_to_play: &CardType) -> bool {
+        self.cards
+            .iter()
+            .all(|card| card.card_type == *card_to_play || card.card_type == CardType::Joker)
+    }
+
+    /// Records what the player announced with the claim.
+    ///
+    /// # Arguments
+    ///
+    /// - `card_type` -> The announced card type
+    /// - `count` -> The announced number of cards
+    pub fn declare(mut self, card_type: CardType, count: usize) -> Self {
+        self.declared_card_type = Some(card_type);
+        self.declared_count = Some(count);
+        self
+    }
+}
+
 impl fmt::Display for Claim {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -82,10 +177,22 @@ impl fmt::Display for Claim {
             "
         Id: {},
         Created By: {},
+        Game: {},
+        Round: {},
+        Created At: {},
         Number of Cards: {},
+        Declared: {:?} x {:?},
         All cards: {:?}
             ",
-            self.id, self.created_by, self.number_of_cards, self.cards
+            self.id,
+            self.created_by,
+            self.game_id,
+            self.round_number,
+            self.created_at,
+            self.number_of_cards,
+            self.declared_count,
+            self.declared_card_type,
+            self.cards
         )
     }
 }
@@ -97,12 +204,218 @@ impl fmt::Debug for Claim {
             "
         id: {},
         Created By: {},
+        Game: {},
+        Round: {},
+        Created At: {},
         Number of Cards: {},
+        Declared: {:?} x {:?},
         All cards: {:?}
             ",
-            self.id, self.created_by, self.number_of_cards, self.cards
+            self.id,
+            self.created_by,
+            self.game_id,
+            self.round_number,
+            self.created_at,
+            self.number_of_cards,
+            self.declared_count,
+            self.declared_card_type,
+            self.cards
         )
     }
 }
 
 impl<'a> ErrorObject<'a> for Claim {}
+
+impl Validate for Claim {
+    /// Checks the creator and the number of placed cards.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.created_by.is_empty() {
+            errors.add(
+                "created_by",
+                "required",
+                "The creator of a claim is mandatory!",
+            );
+        }
+
+        if self.game_id.is_empty() {
+            errors.add("game_id", "required", "The game of a claim is mandatory!");
+        }
+
+        if self.number_of_cards == 0 || self.number_of_cards > MAX_CARDS_PER_CLAIM {
+            errors.add(
+                "number_of_cards",
+                "out_of_range",
+                &Message::ClaimCardsOutOfRange
+                    .format(locale, &[("max", &MAX_CARDS_PER_CLAIM.to_string())]),
+            );
+        }
+
+        if self.number_of_cards != self.cards.len() {
+            errors.add(
+                "cards",
+                "count_mismatch",
+                &Message::ClaimCardsMismatch.text(locale),
+            );
+        }
+
+        if let Some(declared_count) = self.declared_count {
+            if declared_count == 0 || declared_count > MAX_CARDS_PER_CLAIM {
+                errors.add(
+                    "declared_count",
+                    "out_of_range",
+                    &format!("A player can declare between 1 and {MAX_CARDS_PER_CLAIM} cards!"),
+                );
+            }
+        }
+
+        if self.declared_card_type == Some(CardType::Joker) {
+            errors.add(
+                "declared_card_type",
+                "joker",
+                &Message::DeclaredJoker.text(locale),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+// ----- Implementation of 'Redact' for 'Claim' -----
+
+impl Redact for Claim {
+    /// The placed cards of a claim are face-down, so they are removed.
+    ///
+    /// The `number_of_cards` and the declaration stay visible.
+    fn redact(&mut self) {
+        self.cards = vec![];
+    }
+}
+
+// ----- Implementation of 'IntoResponse' for 'Claim' -----
+
+impl IntoResponse for Claim {
+    /// Converts the `Claim` into a response with status code 200.
+    ///
+    /// The placed cards stay hidden until the claim was revealed.
+    fn into_response(mut self) -> Response {
+        if !self.revealed {
+            self.redact();
+        }
+
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// ----- DTO for creating a claim -----
+
+/// DTO type for a player who places cards on the stack.
+///
+/// The round number and the creation timestamp are determined by the server.
+///
+/// # Props
+///
+/// - `created_by` -> Id of the player who makes the claim
+/// - `game_id` -> Id of the game the claim is made in
+/// - `cards` -> The cards the player places on the stack
+/// - `declared_card_type` -> Card type the player announces; the card to play if omitted
+/// - `declared_count` -> Number of cards the player announces; the number of placed cards if
+///   omitted
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateClaimDTO {
+    /// Id of the player who makes the claim
+    #[serde(alias = "created_by")]
+    pub created_by: String,
+    /// Id of the game the claim is made in
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// Placed cards
+    pub cards: Vec<Card>,
+    /// Announced card type, e.g. the Kings of "three Kings"
+    #[serde(alias = "declared_card_type", default)]
+    pub declared_card_type: Option<CardType>,
+    /// Announced number of cards, e.g. the three of "three Kings"
+    #[serde(alias = "declared_count", default)]
+    pub declared_count: Option<usize>,
+}
+
+impl CreateClaimDTO {
+    /// Creates a new `Claim` in the provided round of the game.
+    ///
+    /// # Arguments
+    ///
+    /// - `round_number` -> Current round of the game the claim is made in
+    /// - `card_to_play` -> Card type of the round, declared if the player didn't name one
+    ///
+    /// # Errors
+    ///
+    /// Returns a `BadClientRequest<Claim>` if too many cards were placed.
+    pub fn into_claim(
+        self,
+        round_number: usize,
+        card_to_play: &CardType,
+    ) -> Result<Claim, BadClientRequest<Claim>> {
+        let number_of_cards = self.cards.len();
+        let declared_card_type = self
+            .declared_card_type
+            .unwrap_or_else(|| card_to_play.clone());
+        let declared_count = self.declared_count.unwrap_or(number_of_cards);
+
+        Claim::new(
+            self.created_by,
+            self.game_id,
+            round_number,
+            number_of_cards,
+            self.cards,
+        )
+        .map(|claim| claim.declare(declared_card_type, declared_count))
+    }
+}
+
+impl fmt::Display for CreateClaimDTO {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Created By: {}, Game: {}, Cards: {:?}, Declared: {:?} x {:?}",
+            self.created_by, self.game_id, self.cards, self.declared_count, self.declared_card_type
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for CreateClaimDTO {}
+
+impl Redact for CreateClaimDTO {
+    /// The placed cards are face-down.
+    fn redact(&mut self) {
+        self.cards = vec![];
+    }
+}
+
+impl ActingPlayer for CreateClaimDTO {
+    fn acting_player_id(&self) -> &str {
+        &self.created_by
+    }
+}
+
+// ----- DTO for challenging a claim -----
+
+/// DTO type for a player who doubts the last claim of the round.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the player who challenges the claim
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengeDTO {
+    /// Id of the player who challenges the claim
+    #[serde(alias = "player_id")]
+    pub player_id: String,
+}
+
+impl ActingPlayer for ChallengeDTO {
+    fn acting_player_id(&self) -> &str {
+        &self.player_id
+    }
+}
@@ -0,0 +1,147 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::application_error::{ErrorObject, Redact},
+    utils::d1_conversions::{bool_from_number, datetime_from_text},
+};
+
+/// Template of the daily challenge of one UTC day.
+///
+/// Every game of the day is shuffled and picks its cards to play with the same seed, so the
+/// players can compare their results on the same scenario.
+///
+/// # Props
+///
+/// - `date` -> Day of the challenge
+/// - `seed` -> Secret seed of the deck and the cards to play; never sent to a client
+/// - `created_at` -> Point in time the template was created
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyChallenge {
+    /// Day of the challenge
+    pub date: NaiveDate,
+    /// Secret seed of the deck and the cards to play
+    #[serde(skip_serializing)]
+    pub seed: String,
+    /// Point in time the template was created
+    #[serde(alias = "created_at", deserialize_with = "datetime_from_text")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl fmt::Display for DailyChallenge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Daily challenge of {}", self.date)
+    }
+}
+
+impl<'a> ErrorObject<'a> for DailyChallenge {}
+
+impl Redact for DailyChallenge {
+    /// Removes the seed, which reveals the hands of the day.
+    fn redact(&mut self) {
+        self.seed.clear();
+    }
+}
+
+/// Result of one player in a daily challenge.
+///
+/// # Props
+///
+/// - `rank` -> Position among all results of the day, starting at 1
+/// - `player_name` -> Name of the player
+/// - `score` -> Final score of the player
+/// - `is_winner` -> Whether the player won their game
+/// - `rounds_played` -> Number of rounds of the game
+/// - `player_count` -> Number of players of the game, which decides the dealt hands
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyResult {
+    /// Position among all results of the day
+    #[serde(default)]
+    pub rank: usize,
+    /// Name of the player
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+    /// Final score of the player
+    pub score: usize,
+    /// Whether the player won their game
+    #[serde(alias = "is_winner", deserialize_with = "bool_from_number")]
+    pub is_winner: bool,
+    /// Number of rounds of the game
+    #[serde(alias = "rounds_played")]
+    pub rounds_played: usize,
+    /// Number of players of the game
+    #[serde(alias = "player_count")]
+    pub player_count: usize,
+}
+
+impl fmt::Display for DailyResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} with a score of {}",
+            self.rank, self.player_name, self.score
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for DailyResult {}
+
+impl Redact for DailyResult {}
+
+/// The daily challenge of a day with the best results so far.
+///
+/// # Props
+///
+/// - `challenge` -> Template of the day
+/// - `games_played` -> Finished games of the challenge
+/// - `results` -> Best results, winners first, then by score and fewer rounds
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyChallengeView {
+    /// Template of the day
+    pub challenge: DailyChallenge,
+    /// Finished games of the challenge
+    pub games_played: usize,
+    /// Best results
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyChallengeView {
+    /// Creates the view from a template and its best results.
+    ///
+    /// # Arguments
+    ///
+    /// - `challenge` -> Template of the day
+    /// - `games_played` -> Finished games of the challenge
+    /// - `results` -> Best results, best first
+    pub fn new(
+        challenge: DailyChallenge,
+        games_played: usize,
+        mut results: Vec<DailyResult>,
+    ) -> Self {
+        for (index, result) in results.iter_mut().enumerate() {
+            result.rank = index + 1;
+        }
+
+        DailyChallengeView {
+            challenge,
+            games_played,
+            results,
+        }
+    }
+}
+
+impl IntoResponse for DailyChallengeView {
+    /// Converts the `DailyChallengeView` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,88 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::enums::deployment_environment::DeploymentEnvironment;
+
+/// State of a deployed worker, returned to an operator who debugs it.
+///
+/// # Props
+///
+/// - `build` -> Commit and time of the build
+/// - `environment` -> Environment the worker is deployed to
+/// - `bindings` -> Every binding and secret the worker can use and whether it is configured
+/// - `latest_migration` -> Last applied schema migration; `None` if none or the query failed
+/// - `database_latency_ms` -> Round trip of a trivial query to D1; `None` if it failed
+/// - `database_error` -> Why D1 couldn't be queried
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostics {
+    /// Commit and time of the build
+    pub build: BuildInfo,
+    /// Environment the worker is deployed to
+    pub environment: DeploymentEnvironment,
+    /// Every binding and secret the worker can use
+    pub bindings: Vec<BindingStatus>,
+    /// Last applied schema migration
+    pub latest_migration: Option<String>,
+    /// Round trip of a trivial query to D1
+    pub database_latency_ms: Option<i64>,
+    /// Why D1 couldn't be queried
+    pub database_error: Option<String>,
+}
+
+impl IntoResponse for Diagnostics {
+    /// Converts the `Diagnostics` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Commit and time of the build, embedded by `build.rs`.
+///
+/// # Props
+///
+/// - `git_sha` -> Abbreviated hash of the built commit; `unknown` outside of a git checkout
+/// - `built_at` -> Point in time the worker was compiled
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    /// Abbreviated hash of the built commit
+    pub git_sha: &'static str,
+    /// Point in time the worker was compiled
+    pub built_at: Option<DateTime<Utc>>,
+}
+
+impl BuildInfo {
+    /// Returns the build info of the running worker.
+    pub fn current() -> Self {
+        BuildInfo {
+            git_sha: option_env!("LUELUE_GIT_SHA").unwrap_or("unknown"),
+            built_at: option_env!("LUELUE_BUILT_AT")
+                .and_then(|seconds| seconds.parse::<i64>().ok())
+                .and_then(|seconds| DateTime::from_timestamp(seconds, 0)),
+        }
+    }
+}
+
+/// Whether a binding or secret is configured.
+///
+/// # Props
+///
+/// - `name` -> Name of the binding, e.g. `GAME_CACHE`
+/// - `kind` -> Type of the binding, e.g. `kv` or `secret`
+/// - `present` -> `true` if the worker can access it
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingStatus {
+    /// Name of the binding
+    pub name: &'static str,
+    /// Type of the binding
+    pub kind: &'static str,
+    /// `true` if the worker can access it
+    pub present: bool,
+}
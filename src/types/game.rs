@@ -1,20 +1,38 @@
 use std::fmt::{Debug, Display};
 
+use crate::durable::game_room::is_archived_state;
 use crate::enums::game_state::GameState;
-use crate::errors::application_error::ErrorObject;
+use crate::errors::application_error::{ErrorObject, Redact};
 use crate::errors::process_error::ProcessError;
+use crate::errors::validation_errors::{Validate, ValidationErrors};
+use crate::i18n::{catalog::Message, locale::Locale};
+use crate::logic::daily_challenge::{daily_card_to_play, daily_deck_rng};
+use crate::types::abandonment::Abandonment;
 use crate::types::chat::Chat;
 use crate::types::claim::Claim;
-use crate::utils::game_service::select_new_card_to_be_played;
-use crate::{enums::card_types::CardType, types::player::Player};
+use crate::types::game_settings::GameSettings;
+use crate::types::pile_summary::PileSummary;
+use crate::utils::d1_conversions::{
+    datetime_from_text, enum_from_column, optional_datetime_from_text,
+};
+use crate::utils::game_service::{generate_join_code, new_rng, select_new_card_to_be_played};
+use crate::utils::time::server_time;
+use crate::{
+    enums::card_types::CardType,
+    types::player::{validate_player_name, Player},
+};
 use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, NaiveDate, Utc};
+use rand_chacha::{rand_core::RngCore, ChaCha8Rng};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 // constants
 /// The maximum number of players allowed in a game.
-const MAX_PLAYERS: usize = 5;
+pub const MAX_PLAYERS: usize = 5;
 
 /// Global struct representing a game in the system.k
 ///
@@ -24,28 +42,105 @@ const MAX_PLAYERS: usize = 5;
 ///
 /// Holds information about the state of the game, such as players, scores, and other relevant
 /// details.
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct Game {
     /// Unique identifier for the game instance.
     pub id: String,
+    /// ID of the player who created the game and is allowed to manage it.
+    #[serde(alias = "host_player_id", default)]
+    pub host_player_id: String,
     /// List of player IDs participating in the game.
+    ///
+    /// Isn't a column of the `games` table and needs to be fetched separately.
+    #[serde(default)]
     pub players: Vec<Player>,
     /// ID of the player whose turn it is.
+    #[serde(alias = "which_player_turn")]
     pub which_player_turn: String, // ID of the player whose turn it is
-    /// Current state of the game, represented as a string.
+    /// Current state of the game.
+    #[serde(deserialize_with = "enum_from_column")]
     pub state: GameState,
     /// Timestamp when the game was created
     ///
     /// This property is static.
-    pub started_at: String,
+    #[serde(alias = "started_at", deserialize_with = "datetime_from_text")]
+    pub started_at: DateTime<Utc>,
     /// The round number of the game
+    #[serde(alias = "round_number")]
     pub round_number: usize,
     /// Chat of the specific game
+    #[serde(default)]
     pub chat: Chat,
     /// Changes after every round and is randomly selected.
+    #[serde(alias = "card_to_play", deserialize_with = "enum_from_column")]
     pub card_to_play: CardType,
     /// Vector of claims every player made
+    #[serde(default)]
     pub claims: Vec<Claim>,
+    /// Settings chosen by the host when the game was created
+    #[serde(flatten)]
+    pub settings: GameSettings,
+    /// ID of the player who won the game
+    ///
+    /// Only set after the game ended with a single player holding the fewest cards.
+    #[serde(alias = "winner_player_id", default)]
+    pub winner_player_id: Option<String>,
+    /// Timestamp when the game ended
+    #[serde(
+        alias = "finished_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Number of rounds that were played until the game ended
+    #[serde(alias = "total_rounds", default)]
+    pub total_rounds: Option<usize>,
+    /// Short code other players can type in to join the game
+    #[serde(alias = "join_code", default)]
+    pub join_code: Option<String>,
+    /// Day of the daily challenge the game plays
+    #[serde(alias = "daily_date", default)]
+    pub daily_date: Option<NaiveDate>,
+    /// Secret seed of the daily challenge, which decides the deck and the card of every round
+    ///
+    /// Removed by `redact`, since it reveals the hands of all players.
+    #[serde(alias = "daily_seed", default)]
+    pub daily_seed: Option<String>,
+    /// Point in time the turn passed to `which_player_turn`; `None` before the first turn
+    #[serde(
+        alias = "turn_started_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub turn_started_at: Option<DateTime<Utc>>,
+    /// Point in time the host paused the game; `None` unless the game is paused
+    #[serde(
+        alias = "paused_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub paused_at: Option<DateTime<Utc>>,
+    /// Point in time the host reclaimed the game after it was abandoned; `None` if it never was
+    ///
+    /// The cleanup limits count from this point in time instead of the creation of the game.
+    #[serde(
+        alias = "reclaimed_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub reclaimed_at: Option<DateTime<Utc>>,
+    /// Version counter of the game, incremented with every update
+    ///
+    /// Read from D1 and replaced by the revision of the `GameRoom` Durable Object in the
+    /// `durable-object` storage mode. Isn't sent to the clients.
+    #[serde(default, skip_serializing)]
+    pub revision: u64,
+    /// Summary of the stack of the current round, read by `GameRepository::get_snapshot`
+    ///
+    /// Isn't a column of the `games` table; `None` for games read any other way.
+    #[serde(skip)]
+    pub pile: Option<PileSummary>,
 }
 
 impl Default for Game {
@@ -73,14 +168,27 @@ impl Game {
     pub fn new() -> Self {
         Game {
             id: Uuid::new_v4().to_string(),
+            host_player_id: String::new(),
             players: vec![],
             which_player_turn: String::new(),
             state: GameState::Starting, // Placeholder for actual game state
-            started_at: chrono::Utc::now().to_string(),
+            started_at: server_time(),
             card_to_play: CardType::King,
             chat: Chat::new(),
             claims: vec![],
             round_number: 1,
+            settings: GameSettings::default(),
+            winner_player_id: None,
+            finished_at: None,
+            total_rounds: None,
+            join_code: None,
+            daily_date: None,
+            daily_seed: None,
+            turn_started_at: None,
+            paused_at: None,
+            reclaimed_at: None,
+            revision: 0,
+            pile: None,
         }
     }
 
@@ -97,6 +205,7 @@ impl Game {
     pub fn from_ref(game: &Game) -> Self {
         Game {
             id: game.id.clone(),
+            host_player_id: game.host_player_id.clone(),
             players: game.players.clone(),
             which_player_turn: game.which_player_turn.clone(),
             state: game.state.clone(),
@@ -105,15 +214,27 @@ impl Game {
             chat: game.chat.clone(),
             claims: game.claims.clone(),
             round_number: game.round_number.clone(),
+            settings: game.settings.clone(),
+            winner_player_id: game.winner_player_id.clone(),
+            finished_at: game.finished_at,
+            total_rounds: game.total_rounds,
+            join_code: game.join_code.clone(),
+            daily_date: game.daily_date,
+            daily_seed: game.daily_seed.clone(),
+            turn_started_at: game.turn_started_at,
+            paused_at: game.paused_at,
+            reclaimed_at: game.reclaimed_at,
+            revision: game.revision,
+            pile: game.pile.clone(),
         }
     }
 
     /// Prepares a Game for it's next round.
     ///
     /// -> Select the first player in the list to start again in the new round
-    /// -> Randomly select one card that needs to be played in tht next round
     /// -> Empties the claims list
     /// -> Increments the round counter
+    /// -> Select the card that needs to be played in the next round, see `card_for_round`
     ///
     pub fn prep_for_new_round(&mut self) -> Result<(), ProcessError<Game>> {
         // set select player to the first in the list
@@ -125,16 +246,423 @@ impl Game {
 
         self.which_player_turn = self.players[0].id.clone();
 
-        // get new card to play -> with csprng
-        self.card_to_play = select_new_card_to_be_played();
-
-        // empty claims list
+        // empty claims list and the stack they formed
         self.claims = vec![];
+        self.pile = None;
         // increment the round number
         self.round_number += 1;
 
+        // get new card to play -> with csprng or the seed of the daily challenge
+        self.card_to_play = self.card_for_round();
+
+        Ok(())
+    }
+
+    /// Selects the card to play of the current round.
+    ///
+    /// A daily challenge derives it from its seed, every other game selects it randomly.
+    pub fn card_for_round(&self) -> CardType {
+        match &self.daily_seed {
+            Some(seed) => daily_card_to_play(seed, self.round_number),
+            None => select_new_card_to_be_played(),
+        }
+    }
+
+    /// Creates the generator the deck of the game is shuffled with.
+    ///
+    /// A daily challenge shuffles with its seed, every other game with a random one.
+    pub fn deck_rng(&self) -> ChaCha8Rng {
+        match &self.daily_seed {
+            Some(seed) => daily_deck_rng(seed),
+            None => new_rng(),
+        }
+    }
+
+    /// Returns the id of the player who moves after the passed player.
+    ///
+    /// The players move in the order of their seats; after the last seat the first one moves
+    /// again.
+    pub fn next_player_id(&self, player_id: &str) -> Option<String> {
+        let seated = self.seating_order();
+        let index = seated.iter().position(|player| player.id == player_id)?;
+
+        Some(seated[(index + 1) % seated.len()].id.clone())
+    }
+
+    /// Returns the players in the order of their seats.
+    ///
+    /// Players sharing a seat index, e.g. of games created before the seats were stored, sit in
+    /// the order they joined.
+    pub fn seating_order(&self) -> Vec<&Player> {
+        let mut seated: Vec<&Player> = self.players.iter().collect();
+        seated.sort_by_key(|player| (player.seat_index, player.joined_at));
+        seated
+    }
+
+    /// Returns the id of the player on the first seat, who makes the first move of a game.
+    ///
+    /// Falls back to the host if the game has no players.
+    pub fn first_seat_player_id(&self) -> String {
+        self.seating_order()
+            .first()
+            .map(|player| player.id.clone())
+            .unwrap_or_else(|| self.host_player_id.clone())
+    }
+
+    /// Seats the players in the passed order.
+    ///
+    /// The first id gets seat 0, the second one seat 1 and so on. The player list is sorted by the
+    /// new seats.
+    ///
+    /// # Arguments
+    ///
+    /// - `player_ids` -> Ids of all players of the game, each exactly once
+    ///
+    /// # Returns
+    ///
+    /// `false` without changing any seat if the ids aren't exactly the ids of the players.
+    pub fn seat_players(&mut self, player_ids: &[String]) -> bool {
+        let is_permutation = player_ids.len() == self.players.len()
+            && self
+                .players
+                .iter()
+                .all(|player| player_ids.contains(&player.id));
+        if !is_permutation {
+            return false;
+        }
+
+        for player in self.players.iter_mut() {
+            player.seat_index = player_ids
+                .iter()
+                .position(|id| *id == player.id)
+                .unwrap_or_default();
+        }
+        self.players.sort_by_key(|player| player.seat_index);
+        true
+    }
+
+    /// Seats the players in a random order.
+    ///
+    /// # Arguments
+    ///
+    /// - `rng` -> Generator the order is drawn with
+    pub fn shuffle_seats(&mut self, rng: &mut impl RngCore) {
+        let mut player_ids: Vec<String> = self
+            .players
+            .iter()
+            .map(|player| player.id.clone())
+            .collect();
+        for i in (1..player_ids.len()).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            player_ids.swap(i, j);
+        }
+
+        self.seat_players(&player_ids);
+    }
+
+    /// Returns `true` if the player with the passed id is the host of the game.
+    pub fn is_host(&self, player_id: &str) -> bool {
+        !self.host_player_id.is_empty() && self.host_player_id == player_id
+    }
+
+    /// Moves the game into a new state.
+    ///
+    /// Only transitions allowed by `GameState::can_transition_to` are applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` if the lifecycle of a game doesn't allow the transition.
+    pub fn transition_to(&mut self, next: GameState) -> Result<(), ProcessError<Game>> {
+        if !self.state.can_transition_to(&next) {
+            return Err(ProcessError::new(
+                format!(
+                    "The game can't change from the state '{}' to '{}'!",
+                    self.state, next
+                ),
+                "Game::transition_to".to_string(),
+                Some(Game::from_ref(self)),
+            ));
+        }
+
+        self.state = next;
+
+        Ok(())
+    }
+
+    /// Pauses a running game; no moves can be made until it is resumed.
+    ///
+    /// The countdown of the current turn stops at the point in time the game was paused.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` in the `locale` if the game isn't in progress.
+    pub fn pause(&mut self, locale: &Locale) -> Result<(), ProcessError<Game>> {
+        if self.state != GameState::InProgress {
+            return Err(ProcessError::new(
+                Message::PauseNotInProgress.text(locale),
+                "Game::pause".to_string(),
+                Some(Game::from_ref(self)),
+            ));
+        }
+
+        self.transition_to(GameState::Paused)?;
+        self.paused_at = Some(server_time());
+
+        Ok(())
+    }
+
+    /// Resumes a paused game.
+    ///
+    /// The turn is moved by the duration of the pause, so the player to move keeps the time that
+    /// was left.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` in the `locale` if the game isn't paused.
+    pub fn resume(&mut self, locale: &Locale) -> Result<(), ProcessError<Game>> {
+        if self.state != GameState::Paused {
+            return Err(ProcessError::new(
+                Message::ResumeNotPaused.text(locale),
+                "Game::resume".to_string(),
+                Some(Game::from_ref(self)),
+            ));
+        }
+
+        self.transition_to(GameState::InProgress)?;
+        if let (Some(turn_started_at), Some(paused_at)) = (self.turn_started_at, self.paused_at) {
+            self.turn_started_at = Some(turn_started_at + (server_time() - paused_at));
+        }
+        self.paused_at = None;
+
+        Ok(())
+    }
+
+    /// Creates the DTO to persist a paused or resumed game.
+    pub fn pause_update(&self) -> UpdateGameDTO {
+        UpdateGameDTO {
+            id: self.id.clone(),
+            state: Some(self.state.clone()),
+            turn_started_at: self.turn_started_at,
+            expected_revision: Some(self.revision),
+            ..Default::default()
+        }
+    }
+
+    /// Puts a running game back to its first round, keeping the players and the chat.
+    ///
+    /// -> Moves the game into the `InProgress` state, a paused game continues right away
+    /// -> Empties the claims list and the hands of all players, which have to be dealt again
+    /// -> Sets the round counter back to 1 and selects the card to play of the first round
+    /// -> The player on the first seat makes the first move again
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` in the `locale` if the game isn't running or paused.
+    pub fn reset(&mut self, locale: &Locale) -> Result<(), ProcessError<Game>> {
+        if !matches!(
+            self.state,
+            GameState::InProgress | GameState::RoundEnding | GameState::Paused
+        ) {
+            return Err(ProcessError::new(
+                Message::ResetNotRunning.text(locale),
+                "Game::reset".to_string(),
+                Some(Game::from_ref(self)),
+            ));
+        }
+
+        self.transition_to(GameState::InProgress)?;
+        self.paused_at = None;
+        self.claims = vec![];
+        self.pile = None;
+        for player in &mut self.players {
+            player.assigned_cards = vec![];
+        }
+        self.round_number = 1;
+        self.card_to_play = self.card_for_round();
+        self.which_player_turn = self.first_seat_player_id();
+
+        Ok(())
+    }
+
+    /// Creates the DTO to persist a game that was put back to its first round.
+    pub fn reset_update(&self) -> UpdateGameDTO {
+        UpdateGameDTO {
+            id: self.id.clone(),
+            state: Some(self.state.clone()),
+            which_player_turn: Some(self.which_player_turn.clone()),
+            round_number: Some(self.round_number),
+            card_to_play: Some(self.card_to_play.clone()),
+            expected_revision: Some(self.revision),
+            ..Default::default()
+        }
+    }
+
+    /// Restores a game the host reclaimed after it was abandoned.
+    ///
+    /// -> Moves the game back into the state it had before it was abandoned
+    /// -> Clears the end timestamp and remembers when the game was reclaimed
+    /// -> The player to move gets a fresh turn, since the countdown ran out in the meantime
+    ///
+    /// # Arguments
+    ///
+    /// - `abandonment` -> Latest abandonment of the game, read from the event log
+    /// - `now` -> Point in time of the reclaim
+    /// - `grace_minutes` -> Minutes after the abandonment the game can be reclaimed
+    /// - `locale` -> Language of the error
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` if the game isn't abandoned, the grace period is over or the host
+    /// doesn't hold a seat in the game anymore.
+    pub fn reclaim(
+        &mut self,
+        abandonment: &Abandonment,
+        now: DateTime<Utc>,
+        grace_minutes: i64,
+        locale: &Locale,
+    ) -> Result<(), ProcessError<Game>> {
+        let host_seated = self
+            .players
+            .iter()
+            .any(|player| player.id == self.host_player_id);
+        if self.state != GameState::Abandoned
+            || !abandonment.is_reclaimable(now, grace_minutes)
+            || !host_seated
+        {
+            return Err(ProcessError::new(
+                Message::ReclaimUnavailable.text(locale),
+                "Game::reclaim".to_string(),
+                Some(Game::from_ref(self)),
+            ));
+        }
+
+        self.transition_to(abandonment.previous_state.clone())?;
+        self.finished_at = None;
+        self.reclaimed_at = Some(now);
+        self.paused_at = match self.state {
+            GameState::Paused => Some(now),
+            _ => None,
+        };
+
+        Ok(())
+    }
+
+    /// Creates the DTO to persist a reclaimed game.
+    pub fn reclaim_update(&self) -> UpdateGameDTO {
+        let turn_started = self.turn_started_at.is_some();
+        UpdateGameDTO {
+            id: self.id.clone(),
+            state: Some(self.state.clone()),
+            which_player_turn: turn_started.then(|| self.which_player_turn.clone()),
+            reclaimed_at: self.reclaimed_at,
+            expected_revision: Some(self.revision),
+            ..Default::default()
+        }
+    }
+
+    /// Ends the game and records its outcome.
+    ///
+    /// -> Moves the game into the `Ended` state
+    /// -> Sets the player with the fewest cards in hand as the winner, see `leading_player_id`
+    /// -> Stores the end timestamp and the number of played rounds
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessError` if the game can't be ended in its current state.
+    pub fn finish(&mut self) -> Result<(), ProcessError<Game>> {
+        self.transition_to(GameState::Ended)?;
+
+        self.winner_player_id = self.leading_player_id();
+        self.finished_at = Some(server_time());
+        self.total_rounds = Some(self.round_number);
+
         Ok(())
     }
+
+    /// Returns the id of the player who leads by the rules of the game.
+    ///
+    /// The goal is to get rid of all cards, so the player with the fewest cards in hand leads and
+    /// a player who emptied their hand always does. A draw has no leader.
+    pub fn leading_player_id(&self) -> Option<String> {
+        let fewest_cards = self
+            .players
+            .iter()
+            .map(|player| player.assigned_cards.len())
+            .min();
+        let mut leaders = self
+            .players
+            .iter()
+            .filter(|player| Some(player.assigned_cards.len()) == fewest_cards);
+
+        match (leaders.next(), leaders.next()) {
+            (Some(leader), None) => Some(leader.id.clone()),
+            _ => None,
+        }
+    }
+
+    /// Creates the DTO to persist the outcome of a finished game.
+    pub fn outcome_update(&self) -> UpdateGameDTO {
+        UpdateGameDTO {
+            id: self.id.clone(),
+            state: Some(self.state.clone()),
+            winner_player_id: self.winner_player_id.clone(),
+            finished_at: self.finished_at,
+            total_rounds: self.total_rounds,
+            expected_revision: Some(self.revision),
+            ..Default::default()
+        }
+    }
+
+    /// Applies the columns of an update to the game.
+    ///
+    /// Only the fields stored in the `games` table are applied; players, claims and the chat are
+    /// stored separately.
+    ///
+    /// # Arguments
+    ///
+    /// - `update` -> Changed data of the game
+    pub fn apply_update(&mut self, update: &UpdateGameDTO) {
+        if let Some(which_player_turn) = &update.which_player_turn {
+            self.which_player_turn = which_player_turn.clone();
+            self.turn_started_at = Some(server_time());
+        } else if let Some(turn_started_at) = update.turn_started_at {
+            self.turn_started_at = Some(turn_started_at);
+        }
+        if let Some(state) = &update.state {
+            self.state = state.clone();
+            self.paused_at = match state {
+                GameState::Paused => Some(server_time()),
+                _ => None,
+            };
+            if !is_archived_state(state) {
+                self.finished_at = None;
+            }
+        }
+        if let Some(round_number) = update.round_number {
+            self.round_number = round_number;
+        }
+        if let Some(card_to_play) = &update.card_to_play {
+            self.card_to_play = card_to_play.clone();
+        }
+        if let Some(settings) = &update.settings {
+            self.settings = settings.clone();
+        }
+        if let Some(winner_player_id) = &update.winner_player_id {
+            self.winner_player_id = Some(winner_player_id.clone());
+        }
+        if let Some(finished_at) = update.finished_at {
+            self.finished_at = Some(finished_at);
+        }
+        if let Some(total_rounds) = update.total_rounds {
+            self.total_rounds = Some(total_rounds);
+        }
+        if let Some(host_player_id) = &update.host_player_id {
+            self.host_player_id = host_player_id.clone();
+        }
+        if let Some(reclaimed_at) = update.reclaimed_at {
+            self.reclaimed_at = Some(reclaimed_at);
+        }
+    }
 }
 
 // ----- Implementation 'ErrorObject' for 'Game' -----
@@ -157,8 +685,9 @@ impl Debug for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "Game {{ id: {}, players: {:?}, which_player_turn: {}, state: {:?}, started_at: {}, round_number: {}, card_to_play: {:?}, claims: {:?} }}",
+            "Game {{ id: {}, host_player_id: {}, players: {:?}, which_player_turn: {}, state: {:?}, started_at: {}, round_number: {}, card_to_play: {:?}, claims: {:?} }}",
             self.id,
+            self.host_player_id,
             self.players,
             self.which_player_turn,
             self.state,
@@ -172,14 +701,113 @@ impl Debug for Game {
 
 impl<'a> ErrorObject<'a> for Game {}
 
+// ----- Implementation of 'Redact' for 'Game' -----
+
+impl Redact for Game {
+    /// Redacts the hands of all players, the cards of all claims and the seed of a daily challenge.
+    fn redact(&mut self) {
+        self.daily_seed = None;
+        self.players.iter_mut().for_each(|player| player.redact());
+        self.claims.iter_mut().for_each(|claim| claim.redact());
+    }
+}
+
 // ----- Implementation of the 'IntoResponse' trai for the 'Game' struct -----
 
 impl IntoResponse for Game {
     /// Convert a 'Game' instance into a response object.
     ///
-    /// Comes with status code 200.
-    fn into_response(self) -> axum::response::Response {
-        (StatusCode::OK, self).into_response()
+    /// Comes with status code 200. The game is redacted because it isn't bound to a single
+    /// player; a `GameView` is used to include the hand of the requesting player.
+    fn into_response(mut self) -> Response {
+        self.redact();
+
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// DTO type for the purpose of creating a new game.
+///
+/// The client only provides the name of the host and optional settings. Every identifier and
+/// timestamp is generated by the server.
+///
+/// # Props
+///
+/// - `host_name` -> Name of the player who creates the game
+/// - `settings` -> Optional settings of the game; the defaults are used if none are provided
+/// - `turnstile_token` -> Token of the Turnstile widget proving the client isn't a bot
+/// - `daily` -> Whether the game plays the daily challenge of today
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGameDTO {
+    /// Name of the player who creates the game
+    #[serde(alias = "host_name")]
+    pub host_name: String,
+    /// Optional settings of the new game
+    pub settings: Option<GameSettings>,
+    /// Token of the Turnstile widget proving the client isn't a bot
+    #[serde(default, alias = "turnstile_token", alias = "cf-turnstile-response")]
+    pub turnstile_token: Option<String>,
+    /// Whether the game plays the daily challenge of today
+    #[serde(default)]
+    pub daily: bool,
+}
+
+impl CreateGameDTO {
+    /// Builds a new `Game` with its host as the first player.
+    ///
+    /// Ids and timestamps are generated on the server side.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the new `Game` and the host `Player`, who owns the game and is also the first
+    /// player to make a move.
+    pub fn into_game(self) -> (Game, Player) {
+        let mut game = Game::new();
+        game.settings = self.settings.unwrap_or_default();
+        game.state = GameState::WaitingForPlayers;
+        game.join_code = Some(generate_join_code());
+
+        let host = Player::new(self.host_name, game.id.clone());
+        game.host_player_id = host.id.clone();
+        game.which_player_turn = host.id.clone();
+        game.players = vec![host.clone()];
+
+        (game, host)
+    }
+}
+
+impl Display for CreateGameDTO {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Host Name: {}, Settings: {:?}",
+            self.host_name, self.settings
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for CreateGameDTO {}
+
+impl Redact for CreateGameDTO {
+    /// The Turnstile token is never echoed back.
+    fn redact(&mut self) {
+        self.turnstile_token = None;
+    }
+}
+
+impl Validate for CreateGameDTO {
+    /// Checks the host name and the provided settings.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = validate_player_name("host_name", &self.host_name, locale);
+
+        if let Some(settings) = &self.settings {
+            if let Err(settings_errors) = settings.validate(locale) {
+                errors.merge(settings_errors);
+            }
+        }
+
+        errors.into_result()
     }
 }
 
@@ -197,26 +825,70 @@ impl IntoResponse for Game {
 /// - `chat` -> Potentially new chat instance
 /// - `card_to_play` -> Changes after every made round
 /// - `claims` -> List of claims in the current round
-#[derive(Deserialize, Debug, Clone)]
+/// - `settings` -> Changed settings of the game
+/// - `winner_player_id` -> Id of the winner once the game ended
+/// - `finished_at` -> Timestamp when the game ended
+/// - `total_rounds` -> Number of played rounds once the game ended
+/// - `host_player_id` -> New host if the previous one left the game
+/// - `turn_started_at` -> New start of the current turn after a pause
+/// - `expected_revision` -> Revision of the game the update is based on
+#[derive(Deserialize, Serialize, Debug, Clone, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateGameDTO {
     /// Identifier of the game is always needed.
     pub id: String,
     /// Optional list of players, who joined the game
     pub players: Option<Vec<Player>>,
     /// Optional identifier of the player, who needs to make his / her move next
+    ///
+    /// Starts a new turn, so the countdown of the turn restarts even if the player stays the same.
+    #[serde(alias = "which_player_turn")]
     pub which_player_turn: Option<String>,
     /// Optional new game state of the game
     pub state: Option<GameState>,
     /// Optional new round number
     ///
     /// Starts by 1 and increments by 1
+    #[serde(alias = "round_number")]
     pub round_number: Option<usize>,
     /// Optional modified chat instance
     pub chat: Option<Chat>,
     /// Optional mutated card to play in the current round
+    #[serde(alias = "card_to_play")]
     pub card_to_play: Option<CardType>,
     /// Optional list of new claims made by users
     pub claims: Option<Vec<Claim>>,
+    /// Optional new settings of the game
+    ///
+    /// Can only be changed by the host before the game started.
+    #[serde(default)]
+    pub settings: Option<GameSettings>,
+    /// Optional id of the player who won the game
+    #[serde(alias = "winner_player_id", default)]
+    pub winner_player_id: Option<String>,
+    /// Optional timestamp when the game ended
+    #[serde(alias = "finished_at", default)]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Optional number of rounds played until the game ended
+    #[serde(alias = "total_rounds", default)]
+    pub total_rounds: Option<usize>,
+    /// Optional new host if the previous one left the game
+    #[serde(alias = "host_player_id", default)]
+    pub host_player_id: Option<String>,
+    /// Optional new start of the current turn
+    ///
+    /// Set when a paused game is resumed, so the countdown continues where it stopped. Ignored if
+    /// `which_player_turn` is passed as well, which always starts a new turn.
+    #[serde(alias = "turn_started_at", default)]
+    pub turn_started_at: Option<DateTime<Utc>>,
+    /// Optional point in time the host reclaimed the abandoned game
+    #[serde(alias = "reclaimed_at", default)]
+    pub reclaimed_at: Option<DateTime<Utc>>,
+    /// Revision of the game the update is based on
+    ///
+    /// The `GameRoom` Durable Object rejects the update if the game changed in the meantime.
+    #[serde(alias = "expected_revision", default)]
+    pub expected_revision: Option<u64>,
 }
 
 impl UpdateGameDTO {
@@ -246,6 +918,7 @@ impl UpdateGameDTO {
             chat,
             card_to_play,
             claims,
+            ..Default::default()
         }
     }
 }
@@ -256,7 +929,7 @@ impl Display for UpdateGameDTO {
             f,
             "Id: {}, Players: {:?}, Id of Player who needs to make a claim: {:?},
                 Game State: {:?}, Round: {:?}, 
-                Chat: {:?}, Card to Play: {:?},  Claims: {:?}",
+                Chat: {:?}, Card to Play: {:?},  Claims: {:?}, Settings: {:?}",
             self.id,
             self.players,
             self.which_player_turn,
@@ -264,9 +937,87 @@ impl Display for UpdateGameDTO {
             self.round_number,
             self.chat,
             self.card_to_play,
-            self.claims
+            self.claims,
+            self.settings
         )
     }
 }
 
 impl<'a> ErrorObject<'a> for UpdateGameDTO {}
+
+impl Validate for UpdateGameDTO {
+    /// Checks the id and all provided properties of the DTO.
+    ///
+    /// Nested players and claims are validated as well.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.id.is_empty() {
+            errors.add("id", "required", "The game id must not be empty!");
+        }
+
+        if let Some(players) = &self.players {
+            if players.is_empty() {
+                errors.add(
+                    "players",
+                    "required",
+                    "The list of players must not be empty!",
+                );
+            } else if players.len() > MAX_PLAYERS {
+                errors.add(
+                    "players",
+                    "too_many",
+                    &format!("A game can't have more than {MAX_PLAYERS} players!"),
+                );
+            }
+        }
+
+        if let Some(player_id) = &self.which_player_turn {
+            if player_id.is_empty() {
+                errors.add(
+                    "which_player_turn",
+                    "required",
+                    "The id of the player whose turn it is must not be empty!",
+                );
+            }
+        }
+
+        if let Some(round_number) = self.round_number {
+            if round_number == 0 {
+                errors.add(
+                    "round_number",
+                    "out_of_range",
+                    "The round number starts at 1!",
+                );
+            }
+        }
+
+        if let Some(claims) = &self.claims {
+            for claim in claims {
+                if let Err(claim_errors) = claim.validate(locale) {
+                    errors.merge(claim_errors);
+                }
+            }
+        }
+
+        if let Some(settings) = &self.settings {
+            if let Err(settings_errors) = settings.validate(locale) {
+                errors.merge(settings_errors);
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Redact for UpdateGameDTO {
+    /// Redacts the hands of all provided players and the cards of all provided claims.
+    fn redact(&mut self) {
+        if let Some(players) = &mut self.players {
+            players.iter_mut().for_each(|player| player.redact());
+        }
+        if let Some(claims) = &mut self.claims {
+            claims.iter_mut().for_each(|claim| claim.redact());
+        }
+    }
+}
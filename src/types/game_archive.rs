@@ -0,0 +1,103 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::types::{chat::ChatMessage, game_event::StoredGameEvent, game_view::GameView};
+
+// constants
+/// Content type of an exported game.
+pub const ARCHIVE_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// One line of an exported game.
+///
+/// An export is newline-delimited JSON: the final state of the game first, followed by all public
+/// events and all chat messages in their order.
+///
+/// ```json
+/// {"record":"game","data":{"id":"...","state":"Ended", ...}}
+/// {"record":"event","data":{"sequence":1,"eventType":"turnChanged", ...}}
+/// {"record":"chatMessage","data":{"playerId":"...","content":"gg", ...}}
+/// ```
+#[derive(Serialize)]
+#[serde(tag = "record", content = "data", rename_all = "camelCase")]
+pub enum ArchiveRecord<'a> {
+    /// Final state of the game without any hands
+    Game(&'a GameView),
+    /// Event published while the game was running
+    #[serde(rename_all = "camelCase")]
+    Event {
+        /// Position of the event in the log
+        sequence: usize,
+        /// Name of the event
+        event_type: &'a str,
+        /// Point in time the event was published
+        created_at: DateTime<Utc>,
+        /// The event itself
+        payload: Value,
+    },
+    /// Message sent in the chat of the game
+    ChatMessage(&'a ChatMessage),
+}
+
+impl<'a> From<&'a StoredGameEvent> for ArchiveRecord<'a> {
+    fn from(event: &'a StoredGameEvent) -> Self {
+        ArchiveRecord::Event {
+            sequence: event.sequence,
+            event_type: &event.event_type,
+            created_at: event.created_at,
+            payload: serde_json::from_str(&event.payload).unwrap_or(Value::Null),
+        }
+    }
+}
+
+/// Serializes a finished game into newline-delimited JSON.
+///
+/// # Arguments
+///
+/// - `game` -> Final state of the game
+/// - `events` -> Public events of the game, oldest first
+/// - `messages` -> Chat messages of the game, oldest first
+pub fn build_archive(
+    game: &GameView,
+    events: &[StoredGameEvent],
+    messages: &[ChatMessage],
+) -> Result<String, serde_json::Error> {
+    let records = std::iter::once(ArchiveRecord::Game(game))
+        .chain(events.iter().map(ArchiveRecord::from))
+        .chain(messages.iter().map(ArchiveRecord::ChatMessage));
+
+    let mut archive = String::new();
+    for record in records {
+        archive.push_str(&serde_json::to_string(&record)?);
+        archive.push('\n');
+    }
+
+    Ok(archive)
+}
+
+/// Response body of a download link of an exported game.
+///
+/// # Props
+///
+/// - `url` -> Path of the download including the signed token
+/// - `expires_at` -> Point in time after which the link is invalid
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportLink {
+    /// Path of the download including the signed token
+    pub url: String,
+    /// Point in time after which the link is invalid
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IntoResponse for ExportLink {
+    /// Converts the `ExportLink` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
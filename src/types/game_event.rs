@@ -0,0 +1,480 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    enums::{achievement::Achievement, deck_theme::DeckTheme, emote::Emote, game_state::GameState},
+    errors::application_error::{ErrorObject, Redact},
+    types::{
+        abandonment::Abandonment, chat::ChatMessage, game::Game, game_recap::GameRecap,
+        hand_summary::HandSummary, round_summary::RoundSummary,
+    },
+    utils::{d1_conversions::datetime_from_text, time::turn_deadline},
+};
+
+// constants
+
+/// Name of the event published at the end of each round, used to read the summaries back.
+pub const ROUND_SUMMARY_EVENT: &str = "roundSummary";
+
+/// Name of the event published when a game is abandoned, used to reclaim the game.
+pub const GAME_ABANDONED_EVENT: &str = "gameAbandoned";
+
+/// Name of the event published when a player reacts with an emote, used to rate-limit reactions.
+pub const REACTION_EVENT: &str = "reaction";
+
+/// Seconds a reaction is delivered to the event streams before it expires.
+pub const REACTION_TTL_SECONDS: i64 = 30;
+
+/// Name of the event sent while a player types a chat message.
+pub const PLAYER_TYPING_EVENT: &str = "playerTyping";
+
+/// Seconds a typing indicator is delivered to the event streams after the last notification.
+pub const PLAYER_TYPING_TTL_SECONDS: i64 = 5;
+
+/// Events that are pushed to all players of a game.
+///
+/// Events never contain hidden information, so the same event can be sent to every player.
+///
+/// # Variants
+///
+/// - `TurnChanged` -> Another player needs to make a move
+/// - `PlayerRemoved` -> A player was kicked, left or timed out
+/// - `GameEnded` -> The game ended or was abandoned
+/// - `AchievementUnlocked` -> A player unlocked an achievement
+/// - `PlayerInvited` -> A recent opponent invited the player into another game; only sent to the
+///   invited player
+/// - `GamePaused` -> The host paused the game
+/// - `GameResumed` -> The host resumed the game
+/// - `ClaimRetracted` -> A player took back their last claim and is to move again
+/// - `RoundSummary` -> A round ended with a challenge
+/// - `Reaction` -> A player reacted with an emote; expires after `REACTION_TTL_SECONDS`
+/// - `PlayerTyping` -> A player types a chat message; never stored, the event streams send it
+///   until `PLAYER_TYPING_TTL_SECONDS` passed
+/// - `Whisper` -> A player whispered to another player; only sent to the two of them
+/// - `ChatMessageEdited` -> The author changed the content of a chat message
+/// - `ChatMessageDeleted` -> The author deleted a chat message
+/// - `WaitlistPromoted` -> A client from the waitlist took a free seat
+/// - `Mentioned` -> A chat message mentions a player with `@name`; only sent to that player
+/// - `PlayerDisconnected` -> The heartbeat of a player stopped during their turn, which was passed
+/// - `PlayerReconnected` -> The heartbeat of a disconnected player is back
+/// - `PlayerMuted` -> The host or an operator muted a player in the chat
+/// - `GameReset` -> The host put the game back to its first round and dealt new hands
+/// - `GameAbandoned` -> The game was abandoned; published next to `GameEnded` with the state to
+///   restore
+/// - `GameReclaimed` -> The host came back and restored an abandoned game
+/// - `DeckThemeChanged` -> The host picked another skin for the cards of the game
+/// - `SeatsChanged` -> The host rearranged the seats or they were shuffled at the start
+/// - `GameRecap` -> The game ended; highlights derived from the event log for the recap screen
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GameEvent {
+    /// Another player needs to make a move.
+    #[serde(rename_all = "camelCase")]
+    TurnChanged {
+        /// Id of the game
+        game_id: String,
+        /// Id of the player who needs to make a move
+        which_player_turn: String,
+        /// Current round of the game
+        round_number: usize,
+        /// Number of cards every player holds
+        hands: Vec<HandSummary>,
+    },
+    /// A player was kicked, left or timed out.
+    #[serde(rename_all = "camelCase")]
+    PlayerRemoved {
+        /// Id of the game
+        game_id: String,
+        /// Id of the removed player
+        player_id: String,
+        /// Why the player was removed, e.g. `kicked`, `left` or `timedOut`
+        reason: String,
+    },
+    /// The game ended or was abandoned.
+    #[serde(rename_all = "camelCase")]
+    GameEnded {
+        /// Id of the game
+        game_id: String,
+        /// Final state of the game
+        state: GameState,
+        /// Id of the winner, if there is one
+        winner_player_id: Option<String>,
+    },
+    /// A player unlocked an achievement.
+    #[serde(rename_all = "camelCase")]
+    AchievementUnlocked {
+        /// Id of the game
+        game_id: String,
+        /// Id of the player who unlocked the achievement
+        player_id: String,
+        /// The unlocked achievement
+        achievement: Achievement,
+        /// Name of the achievement
+        title: String,
+    },
+    /// A recent opponent invited the player into another game.
+    #[serde(rename_all = "camelCase")]
+    PlayerInvited {
+        /// Id of the game the invited player currently plays
+        game_id: String,
+        /// Id of the invited player
+        player_id: String,
+        /// Id of the game the player is invited to
+        invited_to_game_id: String,
+        /// Join code of the game the player is invited to, if it has one
+        join_code: Option<String>,
+        /// Name of the player who sent the invitation
+        invited_by: String,
+    },
+    /// The host paused the game.
+    #[serde(rename_all = "camelCase")]
+    GamePaused {
+        /// Id of the game
+        game_id: String,
+        /// Point in time the game was paused
+        paused_at: Option<DateTime<Utc>>,
+    },
+    /// The host resumed the game.
+    #[serde(rename_all = "camelCase")]
+    GameResumed {
+        /// Id of the game
+        game_id: String,
+        /// Id of the player who needs to make a move
+        which_player_turn: String,
+        /// Point in time the turn ends, moved by the duration of the pause
+        turn_deadline: Option<DateTime<Utc>>,
+    },
+    /// A player took back their last claim and is to move again.
+    #[serde(rename_all = "camelCase")]
+    ClaimRetracted {
+        /// Id of the game
+        game_id: String,
+        /// Id of the claim that was taken back
+        claim_id: String,
+        /// Id of the player who needs to make a move, the author of the claim
+        which_player_turn: String,
+        /// Number of cards every player holds
+        hands: Vec<HandSummary>,
+    },
+    /// A round ended with a challenge.
+    RoundSummary(RoundSummary),
+    /// A player reacted with an emote.
+    #[serde(rename_all = "camelCase")]
+    Reaction {
+        /// Id of the game
+        game_id: String,
+        /// Id of the player who reacted
+        player_id: String,
+        /// The emote
+        emote: Emote,
+    },
+    /// A player types a chat message.
+    #[serde(rename_all = "camelCase")]
+    PlayerTyping {
+        /// Id of the game
+        game_id: String,
+        /// Id of the typing player
+        player_id: String,
+    },
+    /// A player whispered to another player.
+    #[serde(rename_all = "camelCase")]
+    Whisper {
+        /// Id of the game
+        game_id: String,
+        /// The whispered message
+        message: ChatMessage,
+    },
+    /// The author changed the content of a chat message.
+    #[serde(rename_all = "camelCase")]
+    ChatMessageEdited {
+        /// Id of the game
+        game_id: String,
+        /// The message with its new content
+        message: ChatMessage,
+    },
+    /// The author deleted a chat message.
+    #[serde(rename_all = "camelCase")]
+    ChatMessageDeleted {
+        /// Id of the game
+        game_id: String,
+        /// Id of the deleted message
+        message_id: String,
+        /// Point in time the message was deleted
+        deleted_at: Option<DateTime<Utc>>,
+    },
+    /// A client from the waitlist took a free seat.
+    #[serde(rename_all = "camelCase")]
+    WaitlistPromoted {
+        /// Id of the game
+        game_id: String,
+        /// Id of the new player
+        player_id: String,
+        /// Name of the new player
+        name: String,
+    },
+    /// A chat message mentions the receiving player.
+    #[serde(rename_all = "camelCase")]
+    Mentioned {
+        /// Id of the game
+        game_id: String,
+        /// The message mentioning the player
+        message: ChatMessage,
+    },
+    /// The heartbeat of a player stopped during their turn, so the turn was passed.
+    #[serde(rename_all = "camelCase")]
+    PlayerDisconnected {
+        /// Id of the game
+        game_id: String,
+        /// Id of the disconnected player
+        player_id: String,
+        /// Id of the player who moves instead
+        which_player_turn: String,
+    },
+    /// The heartbeat of a disconnected player is back.
+    #[serde(rename_all = "camelCase")]
+    PlayerReconnected {
+        /// Id of the game
+        game_id: String,
+        /// Id of the reconnected player
+        player_id: String,
+    },
+    /// The host or an operator muted a player in the chat.
+    #[serde(rename_all = "camelCase")]
+    PlayerMuted {
+        /// Id of the game
+        game_id: String,
+        /// Id of the muted player
+        player_id: String,
+        /// Point in time the player can write in the chat again
+        muted_until: DateTime<Utc>,
+    },
+    /// The host put the game back to its first round and dealt new hands.
+    #[serde(rename_all = "camelCase")]
+    GameReset {
+        /// Id of the game
+        game_id: String,
+        /// Id of the player who makes the first move
+        which_player_turn: String,
+    },
+    /// The game was abandoned.
+    GameAbandoned(Abandonment),
+    /// The host came back and restored an abandoned game.
+    #[serde(rename_all = "camelCase")]
+    GameReclaimed {
+        /// Id of the game
+        game_id: String,
+        /// The restored state
+        state: GameState,
+    },
+    /// The host picked another skin for the cards of the game.
+    #[serde(rename_all = "camelCase")]
+    DeckThemeChanged {
+        /// Id of the game
+        game_id: String,
+        /// The skin every client renders from now on
+        deck_theme: DeckTheme,
+    },
+    /// The players were seated in a new order, which is the order of the turns from now on.
+    #[serde(rename_all = "camelCase")]
+    SeatsChanged {
+        /// Id of the game
+        game_id: String,
+        /// Ids of the players, from the first seat to the last one
+        player_ids: Vec<String>,
+    },
+    /// Highlights of the game, published once it ended.
+    GameRecap(GameRecap),
+}
+
+impl GameEvent {
+    /// Creates the `TurnChanged` event for the current state of a game.
+    pub fn turn_changed(game: &Game) -> Self {
+        GameEvent::TurnChanged {
+            game_id: game.id.clone(),
+            which_player_turn: game.which_player_turn.clone(),
+            round_number: game.round_number,
+            hands: HandSummary::for_game(game),
+        }
+    }
+
+    /// Creates the `GameEnded` event for a game that ended or was abandoned.
+    pub fn game_ended(game: &Game) -> Self {
+        GameEvent::GameEnded {
+            game_id: game.id.clone(),
+            state: game.state.clone(),
+            winner_player_id: game.winner_player_id.clone(),
+        }
+    }
+
+    /// Creates the `SeatsChanged` event with the current seats of the players of a game.
+    pub fn seats_changed(game: &Game) -> Self {
+        GameEvent::SeatsChanged {
+            game_id: game.id.clone(),
+            player_ids: game
+                .seating_order()
+                .into_iter()
+                .map(|player| player.id.clone())
+                .collect(),
+        }
+    }
+
+    /// Creates the `GamePaused` or `GameResumed` event for a game the host paused or resumed.
+    pub fn pause_changed(game: &Game) -> Self {
+        match game.state {
+            GameState::Paused => GameEvent::GamePaused {
+                game_id: game.id.clone(),
+                paused_at: game.paused_at,
+            },
+            _ => GameEvent::GameResumed {
+                game_id: game.id.clone(),
+                which_player_turn: game.which_player_turn.clone(),
+                turn_deadline: turn_deadline(game.turn_started_at, game.settings.turn_seconds),
+            },
+        }
+    }
+
+    /// Creates the `ClaimRetracted` event for a game whose last claim was taken back.
+    pub fn claim_retracted(game: &Game, claim_id: &str) -> Self {
+        GameEvent::ClaimRetracted {
+            game_id: game.id.clone(),
+            claim_id: claim_id.to_string(),
+            which_player_turn: game.which_player_turn.clone(),
+            hands: HandSummary::for_game(game),
+        }
+    }
+
+    /// Returns the id of the game the event belongs to.
+    pub fn game_id(&self) -> &str {
+        match self {
+            GameEvent::TurnChanged { game_id, .. }
+            | GameEvent::PlayerRemoved { game_id, .. }
+            | GameEvent::GameEnded { game_id, .. }
+            | GameEvent::AchievementUnlocked { game_id, .. }
+            | GameEvent::PlayerInvited { game_id, .. }
+            | GameEvent::GamePaused { game_id, .. }
+            | GameEvent::GameResumed { game_id, .. }
+            | GameEvent::ClaimRetracted { game_id, .. }
+            | GameEvent::Reaction { game_id, .. }
+            | GameEvent::PlayerTyping { game_id, .. }
+            | GameEvent::Whisper { game_id, .. }
+            | GameEvent::ChatMessageEdited { game_id, .. }
+            | GameEvent::ChatMessageDeleted { game_id, .. }
+            | GameEvent::WaitlistPromoted { game_id, .. }
+            | GameEvent::Mentioned { game_id, .. }
+            | GameEvent::PlayerDisconnected { game_id, .. }
+            | GameEvent::PlayerReconnected { game_id, .. }
+            | GameEvent::PlayerMuted { game_id, .. }
+            | GameEvent::GameReset { game_id, .. }
+            | GameEvent::GameReclaimed { game_id, .. }
+            | GameEvent::DeckThemeChanged { game_id, .. }
+            | GameEvent::SeatsChanged { game_id, .. } => game_id,
+            GameEvent::RoundSummary(summary) => &summary.game_id,
+            GameEvent::GameAbandoned(abandonment) => &abandonment.game_id,
+            GameEvent::GameRecap(recap) => &recap.game_id,
+        }
+    }
+
+    /// Returns the name of the event, which matches its `type` field.
+    pub fn event_type(&self) -> &str {
+        match self {
+            GameEvent::TurnChanged { .. } => "turnChanged",
+            GameEvent::PlayerRemoved { .. } => "playerRemoved",
+            GameEvent::GameEnded { .. } => "gameEnded",
+            GameEvent::AchievementUnlocked { .. } => "achievementUnlocked",
+            GameEvent::PlayerInvited { .. } => "playerInvited",
+            GameEvent::GamePaused { .. } => "gamePaused",
+            GameEvent::GameResumed { .. } => "gameResumed",
+            GameEvent::ClaimRetracted { .. } => "claimRetracted",
+            GameEvent::RoundSummary(_) => ROUND_SUMMARY_EVENT,
+            GameEvent::Reaction { .. } => REACTION_EVENT,
+            GameEvent::PlayerTyping { .. } => PLAYER_TYPING_EVENT,
+            GameEvent::Whisper { .. } => "whisper",
+            GameEvent::ChatMessageEdited { .. } => "chatMessageEdited",
+            GameEvent::ChatMessageDeleted { .. } => "chatMessageDeleted",
+            GameEvent::WaitlistPromoted { .. } => "waitlistPromoted",
+            GameEvent::Mentioned { .. } => "mentioned",
+            GameEvent::PlayerDisconnected { .. } => "playerDisconnected",
+            GameEvent::PlayerReconnected { .. } => "playerReconnected",
+            GameEvent::PlayerMuted { .. } => "playerMuted",
+            GameEvent::GameReset { .. } => "gameReset",
+            GameEvent::GameAbandoned(_) => GAME_ABANDONED_EVENT,
+            GameEvent::GameReclaimed { .. } => "gameReclaimed",
+            GameEvent::DeckThemeChanged { .. } => "deckThemeChanged",
+            GameEvent::SeatsChanged { .. } => "seatsChanged",
+            GameEvent::GameRecap(_) => "gameRecap",
+        }
+    }
+
+    /// Returns the seconds the event is delivered before it expires.
+    ///
+    /// Short-lived events like reactions are neither replayed to clients who reconnect later nor
+    /// kept in the log; `None` keeps the event for good.
+    pub fn ttl_seconds(&self) -> Option<i64> {
+        match self {
+            GameEvent::Reaction { .. } => Some(REACTION_TTL_SECONDS),
+            _ => None,
+        }
+    }
+}
+
+// ----- Events stored in the database -----
+
+/// A `GameEvent` as it is stored in the `game_events` table.
+///
+/// The sequence number is increasing, so a client only needs to remember the last one it saw.
+///
+/// # Props
+///
+/// - `sequence` -> Position of the event in the log of all events
+/// - `game_id` -> Id of the game the event belongs to
+/// - `recipient_player_id` -> Only this player receives the event; everyone if `None`
+/// - `event_type` -> Name of the event
+/// - `payload` -> The serialized `GameEvent`
+/// - `created_at` -> Point in time the event was published
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredGameEvent {
+    /// Position of the event in the log of all events
+    pub sequence: usize,
+    /// Id of the game the event belongs to
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// Only this player receives the event; everyone if `None`
+    #[serde(alias = "recipient_player_id", default)]
+    pub recipient_player_id: Option<String>,
+    /// Name of the event
+    #[serde(alias = "event_type")]
+    pub event_type: String,
+    /// The serialized `GameEvent`
+    pub payload: String,
+    /// Point in time the event was published
+    #[serde(alias = "created_at", deserialize_with = "datetime_from_text")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredGameEvent {
+    /// Formats the event as a message of a `text/event-stream`.
+    ///
+    /// The sequence is used as the event id, so a reconnecting `EventSource` continues after it.
+    pub fn to_sse_message(&self) -> String {
+        format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            self.sequence, self.event_type, self.payload
+        )
+    }
+}
+
+impl fmt::Display for StoredGameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Event #{} of game {}: {}",
+            self.sequence, self.game_id, self.event_type
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for StoredGameEvent {}
+
+impl Redact for StoredGameEvent {}
@@ -0,0 +1,212 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    enums::game_outcome::GameOutcome,
+    errors::application_error::{ErrorObject, Redact},
+    utils::d1_conversions::{bool_from_number, datetime_from_text, optional_datetime_from_text},
+};
+
+/// A result of a player joined with whether its game had a winner, as read for the history.
+///
+/// # Props
+///
+/// - `game_id` -> Finished game
+/// - `player_name` -> Name of the player in the game
+/// - `score` -> Final score of the player
+/// - `is_winner` -> Whether the player won the game
+/// - `has_winner` -> Whether any player of the game won
+/// - `rounds_played` -> Number of rounds of the game
+/// - `player_count` -> Number of players at the end of the game
+/// - `started_at` -> Point in time the game started
+/// - `finished_at` -> Point in time the game ended
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GameHistoryRow {
+    /// Finished game
+    pub game_id: String,
+    /// Name of the player in the game
+    pub player_name: String,
+    /// Final score of the player
+    pub score: usize,
+    /// Whether the player won the game
+    #[serde(deserialize_with = "bool_from_number")]
+    pub is_winner: bool,
+    /// Whether any player of the game won
+    #[serde(deserialize_with = "bool_from_number")]
+    pub has_winner: bool,
+    /// Number of rounds of the game
+    pub rounds_played: usize,
+    /// Number of players at the end of the game
+    #[serde(default)]
+    pub player_count: usize,
+    /// Point in time the game started
+    #[serde(default, deserialize_with = "optional_datetime_from_text")]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Point in time the game ended
+    #[serde(deserialize_with = "datetime_from_text")]
+    pub finished_at: DateTime<Utc>,
+}
+
+impl fmt::Display for GameHistoryRow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Game ID: {}, Score: {}, Winner: {}",
+            self.game_id, self.score, self.is_winner
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for GameHistoryRow {}
+
+impl Redact for GameHistoryRow {}
+
+/// A past game of a player as shown in the "recent games" panel.
+///
+/// # Props
+///
+/// - `game_id` -> Finished game
+/// - `player_name` -> Name of the player in the game
+/// - `outcome` -> Whether the player won, lost or the game was abandoned
+/// - `score` -> Final score of the player
+/// - `rounds_played` -> Number of rounds of the game
+/// - `player_count` -> Number of players at the end of the game
+/// - `started_at` -> Point in time the game started; `None` for old games
+/// - `finished_at` -> Point in time the game ended
+/// - `duration_seconds` -> Seconds the game lasted; `None` for old games
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameHistoryEntry {
+    /// Finished game
+    pub game_id: String,
+    /// Name of the player in the game
+    pub player_name: String,
+    /// Whether the player won, lost or the game was abandoned
+    pub outcome: GameOutcome,
+    /// Final score of the player
+    pub score: usize,
+    /// Number of rounds of the game
+    pub rounds_played: usize,
+    /// Number of players at the end of the game
+    pub player_count: usize,
+    /// Point in time the game started
+    pub started_at: Option<DateTime<Utc>>,
+    /// Point in time the game ended
+    pub finished_at: DateTime<Utc>,
+    /// Seconds the game lasted
+    pub duration_seconds: Option<i64>,
+}
+
+impl From<GameHistoryRow> for GameHistoryEntry {
+    fn from(row: GameHistoryRow) -> Self {
+        GameHistoryEntry {
+            outcome: GameOutcome::from_result(row.is_winner, row.has_winner),
+            duration_seconds: row
+                .started_at
+                .map(|started_at| (row.finished_at - started_at).num_seconds().max(0)),
+            game_id: row.game_id,
+            player_name: row.player_name,
+            score: row.score,
+            rounds_played: row.rounds_played,
+            player_count: row.player_count,
+            started_at: row.started_at,
+            finished_at: row.finished_at,
+        }
+    }
+}
+
+/// Position in the history of a player, the newest game a page starts after.
+///
+/// Sent to the client as an opaque base64url string, since the end of a game alone isn't unique.
+///
+/// # Props
+///
+/// - `finished_at` -> End of the last game of the previous page
+/// - `game_id` -> Last game of the previous page
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryCursor {
+    /// End of the last game of the previous page
+    pub finished_at: DateTime<Utc>,
+    /// Last game of the previous page
+    pub game_id: String,
+}
+
+impl HistoryCursor {
+    /// Encodes the cursor into the string handed to the client.
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}|{}",
+            self.finished_at.to_rfc3339(),
+            self.game_id
+        ))
+    }
+
+    /// Decodes a cursor a client sent.
+    ///
+    /// # Returns
+    ///
+    /// The cursor or `None` if the string wasn't created by `encode`.
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let (finished_at, game_id) = decoded.split_once('|')?;
+
+        Some(HistoryCursor {
+            finished_at: DateTime::parse_from_rfc3339(finished_at)
+                .ok()?
+                .with_timezone(&Utc),
+            game_id: game_id.to_string(),
+        })
+    }
+}
+
+/// One page of the history of a player, newest games first.
+///
+/// # Props
+///
+/// - `games` -> Games of the page
+/// - `next_cursor` -> Pass as `cursor` to get the next page; `None` on the last page
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GameHistoryPage {
+    /// Games of the page
+    pub games: Vec<GameHistoryEntry>,
+    /// Pass as `cursor` to get the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl GameHistoryPage {
+    /// Creates a page from the games returned for a page size.
+    ///
+    /// # Arguments
+    ///
+    /// - `games` -> Games of the page, newest first
+    /// - `page_size` -> Requested number of games; a full page may be followed by another one
+    pub fn new(games: Vec<GameHistoryEntry>, page_size: usize) -> Self {
+        let next_cursor = match games.len() == page_size {
+            true => games.last().map(|game| {
+                HistoryCursor {
+                    finished_at: game.finished_at,
+                    game_id: game.game_id.clone(),
+                }
+                .encode()
+            }),
+            false => None,
+        };
+
+        GameHistoryPage { games, next_cursor }
+    }
+}
+
+impl IntoResponse for GameHistoryPage {
+    /// Converts the `GameHistoryPage` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,119 @@
+use serde::Serialize;
+
+/// Highlights of a finished game, shown by the recap screen of the clients.
+///
+/// Published as a `gameRecap` event when the game ends. Every highlight is derived from the event
+/// log, so it only covers what happened since the last reset of the game. A highlight is `None` if
+/// no round of the game produced it, e.g. if no bluff was ever caught.
+///
+/// # Props
+///
+/// - `game_id` -> Id of the game
+/// - `rounds` -> Rounds that ended with a challenge
+/// - `biggest_bluff` -> Bluff with the most cards that was uncovered
+/// - `top_challenger` -> Player who uncovered the most bluffs
+/// - `fastest_turn` -> Quickest move of any player
+/// - `longest_round` -> Round that took the longest from its first turn to the challenge
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameRecap {
+    /// Id of the game
+    pub game_id: String,
+    /// Rounds that ended with a challenge
+    pub rounds: usize,
+    /// Bluff with the most cards that was uncovered; the earlier one wins a tie
+    pub biggest_bluff: Option<BluffHighlight>,
+    /// Player who uncovered the most bluffs; fewer challenges win a tie
+    pub top_challenger: Option<ChallengerHighlight>,
+    /// Quickest move of any player; the earlier one wins a tie
+    pub fastest_turn: Option<TurnHighlight>,
+    /// Round that took the longest from its first turn to the challenge
+    pub longest_round: Option<RoundHighlight>,
+}
+
+impl GameRecap {
+    /// Creates a recap without any highlights.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    pub fn new(game_id: &str) -> Self {
+        GameRecap {
+            game_id: game_id.to_string(),
+            rounds: 0,
+            biggest_bluff: None,
+            top_challenger: None,
+            fastest_turn: None,
+            longest_round: None,
+        }
+    }
+}
+
+/// A bluff that was uncovered by a challenge.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the bluffing player
+/// - `round_number` -> Round of the bluff
+/// - `card_count` -> Number of cards of the challenged claim
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BluffHighlight {
+    /// Id of the bluffing player
+    pub player_id: String,
+    /// Round of the bluff
+    pub round_number: usize,
+    /// Number of cards of the challenged claim
+    pub card_count: usize,
+}
+
+/// Challenges of a single player over the game.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the challenger
+/// - `caught_bluffs` -> Challenges which uncovered a bluff
+/// - `challenges` -> All challenges of the player
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengerHighlight {
+    /// Id of the challenger
+    pub player_id: String,
+    /// Challenges which uncovered a bluff
+    pub caught_bluffs: usize,
+    /// All challenges of the player
+    pub challenges: usize,
+}
+
+/// A single turn of a player.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the player who moved
+/// - `round_number` -> Round of the turn
+/// - `milliseconds` -> Time from the start of the turn to the move
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TurnHighlight {
+    /// Id of the player who moved
+    pub player_id: String,
+    /// Round of the turn
+    pub round_number: usize,
+    /// Time from the start of the turn to the move
+    pub milliseconds: i64,
+}
+
+/// A round that ended with a challenge.
+///
+/// # Props
+///
+/// - `round_number` -> The round
+/// - `milliseconds` -> Time from the first turn of the round to the challenge, including pauses
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundHighlight {
+    /// The round
+    pub round_number: usize,
+    /// Time from the first turn of the round to the challenge, including pauses
+    pub milliseconds: i64,
+}
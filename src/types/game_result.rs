@@ -0,0 +1,144 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::application_error::{ErrorObject, Redact},
+    types::{game::Game, player::player_identity},
+    utils::{
+        d1_conversions::{bool_from_number, datetime_from_text, optional_datetime_from_text},
+        time::server_time,
+    },
+};
+
+/// Final result of one player in a finished game.
+///
+/// Written once a game ended, so statistics don't need to load whole games.
+///
+/// # Props
+///
+/// - `game_id` -> Finished game
+/// - `player_id` -> Player the result belongs to
+/// - `player_name` -> Name of the player at the end of the game
+/// - `fingerprint` -> Fingerprint of the client, identifying the player across games
+/// - `score` -> Final score of the player
+/// - `is_winner` -> Whether the player won the game
+/// - `rounds_played` -> Number of rounds of the game
+/// - `finished_at` -> Point in time the game ended
+/// - `bluffs` -> Claims of the player which weren't truthful
+/// - `successful_bluffs` -> Bluffs of the player nobody challenged
+/// - `challenges` -> Claims the player challenged
+/// - `successful_challenges` -> Challenges of the player which uncovered a bluff
+/// - `started_at` -> Point in time the game started; `None` for results recorded before it was
+///   stored
+/// - `player_count` -> Number of players at the end of the game
+/// - `daily_date` -> Day of the daily challenge the game played
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameResult {
+    /// Finished game
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// Player the result belongs to
+    #[serde(alias = "player_id")]
+    pub player_id: String,
+    /// Name of the player at the end of the game
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+    /// Fingerprint of the client, identifying the player across games
+    #[serde(default, skip_serializing)]
+    pub fingerprint: Option<String>,
+    /// Final score of the player
+    pub score: usize,
+    /// Whether the player won the game
+    #[serde(alias = "is_winner", deserialize_with = "bool_from_number")]
+    pub is_winner: bool,
+    /// Number of rounds of the game
+    #[serde(alias = "rounds_played")]
+    pub rounds_played: usize,
+    /// Point in time the game ended
+    #[serde(alias = "finished_at", deserialize_with = "datetime_from_text")]
+    pub finished_at: DateTime<Utc>,
+    /// Claims of the player which weren't truthful
+    #[serde(default)]
+    pub bluffs: usize,
+    /// Bluffs of the player nobody challenged
+    #[serde(alias = "successful_bluffs", default)]
+    pub successful_bluffs: usize,
+    /// Claims the player challenged
+    #[serde(default)]
+    pub challenges: usize,
+    /// Challenges of the player which uncovered a bluff
+    #[serde(alias = "successful_challenges", default)]
+    pub successful_challenges: usize,
+    /// Point in time the game started
+    #[serde(
+        alias = "started_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Number of players at the end of the game
+    #[serde(alias = "player_count", default)]
+    pub player_count: usize,
+    /// Day of the daily challenge the game played
+    #[serde(alias = "daily_date", default)]
+    pub daily_date: Option<NaiveDate>,
+}
+
+impl GameResult {
+    /// Creates the results of all players of a finished game.
+    ///
+    /// # Arguments
+    ///
+    /// - `game` -> Finished game including its players
+    pub fn from_game(game: &Game) -> Vec<GameResult> {
+        let finished_at = game.finished_at.unwrap_or_else(server_time);
+        let rounds_played = game.total_rounds.unwrap_or(game.round_number);
+
+        game.players
+            .iter()
+            .map(|player| GameResult {
+                game_id: game.id.clone(),
+                player_id: player.id.clone(),
+                player_name: player.name.clone(),
+                fingerprint: player.fingerprint.clone(),
+                score: player.score,
+                is_winner: game.winner_player_id.as_deref() == Some(player.id.as_str()),
+                rounds_played,
+                finished_at,
+                bluffs: player.bluffs,
+                successful_bluffs: player.successful_bluffs,
+                challenges: player.challenges,
+                successful_challenges: player.successful_challenges,
+                started_at: Some(game.started_at),
+                player_count: game.players.len(),
+                daily_date: game.daily_date,
+            })
+            .collect()
+    }
+
+    /// Returns the key identifying the player across games, see `player_identity`.
+    pub fn identity(&self) -> String {
+        player_identity(self.fingerprint.as_deref(), &self.player_name)
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Game ID: {}, Player ID: {}, Score: {}, Winner: {}",
+            self.game_id, self.player_id, self.score, self.is_winner
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for GameResult {}
+
+impl Redact for GameResult {
+    /// Removes the fingerprint of the client.
+    fn redact(&mut self) {
+        self.fingerprint = None;
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::{deck_theme::DeckTheme, visibility::Visibility},
+    errors::validation_errors::{Validate, ValidationErrors},
+    i18n::locale::Locale,
+    logic::deck::STANDARD_DECK_SIZE,
+    types::game::MAX_PLAYERS,
+    utils::d1_conversions::{bool_from_number, enum_from_column},
+};
+
+// constants
+
+/// The minimum number of players needed to play a game.
+pub const MIN_PLAYERS: usize = 2;
+
+/// Seconds a player has for a move unless the host chooses otherwise.
+pub const DEFAULT_TURN_SECONDS: usize = 60;
+
+/// Shortest and longest time a host can give the players for a move, in seconds.
+pub const TURN_SECONDS_RANGE: (usize, usize) = (10, 300);
+
+/// Longest grace window a host can give the players to take back their last claim, in seconds.
+pub const MAX_UNDO_SECONDS: usize = 30;
+
+/// Fewest and most standard decks that can be shuffled together for a game.
+pub const DECK_COUNT_RANGE: (usize, usize) = (1, 3);
+
+/// Settings of a game chosen by the host when the game is created.
+///
+/// Every setting is stored in its own column of the `games` table.
+///
+/// # Props
+///
+/// - `max_players` -> Maximum number of players that can join the game
+/// - `visibility` -> Whether the game is listed in the public lobby
+/// - `turn_seconds` -> Seconds a player has for a move
+/// - `undo_seconds` -> Seconds a player can take back their last claim; `0` turns the house rule
+///   off
+/// - `deck_count` -> Number of standard decks shuffled together
+/// - `hand_size` -> Cards dealt to every player; `None` deals the whole deck
+/// - `language` -> Language spoken at the table as a lowercase ISO 639 code, e.g. `de`
+/// - `region` -> Region of the table as an uppercase ISO 3166 code, e.g. `AT`
+/// - `deck_theme` -> Skin of the cards every client of the game renders
+/// - `randomize_seats` -> Whether the seats are shuffled when the game starts
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSettings {
+    /// Maximum number of players that can join the game
+    #[serde(alias = "max_players", default = "GameSettings::default_max_players")]
+    pub max_players: usize,
+    /// Whether the game is listed in the public lobby or only joinable with its code
+    #[serde(default, deserialize_with = "enum_from_column")]
+    pub visibility: Visibility,
+    /// Seconds a player has for a move, shown to the clients as a countdown
+    #[serde(alias = "turn_seconds", default = "GameSettings::default_turn_seconds")]
+    pub turn_seconds: usize,
+    /// House rule: seconds a player can take back their last claim as long as nobody challenged it
+    #[serde(alias = "undo_seconds", default)]
+    pub undo_seconds: usize,
+    /// Number of standard decks of 52 cards shuffled together
+    #[serde(alias = "deck_count", default = "GameSettings::default_deck_count")]
+    pub deck_count: usize,
+    /// Cards dealt to every player at the start; the whole deck is dealt if `None`
+    #[serde(alias = "hand_size", default)]
+    pub hand_size: Option<usize>,
+    /// Language spoken in the chat of the game, used by the lobby filter
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Region the players of the game come from, used by the lobby filter
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Skin of the cards every client of the game renders
+    #[serde(alias = "deck_theme", default, deserialize_with = "enum_from_column")]
+    pub deck_theme: DeckTheme,
+    /// Whether the seats are shuffled when the host starts the game instead of keeping the order
+    /// the host arranged
+    #[serde(
+        alias = "randomize_seats",
+        default,
+        deserialize_with = "bool_from_number"
+    )]
+    pub randomize_seats: bool,
+}
+
+impl GameSettings {
+    /// Default value of the `max_players` setting.
+    fn default_max_players() -> usize {
+        MAX_PLAYERS
+    }
+
+    /// Default value of the `turn_seconds` setting.
+    fn default_turn_seconds() -> usize {
+        DEFAULT_TURN_SECONDS
+    }
+
+    /// Default value of the `deck_count` setting.
+    fn default_deck_count() -> usize {
+        DECK_COUNT_RANGE.0
+    }
+}
+
+impl Default for GameSettings {
+    /// Creates settings with the default values.
+    fn default() -> Self {
+        GameSettings {
+            max_players: Self::default_max_players(),
+            visibility: Visibility::default(),
+            turn_seconds: Self::default_turn_seconds(),
+            undo_seconds: 0,
+            deck_count: Self::default_deck_count(),
+            hand_size: None,
+            language: None,
+            region: None,
+            deck_theme: DeckTheme::default(),
+            randomize_seats: false,
+        }
+    }
+}
+
+impl Validate for GameSettings {
+    /// Checks that all settings are in their allowed ranges.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.max_players < MIN_PLAYERS || self.max_players > MAX_PLAYERS {
+            errors.add(
+                "max_players",
+                "out_of_range",
+                &format!("The maximum number of players must be between {MIN_PLAYERS} and {MAX_PLAYERS}!"),
+            );
+        }
+
+        let (min_turn_seconds, max_turn_seconds) = TURN_SECONDS_RANGE;
+        if self.turn_seconds < min_turn_seconds || self.turn_seconds > max_turn_seconds {
+            errors.add(
+                "turn_seconds",
+                "out_of_range",
+                &format!("The time for a move must be between {min_turn_seconds} and {max_turn_seconds} seconds!"),
+            );
+        }
+
+        if self.undo_seconds > MAX_UNDO_SECONDS {
+            errors.add(
+                "undo_seconds",
+                "out_of_range",
+                &format!("A claim can be taken back for at most {MAX_UNDO_SECONDS} seconds!"),
+            );
+        }
+
+        let (min_decks, max_decks) = DECK_COUNT_RANGE;
+        if self.deck_count < min_decks || self.deck_count > max_decks {
+            errors.add(
+                "deck_count",
+                "out_of_range",
+                &format!("A game is played with {min_decks} to {max_decks} decks!"),
+            );
+        }
+
+        // every seat needs a full hand, even if the game fills up
+        if let Some(hand_size) = self.hand_size {
+            let cards = STANDARD_DECK_SIZE * self.deck_count;
+            if hand_size == 0 || hand_size * self.max_players > cards {
+                errors.add(
+                    "hand_size",
+                    "out_of_range",
+                    &format!(
+                        "A hand must hold between 1 and {} cards with {} players and {cards} cards!",
+                        cards / self.max_players.max(1),
+                        self.max_players
+                    ),
+                );
+            }
+        }
+
+        if let Some(language) = &self.language {
+            let is_language_code = (2..=3).contains(&language.len())
+                && language.chars().all(|c| c.is_ascii_lowercase());
+            if !is_language_code {
+                errors.add(
+                    "language",
+                    "invalid_format",
+                    "The language must be a lowercase ISO 639 code like 'de'!",
+                );
+            }
+        }
+
+        if let Some(region) = &self.region {
+            let is_region_code =
+                region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase());
+            if !is_region_code {
+                errors.add(
+                    "region",
+                    "invalid_format",
+                    "The region must be an uppercase ISO 3166 code like 'AT'!",
+                );
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+impl fmt::Display for GameSettings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Max Players: {}, Visibility: {}, Turn Seconds: {}, Undo Seconds: {}, Decks: {}, Hand Size: {:?}, Language: {:?}, Region: {:?}, Deck Theme: {}, Randomize Seats: {}",
+            self.max_players,
+            self.visibility,
+            self.turn_seconds,
+            self.undo_seconds,
+            self.deck_count,
+            self.hand_size,
+            self.language,
+            self.region,
+            self.deck_theme,
+            self.randomize_seats
+        )
+    }
+}
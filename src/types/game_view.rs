@@ -0,0 +1,305 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::{
+    enums::{card_types::CardType, game_state::GameState},
+    i18n::locale::Locale,
+    logic::hand::{group_hand, sort_hand, CardGroup},
+    types::{
+        card::Card,
+        chat::Chat,
+        claim::Claim,
+        game::Game,
+        game_settings::GameSettings,
+        hand_summary::HandSummary,
+        pile_summary::{Declaration, PileSummary},
+        player::{Player, PlayerProfile},
+    },
+    utils::time::{server_time, turn_deadline},
+};
+
+/// Perspective-aware representation of a `Game` that is sent to a single player.
+///
+/// Serializing a `Game` directly would leak the hands of all players. A `GameView` only contains
+/// the cards of the player it was created for, the number of cards of every opponent and
+/// face-down claims until they are revealed.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the game
+/// - `host_player_id` -> Id of the player who manages the game
+/// - `state` -> Current state of the game
+/// - `which_player_turn` -> Id of the player who needs to make a move
+/// - `started_at` -> Timestamp when the game was created
+/// - `round_number` -> Current round
+/// - `card_to_play` -> Card type that has to be played in the current round
+/// - `settings` -> Settings of the game
+/// - `chat` -> Chat of the game
+/// - `own_cards` -> Hand of the viewing player, sorted in the order the player prefers
+/// - `own_card_groups` -> Number of cards of every card type in the hand of the viewing player
+/// - `players` -> All players without their hands, in the order of their seats
+/// - `hands` -> Number of cards every player holds
+/// - `claims` -> All claims of the current round
+/// - `pile_size` -> Number of cards placed in the current round
+/// - `last_claim_by` -> Id of the player who made the last claim of the round
+/// - `last_declared` -> What the last claim of the round announced
+/// - `winner_player_id` -> Id of the winner once the game ended
+/// - `finished_at` -> Timestamp when the game ended
+/// - `total_rounds` -> Number of played rounds once the game ended
+/// - `join_code` -> Code to invite other players
+/// - `daily_date` -> Day of the daily challenge the game plays
+/// - `turn_deadline` -> Point in time the current turn ends; `None` unless the game is in progress
+/// - `server_time` -> Time of the server when the view was created, to correct the countdown for
+///   the skew of the client clock
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GameView {
+    /// Identifier of the game
+    pub id: String,
+    /// Id of the host of the game
+    pub host_player_id: String,
+    /// Current state of the game
+    pub state: GameState,
+    /// Id of the player whose turn it is
+    pub which_player_turn: String,
+    /// Timestamp when the game was created
+    pub started_at: DateTime<Utc>,
+    /// The round number of the game
+    pub round_number: usize,
+    /// Card type that needs to be played in this round
+    pub card_to_play: CardType,
+    /// Settings chosen by the host
+    pub settings: GameSettings,
+    /// Chat of the game
+    pub chat: Chat,
+    /// The cards of the player the view was created for
+    pub own_cards: Vec<Card>,
+    /// Cards of the player the view was created for, counted by their type
+    pub own_card_groups: Vec<CardGroup>,
+    /// All players of the game in the order of their seats, opponents only with their number of
+    /// cards
+    pub players: Vec<PlayerView>,
+    /// Number of cards every player holds
+    pub hands: Vec<HandSummary>,
+    /// Claims of the current round with face-down cards
+    pub claims: Vec<ClaimView>,
+    /// Number of cards placed in the current round
+    pub pile_size: usize,
+    /// Id of the player who made the last claim of the round
+    pub last_claim_by: Option<String>,
+    /// Card type and number of cards the last claim of the round announced
+    pub last_declared: Option<Declaration>,
+    /// Id of the player who won the game
+    pub winner_player_id: Option<String>,
+    /// Timestamp when the game ended
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Number of rounds that were played
+    pub total_rounds: Option<usize>,
+    /// Code to invite other players
+    pub join_code: Option<String>,
+    /// Day of the daily challenge the game plays
+    pub daily_date: Option<NaiveDate>,
+    /// Point in time the current turn ends
+    pub turn_deadline: Option<DateTime<Utc>>,
+    /// Time of the server when the view was created
+    pub server_time: DateTime<Utc>,
+}
+
+/// Public information about a player in a `GameView`.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the player
+/// - `name` -> Name of the player
+/// - `score` -> Current score
+/// - `joined_at` -> Timestamp when the player joined
+/// - `seat_index` -> Position of the player at the table, which decides the order of the turns
+/// - `card_count` -> Number of cards in the hand of the player
+/// - `profile` -> Avatar, color and emoji of the player
+/// - `disconnected` -> Whether the heartbeat of the player stopped and their turns are passed
+/// - `muted_until` -> Point in time the mute of the player in the chat ends, if they are muted
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerView {
+    /// Identifier of the player
+    pub id: String,
+    /// Name of the player
+    pub name: String,
+    /// Score of the player
+    pub score: usize,
+    /// Timestamp when the player joined the game
+    pub joined_at: DateTime<Utc>,
+    /// Position of the player at the table
+    pub seat_index: usize,
+    /// Number of cards the player holds
+    pub card_count: usize,
+    /// Profile data to render the seat of the player
+    #[serde(flatten)]
+    pub profile: PlayerProfile,
+    /// Whether the heartbeat of the player stopped and their turns are passed
+    pub disconnected: bool,
+    /// Point in time the mute of the player in the chat ends, if they are muted
+    pub muted_until: Option<DateTime<Utc>>,
+}
+
+/// Claim as it is seen by a player in a `GameView`.
+///
+/// The placed cards are only included after they were revealed.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the claim
+/// - `created_by` -> Id of the player who made the claim
+/// - `round_number` -> Round the claim was made in
+/// - `created_at` -> Timestamp when the claim was made
+/// - `number_of_cards` -> Number of placed cards
+/// - `declared_card_type` -> Card type the player announced
+/// - `declared_count` -> Number of cards the player announced
+/// - `cards` -> Placed cards if they were revealed
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimView {
+    /// Identifier of the claim
+    pub id: String,
+    /// Id of the player who made the claim
+    pub created_by: String,
+    /// Round the claim was made in
+    pub round_number: usize,
+    /// Timestamp when the claim was made
+    pub created_at: DateTime<Utc>,
+    /// Number of placed cards
+    pub number_of_cards: usize,
+    /// Card type the player announced
+    pub declared_card_type: Option<CardType>,
+    /// Number of cards the player announced
+    pub declared_count: Option<usize>,
+    /// Placed cards, only present if the claim was revealed
+    pub cards: Option<Vec<Card>>,
+}
+
+// ----- Implementation of 'GameView' -----
+
+impl GameView {
+    /// Creates the view of a game from the perspective of one player.
+    ///
+    /// If the id doesn't belong to a player of the game, the view contains no cards at all.
+    ///
+    /// # Arguments
+    ///
+    /// - `game` -> Fully hydrated game instance
+    /// - `player_id` -> Id of the player the view is created for
+    /// - `locale` -> Language the system messages of the chat are written in
+    pub fn for_player(game: &Game, player_id: &str, locale: &Locale) -> Self {
+        let mut own_cards = vec![];
+        if let Some(player) = game.players.iter().find(|player| player.id == player_id) {
+            own_cards = player.assigned_cards.clone();
+            sort_hand(&mut own_cards, &player.hand_sort);
+        }
+        let own_card_groups = group_hand(&own_cards);
+        let pile = game
+            .pile
+            .clone()
+            .unwrap_or_else(|| PileSummary::for_claims(&game.claims, &game.card_to_play));
+
+        GameView {
+            id: game.id.clone(),
+            host_player_id: game.host_player_id.clone(),
+            state: game.state.clone(),
+            which_player_turn: game.which_player_turn.clone(),
+            started_at: game.started_at,
+            round_number: game.round_number,
+            card_to_play: game.card_to_play.clone(),
+            settings: game.settings.clone(),
+            chat: game.chat.clone().localized(locale),
+            own_cards,
+            own_card_groups,
+            players: game
+                .seating_order()
+                .into_iter()
+                .map(PlayerView::from)
+                .collect(),
+            hands: HandSummary::for_game(game),
+            claims: game.claims.iter().map(ClaimView::from).collect(),
+            pile_size: pile.pile_size,
+            last_claim_by: pile.last_claim_by,
+            last_declared: pile.last_declared,
+            winner_player_id: game.winner_player_id.clone(),
+            finished_at: game.finished_at,
+            total_rounds: game.total_rounds,
+            join_code: game.join_code.clone(),
+            daily_date: game.daily_date,
+            turn_deadline: match game.state {
+                GameState::InProgress => {
+                    turn_deadline(game.turn_started_at, game.settings.turn_seconds)
+                }
+                _ => None,
+            },
+            server_time: server_time(),
+        }
+    }
+
+    /// Creates the view of a game for a spectator, who isn't a player of the game.
+    ///
+    /// Contains no cards of any hand and no join code.
+    ///
+    /// # Arguments
+    ///
+    /// - `game` -> Fully hydrated game instance
+    /// - `locale` -> Language the system messages of the chat are written in
+    pub fn for_spectator(game: &Game, locale: &Locale) -> Self {
+        let mut view = GameView::for_player(game, "", locale);
+        view.join_code = None;
+        view
+    }
+}
+
+impl IntoResponse for GameView {
+    /// Converts the `GameView` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl From<&Player> for PlayerView {
+    /// Creates the public view of a player without the hand.
+    fn from(player: &Player) -> Self {
+        PlayerView {
+            id: player.id.clone(),
+            name: player.name.clone(),
+            score: player.score,
+            joined_at: player.joined_at,
+            seat_index: player.seat_index,
+            card_count: player.assigned_cards.len(),
+            profile: player.profile.clone(),
+            disconnected: player.disconnected_at.is_some(),
+            muted_until: player
+                .muted_until
+                .filter(|muted_until| *muted_until > server_time()),
+        }
+    }
+}
+
+impl From<&Claim> for ClaimView {
+    /// Creates the view of a claim; the cards stay face-down until the claim is revealed.
+    fn from(claim: &Claim) -> Self {
+        ClaimView {
+            id: claim.id.clone(),
+            created_by: claim.created_by.clone(),
+            round_number: claim.round_number,
+            created_at: claim.created_at,
+            number_of_cards: claim.number_of_cards,
+            declared_card_type: claim.declared_card_type.clone(),
+            declared_count: claim.declared_count,
+            cards: if claim.revealed {
+                Some(claim.cards.clone())
+            } else {
+                None
+            },
+        }
+    }
+}
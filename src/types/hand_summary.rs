@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{game::Game, player::Player};
+
+/// Public summary of the hand of a player.
+///
+/// Opponents are allowed to know how many cards a player holds but never which ones.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the player holding the cards
+/// - `card_count` -> Number of cards in the hand of the player
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HandSummary {
+    /// Id of the player holding the cards
+    #[serde(alias = "player_id")]
+    pub player_id: String,
+    /// Number of cards in the hand
+    #[serde(alias = "card_count")]
+    pub card_count: usize,
+}
+
+impl HandSummary {
+    /// Creates the hand summaries of all players of a game in the order of the player list.
+    pub fn for_game(game: &Game) -> Vec<HandSummary> {
+        game.players.iter().map(HandSummary::from).collect()
+    }
+}
+
+impl From<&Player> for HandSummary {
+    /// Counts the cards of a player without exposing them.
+    fn from(player: &Player) -> Self {
+        HandSummary {
+            player_id: player.id.clone(),
+            card_count: player.assigned_cards.len(),
+        }
+    }
+}
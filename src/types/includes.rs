@@ -0,0 +1,73 @@
+use bitflags::bitflags;
+
+use crate::errors::validation_errors::ValidationErrors;
+
+bitflags! {
+    /// Parts of a game hydrated next to its row.
+    ///
+    /// Clients which only follow the turn and the state of a game skip the rest, so their polls
+    /// neither read nor transfer the chat and the claims.
+    ///
+    /// # Example usage:
+    /// ```rust
+    /// use your_crate::includes::Includes;
+    /// let includes = Includes::parse("players,claims")?;
+    /// assert!(!includes.contains(Includes::CHAT));
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Includes: u8 {
+        /// Players of the game with their hands
+        const PLAYERS = 1;
+        /// Claims of the current round with their cards
+        const CLAIMS = 1 << 1;
+        /// Chat of the game with its messages
+        const CHAT = 1 << 2;
+    }
+}
+
+impl Includes {
+    /// Parses the comma-separated value of an `include` query parameter.
+    ///
+    /// An empty value only includes the row of the game.
+    ///
+    /// # Arguments
+    ///
+    /// - `value` -> Names of the parts, e.g. `players,claims,chat`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationErrors` if a name isn't `players`, `claims` or `chat`.
+    pub fn parse(value: &str) -> Result<Self, ValidationErrors> {
+        let mut includes = Includes::empty();
+
+        for name in value
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            includes |= match name {
+                "players" => Includes::PLAYERS,
+                "claims" => Includes::CLAIMS,
+                "chat" => Includes::CHAT,
+                _ => {
+                    let mut errors = ValidationErrors::new();
+                    errors.add(
+                        "include",
+                        "unknown_include",
+                        "Only players, claims and chat can be included!",
+                    );
+                    return Err(errors);
+                }
+            };
+        }
+
+        Ok(includes)
+    }
+}
+
+impl Default for Includes {
+    /// Everything is hydrated unless a client asks for less.
+    fn default() -> Self {
+        Includes::all()
+    }
+}
@@ -0,0 +1,165 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::{
+        application_error::{ErrorObject, Redact},
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::locale::Locale,
+    utils::{d1_conversions::datetime_from_text, time::server_time},
+};
+
+// constants
+/// Maximum number of characters of an email address.
+pub const MAX_EMAIL_LENGTH: usize = 254;
+
+/// Maximum number of characters of the personal note of an invitation.
+pub const MAX_INVITE_NOTE_LENGTH: usize = 200;
+
+/// Invitation email a host sent to a friend.
+///
+/// The address of the recipient isn't kept; the record only counts towards the rate limit of the
+/// host.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the invitation
+/// - `game_id` -> Game the friend was invited to
+/// - `sender_id` -> Id of the host who sent the invitation
+/// - `sent_at` -> Point in time the email was sent
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Invitation {
+    /// Identifier of the invitation
+    pub id: String,
+    /// Game the friend was invited to
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// Id of the host who sent the invitation
+    #[serde(alias = "sender_id")]
+    pub sender_id: String,
+    /// Point in time the email was sent
+    #[serde(alias = "sent_at", deserialize_with = "datetime_from_text")]
+    pub sent_at: DateTime<Utc>,
+}
+
+impl Invitation {
+    /// Creates the record of an invitation sent right now.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Game the friend was invited to
+    /// - `sender_id` -> Id of the host
+    pub fn new(game_id: String, sender_id: String) -> Self {
+        Invitation {
+            id: uuid::Uuid::new_v4().to_string(),
+            game_id,
+            sender_id,
+            sent_at: server_time(),
+        }
+    }
+}
+
+impl IntoResponse for Invitation {
+    /// Converts the `Invitation` into a response with status code 201.
+    fn into_response(self) -> Response {
+        (StatusCode::CREATED, Json(self)).into_response()
+    }
+}
+
+impl fmt::Display for Invitation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invitation ID: {}, Game ID: {}, Sender: {}",
+            self.id, self.game_id, self.sender_id
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for Invitation {}
+
+impl Redact for Invitation {}
+
+// ----- DTO for inviting a friend -----
+
+/// DTO type for a host who invites a friend by email.
+///
+/// # Props
+///
+/// - `email` -> Address of the friend
+/// - `note` -> Optional personal note added to the email
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInvitationDTO {
+    /// Address of the friend
+    pub email: String,
+    /// Optional personal note added to the email
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl CreateInvitationDTO {
+    /// Returns the trimmed note, `None` if it is empty.
+    pub fn trimmed_note(&self) -> Option<&str> {
+        self.note
+            .as_deref()
+            .map(str::trim)
+            .filter(|note| !note.is_empty())
+    }
+}
+
+impl Validate for CreateInvitationDTO {
+    /// Checks the format of the address and the length of the note.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        let email = self.email.trim();
+
+        if email.is_empty() {
+            errors.add("email", "required", "The email address is mandatory!");
+        } else if email.chars().count() > MAX_EMAIL_LENGTH {
+            errors.add(
+                "email",
+                "too_long",
+                &format!("The email address can't be longer than {MAX_EMAIL_LENGTH} characters!"),
+            );
+        } else if !is_email_address(email) {
+            errors.add("email", "invalid_format", "The email address is invalid!");
+        }
+        if self
+            .trimmed_note()
+            .is_some_and(|note| note.chars().count() > MAX_INVITE_NOTE_LENGTH)
+        {
+            errors.add(
+                "note",
+                "too_long",
+                &format!("The note can't be longer than {MAX_INVITE_NOTE_LENGTH} characters!"),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Checks the rough shape `local@domain.tld` without whitespace.
+///
+/// Whether the address really exists only shows when the email is sent.
+fn is_email_address(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && !email.chars().any(char::is_whitespace)
+        && !domain.contains('@')
+        && domain
+            .split_once('.')
+            .is_some_and(|(name, tld)| !name.is_empty() && !tld.is_empty() && !tld.ends_with('.'))
+}
@@ -0,0 +1,133 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    enums::leaderboard_period::LeaderboardPeriod,
+    errors::application_error::{ErrorObject, Redact},
+    types::season::Season,
+};
+
+/// Standing of one player on a leaderboard.
+///
+/// Players are identified by the fingerprint of their client, or by their name if they joined
+/// without one. The identity itself is never sent to anyone.
+///
+/// # Props
+///
+/// - `rank` -> Position on the leaderboard, starting at 1
+/// - `player_name` -> Name the player used in their latest game
+/// - `games_played` -> Finished games of the player in the period
+/// - `wins` -> Games the player won
+/// - `losses` -> Games the player didn't win
+/// - `successful_bluffs` -> Bluffs of the player nobody challenged
+/// - `successful_challenges` -> Challenges of the player which uncovered a bluff
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    /// Position on the leaderboard
+    #[serde(default)]
+    pub rank: usize,
+    /// Name the player used in their latest game
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+    /// Finished games of the player in the period
+    #[serde(alias = "games_played")]
+    pub games_played: usize,
+    /// Games the player won
+    pub wins: usize,
+    /// Games the player didn't win
+    pub losses: usize,
+    /// Bluffs of the player nobody challenged
+    #[serde(alias = "successful_bluffs")]
+    pub successful_bluffs: usize,
+    /// Challenges of the player which uncovered a bluff
+    #[serde(alias = "successful_challenges")]
+    pub successful_challenges: usize,
+}
+
+impl fmt::Display for LeaderboardEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} with {} wins",
+            self.rank, self.player_name, self.wins
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for LeaderboardEntry {}
+
+impl Redact for LeaderboardEntry {}
+
+/// One page of a leaderboard, best players first.
+///
+/// # Props
+///
+/// - `period` -> Span of time the leaderboard covers
+/// - `period_start` -> First moment of the period; `None` for `alltime`
+/// - `season` -> The season of a `season` leaderboard
+/// - `entries` -> Standings of the page
+/// - `next_offset` -> Pass as `offset` to get the next page; `None` on the last page
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardPage {
+    /// Span of time the leaderboard covers
+    pub period: LeaderboardPeriod,
+    /// First moment of the period
+    pub period_start: Option<DateTime<Utc>>,
+    /// The season of a `season` leaderboard
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub season: Option<Season>,
+    /// Standings of the page
+    pub entries: Vec<LeaderboardEntry>,
+    /// Pass as `offset` to get the next page; `None` on the last page
+    pub next_offset: Option<usize>,
+}
+
+impl LeaderboardPage {
+    /// Creates a page from the entries returned for an offset and a page size.
+    ///
+    /// # Arguments
+    ///
+    /// - `period` -> Span of time the leaderboard covers
+    /// - `period_start` -> First moment of the period
+    /// - `entries` -> Standings of the page, best first
+    /// - `offset` -> Number of standings before the page
+    /// - `page_size` -> Requested number of entries; a full page may be followed by another one
+    pub fn new(
+        period: LeaderboardPeriod,
+        period_start: Option<DateTime<Utc>>,
+        mut entries: Vec<LeaderboardEntry>,
+        offset: usize,
+        page_size: usize,
+    ) -> Self {
+        for (index, entry) in entries.iter_mut().enumerate() {
+            entry.rank = offset + index + 1;
+        }
+        let next_offset = match entries.len() == page_size {
+            true => Some(offset + page_size),
+            false => None,
+        };
+
+        LeaderboardPage {
+            period,
+            period_start,
+            season: None,
+            entries,
+            next_offset,
+        }
+    }
+}
+
+impl IntoResponse for LeaderboardPage {
+    /// Converts the `LeaderboardPage` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
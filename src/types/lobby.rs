@@ -0,0 +1,169 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::{game_mode::GameMode, game_state::GameState, visibility::Visibility},
+    errors::validation_errors::{Validate, ValidationErrors},
+    i18n::locale::Locale,
+    types::player::{validate_player_name, PlayerProfile},
+    utils::d1_conversions::{datetime_from_text, enum_from_column},
+};
+
+/// Summary of a game that is shown in the lobby before a player joins.
+///
+/// Doesn't contain any data of a running game, only what is needed to decide whether to join.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the game
+/// - `host_name` -> Name of the player who created the game
+/// - `player_count` -> Number of players who already joined
+/// - `max_players` -> Maximum number of players
+/// - `visibility` -> Whether the game is listed in the public lobby
+/// - `state` -> Current state of the game
+/// - `started_at` -> Timestamp when the game was created
+/// - `language` -> Language spoken at the table, if the host set one
+/// - `region` -> Region of the table, if the host set one
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyGame {
+    /// Identifier of the game
+    pub id: String,
+    /// Name of the host
+    #[serde(alias = "host_name", default)]
+    pub host_name: Option<String>,
+    /// Number of players who already joined
+    #[serde(alias = "player_count")]
+    pub player_count: usize,
+    /// Maximum number of players
+    #[serde(alias = "max_players")]
+    pub max_players: usize,
+    /// Visibility of the game
+    #[serde(deserialize_with = "enum_from_column")]
+    pub visibility: Visibility,
+    /// Current state of the game
+    #[serde(deserialize_with = "enum_from_column")]
+    pub state: GameState,
+    /// Timestamp when the game was created
+    #[serde(alias = "started_at", deserialize_with = "datetime_from_text")]
+    pub started_at: DateTime<Utc>,
+    /// Language spoken at the table
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Region of the table
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
+impl LobbyGame {
+    /// Returns `true` if another player can join the game.
+    pub fn is_joinable(&self) -> bool {
+        self.state == GameState::WaitingForPlayers && self.player_count < self.max_players
+    }
+}
+
+/// Query parameters narrowing down the games listed in the lobby.
+///
+/// All filters that are set must match. The codes are compared case-insensitively, so
+/// `?language=DE` finds the games tagged with `de`. Private games are never listed.
+///
+/// # Props
+///
+/// - `state` -> Only games in this state; `WaitingForPlayers` if `None`
+/// - `mode` -> Only games of this mode; all modes if `None`
+/// - `language` -> Only games with this language; all games if `None`
+/// - `region` -> Only games with this region; all games if `None`
+/// - `has_open_seats` -> Only games with (`true`) or without (`false`) a free seat
+/// - `created_after` -> Only games created after this timestamp
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyFilter {
+    /// Only games in this state
+    #[serde(default)]
+    pub state: Option<GameState>,
+    /// Only games of this mode
+    #[serde(default)]
+    pub mode: Option<GameMode>,
+    /// Only games with this language
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Only games with this region
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Only games with or without a free seat
+    #[serde(alias = "has_open_seats", default)]
+    pub has_open_seats: Option<bool>,
+    /// Only games created after this timestamp
+    #[serde(alias = "created_after", default)]
+    pub created_after: Option<DateTime<Utc>>,
+}
+
+impl LobbyFilter {
+    /// Returns the state of the listed games.
+    pub fn state(&self) -> GameState {
+        self.state.clone().unwrap_or(GameState::WaitingForPlayers)
+    }
+
+    /// Returns the language in the way it's stored with the games.
+    pub fn language(&self) -> Option<String> {
+        self.language
+            .as_deref()
+            .map(str::trim)
+            .filter(|language| !language.is_empty())
+            .map(str::to_ascii_lowercase)
+    }
+
+    /// Returns the region in the way it's stored with the games.
+    pub fn region(&self) -> Option<String> {
+        self.region
+            .as_deref()
+            .map(str::trim)
+            .filter(|region| !region.is_empty())
+            .map(str::to_ascii_uppercase)
+    }
+}
+
+impl IntoResponse for LobbyGame {
+    /// Converts the `LobbyGame` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// DTO of a player who wants to play right away, without choosing a game.
+///
+/// # Props
+///
+/// - `name` -> Name the client wants to play with; numbered if the game already has it
+/// - `profile` -> Optional profile of the new player
+/// - `turnstile_token` -> Token of the Turnstile widget, only checked if a game has to be created
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickPlayDTO {
+    /// Name the client wants to play with
+    pub name: String,
+    /// Optional profile of the new player
+    #[serde(flatten)]
+    pub profile: PlayerProfile,
+    /// Token of the Turnstile widget proving the client isn't a bot
+    #[serde(default, alias = "turnstile_token", alias = "cf-turnstile-response")]
+    pub turnstile_token: Option<String>,
+}
+
+impl Validate for QuickPlayDTO {
+    /// Checks the name and the profile of the new player.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = validate_player_name("name", &self.name, locale);
+
+        if let Err(profile_errors) = self.profile.validate(locale) {
+            errors.merge(profile_errors);
+        }
+
+        errors.into_result()
+    }
+}
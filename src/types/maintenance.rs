@@ -0,0 +1,69 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    enums::data_migration::DataMigration,
+    errors::application_error::{ErrorObject, Redact},
+};
+
+/// Maintenance mode of the whole backend.
+///
+/// While it is enabled, no game can be created or joined. Running games aren't interrupted.
+///
+/// # Props
+///
+/// - `enabled` -> Whether the maintenance mode is active
+/// - `message` -> Optional explanation shown to the players
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceMode {
+    /// Whether the maintenance mode is active
+    pub enabled: bool,
+    /// Optional explanation shown to the players
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl IntoResponse for MaintenanceMode {
+    /// Converts the `MaintenanceMode` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl fmt::Display for MaintenanceMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Maintenance enabled: {}", self.enabled)
+    }
+}
+
+impl<'a> ErrorObject<'a> for MaintenanceMode {}
+
+impl Redact for MaintenanceMode {}
+
+/// Result of a data migration run by an operator.
+///
+/// # Props
+///
+/// - `migration` -> The migration that was run
+/// - `affected_rows` -> Number of changed rows
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    /// The migration that was run
+    pub migration: DataMigration,
+    /// Number of changed rows
+    pub affected_rows: usize,
+}
+
+impl IntoResponse for MigrationReport {
+    /// Converts the `MigrationReport` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,195 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::{
+        application_error::{ErrorObject, Redact},
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::locale::Locale,
+    types::player::validate_player_name,
+    utils::time::server_time,
+};
+
+// constants
+/// Fewest waiting players a game is assembled from once the oldest of them waited long enough.
+pub const MIN_MATCH_PLAYERS: usize = 3;
+
+/// Most players assembled into one game; a game is assembled right away once this many wait.
+pub const MAX_MATCH_PLAYERS: usize = 5;
+
+/// Seconds the oldest waiting player waits for a full game before a smaller one is assembled.
+pub const MATCHMAKING_TIMEOUT_SECONDS: i64 = 30;
+
+/// Minutes an assembled game is kept for the players who haven't picked it up yet.
+pub const ASSIGNMENT_LIFETIME_MINUTES: i64 = 10;
+
+/// A solo player waiting in the matchmaking queue.
+///
+/// The id is only handed to the player who entered the queue and identifies them until they are
+/// assigned to a game, so it has to be unguessable.
+///
+/// # Props
+///
+/// - `id` -> Id of the ticket
+/// - `player_name` -> Name the player wants to play with
+/// - `fingerprint` -> Fingerprint of the client, attached to the player of the assembled game
+/// - `entered_at` -> Point in time the player entered the queue
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueTicket {
+    /// Id of the ticket
+    pub id: String,
+    /// Name the player wants to play with
+    pub player_name: String,
+    /// Fingerprint of the client
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// Point in time the player entered the queue
+    pub entered_at: DateTime<Utc>,
+}
+
+impl QueueTicket {
+    /// Creates a ticket with a new id.
+    ///
+    /// # Arguments
+    ///
+    /// - `player_name` -> Name the player wants to play with
+    /// - `fingerprint` -> Fingerprint of the client
+    pub fn new(player_name: String, fingerprint: Option<String>) -> Self {
+        QueueTicket {
+            id: uuid::Uuid::new_v4().to_string(),
+            player_name,
+            fingerprint,
+            entered_at: server_time(),
+        }
+    }
+}
+
+impl fmt::Display for QueueTicket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Ticket of {} entered at {}",
+            self.player_name, self.entered_at
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for QueueTicket {}
+
+impl Redact for QueueTicket {
+    /// Removes the id, which grants access to the assigned game, and the fingerprint.
+    fn redact(&mut self) {
+        self.id.clear();
+        self.fingerprint = None;
+    }
+}
+
+/// Picks the players of the next game from the waiting tickets, oldest first.
+///
+/// # Arguments
+///
+/// - `tickets` -> Waiting tickets in the order they entered the queue
+/// - `now` -> Point in time of the check
+///
+/// # Returns
+///
+/// The number of tickets from the front of the queue to assemble into a game; `0` if the players
+/// have to wait longer.
+pub fn match_size(tickets: &[QueueTicket], now: DateTime<Utc>) -> usize {
+    let timed_out = tickets.first().is_some_and(|oldest| {
+        now - oldest.entered_at >= Duration::seconds(MATCHMAKING_TIMEOUT_SECONDS)
+    });
+
+    match tickets.len() {
+        waiting if waiting >= MAX_MATCH_PLAYERS => MAX_MATCH_PLAYERS,
+        waiting if waiting >= MIN_MATCH_PLAYERS && timed_out => waiting,
+        _ => 0,
+    }
+}
+
+/// Game a ticket was assigned to.
+///
+/// # Props
+///
+/// - `game_id` -> The assembled game
+/// - `player_id` -> Player created for the ticket
+/// - `assigned_at` -> Point in time the game was assembled
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchAssignment {
+    /// The assembled game
+    pub game_id: String,
+    /// Player created for the ticket
+    pub player_id: String,
+    /// Point in time the game was assembled
+    pub assigned_at: DateTime<Utc>,
+}
+
+/// Where a ticket stands in the matchmaking queue.
+///
+/// # Props
+///
+/// - `ticket_id` -> Id of the ticket
+/// - `position` -> Position in the queue starting at 1; `None` once assigned
+/// - `waiting` -> Number of players waiting in total
+/// - `assignment` -> Game the ticket was assigned to, if any
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TicketStatus {
+    /// Id of the ticket
+    pub ticket_id: String,
+    /// Position in the queue starting at 1
+    pub position: Option<usize>,
+    /// Number of players waiting in total
+    pub waiting: usize,
+    /// Game the ticket was assigned to
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub assignment: Option<MatchAssignment>,
+}
+
+impl IntoResponse for TicketStatus {
+    /// Converts the `TicketStatus` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// ----- DTO for entering the queue -----
+
+/// DTO type for a solo player entering the matchmaking queue.
+///
+/// # Props
+///
+/// - `player_name` -> Name the player wants to play with
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EnterQueueDTO {
+    /// Name the player wants to play with
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+}
+
+impl fmt::Display for EnterQueueDTO {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Player Name: {}", self.player_name)
+    }
+}
+
+impl<'a> ErrorObject<'a> for EnterQueueDTO {}
+
+impl Redact for EnterQueueDTO {}
+
+impl Validate for EnterQueueDTO {
+    /// Checks the name of the player.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        validate_player_name("player_name", &self.player_name, locale).into_result()
+    }
+}
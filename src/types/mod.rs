@@ -1,6 +1,41 @@
+pub mod abandonment;
+pub mod achievement;
+pub mod audit;
+pub mod background_task;
+pub mod backup;
 pub mod card;
 pub mod chat;
 pub mod claim;
+pub mod daily_challenge;
+pub mod diagnostics;
 pub mod game;
+pub mod game_archive;
+pub mod game_event;
+pub mod game_history;
+pub mod game_recap;
+pub mod game_result;
+pub mod game_settings;
+pub mod game_view;
+pub mod hand_summary;
+pub mod includes;
+pub mod invitation;
+pub mod leaderboard;
+pub mod lobby;
+pub mod maintenance;
+pub mod matchmaking;
+pub mod moderation;
+pub mod overview;
+pub mod pile_summary;
 pub mod player;
+pub mod player_stats;
+pub mod recent_opponent;
+pub mod resync;
+pub mod round_summary;
+pub mod score_history;
+pub mod season;
+pub mod seating;
+pub mod share;
 pub mod status;
+pub mod tournament;
+pub mod waitlist;
+pub mod webhook;
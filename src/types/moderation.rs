@@ -0,0 +1,296 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::{
+        application_error::{ErrorObject, Redact},
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::locale::Locale,
+    middleware::authorization::ActingPlayer,
+    utils::{
+        d1_conversions::{datetime_from_text, optional_datetime_from_text},
+        time::server_time,
+    },
+};
+
+// constants
+/// Maximum number of characters of the reason of a report or a ban.
+pub const MAX_REASON_LENGTH: usize = 500;
+
+/// Report of a player about the behaviour of another player.
+///
+/// Reports are reviewed by an admin, who can ban the fingerprint of the reported player.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the report
+/// - `game_id` -> Game both players are part of
+/// - `reporter_id` -> Id of the player who filed the report
+/// - `reported_player_id` -> Id of the reported player
+/// - `reported_fingerprint` -> Fingerprint of the reported client, kept after the player left
+/// - `reason` -> Description of the behaviour
+/// - `created_at` -> Point in time the report was filed
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    /// Identifier of the report
+    pub id: String,
+    /// Game both players are part of
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// Id of the player who filed the report
+    #[serde(alias = "reporter_id")]
+    pub reporter_id: String,
+    /// Id of the reported player
+    #[serde(alias = "reported_player_id")]
+    pub reported_player_id: String,
+    /// Fingerprint of the reported client, kept after the player left
+    #[serde(alias = "reported_fingerprint", default)]
+    pub reported_fingerprint: Option<String>,
+    /// Description of the behaviour
+    pub reason: String,
+    /// Point in time the report was filed
+    #[serde(alias = "created_at", deserialize_with = "datetime_from_text")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl IntoResponse for Report {
+    /// Converts the `Report` into a response with status code 201.
+    ///
+    /// The fingerprint is only meant for admins and gets removed.
+    fn into_response(mut self) -> Response {
+        self.redact();
+        (StatusCode::CREATED, Json(self)).into_response()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Report ID: {}, Game ID: {}, Reporter: {}, Reported: {}",
+            self.id, self.game_id, self.reporter_id, self.reported_player_id
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for Report {}
+
+impl Redact for Report {
+    /// The fingerprint of the reported client is removed.
+    fn redact(&mut self) {
+        self.reported_fingerprint = None;
+    }
+}
+
+// ----- DTO for reporting a player -----
+
+/// DTO type for a player who reports another player of the same game.
+///
+/// # Props
+///
+/// - `reporter_id` -> Id of the player who files the report
+/// - `reported_player_id` -> Id of the reported player
+/// - `reason` -> Description of the behaviour
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReportDTO {
+    /// Id of the player who files the report
+    #[serde(alias = "reporter_id")]
+    pub reporter_id: String,
+    /// Id of the reported player
+    #[serde(alias = "reported_player_id")]
+    pub reported_player_id: String,
+    /// Description of the behaviour
+    pub reason: String,
+}
+
+impl CreateReportDTO {
+    /// Creates the `Report` with a generated id, filed right now.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Game both players are part of
+    /// - `reported_fingerprint` -> Fingerprint of the reported client
+    pub fn into_report(self, game_id: String, reported_fingerprint: Option<String>) -> Report {
+        Report {
+            id: uuid::Uuid::new_v4().to_string(),
+            game_id,
+            reporter_id: self.reporter_id,
+            reported_player_id: self.reported_player_id,
+            reported_fingerprint,
+            reason: self.reason.trim().to_string(),
+            created_at: server_time(),
+        }
+    }
+}
+
+impl Validate for CreateReportDTO {
+    /// Checks that both players are provided, differ and a reason is given.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.reported_player_id.is_empty() {
+            errors.add(
+                "reported_player_id",
+                "required",
+                "The reported player is mandatory!",
+            );
+        } else if self.reported_player_id == self.reporter_id {
+            errors.add(
+                "reported_player_id",
+                "self_report",
+                "A player can't report themself!",
+            );
+        }
+        validate_reason(&mut errors, &self.reason);
+
+        errors.into_result()
+    }
+}
+
+impl ActingPlayer for CreateReportDTO {
+    fn acting_player_id(&self) -> &str {
+        &self.reporter_id
+    }
+}
+
+// ----- Banned clients -----
+
+/// Entry of the ban list.
+///
+/// A banned client can neither create nor join a game until the ban expires.
+///
+/// # Props
+///
+/// - `fingerprint` -> Fingerprint of the banned client
+/// - `reason` -> Why the client was banned
+/// - `banned_at` -> Point in time the ban was issued
+/// - `expires_at` -> Point in time the ban ends; permanent if `None`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Ban {
+    /// Fingerprint of the banned client
+    pub fingerprint: String,
+    /// Why the client was banned
+    pub reason: String,
+    /// Point in time the ban was issued
+    #[serde(alias = "banned_at", deserialize_with = "datetime_from_text")]
+    pub banned_at: DateTime<Utc>,
+    /// Point in time the ban ends; permanent if `None`
+    #[serde(
+        alias = "expires_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Ban {
+    /// Returns `true` if the ban still applies at the passed point in time.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+impl IntoResponse for Ban {
+    /// Converts the `Ban` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl fmt::Display for Ban {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Ban of {}: {}", self.fingerprint, self.reason)
+    }
+}
+
+impl<'a> ErrorObject<'a> for Ban {}
+
+impl Redact for Ban {}
+
+impl Validate for Ban {
+    /// Checks the fingerprint, the reason and that the ban doesn't end before it started.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.fingerprint.trim().is_empty() {
+            errors.add(
+                "fingerprint",
+                "required",
+                "The fingerprint of the client is mandatory!",
+            );
+        }
+        validate_reason(&mut errors, &self.reason);
+        if self
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= self.banned_at)
+        {
+            errors.add(
+                "expires_at",
+                "before_start",
+                "A ban must end after it was issued!",
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+// ----- DTO for banning a client -----
+
+/// DTO type for an operator who bans a client.
+///
+/// # Props
+///
+/// - `fingerprint` -> Fingerprint of the client, usually taken from a report
+/// - `reason` -> Why the client is banned
+/// - `duration_minutes` -> Length of the ban; permanent if `None`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBanDTO {
+    /// Fingerprint of the client, usually taken from a report
+    pub fingerprint: String,
+    /// Why the client is banned
+    pub reason: String,
+    /// Length of the ban; permanent if `None`
+    #[serde(alias = "duration_minutes", default)]
+    pub duration_minutes: Option<i64>,
+}
+
+impl CreateBanDTO {
+    /// Creates the `Ban`, which starts right now.
+    pub fn into_ban(self) -> Ban {
+        let banned_at = server_time();
+
+        Ban {
+            fingerprint: self.fingerprint.trim().to_string(),
+            reason: self.reason.trim().to_string(),
+            banned_at,
+            expires_at: self
+                .duration_minutes
+                .map(|minutes| banned_at + Duration::minutes(minutes)),
+        }
+    }
+}
+
+/// Checks that a reason is provided and not too long.
+fn validate_reason(errors: &mut ValidationErrors, reason: &str) {
+    if reason.trim().is_empty() {
+        errors.add("reason", "required", "A reason is mandatory!");
+    } else if reason.chars().count() > MAX_REASON_LENGTH {
+        errors.add(
+            "reason",
+            "too_long",
+            &format!("The reason must not be longer than {MAX_REASON_LENGTH} characters!"),
+        );
+    }
+}
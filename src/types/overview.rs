@@ -0,0 +1,104 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Key figures of the worker for the dashboard of the operators.
+///
+/// # Props
+///
+/// - `active_games` -> Games that haven't ended or been abandoned yet
+/// - `players_online` -> Players whose heartbeat is alive
+/// - `games_created_today` -> Games created since midnight UTC
+/// - `average_game_seconds` -> Average duration of the games that ended in the recent days;
+///   `None` if none ended
+/// - `failed_webhook_deliveries_today` -> Webhook deliveries moved to the dead-letter log since
+///   midnight UTC
+/// - `errors` -> Failed requests counted by the isolate that answered
+/// - `generated_at` -> Point in time the figures were read
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminOverview {
+    /// Games that haven't ended or been abandoned yet
+    pub active_games: usize,
+    /// Players whose heartbeat is alive
+    pub players_online: usize,
+    /// Games created since midnight UTC
+    pub games_created_today: usize,
+    /// Average duration of the recently ended games in seconds
+    pub average_game_seconds: Option<f64>,
+    /// Webhook deliveries moved to the dead-letter log since midnight UTC
+    pub failed_webhook_deliveries_today: usize,
+    /// Failed requests counted by the isolate that answered
+    pub errors: ErrorCounts,
+    /// Point in time the figures were read
+    pub generated_at: DateTime<Utc>,
+}
+
+impl AdminOverview {
+    /// Puts the figures read from D1 and the counters of the isolate together.
+    ///
+    /// # Arguments
+    ///
+    /// - `counts` -> Figures read from D1
+    /// - `errors` -> Failed requests counted by this isolate
+    /// - `generated_at` -> Point in time the figures were read
+    pub fn new(counts: OverviewCounts, errors: ErrorCounts, generated_at: DateTime<Utc>) -> Self {
+        AdminOverview {
+            active_games: counts.active_games,
+            players_online: counts.players_online,
+            games_created_today: counts.games_created_today,
+            average_game_seconds: counts.average_game_seconds,
+            failed_webhook_deliveries_today: counts.failed_webhook_deliveries_today,
+            errors,
+            generated_at,
+        }
+    }
+}
+
+impl IntoResponse for AdminOverview {
+    /// Converts the `AdminOverview` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Figures of an `AdminOverview` read from D1 in a single query.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct OverviewCounts {
+    /// Games that haven't ended or been abandoned yet
+    pub active_games: usize,
+    /// Players whose heartbeat is alive
+    pub players_online: usize,
+    /// Games created since midnight UTC
+    pub games_created_today: usize,
+    /// Average duration of the recently ended games in seconds
+    #[serde(default)]
+    pub average_game_seconds: Option<f64>,
+    /// Webhook deliveries moved to the dead-letter log since midnight UTC
+    pub failed_webhook_deliveries_today: usize,
+}
+
+/// Requests that failed, counted since the isolate started.
+///
+/// Every isolate keeps its own counters, like the ones of the `/metrics` endpoint; Analytics
+/// Engine holds the totals of every isolate.
+///
+/// # Props
+///
+/// - `server_errors` -> Requests answered with a `5xx` status
+/// - `client_errors` -> Requests answered with a `4xx` status
+/// - `database_errors` -> Requests that failed because of a database query
+#[derive(Serialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCounts {
+    /// Requests answered with a `5xx` status
+    pub server_errors: u64,
+    /// Requests answered with a `4xx` status
+    pub client_errors: u64,
+    /// Requests that failed because of a database query
+    pub database_errors: u64,
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::card_types::CardType, types::claim::Claim,
+    utils::d1_conversions::optional_enum_from_column,
+};
+
+/// Public summary of the stack of face-down cards in the current round.
+///
+/// Spares the clients from adding up the claims after every update. Snapshots read it from the
+/// database next to the game row, so it's also present if the claims weren't requested.
+///
+/// # Props
+///
+/// - `pile_size` -> Number of cards placed in the current round
+/// - `last_claim_by` -> Id of the player who made the last claim of the round
+/// - `last_declared` -> What the last claim of the round announced
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PileSummary {
+    /// Number of cards placed in the current round
+    #[serde(alias = "pile_size")]
+    pub pile_size: usize,
+    /// Id of the player who made the last claim; `None` before the first claim of the round
+    #[serde(alias = "last_claim_by", default)]
+    pub last_claim_by: Option<String>,
+    /// Announcement of the last claim; `None` before the first claim of the round
+    #[serde(default)]
+    pub last_declared: Option<Declaration>,
+}
+
+/// Card type and number of cards a claim announced.
+///
+/// # Props
+///
+/// - `card_type` -> Announced card type
+/// - `count` -> Announced number of cards
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Declaration {
+    /// Announced card type
+    pub card_type: CardType,
+    /// Announced number of cards
+    pub count: usize,
+}
+
+/// Row of the pile query of a snapshot.
+///
+/// The declaration is already completed with the card to play and the number of placed cards
+/// for claims made before declarations were recorded.
+#[derive(Deserialize, Debug)]
+pub struct PileRow {
+    /// Number of cards placed in the current round
+    pub pile_size: usize,
+    /// Author of the last claim, `NULL` before the first claim of the round
+    pub last_claim_by: Option<String>,
+    /// Card type of the last claim
+    #[serde(default, deserialize_with = "optional_enum_from_column")]
+    pub last_declared_card_type: Option<CardType>,
+    /// Number of cards of the last claim
+    pub last_declared_count: Option<usize>,
+}
+
+impl PileSummary {
+    /// Sums up the claims of a round.
+    ///
+    /// Used for games that weren't read as a snapshot, e.g. right after an update.
+    ///
+    /// # Arguments
+    ///
+    /// - `claims` -> Claims of the current round in the order they were made
+    /// - `card_to_play` -> Card type of the round, announced by claims without a declaration
+    pub fn for_claims(claims: &[Claim], card_to_play: &CardType) -> Self {
+        let last_claim = claims.iter().max_by_key(|claim| claim.created_at);
+
+        PileSummary {
+            pile_size: claims.iter().map(|claim| claim.number_of_cards).sum(),
+            last_claim_by: last_claim.map(|claim| claim.created_by.clone()),
+            last_declared: last_claim.map(|claim| Declaration {
+                card_type: claim
+                    .declared_card_type
+                    .clone()
+                    .unwrap_or_else(|| card_to_play.clone()),
+                count: claim.declared_count.unwrap_or(claim.number_of_cards),
+            }),
+        }
+    }
+}
+
+impl From<PileRow> for PileSummary {
+    /// Nests the declaration of the row.
+    fn from(row: PileRow) -> Self {
+        let last_declared = match (row.last_declared_card_type, row.last_declared_count) {
+            (Some(card_type), Some(count)) => Some(Declaration { card_type, count }),
+            _ => None,
+        };
+
+        PileSummary {
+            pile_size: row.pile_size,
+            last_claim_by: row.last_claim_by,
+            last_declared,
+        }
+    }
+}
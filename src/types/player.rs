@@ -1,19 +1,50 @@
 use std::fmt::Display;
 
+use chrono::{DateTime, Duration, Utc};
+
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
+    Json,
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::{errors::application_error::ErrorObject, types::card::Card};
+use crate::{
+    enums::hand_sort::HandSort,
+    errors::{
+        application_error::{ErrorObject, Redact},
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::{catalog::Message, locale::Locale},
+    middleware::authorization::ActingPlayer,
+    types::{card::Card, game::MAX_PLAYERS},
+    utils::{
+        d1_conversions::{datetime_from_text, enum_from_column, optional_datetime_from_text},
+        time::server_time,
+    },
+};
+
+// constants
+
+/// Maximum number of characters a player name can have.
+pub const MAX_PLAYER_NAME_LENGTH: usize = 32;
+
+/// Maximum number of characters of an avatar URL.
+pub const MAX_AVATAR_URL_LENGTH: usize = 512;
+
+/// Maximum number of characters of a profile emoji.
+///
+/// An emoji can consist of multiple code points, e.g. with skin tones or joined sequences.
+pub const MAX_EMOJI_LENGTH: usize = 8;
 
 /// Player struct representing a player in the game system.
 ///
 /// He / she can be identified by a unique ID.
 ///
 /// Contains data set by the user like the name, etc. ...
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct Player {
     /// Unique identifier of the player.
     pub id: String,
@@ -25,20 +56,94 @@ pub struct Player {
     pub score: usize,
 
     /// The date and time when the player joined the game.
-    pub joined_at: String,
+    #[serde(alias = "joined_at", deserialize_with = "datetime_from_text")]
+    pub joined_at: DateTime<Utc>,
+
+    /// Position of the player at the table, starting at 0.
+    ///
+    /// The players move in the order of their seats. A player gets the seat after the last one when
+    /// joining; the host can rearrange the seats until the game starts.
+    #[serde(alias = "seat_index", default)]
+    pub seat_index: usize,
 
     /// The cards assigned to the player.
+    ///
+    /// Isn't a column of the `players` table and needs to be fetched separately.
+    #[serde(alias = "assigned_cards", default)]
     pub assigned_cards: Vec<Card>,
 
     /// The ID of the game the player is currently in.
     ///
     /// This field is used to associate the player with a specific game instance.
+    #[serde(alias = "game_id")]
     pub game_id: String,
 
     /// The last time a player requested a status updated.
     ///
     /// If the time exceeds 5 minutes the player will be deleted from the gaming session.
-    pub last_time_update_requested: String,
+    #[serde(
+        alias = "last_time_update_requested",
+        deserialize_with = "datetime_from_text"
+    )]
+    pub last_time_update_requested: DateTime<Utc>,
+
+    /// Optional profile data to distinguish the seats of the players.
+    #[serde(flatten)]
+    pub profile: PlayerProfile,
+
+    /// Fingerprint of the client the player joined with.
+    ///
+    /// Only used to ban reported clients and never sent to anyone.
+    #[serde(default, skip_serializing)]
+    pub fingerprint: Option<String>,
+
+    /// Hash of the token the client can rejoin the game with after losing its session.
+    ///
+    /// Never sent to anyone; the client gets the token itself once, when the player joins.
+    #[serde(alias = "rejoin_token_hash", default, skip_serializing)]
+    pub rejoin_token_hash: Option<String>,
+
+    /// Claims of the player in this game which weren't truthful.
+    ///
+    /// Never sent to anyone, since it would reveal a bluff right after the claim.
+    #[serde(default, skip_serializing)]
+    pub bluffs: usize,
+
+    /// Bluffs of the player the next player didn't challenge.
+    #[serde(alias = "successful_bluffs", default, skip_serializing)]
+    pub successful_bluffs: usize,
+
+    /// Claims of other players the player challenged.
+    #[serde(default, skip_serializing)]
+    pub challenges: usize,
+
+    /// Challenges of the player which uncovered a bluff.
+    #[serde(alias = "successful_challenges", default, skip_serializing)]
+    pub successful_challenges: usize,
+
+    /// Order in which the player wants to see the cards of their hand.
+    #[serde(alias = "hand_sort", default, deserialize_with = "enum_from_column")]
+    pub hand_sort: HandSort,
+
+    /// Point in time the heartbeat of the player stopped during their turn.
+    ///
+    /// The turns of a disconnected player are passed until a status update request arrives again.
+    #[serde(
+        alias = "disconnected_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub disconnected_at: Option<DateTime<Utc>>,
+
+    /// Point in time until which the player can't write in the chat.
+    ///
+    /// Set by the host or an operator; the mute ends on its own.
+    #[serde(
+        alias = "muted_until",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub muted_until: Option<DateTime<Utc>>,
 }
 
 impl Player {
@@ -55,12 +160,63 @@ impl Player {
             name,
             game_id,
             score: 0,
-            joined_at: chrono::Utc::now().to_string(),
+            joined_at: server_time(),
+            seat_index: 0,
             assigned_cards: Vec::new(),
-            last_time_update_requested: chrono::Utc::now().to_string(),
+            last_time_update_requested: server_time(),
+            profile: PlayerProfile::default(),
+            fingerprint: None,
+            rejoin_token_hash: None,
+            bluffs: 0,
+            successful_bluffs: 0,
+            challenges: 0,
+            successful_challenges: 0,
+            hand_sort: HandSort::default(),
+            disconnected_at: None,
+            muted_until: None,
         }
     }
 
+    /// Checks whether the player hasn't requested a status update for too long.
+    ///
+    /// # Arguments
+    /// - `now`: The current point in time.
+    ///
+    /// # Returns
+    /// `true` if the last status update request is more than `timeout_minutes` ago.
+    pub fn is_timed_out(&self, now: DateTime<Utc>, timeout_minutes: i64) -> bool {
+        now - self.last_time_update_requested > Duration::minutes(timeout_minutes)
+    }
+
+    /// Checks whether the heartbeat of the player stopped, long before the player times out.
+    ///
+    /// # Arguments
+    /// - `now`: The current point in time.
+    /// - `disconnect_after_seconds`: Seconds without a status update request the player may take.
+    ///
+    /// # Returns
+    /// `true` if the last status update request is more than `disconnect_after_seconds` ago.
+    pub fn is_unresponsive(&self, now: DateTime<Utc>, disconnect_after_seconds: i64) -> bool {
+        now - self.last_time_update_requested > Duration::seconds(disconnect_after_seconds)
+    }
+
+    /// Checks whether the player is muted in the chat.
+    ///
+    /// # Arguments
+    /// - `now`: The current point in time.
+    ///
+    /// # Returns
+    /// `true` if the mute of the player didn't end yet.
+    pub fn is_muted(&self, now: DateTime<Utc>) -> bool {
+        self.muted_until
+            .is_some_and(|muted_until| muted_until > now)
+    }
+
+    /// Returns the key identifying the player across games, see `player_identity`.
+    pub fn identity(&self) -> String {
+        player_identity(self.fingerprint.as_deref(), &self.name)
+    }
+
     // ----- Implementation for 'Vec<Player>' to be serialized to JSON -----
 
     /// Converts a vector of `Player` instances into a JSON string.
@@ -96,6 +252,332 @@ impl Display for Player {
 
 impl<'a> ErrorObject<'a> for Player {}
 
+// ----- Implementation of 'Redact' for 'Player' -----
+
+impl Redact for Player {
+    /// The hand of a player is hidden information and gets removed.
+    fn redact(&mut self) {
+        self.assigned_cards = vec![];
+    }
+}
+
+// ----- Profile of a player -----
+
+/// Optional profile data chosen by a player.
+///
+/// Every field is stored in its own column of the `players` table.
+///
+/// # Fields
+///
+/// - `avatar_url`: URL of an image shown as the avatar of the player.
+/// - `color`: Color of the seat as a hex code like `#FF8800`.
+/// - `emoji`: Emoji shown next to the name of the player.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerProfile {
+    /// URL of the avatar image, must use `https`.
+    #[serde(alias = "avatar_url", default)]
+    pub avatar_url: Option<String>,
+
+    /// Color of the seat as a hex code.
+    #[serde(default)]
+    pub color: Option<String>,
+
+    /// Emoji shown next to the name.
+    #[serde(default)]
+    pub emoji: Option<String>,
+}
+
+impl PlayerProfile {
+    /// Returns `true` if no profile field is set.
+    pub fn is_empty(&self) -> bool {
+        self.avatar_url.is_none() && self.color.is_none() && self.emoji.is_none()
+    }
+}
+
+impl Validate for PlayerProfile {
+    /// Checks the format and length of all provided profile fields.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(avatar_url) = &self.avatar_url {
+            if !avatar_url.starts_with("https://") {
+                errors.add(
+                    "avatar_url",
+                    "invalid_format",
+                    "The avatar URL must start with 'https://'!",
+                );
+            }
+            if avatar_url.chars().count() > MAX_AVATAR_URL_LENGTH {
+                errors.add(
+                    "avatar_url",
+                    "too_long",
+                    &format!(
+                        "The avatar URL must not be longer than {MAX_AVATAR_URL_LENGTH} characters!"
+                    ),
+                );
+            }
+        }
+
+        if let Some(color) = &self.color {
+            let is_hex_color = color.len() == 7
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !is_hex_color {
+                errors.add(
+                    "color",
+                    "invalid_format",
+                    "The color must be a hex code like '#FF8800'!",
+                );
+            }
+        }
+
+        if let Some(emoji) = &self.emoji {
+            let length = emoji.chars().count();
+            if length == 0 {
+                errors.add("emoji", "required", "The emoji must not be empty!");
+            } else if length > MAX_EMOJI_LENGTH {
+                errors.add("emoji", "too_long", "Only a single emoji is allowed!");
+            } else if emoji
+                .chars()
+                .any(|c| c.is_alphanumeric() || c.is_whitespace())
+            {
+                errors.add(
+                    "emoji",
+                    "invalid_format",
+                    "The emoji must not contain text!",
+                );
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+// ----- Tally of the bluffs and challenges of a player -----
+
+/// Change of the bluff and challenge counters of a player.
+///
+/// Added to the counters of the `players` table while the game runs and copied into the results
+/// once it ended.
+///
+/// # Fields
+///
+/// - `bluffs`: Claims which weren't truthful.
+/// - `successful_bluffs`: Bluffs the next player didn't challenge.
+/// - `challenges`: Claims of other players that were challenged.
+/// - `successful_challenges`: Challenges which uncovered a bluff.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MoveTally {
+    /// Claims which weren't truthful.
+    pub bluffs: usize,
+    /// Bluffs the next player didn't challenge.
+    pub successful_bluffs: usize,
+    /// Claims of other players that were challenged.
+    pub challenges: usize,
+    /// Challenges which uncovered a bluff.
+    pub successful_challenges: usize,
+}
+
+impl MoveTally {
+    /// Tally of a claim, counting it as a bluff if it wasn't truthful.
+    pub fn claim(truthful: bool) -> Self {
+        MoveTally {
+            bluffs: usize::from(!truthful),
+            ..Default::default()
+        }
+    }
+
+    /// Tally of a bluff the next player played on instead of challenging it.
+    pub fn successful_bluff() -> Self {
+        MoveTally {
+            successful_bluffs: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Tally of a challenge, counting it as successful if it uncovered a bluff.
+    pub fn challenge(bluff_caught: bool) -> Self {
+        MoveTally {
+            challenges: 1,
+            successful_challenges: usize::from(bluff_caught),
+            ..Default::default()
+        }
+    }
+}
+
+// ----- DTO for creating a player entity -----
+
+/// Data Transfer Object (DTO) for a player who wants to join a game.
+///
+/// Only the name and the game are chosen by the client. The id, score and all timestamps are
+/// generated by the server.
+///
+/// # Fields
+///
+/// - `name`: The name of the new player.
+/// - `game_id`: The ID of the game the player wants to join.
+/// - `auto_suffix`: Whether a name that is taken in the game gets numbered instead of refused.
+#[derive(Deserialize, Serialize, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePlayerDTO {
+    /// The name of the new player.
+    pub name: String,
+
+    /// Whether a name that is taken in the game gets numbered, like `Alice 2`, instead of refused.
+    #[serde(alias = "auto_suffix", default)]
+    pub auto_suffix: bool,
+
+    /// The ID of the game the player wants to join.
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+
+    /// Optional profile of the new player.
+    #[serde(flatten)]
+    pub profile: PlayerProfile,
+}
+
+impl CreatePlayerDTO {
+    /// Builds a new `Player` instance from the DTO.
+    ///
+    /// # Returns
+    /// A new `Player` with a generated ID, a score of 0 and the current time as timestamps.
+    pub fn into_player(self) -> Player {
+        let mut player = Player::new(self.name, self.game_id);
+        player.profile = self.profile;
+        player
+    }
+}
+
+impl Display for CreatePlayerDTO {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CreatePlayerDTO Name: {}, Game ID: {}",
+            self.name, self.game_id
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for CreatePlayerDTO {}
+
+impl Redact for CreatePlayerDTO {}
+
+impl Validate for CreatePlayerDTO {
+    /// Checks the name and the game id of the new player.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = validate_player_name("name", &self.name, locale);
+
+        if self.game_id.is_empty() {
+            errors.add("game_id", "required", "The game id must not be empty!");
+        }
+
+        if let Err(profile_errors) = self.profile.validate(locale) {
+            errors.merge(profile_errors);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Checks a player name and collects all problems.
+///
+/// # Arguments
+///
+/// - `field` -> Name of the field the name was provided in
+/// - `name` -> The name to check
+/// - `locale` -> Language of the messages of the problems
+///
+/// # Returns
+///
+/// A `ValidationErrors` instance which is empty if the name is valid.
+pub fn validate_player_name(field: &str, name: &str, locale: &Locale) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+
+    if name.trim().is_empty() {
+        errors.add(field, "required", &Message::NameEmpty.text(locale));
+    } else if name.chars().count() > MAX_PLAYER_NAME_LENGTH {
+        errors.add(
+            field,
+            "too_long",
+            &Message::NameTooLong.format(locale, &[("max", &MAX_PLAYER_NAME_LENGTH.to_string())]),
+        );
+    }
+
+    errors
+}
+
+/// Makes sure that no other player of a game uses a name, ignoring case.
+///
+/// A taken name is refused, or with `auto_suffix` numbered like `Alice 2`, cut short to stay
+/// within `MAX_PLAYER_NAME_LENGTH`.
+///
+/// # Arguments
+///
+/// - `field` -> Name of the field the name was provided in
+/// - `name` -> The requested name
+/// - `taken_names` -> Names of the other players of the game
+/// - `auto_suffix` -> Whether a taken name is numbered instead of refused
+/// - `locale` -> Language of the message if the name is refused
+///
+/// # Errors
+///
+/// Returns `ValidationErrors` with the code `name_taken` if the name can't be used.
+pub fn resolve_player_name(
+    field: &str,
+    name: &str,
+    taken_names: &[String],
+    auto_suffix: bool,
+    locale: &Locale,
+) -> Result<String, ValidationErrors> {
+    let is_taken = |candidate: &str| {
+        let candidate = candidate.trim().to_lowercase();
+        taken_names
+            .iter()
+            .any(|taken| taken.trim().to_lowercase() == candidate)
+    };
+
+    if !is_taken(name) {
+        return Ok(name.to_string());
+    }
+
+    if auto_suffix {
+        // a game has fewer other players than there are numbers, so one of them is free
+        for number in 2..=MAX_PLAYERS + 1 {
+            let suffix = format!(" {number}");
+            let base: String = name
+                .trim()
+                .chars()
+                .take(MAX_PLAYER_NAME_LENGTH - suffix.len())
+                .collect();
+            let candidate = format!("{base}{suffix}");
+            if !is_taken(&candidate) {
+                return Ok(candidate);
+            }
+        }
+    }
+
+    let mut errors = ValidationErrors::new();
+    errors.add(field, "name_taken", &Message::NameTaken.text(locale));
+    Err(errors)
+}
+
+/// Returns the key identifying a player across games.
+///
+/// The fingerprint of the client, or the name for players who joined without one. Must match
+/// `COALESCE(fingerprint, 'name:' || player_name)` in the queries over `game_results`.
+///
+/// # Arguments
+///
+/// - `fingerprint` -> Fingerprint of the client of the player
+/// - `name` -> Name of the player
+pub fn player_identity(fingerprint: Option<&str>, name: &str) -> String {
+    match fingerprint {
+        Some(fingerprint) => fingerprint.to_string(),
+        None => format!("name:{name}"),
+    }
+}
+
 // ----- DTO for updating a player entity -----
 
 /// Data Transfer Object (DTO) for updating a player's information.
@@ -110,7 +592,9 @@ impl<'a> ErrorObject<'a> for Player {}
 /// - `name`: An optional new name for the player.
 /// - `score`: An optional new score for the player.
 /// - `assigned_cards`: An optional list of new cards assigned to the player.
-#[derive(Deserialize, Debug)]
+/// - `hand_sort`: An optional new order of the hand of the player.
+#[derive(Deserialize, Serialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdatePlayerDTO {
     /// The unique identifier of the player to be updated.
     pub id: String,
@@ -122,10 +606,20 @@ pub struct UpdatePlayerDTO {
     pub score: Option<usize>,
 
     /// The new game ID for the player.
+    #[serde(alias = "assigned_cards")]
     pub assigned_cards: Option<Vec<Card>>,
 
     /// The last time when the client requested a status update
-    pub last_time_update_requested: Option<String>,
+    #[serde(alias = "last_time_update_requested")]
+    pub last_time_update_requested: Option<DateTime<Utc>>,
+
+    /// New profile fields; only the provided fields are changed.
+    #[serde(flatten)]
+    pub profile: PlayerProfile,
+
+    /// The new order in which the hand of the player is sorted.
+    #[serde(alias = "hand_sort", default)]
+    pub hand_sort: Option<HandSort>,
 }
 
 impl UpdatePlayerDTO {
@@ -136,6 +630,7 @@ impl UpdatePlayerDTO {
     /// - `name`: An optional new name for the player.
     /// - `score`: An optional new score for the player.
     /// - `assigned_cards`: An optional list of new cards assigned to the player.
+    /// - `profile`: New profile fields of the player.
     ///
     /// # Returns
     /// A new `UpdatePlayerDTO` instance with the provided player ID and default values for other fields.
@@ -144,7 +639,8 @@ impl UpdatePlayerDTO {
         name: Option<String>,
         score: Option<usize>,
         assigned_cards: Option<Vec<Card>>,
-        last_time_update_requested: Option<String>,
+        last_time_update_requested: Option<DateTime<Utc>>,
+        profile: PlayerProfile,
     ) -> Self {
         UpdatePlayerDTO {
             id,
@@ -152,6 +648,8 @@ impl UpdatePlayerDTO {
             score,
             assigned_cards,
             last_time_update_requested,
+            profile,
+            hand_sort: None,
         }
     }
 }
@@ -175,13 +673,59 @@ impl Display for UpdatePlayerDTO {
 
 impl<'a> ErrorObject<'a> for UpdatePlayerDTO {}
 
+impl Validate for UpdatePlayerDTO {
+    /// Checks the id and all provided fields of the DTO.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.id.is_empty() {
+            errors.add("id", "required", "The player id must not be empty!");
+        }
+
+        if let Some(name) = &self.name {
+            errors.merge(validate_player_name("name", name, locale));
+        }
+
+        if let Err(profile_errors) = self.profile.validate(locale) {
+            errors.merge(profile_errors);
+        }
+
+        if self.name.is_none()
+            && self.score.is_none()
+            && self.assigned_cards.is_none()
+            && self.last_time_update_requested.is_none()
+            && self.profile.is_empty()
+            && self.hand_sort.is_none()
+        {
+            errors.add("", "no_changes", "No new data was provided!");
+        }
+
+        errors.into_result()
+    }
+}
+
+impl ActingPlayer for UpdatePlayerDTO {
+    fn acting_player_id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Redact for UpdatePlayerDTO {
+    /// Removes the new hand of the player.
+    fn redact(&mut self) {
+        self.assigned_cards = None;
+    }
+}
+
 // ----- Implementation of 'IntoResponse' trait for 'Player' -----
 impl IntoResponse for Player {
     /// Converts the `Player` instance into a response.
     ///
     /// # Returns
     /// A `Response` containing the serialized `Player` instance.
+    ///
+    /// The hand of the player is included, so it must only be sent to the player themself.
     fn into_response(self) -> Response {
-        (StatusCode::OK, self).into_response()
+        (StatusCode::OK, Json(self)).into_response()
     }
 }
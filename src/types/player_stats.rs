@@ -0,0 +1,131 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::errors::application_error::{ErrorObject, Redact};
+
+/// Lifetime statistics of a player across all finished games.
+///
+/// Keyed by the identity of the player, so the statistics follow a player from game to game.
+/// The counters are read from the `player_stats` table, the rates are derived from them.
+///
+/// # Props
+///
+/// - `identity` -> Fingerprint or name the statistics are keyed by; never sent to a client
+/// - `player_name` -> Name the player used in their latest game
+/// - `games_played` -> Finished games of the player
+/// - `wins` -> Games the player won
+/// - `bluffs` -> Claims of the player which weren't truthful
+/// - `successful_bluffs` -> Bluffs of the player nobody challenged
+/// - `challenges` -> Claims the player challenged
+/// - `successful_challenges` -> Challenges of the player which uncovered a bluff
+/// - `win_rate` -> Share of the games the player won, between 0 and 1
+/// - `average_bluffs` -> Bluffs per finished game
+/// - `challenge_accuracy` -> Share of the challenges which uncovered a bluff; `None` without
+///   challenges
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStats {
+    /// Fingerprint or name the statistics are keyed by
+    #[serde(default, skip_serializing)]
+    pub identity: String,
+    /// Name the player used in their latest game
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+    /// Finished games of the player
+    #[serde(alias = "games_played")]
+    pub games_played: usize,
+    /// Games the player won
+    pub wins: usize,
+    /// Claims of the player which weren't truthful
+    pub bluffs: usize,
+    /// Bluffs of the player nobody challenged
+    #[serde(alias = "successful_bluffs")]
+    pub successful_bluffs: usize,
+    /// Claims the player challenged
+    pub challenges: usize,
+    /// Challenges of the player which uncovered a bluff
+    #[serde(alias = "successful_challenges")]
+    pub successful_challenges: usize,
+    /// Share of the games the player won
+    #[serde(default)]
+    pub win_rate: f64,
+    /// Bluffs per finished game
+    #[serde(default)]
+    pub average_bluffs: f64,
+    /// Share of the challenges which uncovered a bluff
+    #[serde(default)]
+    pub challenge_accuracy: Option<f64>,
+}
+
+impl PlayerStats {
+    /// Creates the statistics of a player who didn't finish a game yet.
+    ///
+    /// # Arguments
+    ///
+    /// - `identity` -> Identity of the player
+    /// - `player_name` -> Current name of the player
+    pub fn empty(identity: String, player_name: String) -> Self {
+        PlayerStats {
+            identity,
+            player_name,
+            games_played: 0,
+            wins: 0,
+            bluffs: 0,
+            successful_bluffs: 0,
+            challenges: 0,
+            successful_challenges: 0,
+            win_rate: 0.0,
+            average_bluffs: 0.0,
+            challenge_accuracy: None,
+        }
+    }
+
+    /// Derives the rates from the counters.
+    ///
+    /// # Returns
+    ///
+    /// The statistics including the win rate, the average bluffs and the challenge accuracy.
+    pub fn with_rates(mut self) -> Self {
+        if self.games_played > 0 {
+            self.win_rate = self.wins as f64 / self.games_played as f64;
+            self.average_bluffs = self.bluffs as f64 / self.games_played as f64;
+        }
+        self.challenge_accuracy = match self.challenges {
+            0 => None,
+            challenges => Some(self.successful_challenges as f64 / challenges as f64),
+        };
+
+        self
+    }
+}
+
+impl fmt::Display for PlayerStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} wins in {} games",
+            self.player_name, self.wins, self.games_played
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for PlayerStats {}
+
+impl Redact for PlayerStats {
+    /// Removes the identity, which may be the fingerprint of a client.
+    fn redact(&mut self) {
+        self.identity.clear();
+    }
+}
+
+impl IntoResponse for PlayerStats {
+    /// Converts the `PlayerStats` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
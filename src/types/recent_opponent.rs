@@ -0,0 +1,82 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::application_error::{ErrorObject, Redact},
+    utils::d1_conversions::datetime_from_text,
+};
+
+/// A player who finished games together with another player.
+///
+/// Opponents are grouped by their identity, which is never sent to a client. If the opponent
+/// currently sits in a game that hasn't ended, its player id can be used to invite them.
+///
+/// # Props
+///
+/// - `player_name` -> Name the opponent used in the latest shared game
+/// - `games_together` -> Finished games both players took part in
+/// - `last_game_id` -> Latest shared game
+/// - `last_played_at` -> Point in time the latest shared game ended
+/// - `active_player_id` -> Player of the opponent in a running game; `None` if they aren't playing
+/// - `active_game_id` -> The running game of the opponent
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentOpponent {
+    /// Name the opponent used in the latest shared game
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+    /// Finished games both players took part in
+    #[serde(alias = "games_together")]
+    pub games_together: usize,
+    /// Latest shared game
+    #[serde(alias = "last_game_id")]
+    pub last_game_id: String,
+    /// Point in time the latest shared game ended
+    #[serde(alias = "last_played_at", deserialize_with = "datetime_from_text")]
+    pub last_played_at: DateTime<Utc>,
+    /// Player of the opponent in a running game
+    #[serde(alias = "active_player_id", default)]
+    pub active_player_id: Option<String>,
+    /// The running game of the opponent
+    #[serde(alias = "active_game_id", default)]
+    pub active_game_id: Option<String>,
+}
+
+impl fmt::Display for RecentOpponent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Opponent: {}, Games together: {}",
+            self.player_name, self.games_together
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for RecentOpponent {}
+
+impl Redact for RecentOpponent {}
+
+/// The players a player recently played with, latest shared game first.
+///
+/// # Props
+///
+/// - `opponents` -> The recent opponents
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentOpponentList {
+    /// The recent opponents
+    pub opponents: Vec<RecentOpponent>,
+}
+
+impl IntoResponse for RecentOpponentList {
+    /// Converts the `RecentOpponentList` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{game_event::StoredGameEvent, game_view::GameView};
+
+/// Body of a resync request sent by a client that lost its connection, e.g. after a refreshed tab.
+///
+/// # Props
+///
+/// - `last_sequence` -> Sequence number of the last event the client received; `0` if it doesn't
+///   know any
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResyncRequest {
+    /// Sequence number of the last event the client received
+    #[serde(alias = "last_sequence", default)]
+    pub last_sequence: usize,
+}
+
+/// Body of a rejoin request sent by a client that lost its session token, e.g. after a refreshed
+/// tab.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the player the client joined as
+/// - `rejoin_token` -> Token the client got when the player joined
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RejoinRequest {
+    /// Id of the player the client joined as
+    #[serde(alias = "player_id")]
+    pub player_id: String,
+    /// Token the client got when the player joined
+    #[serde(alias = "rejoin_token")]
+    pub rejoin_token: String,
+}
+
+/// Answer of a resync request.
+///
+/// A client that missed only a few events replays them; otherwise it replaces its state with a
+/// fresh view. Both variants carry the sequence number the event stream continues after.
+///
+/// # Example usage:
+/// ```rust
+/// use your_crate::resync::ResyncResponse;
+/// let response = ResyncResponse::Events { events: vec![], latest_sequence: 42 };
+/// ```
+#[derive(Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ResyncResponse {
+    /// Events published since the last one the client received, oldest first.
+    #[serde(rename_all = "camelCase")]
+    Events {
+        /// Missed events of the game
+        events: Vec<StoredGameEvent>,
+        /// Sequence number to pass as `after` to the event stream
+        latest_sequence: usize,
+    },
+    /// Current state of the game, since too many events were missed.
+    #[serde(rename_all = "camelCase")]
+    Snapshot {
+        /// The game from the perspective of the player
+        game: Box<GameView>,
+        /// Sequence number to pass as `after` to the event stream
+        latest_sequence: usize,
+    },
+}
+
+impl IntoResponse for ResyncResponse {
+    /// Converts the `ResyncResponse` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,138 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums::card_types::CardType,
+    logic::rules::ChallengeOutcome,
+    types::{card::Card, claim::Claim, game::Game},
+};
+
+/// Summary of a round that ended with a challenge.
+///
+/// Published as a `roundSummary` event and kept in the event log, so it can be read again later.
+///
+/// # Props
+///
+/// - `game_id` -> Id of the game
+/// - `round_number` -> The round that ended
+/// - `card_to_play` -> Card type of the round
+/// - `claimant_id` -> Id of the player whose claim was challenged
+/// - `challenger_id` -> Id of the player who challenged the claim
+/// - `bluffer_id` -> Id of the claimant if the challenged claim was a lie
+/// - `revealed_cards` -> Cards of the challenged claim
+/// - `loser_id` -> Id of the player who picked up the stack
+/// - `winner_id` -> Id of the player who starts the next round
+/// - `score_deltas` -> Change of the hand of every player over the round
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundSummary {
+    /// Id of the game
+    pub game_id: String,
+    /// The round that ended
+    pub round_number: usize,
+    /// Card type of the round
+    pub card_to_play: CardType,
+    /// Id of the player whose claim was challenged
+    pub claimant_id: String,
+    /// Id of the player who challenged the claim
+    pub challenger_id: String,
+    /// Id of the claimant if the challenged claim was a lie; `None` if it was truthful
+    pub bluffer_id: Option<String>,
+    /// Cards of the challenged claim, turned face-up by the challenge
+    pub revealed_cards: Vec<Card>,
+    /// Id of the player who picked up the stack
+    pub loser_id: String,
+    /// Id of the player who starts the next round
+    pub winner_id: String,
+    /// Change of the hand of every player over the round, in the order of the player list
+    pub score_deltas: Vec<ScoreDelta>,
+}
+
+/// Change of the hand of a player over a round.
+///
+/// Fewer cards are better, so a negative delta means the player got closer to winning.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the player
+/// - `cards` -> Cards gained, negative for cards placed
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreDelta {
+    /// Id of the player
+    pub player_id: String,
+    /// Cards gained over the round; negative if the player placed more than they picked up
+    pub cards: i64,
+}
+
+impl RoundSummary {
+    /// Summarizes a round right after its last claim was challenged.
+    ///
+    /// # Arguments
+    ///
+    /// - `game` -> The game with the claims of the round, before the next round is prepared
+    /// - `challenged` -> The revealed claim
+    /// - `challenger_id` -> Id of the player who challenged the claim
+    /// - `outcome` -> Outcome of the challenge
+    pub fn new(
+        game: &Game,
+        challenged: &Claim,
+        challenger_id: &str,
+        outcome: &ChallengeOutcome,
+    ) -> Self {
+        let claims: Vec<&Claim> = game
+            .claims
+            .iter()
+            .filter(|claim| claim.round_number == game.round_number)
+            .collect();
+        let stack_size: usize = claims.iter().map(|claim| claim.number_of_cards).sum();
+
+        let score_deltas = game
+            .players
+            .iter()
+            .map(|player| {
+                let placed: usize = claims
+                    .iter()
+                    .filter(|claim| claim.created_by == player.id)
+                    .map(|claim| claim.number_of_cards)
+                    .sum();
+                let picked_up = match player.id == outcome.loser_id {
+                    true => stack_size,
+                    false => 0,
+                };
+
+                ScoreDelta {
+                    player_id: player.id.clone(),
+                    cards: picked_up as i64 - placed as i64,
+                }
+            })
+            .collect();
+
+        RoundSummary {
+            game_id: game.id.clone(),
+            round_number: game.round_number,
+            card_to_play: game.card_to_play.clone(),
+            claimant_id: challenged.created_by.clone(),
+            challenger_id: challenger_id.to_string(),
+            bluffer_id: match outcome.truthful {
+                true => None,
+                false => Some(challenged.created_by.clone()),
+            },
+            revealed_cards: challenged.cards.clone(),
+            loser_id: outcome.loser_id.clone(),
+            winner_id: outcome.winner_id.clone(),
+            score_deltas,
+        }
+    }
+}
+
+impl IntoResponse for RoundSummary {
+    /// Converts the `RoundSummary` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,183 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::application_error::{ErrorObject, Redact},
+    types::game::Game,
+};
+
+/// Score and hand of a player after a round, as it is stored in the `score_history` table.
+///
+/// # Props
+///
+/// - `game_id` -> Id of the game
+/// - `round_number` -> The round that ended
+/// - `player_id` -> Id of the player
+/// - `player_name` -> Name of the player, kept for players who left before the game ended
+/// - `score` -> Score of the player after the round
+/// - `card_count` -> Cards in the hand of the player after the round
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreSnapshot {
+    /// Id of the game
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// The round that ended
+    #[serde(alias = "round_number")]
+    pub round_number: usize,
+    /// Id of the player
+    #[serde(alias = "player_id")]
+    pub player_id: String,
+    /// Name of the player, kept for players who left before the game ended
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+    /// Score of the player after the round
+    pub score: usize,
+    /// Cards in the hand of the player after the round
+    #[serde(alias = "card_count")]
+    pub card_count: usize,
+}
+
+impl ScoreSnapshot {
+    /// Takes a snapshot of every player of a game after a round.
+    ///
+    /// # Arguments
+    ///
+    /// - `game` -> Fully hydrated game right after the round ended
+    /// - `round_number` -> The round that ended
+    pub fn for_round(game: &Game, round_number: usize) -> Vec<ScoreSnapshot> {
+        game.seating_order()
+            .into_iter()
+            .map(|player| ScoreSnapshot {
+                game_id: game.id.clone(),
+                round_number,
+                player_id: player.id.clone(),
+                player_name: player.name.clone(),
+                score: player.score,
+                card_count: player.assigned_cards.len(),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ScoreSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Game: {}, Round: {}, Player: {}, Score: {}, Cards: {}",
+            self.game_id, self.round_number, self.player_id, self.score, self.card_count
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for ScoreSnapshot {}
+
+impl Redact for ScoreSnapshot {}
+
+/// Score and hand of a player after one round, a single point of a `ScoreSeries`.
+///
+/// # Props
+///
+/// - `round_number` -> The round that ended
+/// - `score` -> Score of the player after the round
+/// - `card_count` -> Cards in the hand of the player after the round
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScorePoint {
+    /// The round that ended
+    pub round_number: usize,
+    /// Score of the player after the round
+    pub score: usize,
+    /// Cards in the hand of the player after the round
+    pub card_count: usize,
+}
+
+/// Progression of a single player over the rounds of a game.
+///
+/// # Props
+///
+/// - `player_id` -> Id of the player
+/// - `player_name` -> Name of the player
+/// - `points` -> Score and hand after every round the player took part in, oldest first
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreSeries {
+    /// Id of the player
+    pub player_id: String,
+    /// Name of the player
+    pub player_name: String,
+    /// Score and hand after every round the player took part in, oldest first
+    pub points: Vec<ScorePoint>,
+}
+
+/// Score progression of all players of a game, ready to be plotted by the end screen.
+///
+/// # Props
+///
+/// - `game_id` -> Id of the game
+/// - `rounds` -> Number of rounds with a snapshot
+/// - `players` -> One series per player, in the order they were first recorded
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreHistory {
+    /// Id of the game
+    pub game_id: String,
+    /// Number of rounds with a snapshot
+    pub rounds: usize,
+    /// One series per player, in the order they were first recorded
+    pub players: Vec<ScoreSeries>,
+}
+
+impl ScoreHistory {
+    /// Groups the snapshots of a game by player.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Id of the game
+    /// - `snapshots` -> All snapshots of the game, ordered by round
+    pub fn new(game_id: String, snapshots: Vec<ScoreSnapshot>) -> Self {
+        let rounds = snapshots
+            .iter()
+            .map(|snapshot| snapshot.round_number)
+            .max()
+            .unwrap_or_default();
+
+        let mut players: Vec<ScoreSeries> = vec![];
+        for snapshot in snapshots {
+            let point = ScorePoint {
+                round_number: snapshot.round_number,
+                score: snapshot.score,
+                card_count: snapshot.card_count,
+            };
+            match players
+                .iter_mut()
+                .find(|series| series.player_id == snapshot.player_id)
+            {
+                Some(series) => series.points.push(point),
+                None => players.push(ScoreSeries {
+                    player_id: snapshot.player_id,
+                    player_name: snapshot.player_name,
+                    points: vec![point],
+                }),
+            }
+        }
+
+        ScoreHistory {
+            game_id,
+            rounds,
+            players,
+        }
+    }
+}
+
+impl IntoResponse for ScoreHistory {
+    /// Converts the `ScoreHistory` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
@@ -0,0 +1,169 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::{
+        application_error::{ErrorObject, Redact},
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::locale::Locale,
+    utils::d1_conversions::{datetime_from_text, optional_datetime_from_text},
+};
+
+// constants
+/// Maximum number of characters of the name of a season.
+pub const MAX_SEASON_NAME_LENGTH: usize = 40;
+
+/// A span of time with its own leaderboard.
+///
+/// Exactly one season is current, it has no end until an operator rolls it over. Past seasons
+/// keep their leaderboards.
+///
+/// # Props
+///
+/// - `number` -> Position of the season starting at 1
+/// - `name` -> Name shown to the players
+/// - `starts_at` -> Point in time the season started
+/// - `ends_at` -> Point in time the season ended; `None` for the current season
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Season {
+    /// Position of the season starting at 1
+    pub number: usize,
+    /// Name shown to the players
+    pub name: String,
+    /// Point in time the season started
+    #[serde(alias = "starts_at", deserialize_with = "datetime_from_text")]
+    pub starts_at: DateTime<Utc>,
+    /// Point in time the season ended
+    #[serde(
+        alias = "ends_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl Season {
+    /// Returns the bounds of the season.
+    ///
+    /// # Returns
+    ///
+    /// The inclusive start and the exclusive end; no end for the current season.
+    pub fn bounds(&self) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        (Some(self.starts_at), self.ends_at)
+    }
+}
+
+impl IntoResponse for Season {
+    /// Converts the `Season` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl fmt::Display for Season {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Season {}: {}", self.number, self.name)
+    }
+}
+
+impl<'a> ErrorObject<'a> for Season {}
+
+impl Redact for Season {}
+
+/// All seasons, newest first.
+///
+/// # Props
+///
+/// - `current_season` -> Number of the current season
+/// - `seasons` -> The seasons
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SeasonList {
+    /// Number of the current season
+    pub current_season: usize,
+    /// The seasons
+    pub seasons: Vec<Season>,
+}
+
+impl IntoResponse for SeasonList {
+    /// Converts the `SeasonList` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// DTO type for an operator ending the current season and starting the next one.
+///
+/// # Props
+///
+/// - `name` -> Name of the next season; `Season <number>` if `None`
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RollOverSeasonDTO {
+    /// Name of the next season
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl RollOverSeasonDTO {
+    /// Creates the season following the current one, starting at the passed point in time.
+    ///
+    /// # Arguments
+    ///
+    /// - `current` -> The current season
+    /// - `at` -> Point in time the current season ends and the next one starts
+    pub fn into_next_season(self, current: &Season, at: DateTime<Utc>) -> Season {
+        let number = current.number + 1;
+
+        Season {
+            number,
+            name: self
+                .name
+                .map(|name| name.trim().to_string())
+                .unwrap_or_else(|| format!("Season {number}")),
+            starts_at: at,
+            ends_at: None,
+        }
+    }
+}
+
+impl fmt::Display for RollOverSeasonDTO {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Season Name: {:?}", self.name)
+    }
+}
+
+impl<'a> ErrorObject<'a> for RollOverSeasonDTO {}
+
+impl Redact for RollOverSeasonDTO {}
+
+impl Validate for RollOverSeasonDTO {
+    /// Checks the name of the next season.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(name) = &self.name {
+            if name.trim().is_empty() {
+                errors.add("name", "required", "The name must not be empty!");
+            } else if name.chars().count() > MAX_SEASON_NAME_LENGTH {
+                errors.add(
+                    "name",
+                    "too_long",
+                    &format!(
+                        "The name must not be longer than {MAX_SEASON_NAME_LENGTH} characters!"
+                    ),
+                );
+            }
+        }
+
+        errors.into_result()
+    }
+}
@@ -0,0 +1,49 @@
+use serde::Deserialize;
+
+use crate::errors::validation_errors::{Validate, ValidationErrors};
+use crate::i18n::locale::Locale;
+
+/// DTO of the host rearranging the seats of a game before it starts.
+///
+/// # Props
+///
+/// - `player_ids` -> Ids of all players of the game, from the first seat to the last one
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SeatOrderDTO {
+    /// Ids of all players of the game, from the first seat to the last one
+    #[serde(alias = "player_ids")]
+    pub player_ids: Vec<String>,
+}
+
+impl Validate for SeatOrderDTO {
+    /// Checks that every player is seated only once.
+    ///
+    /// Whether the ids are exactly the players of the game is checked against the game itself.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.player_ids.is_empty() {
+            errors.add(
+                "player_ids",
+                "required",
+                "The new order of the seats is missing!",
+            );
+        }
+
+        let has_duplicates = self
+            .player_ids
+            .iter()
+            .enumerate()
+            .any(|(index, id)| self.player_ids[..index].contains(id));
+        if has_duplicates {
+            errors.add(
+                "player_ids",
+                "duplicate",
+                "A player can only take one seat!",
+            );
+        }
+
+        errors.into_result()
+    }
+}
@@ -0,0 +1,109 @@
+use axum::{
+    http::{header::CACHE_CONTROL, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{
+    enums::{game_state::GameState, visibility::Visibility},
+    types::game::Game,
+};
+
+// constants
+/// Seconds a share payload may be reused by this isolate, the edge and the unfurling clients.
+pub const SHARE_CACHE_SECONDS: i64 = 60;
+
+/// Name of the site shown on a link preview.
+const SITE_NAME: &str = "Lue Lue";
+
+/// Open Graph metadata of a game, rendered by chat apps when a link to the game is shared.
+///
+/// # Props
+///
+/// - `title` -> Headline of the preview, `og:title`
+/// - `description` -> Line below the headline, `og:description`
+/// - `url` -> Deep link into the frontend, `og:url`; `None` if no frontend is configured
+/// - `site_name` -> Name of the site, `og:site_name`
+/// - `player_count` -> Number of players who joined
+/// - `max_players` -> Maximum number of players
+/// - `state` -> Current state of the game
+/// - `join_code` -> Code to join the game; only for public games
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SharePayload {
+    /// Headline of the preview
+    pub title: String,
+    /// Line below the headline
+    pub description: String,
+    /// Deep link into the frontend
+    pub url: Option<String>,
+    /// Name of the site
+    pub site_name: &'static str,
+    /// Number of players who joined
+    pub player_count: usize,
+    /// Maximum number of players
+    pub max_players: usize,
+    /// Current state of the game
+    pub state: GameState,
+    /// Code to join the game; only for public games
+    pub join_code: Option<String>,
+}
+
+impl SharePayload {
+    /// Creates the preview of a game.
+    ///
+    /// # Arguments
+    ///
+    /// - `game` -> The game including its players
+    /// - `frontend_url` -> Base URL of the frontend the deep link points to
+    pub fn for_game(game: &Game, frontend_url: Option<&str>) -> Self {
+        let host_name = game
+            .players
+            .iter()
+            .find(|player| game.is_host(&player.id))
+            .map(|host| host.name.as_str());
+        let join_code = match game.settings.visibility {
+            Visibility::Public => game.join_code.clone(),
+            Visibility::Private => None,
+        };
+        let url = frontend_url.map(|frontend_url| match &join_code {
+            Some(join_code) => format!("{frontend_url}/join/{join_code}"),
+            None => format!("{frontend_url}/game/{}", game.id),
+        });
+
+        SharePayload {
+            title: match host_name {
+                Some(host_name) => format!("{host_name}'s game of {SITE_NAME}"),
+                None => format!("A game of {SITE_NAME}"),
+            },
+            description: format!(
+                "{} of {} players · {}",
+                game.players.len(),
+                game.settings.max_players,
+                game.state.as_str()
+            ),
+            url,
+            site_name: SITE_NAME,
+            player_count: game.players.len(),
+            max_players: game.settings.max_players,
+            state: game.state.clone(),
+            join_code,
+        }
+    }
+}
+
+impl IntoResponse for SharePayload {
+    /// Converts the `SharePayload` into a response with status code 200 the edge may cache.
+    fn into_response(self) -> Response {
+        (
+            StatusCode::OK,
+            [(
+                CACHE_CONTROL,
+                format!("public, max-age={SHARE_CACHE_SECONDS}"),
+            )],
+            Json(self),
+        )
+            .into_response()
+    }
+}
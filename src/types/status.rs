@@ -1,8 +1,13 @@
 use axum::response::IntoResponse;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::types::{game::Game, player::Player};
+use crate::{
+    middleware::authorization::ActingPlayer,
+    types::{game_view::GameView, player::Player},
+    utils::time::server_time,
+};
 
 /// A simple request sent by a user for a status update.
 ///
@@ -14,10 +19,13 @@ use crate::types::{game::Game, player::Player};
 /// - player_id: The ID of the player requesting the status update.
 /// - game_id: The ID of the game for which the status update is requested.
 #[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StatusUpdateRequest {
     /// The ID of the player requesting the status update.
+    #[serde(alias = "player_id")]
     pub player_id: String,
     /// The ID of the game for which the status update is requested.
+    #[serde(alias = "game_id")]
     pub game_id: String,
 }
 
@@ -30,14 +38,18 @@ pub struct StatusUpdateRequest {
 /// - player_data: Optional player data that has been updated or changed.
 /// - player_execluded_from_game: Indicates whether the player has been execluded from the game
 ///   session.
-#[derive(Deserialize, Serialize)]
+/// - server_time: Time of the server when the answer was created, also sent without game data.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StatusUpdate {
-    /// The game data that has been updated or changed.
-    pub game_data: Option<Game>,
+    /// The game data that has been updated or changed, seen from the perspective of the player.
+    pub game_data: Option<GameView>,
     /// The player data that has been updated or changed.
     pub player_data: Option<Player>,
     /// Indicates whether the player has been execluded from the game session.
     pub player_execluded_from_game: bool,
+    /// Time of the server when the answer was created.
+    pub server_time: DateTime<Utc>,
 }
 
 // ----- Implementation 'StatusUpdateRequest' -----
@@ -56,6 +68,12 @@ impl StatusUpdateRequest {
     }
 }
 
+impl ActingPlayer for StatusUpdateRequest {
+    fn acting_player_id(&self) -> &str {
+        &self.player_id
+    }
+}
+
 impl Default for StatusUpdateRequest {
     /// Creates a default `StatusUpdateRequest` instance with empty player ID and game ID.
     ///
@@ -79,7 +97,7 @@ impl StatusUpdate {
     /// player execlusion status.
     ///
     /// # Arguments
-    /// - `game_data`: An optional `Game` instance representing the updated game data.
+    /// - `game_data`: An optional `GameView` instance representing the updated game data.
     /// - `player_data`: An optional `Player` instance representing the updated player data.
     /// - `player_execluded_from_game`: A boolean indicating whether the player has been execluded
     ///   from the game session.
@@ -87,7 +105,7 @@ impl StatusUpdate {
     /// # Returns
     /// A new `StatusUpdate` instance.
     pub fn new(
-        game_data: Option<Game>,
+        game_data: Option<GameView>,
         player_data: Option<Player>,
         player_execluded_from_game: bool,
     ) -> Self {
@@ -95,6 +113,7 @@ impl StatusUpdate {
             game_data,
             player_data,
             player_execluded_from_game,
+            server_time: server_time(),
         }
     }
 }
@@ -110,6 +129,7 @@ impl Default for StatusUpdate {
             game_data: None,
             player_data: None,
             player_execluded_from_game: false,
+            server_time: server_time(),
         }
     }
 }
@@ -120,6 +140,44 @@ impl IntoResponse for StatusUpdate {
     /// # Returns
     /// An HTTP response containing the serialized `StatusUpdate` data.
     fn into_response(self) -> axum::response::Response {
-        axum::Json(self).into_response()
+        (axum::http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+/// Current time of the server, requested by clients to compute the offset of their clock.
+///
+/// # Properties
+/// - server_time: Time of the server when the answer was created.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerTime {
+    /// Time of the server when the answer was created.
+    pub server_time: DateTime<Utc>,
+}
+
+impl ServerTime {
+    /// Reads the current time of the server.
+    ///
+    /// # Returns
+    /// A new `ServerTime` instance.
+    pub fn now() -> Self {
+        ServerTime {
+            server_time: server_time(),
+        }
+    }
+}
+
+impl IntoResponse for ServerTime {
+    /// Converts the `ServerTime` instance into an HTTP response that is never cached.
+    ///
+    /// # Returns
+    /// An HTTP response containing the serialized `ServerTime` data.
+    fn into_response(self) -> axum::response::Response {
+        (
+            axum::http::StatusCode::OK,
+            [(axum::http::header::CACHE_CONTROL, "no-store")],
+            axum::Json(self),
+        )
+            .into_response()
     }
 }
@@ -0,0 +1,624 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    enums::tournament_state::TournamentState,
+    errors::{
+        application_error::{ErrorObject, Redact},
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::locale::Locale,
+    types::{game::MAX_PLAYERS, game_settings::MIN_PLAYERS, player::validate_player_name},
+    utils::{
+        d1_conversions::{datetime_from_text, enum_from_column, optional_datetime_from_text},
+        time::server_time,
+    },
+};
+
+// constants
+/// Maximum number of characters of the name of a tournament.
+pub const MAX_TOURNAMENT_NAME_LENGTH: usize = 60;
+
+/// Most entrants a tournament can be created for.
+pub const MAX_TOURNAMENT_ENTRANTS: usize = 128;
+
+/// Players per game if the operator doesn't choose a number.
+const DEFAULT_PLAYERS_PER_GAME: usize = 4;
+
+/// Entrants of a tournament if the operator doesn't choose a number.
+const DEFAULT_MAX_ENTRANTS: usize = 32;
+
+/// A bracket of games whose winners advance into new games until one player is left.
+///
+/// The entrants of a round are spread over games of at most `players_per_game` players, the
+/// winner of each game advances into the next round.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the tournament
+/// - `name` -> Name shown to the players
+/// - `state` -> Current phase of the tournament
+/// - `players_per_game` -> Most players of one game
+/// - `max_entrants` -> Most players who can register
+/// - `current_round` -> Round whose games are played, starting at 1; `0` before the start
+/// - `winner_entrant_id` -> Entrant who won the final game; never sent to a client
+/// - `created_at` -> Point in time the tournament was created
+/// - `started_at` -> Point in time the first round started
+/// - `finished_at` -> Point in time the tournament was won or called off
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Tournament {
+    /// Identifier of the tournament
+    pub id: String,
+    /// Name shown to the players
+    pub name: String,
+    /// Current phase of the tournament
+    #[serde(deserialize_with = "enum_from_column")]
+    pub state: TournamentState,
+    /// Most players of one game
+    #[serde(alias = "players_per_game")]
+    pub players_per_game: usize,
+    /// Most players who can register
+    #[serde(alias = "max_entrants")]
+    pub max_entrants: usize,
+    /// Round whose games are played
+    #[serde(alias = "current_round", default)]
+    pub current_round: usize,
+    /// Entrant who won the final game
+    #[serde(alias = "winner_entrant_id", default, skip_serializing)]
+    pub winner_entrant_id: Option<String>,
+    /// Point in time the tournament was created
+    #[serde(alias = "created_at", deserialize_with = "datetime_from_text")]
+    pub created_at: DateTime<Utc>,
+    /// Point in time the first round started
+    #[serde(
+        alias = "started_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Point in time the tournament was won or called off
+    #[serde(
+        alias = "finished_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl IntoResponse for Tournament {
+    /// Converts the `Tournament` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl fmt::Display for Tournament {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Tournament ID: {}, Name: {}, State: {}, Round: {}",
+            self.id, self.name, self.state, self.current_round
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for Tournament {}
+
+impl Redact for Tournament {}
+
+/// A player registered for a tournament.
+///
+/// The id is only handed to the client who registered, since it grants the sessions of the
+/// entrant in the games of the tournament. Everyone else sees the seed.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the entrant
+/// - `tournament_id` -> The tournament
+/// - `player_name` -> Name the entrant plays with
+/// - `fingerprint` -> Fingerprint of the client, attached to the players of the entrant
+/// - `seed` -> Position of the registration starting at 1; the lower seed wins ties
+/// - `eliminated_in_round` -> Round the entrant lost; `None` while still in the tournament
+/// - `registered_at` -> Point in time the entrant registered
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentEntrant {
+    /// Identifier of the entrant
+    pub id: String,
+    /// The tournament
+    #[serde(alias = "tournament_id")]
+    pub tournament_id: String,
+    /// Name the entrant plays with
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+    /// Fingerprint of the client
+    #[serde(default, skip_serializing)]
+    pub fingerprint: Option<String>,
+    /// Position of the registration starting at 1
+    pub seed: usize,
+    /// Round the entrant lost
+    #[serde(alias = "eliminated_in_round", default)]
+    pub eliminated_in_round: Option<usize>,
+    /// Point in time the entrant registered
+    #[serde(alias = "registered_at", deserialize_with = "datetime_from_text")]
+    pub registered_at: DateTime<Utc>,
+}
+
+impl TournamentEntrant {
+    /// Creates an entrant with a new id; the seed is assigned when it is stored.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_id` -> The tournament
+    /// - `player_name` -> Name the entrant plays with
+    /// - `fingerprint` -> Fingerprint of the client
+    pub fn new(tournament_id: String, player_name: String, fingerprint: Option<String>) -> Self {
+        TournamentEntrant {
+            id: uuid::Uuid::new_v4().to_string(),
+            tournament_id,
+            player_name: player_name.trim().to_string(),
+            fingerprint,
+            seed: 0,
+            eliminated_in_round: None,
+            registered_at: server_time(),
+        }
+    }
+}
+
+impl IntoResponse for TournamentEntrant {
+    /// Converts the `TournamentEntrant` into a response with status code 201.
+    fn into_response(self) -> Response {
+        (StatusCode::CREATED, Json(self)).into_response()
+    }
+}
+
+impl fmt::Display for TournamentEntrant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Entrant #{} of tournament {}: {}",
+            self.seed, self.tournament_id, self.player_name
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for TournamentEntrant {}
+
+impl Redact for TournamentEntrant {
+    /// Removes the id, which grants the sessions of the entrant, and the fingerprint.
+    fn redact(&mut self) {
+        self.id.clear();
+        self.fingerprint = None;
+    }
+}
+
+/// One game of a round of a tournament.
+///
+/// A match without a game is a bye: its only entrant advances right away.
+///
+/// # Props
+///
+/// - `tournament_id` -> The tournament
+/// - `round` -> Round of the match starting at 1
+/// - `slot` -> Position of the match within its round starting at 1
+/// - `game_id` -> Game of the match; `None` for a bye
+/// - `winner_entrant_id` -> Entrant who advances; `None` while the game is played
+/// - `finished_at` -> Point in time the winner was decided
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentMatch {
+    /// The tournament
+    #[serde(alias = "tournament_id")]
+    pub tournament_id: String,
+    /// Round of the match starting at 1
+    pub round: usize,
+    /// Position of the match within its round starting at 1
+    pub slot: usize,
+    /// Game of the match
+    #[serde(alias = "game_id", default)]
+    pub game_id: Option<String>,
+    /// Entrant who advances
+    #[serde(alias = "winner_entrant_id", default, skip_serializing)]
+    pub winner_entrant_id: Option<String>,
+    /// Point in time the winner was decided
+    #[serde(
+        alias = "finished_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl fmt::Display for TournamentMatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Match {} of round {} of tournament {}",
+            self.slot, self.round, self.tournament_id
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for TournamentMatch {}
+
+impl Redact for TournamentMatch {}
+
+/// Seat of an entrant in a match, joined with the seed and name of the entrant.
+///
+/// # Props
+///
+/// - `round` -> Round of the match
+/// - `slot` -> Position of the match within its round
+/// - `entrant_id` -> The seated entrant; never sent to a client
+/// - `player_id` -> Player created for the entrant; `None` for a bye
+/// - `seed` -> Seed of the entrant
+/// - `player_name` -> Name of the entrant
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentSeat {
+    /// Round of the match
+    pub round: usize,
+    /// Position of the match within its round
+    pub slot: usize,
+    /// The seated entrant
+    #[serde(alias = "entrant_id", skip_serializing)]
+    pub entrant_id: String,
+    /// Player created for the entrant
+    #[serde(alias = "player_id", default)]
+    pub player_id: Option<String>,
+    /// Seed of the entrant
+    pub seed: usize,
+    /// Name of the entrant
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+}
+
+impl fmt::Display for TournamentSeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Seat of entrant #{} in match {} of round {}",
+            self.seed, self.slot, self.round
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for TournamentSeat {}
+
+impl Redact for TournamentSeat {}
+
+/// A match as shown in the bracket, with the entrants referred to by their seed.
+///
+/// # Props
+///
+/// - `round` -> Round of the match starting at 1
+/// - `slot` -> Position of the match within its round starting at 1
+/// - `game_id` -> Game of the match, which can be spectated; `None` for a bye
+/// - `seeds` -> Seeds of the entrants of the match
+/// - `winner_seed` -> Seed of the entrant who advances; `None` while the game is played
+/// - `finished_at` -> Point in time the winner was decided
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentMatchView {
+    /// Round of the match starting at 1
+    pub round: usize,
+    /// Position of the match within its round starting at 1
+    pub slot: usize,
+    /// Game of the match
+    pub game_id: Option<String>,
+    /// Seeds of the entrants of the match
+    pub seeds: Vec<usize>,
+    /// Seed of the entrant who advances
+    pub winner_seed: Option<usize>,
+    /// Point in time the winner was decided
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl TournamentMatchView {
+    /// Creates the view of a match from the seats of its tournament.
+    ///
+    /// # Arguments
+    ///
+    /// - `tournament_match` -> The match
+    /// - `seats` -> Seats of the tournament, at least those of the match
+    pub fn new(tournament_match: &TournamentMatch, seats: &[TournamentSeat]) -> Self {
+        let seats_of_match: Vec<&TournamentSeat> = seats
+            .iter()
+            .filter(|seat| {
+                seat.round == tournament_match.round && seat.slot == tournament_match.slot
+            })
+            .collect();
+
+        TournamentMatchView {
+            round: tournament_match.round,
+            slot: tournament_match.slot,
+            game_id: tournament_match.game_id.clone(),
+            seeds: seats_of_match.iter().map(|seat| seat.seed).collect(),
+            winner_seed: seats_of_match
+                .iter()
+                .find(|seat| Some(&seat.entrant_id) == tournament_match.winner_entrant_id.as_ref())
+                .map(|seat| seat.seed),
+            finished_at: tournament_match.finished_at,
+        }
+    }
+}
+
+/// A tournament with its bracket.
+///
+/// # Props
+///
+/// - `tournament` -> The tournament
+/// - `entrant_count` -> Number of registered players
+/// - `matches` -> Matches of all rounds, by round and slot
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentDetails {
+    /// The tournament
+    pub tournament: Tournament,
+    /// Number of registered players
+    pub entrant_count: usize,
+    /// Matches of all rounds, by round and slot
+    pub matches: Vec<TournamentMatchView>,
+}
+
+impl IntoResponse for TournamentDetails {
+    /// Converts the `TournamentDetails` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// The tournaments of a state, newest first.
+///
+/// # Props
+///
+/// - `tournaments` -> The tournaments
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentList {
+    /// The tournaments
+    pub tournaments: Vec<Tournament>,
+}
+
+impl IntoResponse for TournamentList {
+    /// Converts the `TournamentList` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Position of an entrant in the standings of a tournament.
+///
+/// # Props
+///
+/// - `rank` -> Position in the standings starting at 1
+/// - `seed` -> Seed of the entrant
+/// - `player_name` -> Name of the entrant
+/// - `games_won` -> Games of the tournament the entrant won; byes don't count
+/// - `eliminated_in_round` -> Round the entrant lost; `None` while still in the tournament
+/// - `is_winner` -> Whether the entrant won the tournament
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentStanding {
+    /// Position in the standings starting at 1
+    pub rank: usize,
+    /// Seed of the entrant
+    pub seed: usize,
+    /// Name of the entrant
+    pub player_name: String,
+    /// Games of the tournament the entrant won
+    pub games_won: usize,
+    /// Round the entrant lost
+    pub eliminated_in_round: Option<usize>,
+    /// Whether the entrant won the tournament
+    pub is_winner: bool,
+}
+
+/// Standings of all entrants of a tournament.
+///
+/// # Props
+///
+/// - `tournament_id` -> The tournament
+/// - `state` -> Current phase of the tournament
+/// - `current_round` -> Round whose games are played
+/// - `standings` -> The entrants, best first
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentStandings {
+    /// The tournament
+    pub tournament_id: String,
+    /// Current phase of the tournament
+    pub state: TournamentState,
+    /// Round whose games are played
+    pub current_round: usize,
+    /// The entrants, best first
+    pub standings: Vec<TournamentStanding>,
+}
+
+impl IntoResponse for TournamentStandings {
+    /// Converts the `TournamentStandings` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// ----- DTOs for managing tournaments -----
+
+/// DTO type for an operator creating a tournament.
+///
+/// # Props
+///
+/// - `name` -> Name shown to the players
+/// - `players_per_game` -> Most players of one game; `DEFAULT_PLAYERS_PER_GAME` if `None`
+/// - `max_entrants` -> Most players who can register; `DEFAULT_MAX_ENTRANTS` if `None`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTournamentDTO {
+    /// Name shown to the players
+    pub name: String,
+    /// Most players of one game
+    #[serde(alias = "players_per_game", default)]
+    pub players_per_game: Option<usize>,
+    /// Most players who can register
+    #[serde(alias = "max_entrants", default)]
+    pub max_entrants: Option<usize>,
+}
+
+impl CreateTournamentDTO {
+    /// Creates the `Tournament`, which opens its registration right away.
+    pub fn into_tournament(self) -> Tournament {
+        Tournament {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.name.trim().to_string(),
+            state: TournamentState::Registration,
+            players_per_game: self.players_per_game.unwrap_or(DEFAULT_PLAYERS_PER_GAME),
+            max_entrants: self.max_entrants.unwrap_or(DEFAULT_MAX_ENTRANTS),
+            current_round: 0,
+            winner_entrant_id: None,
+            created_at: server_time(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+}
+
+impl fmt::Display for CreateTournamentDTO {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Tournament Name: {}", self.name)
+    }
+}
+
+impl<'a> ErrorObject<'a> for CreateTournamentDTO {}
+
+impl Redact for CreateTournamentDTO {}
+
+impl Validate for CreateTournamentDTO {
+    /// Checks the name and the limits of the tournament.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        validate_tournament(Some(&self.name), self.players_per_game, self.max_entrants)
+            .into_result()
+    }
+}
+
+/// DTO type for an operator changing a tournament before it starts.
+///
+/// # Props
+///
+/// - `name` -> New name, if it changes
+/// - `players_per_game` -> New number of players per game, if it changes
+/// - `max_entrants` -> New number of entrants, if it changes
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTournamentDTO {
+    /// New name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// New number of players per game
+    #[serde(alias = "players_per_game", default)]
+    pub players_per_game: Option<usize>,
+    /// New number of entrants
+    #[serde(alias = "max_entrants", default)]
+    pub max_entrants: Option<usize>,
+}
+
+impl fmt::Display for UpdateTournamentDTO {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Tournament Name: {:?}", self.name)
+    }
+}
+
+impl<'a> ErrorObject<'a> for UpdateTournamentDTO {}
+
+impl Redact for UpdateTournamentDTO {}
+
+impl Validate for UpdateTournamentDTO {
+    /// Checks the changed name and limits of the tournament.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        validate_tournament(
+            self.name.as_deref(),
+            self.players_per_game,
+            self.max_entrants,
+        )
+        .into_result()
+    }
+}
+
+/// DTO type for a player registering for a tournament.
+///
+/// # Props
+///
+/// - `player_name` -> Name the player wants to play with
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterEntrantDTO {
+    /// Name the player wants to play with
+    #[serde(alias = "player_name")]
+    pub player_name: String,
+}
+
+impl fmt::Display for RegisterEntrantDTO {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Player Name: {}", self.player_name)
+    }
+}
+
+impl<'a> ErrorObject<'a> for RegisterEntrantDTO {}
+
+impl Redact for RegisterEntrantDTO {}
+
+impl Validate for RegisterEntrantDTO {
+    /// Checks the name of the player.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        validate_player_name("player_name", &self.player_name, locale).into_result()
+    }
+}
+
+/// Checks the fields of a tournament an operator sets; `None` fields aren't checked.
+fn validate_tournament(
+    name: Option<&str>,
+    players_per_game: Option<usize>,
+    max_entrants: Option<usize>,
+) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+
+    if let Some(name) = name {
+        if name.trim().is_empty() {
+            errors.add("name", "required", "The name must not be empty!");
+        } else if name.chars().count() > MAX_TOURNAMENT_NAME_LENGTH {
+            errors.add(
+                "name",
+                "too_long",
+                &format!(
+                    "The name must not be longer than {MAX_TOURNAMENT_NAME_LENGTH} characters!"
+                ),
+            );
+        }
+    }
+    if players_per_game.is_some_and(|players| !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players)) {
+        errors.add(
+            "players_per_game",
+            "out_of_range",
+            &format!("The players per game must be between {MIN_PLAYERS} and {MAX_PLAYERS}!"),
+        );
+    }
+    if max_entrants
+        .is_some_and(|entrants| !(MIN_PLAYERS..=MAX_TOURNAMENT_ENTRANTS).contains(&entrants))
+    {
+        errors.add(
+            "max_entrants",
+            "out_of_range",
+            &format!(
+                "The number of entrants must be between {MIN_PLAYERS} and {MAX_TOURNAMENT_ENTRANTS}!"
+            ),
+        );
+    }
+
+    errors
+}
@@ -0,0 +1,207 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    errors::{
+        application_error::{ErrorObject, Redact},
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::locale::Locale,
+    types::player::{validate_player_name, Player, PlayerProfile},
+    utils::{
+        d1_conversions::{datetime_from_text, optional_datetime_from_text},
+        time::server_time,
+    },
+};
+
+/// A client waiting for a seat in a full game.
+///
+/// The waiting client gets a token along with the id when it joins, which it has to send to leave
+/// the waitlist or to follow the entry. Only the hash of the token is stored.
+///
+/// # Props
+///
+/// - `id` -> Id of the entry
+/// - `game_id` -> The full game
+/// - `name` -> Name the client wants to play with
+/// - `profile` -> Profile of the player created on promotion
+/// - `fingerprint` -> Fingerprint of the client, attached to the player created on promotion
+/// - `token_hash` -> Hash of the token of the waiting client
+/// - `joined_at` -> Point in time the client joined the waitlist
+/// - `player_id` -> Player created when the entry was promoted; `None` while it waits
+/// - `promoted_at` -> Point in time a seat freed up for the entry
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitlistEntry {
+    /// Id of the entry
+    pub id: String,
+    /// The full game
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// Name the client wants to play with
+    pub name: String,
+    /// Profile of the player created on promotion
+    #[serde(flatten)]
+    pub profile: PlayerProfile,
+    /// Fingerprint of the client
+    #[serde(default, skip_serializing)]
+    pub fingerprint: Option<String>,
+    /// Hash of the token of the waiting client; `None` for entries that joined without one
+    #[serde(alias = "token_hash", default, skip_serializing)]
+    pub token_hash: Option<String>,
+    /// Point in time the client joined the waitlist
+    #[serde(alias = "joined_at", deserialize_with = "datetime_from_text")]
+    pub joined_at: DateTime<Utc>,
+    /// Player created when the entry was promoted
+    #[serde(alias = "player_id", default)]
+    pub player_id: Option<String>,
+    /// Point in time a seat freed up for the entry
+    #[serde(
+        alias = "promoted_at",
+        default,
+        deserialize_with = "optional_datetime_from_text"
+    )]
+    pub promoted_at: Option<DateTime<Utc>>,
+}
+
+impl WaitlistEntry {
+    /// Builds the player taking the seat of the entry.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` -> Name of the player, numbered if the requested one was taken in the meantime
+    pub fn to_player(&self, name: String) -> Player {
+        let mut player = Player::new(name, self.game_id.clone());
+        player.profile = self.profile.clone();
+        player.fingerprint = self.fingerprint.clone();
+        player
+    }
+}
+
+impl fmt::Display for WaitlistEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Waitlist entry of {} for game {} joined at {}",
+            self.name, self.game_id, self.joined_at
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for WaitlistEntry {}
+
+impl Redact for WaitlistEntry {
+    /// Removes the id, the hash of the token and the fingerprint.
+    fn redact(&mut self) {
+        self.id.clear();
+        self.fingerprint = None;
+        self.token_hash = None;
+    }
+}
+
+/// Where an entry stands in the waitlist of a game.
+///
+/// # Props
+///
+/// - `entry_id` -> Id of the entry
+/// - `game_id` -> The full game
+/// - `position` -> Position in the waitlist starting at 1; `None` once promoted
+/// - `waiting` -> Number of clients waiting in total
+/// - `token` -> Token of the waiting client, only sent once when it joins
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitlistStatus {
+    /// Id of the entry
+    pub entry_id: String,
+    /// The full game
+    pub game_id: String,
+    /// Position in the waitlist starting at 1
+    pub position: Option<usize>,
+    /// Number of clients waiting in total
+    pub waiting: usize,
+    /// Token of the waiting client, only sent once when it joins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl IntoResponse for WaitlistStatus {
+    /// Converts the `WaitlistStatus` into a response with status code 200.
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// ----- DTO for joining the waitlist -----
+
+/// DTO type for a client joining the waitlist of a full game.
+///
+/// # Props
+///
+/// - `name` -> Name the client wants to play with
+/// - `profile` -> Optional profile of the player created on promotion
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JoinWaitlistDTO {
+    /// Name the client wants to play with
+    pub name: String,
+    /// Optional profile of the player created on promotion
+    #[serde(flatten)]
+    pub profile: PlayerProfile,
+}
+
+impl JoinWaitlistDTO {
+    /// Creates the entry of the client for a game.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> The full game
+    /// - `fingerprint` -> Fingerprint of the client
+    /// - `token_hash` -> Hash of the token handed to the client
+    pub fn into_entry(
+        self,
+        game_id: String,
+        fingerprint: Option<String>,
+        token_hash: String,
+    ) -> WaitlistEntry {
+        WaitlistEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            game_id,
+            name: self.name,
+            profile: self.profile,
+            fingerprint,
+            token_hash: Some(token_hash),
+            joined_at: server_time(),
+            player_id: None,
+            promoted_at: None,
+        }
+    }
+}
+
+impl fmt::Display for JoinWaitlistDTO {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Name: {}", self.name)
+    }
+}
+
+impl<'a> ErrorObject<'a> for JoinWaitlistDTO {}
+
+impl Redact for JoinWaitlistDTO {}
+
+impl Validate for JoinWaitlistDTO {
+    /// Checks the name and the profile of the waiting client.
+    fn validate(&self, locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = validate_player_name("name", &self.name, locale);
+
+        if let Err(profile_errors) = self.profile.validate(locale) {
+            errors.merge(profile_errors);
+        }
+
+        errors.into_result()
+    }
+}
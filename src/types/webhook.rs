@@ -0,0 +1,316 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+use crate::{
+    enums::webhook_event::WebhookEvent,
+    errors::{
+        application_error::{ErrorObject, Redact},
+        validation_errors::{Validate, ValidationErrors},
+    },
+    i18n::locale::Locale,
+    utils::{d1_conversions::datetime_from_text, time::server_time},
+};
+
+// constants
+/// Maximum number of characters of a callback URL.
+pub const MAX_WEBHOOK_URL_LENGTH: usize = 512;
+
+/// Maximum number of webhooks a single game can register.
+pub const MAX_WEBHOOKS_PER_GAME: usize = 3;
+
+/// Callback URL receiving the lifecycle events of a game.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the webhook
+/// - `game_id` -> Game whose events are sent; `None` for a webhook of an operator receiving the
+///   events of all games
+/// - `url` -> `https` URL the events are posted to
+/// - `created_at` -> Point in time the webhook was registered
+/// - `secret` -> Key the payloads are signed with; only sent once, when the webhook is registered
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    /// Identifier of the webhook
+    pub id: String,
+    /// Game whose events are sent; `None` for all games
+    #[serde(alias = "game_id", default)]
+    pub game_id: Option<String>,
+    /// `https` URL the events are posted to
+    pub url: String,
+    /// Point in time the webhook was registered
+    #[serde(alias = "created_at", deserialize_with = "datetime_from_text")]
+    pub created_at: DateTime<Utc>,
+    /// Key the payloads are signed with
+    ///
+    /// `None` for webhooks registered before every webhook got its own key; their payloads are
+    /// signed with the `WEBHOOK_SECRET` of the worker.
+    #[serde(default, skip_serializing)]
+    pub secret: Option<String>,
+}
+
+impl IntoResponse for Webhook {
+    /// Converts the `Webhook` into a response with status code 201.
+    fn into_response(self) -> Response {
+        (StatusCode::CREATED, Json(self)).into_response()
+    }
+}
+
+impl fmt::Display for Webhook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Webhook ID: {}, Game ID: {}",
+            self.id,
+            self.game_id.as_deref().unwrap_or("all games")
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for Webhook {}
+
+impl Redact for Webhook {
+    /// The URL is removed, since callback URLs like the ones of Discord contain a token.
+    fn redact(&mut self) {
+        self.url = String::new();
+        self.secret = None;
+    }
+}
+
+/// Response to the registration of a webhook.
+///
+/// The only response containing the secret of the webhook; the receiver needs it to verify the
+/// signatures and can't look it up later.
+///
+/// # Props
+///
+/// - `webhook` -> The registered webhook
+/// - `secret` -> Key the payloads are signed with
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredWebhook {
+    /// The registered webhook
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    /// Key the payloads are signed with
+    pub secret: String,
+}
+
+impl From<Webhook> for RegisteredWebhook {
+    /// Reveals the secret of a webhook that was just stored.
+    fn from(webhook: Webhook) -> Self {
+        let secret = webhook.secret.clone().unwrap_or_default();
+        RegisteredWebhook { webhook, secret }
+    }
+}
+
+impl IntoResponse for RegisteredWebhook {
+    /// Converts the `RegisteredWebhook` into a response with status code 201.
+    fn into_response(self) -> Response {
+        (StatusCode::CREATED, Json(self)).into_response()
+    }
+}
+
+// ----- DTO for registering a webhook -----
+
+/// DTO type for a host or an operator who registers a webhook.
+///
+/// # Props
+///
+/// - `url` -> `https` URL the events are posted to
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookDTO {
+    /// `https` URL the events are posted to
+    pub url: String,
+}
+
+impl CreateWebhookDTO {
+    /// Creates the `Webhook` with a generated id and secret, registered right now.
+    ///
+    /// # Arguments
+    ///
+    /// - `game_id` -> Game whose events are sent; `None` for all games
+    pub fn into_webhook(self, game_id: Option<String>) -> Webhook {
+        Webhook {
+            id: uuid::Uuid::new_v4().to_string(),
+            game_id,
+            url: self.url.trim().to_string(),
+            created_at: server_time(),
+            secret: Some(format!(
+                "{}{}",
+                uuid::Uuid::new_v4().simple(),
+                uuid::Uuid::new_v4().simple()
+            )),
+        }
+    }
+}
+
+impl Validate for CreateWebhookDTO {
+    /// Checks that the URL uses `https` and isn't too long.
+    fn validate(&self, _locale: &Locale) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        let url = self.url.trim();
+
+        if url.is_empty() {
+            errors.add("url", "required", "The callback URL is mandatory!");
+        } else if !url.starts_with("https://") || url.len() <= "https://".len() {
+            errors.add(
+                "url",
+                "invalid_format",
+                "The callback URL must start with 'https://'!",
+            );
+        } else if url.chars().count() > MAX_WEBHOOK_URL_LENGTH {
+            errors.add(
+                "url",
+                "too_long",
+                &format!(
+                    "The callback URL can't be longer than {MAX_WEBHOOK_URL_LENGTH} characters!"
+                ),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+// ----- Payload posted to a webhook -----
+
+/// JSON body posted to a webhook.
+///
+/// Signed with the secret of the receiving webhook; the signature is sent in the `X-Signature`
+/// header.
+///
+/// # Props
+///
+/// - `delivery_id` -> Identifier of the delivery, the same for every retry
+/// - `event` -> Lifecycle event, e.g. `game_started`
+/// - `game_id` -> Game the event happened in
+/// - `occurred_at` -> Point in time of the event
+/// - `data` -> Details of the event, e.g. the id of the kicked player
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    /// Identifier of the delivery, the same for every retry
+    pub delivery_id: String,
+    /// Lifecycle event
+    pub event: WebhookEvent,
+    /// Game the event happened in
+    pub game_id: String,
+    /// Point in time of the event
+    pub occurred_at: DateTime<Utc>,
+    /// Details of the event
+    pub data: Value,
+}
+
+impl WebhookPayload {
+    /// Creates the payload of an event that happened right now.
+    ///
+    /// # Arguments
+    ///
+    /// - `event` -> Lifecycle event
+    /// - `game_id` -> Game the event happened in
+    /// - `data` -> Details of the event
+    pub fn new(event: WebhookEvent, game_id: String, data: Value) -> Self {
+        WebhookPayload {
+            delivery_id: uuid::Uuid::new_v4().to_string(),
+            event,
+            game_id,
+            occurred_at: server_time(),
+            data,
+        }
+    }
+}
+
+// ----- Deliveries that failed for good -----
+
+/// Delivery of a payload that failed on every attempt.
+///
+/// # Props
+///
+/// - `id` -> Identifier of the entry
+/// - `webhook_id` -> Webhook the payload was posted to
+/// - `delivery_id` -> Identifier of the delivery, as sent in the `X-LueLue-Delivery` header
+/// - `event` -> Lifecycle event of the payload
+/// - `game_id` -> Game the event happened in
+/// - `payload` -> Body that was posted, as JSON text
+/// - `attempts` -> Number of attempts made
+/// - `last_error` -> Why the last attempt failed
+/// - `failed_at` -> Point in time the last attempt failed
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeadLetter {
+    /// Identifier of the entry
+    pub id: String,
+    /// Webhook the payload was posted to
+    #[serde(alias = "webhook_id")]
+    pub webhook_id: String,
+    /// Identifier of the delivery
+    #[serde(alias = "delivery_id")]
+    pub delivery_id: String,
+    /// Lifecycle event of the payload
+    pub event: WebhookEvent,
+    /// Game the event happened in
+    #[serde(alias = "game_id")]
+    pub game_id: String,
+    /// Body that was posted, as JSON text
+    pub payload: String,
+    /// Number of attempts made
+    pub attempts: u32,
+    /// Why the last attempt failed
+    #[serde(alias = "last_error")]
+    pub last_error: String,
+    /// Point in time the last attempt failed
+    #[serde(alias = "failed_at", deserialize_with = "datetime_from_text")]
+    pub failed_at: DateTime<Utc>,
+}
+
+impl WebhookDeadLetter {
+    /// Creates the entry of a delivery whose last attempt failed right now.
+    ///
+    /// # Arguments
+    ///
+    /// - `webhook_id` -> Webhook the payload was posted to
+    /// - `payload` -> The payload that couldn't be delivered
+    /// - `attempts` -> Number of attempts made
+    /// - `last_error` -> Why the last attempt failed
+    pub fn new(
+        webhook_id: String,
+        payload: &WebhookPayload,
+        attempts: u32,
+        last_error: String,
+    ) -> Self {
+        WebhookDeadLetter {
+            id: uuid::Uuid::new_v4().to_string(),
+            webhook_id,
+            delivery_id: payload.delivery_id.clone(),
+            event: payload.event,
+            game_id: payload.game_id.clone(),
+            payload: serde_json::to_string(payload).unwrap_or_default(),
+            attempts,
+            last_error,
+            failed_at: server_time(),
+        }
+    }
+}
+
+impl fmt::Display for WebhookDeadLetter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Delivery {} to webhook {} failed after {} attempts",
+            self.delivery_id, self.webhook_id, self.attempts
+        )
+    }
+}
+
+impl<'a> ErrorObject<'a> for WebhookDeadLetter {}
+
+impl Redact for WebhookDeadLetter {}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::{fmt::Display, str::FromStr};
+
+use serde::{de::Error, Deserialize, Deserializer};
+use serde_json::Value;
+use wasm_bindgen::JsValue;
+
+/// Raw representation of a boolean column as it is returned by D1.
+///
+/// SQLite has no boolean type, so flags are stored as `0` / `1`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BoolOrNumber {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+}
+
+/// Deserializes a boolean from a D1 row where it is stored as an integer.
+///
+/// Accepts real booleans as well, so the same type can be deserialized from a request body.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(Deserialize)]
+/// struct Row {
+///     #[serde(deserialize_with = "bool_from_number")]
+///     flag: bool,
+/// }
+/// ```
+pub fn bool_from_number<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match BoolOrNumber::deserialize(deserializer)? {
+        BoolOrNumber::Bool(value) => value,
+        BoolOrNumber::Integer(value) => value != 0,
+        BoolOrNumber::Float(value) => value != 0.0,
+    })
+}
+
+/// Format of the `CURRENT_TIMESTAMP` default value of SQLite.
+const SQLITE_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Parses a timestamp stored in a D1 text column.
+///
+/// Timestamps written by the backend are ISO 8601 / RFC 3339 strings. Columns filled by the
+/// `CURRENT_TIMESTAMP` default of SQLite have the format `YYYY-MM-DD HH:MM:SS` and are always UTC.
+///
+/// # Returns
+///
+/// The parsed timestamp or `None` if the text has neither of the formats.
+pub fn parse_timestamp(text: &str) -> Option<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(text) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+
+    NaiveDateTime::parse_from_str(text, SQLITE_TIMESTAMP_FORMAT)
+        .ok()
+        .map(|timestamp| timestamp.and_utc())
+}
+
+/// Deserializes a `DateTime<Utc>` from a D1 text column.
+///
+/// See `parse_timestamp` for all accepted formats.
+pub fn datetime_from_text<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+
+    parse_timestamp(&text)
+        .ok_or_else(|| D::Error::custom(format!("'{text}' is not a valid timestamp")))
+}
+
+/// Deserializes an optional `DateTime<Utc>` from a nullable D1 text column.
+///
+/// See `parse_timestamp` for all accepted formats.
+pub fn optional_datetime_from_text<'de, D>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(text) => parse_timestamp(&text)
+            .map(Some)
+            .ok_or_else(|| D::Error::custom(format!("'{text}' is not a valid timestamp"))),
+    }
+}
+
+/// Raw representation of an enum column.
+///
+/// The value is either the text of the variant or the integer code of older rows.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EnumColumn {
+    Code(i32),
+    Text(String),
+}
+
+impl EnumColumn {
+    /// Parses the raw value into the enum.
+    fn parse<T, E>(self) -> Result<T, E>
+    where
+        T: TryFrom<i32> + FromStr,
+        <T as TryFrom<i32>>::Error: Display,
+        <T as FromStr>::Err: Display,
+        E: Error,
+    {
+        match self {
+            EnumColumn::Code(code) => T::try_from(code).map_err(E::custom),
+            EnumColumn::Text(text) => text.parse::<T>().map_err(E::custom),
+        }
+    }
+}
+
+/// Deserializes an enum stored in a D1 column.
+///
+/// Accepts the `as_str()` value, the name of the variant (like in a request body) and the legacy
+/// integer code.
+pub fn enum_from_column<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<i32> + FromStr,
+    <T as TryFrom<i32>>::Error: Display,
+    <T as FromStr>::Err: Display,
+{
+    EnumColumn::deserialize(deserializer)?.parse()
+}
+
+/// Deserializes an optional enum from a nullable D1 column.
+///
+/// See `enum_from_column` for the accepted formats.
+pub fn optional_enum_from_column<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<i32> + FromStr,
+    <T as TryFrom<i32>>::Error: Display,
+    <T as FromStr>::Err: Display,
+{
+    match Option::<EnumColumn>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(column) => column.parse().map(Some),
+    }
+}
+
+/// Converts an optional value into a D1 binding.
+///
+/// `None` becomes `NULL`, since D1 rejects `undefined` as a binding.
+pub fn optional_binding<T: Into<JsValue>>(value: Option<T>) -> JsValue {
+    match value {
+        Some(value) => value.into(),
+        None => JsValue::NULL,
+    }
+}
+
+/// Converts a JSON value of a raw row into a D1 binding.
+///
+/// Booleans become `0` / `1`; arrays and objects are stored as JSON text.
+pub fn json_binding(value: &Value) -> JsValue {
+    match value {
+        Value::Null => JsValue::NULL,
+        Value::Bool(flag) => JsValue::from(u8::from(*flag)),
+        Value::Number(number) => number.as_f64().map(JsValue::from).unwrap_or(JsValue::NULL),
+        Value::String(text) => JsValue::from(text.as_str()),
+        Value::Array(_) | Value::Object(_) => JsValue::from(value.to_string()),
+    }
+}
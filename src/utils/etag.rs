@@ -0,0 +1,97 @@
+use axum::{
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+// constants
+/// Number of bytes of the body digest that end up in an entity tag.
+const ETAG_DIGEST_BYTES: usize = 12;
+
+/// Caching policy of the polled responses; every poll is revalidated with the entity tag.
+const REVALIDATE_CACHE_CONTROL: &str = "private, no-cache";
+
+/// Top-level fields of a body which change with every response, like the time of the server.
+///
+/// They are left out of the digest, so an unchanged game is still answered with `304`.
+const VOLATILE_FIELDS: [&str; 1] = ["serverTime"];
+
+/// Computes the weak entity tag of a response body.
+///
+/// The revision of a game only counts the updates of its row; new chat messages or claims don't
+/// change it and a view differs per player. The digest of the body covers both.
+///
+/// # Arguments
+///
+/// - `revision` -> Version counter of the game
+/// - `body` -> Serialized body of the response
+pub fn weak_etag(revision: u64, body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+
+    format!(
+        "W/\"{revision}-{}\"",
+        URL_SAFE_NO_PAD.encode(&digest[..ETAG_DIGEST_BYTES])
+    )
+}
+
+/// Checks whether the `If-None-Match` header of a request lists an entity tag.
+///
+/// Tags are compared weakly, as required for `If-None-Match`.
+pub fn matches_if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+/// Serializes a body as JSON and answers with `304 Not Modified` if the client already holds it.
+///
+/// # Arguments
+///
+/// - `headers` -> Headers of the request carrying `If-None-Match`
+/// - `revision` -> Version counter of the game the body shows
+/// - `body` -> Body of the response
+pub fn conditional_json<T: Serialize>(headers: &HeaderMap, revision: u64, body: &T) -> Response {
+    let (Ok(bytes), Ok(mut value)) = (serde_json::to_vec(body), serde_json::to_value(body)) else {
+        // lets `Json` turn the serialization error into a response
+        return Json(body).into_response();
+    };
+    if let Some(fields) = value.as_object_mut() {
+        for field in VOLATILE_FIELDS {
+            fields.remove(field);
+        }
+    }
+    let etag = weak_etag(revision, value.to_string().as_bytes());
+    let etag_header = HeaderValue::from_str(&etag).expect("entity tags are valid header values");
+    let cache_control = HeaderValue::from_static(REVALIDATE_CACHE_CONTROL);
+
+    if matches_if_none_match(headers, &etag) {
+        return (
+            StatusCode::NOT_MODIFIED,
+            [(ETAG, etag_header), (CACHE_CONTROL, cache_control)],
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, HeaderValue::from_static("application/json")),
+            (ETAG, etag_header),
+            (CACHE_CONTROL, cache_control),
+        ],
+        bytes,
+    )
+        .into_response()
+}
@@ -5,11 +5,57 @@ use rand_chacha::{
 
 use crate::enums::card_types::CardType;
 
+// constants
+/// Number of characters of a join code.
+pub const JOIN_CODE_LENGTH: usize = 6;
+
+/// Characters a join code is made of.
+///
+/// Characters that are easily mixed up like `0` / `O` or `1` / `I` are left out, so the code can
+/// be typed by hand.
+const JOIN_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Creates a new randomly seeded CSPRNG.
+///
+/// The seed is taken from two random v4 UUIDs, which are generated with the random source of the
+/// runtime.
+pub fn new_rng() -> ChaCha8Rng {
+    let mut seed = [0u8; 32];
+    seed[..16].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    seed[16..].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+
+    ChaCha8Rng::from_seed(seed)
+}
+
 /// Randomly generates a new card type like 'King' or 'Queen'.
 ///
+/// Only ranks of a standard deck are selected; a Joker is never the card to play.
+///
 /// It uses CSPRNG function to ensure best practice for random-generated output.
 pub fn select_new_card_to_be_played() -> CardType {
-    let mut rng = ChaCha8Rng::from_seed(Default::default());
-    let num: usize = (rng.next_u32() % CardType::number_of_values() as u32) as usize;
-    return CardType::from_usize(num);
+    let mut rng = new_rng();
+    let ranks = CardType::ranks();
+    let num: usize = (rng.next_u32() % ranks.len() as u32) as usize;
+    return ranks[num].clone();
+}
+
+/// Generates a short, human-typable code to join a game.
+///
+/// It uses CSPRNG function, so codes of private games can't be guessed from each other.
+pub fn generate_join_code() -> String {
+    let mut rng = new_rng();
+
+    (0..JOIN_CODE_LENGTH)
+        .map(|_| {
+            let index = (rng.next_u32() % JOIN_CODE_ALPHABET.len() as u32) as usize;
+            JOIN_CODE_ALPHABET[index] as char
+        })
+        .collect()
+}
+
+/// Brings a join code typed by a player into the stored format.
+///
+/// Surrounding whitespace is removed and all letters are uppercased.
+pub fn normalize_join_code(code: &str) -> String {
+    code.trim().to_uppercase()
 }
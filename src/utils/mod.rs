@@ -1 +1,5 @@
+pub mod d1_conversions;
+pub mod etag;
 pub mod game_service;
+pub mod ndjson;
+pub mod time;
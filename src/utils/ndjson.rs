@@ -0,0 +1,124 @@
+use std::{convert::Infallible, future::Future};
+
+use axum::{
+    body::{Body, Bytes},
+    http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use futures::stream;
+use serde::Serialize;
+use serde_json::json;
+
+// constants
+/// Content type of a response with one JSON document per line.
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Most rows written into one streamed response.
+///
+/// A response which hits the limit ends with a `{"truncated":true}` line; the client continues
+/// with the paged JSON endpoint.
+pub const MAX_STREAMED_ROWS: usize = 10_000;
+
+/// Page of rows read by a streamed response.
+///
+/// The cursor of the next page is `None` on the last page.
+pub type StreamPage<T, C> = (Vec<T>, Option<C>);
+
+/// Checks whether the `Accept` header of a request asks for newline-delimited JSON.
+pub fn accepts_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_type| media_type.split(';').next().map(str::trim) == Some(NDJSON_CONTENT_TYPE))
+}
+
+/// Streams rows as newline-delimited JSON, reading the next page only after the previous one was
+/// written.
+///
+/// The body is sent in chunks, so neither the rows nor the serialized response are held in the
+/// memory of the isolate at once. The status code is sent before the first page is read; a
+/// failing page ends the body with an `{"error":"..."}` line.
+///
+/// # Arguments
+///
+/// - `first_cursor` -> Cursor of the first page; `None` starts at the beginning
+/// - `fetch_page` -> Reads the page after a cursor
+pub fn stream_ndjson<T, C, F, Fut>(first_cursor: Option<C>, fetch_page: F) -> Response
+where
+    T: Serialize,
+    C: Send + 'static,
+    F: FnMut(Option<C>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<StreamPage<T, C>, String>> + Send + 'static,
+{
+    let state = StreamState {
+        fetch_page,
+        cursor: first_cursor,
+        written_rows: 0,
+        done: false,
+    };
+
+    let chunks = stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+
+        let chunk = match (state.fetch_page)(state.cursor.take()).await {
+            Ok((rows, next_cursor)) => {
+                let remaining = MAX_STREAMED_ROWS - state.written_rows;
+                let truncated =
+                    rows.len() > remaining || (rows.len() == remaining && next_cursor.is_some());
+                let mut chunk = String::new();
+
+                for row in rows.iter().take(remaining) {
+                    match serde_json::to_string(row) {
+                        Ok(line) => chunk.push_str(&line),
+                        Err(err) => {
+                            chunk.push_str(&json!({ "error": err.to_string() }).to_string());
+                            state.done = true;
+                            break;
+                        }
+                    }
+                    chunk.push('\n');
+                    state.written_rows += 1;
+                }
+                if truncated && !state.done {
+                    chunk.push_str("{\"truncated\":true}\n");
+                }
+
+                state.done = state.done || truncated || next_cursor.is_none();
+                state.cursor = next_cursor;
+                chunk
+            }
+            Err(message) => {
+                state.done = true;
+                format!("{}\n", json!({ "error": message }))
+            }
+        };
+
+        Some((Ok::<Bytes, Infallible>(Bytes::from(chunk)), state))
+    });
+
+    (
+        StatusCode::OK,
+        [(CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(chunks),
+    )
+        .into_response()
+}
+
+/// Progress of a streamed response.
+struct StreamState<F, C> {
+    /// Reads the page after a cursor
+    fetch_page: F,
+    /// Cursor of the next page
+    cursor: Option<C>,
+    /// Rows written so far
+    written_rows: usize,
+    /// Whether the last chunk was written
+    done: bool,
+}
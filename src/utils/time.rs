@@ -0,0 +1,106 @@
+use std::{cell::RefCell, rc::Rc};
+
+#[cfg(any(test, feature = "test_support"))]
+use std::cell::Cell;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Source of the current time.
+///
+/// Every timestamp of the server is taken from the clock of the thread with `server_time`, so a
+/// test can install a `FixedClock` with `set_clock` and gets the same timestamps on every run.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock reading the time of the system, used by the worker.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock standing still at a point in time until a test moves it on.
+///
+/// # Example usage:
+/// ```rust
+/// let clock = Rc::new(FixedClock::new(started_at));
+/// set_clock(clock.clone());
+/// clock.advance(Duration::seconds(30));
+/// ```
+#[cfg(any(test, feature = "test_support"))]
+pub struct FixedClock {
+    /// Time the clock shows
+    now: Cell<DateTime<Utc>>,
+}
+
+#[cfg(any(test, feature = "test_support"))]
+impl FixedClock {
+    /// Creates a clock showing a point in time.
+    ///
+    /// # Arguments
+    ///
+    /// - `now` -> Time the clock shows until it's moved on
+    pub fn new(now: DateTime<Utc>) -> Self {
+        FixedClock {
+            now: Cell::new(now),
+        }
+    }
+
+    /// Moves the clock on by a duration.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    /// Sets the clock to a point in time.
+    pub fn set(&self, now: DateTime<Utc>) {
+        self.now.set(now);
+    }
+}
+
+#[cfg(any(test, feature = "test_support"))]
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now.get()
+    }
+}
+
+thread_local! {
+    /// Clock of the thread; a worker isolate runs every request on a single thread.
+    static CLOCK: RefCell<Rc<dyn Clock>> = RefCell::new(Rc::new(SystemClock));
+}
+
+/// Replaces the clock of the current thread.
+///
+/// # Arguments
+///
+/// - `clock` -> Clock every following timestamp of the thread is taken from
+#[cfg(any(test, feature = "test_support"))]
+pub fn set_clock(clock: Rc<dyn Clock>) {
+    CLOCK.with(|current| *current.borrow_mut() = clock);
+}
+
+/// Current time of the server.
+///
+/// Every timestamp is created with this function instead of `Utc::now`. It's also sent next to
+/// every deadline, so clients count down with the offset between their clock and this one
+/// instead of trusting their own clock.
+pub fn server_time() -> DateTime<Utc> {
+    CLOCK.with(|clock| clock.borrow().now())
+}
+
+/// Point in time the current turn ends.
+///
+/// # Arguments
+///
+/// - `turn_started_at` -> Point in time the turn passed to the player; `None` before the first turn
+/// - `turn_seconds` -> Seconds a player has for a move
+pub fn turn_deadline(
+    turn_started_at: Option<DateTime<Utc>>,
+    turn_seconds: usize,
+) -> Option<DateTime<Utc>> {
+    turn_started_at.map(|started_at| started_at + Duration::seconds(turn_seconds as i64))
+}
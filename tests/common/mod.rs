@@ -0,0 +1,225 @@
+//! Harness running the worker locally for the integration tests.
+//!
+//! `TestWorker::start` applies the migrations to a fresh local D1 database and serves the worker
+//! with `wrangler dev --local`, so the tests talk to the same bindings as in production. Set
+//! `LUELUE_WORKER_URL` to test against a worker that already runs instead.
+
+#![allow(dead_code)]
+
+use std::{
+    env, fs,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::{
+    blocking::{Client, RequestBuilder},
+    Method, StatusCode,
+};
+use serde_json::Value;
+
+// constants
+/// Variable with the URL of a running worker which replaces the local one.
+const WORKER_URL_VAR: &str = "LUELUE_WORKER_URL";
+/// Name of the D1 database in `wrangler.toml`.
+const DATABASE_NAME: &str = "luelue-database";
+/// Turnstile secret of Cloudflare which accepts every token.
+pub const TURNSTILE_TEST_SECRET: &str = "1x0000000000000000000000000000000AA";
+/// Token the Turnstile test secret accepts.
+pub const TURNSTILE_TEST_TOKEN: &str = "XXXX.DUMMY.TOKEN.XXXX";
+/// Key the local worker signs the session tokens with.
+const SESSION_TEST_SECRET: &str = "integration-test-session-secret";
+/// Token of the admin endpoints of the local worker.
+pub const ADMIN_TEST_TOKEN: &str = "integration-test-admin-token";
+/// Time the first build and start of the worker may take.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(600);
+/// Pause between two readiness checks.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A worker serving the HTTP API for the tests.
+///
+/// # Props
+///
+/// - `base_url` -> URL the worker listens on, without a trailing slash
+/// - `client` -> HTTP client of the tests
+/// - `process` -> The `wrangler dev` process, `None` for an external worker
+/// - `persist_dir` -> Directory of the local D1 database, removed on drop
+pub struct TestWorker {
+    /// URL the worker listens on
+    pub base_url: String,
+    /// HTTP client of the tests
+    client: Client,
+    /// The `wrangler dev` process
+    process: Option<Child>,
+    /// Directory of the local D1 database
+    persist_dir: Option<PathBuf>,
+}
+
+impl TestWorker {
+    /// Starts a local worker with an empty, fully migrated database and waits until it answers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the migrations fail or the worker doesn't come up within `STARTUP_TIMEOUT`.
+    pub fn start() -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to build the HTTP client");
+
+        if let Ok(base_url) = env::var(WORKER_URL_VAR) {
+            let worker = TestWorker {
+                base_url: base_url.trim_end_matches('/').to_string(),
+                client,
+                process: None,
+                persist_dir: None,
+            };
+            worker.wait_until_ready();
+            return worker;
+        }
+
+        let port = free_port();
+        let persist_dir = env::temp_dir().join(format!("luelue-it-{}-{port}", std::process::id()));
+        fs::create_dir_all(&persist_dir).expect("Failed to create the database directory");
+
+        apply_migrations(&persist_dir);
+
+        let process = Command::new("npx")
+            .args(["wrangler", "dev", "--local", "--port", &port.to_string()])
+            .arg("--persist-to")
+            .arg(&persist_dir)
+            .args([
+                "--var",
+                &format!("TURNSTILE_SECRET:{TURNSTILE_TEST_SECRET}"),
+            ])
+            .args(["--var", &format!("SESSION_SECRET:{SESSION_TEST_SECRET}")])
+            .args(["--var", &format!("ADMIN_TOKEN:{ADMIN_TEST_TOKEN}")])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .expect("Failed to run `npx wrangler dev`");
+
+        let worker = TestWorker {
+            base_url: format!("http://127.0.0.1:{port}"),
+            client,
+            process: Some(process),
+            persist_dir: Some(persist_dir),
+        };
+        worker.wait_until_ready();
+
+        worker
+    }
+
+    /// Creates a request to a path of the worker.
+    ///
+    /// Every request carries the client id of the test, so the worker doesn't need the
+    /// `CF-Connecting-IP` header of Cloudflare.
+    ///
+    /// # Arguments
+    ///
+    /// - `method` -> HTTP method of the request
+    /// - `path` -> Path of the endpoint starting with `/`
+    /// - `token` -> Session token sent as bearer token
+    pub fn request(&self, method: Method, path: &str, token: Option<&str>) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}{path}", self.base_url))
+            .header("X-Client-Id", "integration-test");
+
+        match token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Sends a `GET` request and returns the status code with the JSON body.
+    pub fn get(&self, path: &str, token: Option<&str>) -> (StatusCode, Value) {
+        send(self.request(Method::GET, path, token))
+    }
+
+    /// Sends a `POST` request with a JSON body and returns the status code with the JSON body.
+    pub fn post(&self, path: &str, token: Option<&str>, body: &Value) -> (StatusCode, Value) {
+        send(self.request(Method::POST, path, token).json(body))
+    }
+
+    /// Sends a `PUT` request with a JSON body and returns the status code with the JSON body.
+    pub fn put(&self, path: &str, token: Option<&str>, body: &Value) -> (StatusCode, Value) {
+        send(self.request(Method::PUT, path, token).json(body))
+    }
+
+    /// Polls the feature flags until the worker answers.
+    fn wait_until_ready(&self) {
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+
+        while Instant::now() < deadline {
+            let ready = self
+                .request(Method::GET, "/features", None)
+                .send()
+                .is_ok_and(|response| response.status().is_success());
+            if ready {
+                return;
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        panic!("The worker at {} didn't start in time", self.base_url);
+    }
+}
+
+impl Drop for TestWorker {
+    /// Stops `wrangler dev` and removes the local database.
+    fn drop(&mut self) {
+        if let Some(process) = self.process.as_mut() {
+            let _ = process.kill();
+            let _ = process.wait();
+        }
+        if let Some(persist_dir) = self.persist_dir.as_ref() {
+            let _ = fs::remove_dir_all(persist_dir);
+        }
+    }
+}
+
+/// Sends a request and reads the JSON body; an empty or non-JSON body becomes `Value::Null`.
+pub fn send(request: RequestBuilder) -> (StatusCode, Value) {
+    let response = request.send().expect("Failed to reach the worker");
+    let status = response.status();
+    let body = response.json::<Value>().unwrap_or(Value::Null);
+
+    (status, body)
+}
+
+/// Applies all migrations of `migrations/` to the local D1 database in a directory.
+fn apply_migrations(persist_dir: &Path) {
+    let status = Command::new("npx")
+        .args([
+            "wrangler",
+            "d1",
+            "migrations",
+            "apply",
+            DATABASE_NAME,
+            "--local",
+        ])
+        .arg("--persist-to")
+        .arg(persist_dir)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("CI", "true")
+        .stdin(Stdio::null())
+        .status()
+        .expect("Failed to run `npx wrangler d1 migrations apply`");
+
+    assert!(status.success(), "Failed to apply the migrations");
+}
+
+/// Asks the OS for a port nobody listens on.
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|address| address.port())
+        .expect("Failed to find a free port")
+}
@@ -0,0 +1,364 @@
+//! Plays a whole game against a local worker over HTTP.
+//!
+//! The test needs Node.js with `npx wrangler` and builds the worker with `worker-build` on its
+//! first run, so it is ignored by default. Run it with `cargo test --test game_flow -- --ignored`.
+
+mod common;
+
+use std::thread;
+
+use common::{send, TestWorker, TURNSTILE_TEST_TOKEN};
+use reqwest::{Method, StatusCode};
+use serde_json::{json, Value};
+
+/// Reads a string field of a JSON body.
+fn text<'a>(body: &'a Value, pointer: &str) -> &'a str {
+    body.pointer(pointer)
+        .and_then(Value::as_str)
+        .unwrap_or_else(|| panic!("`{pointer}` is missing in {body}"))
+}
+
+#[test]
+#[ignore = "needs `npx wrangler`; run with `cargo test -- --ignored`"]
+fn create_join_start_claim_challenge_end() {
+    let worker = TestWorker::start();
+
+    // create the game as host
+    let (status, created) = worker.post(
+        "/game/create",
+        None,
+        &json!({ "hostName": "Host", "turnstileToken": TURNSTILE_TEST_TOKEN }),
+    );
+    assert_eq!(status, StatusCode::CREATED, "{created}");
+    let game_id = text(&created, "/game/id").to_string();
+    let host_id = text(&created, "/game/hostPlayerId").to_string();
+    let host_token = text(&created, "/session/token").to_string();
+    assert_eq!(text(&created, "/game/state"), "WaitingForPlayers");
+
+    // join as second player
+    let (status, session) = worker.post(
+        "/auth/session",
+        None,
+        &json!({ "name": "Guest", "gameId": game_id }),
+    );
+    assert_eq!(status, StatusCode::OK, "{session}");
+    let guest_id = text(&session, "/player/id").to_string();
+    let guest_token = text(&session, "/token").to_string();
+
+    // only the host can start the game
+    let (status, _) = worker.post(
+        &format!("/game/{game_id}/start"),
+        Some(&guest_token),
+        &json!({}),
+    );
+    assert!(status.is_client_error(), "the guest started the game");
+
+    let (status, started) = worker.post(
+        &format!("/game/{game_id}/start"),
+        Some(&host_token),
+        &json!({}),
+    );
+    assert_eq!(status, StatusCode::OK, "{started}");
+    assert_eq!(text(&started, "/state"), "InProgress");
+    assert_eq!(text(&started, "/whichPlayerTurn"), host_id);
+    let hand = started["ownCards"]
+        .as_array()
+        .expect("the host has no hand");
+    assert!(!hand.is_empty(), "the host was dealt no cards");
+
+    // the host places one card
+    let (status, claimed) = worker.post(
+        &format!("/game/{game_id}/claim"),
+        Some(&host_token),
+        &json!({ "createdBy": host_id, "gameId": game_id, "cards": [hand[0]] }),
+    );
+    assert_eq!(status, StatusCode::OK, "{claimed}");
+    assert_eq!(text(&claimed, "/whichPlayerTurn"), guest_id);
+    assert_eq!(claimed["claims"].as_array().map(Vec::len), Some(1));
+
+    // the guest doubts it
+    let (status, challenged) = worker.post(
+        &format!("/game/{game_id}/challenge"),
+        Some(&guest_token),
+        &json!({ "playerId": guest_id }),
+    );
+    assert_eq!(status, StatusCode::OK, "{challenged}");
+    let loser_id = text(&challenged, "/loserId");
+    let expected_loser = if challenged["truthful"].as_bool() == Some(true) {
+        &guest_id
+    } else {
+        &host_id
+    };
+    assert_eq!(loser_id, expected_loser);
+    assert_eq!(challenged["claim"]["revealed"].as_bool(), Some(true));
+
+    // the host ends the game
+    let (status, _) = worker.post(
+        &format!("/game/{game_id}/end"),
+        Some(&guest_token),
+        &json!({}),
+    );
+    assert!(status.is_client_error(), "the guest ended the game");
+
+    let (status, ended) = worker.post(
+        &format!("/game/{game_id}/end"),
+        Some(&host_token),
+        &json!({}),
+    );
+    assert_eq!(status, StatusCode::OK, "{ended}");
+    assert_eq!(text(&ended, "/state"), "Ended");
+    assert!(ended["finishedAt"].is_string(), "{ended}");
+}
+
+#[test]
+#[ignore = "needs `npx wrangler`; run with `cargo test -- --ignored`"]
+fn removed_players_lose_their_session_and_the_first_seat_hosts() {
+    let worker = TestWorker::start();
+
+    let (status, created) = worker.post(
+        "/game/create",
+        None,
+        &json!({ "hostName": "Host", "turnstileToken": TURNSTILE_TEST_TOKEN }),
+    );
+    assert_eq!(status, StatusCode::CREATED, "{created}");
+    let game_id = text(&created, "/game/id").to_string();
+    let host_id = text(&created, "/game/hostPlayerId").to_string();
+    let host_token = text(&created, "/session/token").to_string();
+
+    let mut guests = vec![];
+    for name in ["Kicked", "Second", "Third"] {
+        let (status, session) = worker.post(
+            "/auth/session",
+            None,
+            &json!({ "name": name, "gameId": game_id }),
+        );
+        assert_eq!(status, StatusCode::OK, "{session}");
+        guests.push((
+            text(&session, "/player/id").to_string(),
+            text(&session, "/token").to_string(),
+        ));
+    }
+    let (kicked_id, kicked_token) = &guests[0];
+    let (second_id, second_token) = &guests[1];
+    let (third_id, _) = &guests[2];
+
+    // a kicked player can't read the game with their old session
+    let (status, kicked) = worker.post(
+        &format!("/game/{game_id}/kick/{kicked_id}"),
+        Some(&host_token),
+        &json!({}),
+    );
+    assert_eq!(status, StatusCode::OK, "{kicked}");
+    let (status, _) = worker.get(&format!("/game/{game_id}"), Some(kicked_token));
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = worker.get(&format!("/game/{game_id}/events"), Some(kicked_token));
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // the player on the first seat takes over when the host leaves, not the one who joined first
+    let (status, seated) = worker.put(
+        &format!("/game/{game_id}/seats"),
+        Some(&host_token),
+        &json!({ "playerIds": [third_id, host_id, second_id] }),
+    );
+    assert_eq!(status, StatusCode::OK, "{seated}");
+    let (status, _) = worker.post(
+        &format!("/game/{game_id}/leave"),
+        Some(&host_token),
+        &json!({}),
+    );
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, game) = worker.get(&format!("/game/{game_id}"), Some(second_token));
+    assert_eq!(status, StatusCode::OK, "{game}");
+    assert_eq!(text(&game, "/hostPlayerId"), third_id);
+    let (status, _) = worker.get(&format!("/game/{game_id}"), Some(&host_token));
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[test]
+#[ignore = "needs `npx wrangler`; run with `cargo test -- --ignored`"]
+fn conflicting_claims_store_only_the_accepted_one() {
+    let worker = TestWorker::start();
+
+    let (status, created) = worker.post(
+        "/game/create",
+        None,
+        &json!({ "hostName": "Host", "turnstileToken": TURNSTILE_TEST_TOKEN }),
+    );
+    assert_eq!(status, StatusCode::CREATED, "{created}");
+    let game_id = text(&created, "/game/id").to_string();
+    let host_id = text(&created, "/game/hostPlayerId").to_string();
+    let host_token = text(&created, "/session/token").to_string();
+
+    let (status, session) = worker.post(
+        "/auth/session",
+        None,
+        &json!({ "name": "Guest", "gameId": game_id }),
+    );
+    assert_eq!(status, StatusCode::OK, "{session}");
+
+    let (status, started) = worker.post(
+        &format!("/game/{game_id}/start"),
+        Some(&host_token),
+        &json!({}),
+    );
+    assert_eq!(status, StatusCode::OK, "{started}");
+    let hand = started["ownCards"]
+        .as_array()
+        .expect("the host has no hand")
+        .clone();
+    assert!(hand.len() >= 2, "the host was dealt too few cards");
+
+    // both requests are based on the same revision, so only one of them may be applied
+    let statuses: Vec<StatusCode> = thread::scope(|scope| {
+        let requests: Vec<_> = hand[..2]
+            .iter()
+            .map(|card| {
+                let (worker, game_id, host_id, host_token) =
+                    (&worker, &game_id, &host_id, &host_token);
+                scope.spawn(move || {
+                    worker
+                        .post(
+                            &format!("/game/{game_id}/claim"),
+                            Some(host_token),
+                            &json!({ "createdBy": host_id, "gameId": game_id, "cards": [card] }),
+                        )
+                        .0
+                })
+            })
+            .collect();
+
+        requests
+            .into_iter()
+            .map(|request| request.join().expect("a claim request panicked"))
+            .collect()
+    });
+    assert_eq!(
+        statuses.iter().filter(|status| status.is_success()).count(),
+        1,
+        "{statuses:?}"
+    );
+    assert!(statuses.contains(&StatusCode::CONFLICT), "{statuses:?}");
+
+    // the rejected claim left neither a claim nor a missing card behind
+    let (status, game) = worker.get(&format!("/game/{game_id}"), Some(&host_token));
+    assert_eq!(status, StatusCode::OK, "{game}");
+    assert_eq!(game["claims"].as_array().map(Vec::len), Some(1), "{game}");
+    assert_eq!(
+        game["ownCards"].as_array().map(Vec::len),
+        Some(hand.len() - 1),
+        "{game}"
+    );
+}
+
+#[test]
+#[ignore = "needs `npx wrangler`; run with `cargo test -- --ignored`"]
+fn messages_are_written_in_the_accepted_language() {
+    let worker = TestWorker::start();
+
+    let (status, created) = worker.post(
+        "/game/create",
+        None,
+        &json!({ "hostName": "Host", "turnstileToken": TURNSTILE_TEST_TOKEN }),
+    );
+    assert_eq!(status, StatusCode::CREATED, "{created}");
+    let game_id = text(&created, "/game/id").to_string();
+    let host_token = text(&created, "/session/token").to_string();
+
+    // a text with a placeholder is written in German right away
+    let (status, refused) = send(
+        worker
+            .request(
+                Method::POST,
+                &format!("/game/{game_id}/start"),
+                Some(&host_token),
+            )
+            .header("Accept-Language", "de-DE,de;q=0.9,en;q=0.8")
+            .json(&json!({})),
+    );
+    assert_eq!(status, StatusCode::CONFLICT, "{refused}");
+    assert_eq!(
+        text(&refused, "/message"),
+        "Zum Starten des Spiels werden mindestens 2 Spieler gebraucht!"
+    );
+
+    let (status, invalid) = send(
+        worker
+            .request(Method::POST, "/auth/session", None)
+            .header("Accept-Language", "de")
+            .json(&json!({ "name": "x".repeat(40), "gameId": game_id })),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{invalid}");
+    assert_eq!(
+        text(&invalid, "/errors/0/message"),
+        "Der Name darf höchstens 32 Zeichen lang sein!"
+    );
+
+    // without the header the texts stay English
+    let (status, refused) = worker.post(
+        &format!("/game/{game_id}/start"),
+        Some(&host_token),
+        &json!({}),
+    );
+    assert_eq!(status, StatusCode::CONFLICT, "{refused}");
+    assert_eq!(
+        text(&refused, "/message"),
+        "At least 2 players are needed to start the game!"
+    );
+}
+
+#[test]
+#[ignore = "needs `npx wrangler`; run with `cargo test -- --ignored`"]
+fn only_the_waiting_client_can_follow_or_leave_its_entry() {
+    let worker = TestWorker::start();
+
+    let (status, created) = worker.post(
+        "/game/create",
+        None,
+        &json!({
+            "hostName": "Host",
+            "settings": { "maxPlayers": 2 },
+            "turnstileToken": TURNSTILE_TEST_TOKEN,
+        }),
+    );
+    assert_eq!(status, StatusCode::CREATED, "{created}");
+    let game_id = text(&created, "/game/id").to_string();
+
+    let (status, session) = worker.post(
+        "/auth/session",
+        None,
+        &json!({ "name": "Guest", "gameId": game_id }),
+    );
+    assert_eq!(status, StatusCode::OK, "{session}");
+
+    // the game is full, so the next client waits
+    let (status, waiting) = worker.post(
+        &format!("/game/{game_id}/waitlist"),
+        None,
+        &json!({ "name": "Waiting" }),
+    );
+    assert_eq!(status, StatusCode::OK, "{waiting}");
+    let entry_id = text(&waiting, "/entryId").to_string();
+    let token = text(&waiting, "/token").to_string();
+
+    let entry_path = format!("/game/{game_id}/waitlist/{entry_id}");
+    let (status, _) = worker.get(&format!("{entry_path}/events?token=guessed"), None);
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = worker.get(&format!("{entry_path}/events?token={token}"), None);
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(worker.request(Method::DELETE, &entry_path, None));
+    assert!(
+        status.is_client_error(),
+        "the entry was left without a token"
+    );
+    let (status, _) =
+        send(worker.request(Method::DELETE, &format!("{entry_path}?token=guessed"), None));
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) =
+        send(worker.request(Method::DELETE, &format!("{entry_path}?token={token}"), None));
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = worker.get(&format!("{entry_path}/events?token={token}"), None);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}